@@ -0,0 +1,97 @@
+//! Benchmarks of every pipeline stage on large synthetic molecules.
+//!
+//! Each family is generated at 1k, 10k, and 100k atoms. Stages are timed separately through
+//! the progress observer: a stage lasts from its own progress report until the next one, and
+//! the building stage lasts until the topology is returned. A full run is benchmarked as well.
+//!
+//! Run with `cargo bench`. Benchmark IDs have the form `family/Stage/atoms`, so a regex filter
+//! selects a subset, e.g. `cargo bench -- 'polyaromatic/Rings'` or `cargo bench -- '/1000$'`.
+//! Ring perception on the 100k-atom aromatic polymer takes on the order of a minute per run and
+//! dominates the full suite.
+
+#[path = "../tests/generators/carbon.rs"]
+mod carbon;
+mod synthetic;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use dreid_typer::{MolecularGraph, Progress, Stage, Typer};
+use std::hint::black_box;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Atom counts every family is generated at.
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// Builds a molecule of at least the given number of atoms.
+type Generator = fn(usize) -> MolecularGraph;
+
+/// Molecule families and their generators.
+const FAMILIES: [(&str, Generator); 5] = [
+    ("alkane", synthetic::alkane),
+    ("polyaromatic", synthetic::polyaromatic),
+    ("dendrimer", synthetic::dendrimer),
+    ("drug_like", synthetic::drug_like_set),
+    ("nanotube", carbon::nanotube),
+];
+
+/// Typer that records the instant at which every stage starts.
+struct StageTimer {
+    typer: Typer,
+    log: Arc<Mutex<Vec<(Stage, Instant)>>>,
+}
+
+impl StageTimer {
+    fn new() -> Self {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&log);
+        let typer = Typer::new().with_progress_observer(move |progress: &Progress| {
+            sink.lock().unwrap().push((progress.stage, Instant::now()));
+        });
+        Self { typer, log }
+    }
+
+    /// Types `graph` once and returns the total time spent in each stage.
+    fn run(&self, graph: &MolecularGraph) -> [Duration; Stage::ALL.len()] {
+        self.log.lock().unwrap().clear();
+        let topology = self
+            .typer
+            .assign_topology(graph)
+            .expect("benchmark molecules type");
+        let finished = Instant::now();
+        black_box(topology);
+
+        let log = self.log.lock().unwrap();
+        let mut durations = [Duration::ZERO; Stage::ALL.len()];
+        for (i, &(stage, started)) in log.iter().enumerate() {
+            let ended = log.get(i + 1).map_or(finished, |&(_, next)| next);
+            durations[stage.index()] += ended - started;
+        }
+        durations
+    }
+}
+
+fn stages(c: &mut Criterion) {
+    let timer = StageTimer::new();
+    for (family, generate) in FAMILIES {
+        let mut group = c.benchmark_group(family);
+        group.sample_size(10);
+        for size in SIZES {
+            let graph = generate(size);
+            group.throughput(Throughput::Elements(graph.atoms.len() as u64));
+
+            for stage in Stage::ALL {
+                group.bench_with_input(BenchmarkId::new(stage.name(), size), &graph, |b, graph| {
+                    b.iter_custom(|iters| (0..iters).map(|_| timer.run(graph)[stage.index()]).sum())
+                });
+            }
+
+            group.bench_with_input(BenchmarkId::new("Total", size), &graph, |b, graph| {
+                b.iter(|| timer.typer.assign_topology(black_box(graph)))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, stages);
+criterion_main!(benches);
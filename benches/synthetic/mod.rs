@@ -0,0 +1,160 @@
+//! Generators for large synthetic molecules used by the benchmarks.
+//!
+//! Every generator grows its structure until it holds at least `target` atoms, so the families
+//! can be compared at the same sizes. All output is deterministic.
+
+use dreid_typer::io::smiles::parse_smiles;
+use dreid_typer::{Element, GraphBondOrder, MolecularGraph};
+use std::collections::VecDeque;
+
+/// Drug-like molecules sampled by [`drug_like_set`].
+const DRUG_LIKE: &[&str] = &[
+    "CC(=O)Oc1ccccc1C(=O)O",
+    "Cn1cnc2c1c(=O)n(C)c(=O)n2C",
+    "CC(C)Cc1ccc(cc1)C(C)C(=O)O",
+    "CC(=O)Nc1ccc(O)cc1",
+    "CN1CCCC1c1cccnc1",
+    "CN1C(=O)CN=C(c2ccccc2)c2cc(Cl)ccc21",
+    "Cc1cc(NS(=O)(=O)c2ccc(N)cc2)no1",
+    "CN(C)C(=N)NC(=N)N",
+    "CCOC(=O)c1ccc(N)cc1",
+    "CCN(CC)CC(=O)Nc1c(C)cccc1C",
+];
+
+/// Adds `count` hydrogens bonded to `atom`.
+fn add_hydrogens(graph: &mut MolecularGraph, atom: usize, count: usize) {
+    for _ in 0..count {
+        let h = graph.add_atom(Element::H);
+        bond(graph, atom, h, GraphBondOrder::Single);
+    }
+}
+
+/// Adds a bond between two atoms known to exist.
+fn bond(graph: &mut MolecularGraph, a: usize, b: usize, order: GraphBondOrder) {
+    graph
+        .add_bond(a, b, order)
+        .expect("generated atoms exist and differ");
+}
+
+/// Linear alkane `CH3-(CH2)n-CH3`.
+pub fn alkane(target: usize) -> MolecularGraph {
+    let carbons = target.saturating_sub(2).div_ceil(3).max(2);
+    let mut graph = MolecularGraph::new();
+    let mut previous = None;
+    for i in 0..carbons {
+        let c = graph.add_atom(Element::C);
+        if let Some(p) = previous {
+            bond(&mut graph, p, c, GraphBondOrder::Single);
+        }
+        let terminal = i == 0 || i + 1 == carbons;
+        add_hydrogens(&mut graph, c, if terminal { 3 } else { 2 });
+        previous = Some(c);
+    }
+    graph
+}
+
+/// Poly(2,6-naphthylene): fused aromatic bicycles joined by single bonds.
+///
+/// Aromatic bonds are given as input, so every unit exercises ring perception, Kekulé
+/// expansion, and aromaticity.
+pub fn polyaromatic(target: usize) -> MolecularGraph {
+    // Naphthalene positions 1, 2, 3, 4, 4a, 5, 6, 7, 8, 8a.
+    const RING_BONDS: [(usize, usize); 11] = [
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 9),
+        (9, 0),
+        (4, 5),
+        (5, 6),
+        (6, 7),
+        (7, 8),
+        (8, 9),
+    ];
+    const HYDROGEN_SITES: [usize; 6] = [0, 2, 3, 5, 7, 8];
+    const HEAD: usize = 1;
+    const TAIL: usize = 6;
+
+    let units = target.div_ceil(16).max(1);
+    let mut graph = MolecularGraph::new();
+    let mut previous_tail = None;
+    for unit in 0..units {
+        let atoms: Vec<usize> = (0..10).map(|_| graph.add_atom(Element::C)).collect();
+        for (a, b) in RING_BONDS {
+            bond(&mut graph, atoms[a], atoms[b], GraphBondOrder::Aromatic);
+        }
+        for site in HYDROGEN_SITES {
+            add_hydrogens(&mut graph, atoms[site], 1);
+        }
+        match previous_tail {
+            Some(tail) => bond(&mut graph, tail, atoms[HEAD], GraphBondOrder::Single),
+            None => add_hydrogens(&mut graph, atoms[HEAD], 1),
+        }
+        if unit + 1 == units {
+            add_hydrogens(&mut graph, atoms[TAIL], 1);
+        }
+        previous_tail = Some(atoms[TAIL]);
+    }
+    graph
+}
+
+/// Polyether dendrimer grown breadth-first from a quaternary carbon core.
+///
+/// Each branch is `-CH2-O-CH2-CH<` with two further branch sites; the sites left open once the
+/// target size is reached are capped with hydrogens.
+pub fn dendrimer(target: usize) -> MolecularGraph {
+    let mut graph = MolecularGraph::new();
+    let core = graph.add_atom(Element::C);
+    let mut open_sites: VecDeque<usize> = std::iter::repeat_n(core, 4).collect();
+
+    while graph.atoms.len() + open_sites.len() < target {
+        let parent = open_sites
+            .pop_front()
+            .expect("a branch always leaves open sites");
+        let first = graph.add_atom(Element::C);
+        let oxygen = graph.add_atom(Element::O);
+        let second = graph.add_atom(Element::C);
+        let branch = graph.add_atom(Element::C);
+        bond(&mut graph, parent, first, GraphBondOrder::Single);
+        bond(&mut graph, first, oxygen, GraphBondOrder::Single);
+        bond(&mut graph, oxygen, second, GraphBondOrder::Single);
+        bond(&mut graph, second, branch, GraphBondOrder::Single);
+        add_hydrogens(&mut graph, first, 2);
+        add_hydrogens(&mut graph, second, 2);
+        add_hydrogens(&mut graph, branch, 1);
+        open_sites.extend([branch, branch]);
+    }
+    for site in open_sites {
+        add_hydrogens(&mut graph, site, 1);
+    }
+    graph
+}
+
+/// Disconnected set of drug-like molecules drawn pseudo-randomly from a fixed list.
+pub fn drug_like_set(target: usize) -> MolecularGraph {
+    let molecules: Vec<MolecularGraph> = DRUG_LIKE
+        .iter()
+        .map(|smiles| parse_smiles(smiles).expect("benchmark SMILES are valid"))
+        .collect();
+
+    let mut graph = MolecularGraph::new();
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    while graph.atoms.len() < target {
+        // xorshift64 keeps the selection reproducible without a random number crate.
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let molecule = &molecules[(state % molecules.len() as u64) as usize];
+
+        let offset = graph.atoms.len();
+        for atom in &molecule.atoms {
+            graph.add_atom(atom.element);
+        }
+        for edge in &molecule.bonds {
+            let (a, b) = edge.atom_ids;
+            bond(&mut graph, a + offset, b + offset, edge.order);
+        }
+    }
+    graph
+}
@@ -0,0 +1,18 @@
+//! Builds an arbitrary graph from the input and runs the full pipeline on it.
+//!
+//! Any input may be rejected with an error, but none may panic.
+
+#![no_main]
+
+#[path = "../../tests/generators/mod.rs"]
+mod generators;
+
+use dreid_typer::{Typer, assign_bond_orders};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut graph = generators::build_graph(data);
+    let _ = Typer::new().assign_topology(&graph);
+    assign_bond_orders(&mut graph);
+    let _ = Typer::new().assign_topology(&graph);
+});
@@ -0,0 +1,23 @@
+//! Builds a valence-respecting molecule from the input and checks that perception accepts it.
+
+#![no_main]
+
+#[path = "../../tests/generators/mod.rs"]
+mod generators;
+
+use dreid_typer::{AssignmentPolicy, Typer};
+use generators::{Op, build_molecule};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let ops: Vec<Op> = data
+        .chunks_exact(4)
+        .map(|chunk| Op::from_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect();
+    let molecule = build_molecule(&ops);
+    let topology = Typer::new()
+        .with_assignment_policy(AssignmentPolicy::Fallback)
+        .assign_topology(&molecule.graph)
+        .expect("valence-respecting molecules are always typed");
+    assert_eq!(topology.atoms.len(), molecule.valences.len());
+});
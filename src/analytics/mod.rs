@@ -0,0 +1,278 @@
+//! Dataset-level statistics over typed topologies.
+//!
+//! A [`CompositionReport`] aggregates atom-type frequencies, element composition, ring sizes,
+//! and resonance-system sizes over one or many [`MolecularTopology`] values. Reports merge
+//! cheaply, so per-thread or per-file reports can be combined into a single summary, and they
+//! implement [`serde::Serialize`] for dashboards and QC pipelines.
+
+use crate::compat::prelude::*;
+use crate::core::properties::TopologyBondOrder;
+use crate::core::topology::MolecularTopology;
+use alloc::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+/// Aggregated composition statistics for a set of topologies.
+///
+/// Maps are keyed by their natural label (type, element symbol, or size) and sorted, so
+/// serialized reports are stable across runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompositionReport {
+    /// Number of topologies aggregated into the report.
+    pub topologies: usize,
+    /// Total number of atoms across all topologies.
+    pub atoms: usize,
+    /// Total number of bonds across all topologies.
+    pub bonds: usize,
+    /// Number of atoms carrying each assigned atom type.
+    pub type_counts: BTreeMap<String, usize>,
+    /// Number of atoms of each element, keyed by element symbol.
+    pub element_counts: BTreeMap<String, usize>,
+    /// Number of smallest-set rings of each size.
+    pub ring_sizes: BTreeMap<usize, usize>,
+    /// Number of atoms belonging to at least one ring.
+    pub ring_atoms: usize,
+    /// Number of resonance systems of each size, measured in atoms.
+    pub resonance_system_sizes: BTreeMap<usize, usize>,
+}
+
+impl CompositionReport {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a report describing a single topology.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Typed topology to analyze.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::analytics::CompositionReport;
+    /// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+    ///
+    /// let mut graph = MolecularGraph::new();
+    /// let o = graph.add_atom(Element::O);
+    /// let h1 = graph.add_atom(Element::H);
+    /// let h2 = graph.add_atom(Element::H);
+    /// graph.add_bond(o, h1, GraphBondOrder::Single).unwrap();
+    /// graph.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+    ///
+    /// let report = CompositionReport::from_topology(&assign_topology(&graph).unwrap());
+    /// assert_eq!(report.type_counts["H_HB"], 2);
+    /// assert_eq!(report.element_counts["O"], 1);
+    /// assert!(report.ring_sizes.is_empty());
+    /// ```
+    pub fn from_topology(topology: &MolecularTopology) -> Self {
+        let mut report = Self::new();
+        report.add(topology);
+        report
+    }
+
+    /// Builds a report aggregating every topology in `topologies`.
+    ///
+    /// # Arguments
+    ///
+    /// * `topologies` - Typed topologies to analyze.
+    pub fn from_topologies<'a, I>(topologies: I) -> Self
+    where
+        I: IntoIterator<Item = &'a MolecularTopology>,
+    {
+        let mut report = Self::new();
+        for topology in topologies {
+            report.add(topology);
+        }
+        report
+    }
+
+    /// Adds the statistics of one topology to the report.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Typed topology to analyze.
+    pub fn add(&mut self, topology: &MolecularTopology) {
+        self.topologies += 1;
+        self.atoms += topology.atoms.len();
+        self.bonds += topology.bonds.len();
+
+        for atom in &topology.atoms {
+            *self
+                .type_counts
+                .entry(atom.atom_type.to_string())
+                .or_default() += 1;
+            *self
+                .element_counts
+                .entry(atom.element.to_string())
+                .or_default() += 1;
+        }
+
+        let mut in_ring = vec![false; topology.atoms.len()];
+        for ring in &topology.rings {
+            *self.ring_sizes.entry(ring.size()).or_default() += 1;
+            for &atom_id in &ring.atom_ids {
+                in_ring[atom_id] = true;
+            }
+        }
+        self.ring_atoms += in_ring.iter().filter(|&&flag| flag).count();
+
+        for size in resonance_system_sizes(topology) {
+            *self.resonance_system_sizes.entry(size).or_default() += 1;
+        }
+    }
+
+    /// Folds another report into this one.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Report whose counts are added to `self`.
+    pub fn merge(&mut self, other: &CompositionReport) {
+        self.topologies += other.topologies;
+        self.atoms += other.atoms;
+        self.bonds += other.bonds;
+        self.ring_atoms += other.ring_atoms;
+        merge_counts(&mut self.type_counts, &other.type_counts);
+        merge_counts(&mut self.element_counts, &other.element_counts);
+        merge_counts(&mut self.ring_sizes, &other.ring_sizes);
+        merge_counts(
+            &mut self.resonance_system_sizes,
+            &other.resonance_system_sizes,
+        );
+    }
+
+    /// Returns the fraction of atoms carrying `atom_type`, or `0.0` for an empty report.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_type` - Atom type label to look up.
+    pub fn type_fraction(&self, atom_type: &str) -> f64 {
+        if self.atoms == 0 {
+            return 0.0;
+        }
+        self.type_counts.get(atom_type).copied().unwrap_or(0) as f64 / self.atoms as f64
+    }
+}
+
+impl<'a> FromIterator<&'a MolecularTopology> for CompositionReport {
+    fn from_iter<I: IntoIterator<Item = &'a MolecularTopology>>(iter: I) -> Self {
+        Self::from_topologies(iter)
+    }
+}
+
+/// Adds every count in `source` to the matching entry in `target`.
+fn merge_counts<K: Ord + Clone>(target: &mut BTreeMap<K, usize>, source: &BTreeMap<K, usize>) {
+    for (key, count) in source {
+        *target.entry(key.clone()).or_default() += count;
+    }
+}
+
+/// Returns the atom counts of the connected components formed by resonant bonds.
+fn resonance_system_sizes(topology: &MolecularTopology) -> Vec<usize> {
+    let mut parent: Vec<usize> = (0..topology.atoms.len()).collect();
+
+    let mut resonant = vec![false; topology.atoms.len()];
+    for bond in &topology.bonds {
+        if bond.order != TopologyBondOrder::Resonant {
+            continue;
+        }
+        let (a, b) = bond.atom_ids;
+        resonant[a] = true;
+        resonant[b] = true;
+        let (ra, rb) = (find_root(&mut parent, a), find_root(&mut parent, b));
+        if ra != rb {
+            parent[ra] = rb;
+        }
+    }
+
+    let mut sizes: BTreeMap<usize, usize> = BTreeMap::new();
+    for atom_id in (0..topology.atoms.len()).filter(|&id| resonant[id]) {
+        *sizes.entry(find_root(&mut parent, atom_id)).or_default() += 1;
+    }
+    sizes.into_values().collect()
+}
+
+/// Union-find root lookup with path halving.
+fn find_root(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::properties::{Element, GraphBondOrder};
+    use crate::pipeline::Typer;
+
+    fn benzene() -> MolecularTopology {
+        let mut graph = MolecularGraph::new();
+        let carbons: Vec<usize> = (0..6).map(|_| graph.add_atom(Element::C)).collect();
+        for i in 0..6 {
+            let order = if i % 2 == 0 {
+                GraphBondOrder::Double
+            } else {
+                GraphBondOrder::Single
+            };
+            graph
+                .add_bond(carbons[i], carbons[(i + 1) % 6], order)
+                .expect("valid ring bond");
+            let h = graph.add_atom(Element::H);
+            graph
+                .add_bond(carbons[i], h, GraphBondOrder::Single)
+                .expect("valid C-H bond");
+        }
+        Typer::new()
+            .assign_topology(&graph)
+            .expect("benzene should type")
+    }
+
+    fn methane() -> MolecularTopology {
+        let mut graph = MolecularGraph::new();
+        let c = graph.add_atom(Element::C);
+        for _ in 0..4 {
+            let h = graph.add_atom(Element::H);
+            graph
+                .add_bond(c, h, GraphBondOrder::Single)
+                .expect("valid C-H bond");
+        }
+        Typer::new()
+            .assign_topology(&graph)
+            .expect("methane should type")
+    }
+
+    #[test]
+    fn benzene_reports_ring_and_resonance_system() {
+        let report = CompositionReport::from_topology(&benzene());
+        assert_eq!(report.atoms, 12);
+        assert_eq!(report.type_counts["C_R"], 6);
+        assert_eq!(report.element_counts["H"], 6);
+        assert_eq!(report.ring_sizes, BTreeMap::from([(6, 1)]));
+        assert_eq!(report.ring_atoms, 6);
+        assert_eq!(report.resonance_system_sizes, BTreeMap::from([(6, 1)]));
+    }
+
+    #[test]
+    fn reports_aggregate_and_merge_consistently() {
+        let (benzene, methane) = (benzene(), methane());
+        let combined = CompositionReport::from_topologies([&benzene, &methane, &methane]);
+        assert_eq!(combined.topologies, 3);
+        assert_eq!(combined.atoms, 22);
+        assert_eq!(combined.type_counts["C_3"], 2);
+        assert!((combined.type_fraction("H_") - 14.0 / 22.0).abs() < 1e-12);
+
+        let mut merged = CompositionReport::from_topology(&benzene);
+        merged.merge(&[&methane, &methane].into_iter().collect());
+        assert_eq!(merged, combined);
+    }
+
+    #[test]
+    fn empty_report_has_zero_fractions() {
+        let report = CompositionReport::new();
+        assert_eq!(report.type_fraction("C_3"), 0.0);
+        assert!(report.type_counts.is_empty());
+    }
+}
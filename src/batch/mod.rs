@@ -0,0 +1,490 @@
+//! Parallel batch processing of molecule files through a shared [`Typer`].
+//!
+//! The batch front-end pairs a user-supplied [`MoleculeReader`] (file → graph) with a
+//! [`TopologyWriter`] (topology → output), fans the inputs out over a pool of scoped worker
+//! threads, and isolates every failure to the file that caused it. The returned [`BatchReport`]
+//! lists one [`FileOutcome`] per input in the original order.
+
+use crate::core::error::{BatchFileError, BoxedError};
+use crate::core::graph::MolecularGraph;
+use crate::core::topology::MolecularTopology;
+use crate::pipeline::Typer;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Converts an input file into a [`MolecularGraph`].
+///
+/// Any `Fn(&Path) -> Result<MolecularGraph, E>` closure whose error converts into a boxed error
+/// implements this trait, so simple readers need no dedicated type.
+pub trait MoleculeReader: Sync {
+    /// Reads the molecule stored at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error that prevented a graph from being produced; the batch records it as
+    /// [`BatchFileError::Read`].
+    fn read(&self, path: &Path) -> Result<MolecularGraph, BoxedError>;
+}
+
+impl<F, E> MoleculeReader for F
+where
+    F: Fn(&Path) -> Result<MolecularGraph, E> + Sync,
+    E: Into<BoxedError>,
+{
+    fn read(&self, path: &Path) -> Result<MolecularGraph, BoxedError> {
+        self(path).map_err(Into::into)
+    }
+}
+
+/// Persists the typed topology produced for an input file.
+///
+/// Any `Fn(&Path, &MolecularTopology) -> Result<(), E>` closure whose error converts into a boxed
+/// error implements this trait.
+pub trait TopologyWriter: Sync {
+    /// Writes the topology derived from the input at `source`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error raised while writing; the batch records it as
+    /// [`BatchFileError::Write`].
+    fn write(&self, source: &Path, topology: &MolecularTopology) -> Result<(), BoxedError>;
+}
+
+impl<F, E> TopologyWriter for F
+where
+    F: Fn(&Path, &MolecularTopology) -> Result<(), E> + Sync,
+    E: Into<BoxedError>,
+{
+    fn write(&self, source: &Path, topology: &MolecularTopology) -> Result<(), BoxedError> {
+        self(source, topology).map_err(Into::into)
+    }
+}
+
+/// Tuning knobs for a batch run.
+#[derive(Debug, Clone, Default)]
+pub struct BatchOptions {
+    /// Number of worker threads; `None` uses the available parallelism of the host.
+    pub threads: Option<NonZeroUsize>,
+    /// File extensions (without the leading dot, compared case-insensitively) accepted by
+    /// [`process_directory`]. An empty list accepts every regular file.
+    pub extensions: Vec<String>,
+    /// Whether [`process_directory`] descends into subdirectories.
+    pub recursive: bool,
+}
+
+/// Size of the topology produced for one successfully processed file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileSummary {
+    /// Number of atoms in the topology.
+    pub atoms: usize,
+    /// Number of bonds in the topology.
+    pub bonds: usize,
+    /// Number of angles in the topology.
+    pub angles: usize,
+    /// Number of torsions in the topology.
+    pub torsions: usize,
+    /// Number of inversions in the topology.
+    pub inversions: usize,
+}
+
+impl FileSummary {
+    fn from_topology(topology: &MolecularTopology) -> Self {
+        Self {
+            atoms: topology.atoms.len(),
+            bonds: topology.bonds.len(),
+            angles: topology.angles.len(),
+            torsions: topology.torsions.len(),
+            inversions: topology.inversions.len(),
+        }
+    }
+}
+
+/// Result of processing a single input file.
+#[derive(Debug)]
+pub struct FileOutcome {
+    /// Path of the input file.
+    pub path: PathBuf,
+    /// Wall-clock time spent reading, typing, and writing this file.
+    pub elapsed: Duration,
+    /// Topology summary on success, or the isolated failure.
+    pub result: Result<FileSummary, BatchFileError>,
+}
+
+/// Aggregated outcome of a batch run.
+#[derive(Debug)]
+pub struct BatchReport {
+    /// One outcome per input file, in input order.
+    pub outcomes: Vec<FileOutcome>,
+    /// Wall-clock duration of the whole batch.
+    pub elapsed: Duration,
+}
+
+impl BatchReport {
+    /// Returns the outcomes that completed successfully.
+    pub fn succeeded(&self) -> impl Iterator<Item = &FileOutcome> {
+        self.outcomes.iter().filter(|o| o.result.is_ok())
+    }
+
+    /// Returns the outcomes that failed at any stage.
+    pub fn failed(&self) -> impl Iterator<Item = &FileOutcome> {
+        self.outcomes.iter().filter(|o| o.result.is_err())
+    }
+
+    /// Returns `true` when every file was processed successfully.
+    pub fn is_success(&self) -> bool {
+        self.outcomes.iter().all(|o| o.result.is_ok())
+    }
+
+    /// Total number of atoms typed across all successful files.
+    pub fn total_atoms(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter_map(|o| o.result.as_ref().ok())
+            .map(|s| s.atoms)
+            .sum()
+    }
+}
+
+impl fmt::Display for BatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let failed = self.failed().count();
+        writeln!(
+            f,
+            "processed {} file(s) in {:.3}s: {} succeeded, {} failed, {} atoms typed",
+            self.outcomes.len(),
+            self.elapsed.as_secs_f64(),
+            self.outcomes.len() - failed,
+            failed,
+            self.total_atoms()
+        )?;
+        for outcome in self.failed() {
+            if let Err(err) = &outcome.result {
+                write!(f, "  {}: {}", outcome.path.display(), err)?;
+                let mut source = std::error::Error::source(err);
+                while let Some(cause) = source {
+                    write!(f, ": {}", cause)?;
+                    source = cause.source();
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Types every file in `paths` in parallel and writes the results.
+///
+/// Each file is read, typed with `typer`, and written independently. Failures (including panics)
+/// are recorded in that file's [`FileOutcome`] and never abort the rest of the batch.
+///
+/// # Arguments
+///
+/// * `typer` - Shared typer used by every worker.
+/// * `paths` - Input files to process.
+/// * `reader` - Converts each input file into a molecular graph.
+/// * `writer` - Persists each resulting topology.
+/// * `options` - Worker count and other batch settings.
+///
+/// # Returns
+///
+/// A [`BatchReport`] with one outcome per path, in the order the paths were supplied.
+pub fn process_files<R, W>(
+    typer: &Typer,
+    paths: &[PathBuf],
+    reader: &R,
+    writer: &W,
+    options: &BatchOptions,
+) -> BatchReport
+where
+    R: MoleculeReader + ?Sized,
+    W: TopologyWriter + ?Sized,
+{
+    let started = Instant::now();
+    let workers = worker_count(options, paths.len());
+    let next = AtomicUsize::new(0);
+
+    let mut indexed: Vec<(usize, FileOutcome)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut local = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = paths.get(index) else {
+                            break;
+                        };
+                        local.push((index, process_one(typer, path, reader, writer)));
+                    }
+                    local
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("batch worker panicked outside a file"))
+            .collect()
+    });
+
+    indexed.sort_unstable_by_key(|(index, _)| *index);
+
+    BatchReport {
+        outcomes: indexed.into_iter().map(|(_, outcome)| outcome).collect(),
+        elapsed: started.elapsed(),
+    }
+}
+
+/// Types every matching file inside `dir` in parallel.
+///
+/// Files are selected with [`BatchOptions::extensions`] and [`BatchOptions::recursive`], sorted
+/// by path for reproducible ordering, and then handed to [`process_files`].
+///
+/// # Errors
+///
+/// Returns an [`io::Error`] if the directory (or a subdirectory, when recursing) cannot be
+/// listed. Per-file failures are reported in the [`BatchReport`] instead.
+pub fn process_directory<R, W>(
+    typer: &Typer,
+    dir: &Path,
+    reader: &R,
+    writer: &W,
+    options: &BatchOptions,
+) -> io::Result<BatchReport>
+where
+    R: MoleculeReader + ?Sized,
+    W: TopologyWriter + ?Sized,
+{
+    let mut paths = Vec::new();
+    collect_files(dir, options, &mut paths)?;
+    paths.sort();
+    Ok(process_files(typer, &paths, reader, writer, options))
+}
+
+/// Reads, types, and writes one file, containing any panic to this file.
+fn process_one<R, W>(typer: &Typer, path: &Path, reader: &R, writer: &W) -> FileOutcome
+where
+    R: MoleculeReader + ?Sized,
+    W: TopologyWriter + ?Sized,
+{
+    let started = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let graph = reader.read(path).map_err(BatchFileError::Read)?;
+        let topology = typer
+            .assign_topology(&graph)
+            .map_err(BatchFileError::Typing)?;
+        writer
+            .write(path, &topology)
+            .map_err(BatchFileError::Write)?;
+        Ok(FileSummary::from_topology(&topology))
+    }))
+    .unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+        Err(BatchFileError::Panicked { message })
+    });
+
+    FileOutcome {
+        path: path.to_path_buf(),
+        elapsed: started.elapsed(),
+        result,
+    }
+}
+
+/// Resolves the number of worker threads, never exceeding the number of jobs.
+fn worker_count(options: &BatchOptions, jobs: usize) -> usize {
+    let requested = options
+        .threads
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get);
+    requested.min(jobs).max(1)
+}
+
+/// Collects regular files below `dir` that pass the extension filter.
+fn collect_files(dir: &Path, options: &BatchOptions, out: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if options.recursive {
+                collect_files(&path, options, out)?;
+            }
+        } else if path.is_file() && matches_extension(&path, &options.extensions) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Checks a path's extension against the (case-insensitive) allowlist.
+fn matches_extension(path: &Path, extensions: &[String]) -> bool {
+    if extensions.is_empty() {
+        return true;
+    }
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::properties::{Element, GraphBondOrder};
+    use std::sync::Mutex;
+
+    fn water() -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        let o = graph.add_atom(Element::O);
+        let h1 = graph.add_atom(Element::H);
+        let h2 = graph.add_atom(Element::H);
+        graph.add_bond(o, h1, GraphBondOrder::Single).unwrap();
+        graph.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+        graph
+    }
+
+    fn untypable() -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        graph.add_atom(Element::Og);
+        graph
+    }
+
+    fn reader(path: &Path) -> Result<MolecularGraph, String> {
+        match path.file_stem().and_then(|s| s.to_str()) {
+            Some(stem) if stem.starts_with("water") => Ok(water()),
+            Some(stem) if stem.starts_with("exotic") => Ok(untypable()),
+            Some(stem) if stem.starts_with("panic") => panic!("reader exploded"),
+            _ => Err(format!("unreadable: {}", path.display())),
+        }
+    }
+
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn process_files_preserves_order_and_isolates_failures() {
+        let typer = Typer::new();
+        let written = Mutex::new(Vec::new());
+        let writer = |source: &Path, topology: &MolecularTopology| -> Result<(), io::Error> {
+            written
+                .lock()
+                .unwrap()
+                .push((source.to_path_buf(), topology.atoms.len()));
+            Ok(())
+        };
+
+        let inputs = paths(&[
+            "water1.mol",
+            "missing.mol",
+            "exotic.mol",
+            "panic.mol",
+            "water2.mol",
+        ]);
+        let options = BatchOptions {
+            threads: NonZeroUsize::new(3),
+            ..BatchOptions::default()
+        };
+        let report = process_files(&typer, &inputs, &reader, &writer, &options);
+
+        let order: Vec<_> = report.outcomes.iter().map(|o| o.path.clone()).collect();
+        assert_eq!(order, inputs);
+
+        assert!(matches!(
+            report.outcomes[0].result,
+            Ok(FileSummary {
+                atoms: 3,
+                bonds: 2,
+                angles: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            report.outcomes[1].result,
+            Err(BatchFileError::Read(_))
+        ));
+        assert!(matches!(
+            report.outcomes[2].result,
+            Err(BatchFileError::Typing(_))
+        ));
+        assert!(matches!(
+            &report.outcomes[3].result,
+            Err(BatchFileError::Panicked { message }) if message == "reader exploded"
+        ));
+        assert!(report.outcomes[4].result.is_ok());
+
+        assert_eq!(report.succeeded().count(), 2);
+        assert_eq!(report.failed().count(), 3);
+        assert!(!report.is_success());
+        assert_eq!(report.total_atoms(), 6);
+        assert_eq!(written.lock().unwrap().len(), 2);
+
+        let summary = report.to_string();
+        assert!(summary.contains("2 succeeded, 3 failed"));
+        assert!(summary.contains("unreadable: missing.mol"));
+    }
+
+    #[test]
+    fn writer_failures_are_reported_per_file() {
+        let typer = Typer::new();
+        let writer = |_: &Path, _: &MolecularTopology| -> Result<(), io::Error> {
+            Err(io::Error::other("disk full"))
+        };
+
+        let report = process_files(
+            &typer,
+            &paths(&["water.mol"]),
+            &reader,
+            &writer,
+            &BatchOptions::default(),
+        );
+        assert!(matches!(
+            report.outcomes[0].result,
+            Err(BatchFileError::Write(_))
+        ));
+    }
+
+    #[test]
+    fn empty_batch_produces_empty_report() {
+        let typer = Typer::new();
+        let writer = |_: &Path, _: &MolecularTopology| -> Result<(), io::Error> { Ok(()) };
+        let report = process_files(&typer, &[], &reader, &writer, &BatchOptions::default());
+        assert!(report.outcomes.is_empty());
+        assert!(report.is_success());
+    }
+
+    #[test]
+    fn process_directory_filters_by_extension() {
+        let dir = std::env::temp_dir().join(format!("dreid-batch-{}", std::process::id()));
+        let nested = dir.join("nested");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.join("water_a.mol"), "").unwrap();
+        fs::write(dir.join("water_b.MOL"), "").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+        fs::write(nested.join("water_c.mol"), "").unwrap();
+
+        let typer = Typer::new();
+        let writer = |_: &Path, _: &MolecularTopology| -> Result<(), io::Error> { Ok(()) };
+        let mut options = BatchOptions {
+            extensions: vec!["mol".to_string()],
+            ..BatchOptions::default()
+        };
+
+        let flat = process_directory(&typer, &dir, &reader, &writer, &options).unwrap();
+        assert_eq!(flat.outcomes.len(), 2);
+        assert!(flat.is_success());
+
+        options.recursive = true;
+        let deep = process_directory(&typer, &dir, &reader, &writer, &options).unwrap();
+        assert_eq!(deep.outcomes.len(), 3);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,350 @@
+//! Command-line front-end: reads a SMILES string, MDL molfile, PDB file, or XYZ file, assigns
+//! DREIDING types, and writes the typed topology as JSON, a LAMMPS data file, or a plain-text
+//! table.
+//!
+//! Built only with the `cli` feature: `cargo install dreid-typer --features cli`.
+
+use dreid_typer::export::json;
+use dreid_typer::export::lammps::LammpsData;
+use dreid_typer::io::{pdb, sdf, smiles, xyz};
+use dreid_typer::rules::{RuleFormat, RulesetBuilder};
+use dreid_typer::{AssignmentPolicy, MolecularGraph, MolecularTopology, Typer, assign_bond_orders};
+use std::error::Error;
+use std::fmt::Write as _;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: dreid-typer [OPTIONS] <INPUT>
+
+Assigns DREIDING atom types to a molecule and prints its typed topology.
+
+Arguments:
+  <INPUT>  Input file, or `-` to read standard input
+
+Options:
+  -f, --from <FORMAT>  Input format: smiles, mol, pdb, or xyz [default: from the file extension]
+  -t, --to <FORMAT>    Output format: table, json, or lammps [default: table]
+  -o, --output <FILE>  Write to FILE instead of standard output
+  -r, --rules <FILE>   Merge the rules in a TOML, JSON, or YAML file over the default rules
+      --uff-fallback   Assign generic UFF types to atoms no rule matches
+      --fallback       Type atoms no rule matches by element and warn instead of failing
+      --bond-orders    Infer double and triple bonds from connectivity and hydrogens
+  -h, --help           Print this help
+  -V, --version        Print the version";
+
+/// Molecule formats the CLI can read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputFormat {
+    Smiles,
+    Mol,
+    Pdb,
+    Xyz,
+}
+
+impl InputFormat {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "smi" | "smiles" => Some(Self::Smiles),
+            "mol" | "sdf" | "sd" | "mdl" => Some(Self::Mol),
+            "pdb" | "ent" => Some(Self::Pdb),
+            "xyz" => Some(Self::Xyz),
+            _ => None,
+        }
+    }
+}
+
+/// Formats the CLI can write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Table,
+    Json,
+    Lammps,
+}
+
+impl OutputFormat {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "table" | "text" => Some(Self::Table),
+            "json" => Some(Self::Json),
+            "lammps" | "data" => Some(Self::Lammps),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed command-line arguments.
+#[derive(Debug)]
+struct Args {
+    input: String,
+    from: Option<InputFormat>,
+    to: OutputFormat,
+    output: Option<String>,
+    rules: Option<String>,
+    uff_fallback: bool,
+    fallback_types: bool,
+    bond_orders: bool,
+}
+
+/// What the user asked for.
+enum Command {
+    Run(Args),
+    Help,
+    Version,
+}
+
+/// A molecule read from the input; its graph carries coordinates when the format has them.
+struct Molecule {
+    title: String,
+    graph: MolecularGraph,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match parse_args(&args) {
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Version) => {
+            println!("dreid-typer {}", env!("CARGO_PKG_VERSION"));
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Run(args)) => match run(&args) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("error: {error}");
+                let mut source = error.source();
+                while let Some(cause) = source {
+                    eprintln!("  caused by: {cause}");
+                    source = cause.source();
+                }
+                ExitCode::FAILURE
+            }
+        },
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut input = None;
+    let mut from = None;
+    let mut to = OutputFormat::Table;
+    let mut output = None;
+    let mut rules = None;
+    let mut uff_fallback = false;
+    let mut fallback_types = false;
+    let mut bond_orders = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| format!("`{flag}` expects a value"))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "-f" | "--from" => {
+                let name = value(arg)?;
+                from = Some(
+                    InputFormat::parse(&name)
+                        .ok_or_else(|| format!("unknown input format `{name}`"))?,
+                );
+            }
+            "-t" | "--to" => {
+                let name = value(arg)?;
+                to = OutputFormat::parse(&name)
+                    .ok_or_else(|| format!("unknown output format `{name}`"))?;
+            }
+            "-o" | "--output" => output = Some(value(arg)?),
+            "-r" | "--rules" => rules = Some(value(arg)?),
+            "--uff-fallback" => uff_fallback = true,
+            "--fallback" => fallback_types = true,
+            "--bond-orders" => bond_orders = true,
+            flag if flag.starts_with('-') && flag != "-" => {
+                return Err(format!("unknown option `{flag}`"));
+            }
+            path => {
+                if input.replace(path.to_string()).is_some() {
+                    return Err("expected exactly one input".to_string());
+                }
+            }
+        }
+    }
+
+    let input = input.ok_or("missing input")?;
+    Ok(Command::Run(Args {
+        input,
+        from,
+        to,
+        output,
+        rules,
+        uff_fallback,
+        fallback_types,
+        bond_orders,
+    }))
+}
+
+fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let from = match args.from {
+        Some(format) => format,
+        None => Path::new(&args.input)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(InputFormat::parse)
+            .ok_or("cannot infer the input format; pass `--from`")?,
+    };
+    let text = if args.input == "-" {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        std::fs::read_to_string(&args.input)
+            .map_err(|error| format!("cannot read `{}`: {error}", args.input))?
+    };
+    let mut molecule = read_molecule(&text, from)?;
+    if args.bond_orders {
+        assign_bond_orders(&mut molecule.graph);
+    }
+
+    let mut typer = match &args.rules {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)
+                .map_err(|error| format!("cannot read `{path}`: {error}"))?;
+            let format = Path::new(path)
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .and_then(RuleFormat::from_extension);
+            let builder = RulesetBuilder::with_defaults().with_document(&text, format)?;
+            Typer::with_rules(builder.into_rules())
+        }
+        None => Typer::new(),
+    };
+    typer = typer.with_uff_fallback(args.uff_fallback);
+    if args.fallback_types {
+        typer = typer.with_assignment_policy(AssignmentPolicy::Fallback);
+    }
+    let topology = typer.assign_topology(&molecule.graph)?;
+    for atom in topology.fallback_atoms() {
+        eprintln!(
+            "warning: atom {} ({}) has no matching rule; using fallback type `{}`",
+            atom.id, atom.element, atom.atom_type
+        );
+    }
+
+    let rendered = match args.to {
+        OutputFormat::Table => render_table(&molecule, &topology),
+        OutputFormat::Json => render_json(&molecule, &topology)?,
+        OutputFormat::Lammps => {
+            let mut data = LammpsData::new(&topology)
+                .with_title(&molecule.title)
+                .with_type_labels(true);
+            if let Some(positions) = &molecule.graph.positions {
+                data = data.with_positions(positions)?;
+            }
+            data.to_string()
+        }
+    };
+
+    match &args.output {
+        Some(path) => std::fs::write(path, rendered)
+            .map_err(|error| format!("cannot write `{path}`: {error}"))?,
+        None => io::stdout().write_all(rendered.as_bytes())?,
+    }
+    Ok(())
+}
+
+fn read_molecule(text: &str, format: InputFormat) -> Result<Molecule, Box<dyn Error>> {
+    Ok(match format {
+        InputFormat::Smiles => {
+            let line = text
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty() && !line.starts_with('#'))
+                .ok_or("input contains no SMILES string")?;
+            Molecule {
+                title: line.to_string(),
+                graph: smiles::parse_smiles(line)?,
+            }
+        }
+        InputFormat::Mol => {
+            let record = sdf::parse_molfile(text.split("$$$$").next().unwrap_or_default())?;
+            Molecule {
+                title: record.title,
+                graph: record.graph,
+            }
+        }
+        InputFormat::Pdb => {
+            let structure = pdb::parse_pdb(text)?;
+            Molecule {
+                title: String::new(),
+                graph: structure.graph,
+            }
+        }
+        InputFormat::Xyz => {
+            let structure = xyz::parse_xyz(text, &xyz::XyzOptions::DEFAULT)?;
+            Molecule {
+                title: structure.title,
+                graph: structure.graph,
+            }
+        }
+    })
+}
+
+fn render_table(molecule: &Molecule, topology: &MolecularTopology) -> String {
+    let mut out = String::new();
+    if !molecule.title.is_empty() {
+        let _ = writeln!(out, "# {}", molecule.title);
+    }
+    let _ = writeln!(
+        out,
+        "{:>5}  {:<7}  {:<8}  {:<8}  bonded to",
+        "atom", "element", "type", "hybrid."
+    );
+    let mut neighbors = vec![Vec::new(); topology.atoms.len()];
+    for bond in &topology.bonds {
+        let (a, b) = bond.atom_ids;
+        neighbors[a].push(b);
+        neighbors[b].push(a);
+    }
+    for (atom, bonded) in topology.atoms.iter().zip(&mut neighbors) {
+        bonded.sort_unstable();
+        let bonded: Vec<String> = bonded.iter().map(usize::to_string).collect();
+        let _ = writeln!(
+            out,
+            "{:>5}  {:<7}  {:<8}  {:<8}  {}",
+            atom.id,
+            atom.element.to_string(),
+            atom.atom_type,
+            atom.hybridization.to_string(),
+            bonded.join(" ")
+        );
+    }
+    let _ = writeln!(
+        out,
+        "\n{} atoms, {} bonds, {} angles, {} torsions, {} inversions",
+        topology.atoms.len(),
+        topology.bonds.len(),
+        topology.angles.len(),
+        topology.torsions.len(),
+        topology.inversions.len()
+    );
+    out
+}
+
+fn render_json(
+    molecule: &Molecule,
+    topology: &MolecularTopology,
+) -> Result<String, Box<dyn Error>> {
+    let mut document = json::to_json(topology, molecule.graph.positions.as_deref())?;
+    document["title"] = molecule.title.clone().into();
+    let mut rendered = serde_json::to_string_pretty(&document)?;
+    rendered.push('\n');
+    Ok(rendered)
+}
@@ -3,10 +3,26 @@
 //! The builder stage takes the perception output and typing assignments, emitting atoms, bonds,
 //! angles, torsions, and inversions expected by downstream force-field tooling.
 
-use crate::core::properties::{GraphBondOrder, Hybridization, TopologyBondOrder};
-use crate::core::topology::{Angle, Atom, Bond, Inversion, MolecularTopology, Torsion};
+mod sites;
+mod stream;
+mod united;
+
+use crate::compat::prelude::*;
+pub use sites::add_lone_pair_sites;
+pub use stream::{TopologySink, build_topology_streaming};
+pub use united::collapse_nonpolar_hydrogens;
+
+use crate::compat::collections::HashSet;
+use crate::core::metadata::AtomMetadata;
+use crate::core::properties::{
+    DreidingType, Element, GraphBondOrder, Hybridization, TopologyBondOrder,
+};
+use crate::core::topology::{
+    Angle, AngleClass, Atom, Bond, BondClass, HydrogenBondDonor, Inversion, InversionClass,
+    InversionPolicy, MolecularTopology, Ring, Torsion, TorsionClass, TypeProvenance,
+};
 use crate::perception::{AnnotatedMolecule, ResonanceSystem};
-use std::collections::HashSet;
+use core::cmp::Reverse;
 
 /// Builds the `MolecularTopology` aggregate from perception results and atom-type labels.
 ///
@@ -17,155 +33,486 @@ use std::collections::HashSet;
 ///
 /// * `annotated_molecule` - Molecule carrying ring, hybridization, and bonding metadata.
 /// * `atom_types` - Slice of final atom-type names aligned with the molecule's atom ordering.
+/// * `provenance` - Origin of each atom type, aligned with `atom_types`.
+/// * `suppress_barrierless_torsions` - Whether torsions about a central bond without a DREIDING
+///   barrier (an sp center or an unhybridized terminal heavy atom) are moved to
+///   `suppressed_torsions` instead of `torsions`.
+/// * `inversion_policy` - Which inversion terms are emitted for each planar center.
+/// * `exclude_coordination_torsions` - Whether torsions passing through a coordination bond to
+///   a metal center are left out.
 ///
 /// # Returns
 ///
-/// A populated [`MolecularTopology`] containing atoms, bonds, angles, torsions, and inversions.
+/// A populated [`MolecularTopology`] containing atoms, bonds, angles, torsions, inversions, and
+/// rings.
 pub fn build_topology(
     annotated_molecule: &AnnotatedMolecule,
     atom_types: &[String],
+    provenance: &[TypeProvenance],
+    suppress_barrierless_torsions: bool,
+    inversion_policy: InversionPolicy,
+    exclude_coordination_torsions: bool,
 ) -> MolecularTopology {
-    let atoms = build_atoms(annotated_molecule, atom_types);
-    let bonds = build_bonds(annotated_molecule);
-    let angles = build_angles(annotated_molecule);
-    let torsions = build_torsions(annotated_molecule);
-    let inversions = build_inversions(annotated_molecule);
-
-    MolecularTopology {
+    let atoms = build_atoms(annotated_molecule, atom_types, provenance);
+    let mut terms = TermLists {
+        inversion_policy,
+        exclude_coordination_torsions,
+        ..TermLists::default()
+    };
+    build_topology_streaming(annotated_molecule, &mut terms);
+    let hb_donors = build_hb_donors(annotated_molecule, atom_types);
+    let hb_acceptors = build_hb_acceptors(annotated_molecule);
+
+    // Every torsion about a bond shares its class, so the shared counts set while streaming
+    // hold within either list.
+    let (suppressed_torsions, torsions) = if suppress_barrierless_torsions {
+        terms
+            .torsions
+            .into_iter()
+            .partition(|torsion| torsion.class.is_barrierless())
+    } else {
+        (Vec::new(), terms.torsions)
+    };
+
+    let mut topology = MolecularTopology {
         atoms,
-        bonds: bonds.into_iter().collect(),
-        angles: angles.into_iter().collect(),
-        torsions: torsions.into_iter().collect(),
-        inversions: inversions.into_iter().collect(),
+        bonds: terms.bonds,
+        angles: terms.angles,
+        torsions,
+        suppressed_torsions,
+        inversions: terms.inversions,
+        hb_donors,
+        hb_acceptors,
+        rings: build_rings(annotated_molecule),
+        virtual_sites: Vec::new(),
+    };
+    topology.normalize_rings();
+    topology
+}
+
+/// Sink collecting every streamed term into lists, with the settings of the typer.
+#[derive(Default)]
+struct TermLists {
+    bonds: Vec<Bond>,
+    angles: Vec<Angle>,
+    torsions: Vec<Torsion>,
+    inversions: Vec<Inversion>,
+    inversion_policy: InversionPolicy,
+    exclude_coordination_torsions: bool,
+}
+
+impl TopologySink for TermLists {
+    fn bond(&mut self, bond: Bond) {
+        self.bonds.push(bond);
+    }
+
+    fn angle(&mut self, angle: Angle) {
+        self.angles.push(angle);
+    }
+
+    fn torsion(&mut self, torsion: Torsion) {
+        self.torsions.push(torsion);
+    }
+
+    fn inversion(&mut self, inversion: Inversion) {
+        self.inversions.push(inversion);
+    }
+
+    fn inversion_policy(&self) -> InversionPolicy {
+        self.inversion_policy
+    }
+
+    fn exclude_coordination_torsions(&self) -> bool {
+        self.exclude_coordination_torsions
     }
 }
 
-/// Creates the atom list with element, type, and hybridization copies.
+/// Copies the perceived rings, flagging those whose atoms are all aromatic.
+///
+/// Ring systems are assigned afterwards by [`MolecularTopology::normalize_rings`].
+fn build_rings(annotated_molecule: &AnnotatedMolecule) -> Vec<Ring> {
+    annotated_molecule
+        .rings
+        .iter()
+        .map(|ring| Ring {
+            atom_ids: ring.clone(),
+            is_aromatic: ring
+                .iter()
+                .all(|&id| annotated_molecule.atoms[id].is_aromatic),
+            system_id: 0,
+        })
+        .collect()
+}
+
+/// DREIDING type assigned to hydrogens that participate in hydrogen bonds.
+const HYDROGEN_BOND_TYPE: &str = "H_HB";
+
+/// Pairs every `H_HB` hydrogen with the heavy atom it is bonded to.
+///
+/// # Arguments
+///
+/// * `annotated_molecule` - Molecule providing connectivity.
+/// * `atom_types` - Canonical atom types aligned with the molecule's atoms.
+fn build_hb_donors(
+    annotated_molecule: &AnnotatedMolecule,
+    atom_types: &[String],
+) -> Vec<HydrogenBondDonor> {
+    annotated_molecule
+        .atoms
+        .iter()
+        .filter(|atom| atom_types[atom.id] == HYDROGEN_BOND_TYPE)
+        .filter_map(|atom| {
+            annotated_molecule.adjacency[atom.id]
+                .first()
+                .map(|&(donor_id, _)| HydrogenBondDonor {
+                    donor_id,
+                    hydrogen_id: atom.id,
+                })
+        })
+        .collect()
+}
+
+/// Lists N, O, and F atoms that keep at least one lone pair after perception.
+fn build_hb_acceptors(annotated_molecule: &AnnotatedMolecule) -> Vec<usize> {
+    annotated_molecule
+        .atoms
+        .iter()
+        .filter(|atom| {
+            matches!(atom.element, Element::N | Element::O | Element::F) && atom.lone_pairs > 0
+        })
+        .map(|atom| atom.id)
+        .collect()
+}
+
+/// Creates the atom list with element, type, hybridization, and chirality copies.
+///
+/// Metadata is left empty; the pipeline copies it from the input graph once fragments are
+/// merged.
 ///
 /// # Arguments
 ///
 /// * `annotated_molecule` - Source molecule whose atoms provide structural metadata.
 /// * `atom_types` - Slice of assigned atom-type labels.
-fn build_atoms(annotated_molecule: &AnnotatedMolecule, atom_types: &[String]) -> Vec<Atom> {
+/// * `provenance` - Origin of each atom type.
+fn build_atoms(
+    annotated_molecule: &AnnotatedMolecule,
+    atom_types: &[String],
+    provenance: &[TypeProvenance],
+) -> Vec<Atom> {
     annotated_molecule
         .atoms
         .iter()
         .map(|ann_atom| Atom {
             id: ann_atom.id,
             element: ann_atom.element,
-            atom_type: atom_types[ann_atom.id].clone(),
+            atom_type: DreidingType::from(atom_types[ann_atom.id].as_str()),
             hybridization: ann_atom.hybridization,
+            provenance: provenance[ann_atom.id],
+            fragment_id: 0,
+            chirality: ann_atom.chirality,
+            metadata: AtomMetadata::default(),
         })
         .collect()
 }
 
-/// Extracts unique bonds from the annotated molecule.
-///
-/// This function determines the final `TopologyBondOrder` by checking if a bond belongs to
-/// any detected `ResonanceSystem`. If so, it is promoted to `Resonant`. Otherwise, the
-/// Kekulized order (`Single`, `Double`, `Triple`) is used.
-fn build_bonds(annotated_molecule: &AnnotatedMolecule) -> HashSet<Bond> {
-    let resonant_bond_ids: HashSet<usize> = annotated_molecule
+/// Collects the IDs of bonds that belong to any detected `ResonanceSystem`.
+fn resonant_bond_ids(annotated_molecule: &AnnotatedMolecule) -> HashSet<usize> {
+    annotated_molecule
         .resonance_systems
         .iter()
         .flat_map(|sys: &ResonanceSystem| sys.bond_ids.iter())
         .copied()
-        .collect();
+        .collect()
+}
 
-    annotated_molecule
-        .bonds
-        .iter()
-        .map(|edge| {
-            let topology_order = if resonant_bond_ids.contains(&edge.id) {
-                TopologyBondOrder::Resonant
-            } else {
-                match edge.order {
-                    GraphBondOrder::Single => TopologyBondOrder::Single,
-                    GraphBondOrder::Double => TopologyBondOrder::Double,
-                    GraphBondOrder::Triple => TopologyBondOrder::Triple,
-                    GraphBondOrder::Aromatic => TopologyBondOrder::Single, // Fallback; should not occur here
-                }
-            };
+/// Returns the topology order of a bond.
+///
+/// Bonds in a resonance system are promoted to `Resonant`; otherwise the Kekulized order
+/// (`Single`, `Double`, `Triple`) is used.
+fn topology_order(
+    bond_id: usize,
+    order: GraphBondOrder,
+    resonant_bond_ids: &HashSet<usize>,
+) -> TopologyBondOrder {
+    if resonant_bond_ids.contains(&bond_id) {
+        TopologyBondOrder::Resonant
+    } else {
+        match order {
+            GraphBondOrder::Single => TopologyBondOrder::Single,
+            GraphBondOrder::Double => TopologyBondOrder::Double,
+            GraphBondOrder::Triple => TopologyBondOrder::Triple,
+            GraphBondOrder::Aromatic => TopologyBondOrder::Single, // Fallback; should not occur here
+        }
+    }
+}
 
-            Bond::new(edge.atom_ids.0, edge.atom_ids.1, topology_order)
-        })
-        .collect()
+/// Emits every bond of the annotated molecule with its topology order and class.
+fn emit_bonds(
+    annotated_molecule: &AnnotatedMolecule,
+    resonant_bond_ids: &HashSet<usize>,
+    emit: &mut impl FnMut(Bond),
+) {
+    for edge in &annotated_molecule.bonds {
+        let (a, b) = edge.atom_ids;
+        let order = topology_order(edge.id, edge.order, resonant_bond_ids);
+        let atoms = &annotated_molecule.atoms;
+        emit(Bond {
+            stereo: edge.stereo,
+            class: BondClass::of(atoms[a].element, atoms[b].element),
+            ..Bond::new(a, b, order)
+        });
+    }
 }
 
-/// Generates all angle triplets by enumerating neighbor pairs around each atom.
-fn build_angles(annotated_molecule: &AnnotatedMolecule) -> HashSet<Angle> {
-    let mut angles = HashSet::new();
+/// Emits all angle triplets by enumerating neighbor pairs around each atom.
+fn emit_angles(annotated_molecule: &AnnotatedMolecule, emit: &mut impl FnMut(Angle)) {
     for j in 0..annotated_molecule.atoms.len() {
         let neighbors = &annotated_molecule.adjacency[j];
         if neighbors.len() < 2 {
             continue;
         }
+        let class = AngleClass::of(annotated_molecule.atoms[j].hybridization);
         for i in 0..neighbors.len() {
             for k in (i + 1)..neighbors.len() {
                 let atom_i_id = neighbors[i].0;
                 let atom_k_id = neighbors[k].0;
-                angles.insert(Angle::new(atom_i_id, j, atom_k_id));
+                emit(Angle::new(atom_i_id, j, atom_k_id, class));
             }
         }
     }
-    angles
 }
 
-/// Builds torsions by extending each bond to its neighboring atoms.
-fn build_torsions(annotated_molecule: &AnnotatedMolecule) -> HashSet<Torsion> {
-    let mut torsions = HashSet::new();
+/// Emits torsions by extending each bond to its neighboring atoms.
+///
+/// The torsions about one bond are emitted together, each carrying their number as its shared
+/// count. With `exclude_coordination` set, torsions containing a metal center are skipped;
+/// every such torsion passes through at least one coordination bond.
+fn emit_torsions(
+    annotated_molecule: &AnnotatedMolecule,
+    resonant_bond_ids: &HashSet<usize>,
+    exclude_coordination: bool,
+    emit: &mut impl FnMut(Torsion),
+) {
+    let is_excluded =
+        |id: usize| exclude_coordination && annotated_molecule.atoms[id].is_metal_center;
+    let mut batch = Vec::new();
     for bond_jk in &annotated_molecule.bonds {
         let (j, k) = bond_jk.atom_ids;
+        if is_excluded(j) || is_excluded(k) {
+            continue;
+        }
+        let order = topology_order(bond_jk.id, bond_jk.order, resonant_bond_ids);
+        let class = torsion_class(annotated_molecule, j, k, order);
 
         for &(i, _) in &annotated_molecule.adjacency[j] {
             if i == k {
                 continue;
             }
+            if is_excluded(i) {
+                continue;
+            }
             for &(l, _) in &annotated_molecule.adjacency[k] {
-                if l == j || l == i {
+                if l == j || l == i || is_excluded(l) {
                     continue;
                 }
-                torsions.insert(Torsion::new(i, j, k, l));
+                batch.push(Torsion::new(i, j, k, l, class));
+            }
+        }
+
+        let shared_count = batch.len();
+        for torsion in batch.drain(..) {
+            emit(Torsion {
+                shared_count,
+                ..torsion
+            });
+        }
+    }
+}
+
+/// Returns `true` for trigonal and resonant centers.
+fn is_trigonal(hybridization: Hybridization) -> bool {
+    matches!(hybridization, Hybridization::SP2 | Hybridization::Resonant)
+}
+
+/// Returns `true` if `j` and `k` are adjacent atoms of a perceived ring or macrocycle.
+fn is_ring_bond(annotated_molecule: &AnnotatedMolecule, j: usize, k: usize) -> bool {
+    annotated_molecule
+        .rings
+        .iter()
+        .chain(&annotated_molecule.macrocycles)
+        .any(|ring| {
+            (0..ring.len()).any(|i| {
+                let pair = (ring[i], ring[(i + 1) % ring.len()]);
+                pair == (j, k) || pair == (k, j)
+            })
+        })
+}
+
+/// Returns `true` for elements of the oxygen column, which use cases (h) and (i).
+fn is_oxygen_column(element: Element) -> bool {
+    matches!(element, Element::O | Element::S | Element::Se | Element::Te)
+}
+
+/// Selects the DREIDING torsion case for the central bond `j`-`k`.
+fn torsion_class(
+    annotated_molecule: &AnnotatedMolecule,
+    j: usize,
+    k: usize,
+    order: TopologyBondOrder,
+) -> TorsionClass {
+    let atoms = &annotated_molecule.atoms;
+    let (hyb_j, hyb_k) = (atoms[j].hybridization, atoms[k].hybridization);
+
+    if hyb_j == Hybridization::SP3 && hyb_k == Hybridization::SP3 {
+        if is_oxygen_column(atoms[j].element) && is_oxygen_column(atoms[k].element) {
+            TorsionClass::OxygenPair
+        } else {
+            TorsionClass::Sp3Sp3
+        }
+    } else if (hyb_j == Hybridization::SP3 && is_trigonal(hyb_k))
+        || (is_trigonal(hyb_j) && hyb_k == Hybridization::SP3)
+    {
+        let (sp3, sp2) = if hyb_j == Hybridization::SP3 {
+            (j, k)
+        } else {
+            (k, j)
+        };
+        let conjugated = atoms[sp2].hybridization == Hybridization::SP2
+            && annotated_molecule.adjacency[sp2]
+                .iter()
+                .any(|&(other, _)| other != sp3 && is_trigonal(atoms[other].hybridization));
+        if is_oxygen_column(atoms[sp3].element) {
+            TorsionClass::OxygenSp2
+        } else if conjugated {
+            TorsionClass::ConjugatedSp3Sp2
+        } else {
+            TorsionClass::Sp3Sp2
+        }
+    } else if is_trigonal(hyb_j) && is_trigonal(hyb_k) {
+        match order {
+            TopologyBondOrder::Double => TorsionClass::Double,
+            TopologyBondOrder::Resonant => TorsionClass::Resonant,
+            _ if hyb_j == Hybridization::Resonant
+                && hyb_k == Hybridization::Resonant
+                && !is_ring_bond(annotated_molecule, j, k) =>
+            {
+                TorsionClass::Exocyclic
             }
+            _ => TorsionClass::Sp2Sp2Single,
         }
+    } else if hyb_j == Hybridization::SP || hyb_k == Hybridization::SP {
+        TorsionClass::Linear
+    } else {
+        TorsionClass::Unhybridized
     }
-    torsions
 }
 
-/// Builds inversions by identifying planar centers and generating three
-/// terms per center with each neighbor as axis.
-fn build_inversions(annotated_molecule: &AnnotatedMolecule) -> HashSet<Inversion> {
-    let mut inversions = HashSet::new();
+/// Emits inversions for every planar center, choosing the axes according to `policy`.
+fn emit_inversions(
+    annotated_molecule: &AnnotatedMolecule,
+    resonant_bond_ids: &HashSet<usize>,
+    policy: InversionPolicy,
+    emit: &mut impl FnMut(Inversion),
+) {
     for atom in &annotated_molecule.atoms {
-        if atom.degree == 3
-            && matches!(
-                atom.hybridization,
-                Hybridization::SP2 | Hybridization::Resonant
-            )
-        {
-            let neighbors = &annotated_molecule.adjacency[atom.id];
-            let n0 = neighbors[0].0;
-            let n1 = neighbors[1].0;
-            let n2 = neighbors[2].0;
-
-            // Term 1: axis=n0, plane={n1, n2}
-            inversions.insert(Inversion::new(atom.id, n0, n1, n2));
-            // Term 2: axis=n1, plane={n0, n2}
-            inversions.insert(Inversion::new(atom.id, n1, n0, n2));
-            // Term 3: axis=n2, plane={n0, n1}
-            inversions.insert(Inversion::new(atom.id, n2, n0, n1));
+        if atom.degree != 3 || !is_trigonal(atom.hybridization) {
+            continue;
+        }
+        let class = InversionClass::of(atom.hybridization);
+        let neighbors = &annotated_molecule.adjacency_with_bonds[atom.id];
+        let n0 = neighbors[0].neighbor_id;
+        let n1 = neighbors[1].neighbor_id;
+        let n2 = neighbors[2].neighbor_id;
+
+        match policy {
+            InversionPolicy::AllAxes => {}
+            InversionPolicy::UmbrellaOnlyForNitrogen if atom.element == Element::N => {}
+            InversionPolicy::UmbrellaOnlyForNitrogen => continue,
+            InversionPolicy::SingleCanonical => {
+                let axis = neighbors
+                    .iter()
+                    .max_by_key(|bond| {
+                        let order = topology_order(bond.bond_id, bond.order, resonant_bond_ids);
+                        let element = annotated_molecule.atoms[bond.neighbor_id].element;
+                        (
+                            axis_rank(order),
+                            element.atomic_number(),
+                            Reverse(bond.neighbor_id),
+                        )
+                    })
+                    .expect("planar centers have three neighbors")
+                    .neighbor_id;
+                let mut plane = [n0, n1, n2].into_iter().filter(|&id| id != axis);
+                let (p1, p2) = (plane.next().unwrap(), plane.next().unwrap());
+                emit(Inversion::new(atom.id, axis, p1, p2, class));
+                continue;
+            }
         }
+
+        // Term 1: axis=n0, plane={n1, n2}
+        emit(Inversion::new(atom.id, n0, n1, n2, class));
+        // Term 2: axis=n1, plane={n0, n2}
+        emit(Inversion::new(atom.id, n1, n0, n2, class));
+        // Term 3: axis=n2, plane={n0, n1}
+        emit(Inversion::new(atom.id, n2, n0, n1, class));
+    }
+}
+
+/// Ranks bond orders for choosing the single inversion axis of a planar center.
+fn axis_rank(order: TopologyBondOrder) -> u8 {
+    match order {
+        TopologyBondOrder::Single => 0,
+        TopologyBondOrder::Resonant => 1,
+        TopologyBondOrder::Double => 2,
+        TopologyBondOrder::Triple => 3,
     }
-    inversions
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compat::collections::HashSet;
     use crate::core::graph::MolecularGraph;
     use crate::core::properties::{Element, GraphBondOrder, TopologyBondOrder};
     use crate::perception::ResonanceSystem;
-    use std::collections::HashSet;
+
+    // The builders below collect emitted terms into sets and check that none repeats.
+
+    fn build_bonds(molecule: &AnnotatedMolecule, resonant: &HashSet<usize>) -> HashSet<Bond> {
+        let mut bonds = HashSet::new();
+        emit_bonds(molecule, resonant, &mut |bond| assert!(bonds.insert(bond)));
+        bonds
+    }
+
+    fn build_angles(molecule: &AnnotatedMolecule) -> HashSet<Angle> {
+        let mut angles = HashSet::new();
+        emit_angles(molecule, &mut |angle| assert!(angles.insert(angle)));
+        angles
+    }
+
+    fn build_torsions(
+        molecule: &AnnotatedMolecule,
+        resonant: &HashSet<usize>,
+        exclude_coordination: bool,
+    ) -> HashSet<Torsion> {
+        let mut torsions = HashSet::new();
+        emit_torsions(molecule, resonant, exclude_coordination, &mut |torsion| {
+            assert!(torsions.insert(torsion))
+        });
+        torsions
+    }
+
+    fn build_inversions(
+        molecule: &AnnotatedMolecule,
+        resonant: &HashSet<usize>,
+        policy: InversionPolicy,
+    ) -> HashSet<Inversion> {
+        let mut inversions = HashSet::new();
+        emit_inversions(molecule, resonant, policy, &mut |inversion| {
+            assert!(inversions.insert(inversion))
+        });
+        inversions
+    }
 
     fn planar_fragment() -> (AnnotatedMolecule, Vec<String>) {
         let mut graph = MolecularGraph::new();
@@ -193,7 +540,12 @@ mod tests {
             .expect("valid bond");
 
         let mut molecule = AnnotatedMolecule::new(&graph).expect("graph should be valid");
+        molecule.atoms[c_left].hybridization = Hybridization::SP3;
         molecule.atoms[c_center].hybridization = Hybridization::SP2;
+        molecule.atoms[c_right].hybridization = Hybridization::SP2;
+        molecule.atoms[n_cap].hybridization = Hybridization::Resonant;
+        molecule.atoms[c_tail].hybridization = Hybridization::SP3;
+        molecule.atoms[h_tail].hybridization = Hybridization::None;
 
         molecule.resonance_systems.push(ResonanceSystem {
             atom_ids: vec![c_center, c_right, n_cap],
@@ -216,7 +568,8 @@ mod tests {
     fn build_atoms_uses_atom_ids_to_assign_types() {
         let (molecule, atom_types) = planar_fragment();
 
-        let atoms = build_atoms(&molecule, &atom_types);
+        let provenance = vec![TypeProvenance::Rule; atom_types.len()];
+        let atoms = build_atoms(&molecule, &atom_types, &provenance);
 
         assert_eq!(atoms.len(), molecule.atoms.len());
         assert_eq!(atoms[1].atom_type, "C_R");
@@ -228,7 +581,7 @@ mod tests {
     fn build_bonds_assigns_resonant_order_to_system_bonds() {
         let (molecule, _) = planar_fragment();
 
-        let bonds = build_bonds(&molecule);
+        let bonds = build_bonds(&molecule, &resonant_bond_ids(&molecule));
 
         assert_eq!(bonds.len(), molecule.bonds.len());
 
@@ -244,11 +597,11 @@ mod tests {
 
         let angles = build_angles(&molecule);
         let expected: HashSet<_> = vec![
-            Angle::new(0, 1, 2),
-            Angle::new(0, 1, 3),
-            Angle::new(2, 1, 3),
-            Angle::new(1, 2, 4),
-            Angle::new(2, 4, 5),
+            Angle::new(0, 1, 2, AngleClass::Trigonal),
+            Angle::new(0, 1, 3, AngleClass::Trigonal),
+            Angle::new(2, 1, 3, AngleClass::Trigonal),
+            Angle::new(1, 2, 4, AngleClass::Trigonal),
+            Angle::new(2, 4, 5, AngleClass::Tetrahedral),
         ]
         .into_iter()
         .collect();
@@ -260,11 +613,15 @@ mod tests {
     fn build_torsions_emits_all_valid_dihedrals() {
         let (molecule, _) = planar_fragment();
 
-        let torsions = build_torsions(&molecule);
+        let torsions = build_torsions(&molecule, &resonant_bond_ids(&molecule), false);
+        let about_resonant_bond = |i, j, k, l| Torsion {
+            shared_count: 2,
+            ..Torsion::new(i, j, k, l, TorsionClass::Resonant)
+        };
         let expected: HashSet<_> = vec![
-            Torsion::new(0, 1, 2, 4),
-            Torsion::new(3, 1, 2, 4),
-            Torsion::new(1, 2, 4, 5),
+            about_resonant_bond(0, 1, 2, 4),
+            about_resonant_bond(3, 1, 2, 4),
+            Torsion::new(1, 2, 4, 5, TorsionClass::ConjugatedSp3Sp2),
         ]
         .into_iter()
         .collect();
@@ -272,15 +629,60 @@ mod tests {
         assert_eq!(torsions, expected);
     }
 
+    #[test]
+    fn torsions_record_how_many_share_their_central_bond() {
+        let (molecule, types) = planar_fragment();
+        let provenance = vec![TypeProvenance::Rule; types.len()];
+
+        let topology = build_topology(
+            &molecule,
+            &types,
+            &provenance,
+            false,
+            InversionPolicy::AllAxes,
+            false,
+        );
+        let counts: Vec<_> = topology
+            .torsions
+            .iter()
+            .map(|torsion| (torsion.central_bond(), torsion.shared_count))
+            .collect();
+        assert_eq!(counts.len(), 3);
+        for (bond, count) in counts {
+            assert_eq!(count, if bond == (1, 2) { 2 } else { 1 });
+        }
+    }
+
+    #[test]
+    fn torsion_class_follows_the_dreiding_cases() {
+        let (mut molecule, _) = planar_fragment();
+
+        let class = |m: &AnnotatedMolecule, j, k, order| torsion_class(m, j, k, order).case();
+        assert_eq!(class(&molecule, 1, 2, TopologyBondOrder::Double), 'c');
+        assert_eq!(class(&molecule, 1, 2, TopologyBondOrder::Single), 'e');
+        assert_eq!(class(&molecule, 2, 4, TopologyBondOrder::Single), 'j');
+
+        molecule.atoms[2].hybridization = Hybridization::SP3;
+        assert_eq!(class(&molecule, 2, 4, TopologyBondOrder::Single), 'a');
+        molecule.atoms[4].hybridization = Hybridization::SP;
+        assert_eq!(class(&molecule, 2, 4, TopologyBondOrder::Single), 'g');
+        molecule.atoms[4].hybridization = Hybridization::None;
+        assert_eq!(class(&molecule, 2, 4, TopologyBondOrder::Single), 'g');
+    }
+
     #[test]
     fn build_inversions_generates_three_per_planar_center() {
         let (molecule, _) = planar_fragment();
 
-        let inversions = build_inversions(&molecule);
+        let inversions = build_inversions(
+            &molecule,
+            &resonant_bond_ids(&molecule),
+            InversionPolicy::AllAxes,
+        );
         let expected: HashSet<_> = vec![
-            Inversion::new(1, 0, 2, 3),
-            Inversion::new(1, 2, 0, 3),
-            Inversion::new(1, 3, 0, 2),
+            Inversion::new(1, 0, 2, 3, InversionClass::Planar),
+            Inversion::new(1, 2, 0, 3, InversionClass::Planar),
+            Inversion::new(1, 3, 0, 2, InversionClass::Planar),
         ]
         .into_iter()
         .collect();
@@ -288,4 +690,78 @@ mod tests {
         assert_eq!(inversions.len(), 3);
         assert_eq!(inversions, expected);
     }
+
+    #[test]
+    fn inversion_policies_select_the_emitted_axes() {
+        let (molecule, _) = planar_fragment();
+        let resonant = resonant_bond_ids(&molecule);
+
+        // Both resonant bonds of the center tie, so the heavier nitrogen becomes the axis.
+        let single = build_inversions(&molecule, &resonant, InversionPolicy::SingleCanonical);
+        let expected: HashSet<_> = [Inversion::new(1, 3, 0, 2, InversionClass::Planar)]
+            .into_iter()
+            .collect();
+        assert_eq!(single, expected);
+
+        let nitrogen = build_inversions(
+            &molecule,
+            &resonant,
+            InversionPolicy::UmbrellaOnlyForNitrogen,
+        );
+        assert!(nitrogen.is_empty());
+    }
+
+    #[test]
+    fn build_hb_lists_use_types_and_lone_pairs() {
+        let (mut molecule, mut atom_types) = planar_fragment();
+        atom_types[5] = "H_HB".to_string();
+        molecule.atoms[3].lone_pairs = 1;
+
+        assert_eq!(
+            build_hb_donors(&molecule, &atom_types),
+            [HydrogenBondDonor {
+                donor_id: 4,
+                hydrogen_id: 5
+            }]
+        );
+        assert_eq!(build_hb_acceptors(&molecule), [3]);
+
+        molecule.atoms[3].lone_pairs = 0;
+        assert!(build_hb_acceptors(&molecule).is_empty());
+    }
+
+    #[test]
+    fn bonds_are_classified_and_coordination_torsions_can_be_left_out() {
+        // Cu-S-S-C: a metal bound to one end of a disulfide.
+        let mut graph = MolecularGraph::new();
+        let cu = graph.add_atom(Element::Cu);
+        let s1 = graph.add_atom(Element::S);
+        let s2 = graph.add_atom(Element::S);
+        let c = graph.add_atom(Element::C);
+        graph.add_bond(cu, s1, GraphBondOrder::Single).unwrap();
+        graph.add_bond(s1, s2, GraphBondOrder::Single).unwrap();
+        graph.add_bond(s2, c, GraphBondOrder::Single).unwrap();
+        let mut molecule = AnnotatedMolecule::new(&graph).unwrap();
+        for id in [s1, s2, c] {
+            molecule.atoms[id].hybridization = Hybridization::SP3;
+        }
+        let resonant = resonant_bond_ids(&molecule);
+
+        let mut classes: Vec<_> = build_bonds(&molecule, &resonant)
+            .into_iter()
+            .map(|bond| (bond.atom_ids, bond.class))
+            .collect();
+        classes.sort_unstable_by_key(|&(atom_ids, _)| atom_ids);
+        assert_eq!(
+            classes,
+            [
+                ((cu, s1), BondClass::Coordination),
+                ((s1, s2), BondClass::Disulfide),
+                ((s2, c), BondClass::Covalent),
+            ]
+        );
+
+        assert_eq!(build_torsions(&molecule, &resonant, false).len(), 1);
+        assert!(build_torsions(&molecule, &resonant, true).is_empty());
+    }
 }
@@ -0,0 +1,173 @@
+//! Adds lone-pair virtual sites to hydrogen-bond acceptors.
+//!
+//! Force-field variants with off-center charges place massless sites along the lone pairs of
+//! acceptors such as water oxygens or pyridine nitrogens. The sites run on a finished topology:
+//! each one becomes an extra dummy atom after the real atoms, and a [`VirtualSite`] record names
+//! the acceptor it belongs to and the bonded neighbors whose positions define its frame.
+
+use crate::compat::prelude::*;
+use crate::core::metadata::AtomMetadata;
+use crate::core::properties::{DreidingType, Element, Hybridization};
+use crate::core::topology::{Atom, MolecularTopology, TypeProvenance, VirtualSite};
+
+/// Atom type given to every lone-pair site, matching the default rule for dummy atoms.
+const LONE_PAIR_TYPE: &str = "Du";
+
+/// Appends one dummy atom per lone pair of every hydrogen-bond acceptor.
+///
+/// The number of lone pairs follows from the acceptor's steric number: four electron domains
+/// for sp³ and unhybridized centers, three for sp² and resonant centers, and two for sp centers,
+/// less one per bonded neighbor. A carbonyl oxygen therefore receives two sites, a water oxygen
+/// two, an amine or pyridine nitrogen one, and an amide nitrogen none. Sites take the next free
+/// atom IDs in order of their parent, are typed `Du` with [`Hybridization::None`], share the
+/// fragment and residue of their parent, and get no bonded terms.
+///
+/// # Arguments
+///
+/// * `topology` - Finished topology whose `hb_acceptors` list the acceptors.
+///
+/// # Returns
+///
+/// The number of sites added.
+pub fn add_lone_pair_sites(topology: &mut MolecularTopology) -> usize {
+    let mut frames = vec![Vec::new(); topology.atoms.len()];
+    for bond in &topology.bonds {
+        let (a, b) = bond.atom_ids;
+        frames[a].push(b);
+        frames[b].push(a);
+    }
+
+    let acceptors = topology.hb_acceptors.clone();
+    let mut added = 0;
+    for parent_id in acceptors {
+        let parent = &topology.atoms[parent_id];
+        let domains: usize = match parent.hybridization {
+            Hybridization::SP => 2,
+            Hybridization::SP2 | Hybridization::Resonant => 3,
+            Hybridization::SP3 | Hybridization::None => 4,
+            Hybridization::Unknown => continue,
+        };
+        let mut frame_ids = core::mem::take(&mut frames[parent_id]);
+        frame_ids.sort_unstable();
+        let lone_pairs = domains.saturating_sub(frame_ids.len());
+
+        let fragment_id = parent.fragment_id;
+        let metadata = AtomMetadata {
+            residue: parent.metadata.residue.clone(),
+            ..AtomMetadata::default()
+        };
+        for index in 0..lone_pairs {
+            let site_id = topology.atoms.len();
+            topology.atoms.push(Atom {
+                id: site_id,
+                element: Element::Dummy,
+                atom_type: DreidingType::from(LONE_PAIR_TYPE),
+                hybridization: Hybridization::None,
+                provenance: TypeProvenance::Rule,
+                fragment_id,
+                chirality: None,
+                metadata: metadata.clone(),
+            });
+            topology.virtual_sites.push(VirtualSite {
+                site_id,
+                parent_id,
+                frame_ids: frame_ids.clone(),
+                index,
+            });
+        }
+        added += lone_pairs;
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::properties::GraphBondOrder;
+    use crate::pipeline::Typer;
+
+    fn topology_of(graph: &MolecularGraph) -> MolecularTopology {
+        Typer::new().assign_topology(graph).unwrap()
+    }
+
+    fn sites_per_parent(topology: &MolecularTopology) -> Vec<(usize, usize)> {
+        let mut counts = std::collections::BTreeMap::new();
+        for site in &topology.virtual_sites {
+            *counts.entry(site.parent_id).or_insert(0) += 1;
+        }
+        counts.into_iter().collect()
+    }
+
+    #[test]
+    fn water_oxygen_receives_two_sites_framed_by_its_hydrogens() {
+        let mut graph = MolecularGraph::new();
+        let o = graph.add_atom(Element::O);
+        let h1 = graph.add_atom(Element::H);
+        let h2 = graph.add_atom(Element::H);
+        graph.add_bond(o, h1, GraphBondOrder::Single).unwrap();
+        graph.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+
+        let mut topology = topology_of(&graph);
+        let bonds_before = topology.bonds.len();
+        assert_eq!(add_lone_pair_sites(&mut topology), 2);
+
+        assert_eq!(topology.atoms.len(), 5);
+        assert_eq!(topology.bonds.len(), bonds_before);
+        for (index, site) in topology.virtual_sites.iter().enumerate() {
+            assert_eq!(site.site_id, 3 + index);
+            assert_eq!(site.parent_id, o);
+            assert_eq!(site.frame_ids, [h1, h2]);
+            assert_eq!(site.index, index);
+            let atom = &topology.atoms[site.site_id];
+            assert_eq!(atom.element, Element::Dummy);
+            assert_eq!(atom.atom_type, "Du");
+        }
+        assert_eq!(topology.molecule_indices(), [0; 5]);
+    }
+
+    #[test]
+    fn lone_pair_counts_follow_the_steric_number() {
+        // Formamide, pyridine nitrogen, and ammonia in one graph.
+        let mut graph = MolecularGraph::new();
+        let c = graph.add_atom(Element::C);
+        let o = graph.add_atom(Element::O);
+        let amide_n = graph.add_atom(Element::N);
+        graph.add_bond(c, o, GraphBondOrder::Double).unwrap();
+        graph.add_bond(c, amide_n, GraphBondOrder::Single).unwrap();
+        for atom in [c, amide_n, amide_n] {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(atom, h, GraphBondOrder::Single).unwrap();
+        }
+
+        let ring: Vec<usize> = (0..6)
+            .map(|i| graph.add_atom(if i == 0 { Element::N } else { Element::C }))
+            .collect();
+        for i in 0..6 {
+            let order = if i % 2 == 0 {
+                GraphBondOrder::Double
+            } else {
+                GraphBondOrder::Single
+            };
+            graph.add_bond(ring[i], ring[(i + 1) % 6], order).unwrap();
+        }
+        for &carbon in &ring[1..] {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(carbon, h, GraphBondOrder::Single).unwrap();
+        }
+
+        let ammonia = graph.add_atom(Element::N);
+        for _ in 0..3 {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(ammonia, h, GraphBondOrder::Single).unwrap();
+        }
+
+        let mut topology = topology_of(&graph);
+        add_lone_pair_sites(&mut topology);
+
+        assert_eq!(
+            sites_per_parent(&topology),
+            [(o, 2), (ring[0], 1), (ammonia, 1)]
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Streams topology terms to a caller-supplied sink instead of collecting them.
+//!
+//! A topology of a large system holds several angles and torsions per atom. Exporters that write
+//! each term straight to disk do not need the lists in memory: [`build_topology_streaming`]
+//! hands every bond, angle, torsion, and inversion to a [`TopologySink`] as soon as it is
+//! generated, and keeps nothing but the per-bond torsion batch and the IDs of resonant bonds.
+
+use super::{emit_angles, emit_bonds, emit_inversions, emit_torsions, resonant_bond_ids};
+use crate::core::topology::{Angle, Bond, Inversion, InversionPolicy, Torsion};
+use crate::perception::AnnotatedMolecule;
+
+/// Receiver of the terms generated by [`build_topology_streaming`].
+///
+/// Every method has an empty default, so a sink implements only the term kinds it consumes.
+/// Terms arrive exactly as [`Typer::assign_topology`](crate::Typer::assign_topology) stores
+/// them: canonically ordered, each term once, bonds with their topology order and class, and
+/// torsions with their class and [`shared_count`](Torsion::shared_count) already set. The
+/// sink also chooses the settings that change which terms exist.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{
+///     Angle, Element, GraphBondOrder, MolecularGraph, TopologySink, Typer,
+///     build_topology_streaming,
+/// };
+///
+/// #[derive(Default)]
+/// struct AngleCounter(usize);
+///
+/// impl TopologySink for AngleCounter {
+///     fn angle(&mut self, _angle: Angle) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// // Water.
+/// let mut graph = MolecularGraph::new();
+/// let o = graph.add_atom(Element::O);
+/// for _ in 0..2 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+/// }
+///
+/// let molecule = Typer::new().perceive(&graph).unwrap();
+/// let mut counter = AngleCounter::default();
+/// build_topology_streaming(&molecule, &mut counter);
+/// assert_eq!(counter.0, 1);
+/// ```
+pub trait TopologySink {
+    /// Receives one bond.
+    fn bond(&mut self, bond: Bond) {
+        let _ = bond;
+    }
+
+    /// Receives one angle.
+    fn angle(&mut self, angle: Angle) {
+        let _ = angle;
+    }
+
+    /// Receives one torsion.
+    ///
+    /// Torsions about the same central bond arrive one after another. Barrierless torsions,
+    /// which [`Typer::with_torsion_suppression`](crate::Typer::with_torsion_suppression) would
+    /// set aside, are streamed like every other; their class tells them apart.
+    fn torsion(&mut self, torsion: Torsion) {
+        let _ = torsion;
+    }
+
+    /// Receives one inversion.
+    fn inversion(&mut self, inversion: Inversion) {
+        let _ = inversion;
+    }
+
+    /// Which inversion terms are generated for each planar center, as set by
+    /// [`Typer::with_inversion_policy`](crate::Typer::with_inversion_policy).
+    fn inversion_policy(&self) -> InversionPolicy {
+        InversionPolicy::default()
+    }
+
+    /// Whether torsions through a coordination bond are left out, as set by
+    /// [`Typer::with_coordination_torsion_exclusion`](crate::Typer::with_coordination_torsion_exclusion).
+    fn exclude_coordination_torsions(&self) -> bool {
+        false
+    }
+}
+
+/// Generates the bonded terms of a perceived molecule and streams them to `sink`.
+///
+/// Bonds are streamed first, in bond-ID order, then angles by center atom, torsions by central
+/// bond, and inversions by center atom. The terms are those
+/// [`Typer::assign_topology`](crate::Typer::assign_topology) would return for the molecule
+/// with the sink's settings, without atoms, hydrogen-bond sites, or rings, which need atom
+/// types or are small enough to build in full.
+///
+/// # Arguments
+///
+/// * `annotated_molecule` - Molecule carrying ring, hybridization, and bonding metadata, such
+///   as the output of [`Typer::perceive`](crate::Typer::perceive).
+/// * `sink` - Receiver of every term.
+pub fn build_topology_streaming(
+    annotated_molecule: &AnnotatedMolecule,
+    sink: &mut impl TopologySink,
+) {
+    let policy = sink.inversion_policy();
+    let exclude_coordination = sink.exclude_coordination_torsions();
+    let resonant_bond_ids = resonant_bond_ids(annotated_molecule);
+
+    emit_bonds(annotated_molecule, &resonant_bond_ids, &mut |bond| {
+        sink.bond(bond)
+    });
+    emit_angles(annotated_molecule, &mut |angle| sink.angle(angle));
+    emit_torsions(
+        annotated_molecule,
+        &resonant_bond_ids,
+        exclude_coordination,
+        &mut |torsion| sink.torsion(torsion),
+    );
+    emit_inversions(
+        annotated_molecule,
+        &resonant_bond_ids,
+        policy,
+        &mut |inversion| sink.inversion(inversion),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat::prelude::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::properties::{Element, GraphBondOrder};
+    use crate::pipeline::Typer;
+
+    /// Records every streamed term in order.
+    #[derive(Default)]
+    struct Recorder {
+        bonds: Vec<Bond>,
+        angles: Vec<Angle>,
+        torsions: Vec<Torsion>,
+        inversions: Vec<Inversion>,
+        policy: InversionPolicy,
+    }
+
+    impl TopologySink for Recorder {
+        fn bond(&mut self, bond: Bond) {
+            self.bonds.push(bond);
+        }
+        fn angle(&mut self, angle: Angle) {
+            self.angles.push(angle);
+        }
+        fn torsion(&mut self, torsion: Torsion) {
+            self.torsions.push(torsion);
+        }
+        fn inversion(&mut self, inversion: Inversion) {
+            self.inversions.push(inversion);
+        }
+        fn inversion_policy(&self) -> InversionPolicy {
+            self.policy
+        }
+    }
+
+    #[test]
+    fn streamed_terms_match_the_assigned_topology() {
+        // Acetamide, with a resonant amide and a planar carbonyl carbon.
+        let graph = crate::io::smiles::parse_smiles("CC(=O)N").unwrap();
+        let typer = Typer::new().with_inversion_policy(InversionPolicy::SingleCanonical);
+        let topology = typer.assign_topology(&graph).unwrap();
+        let molecule = crate::perception::perceive(
+            &graph,
+            &[],
+            typer.perception_pipeline(),
+            typer.perception_options(),
+            &Default::default(),
+            &mut |_| {},
+        )
+        .unwrap();
+
+        let mut recorder = Recorder {
+            policy: InversionPolicy::SingleCanonical,
+            ..Recorder::default()
+        };
+        build_topology_streaming(&molecule, &mut recorder);
+
+        assert_eq!(recorder.bonds, topology.bonds);
+        assert_eq!(recorder.angles, topology.angles);
+        assert_eq!(recorder.torsions, topology.torsions);
+        assert_eq!(recorder.inversions, topology.inversions);
+        assert_eq!(recorder.inversions.len(), 2);
+    }
+
+    #[test]
+    fn torsions_about_one_bond_arrive_together_with_their_count() {
+        let mut graph = MolecularGraph::new();
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+        for carbon in [c1, c2] {
+            for _ in 0..3 {
+                let h = graph.add_atom(Element::H);
+                graph.add_bond(carbon, h, GraphBondOrder::Single).unwrap();
+            }
+        }
+        let molecule = AnnotatedMolecule::new(&graph).unwrap();
+
+        let mut recorder = Recorder::default();
+        build_topology_streaming(&molecule, &mut recorder);
+
+        assert_eq!(recorder.torsions.len(), 9);
+        assert!(recorder.torsions.iter().all(|t| t.shared_count == 9));
+        assert_eq!(recorder.bonds.len(), 7);
+        assert_eq!(recorder.angles.len(), 12);
+    }
+}
@@ -0,0 +1,132 @@
+//! Collapses nonpolar hydrogens into their carbons to produce united-atom topologies.
+//!
+//! DREIDING's united-atom types (`C_31` … `C_34`, `C_R1`, `C_R2`, `C_21`, `C_22`, `C_11`)
+//! describe a carbon together with its implicit hydrogens. The collapse runs on a finished
+//! all-atom topology, so perception and typing always see the explicit hydrogens they rely on.
+
+use crate::compat::prelude::*;
+use crate::core::properties::Element;
+use crate::core::topology::MolecularTopology;
+
+/// Folds every hydrogen bonded only to a carbon into that carbon.
+///
+/// Each carbon with at least one such hydrogen receives the united-atom type for its type and
+/// hydrogen count, and the hydrogens are removed along with every term touching them. Carbons
+/// whose type has no united-atom counterpart (such as custom labels) keep their hydrogens
+/// explicit. Polar hydrogens are never collapsed.
+///
+/// # Arguments
+///
+/// * `topology` - All-atom topology carrying canonical DREIDING types.
+///
+/// # Returns
+///
+/// The old atom ID of every remaining atom, in its new order.
+pub fn collapse_nonpolar_hydrogens(topology: &mut MolecularTopology) -> Vec<usize> {
+    let n = topology.atoms.len();
+    let mut neighbors = vec![Vec::new(); n];
+    for bond in &topology.bonds {
+        let (a, b) = bond.atom_ids;
+        neighbors[a].push(b);
+        neighbors[b].push(a);
+    }
+
+    let mut hydrogens = vec![Vec::new(); n];
+    for atom in &topology.atoms {
+        if atom.element == Element::H
+            && let [parent] = neighbors[atom.id][..]
+            && topology.atoms[parent].element == Element::C
+        {
+            hydrogens[parent].push(atom.id);
+        }
+    }
+
+    let mut collapsed = vec![false; n];
+    for (atom, attached) in topology.atoms.iter_mut().zip(&hydrogens) {
+        if attached.is_empty() {
+            continue;
+        }
+        if let Some(united) = atom.atom_type.united_atom(attached.len()) {
+            atom.atom_type = united;
+            for &h in attached {
+                collapsed[h] = true;
+            }
+        }
+    }
+
+    topology.retain_atoms(|atom| !collapsed[atom.id])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::properties::{DreidingType, GraphBondOrder};
+    use crate::pipeline::Typer;
+
+    fn add_hydrogens(graph: &mut MolecularGraph, atom: usize, count: usize) {
+        for _ in 0..count {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(atom, h, GraphBondOrder::Single).unwrap();
+        }
+    }
+
+    #[test]
+    fn ethanol_keeps_only_the_hydroxyl_hydrogen() {
+        let mut graph = MolecularGraph::new();
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        let o = graph.add_atom(Element::O);
+        graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+        graph.add_bond(c2, o, GraphBondOrder::Single).unwrap();
+        add_hydrogens(&mut graph, c1, 3);
+        add_hydrogens(&mut graph, c2, 2);
+        add_hydrogens(&mut graph, o, 1);
+
+        let mut topology = Typer::new().assign_topology(&graph).unwrap();
+        let kept = collapse_nonpolar_hydrogens(&mut topology);
+
+        assert_eq!(kept, [0, 1, 2, 8]);
+        let types: Vec<_> = topology.atoms.iter().map(|a| a.atom_type.clone()).collect();
+        assert_eq!(
+            types,
+            [
+                DreidingType::C_33,
+                DreidingType::C_32,
+                DreidingType::O_3,
+                DreidingType::H_HB
+            ]
+        );
+        assert_eq!(topology.bonds.len(), 3);
+        assert_eq!(topology.angles.len(), 2);
+        assert_eq!(topology.torsions.len(), 1);
+        assert_eq!(topology.torsions[0].atom_ids, (0, 1, 2, 3));
+        assert_eq!(topology.hb_donors[0].hydrogen_id, 3);
+    }
+
+    #[test]
+    fn aromatic_ring_carbons_lose_their_inversions() {
+        let mut graph = MolecularGraph::new();
+        let ring: Vec<_> = (0..6).map(|_| graph.add_atom(Element::C)).collect();
+        for i in 0..6 {
+            let order = if i % 2 == 0 {
+                GraphBondOrder::Double
+            } else {
+                GraphBondOrder::Single
+            };
+            graph.add_bond(ring[i], ring[(i + 1) % 6], order).unwrap();
+        }
+        for &c in &ring {
+            add_hydrogens(&mut graph, c, 1);
+        }
+
+        let mut topology = Typer::new().assign_topology(&graph).unwrap();
+        collapse_nonpolar_hydrogens(&mut topology);
+
+        assert_eq!(topology.atoms.len(), 6);
+        assert!(topology.atoms.iter().all(|a| a.atom_type == "C_R1"));
+        assert_eq!(topology.angles.len(), 6);
+        assert_eq!(topology.torsions.len(), 6);
+        assert!(topology.inversions.is_empty());
+    }
+}
@@ -0,0 +1,253 @@
+//! Shims that let the core of the crate build with or without the standard library.
+//!
+//! With the default `std` feature the items here are, or wrap, their `std` counterparts. Without
+//! it, the crate is `no_std` and the `alloc` feature supplies replacements: `hashbrown` for hash
+//! collections, `spin` for one-time initialization and locks, and `libm` for the floating-point
+//! functions `core` lacks. Modules import these items instead of naming `std` directly.
+
+/// Heap-allocated types that the `std` prelude provides implicitly.
+///
+/// Files that allocate import `crate::compat::prelude::*` so they compile unchanged without the
+/// standard library.
+pub(crate) mod prelude {
+    pub(crate) use alloc::boxed::Box;
+    pub(crate) use alloc::string::{String, ToString};
+    pub(crate) use alloc::vec::Vec;
+}
+
+/// Hash-based collections.
+pub(crate) mod collections {
+    #[cfg(not(feature = "std"))]
+    pub(crate) use hashbrown::{HashMap, HashSet, hash_map};
+    #[cfg(feature = "std")]
+    pub(crate) use std::collections::{HashMap, HashSet, hash_map};
+}
+
+/// One-time initialization and locks that recover from a panicking holder.
+pub(crate) mod sync {
+    #[cfg(feature = "std")]
+    pub(crate) use std::sync::OnceLock;
+
+    /// Cell initialized at most once, with the subset of the `std::sync::OnceLock` API the crate
+    /// uses.
+    #[cfg(not(feature = "std"))]
+    pub(crate) struct OnceLock<T>(spin::Once<T>);
+
+    #[cfg(not(feature = "std"))]
+    impl<T> OnceLock<T> {
+        /// Creates an empty cell.
+        pub(crate) const fn new() -> Self {
+            Self(spin::Once::new())
+        }
+
+        /// Returns the value if the cell has been initialized.
+        pub(crate) fn get(&self) -> Option<&T> {
+            self.0.get()
+        }
+
+        /// Returns the value, initializing the cell with `init` if it is empty.
+        pub(crate) fn get_or_init(&self, init: impl FnOnce() -> T) -> &T {
+            self.0.call_once(init)
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    impl<T> Default for OnceLock<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    impl<T: Clone> Clone for OnceLock<T> {
+        fn clone(&self) -> Self {
+            match self.get() {
+                Some(value) => Self(spin::Once::initialized(value.clone())),
+                None => Self::new(),
+            }
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    impl<T: core::fmt::Debug> core::fmt::Debug for OnceLock<T> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_tuple("OnceLock").field(&self.get()).finish()
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub(crate) use spin::{
+        MutexGuard, RwLockReadGuard, RwLockWriteGuard, mutex::Mutex as Inner,
+        rwlock::RwLock as InnerRwLock,
+    };
+    #[cfg(feature = "std")]
+    pub(crate) use std::sync::{MutexGuard, RwLockReadGuard, RwLockWriteGuard};
+
+    /// Mutual-exclusion lock whose `lock` never fails.
+    ///
+    /// With `std`, a lock poisoned by a panicking holder is recovered instead of reported.
+    #[derive(Debug, Default)]
+    pub(crate) struct Mutex<T> {
+        #[cfg(feature = "std")]
+        inner: std::sync::Mutex<T>,
+        #[cfg(not(feature = "std"))]
+        inner: Inner<T>,
+    }
+
+    impl<T> Mutex<T> {
+        /// Creates an unlocked mutex holding `value`.
+        pub(crate) const fn new(value: T) -> Self {
+            Self {
+                #[cfg(feature = "std")]
+                inner: std::sync::Mutex::new(value),
+                #[cfg(not(feature = "std"))]
+                inner: Inner::new(value),
+            }
+        }
+
+        /// Blocks until the lock is acquired.
+        pub(crate) fn lock(&self) -> MutexGuard<'_, T> {
+            #[cfg(feature = "std")]
+            return self
+                .inner
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            #[cfg(not(feature = "std"))]
+            return self.inner.lock();
+        }
+    }
+
+    /// Reader-writer lock whose `read` and `write` never fail.
+    ///
+    /// With `std`, a lock poisoned by a panicking writer is recovered instead of reported.
+    #[derive(Debug, Default)]
+    pub(crate) struct RwLock<T> {
+        #[cfg(feature = "std")]
+        inner: std::sync::RwLock<T>,
+        #[cfg(not(feature = "std"))]
+        inner: InnerRwLock<T>,
+    }
+
+    impl<T> RwLock<T> {
+        /// Creates an unlocked lock holding `value`.
+        pub(crate) const fn new(value: T) -> Self {
+            Self {
+                #[cfg(feature = "std")]
+                inner: std::sync::RwLock::new(value),
+                #[cfg(not(feature = "std"))]
+                inner: InnerRwLock::new(value),
+            }
+        }
+
+        /// Blocks until shared read access is acquired.
+        pub(crate) fn read(&self) -> RwLockReadGuard<'_, T> {
+            #[cfg(feature = "std")]
+            return self
+                .inner
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            #[cfg(not(feature = "std"))]
+            return self.inner.read();
+        }
+
+        /// Blocks until exclusive write access is acquired.
+        pub(crate) fn write(&self) -> RwLockWriteGuard<'_, T> {
+            #[cfg(feature = "std")]
+            return self
+                .inner
+                .write()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            #[cfg(not(feature = "std"))]
+            return self.inner.write();
+        }
+    }
+}
+
+/// Monotonic clock used by time budgets and progress reports.
+pub(crate) mod time {
+    #[cfg(feature = "std")]
+    pub(crate) use std::time::Instant;
+
+    /// Stand-in for `std::time::Instant` on builds without a clock; it has no values.
+    #[cfg(not(feature = "std"))]
+    #[derive(Debug, Clone, Copy)]
+    pub(crate) enum Instant {}
+
+    #[cfg(not(feature = "std"))]
+    impl Instant {
+        /// Time elapsed since this instant; unreachable because no instant exists.
+        pub(crate) fn elapsed(&self) -> core::time::Duration {
+            match *self {}
+        }
+    }
+
+    /// Reads the monotonic clock, or returns `None` where there is none.
+    ///
+    /// `Instant::now` panics on `wasm32-unknown-unknown`, and `no_std` builds have no clock at
+    /// all, so time budgets and progress timings are skipped there.
+    pub(crate) fn now() -> Option<Instant> {
+        #[cfg(feature = "std")]
+        if cfg!(all(target_family = "wasm", target_os = "unknown")) {
+            None
+        } else {
+            Some(Instant::now())
+        }
+        #[cfg(not(feature = "std"))]
+        None
+    }
+}
+
+/// Floating-point functions that `core` does not provide.
+///
+/// Without `std`, modules that need them import [`Float`](float::Float), whose `libm`-backed
+/// methods carry the names of the inherent `f64` methods.
+#[cfg(not(any(feature = "std", test)))]
+pub(crate) mod float {
+    /// `libm`-backed replacements for the `std`-only `f64` methods.
+    pub(crate) trait Float {
+        fn sqrt(self) -> f64;
+        fn powi(self, n: i32) -> f64;
+        fn powf(self, n: f64) -> f64;
+        fn cos(self) -> f64;
+        fn sin(self) -> f64;
+        fn acos(self) -> f64;
+        fn floor(self) -> f64;
+        fn round(self) -> f64;
+        fn rem_euclid(self, rhs: f64) -> f64;
+    }
+
+    impl Float for f64 {
+        fn sqrt(self) -> f64 {
+            libm::sqrt(self)
+        }
+        fn powi(self, n: i32) -> f64 {
+            libm::pow(self, f64::from(n))
+        }
+        fn powf(self, n: f64) -> f64 {
+            libm::pow(self, n)
+        }
+        fn cos(self) -> f64 {
+            libm::cos(self)
+        }
+        fn sin(self) -> f64 {
+            libm::sin(self)
+        }
+        fn acos(self) -> f64 {
+            libm::acos(self)
+        }
+        fn floor(self) -> f64 {
+            libm::floor(self)
+        }
+        fn round(self) -> f64 {
+            libm::round(self)
+        }
+        fn rem_euclid(self, rhs: f64) -> f64 {
+            let remainder = self % rhs;
+            if remainder < 0.0 {
+                remainder + rhs.abs()
+            } else {
+                remainder
+            }
+        }
+    }
+}
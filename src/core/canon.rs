@@ -0,0 +1,361 @@
+//! Canonical atom numbering for molecular graphs.
+//!
+//! [`canonicalize`](crate::canonicalize) refines atom classes in the spirit of the Morgan
+//! algorithm: atoms start out grouped by element, degree, and bond orders, and each round splits
+//! groups whose members see different neighbor classes. When refinement stalls on symmetric
+//! atoms, one atom of the first tied class is singled out and refinement resumes, until every
+//! atom has its own rank. Ordering atoms by rank gives the same sequence for any input numbering
+//! of the same molecule, which makes typed output comparable, cacheable, and deduplicable.
+
+use super::graph::MolecularGraph;
+use super::properties::{Element, GraphBondOrder};
+use crate::compat::prelude::*;
+
+/// Computes a canonical atom order for a molecular graph.
+///
+/// Tie-breaking between atoms that refinement cannot distinguish uses the lowest input ID. For
+/// atoms related by a symmetry of the molecule the choice does not affect the result; highly
+/// regular graphs whose atoms are indistinguishable by neighborhood but not symmetric are rare in
+/// chemistry and may be numbered differently for different inputs.
+///
+/// # Arguments
+///
+/// * `graph` - Molecular graph to number; bonds that reference missing atoms are ignored.
+///
+/// # Returns
+///
+/// A permutation listing the input atom ID at each canonical position, suitable for
+/// [`MolecularTopology::permute`](crate::MolecularTopology::permute).
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{
+///     assign_topology, canonicalize, DreidingType, Element, GraphBondOrder, MolecularGraph,
+/// };
+///
+/// fn methanol(order: &[Element]) -> MolecularGraph {
+///     let mut graph = MolecularGraph::new();
+///     for &element in order {
+///         graph.add_atom(element);
+///     }
+///     let find = |e| order.iter().position(|&x| x == e).unwrap();
+///     let (c, o) = (find(Element::C), find(Element::O));
+///     graph.add_bond(c, o, GraphBondOrder::Single).unwrap();
+///     let hydrogens = (0..order.len()).filter(|&i| order[i] == Element::H);
+///     for (k, h) in hydrogens.enumerate() {
+///         graph.add_bond(if k == 0 { o } else { c }, h, GraphBondOrder::Single).unwrap();
+///     }
+///     graph
+/// }
+///
+/// use Element::{C, H, O};
+/// let a = methanol(&[C, O, H, H, H, H]);
+/// let b = methanol(&[H, H, O, H, H, C]);
+///
+/// let topologies = [a, b].map(|graph| {
+///     let mut topology = assign_topology(&graph).unwrap();
+///     topology.permute(&canonicalize(&graph)).unwrap();
+///     topology.sort_terms();
+///     topology
+/// });
+/// assert_eq!(topologies[0].bonds, topologies[1].bonds);
+/// assert_eq!(topologies[0].torsions, topologies[1].torsions);
+/// let types = |i: usize| -> Vec<DreidingType> {
+///     topologies[i].atoms.iter().map(|atom| atom.atom_type.clone()).collect()
+/// };
+/// assert_eq!(types(0), types(1));
+/// ```
+pub fn canonicalize(graph: &MolecularGraph) -> Vec<usize> {
+    let ranks = canonical_ranks(graph);
+    let mut order: Vec<usize> = (0..ranks.len()).collect();
+    order.sort_unstable_by_key(|&atom| ranks[atom]);
+    order
+}
+
+/// Hashes the canonical form of a graph with 128-bit FNV-1a.
+///
+/// The hashed bytes are the atom count, the atomic number of every atom in canonical order, and
+/// the sorted canonical bond list, so the value is stable across runs and platforms. Graphs with
+/// a stated electronic state on any atom also hash every atom's charge and unpaired electrons.
+pub(crate) fn structure_hash(graph: &MolecularGraph) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+    let order = canonicalize(graph);
+    let mut position = vec![0; order.len()];
+    for (new_id, &old_id) in order.iter().enumerate() {
+        position[old_id] = new_id;
+    }
+    let n = order.len();
+    let mut bonds: Vec<(usize, usize, u8)> = graph
+        .bonds
+        .iter()
+        .filter(|bond| bond.atom_ids.0 < n && bond.atom_ids.1 < n)
+        .map(|bond| {
+            let (a, b) = (position[bond.atom_ids.0], position[bond.atom_ids.1]);
+            let order = match bond.order {
+                GraphBondOrder::Single => 1,
+                GraphBondOrder::Double => 2,
+                GraphBondOrder::Triple => 3,
+                GraphBondOrder::Aromatic => 4,
+            };
+            (a.min(b), a.max(b), order)
+        })
+        .collect();
+    bonds.sort_unstable();
+
+    let mut hash = OFFSET_BASIS;
+    let mut feed = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash ^= u128::from(byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    };
+    feed(&(n as u64).to_le_bytes());
+    for &atom in &order {
+        feed(&[graph.atoms[atom].element.atomic_number()]);
+    }
+    feed(&(bonds.len() as u64).to_le_bytes());
+    for (a, b, order) in bonds {
+        feed(&(a as u64).to_le_bytes());
+        feed(&(b as u64).to_le_bytes());
+        feed(&[order]);
+    }
+    if graph
+        .atoms
+        .iter()
+        .any(|atom| atom.formal_charge.is_some() || atom.radical_electrons > 0)
+    {
+        for &atom in &order {
+            let node = &graph.atoms[atom];
+            let charge = node
+                .formal_charge
+                .map_or(0, |charge| charge.to_le_bytes()[0]);
+            feed(&[
+                u8::from(node.formal_charge.is_some()),
+                charge,
+                node.radical_electrons,
+            ]);
+        }
+    }
+    hash
+}
+
+/// Assigns every atom a distinct canonical rank in `0..atoms.len()`.
+fn canonical_ranks(graph: &MolecularGraph) -> Vec<usize> {
+    let n = graph.atoms.len();
+    let mut neighbors: Vec<Vec<(usize, GraphBondOrder)>> = vec![Vec::new(); n];
+    for bond in &graph.bonds {
+        let (a, b) = bond.atom_ids;
+        if a < n && b < n {
+            neighbors[a].push((b, bond.order));
+            neighbors[b].push((a, bond.order));
+        }
+    }
+
+    type Invariant = (Element, usize, Vec<GraphBondOrder>, Option<i8>, u8);
+    let initial: Vec<Invariant> = graph
+        .atoms
+        .iter()
+        .map(|atom| {
+            let mut orders: Vec<_> = neighbors[atom.id].iter().map(|&(_, o)| o).collect();
+            orders.sort_unstable();
+            (
+                atom.element,
+                orders.len(),
+                orders,
+                atom.formal_charge,
+                atom.radical_electrons,
+            )
+        })
+        .collect();
+    let mut ranks = dense_ranks(&initial);
+    refine(&mut ranks, &neighbors);
+
+    loop {
+        let mut counts = vec![0usize; n];
+        for &rank in &ranks {
+            counts[rank] += 1;
+        }
+        let Some(tied_rank) = (0..n).find(|&rank| counts[rank] > 1) else {
+            return ranks;
+        };
+        let chosen = ranks
+            .iter()
+            .position(|&rank| rank == tied_rank)
+            .expect("tied rank is held by an atom");
+        let split: Vec<(usize, bool)> = ranks
+            .iter()
+            .enumerate()
+            .map(|(atom, &rank)| (rank, atom != chosen))
+            .collect();
+        ranks = dense_ranks(&split);
+        refine(&mut ranks, &neighbors);
+    }
+}
+
+/// Splits rank classes by the multiset of neighbor ranks and bond orders until they stabilize.
+fn refine(ranks: &mut Vec<usize>, neighbors: &[Vec<(usize, GraphBondOrder)>]) {
+    let mut classes = class_count(ranks);
+    loop {
+        let keys: Vec<(usize, Vec<(usize, GraphBondOrder)>)> = neighbors
+            .iter()
+            .enumerate()
+            .map(|(atom, adjacent)| {
+                let mut environment: Vec<_> = adjacent
+                    .iter()
+                    .map(|&(neighbor, order)| (ranks[neighbor], order))
+                    .collect();
+                environment.sort_unstable();
+                (ranks[atom], environment)
+            })
+            .collect();
+        let refined = dense_ranks(&keys);
+        let refined_classes = class_count(&refined);
+        *ranks = refined;
+        if refined_classes == classes {
+            return;
+        }
+        classes = refined_classes;
+    }
+}
+
+/// Maps keys to their position among the distinct sorted keys.
+fn dense_ranks<K: Ord>(keys: &[K]) -> Vec<usize> {
+    let mut distinct: Vec<&K> = keys.iter().collect();
+    distinct.sort_unstable();
+    distinct.dedup();
+    keys.iter()
+        .map(|key| {
+            distinct
+                .binary_search(&key)
+                .expect("every key is among the distinct keys")
+        })
+        .collect()
+}
+
+/// Returns the number of distinct ranks.
+fn class_count(ranks: &[usize]) -> usize {
+    ranks.iter().max().map_or(0, |&max| max + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relabeled(graph: &MolecularGraph, new_order: &[usize]) -> MolecularGraph {
+        let mut old_to_new = vec![0; new_order.len()];
+        for (new_id, &old_id) in new_order.iter().enumerate() {
+            old_to_new[old_id] = new_id;
+        }
+        let mut relabeled = MolecularGraph::new();
+        for &old_id in new_order {
+            relabeled.add_atom(graph.atoms[old_id].element);
+        }
+        for bond in graph.bonds.iter().rev() {
+            let (a, b) = bond.atom_ids;
+            relabeled
+                .add_bond(old_to_new[b], old_to_new[a], bond.order)
+                .expect("relabeled bond is valid");
+        }
+        relabeled
+    }
+
+    fn canonical_form(
+        graph: &MolecularGraph,
+    ) -> (Vec<Element>, Vec<(usize, usize, GraphBondOrder)>) {
+        let order = canonicalize(graph);
+        let mut position = vec![0; order.len()];
+        for (new_id, &old_id) in order.iter().enumerate() {
+            position[old_id] = new_id;
+        }
+        let elements = order.iter().map(|&id| graph.atoms[id].element).collect();
+        let mut bonds: Vec<_> = graph
+            .bonds
+            .iter()
+            .map(|bond| {
+                let (a, b) = (position[bond.atom_ids.0], position[bond.atom_ids.1]);
+                (a.min(b), a.max(b), bond.order)
+            })
+            .collect();
+        bonds.sort_unstable();
+        (elements, bonds)
+    }
+
+    fn acetic_acid() -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        let o1 = graph.add_atom(Element::O);
+        let o2 = graph.add_atom(Element::O);
+        graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+        graph.add_bond(c2, o1, GraphBondOrder::Double).unwrap();
+        graph.add_bond(c2, o2, GraphBondOrder::Single).unwrap();
+        let h = graph.add_atom(Element::H);
+        graph.add_bond(o2, h, GraphBondOrder::Single).unwrap();
+        for _ in 0..3 {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(c1, h, GraphBondOrder::Single).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn canonical_form_is_independent_of_input_numbering() {
+        let graph = acetic_acid();
+        let reference = canonical_form(&graph);
+        for new_order in [
+            vec![7, 6, 5, 4, 3, 2, 1, 0],
+            vec![4, 0, 6, 2, 7, 1, 3, 5],
+            vec![3, 5, 1, 7, 0, 2, 4, 6],
+        ] {
+            assert_eq!(canonical_form(&relabeled(&graph, &new_order)), reference);
+        }
+    }
+
+    #[test]
+    fn symmetric_atoms_are_still_given_distinct_positions() {
+        let mut benzene = MolecularGraph::new();
+        for _ in 0..6 {
+            benzene.add_atom(Element::C);
+        }
+        for i in 0..6 {
+            benzene
+                .add_bond(i, (i + 1) % 6, GraphBondOrder::Aromatic)
+                .unwrap();
+        }
+
+        let mut order = canonicalize(&benzene);
+        assert_eq!(
+            canonical_form(&benzene),
+            canonical_form(&relabeled(&benzene, &[3, 1, 5, 0, 4, 2]))
+        );
+        order.sort_unstable();
+        assert_eq!(order, (0..6).collect::<Vec<_>>());
+        assert!(canonicalize(&MolecularGraph::new()).is_empty());
+    }
+
+    #[test]
+    fn structure_hash_ignores_numbering_but_not_structure() {
+        let graph = acetic_acid();
+        let hash = structure_hash(&graph);
+        assert_eq!(
+            structure_hash(&relabeled(&graph, &[4, 0, 6, 2, 7, 1, 3, 5])),
+            hash
+        );
+
+        let mut peroxide_like = graph.clone();
+        peroxide_like.bonds[1].order = GraphBondOrder::Single;
+        assert_ne!(structure_hash(&peroxide_like), hash);
+
+        let mut radical = graph.clone();
+        radical.atoms[0].radical_electrons = 1;
+        assert_ne!(structure_hash(&radical), hash);
+
+        let mut different = graph.clone();
+        different.atoms[3].element = Element::S;
+        assert_ne!(structure_hash(&different), hash);
+        assert_ne!(structure_hash(&MolecularGraph::new()), hash);
+    }
+}
@@ -0,0 +1,471 @@
+//! Memory-lean storage of a typed topology for very large systems.
+//!
+//! A [`MolecularTopology`](crate::MolecularTopology) stores every atom type as a
+//! [`DreidingType`](crate::DreidingType) and every term as its own struct of `usize` IDs, which
+//! adds up for million-atom materials. [`CompactTopology`](crate::CompactTopology) interns the
+//! atom types into a table indexed by `u16` and stores bonds, angles, torsions, and
+//! inversions as flat `u32` index arrays with one-byte classes alongside.
+
+use super::error::CompactTopologyError;
+use super::periodic::ImageFlags;
+use super::properties::{DreidingType, Element, Hybridization, TopologyBondOrder};
+use super::topology::{
+    Angle, AngleClass, Atom, Bond, BondClass, HydrogenBondDonor, Inversion, InversionClass,
+    MolecularTopology, Torsion, TorsionClass, TypeProvenance, count_shared_torsions,
+};
+use crate::compat::collections::HashMap;
+use crate::compat::prelude::*;
+
+/// Flat, interned form of a [`MolecularTopology`].
+///
+/// Term `i` of a kind occupies the slice `[i * n, (i + 1) * n)` of its index array, where `n` is
+/// the number of atoms per term, in the same canonical order as the corresponding tuple of the
+/// full topology.
+///
+/// Bond images are stored only when some bond crosses a cell face. Atom metadata, stereo
+/// descriptors, rings, and virtual sites are not stored; converting back with
+/// [`to_topology`](Self::to_topology) leaves them empty.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+///
+/// let mut graph = MolecularGraph::new();
+/// let c = graph.add_atom(Element::C);
+/// for _ in 0..4 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+/// }
+///
+/// let topology = assign_topology(&graph).unwrap();
+/// let compact = topology.to_compact().unwrap();
+/// assert_eq!(compact.type_table().len(), 2);
+/// assert_eq!(compact.atom_type(1), "H_");
+/// assert_eq!(compact.bond_count(), 4);
+/// assert_eq!(compact.to_topology().bonds, topology.bonds);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompactTopology {
+    /// Distinct atom types, in order of first appearance.
+    type_table: Vec<DreidingType>,
+    /// Index into `type_table` of every atom.
+    atom_types: Vec<u16>,
+    /// Element of every atom.
+    elements: Vec<Element>,
+    /// Hybridization of every atom.
+    hybridizations: Vec<Hybridization>,
+    /// Origin of every atom type.
+    provenance: Vec<TypeProvenance>,
+    /// Connected fragment of every atom.
+    fragment_ids: Vec<u32>,
+    /// Atom pairs of every bond.
+    bonds: Vec<u32>,
+    /// Order of every bond.
+    bond_orders: Vec<TopologyBondOrder>,
+    /// Class of every bond.
+    bond_classes: Vec<BondClass>,
+    /// Lattice translation of every bond's second atom; empty when every bond stays in its cell.
+    bond_images: Vec<ImageFlags>,
+    /// Atom triples of every angle.
+    angles: Vec<u32>,
+    /// Form of every angle.
+    angle_classes: Vec<AngleClass>,
+    /// Atom quadruples of every torsion.
+    torsions: Vec<u32>,
+    /// Case of every torsion.
+    torsion_classes: Vec<TorsionClass>,
+    /// Atom quadruples of every suppressed torsion.
+    suppressed_torsions: Vec<u32>,
+    /// Case of every suppressed torsion.
+    suppressed_torsion_classes: Vec<TorsionClass>,
+    /// Atom quadruples of every inversion.
+    inversions: Vec<u32>,
+    /// Form of every inversion.
+    inversion_classes: Vec<InversionClass>,
+    /// Heavy atom and hydrogen of every hydrogen-bond donor pair.
+    hb_donors: Vec<u32>,
+    /// Hydrogen-bond acceptor atoms.
+    hb_acceptors: Vec<u32>,
+}
+
+impl CompactTopology {
+    /// Returns the number of atoms.
+    pub fn atom_count(&self) -> usize {
+        self.atom_types.len()
+    }
+
+    /// Returns the number of bonds.
+    pub fn bond_count(&self) -> usize {
+        self.bond_orders.len()
+    }
+
+    /// Returns the number of angles.
+    pub fn angle_count(&self) -> usize {
+        self.angle_classes.len()
+    }
+
+    /// Returns the number of torsions, excluding suppressed ones.
+    pub fn torsion_count(&self) -> usize {
+        self.torsion_classes.len()
+    }
+
+    /// Returns the number of inversions.
+    pub fn inversion_count(&self) -> usize {
+        self.inversion_classes.len()
+    }
+
+    /// Returns the distinct atom types that the entries of [`atom_types`](Self::atom_types)
+    /// index into.
+    pub fn type_table(&self) -> &[DreidingType] {
+        &self.type_table
+    }
+
+    /// Returns the type table index of every atom.
+    pub fn atom_types(&self) -> &[u16] {
+        &self.atom_types
+    }
+
+    /// Returns the atom type of one atom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `atom_id` is out of range.
+    pub fn atom_type(&self, atom_id: usize) -> &DreidingType {
+        &self.type_table[usize::from(self.atom_types[atom_id])]
+    }
+
+    /// Returns the element of every atom.
+    pub fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+
+    /// Returns the hybridization of every atom.
+    pub fn hybridizations(&self) -> &[Hybridization] {
+        &self.hybridizations
+    }
+
+    /// Returns the atom pairs of all bonds, two entries per bond.
+    pub fn bonds(&self) -> &[u32] {
+        &self.bonds
+    }
+
+    /// Returns the order of every bond.
+    pub fn bond_orders(&self) -> &[TopologyBondOrder] {
+        &self.bond_orders
+    }
+
+    /// Returns the class of every bond.
+    pub fn bond_classes(&self) -> &[BondClass] {
+        &self.bond_classes
+    }
+
+    /// Returns the lattice translation of every bond's second atom, or an empty slice when no
+    /// bond crosses a cell face.
+    pub fn bond_images(&self) -> &[ImageFlags] {
+        &self.bond_images
+    }
+
+    /// Returns the atom triples of all angles, three entries per angle.
+    pub fn angles(&self) -> &[u32] {
+        &self.angles
+    }
+
+    /// Returns the form of every angle.
+    pub fn angle_classes(&self) -> &[AngleClass] {
+        &self.angle_classes
+    }
+
+    /// Returns the atom quadruples of all torsions, four entries per torsion.
+    pub fn torsions(&self) -> &[u32] {
+        &self.torsions
+    }
+
+    /// Returns the case of every torsion.
+    pub fn torsion_classes(&self) -> &[TorsionClass] {
+        &self.torsion_classes
+    }
+
+    /// Returns the atom quadruples of all inversions, four entries per inversion.
+    pub fn inversions(&self) -> &[u32] {
+        &self.inversions
+    }
+
+    /// Returns the form of every inversion.
+    pub fn inversion_classes(&self) -> &[InversionClass] {
+        &self.inversion_classes
+    }
+
+    /// Expands the compact form back into a full topology.
+    ///
+    /// Atom metadata, chirality, bond stereo, rings, and virtual sites are not stored in the
+    /// compact form and come back empty; every other field, bond images included, matches the
+    /// topology the compact form was built from.
+    ///
+    /// # Returns
+    ///
+    /// A [`MolecularTopology`] with one atom per entry of [`atom_types`](Self::atom_types).
+    pub fn to_topology(&self) -> MolecularTopology {
+        let atoms = (0..self.atom_count())
+            .map(|id| Atom {
+                id,
+                element: self.elements[id],
+                atom_type: self.atom_type(id).clone(),
+                hybridization: self.hybridizations[id],
+                provenance: self.provenance[id],
+                fragment_id: self.fragment_ids[id] as usize,
+                chirality: None,
+                metadata: Default::default(),
+            })
+            .collect();
+
+        let torsions = |flat: &[u32], classes: &[TorsionClass]| {
+            let mut torsions: Vec<Torsion> = flat
+                .chunks_exact(4)
+                .zip(classes)
+                .map(|(chunk, &class)| Torsion {
+                    atom_ids: quadruple(chunk),
+                    class,
+                    shared_count: 1,
+                })
+                .collect();
+            count_shared_torsions(&mut torsions);
+            torsions
+        };
+
+        MolecularTopology {
+            atoms,
+            bonds: self
+                .bonds
+                .chunks_exact(2)
+                .zip(self.bond_orders.iter().zip(&self.bond_classes))
+                .enumerate()
+                .map(|(id, (chunk, (&order, &class)))| Bond {
+                    atom_ids: (chunk[0] as usize, chunk[1] as usize),
+                    order,
+                    stereo: None,
+                    image: self.bond_images.get(id).copied().unwrap_or_default(),
+                    class,
+                })
+                .collect(),
+            angles: self
+                .angles
+                .chunks_exact(3)
+                .zip(&self.angle_classes)
+                .map(|(chunk, &class)| Angle {
+                    atom_ids: (chunk[0] as usize, chunk[1] as usize, chunk[2] as usize),
+                    class,
+                })
+                .collect(),
+            torsions: torsions(&self.torsions, &self.torsion_classes),
+            suppressed_torsions: torsions(
+                &self.suppressed_torsions,
+                &self.suppressed_torsion_classes,
+            ),
+            inversions: self
+                .inversions
+                .chunks_exact(4)
+                .zip(&self.inversion_classes)
+                .map(|(chunk, &class)| Inversion {
+                    atom_ids: quadruple(chunk),
+                    class,
+                })
+                .collect(),
+            hb_donors: self
+                .hb_donors
+                .chunks_exact(2)
+                .map(|chunk| HydrogenBondDonor {
+                    donor_id: chunk[0] as usize,
+                    hydrogen_id: chunk[1] as usize,
+                })
+                .collect(),
+            hb_acceptors: self.hb_acceptors.iter().map(|&id| id as usize).collect(),
+            rings: Vec::new(),
+            virtual_sites: Vec::new(),
+        }
+    }
+}
+
+/// Reads the four atom IDs of a torsion or inversion chunk.
+fn quadruple(chunk: &[u32]) -> (usize, usize, usize, usize) {
+    (
+        chunk[0] as usize,
+        chunk[1] as usize,
+        chunk[2] as usize,
+        chunk[3] as usize,
+    )
+}
+
+impl MolecularTopology {
+    /// Converts the topology into its memory-lean [`CompactTopology`] form.
+    ///
+    /// Term order and canonical atom order within each term are preserved.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CompactTopologyError`] if the topology has more atoms than fit in a `u32` or
+    /// more distinct atom types than fit in a `u16`.
+    pub fn to_compact(&self) -> Result<CompactTopology, CompactTopologyError> {
+        let count = self.atoms.len();
+        if u32::try_from(count).is_err() {
+            return Err(CompactTopologyError::TooManyAtoms { count });
+        }
+        let index = |id: usize| id as u32;
+
+        let mut type_table = Vec::new();
+        let mut type_ids: HashMap<&DreidingType, u16> = HashMap::new();
+        let mut atom_types = Vec::with_capacity(count);
+        for atom in &self.atoms {
+            let id = match type_ids.get(&atom.atom_type) {
+                Some(&id) => id,
+                None => {
+                    let id = u16::try_from(type_table.len()).map_err(|_| {
+                        CompactTopologyError::TooManyTypes {
+                            count: type_table.len() + 1,
+                        }
+                    })?;
+                    type_ids.insert(&atom.atom_type, id);
+                    type_table.push(atom.atom_type.clone());
+                    id
+                }
+            };
+            atom_types.push(id);
+        }
+
+        let flatten_torsions = |torsions: &[Torsion]| {
+            let flat = torsions
+                .iter()
+                .flat_map(|t| {
+                    let (i, j, k, l) = t.atom_ids;
+                    [index(i), index(j), index(k), index(l)]
+                })
+                .collect();
+            (flat, torsions.iter().map(|t| t.class).collect())
+        };
+        let (torsions, torsion_classes) = flatten_torsions(&self.torsions);
+        let (suppressed_torsions, suppressed_torsion_classes) =
+            flatten_torsions(&self.suppressed_torsions);
+
+        Ok(CompactTopology {
+            type_table,
+            atom_types,
+            elements: self.atoms.iter().map(|atom| atom.element).collect(),
+            hybridizations: self.atoms.iter().map(|atom| atom.hybridization).collect(),
+            provenance: self.atoms.iter().map(|atom| atom.provenance).collect(),
+            fragment_ids: self
+                .atoms
+                .iter()
+                .map(|atom| index(atom.fragment_id))
+                .collect(),
+            bonds: self
+                .bonds
+                .iter()
+                .flat_map(|bond| [index(bond.atom_ids.0), index(bond.atom_ids.1)])
+                .collect(),
+            bond_orders: self.bonds.iter().map(|bond| bond.order).collect(),
+            bond_classes: self.bonds.iter().map(|bond| bond.class).collect(),
+            bond_images: if self.bonds.iter().any(|bond| bond.image != [0; 3]) {
+                self.bonds.iter().map(|bond| bond.image).collect()
+            } else {
+                Vec::new()
+            },
+            angles: self
+                .angles
+                .iter()
+                .flat_map(|angle| {
+                    let (a, center, b) = angle.atom_ids;
+                    [index(a), index(center), index(b)]
+                })
+                .collect(),
+            angle_classes: self.angles.iter().map(|angle| angle.class).collect(),
+            torsions,
+            torsion_classes,
+            suppressed_torsions,
+            suppressed_torsion_classes,
+            inversions: self
+                .inversions
+                .iter()
+                .flat_map(|inversion| {
+                    let (center, axis, p1, p2) = inversion.atom_ids;
+                    [index(center), index(axis), index(p1), index(p2)]
+                })
+                .collect(),
+            inversion_classes: self
+                .inversions
+                .iter()
+                .map(|inversion| inversion.class)
+                .collect(),
+            hb_donors: self
+                .hb_donors
+                .iter()
+                .flat_map(|donor| [index(donor.donor_id), index(donor.hydrogen_id)])
+                .collect(),
+            hb_acceptors: self.hb_acceptors.iter().map(|&id| index(id)).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assign_topology;
+    use crate::io::smiles::parse_smiles;
+
+    fn typed(smiles: &str) -> MolecularTopology {
+        assign_topology(&parse_smiles(smiles).expect("SMILES should parse"))
+            .expect("molecule should type")
+    }
+
+    #[test]
+    fn round_trip_preserves_every_stored_field() {
+        let topology = typed("OC(=O)c1ccccc1.[Na+].[Cl-]");
+        let compact = topology.to_compact().expect("topology fits");
+
+        assert_eq!(compact.atom_count(), topology.atoms.len());
+        assert_eq!(compact.bonds().len(), 2 * topology.bonds.len());
+        assert_eq!(compact.angles().len(), 3 * compact.angle_count());
+        assert_eq!(compact.torsions().len(), 4 * compact.torsion_count());
+        assert_eq!(compact.inversions().len(), 4 * compact.inversion_count());
+
+        let restored = compact.to_topology();
+        assert_eq!(
+            MolecularTopology {
+                rings: Vec::new(),
+                ..topology
+            },
+            restored
+        );
+    }
+
+    #[test]
+    fn bond_images_survive_the_round_trip() {
+        let mut topology = typed("CCO");
+        assert!(topology.to_compact().unwrap().bond_images().is_empty());
+
+        topology.bonds[0].image = [1, 0, -1];
+        let compact = topology.to_compact().expect("topology fits");
+        assert_eq!(compact.bond_images().len(), topology.bonds.len());
+        assert_eq!(compact.to_topology().bonds, topology.bonds);
+    }
+
+    #[test]
+    fn atom_types_are_interned_once() {
+        let topology = typed("CCCCCCCCCC");
+        let compact = topology.to_compact().expect("topology fits");
+
+        assert_eq!(compact.type_table(), ["C_3", "H_"]);
+        assert!(compact.atom_types().iter().all(|&id| id < 2));
+        for (id, atom) in topology.atoms.iter().enumerate() {
+            assert_eq!(compact.atom_type(id), &atom.atom_type);
+        }
+    }
+
+    #[test]
+    fn empty_topology_round_trips() {
+        let compact = MolecularTopology::default()
+            .to_compact()
+            .expect("empty topology fits");
+        assert_eq!(compact, CompactTopology::default());
+        assert_eq!(compact.to_topology(), MolecularTopology::default());
+    }
+}
@@ -0,0 +1,378 @@
+//! Structured comparison of two typed topologies of the same molecule.
+//!
+//! When a rule deck is edited, the interesting question is what changed: which atoms received a
+//! different type, and which bonds, angles, torsions, or inversions appeared or disappeared as a
+//! consequence. [`MolecularTopology::diff`](crate::MolecularTopology::diff) answers it by
+//! matching atoms by ID and terms by their canonical atom tuples, so term order within the lists
+//! does not matter.
+
+use super::properties::{DreidingType, Element, Hybridization, TopologyBondOrder};
+use super::topology::{Angle, Bond, Inversion, MolecularTopology, Torsion};
+use crate::compat::prelude::*;
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt;
+
+/// An atom whose type or hybridization differs between two topologies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtomChange {
+    /// ID of the atom in both topologies.
+    pub atom_id: usize,
+    /// Element of the atom in the first topology.
+    pub element: Element,
+    /// Atom type in the first topology.
+    pub before_type: DreidingType,
+    /// Atom type in the second topology.
+    pub after_type: DreidingType,
+    /// Hybridization in the first topology.
+    pub before_hybridization: Hybridization,
+    /// Hybridization in the second topology.
+    pub after_hybridization: Hybridization,
+}
+
+/// A bond present in both topologies with a different order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BondOrderChange {
+    /// Sorted IDs of the bonded atoms.
+    pub atom_ids: (usize, usize),
+    /// Order in the first topology.
+    pub before: TopologyBondOrder,
+    /// Order in the second topology.
+    pub after: TopologyBondOrder,
+}
+
+/// Differences between two topologies, as produced by [`MolecularTopology::diff`].
+///
+/// "Added" entries exist only in the second topology and "removed" entries only in the first.
+/// Every list is sorted by atom IDs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TopologyDiff {
+    /// Atoms whose type or hybridization changed.
+    pub atom_changes: Vec<AtomChange>,
+    /// IDs of atoms present only in the second topology.
+    pub added_atoms: Vec<usize>,
+    /// IDs of atoms present only in the first topology.
+    pub removed_atoms: Vec<usize>,
+    /// Bonds whose order changed.
+    pub bond_order_changes: Vec<BondOrderChange>,
+    /// Bonds present only in the second topology.
+    pub added_bonds: Vec<Bond>,
+    /// Bonds present only in the first topology.
+    pub removed_bonds: Vec<Bond>,
+    /// Angles present only in the second topology.
+    pub added_angles: Vec<Angle>,
+    /// Angles present only in the first topology.
+    pub removed_angles: Vec<Angle>,
+    /// Torsions present only in the second topology.
+    pub added_torsions: Vec<Torsion>,
+    /// Torsions present only in the first topology.
+    pub removed_torsions: Vec<Torsion>,
+    /// Inversions present only in the second topology.
+    pub added_inversions: Vec<Inversion>,
+    /// Inversions present only in the first topology.
+    pub removed_inversions: Vec<Inversion>,
+}
+
+impl TopologyDiff {
+    /// Returns `true` when the two topologies are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.atom_changes.is_empty()
+            && self.added_atoms.is_empty()
+            && self.removed_atoms.is_empty()
+            && self.bond_order_changes.is_empty()
+            && self.added_bonds.is_empty()
+            && self.removed_bonds.is_empty()
+            && self.added_angles.is_empty()
+            && self.removed_angles.is_empty()
+            && self.added_torsions.is_empty()
+            && self.removed_torsions.is_empty()
+            && self.added_inversions.is_empty()
+            && self.removed_inversions.is_empty()
+    }
+}
+
+impl fmt::Display for TopologyDiff {
+    /// Writes one line per difference, e.g. `atom 3 (C): C_3 -> C_R` or `+ torsion 0-1-2-3`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.atom_changes {
+            write!(f, "atom {} ({}):", change.atom_id, change.element)?;
+            if change.before_type != change.after_type {
+                write!(f, " {} -> {}", change.before_type, change.after_type)?;
+            }
+            if change.before_hybridization != change.after_hybridization {
+                write!(
+                    f,
+                    " [{} -> {}]",
+                    change.before_hybridization, change.after_hybridization
+                )?;
+            }
+            writeln!(f)?;
+        }
+        for id in &self.removed_atoms {
+            writeln!(f, "- atom {id}")?;
+        }
+        for id in &self.added_atoms {
+            writeln!(f, "+ atom {id}")?;
+        }
+        for change in &self.bond_order_changes {
+            let (a, b) = change.atom_ids;
+            writeln!(f, "bond {a}-{b}: {} -> {}", change.before, change.after)?;
+        }
+        for (sign, bonds) in [('-', &self.removed_bonds), ('+', &self.added_bonds)] {
+            for bond in bonds {
+                let (a, b) = bond.atom_ids;
+                writeln!(f, "{sign} bond {a}-{b} ({})", bond.order)?;
+            }
+        }
+        for (sign, angles) in [('-', &self.removed_angles), ('+', &self.added_angles)] {
+            for angle in angles {
+                let (a, b, c) = angle.atom_ids;
+                writeln!(f, "{sign} angle {a}-{b}-{c}")?;
+            }
+        }
+        for (sign, torsions) in [('-', &self.removed_torsions), ('+', &self.added_torsions)] {
+            for torsion in torsions {
+                let (a, b, c, d) = torsion.atom_ids;
+                writeln!(f, "{sign} torsion {a}-{b}-{c}-{d}")?;
+            }
+        }
+        for (sign, inversions) in [
+            ('-', &self.removed_inversions),
+            ('+', &self.added_inversions),
+        ] {
+            for inversion in inversions {
+                let (a, b, c, d) = inversion.atom_ids;
+                writeln!(f, "{sign} inversion {a}-{b}-{c}-{d}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MolecularTopology {
+    /// Compares this topology with another typing of the same molecule.
+    ///
+    /// Atoms are matched by ID, so both topologies must use the same atom numbering, as they do
+    /// when the same graph is typed twice. Terms are matched by their canonical atom tuples.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Topology to compare against, typically produced after a rule edit.
+    ///
+    /// # Returns
+    ///
+    /// A [`TopologyDiff`] describing how `other` differs from `self`; it is empty when the two
+    /// are equivalent up to the order of their term lists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::rules::RulesetBuilder;
+    /// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, Typer};
+    ///
+    /// let mut graph = MolecularGraph::new();
+    /// let o = graph.add_atom(Element::O);
+    /// for _ in 0..2 {
+    ///     let h = graph.add_atom(Element::H);
+    ///     graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+    /// }
+    ///
+    /// let before = Typer::new().assign_topology(&graph).unwrap();
+    /// let rules = RulesetBuilder::with_defaults()
+    ///     .with_toml(r#"
+    ///         [[rule]]
+    ///         name = "Water_O"
+    ///         priority = 1000
+    ///         type = "O_W"
+    ///         conditions = { element = "O" }
+    ///     "#)
+    ///     .unwrap()
+    ///     .build();
+    /// let after = Typer::with_rule_set(rules).assign_topology(&graph).unwrap();
+    ///
+    /// let diff = before.diff(&after);
+    /// assert_eq!(diff.atom_changes.len(), 1);
+    /// assert_eq!(diff.atom_changes[0].after_type, "O_W");
+    /// assert!(diff.added_angles.is_empty());
+    /// assert_eq!(diff.to_string(), "atom 0 (O): O_3 -> O_W\n");
+    /// ```
+    pub fn diff(&self, other: &MolecularTopology) -> TopologyDiff {
+        let mut diff = TopologyDiff::default();
+
+        let common = self.atoms.len().min(other.atoms.len());
+        for (before, after) in self.atoms.iter().zip(&other.atoms) {
+            if before.atom_type != after.atom_type || before.hybridization != after.hybridization {
+                diff.atom_changes.push(AtomChange {
+                    atom_id: before.id,
+                    element: before.element,
+                    before_type: before.atom_type.clone(),
+                    after_type: after.atom_type.clone(),
+                    before_hybridization: before.hybridization,
+                    after_hybridization: after.hybridization,
+                });
+            }
+        }
+        diff.removed_atoms = self.atoms[common..].iter().map(|atom| atom.id).collect();
+        diff.added_atoms = other.atoms[common..].iter().map(|atom| atom.id).collect();
+
+        let bond_orders =
+            |topology: &MolecularTopology| -> BTreeMap<(usize, usize), TopologyBondOrder> {
+                topology
+                    .bonds
+                    .iter()
+                    .map(|bond| (bond.atom_ids, bond.order))
+                    .collect()
+            };
+        let (before_bonds, after_bonds) = (bond_orders(self), bond_orders(other));
+        for (&atom_ids, &before) in &before_bonds {
+            match after_bonds.get(&atom_ids) {
+                Some(&after) if after != before => {
+                    diff.bond_order_changes.push(BondOrderChange {
+                        atom_ids,
+                        before,
+                        after,
+                    });
+                }
+                Some(_) => {}
+                None => diff
+                    .removed_bonds
+                    .push(Bond::new(atom_ids.0, atom_ids.1, before)),
+            }
+        }
+        diff.added_bonds = after_bonds
+            .iter()
+            .filter(|(atom_ids, _)| !before_bonds.contains_key(atom_ids))
+            .map(|(&(a, b), &order)| Bond::new(a, b, order))
+            .collect();
+
+        (diff.removed_angles, diff.added_angles) =
+            term_difference(&self.angles, &other.angles, |angle| angle.atom_ids);
+        (diff.removed_torsions, diff.added_torsions) =
+            term_difference(&self.torsions, &other.torsions, |torsion| torsion.atom_ids);
+        (diff.removed_inversions, diff.added_inversions) =
+            term_difference(&self.inversions, &other.inversions, |inversion| {
+                inversion.atom_ids
+            });
+
+        diff
+    }
+}
+
+/// Splits two term lists into the terms only in `before` and those only in `after`.
+fn term_difference<T, K>(before: &[T], after: &[T], key: impl Fn(&T) -> K) -> (Vec<T>, Vec<T>)
+where
+    T: Clone,
+    K: Ord,
+{
+    let before_keys: BTreeSet<K> = before.iter().map(&key).collect();
+    let after_keys: BTreeSet<K> = after.iter().map(&key).collect();
+    let only = |terms: &[T], others: &BTreeSet<K>| {
+        let mut only: Vec<T> = terms
+            .iter()
+            .filter(|term| !others.contains(&key(term)))
+            .cloned()
+            .collect();
+        only.sort_by_key(|term| key(term));
+        only
+    };
+    (only(before, &after_keys), only(after, &before_keys))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::topology::{AngleClass, Atom, InversionClass};
+
+    fn atom(id: usize, element: Element, atom_type: &str) -> Atom {
+        Atom {
+            id,
+            element,
+            atom_type: atom_type.into(),
+            hybridization: Hybridization::SP3,
+            provenance: Default::default(),
+            fragment_id: 0,
+            chirality: None,
+            metadata: Default::default(),
+        }
+    }
+
+    fn propane_like() -> MolecularTopology {
+        MolecularTopology {
+            atoms: vec![
+                atom(0, Element::C, "C_3"),
+                atom(1, Element::C, "C_3"),
+                atom(2, Element::C, "C_3"),
+            ],
+            bonds: vec![
+                Bond::new(0, 1, TopologyBondOrder::Single),
+                Bond::new(1, 2, TopologyBondOrder::Single),
+            ],
+            angles: vec![Angle::new(0, 1, 2, AngleClass::Tetrahedral)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn identical_topologies_in_different_term_order_have_empty_diff() {
+        let before = propane_like();
+        let mut after = propane_like();
+        after.bonds.reverse();
+        let diff = before.diff(&after);
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "");
+    }
+
+    #[test]
+    fn reports_type_bond_and_term_changes() {
+        let before = propane_like();
+        let mut after = propane_like();
+        after.atoms[1].atom_type = DreidingType::C_2;
+        after.atoms[1].hybridization = Hybridization::SP2;
+        after.atoms.push(atom(3, Element::O, "O_2"));
+        after.bonds[0].order = TopologyBondOrder::Double;
+        after.bonds.push(Bond::new(3, 1, TopologyBondOrder::Single));
+        after.angles = vec![
+            Angle::new(2, 1, 3, AngleClass::Tetrahedral),
+            Angle::new(0, 1, 3, AngleClass::Tetrahedral),
+        ];
+        after
+            .inversions
+            .push(Inversion::new(1, 3, 2, 0, InversionClass::Planar));
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.atom_changes.len(), 1);
+        assert_eq!(diff.atom_changes[0].before_type, "C_3");
+        assert_eq!(diff.added_atoms, vec![3]);
+        assert_eq!(
+            diff.bond_order_changes,
+            vec![BondOrderChange {
+                atom_ids: (0, 1),
+                before: TopologyBondOrder::Single,
+                after: TopologyBondOrder::Double,
+            }]
+        );
+        assert_eq!(
+            diff.added_bonds,
+            vec![Bond::new(1, 3, TopologyBondOrder::Single)]
+        );
+        assert_eq!(
+            diff.removed_angles,
+            vec![Angle::new(0, 1, 2, AngleClass::Tetrahedral)]
+        );
+        assert_eq!(
+            diff.added_angles,
+            vec![
+                Angle::new(0, 1, 3, AngleClass::Tetrahedral),
+                Angle::new(2, 1, 3, AngleClass::Tetrahedral)
+            ]
+        );
+        assert_eq!(diff.added_inversions.len(), 1);
+
+        let reversed = after.diff(&before);
+        assert_eq!(reversed.removed_atoms, vec![3]);
+        assert_eq!(reversed.removed_bonds.len(), 1);
+        assert_eq!(
+            diff.to_string().lines().next(),
+            Some("atom 1 (C): C_3 -> C_2 [SP3 -> SP2]")
+        );
+    }
+}
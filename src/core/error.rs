@@ -3,6 +3,11 @@
 //! These enums aggregate lower-level issues so that library consumers can bubble up a single
 //! `TyperError` while still inspecting fine-grained context when needed.
 
+use crate::compat::prelude::*;
+use crate::core::metadata::ResidueId;
+use crate::core::properties::Element;
+use crate::pipeline::Stage;
+use core::fmt;
 use thiserror::Error;
 
 /// Root error emitted by every fallible operation in the typing pipeline.
@@ -24,6 +29,8 @@ pub enum TyperError {
     PerceptionFailed {
         /// Name of the perception step (e.g., "aromaticity" or "hybridization").
         step: String,
+        /// Pipeline stage the step reports as; custom steps report as the stage they extend.
+        stage: Stage,
         /// Root perception error that triggered the failure.
         #[source]
         source: PerceptionError,
@@ -32,6 +39,66 @@ pub enum TyperError {
     /// The typing engine exhausted its rounds before assigning all atom types.
     #[error("atom typing failed")]
     AssignmentFailed(#[from] AssignmentError),
+
+    /// The configured output label map does not cover every assigned type.
+    #[error("output label remapping failed")]
+    LabelMapping(#[from] UnmappedTypesError),
+
+    /// A configured resource limit stopped the run.
+    #[error("resource limit exceeded")]
+    LimitExceeded(#[from] LimitExceeded),
+
+    /// A caller-supplied type override does not address any atom of the graph.
+    #[error("invalid type override")]
+    InvalidOverride(#[from] OverrideError),
+}
+
+impl TyperError {
+    /// Returns the IDs of the input atoms this error refers to, or an empty list if it names
+    /// none (for example, a rule parse failure).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{Element, MolecularGraph, Typer};
+    ///
+    /// let mut graph = MolecularGraph::new();
+    /// graph.add_atom(Element::C);
+    /// graph.add_atom(Element::La);
+    ///
+    /// let error = Typer::new().assign_topology(&graph).unwrap_err();
+    /// assert_eq!(error.atom_ids(), vec![1]);
+    /// ```
+    pub fn atom_ids(&self) -> Vec<usize> {
+        match self {
+            Self::InvalidInput(
+                GraphValidationError::MissingAtom { atom_id }
+                | GraphValidationError::SelfBondingAtom { atom_id },
+            ) => vec![*atom_id],
+            Self::PerceptionFailed { source, .. } => source.atom_ids(),
+            Self::AssignmentFailed(error) => error.untyped_atom_ids.clone(),
+            Self::LimitExceeded(LimitExceeded::TypingRounds { unsettled, .. }) => {
+                unsettled.iter().map(|atom| atom.atom_id).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Rewrites every atom ID carried by perception and assignment failures through `map`.
+    ///
+    /// Used to translate the IDs of a typed fragment back to the graph it was cut from.
+    pub(crate) fn map_atom_ids(&mut self, map: &dyn Fn(usize) -> usize) {
+        match self {
+            Self::PerceptionFailed { source, .. } => source.map_atom_ids(map),
+            Self::AssignmentFailed(error) => error.map_atom_ids(map),
+            Self::LimitExceeded(LimitExceeded::TypingRounds { unsettled, .. }) => {
+                for atom in unsettled {
+                    atom.atom_id = map(atom.atom_id);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 /// Errors that describe structural or logical issues with the input `MolecularGraph`.
@@ -53,6 +120,22 @@ pub enum GraphValidationError {
         /// Identifier of the atom that incorrectly lists a self-bond.
         atom_id: usize,
     },
+
+    /// An edit references a bond identifier that is missing from the graph.
+    #[error("no bond with ID {bond_id} exists")]
+    MissingBond {
+        /// Identifier of the bond that could not be found.
+        bond_id: usize,
+    },
+
+    /// The coordinate list does not have exactly one entry per atom.
+    #[error("graph has {atoms} atoms but {positions} positions")]
+    PositionCountMismatch {
+        /// Number of atoms in the graph.
+        atoms: usize,
+        /// Number of coordinates supplied.
+        positions: usize,
+    },
 }
 
 /// Errors raised while running the staged chemical perception pipeline.
@@ -66,31 +149,1003 @@ pub enum PerceptionError {
     KekulizationFailed {
         /// Human-readable reason supplied by the Kekulé resolver.
         message: String,
+        /// IDs of the atoms of the offending aromatic bond or system, sorted ascending.
+        atom_ids: Vec<usize>,
     },
 
     /// Hybridization inference could not determine an sp/sp2/sp3 class for an atom.
-    #[error(
-        "could not infer hybridization for atom ID {atom_id}: unhandled steric number or chemical environment"
-    )]
+    #[error("could not infer hybridization for {atom}: unhandled steric number {steric_number}")]
     HybridizationInference {
-        /// Identifier of the atom whose steric number could not be mapped.
-        atom_id: usize,
+        /// Atom whose steric number could not be mapped.
+        atom: AtomContext,
+        /// Covalent degree plus lone pairs of the atom.
+        steric_number: u8,
     },
 
+    /// A bonded atom's element has no valence electron count to balance its bonds against.
+    #[error("valence electrons not defined for {atom}")]
+    MissingValence {
+        /// Atom whose electrons could not be counted.
+        atom: AtomContext,
+    },
+
+    /// The perception stage hit a configured resource limit.
+    #[error("perception aborted by a resource limit")]
+    LimitExceeded(#[from] LimitExceeded),
+
     /// Catch-all variant for perception failures that do not fit the other buckets.
     #[error("an unexpected perception error occurred: {0}")]
     Other(String),
 }
 
+impl PerceptionError {
+    /// Returns the IDs of the atoms this error refers to, or an empty list if it names none.
+    pub fn atom_ids(&self) -> Vec<usize> {
+        match self {
+            Self::KekulizationFailed { atom_ids, .. } => atom_ids.clone(),
+            Self::HybridizationInference { atom, .. } | Self::MissingValence { atom } => {
+                vec![atom.atom_id]
+            }
+            Self::LimitExceeded(_) | Self::Other(_) => Vec::new(),
+        }
+    }
+
+    /// Rewrites every atom ID this error refers to through `map`.
+    pub(crate) fn map_atom_ids(&mut self, map: &dyn Fn(usize) -> usize) {
+        match self {
+            Self::KekulizationFailed { atom_ids, .. } => {
+                for id in atom_ids.iter_mut() {
+                    *id = map(*id);
+                }
+                atom_ids.sort_unstable();
+            }
+            Self::HybridizationInference { atom, .. } | Self::MissingValence { atom } => {
+                atom.atom_id = map(atom.atom_id);
+            }
+            Self::LimitExceeded(_) | Self::Other(_) => {}
+        }
+    }
+}
+
+/// Chemical context of an atom named by an error.
+///
+/// Carries enough of the atom's environment to route a failure back to the atom (for example,
+/// to highlight it in a structure editor) and to describe it without the original graph.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AtomContext {
+    /// ID of the atom in the input graph.
+    pub atom_id: usize,
+    /// Element of the atom.
+    pub element: Element,
+    /// Elements of the bonded neighbors, sorted by atomic number.
+    pub neighbors: Vec<Element>,
+}
+
+impl fmt::Display for AtomContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "atom {} ({}", self.atom_id, self.element)?;
+        if self.neighbors.is_empty() {
+            return f.write_str(", unbonded)");
+        }
+        f.write_str(" bonded to ")?;
+        for (i, neighbor) in self.neighbors.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{neighbor}")?;
+        }
+        f.write_str(")")
+    }
+}
+
 /// Error reported when the typing engine stalls before all atoms receive types.
 ///
 /// This typically indicates that the ruleset lacks coverage for the perceived environments or that
 /// earlier perception output was incomplete.
 #[derive(Debug, Error)]
-#[error("engine stalled after {rounds_completed} rounds with {untyped_atom_ids:?} still untyped")]
+#[error(
+    "engine stalled after {rounds_completed} rounds with {} atoms still untyped: {}",
+    untyped_atoms.len(),
+    list_atoms(untyped_atoms)
+)]
 pub struct AssignmentError {
     /// Unique identifiers of atoms that never converged to a final type.
     pub untyped_atom_ids: Vec<usize>,
+    /// Context of every untyped atom, in the order of `untyped_atom_ids`.
+    pub untyped_atoms: Vec<AtomContext>,
     /// Total number of engine rounds completed before stalling.
     pub rounds_completed: u32,
 }
+
+impl AssignmentError {
+    /// Rewrites every atom ID this error refers to through `map`.
+    pub(crate) fn map_atom_ids(&mut self, map: &dyn Fn(usize) -> usize) {
+        for id in &mut self.untyped_atom_ids {
+            *id = map(*id);
+        }
+        for atom in &mut self.untyped_atoms {
+            atom.atom_id = map(atom.atom_id);
+        }
+    }
+}
+
+/// Joins atom contexts into a semicolon-separated list for error messages.
+fn list_atoms(atoms: &[AtomContext]) -> String {
+    atoms
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Error returned when a [`RepeatUnit`](crate::polymer::RepeatUnit) is defined inconsistently.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PolymerError {
+    /// A head or tail attachment atom is not an atom of the repeat unit.
+    #[error("attachment atom {atom_id} is not one of the {atom_count} atoms of the repeat unit")]
+    AtomOutOfRange {
+        /// Offending atom ID.
+        atom_id: usize,
+        /// Number of atoms in the repeat unit.
+        atom_count: usize,
+    },
+}
+
+/// Error returned when [`TypingOverrides`](crate::TypingOverrides) cannot be applied to a graph.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum OverrideError {
+    /// An override names an atom ID the graph does not have.
+    #[error("override references atom ID {atom_id} but the graph has {atom_count} atoms")]
+    AtomOutOfRange {
+        /// Offending atom ID.
+        atom_id: usize,
+        /// Number of atoms in the graph.
+        atom_count: usize,
+    },
+
+    /// A name override matches no atom's metadata.
+    #[error("no atom named '{atom_name}'{} to override", residue.as_ref().map(|r| format!(" in residue {r}")).unwrap_or_default())]
+    UnmatchedName {
+        /// Atom name of the override.
+        atom_name: String,
+        /// Residue the override was restricted to, if any.
+        residue: Option<ResidueId>,
+    },
+}
+
+/// Error returned when a [`SelectionQuery`](crate::SelectionQuery) cannot be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SelectionError {
+    /// A distance test is anchored at an atom ID the structure does not have.
+    #[error(
+        "selection query references atom ID {atom_id} but the structure has {atom_count} atoms"
+    )]
+    AtomOutOfRange {
+        /// Offending atom ID.
+        atom_id: usize,
+        /// Number of atoms in the structure.
+        atom_count: usize,
+    },
+
+    /// A query testing atom types was evaluated against an untyped graph.
+    #[error("selection query tests atom types, which an untyped graph does not carry")]
+    TypesUnavailable,
+}
+
+/// Error returned when an output label map lacks entries for some assigned atom types.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("no output label configured for atom types {missing:?}")]
+pub struct UnmappedTypesError {
+    /// Canonical types without a label, sorted and deduplicated.
+    pub missing: Vec<String>,
+}
+
+/// A configured [`ResourceLimits`](crate::core::limits::ResourceLimits) bound that was reached, or
+/// a cancellation request that stopped the run.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LimitExceeded {
+    /// The Kekulé solver took more backtracking steps than allowed.
+    #[error("Kekulé search exceeded {limit} backtracking steps")]
+    KekuleSteps {
+        /// Configured step limit.
+        limit: u64,
+    },
+
+    /// Ring search produced more cycle candidates than allowed.
+    #[error("ring search exceeded {limit} cycle candidates")]
+    RingCandidates {
+        /// Configured candidate limit.
+        limit: usize,
+    },
+
+    /// The typing engine was still changing assignments when the round cap was reached.
+    #[error(
+        "typing did not converge within {limit} rounds; atoms still changing: {}",
+        list_unsettled(unsettled)
+    )]
+    TypingRounds {
+        /// Configured round limit.
+        limit: u32,
+        /// Atoms whose type changed in the last round, with the types they held so far.
+        unsettled: Vec<UnsettledAtom>,
+    },
+
+    /// The wall-clock budget for the run was used up.
+    #[error("wall-clock budget of {budget:?} exhausted")]
+    TimeBudget {
+        /// Configured budget.
+        budget: core::time::Duration,
+    },
+
+    /// The run was stopped through its [`CancellationToken`](crate::CancellationToken).
+    #[error("typing was cancelled")]
+    Cancelled,
+}
+
+/// An atom the typing engine was still retyping when it ran out of rounds.
+///
+/// Atoms only ever move to a higher-ranked rule, so the engine cannot cycle; an atom that is
+/// still changing after the round cap sits at the end of a long chain of `neighbor_types`
+/// dependencies. Its history shows which rules took part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsettledAtom {
+    /// ID of the atom in the input graph.
+    pub atom_id: usize,
+    /// Every type the atom received, oldest first.
+    pub types: Vec<String>,
+    /// Name of the rule that assigned each entry of `types`.
+    pub rules: Vec<String>,
+}
+
+impl fmt::Display for UnsettledAtom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "atom {}: ", self.atom_id)?;
+        for (i, (type_name, rule)) in self.types.iter().zip(&self.rules).enumerate() {
+            if i > 0 {
+                f.write_str(" -> ")?;
+            }
+            write!(f, "{type_name} ({rule})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Joins unsettled atoms into a semicolon-separated list for error messages.
+fn list_unsettled(atoms: &[UnsettledAtom]) -> String {
+    atoms
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Error returned when a periodic topology cannot be expanded into a supercell.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ReplicationError {
+    /// A repeat count is zero.
+    #[error("supercell must repeat the cell at least once along every axis, got {counts:?}")]
+    EmptySupercell {
+        /// Requested repeats along `a`, `b`, and `c`.
+        counts: [usize; 3],
+    },
+
+    /// Two consecutive atoms of a term are not bonded, so the image they sit in is unknown.
+    #[error("atoms {atom1_id} and {atom2_id} share a term but are not bonded")]
+    MissingBond {
+        /// First atom of the pair.
+        atom1_id: usize,
+        /// Second atom of the pair.
+        atom2_id: usize,
+    },
+}
+
+/// Error returned when an atom reordering is not a valid permutation of the topology's atoms.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PermutationError {
+    /// The permutation does not list exactly one entry per atom.
+    #[error("permutation has {found} entries but the topology has {expected} atoms")]
+    LengthMismatch {
+        /// Number of atoms in the topology.
+        expected: usize,
+        /// Number of entries supplied.
+        found: usize,
+    },
+
+    /// The permutation references an atom ID that does not exist.
+    #[error("permutation references non-existent atom ID {index}")]
+    OutOfRange {
+        /// Offending atom ID.
+        index: usize,
+    },
+
+    /// The permutation lists the same atom more than once.
+    #[error("permutation lists atom ID {index} more than once")]
+    Duplicate {
+        /// Repeated atom ID.
+        index: usize,
+    },
+}
+
+/// Error returned when a topology is too large for [`CompactTopology`](crate::CompactTopology).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CompactTopologyError {
+    /// Atom IDs do not fit in the `u32` index arrays.
+    #[error("topology has {count} atoms, more than a compact topology can index")]
+    TooManyAtoms {
+        /// Number of atoms in the topology.
+        count: usize,
+    },
+
+    /// Distinct atom types do not fit in the `u16` type IDs.
+    #[error(
+        "topology has at least {count} distinct atom types, more than a compact topology can intern"
+    )]
+    TooManyTypes {
+        /// Number of distinct types seen when the limit was reached.
+        count: usize,
+    },
+}
+
+/// Boxed error produced by user-supplied readers and writers in batch processing.
+pub type BoxedError = Box<dyn core::error::Error + Send + Sync + 'static>;
+
+/// Failure recorded for a single file during batch processing.
+///
+/// Batch runs isolate failures per file, so each variant identifies the stage that failed without
+/// aborting the remaining inputs.
+#[derive(Debug, Error)]
+pub enum BatchFileError {
+    /// The configured reader could not produce a molecular graph from the file.
+    #[error("failed to read input")]
+    Read(#[source] BoxedError),
+
+    /// Perception or typing failed for the molecule read from the file.
+    #[error("failed to type molecule")]
+    Typing(#[source] TyperError),
+
+    /// The configured writer could not persist the typed topology.
+    #[error("failed to write output")]
+    Write(#[source] BoxedError),
+
+    /// Processing the file panicked; the panic was contained to this file.
+    #[error("processing panicked: {message}")]
+    Panicked {
+        /// Panic payload rendered as text when it was a string.
+        message: String,
+    },
+}
+
+/// Errors raised while indexing or parsing records of an SDF (MDL structure-data) file.
+///
+/// Line numbers are 1-based and relative to the start of the offending record, so they can be
+/// reported without rescanning the whole archive.
+#[derive(Debug, Error)]
+pub enum SdfError {
+    /// The requested record index lies outside the indexed archive.
+    #[error("record {index} is out of range for an archive of {len} records")]
+    RecordOutOfRange {
+        /// Requested zero-based record index.
+        index: usize,
+        /// Number of records in the archive.
+        len: usize,
+    },
+
+    /// The record bytes are not valid UTF-8 text.
+    #[error("record is not valid UTF-8 text")]
+    InvalidEncoding,
+
+    /// The record ended before the header, counts line, or connection table was complete.
+    #[error("record is truncated at line {line}")]
+    Truncated {
+        /// Line at which more data was expected.
+        line: usize,
+    },
+
+    /// The counts line does not declare a supported connection table version.
+    #[error("unsupported molfile version '{version}'")]
+    UnsupportedVersion {
+        /// Version tag found on the counts line.
+        version: String,
+    },
+
+    /// A fixed-width field could not be parsed.
+    #[error("line {line}: invalid {field} field '{value}'")]
+    InvalidField {
+        /// Line containing the malformed field.
+        line: usize,
+        /// Name of the field being parsed (e.g., "atom count").
+        field: &'static str,
+        /// Raw text of the field.
+        value: String,
+    },
+
+    /// An atom line carries a symbol that is not a known element.
+    #[error("line {line}: unknown element symbol '{symbol}'")]
+    UnknownElement {
+        /// Line containing the atom entry.
+        line: usize,
+        /// Symbol that failed to parse.
+        symbol: String,
+    },
+
+    /// A bond line uses a bond type code that has no graph bond order equivalent.
+    #[error("line {line}: unsupported bond type {code}")]
+    UnsupportedBondType {
+        /// Line containing the bond entry.
+        line: usize,
+        /// MDL bond type code.
+        code: u8,
+    },
+
+    /// A bond line references atoms that do not form a valid graph edge.
+    #[error("line {line}: invalid bond")]
+    InvalidBond {
+        /// Line containing the bond entry.
+        line: usize,
+        /// Graph validation failure raised for the bond.
+        #[source]
+        source: GraphValidationError,
+    },
+}
+
+/// Errors produced while parsing SMILES strings.
+///
+/// Positions are zero-based byte offsets into the SMILES string.
+#[derive(Debug, Error)]
+pub enum SmilesError {
+    /// The string contains no atoms.
+    #[error("SMILES string contains no atoms")]
+    Empty,
+
+    /// A character cannot appear at this point of the string.
+    #[error("position {position}: unexpected character '{character}'")]
+    UnexpectedCharacter {
+        /// Offset of the character.
+        position: usize,
+        /// Offending character.
+        character: char,
+    },
+
+    /// An atom symbol is not a known element or is not allowed outside brackets.
+    #[error("position {position}: unknown element symbol '{symbol}'")]
+    UnknownElement {
+        /// Offset of the symbol.
+        position: usize,
+        /// Symbol that failed to parse.
+        symbol: String,
+    },
+
+    /// A bracket atom is missing its closing `]`.
+    #[error("position {position}: unclosed bracket atom")]
+    UnclosedBracket {
+        /// Offset of the opening `[`.
+        position: usize,
+    },
+
+    /// A branch is opened without a preceding atom or closed without being opened.
+    #[error("position {position}: unbalanced branch parenthesis")]
+    UnbalancedBranch {
+        /// Offset of the offending parenthesis, or the string length for unclosed branches.
+        position: usize,
+    },
+
+    /// A bond symbol or ring-closure digit is not attached to a preceding atom, or a bond
+    /// symbol is not followed by an atom.
+    #[error("position {position}: bond is missing an atom")]
+    DanglingBond {
+        /// Offset of the bond symbol or ring-closure digit.
+        position: usize,
+    },
+
+    /// A ring-closure label is opened but never closed.
+    #[error("ring closure {label} is never closed")]
+    UnclosedRing {
+        /// Ring-closure number.
+        label: u32,
+    },
+
+    /// The two ends of a ring closure specify conflicting bond symbols.
+    #[error("position {position}: conflicting bond symbols for ring closure {label}")]
+    ConflictingRingBond {
+        /// Offset of the closing ring-closure digit.
+        position: usize,
+        /// Ring-closure number.
+        label: u32,
+    },
+
+    /// A bond does not form a valid graph edge.
+    #[error("position {position}: invalid bond")]
+    InvalidBond {
+        /// Offset of the atom or ring-closure digit that completes the bond.
+        position: usize,
+        /// Graph validation failure raised for the bond.
+        #[source]
+        source: GraphValidationError,
+    },
+}
+
+/// Errors produced while parsing PDB files.
+///
+/// Line numbers are 1-based.
+#[derive(Debug, Error)]
+pub enum PdbError {
+    /// A fixed-width column could not be parsed.
+    #[error("line {line}: invalid {field} field '{value}'")]
+    InvalidField {
+        /// Line containing the malformed column.
+        line: usize,
+        /// Name of the column being parsed (e.g., "x coordinate").
+        field: &'static str,
+        /// Raw text of the column.
+        value: String,
+    },
+
+    /// Neither the element column nor the atom name identifies a known element.
+    #[error("line {line}: cannot determine element from '{symbol}'")]
+    UnknownElement {
+        /// Line containing the atom record.
+        line: usize,
+        /// Element column, or atom name when the column is blank.
+        symbol: String,
+    },
+
+    /// A `CONECT` record references an atom serial number that was not defined.
+    #[error("line {line}: unknown atom serial {serial}")]
+    UnknownAtom {
+        /// Line containing the `CONECT` record.
+        line: usize,
+        /// Serial number that is not defined by any atom record.
+        serial: u32,
+    },
+
+    /// A `CONECT` record connects atoms that do not form a valid graph edge.
+    #[error("line {line}: invalid bond")]
+    InvalidBond {
+        /// Line containing the `CONECT` record.
+        line: usize,
+        /// Graph validation failure raised for the bond.
+        #[source]
+        source: GraphValidationError,
+    },
+}
+
+/// Errors produced while reading XYZ coordinate files.
+///
+/// Line numbers are 1-based.
+#[derive(Debug, Error)]
+pub enum XyzError {
+    /// The input could not be read.
+    #[cfg(feature = "std")]
+    #[error("failed to read input")]
+    Io(#[from] std::io::Error),
+
+    /// The file ended before the header or all announced atom lines.
+    #[error("line {line}: unexpected end of file")]
+    Truncated {
+        /// Line that was expected but missing.
+        line: usize,
+    },
+
+    /// The atom count or a coordinate could not be parsed.
+    #[error("line {line}: invalid {field} '{value}'")]
+    InvalidField {
+        /// Line containing the malformed field.
+        line: usize,
+        /// Name of the field being parsed (e.g., "x coordinate").
+        field: &'static str,
+        /// Raw text of the field.
+        value: String,
+    },
+
+    /// An atom line starts with an unrecognized element symbol.
+    #[error("line {line}: unknown element '{symbol}'")]
+    UnknownElement {
+        /// Line containing the atom.
+        line: usize,
+        /// Symbol as written in the file.
+        symbol: String,
+    },
+}
+
+/// Errors produced while reading CIF and mmCIF files.
+///
+/// Line numbers are 1-based.
+#[derive(Debug, Error)]
+pub enum CifError {
+    /// The input could not be read.
+    #[cfg(feature = "std")]
+    #[error("failed to read input")]
+    Io(#[from] std::io::Error),
+
+    /// A semicolon-delimited text field is not closed before the end of the file.
+    #[error("line {line}: unterminated text field")]
+    UnterminatedTextField {
+        /// Line opening the text field.
+        line: usize,
+    },
+
+    /// A data item has a tag but no value.
+    #[error("line {line}: tag '{tag}' has no value")]
+    MissingValue {
+        /// Line containing the tag.
+        line: usize,
+        /// Tag as written in the file.
+        tag: String,
+    },
+
+    /// The values of a loop do not fill a whole number of rows.
+    #[error("line {line}: loop with {tags} tags holds {values} values")]
+    IncompleteLoop {
+        /// Line containing the `loop_` keyword.
+        line: usize,
+        /// Number of tags in the loop header.
+        tags: usize,
+        /// Number of values that follow.
+        values: usize,
+    },
+
+    /// A data item required to build the structure is absent.
+    #[error("data item '{tag}' is missing")]
+    MissingItem {
+        /// Tag of the missing item, in CIF 1.1 spelling.
+        tag: &'static str,
+    },
+
+    /// A numeric value could not be parsed or is unknown.
+    #[error("line {line}: invalid {tag} '{value}'")]
+    InvalidField {
+        /// Line containing the value.
+        line: usize,
+        /// Tag of the value, in CIF 1.1 spelling.
+        tag: &'static str,
+        /// Raw text of the value.
+        value: String,
+    },
+
+    /// An atom site names an unrecognized element.
+    #[error("line {line}: unknown element '{symbol}'")]
+    UnknownElement {
+        /// Line containing the atom site.
+        line: usize,
+        /// Type symbol or label as written in the file.
+        symbol: String,
+    },
+
+    /// A symmetry operator is not an affine map of `x`, `y`, and `z`.
+    #[error("line {line}: invalid symmetry operator '{operator}'")]
+    InvalidSymmetryOperator {
+        /// Line containing the operator.
+        line: usize,
+        /// Operator as written in the file.
+        operator: String,
+    },
+
+    /// The cell lengths and angles do not describe a cell of positive volume.
+    #[error("cell parameters {lengths:?} / {angles:?} do not describe a valid cell")]
+    DegenerateCell {
+        /// Cell lengths `a`, `b`, and `c` in Å.
+        lengths: [f64; 3],
+        /// Cell angles `α`, `β`, and `γ` in degrees.
+        angles: [f64; 3],
+    },
+}
+
+/// Errors produced while parsing a rule document in one of the supported formats.
+#[derive(Debug, Error)]
+pub enum RuleParseError {
+    /// The document is not a valid TOML rule deck.
+    #[error("invalid TOML rules")]
+    Toml(#[from] toml::de::Error),
+
+    /// The document is not a valid JSON rule deck.
+    #[cfg(feature = "json")]
+    #[error("invalid JSON rules")]
+    Json(#[from] serde_json::Error),
+
+    /// The document is not a valid YAML rule deck.
+    #[cfg(feature = "yaml")]
+    #[error("invalid YAML rules")]
+    Yaml(#[from] serde_yaml_ng::Error),
+
+    /// The document is in a format whose feature is not enabled in this build.
+    #[error("{format} rules require the `{feature}` feature")]
+    UnsupportedFormat {
+        /// Format of the document.
+        format: crate::typing::rules::RuleFormat,
+        /// Cargo feature that enables the format.
+        feature: &'static str,
+    },
+}
+
+/// Errors produced when selecting a force-field profile from a rule registry.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RegistryError {
+    /// No profile is registered under the requested name.
+    #[error("no force-field profile named '{name}' is registered")]
+    UnknownProfile {
+        /// Name that was looked up.
+        name: String,
+    },
+
+    /// The profile's TOML rules failed to parse when first used.
+    #[error("rules of force-field profile '{name}' are invalid")]
+    InvalidRules {
+        /// Name of the profile.
+        name: String,
+        /// Parse failure reported for the rule text.
+        #[source]
+        source: toml::de::Error,
+    },
+}
+
+/// Errors produced while reading or writing Materials Studio `.car`/`.mdf` files.
+///
+/// Line numbers are 1-based and refer to the file being parsed when the error was raised.
+#[derive(Debug, Error)]
+pub enum MsiError {
+    /// The file does not start with the expected `!BIOSYM` header.
+    #[error("missing '{expected}' header")]
+    MissingHeader {
+        /// Header text that was expected on the first line.
+        expected: &'static str,
+    },
+
+    /// An atom line has fewer columns than the format requires.
+    #[error("line {line}: expected at least {expected} columns, found {found}")]
+    MissingColumns {
+        /// Line containing the atom entry.
+        line: usize,
+        /// Minimum number of whitespace-separated columns.
+        expected: usize,
+        /// Number of columns actually present.
+        found: usize,
+    },
+
+    /// A column could not be parsed.
+    #[error("line {line}: invalid {field} field '{value}'")]
+    InvalidField {
+        /// Line containing the malformed column.
+        line: usize,
+        /// Name of the column being parsed (e.g., "x coordinate").
+        field: &'static str,
+        /// Raw text of the column.
+        value: String,
+    },
+
+    /// An atom line carries a symbol that is not a known element.
+    #[error("line {line}: unknown element symbol '{symbol}'")]
+    UnknownElement {
+        /// Line containing the atom entry.
+        line: usize,
+        /// Symbol that failed to parse.
+        symbol: String,
+    },
+
+    /// An `.mdf` entry refers to an atom that is not defined.
+    #[error("line {line}: unknown atom '{reference}'")]
+    UnknownAtom {
+        /// Line of the `.mdf` file containing the reference.
+        line: usize,
+        /// Atom reference as written in the file.
+        reference: String,
+    },
+
+    /// A connection carries a bond order with no graph bond order equivalent.
+    #[error("line {line}: unsupported bond order '{order}'")]
+    UnsupportedBondOrder {
+        /// Line of the `.mdf` file containing the connection.
+        line: usize,
+        /// Bond order suffix as written in the file.
+        order: String,
+    },
+
+    /// A connection does not form a valid graph edge.
+    #[error("line {line}: invalid bond")]
+    InvalidBond {
+        /// Line of the `.mdf` file containing the connection.
+        line: usize,
+        /// Graph validation failure raised for the bond.
+        #[source]
+        source: GraphValidationError,
+    },
+
+    /// A topology does not have one atom per atom of the system.
+    #[error("expected {expected} atoms, found {found}")]
+    AtomCountMismatch {
+        /// Number of atoms in the system.
+        expected: usize,
+        /// Number of atoms in the supplied topology.
+        found: usize,
+    },
+
+    /// A periodic graph was requested for a system whose `.car` file has no cell.
+    #[error("the system has no periodic cell")]
+    MissingCell,
+
+    /// The `.car` cell parameters do not describe a valid cell.
+    #[error("invalid cell")]
+    InvalidCell {
+        /// Failure raised while converting the cell parameters to cell vectors.
+        #[source]
+        source: CifError,
+    },
+}
+
+/// Errors produced while exporting topologies to simulation-engine formats.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ExportError {
+    /// Per-atom data does not provide exactly one entry per topology atom.
+    #[error("{field} has {found} entries but the topology has {expected} atoms")]
+    LengthMismatch {
+        /// Name of the per-atom data (e.g., "positions").
+        field: &'static str,
+        /// Number of atoms in the topology.
+        expected: usize,
+        /// Number of entries supplied.
+        found: usize,
+    },
+}
+
+/// Errors produced while reading a document of the JSON interchange format.
+#[derive(Debug, Error)]
+pub enum InterchangeError {
+    /// The document lacks the `format` and `version` header of the interchange format.
+    #[error("document is not a dreid-typer interchange document")]
+    MissingHeader,
+
+    /// The document was written in a version of the format this reader does not understand.
+    #[error("interchange format version {version} is not supported (expected {supported})")]
+    UnsupportedVersion {
+        /// Version recorded in the document.
+        version: u64,
+        /// Version understood by this reader.
+        supported: u32,
+    },
+
+    /// The document holds a different kind of data than requested.
+    #[error("expected a {expected} document but found a {found} document")]
+    KindMismatch {
+        /// Kind requested by the caller (e.g., "topology").
+        expected: &'static str,
+        /// Kind recorded in the document.
+        found: String,
+    },
+
+    /// The document does not follow the structure of its kind.
+    #[error("malformed interchange document: {message}")]
+    Malformed {
+        /// Description of the structural problem.
+        message: String,
+    },
+
+    /// A field holds a name that does not denote any value of its type.
+    #[error("invalid {field} '{value}'")]
+    InvalidValue {
+        /// Name of the field (e.g., "element").
+        field: &'static str,
+        /// Text found in the document.
+        value: String,
+    },
+
+    /// An atom entry records an ID different from its position in the atom list.
+    #[error("atom entry {index} has ID {id}")]
+    AtomIdMismatch {
+        /// Position of the entry in the atom list.
+        index: usize,
+        /// ID recorded in the entry.
+        id: usize,
+    },
+
+    /// A term, ring, or site refers to an atom the document does not define.
+    #[error("atom ID {atom_id} is out of range for {atom_count} atoms")]
+    AtomOutOfRange {
+        /// Offending atom ID.
+        atom_id: usize,
+        /// Number of atoms in the document.
+        atom_count: usize,
+    },
+
+    /// A resonance system refers to a bond the document does not define.
+    #[error("bond ID {bond_id} is out of range for {bond_count} bonds")]
+    BondOutOfRange {
+        /// Offending bond ID.
+        bond_id: usize,
+        /// Number of bonds in the document.
+        bond_count: usize,
+    },
+
+    /// Per-bond data does not provide exactly one entry per bond.
+    #[error("{field} has {found} entries but the document has {expected} bonds")]
+    LengthMismatch {
+        /// Name of the per-bond data (e.g., "bond_images").
+        field: &'static str,
+        /// Number of bonds in the document.
+        expected: usize,
+        /// Number of entries supplied.
+        found: usize,
+    },
+
+    /// The atoms and bonds of the document do not form a valid molecular graph.
+    #[error("document describes an invalid molecular graph")]
+    InvalidGraph(#[source] GraphValidationError),
+}
+
+/// Errors produced while assigning force-field parameters to a typed topology.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParameterError {
+    /// An atom carries a type label the parameter table does not define.
+    #[error("no parameters for atom type '{atom_type}' (atom ID {atom_id})")]
+    UnknownAtomType {
+        /// ID of the first atom with the label.
+        atom_id: usize,
+        /// Label missing from the table.
+        atom_type: String,
+    },
+}
+
+/// Errors produced while defining residue templates or preparing them for typing.
+#[derive(Debug, Error)]
+pub enum TemplateError {
+    /// The template's SMILES string could not be parsed.
+    #[error("template '{name}' has an invalid SMILES string")]
+    InvalidSmiles {
+        /// Name of the template.
+        name: String,
+        /// Parse failure reported for the SMILES string.
+        #[source]
+        source: SmilesError,
+    },
+
+    /// The core atoms are empty, do not match the atom count, or are not bonded into one piece.
+    #[error("template '{name}' does not have a single connected core")]
+    InvalidCore {
+        /// Name of the template.
+        name: String,
+    },
+
+    /// Typing the capped template with the typer's own rules failed.
+    #[error("template '{name}' could not be typed")]
+    Typing {
+        /// Name of the template.
+        name: String,
+        /// Failure reported by the typer.
+        #[source]
+        source: TyperError,
+    },
+}
+
+/// Errors reported by [`RuleWatcher`](crate::watch::RuleWatcher) while loading a rules file.
+///
+/// Failures after the watcher has started are delivered to its callback; the previously loaded
+/// rules stay in effect until the file loads again.
+#[cfg(feature = "watch")]
+#[derive(Debug, Error)]
+pub enum RuleWatchError {
+    /// The rules file could not be read.
+    #[error("failed to read rules file '{}'", path.display())]
+    Io {
+        /// Path of the rules file.
+        path: std::path::PathBuf,
+        /// Underlying I/O failure.
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The rules file is not a valid rule deck.
+    #[error("failed to parse rules file '{}'", path.display())]
+    RuleParse {
+        /// Path of the rules file.
+        path: std::path::PathBuf,
+        /// Parse failure reported for the TOML content.
+        #[source]
+        source: toml::de::Error,
+    },
+
+    /// Typing the watched molecule with the loaded rules failed.
+    #[error("failed to type the watched molecule")]
+    Typing(#[source] TyperError),
+
+    /// The file system watcher could not be started or stopped delivering events.
+    #[error("file watcher failed")]
+    Watch(#[from] notify::Error),
+}
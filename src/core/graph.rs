@@ -3,10 +3,14 @@
 //! This module provides the `MolecularGraph` container, which serves as the
 //! primary input interface for the library. It captures atoms and bonds with
 //! `GraphBondOrder` connectivity (Single, Double, Triple, Aromatic) before
-//! perception begins.
+//! perception begins, optionally together with 3D atom coordinates.
 
+use super::canon;
 use super::error::GraphValidationError;
+use super::metadata::AtomMetadata;
 use super::properties::{Element, GraphBondOrder};
+use super::stereo::{BondStereo, Chirality};
+use crate::compat::prelude::*;
 
 /// Stores the identifier and element for a single atom within a
 /// [`MolecularGraph`].
@@ -16,6 +20,31 @@ pub struct AtomNode {
     pub id: usize,
     /// Chemical element represented by this node.
     pub element: Element,
+    /// Configuration of the atom if it is a tetrahedral stereocenter.
+    pub chirality: Option<Chirality>,
+    /// Formal charge stated by the input, if any.
+    ///
+    /// Perception derives most charges from connectivity; a stated charge only decides between
+    /// states that connectivity cannot tell apart, such as a carbocation and a carbanion.
+    pub formal_charge: Option<i8>,
+    /// Number of unpaired electrons stated by the input; zero for closed-shell atoms.
+    pub radical_electrons: u8,
+    /// Mass number of a specific isotope, such as 2 for deuterium, or `None` for the natural
+    /// isotope mixture.
+    ///
+    /// Isotopes do not affect typing; a deuterium is perceived and typed as a hydrogen.
+    pub isotope: Option<u16>,
+    /// Name, residue, and tags copied unchanged onto the topology atom.
+    pub metadata: AtomMetadata,
+}
+
+impl AtomNode {
+    /// Returns `true` when perception uses the atom's stated charge or unpaired electrons,
+    /// which is the case for radicals and for charged carbons.
+    pub(crate) fn has_stated_electrons(&self) -> bool {
+        self.radical_electrons > 0
+            || (self.element == Element::C && self.formal_charge.is_some_and(|charge| charge != 0))
+    }
 }
 
 /// Captures a bond between two atoms inside a [`MolecularGraph`].
@@ -27,6 +56,8 @@ pub struct BondEdge {
     pub atom_ids: (usize, usize),
     /// Bond multiplicity recorded for the edge.
     pub order: GraphBondOrder,
+    /// Configuration of the bond if it is a stereogenic double bond.
+    pub stereo: Option<BondStereo>,
 }
 
 /// Mutable graph of atoms and bonds supplied to the perception pipeline.
@@ -36,6 +67,12 @@ pub struct MolecularGraph {
     pub atoms: Vec<AtomNode>,
     /// Collection of all bonds currently present in the graph.
     pub bonds: Vec<BondEdge>,
+    /// Optional Cartesian coordinates in Å, one per atom in atom ID order.
+    ///
+    /// When present, perception refines hybridization from the measured geometry. Coordinates
+    /// whose z components are all equal are taken to be a 2D depiction and ignored.
+    /// [`MolecularGraph::add_atom`] does not extend this list.
+    pub positions: Option<Vec<[f64; 3]>>,
 }
 
 impl MolecularGraph {
@@ -77,7 +114,15 @@ impl MolecularGraph {
     /// ```
     pub fn add_atom(&mut self, element: Element) -> usize {
         let id = self.atoms.len();
-        self.atoms.push(AtomNode { id, element });
+        self.atoms.push(AtomNode {
+            id,
+            element,
+            chirality: None,
+            formal_charge: None,
+            radical_electrons: 0,
+            isotope: None,
+            metadata: AtomMetadata::default(),
+        });
         id
     }
 
@@ -130,9 +175,405 @@ impl MolecularGraph {
             id,
             atom_ids: (atom1_id, atom2_id),
             order,
+            stereo: None,
         });
         Ok(id)
     }
+
+    /// Sets or clears the configuration of a tetrahedral stereocenter.
+    ///
+    /// The configuration is relative to the IDs of the atom's neighbors, as described for
+    /// [`Chirality`], so it should be set once the atom's bonds are in place. It does not affect
+    /// typing and is carried into [`Atom::chirality`](crate::Atom::chirality).
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_id` - Identifier of the stereocenter.
+    /// * `chirality` - Configuration to record, or `None` to clear it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphValidationError::MissingAtom`] if no atom has this ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{assign_topology, Chirality, Element, GraphBondOrder, MolecularGraph};
+    /// let mut graph = MolecularGraph::new();
+    /// let c = graph.add_atom(Element::C);
+    /// for element in [Element::F, Element::Cl, Element::Br, Element::H] {
+    ///     let substituent = graph.add_atom(element);
+    ///     graph.add_bond(c, substituent, GraphBondOrder::Single).unwrap();
+    /// }
+    /// graph.set_chirality(c, Some(Chirality::Clockwise)).unwrap();
+    ///
+    /// let topology = assign_topology(&graph).unwrap();
+    /// assert_eq!(topology.atoms[c].chirality, Some(Chirality::Clockwise));
+    /// ```
+    pub fn set_chirality(
+        &mut self,
+        atom_id: usize,
+        chirality: Option<Chirality>,
+    ) -> Result<(), GraphValidationError> {
+        let atom = self
+            .atoms
+            .get_mut(atom_id)
+            .ok_or(GraphValidationError::MissingAtom { atom_id })?;
+        atom.chirality = chirality;
+        Ok(())
+    }
+
+    /// Sets the electronic state of an atom that connectivity alone does not determine.
+    ///
+    /// A carbon with three single bonds may be a carbocation, a carbanion, or a radical; by
+    /// default perception completes its octet and treats it as a carbanion. Stating the charge
+    /// and the number of unpaired electrons lets perception count its electrons instead. The
+    /// state is honored for carbon charges and for radicals of any element when it fits the
+    /// atom's bonds, and ignored otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_id` - Identifier of the atom.
+    /// * `formal_charge` - Charge of the atom, or `None` to let perception derive it.
+    /// * `radical_electrons` - Number of unpaired electrons on the atom.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphValidationError::MissingAtom`] if no atom has this ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{assign_atom_types, Element, GraphBondOrder, MolecularGraph};
+    /// let mut graph = MolecularGraph::new();
+    /// let c = graph.add_atom(Element::C);
+    /// for _ in 0..3 {
+    ///     let h = graph.add_atom(Element::H);
+    ///     graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+    /// }
+    /// assert_eq!(assign_atom_types(&graph).unwrap()[c], "C_3");
+    ///
+    /// graph.set_electron_state(c, Some(1), 0).unwrap();
+    /// assert_eq!(assign_atom_types(&graph).unwrap()[c], "C_2");
+    /// ```
+    pub fn set_electron_state(
+        &mut self,
+        atom_id: usize,
+        formal_charge: Option<i8>,
+        radical_electrons: u8,
+    ) -> Result<(), GraphValidationError> {
+        let atom = self
+            .atoms
+            .get_mut(atom_id)
+            .ok_or(GraphValidationError::MissingAtom { atom_id })?;
+        atom.formal_charge = formal_charge;
+        atom.radical_electrons = radical_electrons;
+        Ok(())
+    }
+
+    /// Sets the isotope of an atom.
+    ///
+    /// The isotope is carried along for the caller and does not change the assigned types.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_id` - Identifier of the atom.
+    /// * `isotope` - Mass number of the isotope, or `None` for the natural isotope mixture.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphValidationError::MissingAtom`] if no atom has this ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{assign_atom_types, Element, GraphBondOrder, MolecularGraph};
+    /// let mut graph = MolecularGraph::new();
+    /// let o = graph.add_atom(Element::O);
+    /// for _ in 0..2 {
+    ///     let d = graph.add_atom(Element::H);
+    ///     graph.add_bond(o, d, GraphBondOrder::Single).unwrap();
+    ///     graph.set_isotope(d, Some(2)).unwrap();
+    /// }
+    /// assert_eq!(assign_atom_types(&graph).unwrap(), ["O_3", "H_HB", "H_HB"]);
+    /// ```
+    pub fn set_isotope(
+        &mut self,
+        atom_id: usize,
+        isotope: Option<u16>,
+    ) -> Result<(), GraphValidationError> {
+        self.atoms
+            .get_mut(atom_id)
+            .ok_or(GraphValidationError::MissingAtom { atom_id })?
+            .isotope = isotope;
+        Ok(())
+    }
+
+    /// Replaces the metadata of an atom.
+    ///
+    /// Metadata does not affect typing and is carried into
+    /// [`Atom::metadata`](crate::Atom::metadata).
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_id` - Identifier of the atom.
+    /// * `metadata` - Name, residue, and tags to record.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphValidationError::MissingAtom`] if no atom has this ID.
+    pub fn set_atom_metadata(
+        &mut self,
+        atom_id: usize,
+        metadata: AtomMetadata,
+    ) -> Result<(), GraphValidationError> {
+        let atom = self
+            .atoms
+            .get_mut(atom_id)
+            .ok_or(GraphValidationError::MissingAtom { atom_id })?;
+        atom.metadata = metadata;
+        Ok(())
+    }
+
+    /// Sets or clears the configuration of a double bond.
+    ///
+    /// The configuration is relative to the lowest-ID substituent on each end, as described for
+    /// [`BondStereo`]. It does not affect typing and is carried into
+    /// [`Bond::stereo`](crate::Bond::stereo).
+    ///
+    /// # Arguments
+    ///
+    /// * `bond_id` - Identifier of the double bond.
+    /// * `stereo` - Configuration to record, or `None` to clear it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphValidationError::MissingBond`] if no bond has this ID.
+    pub fn set_bond_stereo(
+        &mut self,
+        bond_id: usize,
+        stereo: Option<BondStereo>,
+    ) -> Result<(), GraphValidationError> {
+        let bond = self
+            .bonds
+            .get_mut(bond_id)
+            .ok_or(GraphValidationError::MissingBond { bond_id })?;
+        bond.stereo = stereo;
+        Ok(())
+    }
+
+    /// Splits the graph into its covalently connected fragments.
+    ///
+    /// Solvated systems, salts, and co-crystals consist of several fragments that perception and
+    /// typing can treat independently.
+    ///
+    /// # Returns
+    ///
+    /// Atom IDs of every fragment in ascending order, with fragments ordered by their lowest
+    /// atom ID. Isolated atoms form fragments of their own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{Element, GraphBondOrder, MolecularGraph};
+    /// // Sodium chloride in water: Na+, Cl-, and H2O.
+    /// let mut graph = MolecularGraph::new();
+    /// let na = graph.add_atom(Element::Na);
+    /// let o = graph.add_atom(Element::O);
+    /// let cl = graph.add_atom(Element::Cl);
+    /// for _ in 0..2 {
+    ///     let h = graph.add_atom(Element::H);
+    ///     graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+    /// }
+    ///
+    /// assert_eq!(graph.connected_components(), vec![vec![na], vec![o, 3, 4], vec![cl]]);
+    /// ```
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let n = self.atoms.len();
+        let mut neighbors = vec![Vec::new(); n];
+        for bond in &self.bonds {
+            let (a, b) = bond.atom_ids;
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+
+        let mut seen = vec![false; n];
+        let mut components = Vec::new();
+        for start in 0..n {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = vec![start];
+            let mut cursor = 0;
+            while let Some(&atom) = component.get(cursor) {
+                cursor += 1;
+                for &neighbor in &neighbors[atom] {
+                    if !seen[neighbor] {
+                        seen[neighbor] = true;
+                        component.push(neighbor);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Returns a 128-bit hash of the molecule's structure that does not depend on atom numbering.
+    ///
+    /// The hash covers elements, connectivity, and bond orders of the canonical form computed
+    /// by [`canonicalize`](crate::canonicalize), so differently numbered copies of the same
+    /// molecule share a hash. It is stable across runs and platforms and is suitable as a cache
+    /// key, as used by [`TypingCache`](crate::TypingCache).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{Element, GraphBondOrder, MolecularGraph};
+    ///
+    /// let mut hoh = MolecularGraph::new();
+    /// let h1 = hoh.add_atom(Element::H);
+    /// let o = hoh.add_atom(Element::O);
+    /// let h2 = hoh.add_atom(Element::H);
+    /// hoh.add_bond(h1, o, GraphBondOrder::Single).unwrap();
+    /// hoh.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+    ///
+    /// let mut ohh = MolecularGraph::new();
+    /// let o = ohh.add_atom(Element::O);
+    /// for _ in 0..2 {
+    ///     let h = ohh.add_atom(Element::H);
+    ///     ohh.add_bond(h, o, GraphBondOrder::Single).unwrap();
+    /// }
+    ///
+    /// assert_eq!(hoh.structure_hash(), ohh.structure_hash());
+    /// ```
+    pub fn structure_hash(&self) -> u128 {
+        canon::structure_hash(self)
+    }
+
+    /// Copies the given atoms and the bonds among them into a new graph.
+    ///
+    /// Stereo descriptors are copied unchanged, which keeps them valid as long as the kept atoms
+    /// include every neighbor of a stereocenter or stereogenic bond, as whole connected
+    /// components do.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_ids` - Atoms to keep, in ascending order; atom `atom_ids[i]` becomes atom `i`.
+    ///
+    /// # Returns
+    ///
+    /// The subgraph and, for each of its bonds, the ID of the original bond.
+    pub(crate) fn subgraph(&self, atom_ids: &[usize]) -> (MolecularGraph, Vec<usize>) {
+        let mut subgraph = MolecularGraph::new();
+        for &atom in atom_ids {
+            let id = subgraph.add_atom(self.atoms[atom].element);
+            subgraph.atoms[id].chirality = self.atoms[atom].chirality;
+            subgraph.atoms[id].formal_charge = self.atoms[atom].formal_charge;
+            subgraph.atoms[id].radical_electrons = self.atoms[atom].radical_electrons;
+            subgraph.atoms[id].isotope = self.atoms[atom].isotope;
+            subgraph.atoms[id].metadata = self.atoms[atom].metadata.clone();
+        }
+        let local = |atom: usize| atom_ids.binary_search(&atom).ok();
+        let mut bond_ids = Vec::new();
+        for bond in &self.bonds {
+            let (a, b) = bond.atom_ids;
+            if let (Some(a), Some(b)) = (local(a), local(b)) {
+                let id = subgraph
+                    .add_bond(a, b, bond.order)
+                    .expect("endpoints exist in the subgraph");
+                subgraph.bonds[id].stereo = bond.stereo;
+                bond_ids.push(bond.id);
+            }
+        }
+        subgraph.positions = self.positions.as_ref().and_then(|positions| {
+            atom_ids
+                .iter()
+                .map(|&atom| positions.get(atom).copied())
+                .collect()
+        });
+        (subgraph, bond_ids)
+    }
+
+    /// Attaches 3D coordinates to the atoms.
+    ///
+    /// # Arguments
+    ///
+    /// * `positions` - Cartesian coordinates in Å, one per atom.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphValidationError::PositionCountMismatch`] unless `positions` has exactly one
+    /// entry per atom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{Element, MolecularGraph};
+    /// let mut graph = MolecularGraph::new();
+    /// graph.add_atom(Element::Ne);
+    /// graph.set_positions(vec![[0.0, 0.0, 0.0]]).unwrap();
+    /// assert!(graph.set_positions(Vec::new()).is_err());
+    /// ```
+    pub fn set_positions(&mut self, positions: Vec<[f64; 3]>) -> Result<(), GraphValidationError> {
+        check_position_count(self.atoms.len(), positions.len())?;
+        self.positions = Some(positions);
+        Ok(())
+    }
+
+    /// Removes a bond and returns it.
+    ///
+    /// Bonds after the removed one shift down by one so that bond IDs stay equal to their
+    /// positions.
+    ///
+    /// # Arguments
+    ///
+    /// * `bond_id` - Identifier of the bond to remove.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphValidationError::MissingBond`] if no bond has this ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{Element, GraphBondOrder, MolecularGraph};
+    /// let mut graph = MolecularGraph::new();
+    /// let c = graph.add_atom(Element::C);
+    /// let o = graph.add_atom(Element::O);
+    /// let h = graph.add_atom(Element::H);
+    /// graph.add_bond(c, o, GraphBondOrder::Single).unwrap();
+    /// graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+    ///
+    /// let removed = graph.remove_bond(0).unwrap();
+    /// assert_eq!(removed.atom_ids, (c, o));
+    /// assert_eq!(graph.bonds[0].id, 0);
+    /// assert_eq!(graph.bonds[0].atom_ids, (o, h));
+    /// ```
+    pub fn remove_bond(&mut self, bond_id: usize) -> Result<BondEdge, GraphValidationError> {
+        if bond_id >= self.bonds.len() {
+            return Err(GraphValidationError::MissingBond { bond_id });
+        }
+        let removed = self.bonds.remove(bond_id);
+        for bond in &mut self.bonds[bond_id..] {
+            bond.id -= 1;
+        }
+        Ok(removed)
+    }
+}
+
+/// Checks that a coordinate list has one entry per atom.
+pub(crate) fn check_position_count(
+    atoms: usize,
+    positions: usize,
+) -> Result<(), GraphValidationError> {
+    if atoms == positions {
+        Ok(())
+    } else {
+        Err(GraphValidationError::PositionCountMismatch { atoms, positions })
+    }
 }
 
 #[cfg(test)]
@@ -207,4 +648,50 @@ mod tests {
             _ => panic!("unexpected error returned: {err:?}"),
         }
     }
+
+    #[test]
+    fn molecular_graph_remove_bond_renumbers_later_bonds() {
+        let mut graph = graph_with_atoms(&[Element::C, Element::C, Element::C]);
+        graph.add_bond(0, 1, GraphBondOrder::Single).unwrap();
+        graph.add_bond(1, 2, GraphBondOrder::Double).unwrap();
+
+        let removed = graph.remove_bond(0).expect("bond 0 exists");
+        assert_eq!(removed.atom_ids, (0, 1));
+        assert_eq!(graph.bonds.len(), 1);
+        assert_eq!(graph.bonds[0].id, 0);
+        assert_eq!(graph.bonds[0].order, GraphBondOrder::Double);
+
+        match graph.remove_bond(1) {
+            Err(GraphValidationError::MissingBond { bond_id }) => assert_eq!(bond_id, 1),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn connected_components_and_subgraphs_split_fragments() {
+        let mut graph =
+            graph_with_atoms(&[Element::O, Element::Na, Element::H, Element::C, Element::H]);
+        graph.add_bond(3, 4, GraphBondOrder::Single).unwrap();
+        graph.add_bond(0, 2, GraphBondOrder::Single).unwrap();
+
+        let components = graph.connected_components();
+        assert_eq!(components, vec![vec![0, 2], vec![1], vec![3, 4]]);
+
+        let (fragment, bond_ids) = graph.subgraph(&components[0]);
+        assert_eq!(fragment.atoms.len(), 2);
+        assert_eq!(fragment.atoms[1].element, Element::H);
+        assert_eq!(fragment.bonds[0].atom_ids, (0, 1));
+        assert_eq!(bond_ids, [1]);
+        assert!(fragment.positions.is_none());
+
+        graph
+            .set_positions((0..5).map(|i| [i as f64, 0.0, 0.0]).collect())
+            .unwrap();
+        let (fragment, _) = graph.subgraph(&components[2]);
+        assert_eq!(
+            fragment.positions,
+            Some(vec![[3.0, 0.0, 0.0], [4.0, 0.0, 0.0]])
+        );
+        assert!(MolecularGraph::new().connected_components().is_empty());
+    }
 }
@@ -0,0 +1,212 @@
+//! Configurable resource limits that bound the work spent on a single molecule.
+//!
+//! Pathological inputs (huge fused aromatic systems, densely cyclic cages, rule decks that never
+//! converge) can otherwise consume unbounded CPU time. Each limit converts such a case into a
+//! descriptive [`LimitExceeded`](crate::LimitExceeded) error instead. A
+//! [`CancellationToken`](crate::CancellationToken) lets another thread stop a run at the same
+//! checkpoints.
+
+use super::error::LimitExceeded;
+use crate::compat::time::{self, Instant};
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::time::Duration;
+
+/// Upper bounds applied while perceiving and typing one molecule.
+///
+/// `None` disables the corresponding limit. The defaults reproduce the behavior of releases
+/// without configurable limits: only the historical 100-round typing cap applies, so Kekulé
+/// backtracking and ring enumeration are unbounded. Services that accept untrusted input should
+/// set [`max_kekule_steps`](Self::max_kekule_steps) and a [`time_budget`](Self::time_budget).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Maximum number of backtracking steps the Kekulé solver may take per molecule.
+    pub max_kekule_steps: Option<u64>,
+    /// Maximum number of cycle candidates the ring search may enumerate.
+    pub max_ring_candidates: Option<usize>,
+    /// Maximum number of rounds the typing engine may run before giving up.
+    ///
+    /// Without a cap the engine still terminates, because an atom only ever moves to a
+    /// higher-ranked rule, after at most one round per rule and atom.
+    pub max_typing_rounds: Option<u32>,
+    /// Wall-clock budget for the whole Perceive-Type-Build run.
+    ///
+    /// Ignored on `wasm32-unknown-unknown`, which has no monotonic clock.
+    pub time_budget: Option<Duration>,
+}
+
+impl ResourceLimits {
+    /// Default limits: the historical 100-round typing cap and nothing else.
+    pub const DEFAULT: Self = Self {
+        max_kekule_steps: None,
+        max_ring_candidates: None,
+        max_typing_rounds: Some(100),
+        time_budget: None,
+    };
+
+    /// Limits that never trigger.
+    pub const fn unlimited() -> Self {
+        Self {
+            max_kekule_steps: None,
+            max_ring_candidates: None,
+            max_typing_rounds: None,
+            time_budget: None,
+        }
+    }
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Shared flag that aborts typing runs from another thread.
+///
+/// Clones share the same flag, so a front-end can hand one clone to a [`Typer`](crate::Typer)
+/// and keep another to call [`cancel`](Self::cancel) from a button or a request handler. Runs
+/// check the flag between perception steps, during the Kekulé search, before every typing round,
+/// and before building, and stop with [`LimitExceeded::Cancelled`].
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{
+///     CancellationToken, Element, LimitExceeded, MolecularGraph, Typer, TyperError,
+/// };
+///
+/// let token = CancellationToken::new();
+/// let typer = Typer::new().with_cancellation(token.clone());
+///
+/// let mut graph = MolecularGraph::new();
+/// graph.add_atom(Element::Ne);
+///
+/// token.cancel();
+/// assert!(matches!(
+///     typer.assign_topology(&graph),
+///     Err(TyperError::LimitExceeded(LimitExceeded::Cancelled))
+/// ));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    /// Flag raised by [`cancel`](Self::cancel), shared between clones.
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests that every run observing this token stop at its next checkpoint.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether [`cancel`](Self::cancel) has been called on this token or a clone of it.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Running budget that pairs the configured limits with the start time of a run.
+#[derive(Debug, Clone)]
+pub(crate) struct Budget {
+    /// Limits being enforced.
+    pub limits: ResourceLimits,
+    /// Instant at which the run started, or `None` on targets without a clock.
+    started: Option<Instant>,
+    /// Token that aborts the run when cancelled.
+    cancellation: Option<CancellationToken>,
+}
+
+impl Budget {
+    /// Starts a budget clock for the given limits.
+    pub fn start(limits: ResourceLimits) -> Self {
+        Self {
+            limits,
+            started: time::now(),
+            cancellation: None,
+        }
+    }
+
+    /// Makes the budget fail its checks once `token` is cancelled.
+    pub fn with_cancellation(mut self, token: Option<&CancellationToken>) -> Self {
+        self.cancellation = token.cloned();
+        self
+    }
+
+    /// Fails once the run has been cancelled or the wall-clock budget has been used up.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LimitExceeded::Cancelled`] when the cancellation token has been raised, or
+    /// [`LimitExceeded::TimeBudget`] when the elapsed time exceeds the budget.
+    pub fn check_time(&self) -> Result<(), LimitExceeded> {
+        if self
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Err(LimitExceeded::Cancelled);
+        }
+        match self.limits.time_budget {
+            Some(budget)
+                if self
+                    .started
+                    .is_some_and(|started| started.elapsed() > budget) =>
+            {
+                Err(LimitExceeded::TimeBudget { budget })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self::start(ResourceLimits::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_limits_match_historical_round_cap() {
+        let limits = ResourceLimits::default();
+        assert_eq!(limits.max_typing_rounds, Some(100));
+        assert!(limits.max_kekule_steps.is_none());
+        assert!(limits.time_budget.is_none());
+    }
+
+    // Without `std` there is no clock, and time budgets never expire.
+    #[cfg(feature = "std")]
+    #[test]
+    fn zero_time_budget_expires() {
+        let budget = Budget::start(ResourceLimits {
+            time_budget: Some(Duration::ZERO),
+            ..ResourceLimits::default()
+        });
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(matches!(
+            budget.check_time(),
+            Err(LimitExceeded::TimeBudget { .. })
+        ));
+        assert!(Budget::default().check_time().is_ok());
+    }
+
+    #[test]
+    fn cancelled_token_fails_every_clone_of_the_budget() {
+        let token = CancellationToken::new();
+        let budget = Budget::default().with_cancellation(Some(&token.clone()));
+        assert!(budget.check_time().is_ok());
+
+        token.cancel();
+        assert!(token.is_cancelled());
+        assert_eq!(budget.check_time(), Err(LimitExceeded::Cancelled));
+        assert_eq!(budget.clone().check_time(), Err(LimitExceeded::Cancelled));
+    }
+}
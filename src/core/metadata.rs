@@ -0,0 +1,107 @@
+//! Caller-supplied atom annotations that the typer carries through unchanged.
+//!
+//! Metadata never influences perception or typing. It is attached to graph atoms and copied
+//! onto the matching topology atoms, so output indices can be traced back to names and residues
+//! of the input structure without keeping a parallel array.
+
+use crate::compat::prelude::*;
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+/// Identifies the residue an atom belongs to, as in PDB `ATOM` records.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ResidueId {
+    /// Residue name (e.g., `"ALA"`).
+    pub name: String,
+    /// Chain identifier, or a space if blank.
+    pub chain_id: char,
+    /// Residue sequence number.
+    pub number: i32,
+}
+
+impl ResidueId {
+    /// Creates a residue identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Residue name.
+    /// * `chain_id` - Chain identifier.
+    /// * `number` - Residue sequence number.
+    pub fn new(name: impl Into<String>, chain_id: char, number: i32) -> Self {
+        Self {
+            name: name.into(),
+            chain_id,
+            number,
+        }
+    }
+}
+
+impl fmt::Display for ResidueId {
+    /// Formats the residue as `chain:nameNUMBER`, omitting a blank chain.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.chain_id != ' ' {
+            write!(f, "{}:", self.chain_id)?;
+        }
+        write!(f, "{}{}", self.name, self.number)
+    }
+}
+
+/// Annotations attached to one atom.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{assign_topology, AtomMetadata, Element, MolecularGraph, ResidueId};
+///
+/// let mut graph = MolecularGraph::new();
+/// let na = graph.add_atom(Element::Na);
+/// let metadata = AtomMetadata::named("NA")
+///     .with_residue(ResidueId::new("NA", 'B', 101))
+///     .with_tag("source", "crystal");
+/// graph.set_atom_metadata(na, metadata.clone()).unwrap();
+///
+/// let topology = assign_topology(&graph).unwrap();
+/// assert_eq!(topology.atoms[na].metadata, metadata);
+/// assert_eq!(topology.atoms[na].metadata.tag("source"), Some("crystal"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AtomMetadata {
+    /// Atom name (e.g., `"CA"`).
+    pub name: Option<String>,
+    /// Residue holding the atom.
+    pub residue: Option<ResidueId>,
+    /// Arbitrary key-value pairs, ordered by key.
+    pub tags: BTreeMap<String, String>,
+}
+
+impl AtomMetadata {
+    /// Creates metadata carrying only an atom name.
+    pub fn named(name: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the metadata with its residue set.
+    pub fn with_residue(mut self, residue: ResidueId) -> Self {
+        self.residue = Some(residue);
+        self
+    }
+
+    /// Returns the metadata with a tag added, replacing any previous value of the key.
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the value of a tag.
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.tags.get(key).map(String::as_str)
+    }
+
+    /// Returns `true` if no name, residue, or tag is set.
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.residue.is_none() && self.tags.is_empty()
+    }
+}
@@ -3,11 +3,37 @@
 //! The `core` module houses the basic data types—errors, graph containers, and
 //! chemical properties—that higher layers build upon when inferring topology.
 
+/// Canonical atom numbering that makes output independent of input order.
+pub mod canon;
+/// Memory-lean topology storage with interned types and flat index arrays.
+pub mod compact;
+/// Comparison of two typed topologies for rule regression testing.
+pub mod diff;
 /// Error types describing validation, perception, and typing failure modes.
 pub mod error;
 /// Input graph data structures for constructing molecules.
 pub mod graph;
+/// Resource limits that bound perception and typing work.
+pub mod limits;
+/// Atom names, residues, and tags carried from input to output.
+pub mod metadata;
+/// Unit-cell graphs with periodic bonds for crystalline and polymeric systems.
+pub mod periodic;
 /// Elemental properties, bond orders, and hybridization enums used throughout the pipeline.
 pub mod properties;
+/// Sorted sets of atom IDs addressing a sub-region of a graph.
+pub mod selection;
+/// Tetrahedral and double-bond stereo descriptors.
+pub mod stereo;
+/// Substructure search for locating functional groups and residues.
+pub mod substructure;
+/// Expansion of periodic unit-cell topologies into supercells.
+pub mod supercell;
+/// Lazily built per-atom and per-bond indices over the terms of a topology.
+pub mod term_index;
 /// Output topology data structures representing the final typed molecules.
 pub mod topology;
+/// Optional `tracing` spans and events emitted while a molecule moves through the pipeline.
+pub(crate) mod trace;
+/// Whole-graph diagnostics that report every problem instead of the first.
+pub mod validation;
@@ -0,0 +1,135 @@
+//! Periodic molecular graphs for crystalline frameworks and infinite polymers.
+//!
+//! A [`PeriodicMolecularGraph`](crate::core::periodic::PeriodicMolecularGraph) stores the atoms of
+//! one unit cell together with the cell vectors and, for every bond, the lattice translation of its
+//! second atom relative to its first. Bonds that cross a cell face therefore connect two atoms of
+//! the same cell while recording which neighboring image they actually reach, so perception can
+//! tell a genuine ring apart from a chain that merely wraps around the cell.
+
+use super::error::GraphValidationError;
+use super::graph::MolecularGraph;
+use super::properties::{Element, GraphBondOrder};
+use crate::compat::prelude::*;
+
+/// Lattice translation of a bond's second atom, in units of the cell vectors.
+pub type ImageFlags = [i32; 3];
+
+/// Molecular graph of one unit cell with periodic bonds.
+///
+/// The cell must be large enough that no atom bonds to its own image; use a supercell
+/// otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{Element, GraphBondOrder, PeriodicMolecularGraph};
+///
+/// // Polyethylene: one CH2-CH2 repeat unit per cell along x.
+/// let cell = [[2.55, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]];
+/// let mut chain = PeriodicMolecularGraph::new(cell);
+/// let c1 = chain.add_atom(Element::C);
+/// let c2 = chain.add_atom(Element::C);
+/// chain.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+/// chain.add_periodic_bond(c2, c1, GraphBondOrder::Single, [1, 0, 0]).unwrap();
+/// assert_eq!(chain.image(1), Some([1, 0, 0]));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PeriodicMolecularGraph {
+    /// Atoms and bonds of the unit cell.
+    pub graph: MolecularGraph,
+    /// Cell vectors `a`, `b`, and `c` as rows, in Å.
+    pub cell: [[f64; 3]; 3],
+    /// Image flags of every bond, indexed by bond ID.
+    pub bond_images: Vec<ImageFlags>,
+}
+
+impl PeriodicMolecularGraph {
+    /// Creates an empty periodic graph with the given cell vectors.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell` - Cell vectors `a`, `b`, and `c` as rows, in Å.
+    pub fn new(cell: [[f64; 3]; 3]) -> Self {
+        Self {
+            graph: MolecularGraph::new(),
+            cell,
+            bond_images: Vec::new(),
+        }
+    }
+
+    /// Adds a new atom to the cell and returns its ID.
+    pub fn add_atom(&mut self, element: Element) -> usize {
+        self.graph.add_atom(element)
+    }
+
+    /// Adds a bond between two atoms of the same cell image.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same [`GraphValidationError`]s as [`MolecularGraph::add_bond`].
+    pub fn add_bond(
+        &mut self,
+        atom1_id: usize,
+        atom2_id: usize,
+        order: GraphBondOrder,
+    ) -> Result<usize, GraphValidationError> {
+        self.add_periodic_bond(atom1_id, atom2_id, order, [0; 3])
+    }
+
+    /// Adds a bond from `atom1_id` to the image of `atom2_id` translated by `image`.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom1_id` - Atom in the reference cell.
+    /// * `atom2_id` - Atom whose translated image is bonded.
+    /// * `order` - Bond multiplicity to record.
+    /// * `image` - Translation of `atom2_id` in units of the cell vectors.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same [`GraphValidationError`]s as [`MolecularGraph::add_bond`]; in
+    /// particular an atom may not bond to its own image.
+    pub fn add_periodic_bond(
+        &mut self,
+        atom1_id: usize,
+        atom2_id: usize,
+        order: GraphBondOrder,
+        image: ImageFlags,
+    ) -> Result<usize, GraphValidationError> {
+        let id = self.graph.add_bond(atom1_id, atom2_id, order)?;
+        self.bond_images.push(image);
+        Ok(id)
+    }
+
+    /// Returns the image flags of a bond.
+    pub fn image(&self, bond_id: usize) -> Option<ImageFlags> {
+        self.bond_images.get(bond_id).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bonds_record_their_images() {
+        let mut graph = PeriodicMolecularGraph::new([[5.0, 0.0, 0.0]; 3]);
+        let a = graph.add_atom(Element::C);
+        let b = graph.add_atom(Element::C);
+        assert_eq!(graph.add_bond(a, b, GraphBondOrder::Single).unwrap(), 0);
+        assert_eq!(
+            graph
+                .add_periodic_bond(b, a, GraphBondOrder::Single, [0, -1, 0])
+                .unwrap(),
+            1
+        );
+        assert_eq!(graph.bond_images, [[0, 0, 0], [0, -1, 0]]);
+        assert_eq!(graph.image(2), None);
+        assert!(
+            graph
+                .add_periodic_bond(a, a, GraphBondOrder::Single, [1, 0, 0])
+                .is_err()
+        );
+        assert_eq!(graph.bond_images.len(), graph.graph.bonds.len());
+    }
+}
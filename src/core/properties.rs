@@ -6,16 +6,22 @@
 //! classifications. Keeping these definitions in one place ensures consistent
 //! serialization, parsing, and documentation across the crate.
 
-use serde::{Deserialize, Deserializer};
-use std::fmt;
-use std::str::FromStr;
+use crate::compat::prelude::*;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
 /// Enumerates every element the typer understands along with its atomic number.
 ///
 /// The variants are grouped by periodic trends (non-metals, alkali metals, etc.)
 /// so that code consuming this API can rely on exhaustive matches while still
-/// understanding the chemical context of each atom.
+/// understanding the chemical context of each atom. The [`Dummy`](Element::Dummy)
+/// pseudo-element stands for sites without a nucleus, such as lone pairs and other
+/// virtual sites.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[repr(u8)]
 pub enum Element {
@@ -270,6 +276,13 @@ pub enum Element {
     Ts,
     /// Oganesson (Og, Z = 118).
     Og,
+
+    // --- Pseudo-elements ---
+    /// Dummy atom, lone pair, or other virtual site without a nucleus (Du, Z = 0).
+    ///
+    /// Perception ignores bonds to dummy atoms, so they never count toward the valence of
+    /// their neighbors, but they are kept as atoms and bonds of the output topology.
+    Dummy = 0,
 }
 
 /// Error returned when parsing an unknown or misspelled element symbol.
@@ -283,7 +296,9 @@ impl FromStr for Element {
     /// Parses an atomic symbol into an [`Element`] variant.
     ///
     /// The parser accepts standard IUPAC symbols (e.g., `"C"`, `"Mg"`) and
-    /// maps them to the corresponding enum variant without case folding.
+    /// maps them to the corresponding enum variant without case folding. The
+    /// dummy symbol `"Du"` and the lone-pair symbols `"Lp"` and `"LP"` map to
+    /// [`Element::Dummy`].
     ///
     /// # Errors
     ///
@@ -409,6 +424,7 @@ impl FromStr for Element {
             "Lv" => Ok(Self::Lv),
             "Ts" => Ok(Self::Ts),
             "Og" => Ok(Self::Og),
+            "Du" | "Lp" | "LP" => Ok(Self::Dummy),
             _ => Err(ParseElementError(s.to_string())),
         }
     }
@@ -416,7 +432,10 @@ impl FromStr for Element {
 
 impl fmt::Display for Element {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Self::Dummy => f.write_str("Du"),
+            _ => write!(f, "{:?}", self),
+        }
     }
 }
 
@@ -454,8 +473,90 @@ impl Element {
             _ => None,
         }
     }
+
+    /// Returns `true` for s-, d-, and f-block metals.
+    ///
+    /// Bonds to these elements are treated as coordination bonds during perception, so ligands
+    /// keep the electron bookkeeping of the free species. Hydrogen and the post-transition metals
+    /// (Al, Ga, In, Sn, ...) are excluded because DREIDING types them as covalent centers.
+    pub fn is_metal_center(&self) -> bool {
+        use Element::*;
+        match self {
+            H => false,
+            Li | Na | K | Rb | Cs | Fr | Be | Mg | Ca | Sr | Ba | Ra => true,
+            _ => {
+                let z = self.atomic_number();
+                (21..=30).contains(&z)
+                    || (39..=48).contains(&z)
+                    || (57..=80).contains(&z)
+                    || (89..=112).contains(&z)
+            }
+        }
+    }
+
+    /// Returns `true` for the [`Dummy`](Element::Dummy) pseudo-element.
+    pub fn is_dummy(&self) -> bool {
+        *self == Element::Dummy
+    }
+
+    /// Returns the atomic number (Z), which is zero for dummy atoms.
+    pub fn atomic_number(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns the standard atomic weight in g/mol.
+    ///
+    /// Uses IUPAC conventional weights; elements without a stable isotope report the mass
+    /// number of their longest-lived isotope. Dummy atoms are massless.
+    pub fn atomic_mass(&self) -> f64 {
+        match self.atomic_number().checked_sub(1) {
+            Some(index) => ATOMIC_MASSES[index as usize],
+            None => 0.0,
+        }
+    }
+
+    /// Returns the single-bond covalent radius in Å.
+    ///
+    /// Uses the radii of Cordero et al. (2008), taking the low-spin values for Mn, Fe, and Co;
+    /// elements beyond curium and dummy atoms have no tabulated radius and return `None`.
+    pub fn covalent_radius(&self) -> Option<f64> {
+        let index = self.atomic_number().checked_sub(1)?;
+        COVALENT_RADII.get(index as usize).copied()
+    }
 }
 
+/// Standard atomic weights in g/mol indexed by `Z - 1`.
+#[rustfmt::skip]
+const ATOMIC_MASSES: [f64; 118] = [
+    1.008, 4.0026, 6.94, 9.0122, 10.81, 12.011, 14.007, 15.999, 18.998, 20.180,
+    22.990, 24.305, 26.982, 28.085, 30.974, 32.06, 35.45, 39.948, 39.098, 40.078,
+    44.956, 47.867, 50.942, 51.996, 54.938, 55.845, 58.933, 58.693, 63.546, 65.38,
+    69.723, 72.630, 74.922, 78.971, 79.904, 83.798, 85.468, 87.62, 88.906, 91.224,
+    92.906, 95.95, 98.0, 101.07, 102.91, 106.42, 107.87, 112.41, 114.82, 118.71,
+    121.76, 127.60, 126.90, 131.29, 132.91, 137.33, 138.91, 140.12, 140.91, 144.24,
+    145.0, 150.36, 151.96, 157.25, 158.93, 162.50, 164.93, 167.26, 168.93, 173.05,
+    174.97, 178.49, 180.95, 183.84, 186.21, 190.23, 192.22, 195.08, 196.97, 200.59,
+    204.38, 207.2, 208.98, 209.0, 210.0, 222.0, 223.0, 226.0, 227.0, 232.04,
+    231.04, 238.03, 237.0, 244.0, 243.0, 247.0, 247.0, 251.0, 252.0, 257.0,
+    258.0, 259.0, 266.0, 267.0, 268.0, 269.0, 270.0, 277.0, 278.0, 281.0,
+    282.0, 285.0, 286.0, 289.0, 290.0, 293.0, 294.0, 294.0,
+];
+
+/// Covalent radii in Å indexed by `Z - 1`, from Cordero et al., Dalton Trans. 2008, 2832.
+#[rustfmt::skip]
+const COVALENT_RADII: [f64; 96] = [
+    0.31, 0.28, 1.28, 0.96, 0.84, 0.76, 0.71, 0.66, 0.57, 0.58,
+    1.66, 1.41, 1.21, 1.11, 1.07, 1.05, 1.02, 1.06, 2.03, 1.76,
+    1.70, 1.60, 1.53, 1.39, 1.39, 1.32, 1.26, 1.24, 1.32, 1.22,
+    1.22, 1.20, 1.19, 1.20, 1.20, 1.16, 2.20, 1.95, 1.90, 1.75,
+    1.64, 1.54, 1.47, 1.46, 1.42, 1.39, 1.45, 1.44, 1.42, 1.39,
+    1.39, 1.38, 1.39, 1.40, 2.44, 2.15, 2.07, 2.04, 2.03, 2.01,
+    1.99, 1.98, 1.98, 1.96, 1.94, 1.92, 1.92, 1.89, 1.90, 1.87,
+    1.87, 1.75, 1.70, 1.62, 1.51, 1.44, 1.41, 1.36, 1.36, 1.32,
+    1.45, 1.46, 1.48, 1.40, 1.50, 1.50, 2.60, 2.21, 2.15, 2.06,
+    2.00, 1.96, 1.90, 1.87, 1.80, 1.69,
+];
+
 impl<'de> Deserialize<'de> for Element {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -563,6 +664,16 @@ impl fmt::Display for TopologyBondOrder {
     }
 }
 
+impl<'de> Deserialize<'de> for TopologyBondOrder {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 /// Captures the VSEPR-derived hybridization states recognized by the typer.
 ///
 /// These variants are used both as perception outputs and as serialized atom-type
@@ -628,3 +739,615 @@ impl<'de> Deserialize<'de> for Hybridization {
         Self::from_str(&s).map_err(serde::de::Error::custom)
     }
 }
+
+/// Common functional groups labeled on atoms during perception.
+///
+/// An atom may belong to several groups at once, such as the carbonyl carbon of a carbamate,
+/// which is both an amide and an ester.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FunctionalGroup {
+    /// Carboxylic acid or carboxylate: C(=O)OH or C(=O)O-.
+    Carboxyl,
+    /// Amide, lactam, or urea: C(=O)N.
+    Amide,
+    /// Nitro group: N bearing two terminal oxygens.
+    Nitro,
+    /// Sulfonamide: S(=O)(=O)N.
+    Sulfonamide,
+    /// Phosphate, its esters, and its anions: P bonded to four oxygens.
+    Phosphate,
+    /// Carboxylic ester or lactone: C(=O)OC.
+    Ester,
+    /// Guanidine or guanidinium: C bonded to three nitrogens, one of them doubly.
+    Guanidinium,
+    /// Sulfoxide or sulfinyl group: three-coordinate S bearing one terminal oxygen.
+    Sulfoxide,
+    /// Sulfonyl group of sulfones, sulfonates, sulfates, and sulfonamides: four-coordinate S
+    /// bearing at least two terminal oxygens.
+    Sulfonyl,
+    /// Phosphoryl group of phosphine oxides, phosphonates, and phosphates: four-coordinate P
+    /// bearing at least one terminal oxygen.
+    Phosphoryl,
+    /// Azide group: a chain of three nitrogens ending in a terminal one, N=N=N or N-N#N.
+    Azide,
+}
+
+/// Error returned when parsing an unrecognized functional group label.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid functional group string: '{0}'")]
+pub struct ParseFunctionalGroupError(String);
+
+impl FromStr for FunctionalGroup {
+    type Err = ParseFunctionalGroupError;
+
+    /// Parses a functional group label into the [`FunctionalGroup`] enum.
+    ///
+    /// The parser expects the exact variant names (`"Carboxyl"`, `"Amide"`, etc.).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseFunctionalGroupError`] if the string does not name a known group.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Carboxyl" => Ok(Self::Carboxyl),
+            "Amide" => Ok(Self::Amide),
+            "Nitro" => Ok(Self::Nitro),
+            "Sulfonamide" => Ok(Self::Sulfonamide),
+            "Phosphate" => Ok(Self::Phosphate),
+            "Ester" => Ok(Self::Ester),
+            "Guanidinium" => Ok(Self::Guanidinium),
+            "Sulfoxide" => Ok(Self::Sulfoxide),
+            "Sulfonyl" => Ok(Self::Sulfonyl),
+            "Phosphoryl" => Ok(Self::Phosphoryl),
+            "Azide" => Ok(Self::Azide),
+            _ => Err(ParseFunctionalGroupError(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for FunctionalGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl<'de> Deserialize<'de> for FunctionalGroup {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A DREIDING atom type label.
+///
+/// Every type of the original DREIDING paper and of the embedded default rules has its own
+/// variant, named after its label, so downstream code can match exhaustively instead of comparing
+/// strings. Labels produced by custom rules, UFF fallback, or label remapping are kept verbatim
+/// in [`DreidingType::Custom`].
+///
+/// Parsing never fails: a known label yields its variant and anything else becomes `Custom`, so
+/// `label.parse::<DreidingType>()?.as_str() == label` always holds. Equality, ordering, and
+/// hashing follow the label, which lets maps keyed by `DreidingType` be queried with `&str`.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::DreidingType;
+///
+/// let resonant: DreidingType = "C_R".parse().unwrap();
+/// assert_eq!(resonant, DreidingType::C_R);
+/// assert_eq!(resonant, "C_R");
+///
+/// let custom: DreidingType = "Cu+2".parse().unwrap();
+/// assert_eq!(custom, DreidingType::Custom("Cu+2".to_string()));
+/// assert!(!custom.is_builtin());
+/// ```
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone)]
+pub enum DreidingType {
+    /// Hydrogen.
+    H_,
+    /// Hydrogen capable of hydrogen bonding.
+    H_HB,
+    /// Bridging hydrogen of diborane.
+    H_b,
+    /// Tetrahedral boron.
+    B_3,
+    /// Trigonal boron.
+    B_2,
+    /// sp3 carbon.
+    C_3,
+    /// Resonant or aromatic carbon.
+    C_R,
+    /// sp2 carbon.
+    C_2,
+    /// sp carbon.
+    C_1,
+    /// United-atom sp3 CH carbon.
+    C_31,
+    /// United-atom sp3 CH2 carbon.
+    C_32,
+    /// United-atom sp3 CH3 carbon.
+    C_33,
+    /// United-atom methane.
+    C_34,
+    /// United-atom resonant or aromatic CH carbon.
+    C_R1,
+    /// United-atom resonant CH2 carbon.
+    C_R2,
+    /// United-atom sp2 CH carbon.
+    C_21,
+    /// United-atom sp2 CH2 carbon.
+    C_22,
+    /// United-atom sp CH carbon.
+    C_11,
+    /// sp3 nitrogen.
+    N_3,
+    /// Resonant or aromatic nitrogen.
+    N_R,
+    /// sp2 nitrogen.
+    N_2,
+    /// sp nitrogen.
+    N_1,
+    /// sp3 oxygen.
+    O_3,
+    /// Resonant oxygen.
+    O_R,
+    /// sp2 oxygen.
+    O_2,
+    /// sp oxygen.
+    O_1,
+    /// Fluorine.
+    F_,
+    /// Aluminium.
+    Al3,
+    /// Silicon.
+    Si3,
+    /// Phosphorus.
+    P_3,
+    /// sp3 sulfur.
+    S_3,
+    /// Resonant or aromatic sulfur.
+    S_R,
+    /// sp2 sulfur.
+    S_2,
+    /// Chlorine.
+    Cl,
+    /// Gallium.
+    Ga3,
+    /// Germanium.
+    Ge3,
+    /// Arsenic.
+    As3,
+    /// Selenium.
+    Se3,
+    /// Bromine.
+    Br,
+    /// Indium.
+    In3,
+    /// Tin.
+    Sn3,
+    /// Antimony.
+    Sb3,
+    /// Tellurium.
+    Te3,
+    /// Iodine.
+    I_,
+    /// Lithium ion.
+    Li,
+    /// Sodium ion.
+    Na,
+    /// Potassium ion.
+    K,
+    /// Rubidium ion.
+    Rb,
+    /// Caesium ion.
+    Cs,
+    /// Magnesium ion.
+    Mg,
+    /// Calcium ion.
+    Ca,
+    /// Strontium ion.
+    Sr,
+    /// Barium ion.
+    Ba,
+    /// Titanium.
+    Ti,
+    /// Manganese.
+    Mn,
+    /// Iron.
+    Fe,
+    /// Cobalt.
+    Co,
+    /// Nickel.
+    Ni,
+    /// Copper.
+    Cu,
+    /// Zinc.
+    Zn,
+    /// Technetium.
+    Tc,
+    /// Ruthenium.
+    Ru,
+    /// Palladium.
+    Pd,
+    /// Silver.
+    Ag,
+    /// Cadmium.
+    Cd,
+    /// Platinum.
+    Pt,
+    /// Gold.
+    Au,
+    /// Mercury.
+    Hg,
+    /// Any label without a dedicated variant.
+    Custom(String),
+}
+
+impl DreidingType {
+    /// Every built-in type, in the order of the DREIDING periodic table.
+    pub const BUILTIN: [DreidingType; 68] = [
+        DreidingType::H_,
+        DreidingType::H_HB,
+        DreidingType::H_b,
+        DreidingType::B_3,
+        DreidingType::B_2,
+        DreidingType::C_3,
+        DreidingType::C_R,
+        DreidingType::C_2,
+        DreidingType::C_1,
+        DreidingType::C_31,
+        DreidingType::C_32,
+        DreidingType::C_33,
+        DreidingType::C_34,
+        DreidingType::C_R1,
+        DreidingType::C_R2,
+        DreidingType::C_21,
+        DreidingType::C_22,
+        DreidingType::C_11,
+        DreidingType::N_3,
+        DreidingType::N_R,
+        DreidingType::N_2,
+        DreidingType::N_1,
+        DreidingType::O_3,
+        DreidingType::O_R,
+        DreidingType::O_2,
+        DreidingType::O_1,
+        DreidingType::F_,
+        DreidingType::Al3,
+        DreidingType::Si3,
+        DreidingType::P_3,
+        DreidingType::S_3,
+        DreidingType::S_R,
+        DreidingType::S_2,
+        DreidingType::Cl,
+        DreidingType::Ga3,
+        DreidingType::Ge3,
+        DreidingType::As3,
+        DreidingType::Se3,
+        DreidingType::Br,
+        DreidingType::In3,
+        DreidingType::Sn3,
+        DreidingType::Sb3,
+        DreidingType::Te3,
+        DreidingType::I_,
+        DreidingType::Li,
+        DreidingType::Na,
+        DreidingType::K,
+        DreidingType::Rb,
+        DreidingType::Cs,
+        DreidingType::Mg,
+        DreidingType::Ca,
+        DreidingType::Sr,
+        DreidingType::Ba,
+        DreidingType::Ti,
+        DreidingType::Mn,
+        DreidingType::Fe,
+        DreidingType::Co,
+        DreidingType::Ni,
+        DreidingType::Cu,
+        DreidingType::Zn,
+        DreidingType::Tc,
+        DreidingType::Ru,
+        DreidingType::Pd,
+        DreidingType::Ag,
+        DreidingType::Cd,
+        DreidingType::Pt,
+        DreidingType::Au,
+        DreidingType::Hg,
+    ];
+
+    /// Returns the type label (e.g., `"C_R"`).
+    pub fn as_str(&self) -> &str {
+        match self {
+            DreidingType::H_ => "H_",
+            DreidingType::H_HB => "H_HB",
+            DreidingType::H_b => "H_b",
+            DreidingType::B_3 => "B_3",
+            DreidingType::B_2 => "B_2",
+            DreidingType::C_3 => "C_3",
+            DreidingType::C_R => "C_R",
+            DreidingType::C_2 => "C_2",
+            DreidingType::C_1 => "C_1",
+            DreidingType::C_31 => "C_31",
+            DreidingType::C_32 => "C_32",
+            DreidingType::C_33 => "C_33",
+            DreidingType::C_34 => "C_34",
+            DreidingType::C_R1 => "C_R1",
+            DreidingType::C_R2 => "C_R2",
+            DreidingType::C_21 => "C_21",
+            DreidingType::C_22 => "C_22",
+            DreidingType::C_11 => "C_11",
+            DreidingType::N_3 => "N_3",
+            DreidingType::N_R => "N_R",
+            DreidingType::N_2 => "N_2",
+            DreidingType::N_1 => "N_1",
+            DreidingType::O_3 => "O_3",
+            DreidingType::O_R => "O_R",
+            DreidingType::O_2 => "O_2",
+            DreidingType::O_1 => "O_1",
+            DreidingType::F_ => "F_",
+            DreidingType::Al3 => "Al3",
+            DreidingType::Si3 => "Si3",
+            DreidingType::P_3 => "P_3",
+            DreidingType::S_3 => "S_3",
+            DreidingType::S_R => "S_R",
+            DreidingType::S_2 => "S_2",
+            DreidingType::Cl => "Cl",
+            DreidingType::Ga3 => "Ga3",
+            DreidingType::Ge3 => "Ge3",
+            DreidingType::As3 => "As3",
+            DreidingType::Se3 => "Se3",
+            DreidingType::Br => "Br",
+            DreidingType::In3 => "In3",
+            DreidingType::Sn3 => "Sn3",
+            DreidingType::Sb3 => "Sb3",
+            DreidingType::Te3 => "Te3",
+            DreidingType::I_ => "I_",
+            DreidingType::Li => "Li",
+            DreidingType::Na => "Na",
+            DreidingType::K => "K",
+            DreidingType::Rb => "Rb",
+            DreidingType::Cs => "Cs",
+            DreidingType::Mg => "Mg",
+            DreidingType::Ca => "Ca",
+            DreidingType::Sr => "Sr",
+            DreidingType::Ba => "Ba",
+            DreidingType::Ti => "Ti",
+            DreidingType::Mn => "Mn",
+            DreidingType::Fe => "Fe",
+            DreidingType::Co => "Co",
+            DreidingType::Ni => "Ni",
+            DreidingType::Cu => "Cu",
+            DreidingType::Zn => "Zn",
+            DreidingType::Tc => "Tc",
+            DreidingType::Ru => "Ru",
+            DreidingType::Pd => "Pd",
+            DreidingType::Ag => "Ag",
+            DreidingType::Cd => "Cd",
+            DreidingType::Pt => "Pt",
+            DreidingType::Au => "Au",
+            DreidingType::Hg => "Hg",
+            DreidingType::Custom(label) => label,
+        }
+    }
+
+    /// Returns the united-atom type of this carbon type carrying `hydrogens` implicit hydrogens.
+    ///
+    /// Returns `None` for types other than `C_3`, `C_2`, `C_R`, and `C_1`, and for hydrogen
+    /// counts DREIDING has no united-atom type for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::DreidingType;
+    ///
+    /// assert_eq!(DreidingType::C_3.united_atom(3), Some(DreidingType::C_33));
+    /// assert_eq!(DreidingType::C_R.united_atom(1), Some(DreidingType::C_R1));
+    /// assert_eq!(DreidingType::C_R.united_atom(2), Some(DreidingType::C_R2));
+    /// assert_eq!(DreidingType::C_R.united_atom(3), None);
+    /// assert_eq!(DreidingType::N_3.united_atom(1), None);
+    /// ```
+    pub fn united_atom(&self, hydrogens: usize) -> Option<DreidingType> {
+        match (self, hydrogens) {
+            (DreidingType::C_3, 1) => Some(DreidingType::C_31),
+            (DreidingType::C_3, 2) => Some(DreidingType::C_32),
+            (DreidingType::C_3, 3) => Some(DreidingType::C_33),
+            (DreidingType::C_3, 4) => Some(DreidingType::C_34),
+            (DreidingType::C_R, 1) => Some(DreidingType::C_R1),
+            (DreidingType::C_R, 2) => Some(DreidingType::C_R2),
+            (DreidingType::C_2, 1) => Some(DreidingType::C_21),
+            (DreidingType::C_2, 2) => Some(DreidingType::C_22),
+            (DreidingType::C_1, 1) => Some(DreidingType::C_11),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` unless this is a [`DreidingType::Custom`] label.
+    pub fn is_builtin(&self) -> bool {
+        !matches!(self, DreidingType::Custom(_))
+    }
+
+    /// Converts a label into its built-in variant, or `Custom` when none matches.
+    pub fn from_label(label: impl AsRef<str> + Into<String>) -> Self {
+        Self::BUILTIN
+            .iter()
+            .find(|builtin| builtin.as_str() == label.as_ref())
+            .cloned()
+            .unwrap_or_else(|| DreidingType::Custom(label.into()))
+    }
+}
+
+impl FromStr for DreidingType {
+    type Err = core::convert::Infallible;
+
+    /// Parses a type label; labels without a dedicated variant become `Custom`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_label(s))
+    }
+}
+
+impl From<&str> for DreidingType {
+    fn from(label: &str) -> Self {
+        Self::from_label(label)
+    }
+}
+
+impl From<String> for DreidingType {
+    fn from(label: String) -> Self {
+        Self::from_label(label)
+    }
+}
+
+impl From<DreidingType> for String {
+    fn from(atom_type: DreidingType) -> Self {
+        match atom_type {
+            DreidingType::Custom(label) => label,
+            builtin => builtin.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for DreidingType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for DreidingType {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for DreidingType {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for DreidingType {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for DreidingType {}
+
+impl PartialEq<str> for DreidingType {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for DreidingType {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for DreidingType {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<DreidingType> for str {
+    fn eq(&self, other: &DreidingType) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<DreidingType> for &str {
+    fn eq(&self, other: &DreidingType) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<DreidingType> for String {
+    fn eq(&self, other: &DreidingType) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialOrd for DreidingType {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DreidingType {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for DreidingType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl Serialize for DreidingType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for DreidingType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self::from_label(String::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat::collections::HashMap;
+
+    #[test]
+    fn builtin_labels_round_trip_through_their_variants() {
+        for builtin in &DreidingType::BUILTIN {
+            assert!(builtin.is_builtin());
+            assert_eq!(&DreidingType::from_label(builtin.as_str()), builtin);
+        }
+        assert_eq!(
+            DreidingType::from_label("O_W"),
+            DreidingType::Custom("O_W".to_string())
+        );
+    }
+
+    #[test]
+    fn dummy_atoms_parse_from_their_pseudo_symbols() {
+        for symbol in ["Du", "Lp", "LP"] {
+            assert_eq!(symbol.parse::<Element>(), Ok(Element::Dummy));
+        }
+        assert_eq!(Element::Dummy.to_string(), "Du");
+        assert_eq!(Element::Dummy.atomic_number(), 0);
+        assert_eq!(Element::Dummy.atomic_mass(), 0.0);
+        assert_eq!(Element::Dummy.covalent_radius(), None);
+        assert_eq!(Element::Dummy.valence_electrons(), None);
+        assert!(!Element::Dummy.is_metal_center());
+        assert_eq!(Element::H.atomic_mass(), 1.008);
+    }
+
+    #[test]
+    fn custom_labels_compare_equal_to_matching_builtins() {
+        assert_eq!(DreidingType::Custom("C_R".to_string()), DreidingType::C_R);
+
+        let mut counts = HashMap::new();
+        counts.insert(DreidingType::Custom("H_".to_string()), 2);
+        assert_eq!(counts.get("H_"), Some(&2));
+        assert_eq!(counts.get(&DreidingType::H_), Some(&2));
+    }
+}
@@ -0,0 +1,508 @@
+//! Sets of atom IDs that address a sub-region of a molecular graph.
+//!
+//! A selection names the atoms a caller is interested in, such as a ligand inside a protein or
+//! the QM region of a QM/MM setup, without copying the graph. The IDs are kept sorted and unique
+//! so that selected atoms appear in input order wherever a selection drives the output.
+//! Selections are written out by ID or built from a [`SelectionQuery`](crate::SelectionQuery)
+//! that combines element, atom type, ring membership, residue name, and bond distance tests.
+
+use crate::compat::prelude::*;
+use crate::core::error::SelectionError;
+use crate::core::graph::MolecularGraph;
+use crate::core::properties::Element;
+use crate::core::topology::MolecularTopology;
+use crate::core::validation;
+use alloc::collections::VecDeque;
+use core::ops::Not;
+
+/// Sorted, duplicate-free set of atom IDs.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::AtomSelection;
+///
+/// let mut selection: AtomSelection = [4, 1, 4, 2].into_iter().collect();
+/// selection.insert(0);
+/// assert_eq!(selection.atom_ids(), [0, 1, 2, 4]);
+/// assert!(selection.contains(2));
+/// assert!(!selection.contains(3));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct AtomSelection {
+    /// Selected atom IDs in ascending order.
+    atom_ids: Vec<usize>,
+}
+
+impl AtomSelection {
+    /// Creates an empty selection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a selection from atom IDs given in any order, ignoring duplicates.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_ids` - IDs of the atoms to select.
+    pub fn from_ids(atom_ids: impl IntoIterator<Item = usize>) -> Self {
+        let mut atom_ids: Vec<usize> = atom_ids.into_iter().collect();
+        atom_ids.sort_unstable();
+        atom_ids.dedup();
+        Self { atom_ids }
+    }
+
+    /// Adds an atom to the selection.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_id` - ID of the atom to select.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the atom was not selected before.
+    pub fn insert(&mut self, atom_id: usize) -> bool {
+        match self.atom_ids.binary_search(&atom_id) {
+            Ok(_) => false,
+            Err(index) => {
+                self.atom_ids.insert(index, atom_id);
+                true
+            }
+        }
+    }
+
+    /// Returns whether the atom is selected.
+    pub fn contains(&self, atom_id: usize) -> bool {
+        self.atom_ids.binary_search(&atom_id).is_ok()
+    }
+
+    /// Returns the selected atom IDs in ascending order.
+    pub fn atom_ids(&self) -> &[usize] {
+        &self.atom_ids
+    }
+
+    /// Returns the number of selected atoms.
+    pub fn len(&self) -> usize {
+        self.atom_ids.len()
+    }
+
+    /// Returns whether no atom is selected.
+    pub fn is_empty(&self) -> bool {
+        self.atom_ids.is_empty()
+    }
+}
+
+impl FromIterator<usize> for AtomSelection {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        Self::from_ids(iter)
+    }
+}
+
+/// Composable test that picks atoms out of a graph or typed topology.
+///
+/// Queries are built from the constructors below and combined with [`SelectionQuery::and`],
+/// [`SelectionQuery::or`], and the `!` operator. Evaluating a query yields an [`AtomSelection`]
+/// for partial typing, overrides, or topology filtering.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::smiles::parse_smiles;
+/// use dreid_typer::{Element, SelectionQuery};
+///
+/// // Toluene: ring carbons 1-6, then the methyl carbon 0 and the hydrogens.
+/// let graph = parse_smiles("Cc1ccccc1").unwrap();
+/// let ring_carbons = SelectionQuery::element(Element::C).and(SelectionQuery::in_ring());
+/// assert_eq!(ring_carbons.select(&graph).unwrap().atom_ids(), [1, 2, 3, 4, 5, 6]);
+///
+/// let near_methyl = SelectionQuery::within(0, 1).and(!SelectionQuery::element(Element::H));
+/// assert_eq!(near_methyl.select(&graph).unwrap().atom_ids(), [0, 1]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SelectionQuery {
+    /// Root of the query expression.
+    node: QueryNode,
+}
+
+/// Expression tree of a [`SelectionQuery`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum QueryNode {
+    /// Every atom.
+    All,
+    /// Atoms of one element.
+    Element(Element),
+    /// Atoms carrying one assigned type.
+    AtomType(String),
+    /// Atoms on at least one ring.
+    InRing,
+    /// Atoms whose metadata places them in a residue with this name.
+    ResidueName(String),
+    /// Atoms at most `bonds` bonds away from `atom_id`, including that atom.
+    Within {
+        /// Anchor atom.
+        atom_id: usize,
+        /// Largest bond distance from the anchor.
+        bonds: usize,
+    },
+    /// Atoms selected by both operands.
+    And(Box<QueryNode>, Box<QueryNode>),
+    /// Atoms selected by either operand.
+    Or(Box<QueryNode>, Box<QueryNode>),
+    /// Atoms not selected by the operand.
+    Not(Box<QueryNode>),
+}
+
+impl SelectionQuery {
+    /// Selects every atom.
+    pub fn all() -> Self {
+        Self {
+            node: QueryNode::All,
+        }
+    }
+
+    /// Selects the atoms of one element.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Element to match.
+    pub fn element(element: Element) -> Self {
+        Self {
+            node: QueryNode::Element(element),
+        }
+    }
+
+    /// Selects the atoms carrying one assigned type.
+    ///
+    /// Only topologies carry types, so queries using this test are evaluated with
+    /// [`SelectionQuery::select_in_topology`].
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_type` - Type label to match (e.g., `"C_R"`).
+    pub fn atom_type(atom_type: impl Into<String>) -> Self {
+        Self {
+            node: QueryNode::AtomType(atom_type.into()),
+        }
+    }
+
+    /// Selects the atoms that lie on at least one ring.
+    pub fn in_ring() -> Self {
+        Self {
+            node: QueryNode::InRing,
+        }
+    }
+
+    /// Selects the atoms whose metadata places them in a residue with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Residue name to match (e.g., `"HEM"`).
+    pub fn residue_name(name: impl Into<String>) -> Self {
+        Self {
+            node: QueryNode::ResidueName(name.into()),
+        }
+    }
+
+    /// Selects the atoms at most `bonds` bonds away from an anchor atom, including the anchor.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_id` - Anchor atom.
+    /// * `bonds` - Largest bond distance from the anchor.
+    pub fn within(atom_id: usize, bonds: usize) -> Self {
+        Self {
+            node: QueryNode::Within { atom_id, bonds },
+        }
+    }
+
+    /// Selects the atoms matched by both this query and `other`.
+    pub fn and(self, other: Self) -> Self {
+        Self {
+            node: QueryNode::And(Box::new(self.node), Box::new(other.node)),
+        }
+    }
+
+    /// Selects the atoms matched by this query, `other`, or both.
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            node: QueryNode::Or(Box::new(self.node), Box::new(other.node)),
+        }
+    }
+
+    /// Evaluates the query against an input graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Graph whose atoms are tested.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SelectionError::TypesUnavailable`] if the query tests atom types, and
+    /// [`SelectionError::AtomOutOfRange`] if a distance test is anchored at a missing atom.
+    pub fn select(&self, graph: &MolecularGraph) -> Result<AtomSelection, SelectionError> {
+        let bonds: Vec<(usize, usize)> = graph
+            .bonds
+            .iter()
+            .map(|bond| bond.atom_ids)
+            .filter(|&(a, b)| a < graph.atoms.len() && b < graph.atoms.len())
+            .collect();
+        let view = View {
+            elements: graph.atoms.iter().map(|atom| atom.element).collect(),
+            atom_types: None,
+            residues: graph
+                .atoms
+                .iter()
+                .map(|atom| atom.metadata.residue.as_ref().map(|r| r.name.as_str()))
+                .collect(),
+            ring_atoms: graph_ring_atoms(graph.atoms.len(), &bonds),
+            neighbors: adjacency(graph.atoms.len(), &bonds),
+        };
+        view.select(&self.node)
+    }
+
+    /// Evaluates the query against a typed topology.
+    ///
+    /// Ring membership follows the perceived rings of the topology.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Topology whose atoms are tested.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SelectionError::AtomOutOfRange`] if a distance test is anchored at a missing
+    /// atom.
+    pub fn select_in_topology(
+        &self,
+        topology: &MolecularTopology,
+    ) -> Result<AtomSelection, SelectionError> {
+        let n = topology.atoms.len();
+        let bonds: Vec<(usize, usize)> = topology.bonds.iter().map(|bond| bond.atom_ids).collect();
+        let mut ring_atoms = vec![false; n];
+        for ring in &topology.rings {
+            for &id in &ring.atom_ids {
+                ring_atoms[id] = true;
+            }
+        }
+        let view = View {
+            elements: topology.atoms.iter().map(|atom| atom.element).collect(),
+            atom_types: Some(
+                topology
+                    .atoms
+                    .iter()
+                    .map(|atom| atom.atom_type.as_str())
+                    .collect(),
+            ),
+            residues: topology
+                .atoms
+                .iter()
+                .map(|atom| atom.metadata.residue.as_ref().map(|r| r.name.as_str()))
+                .collect(),
+            ring_atoms,
+            neighbors: adjacency(n, &bonds),
+        };
+        view.select(&self.node)
+    }
+}
+
+impl Not for SelectionQuery {
+    type Output = Self;
+
+    /// Selects the atoms this query does not match.
+    fn not(self) -> Self {
+        Self {
+            node: QueryNode::Not(Box::new(self.node)),
+        }
+    }
+}
+
+/// Per-atom facts a query is evaluated against, gathered from a graph or topology.
+struct View<'a> {
+    /// Element of every atom.
+    elements: Vec<Element>,
+    /// Assigned type of every atom, or `None` for untyped graphs.
+    atom_types: Option<Vec<&'a str>>,
+    /// Residue name of every atom, if its metadata has one.
+    residues: Vec<Option<&'a str>>,
+    /// Whether every atom lies on a ring.
+    ring_atoms: Vec<bool>,
+    /// Bonded neighbors of every atom.
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl View<'_> {
+    /// Evaluates a query expression into a selection.
+    fn select(&self, node: &QueryNode) -> Result<AtomSelection, SelectionError> {
+        let mask = self.mask(node)?;
+        Ok(AtomSelection {
+            atom_ids: (0..mask.len()).filter(|&id| mask[id]).collect(),
+        })
+    }
+
+    /// Evaluates a query expression into one flag per atom.
+    fn mask(&self, node: &QueryNode) -> Result<Vec<bool>, SelectionError> {
+        let n = self.elements.len();
+        Ok(match node {
+            QueryNode::All => vec![true; n],
+            QueryNode::Element(element) => self.elements.iter().map(|e| e == element).collect(),
+            QueryNode::AtomType(atom_type) => self
+                .atom_types
+                .as_ref()
+                .ok_or(SelectionError::TypesUnavailable)?
+                .iter()
+                .map(|t| t == atom_type)
+                .collect(),
+            QueryNode::InRing => self.ring_atoms.clone(),
+            QueryNode::ResidueName(name) => self
+                .residues
+                .iter()
+                .map(|residue| *residue == Some(name.as_str()))
+                .collect(),
+            QueryNode::Within { atom_id, bonds } => self.within(*atom_id, *bonds)?,
+            QueryNode::And(a, b) => zip_masks(self.mask(a)?, self.mask(b)?, |a, b| a && b),
+            QueryNode::Or(a, b) => zip_masks(self.mask(a)?, self.mask(b)?, |a, b| a || b),
+            QueryNode::Not(a) => self.mask(a)?.into_iter().map(|flag| !flag).collect(),
+        })
+    }
+
+    /// Flags the atoms at most `bonds` bonds away from `atom_id`.
+    fn within(&self, atom_id: usize, bonds: usize) -> Result<Vec<bool>, SelectionError> {
+        let n = self.elements.len();
+        if atom_id >= n {
+            return Err(SelectionError::AtomOutOfRange {
+                atom_id,
+                atom_count: n,
+            });
+        }
+        let mut depth = vec![None; n];
+        depth[atom_id] = Some(0);
+        let mut queue = VecDeque::from([atom_id]);
+        while let Some(atom) = queue.pop_front() {
+            let next = depth[atom].expect("queued atoms have a depth") + 1;
+            if next > bonds {
+                continue;
+            }
+            for &neighbor in &self.neighbors[atom] {
+                if depth[neighbor].is_none() {
+                    depth[neighbor] = Some(next);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        Ok(depth.iter().map(Option::is_some).collect())
+    }
+}
+
+/// Combines two per-atom masks flag by flag.
+fn zip_masks(a: Vec<bool>, b: Vec<bool>, op: impl Fn(bool, bool) -> bool) -> Vec<bool> {
+    a.into_iter().zip(b).map(|(a, b)| op(a, b)).collect()
+}
+
+/// Builds neighbor lists from bond endpoints.
+fn adjacency(n: usize, bonds: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut neighbors = vec![Vec::new(); n];
+    for &(a, b) in bonds {
+        neighbors[a].push(b);
+        neighbors[b].push(a);
+    }
+    neighbors
+}
+
+/// Flags the atoms of a graph that lie on a cycle, i.e. carry at least one non-bridge bond.
+fn graph_ring_atoms(n: usize, bonds: &[(usize, usize)]) -> Vec<bool> {
+    let bridges = validation::bridges(n, bonds);
+    let mut ring_atoms = vec![false; n];
+    for (bond, &(a, b)) in bonds.iter().enumerate() {
+        if a != b && bridges.binary_search(&bond).is_err() {
+            ring_atoms[a] = true;
+            ring_atoms[b] = true;
+        }
+    }
+    ring_atoms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::metadata::{AtomMetadata, ResidueId};
+    use crate::io::smiles::parse_smiles;
+
+    #[test]
+    fn ids_are_sorted_and_unique() {
+        let selection = AtomSelection::from_ids([5, 3, 5, 0]);
+        assert_eq!(selection.atom_ids(), [0, 3, 5]);
+        assert_eq!(selection.len(), 3);
+    }
+
+    #[test]
+    fn insert_reports_new_atoms_and_keeps_order() {
+        let mut selection = AtomSelection::new();
+        assert!(selection.is_empty());
+        assert!(selection.insert(7));
+        assert!(selection.insert(2));
+        assert!(!selection.insert(7));
+        assert_eq!(selection.atom_ids(), [2, 7]);
+        assert!(selection.contains(2));
+        assert!(!selection.contains(3));
+    }
+
+    #[test]
+    fn graph_queries_combine_element_ring_and_distance_tests() {
+        // Toluene: methyl carbon 0, ring carbons 1-6, then hydrogens.
+        let graph = parse_smiles("Cc1ccccc1").unwrap();
+        let ring = SelectionQuery::in_ring().select(&graph).unwrap();
+        assert_eq!(ring.atom_ids(), [1, 2, 3, 4, 5, 6]);
+
+        let hydrogens = SelectionQuery::element(Element::H);
+        let methyl = SelectionQuery::within(0, 1)
+            .and(hydrogens.clone().or(SelectionQuery::element(Element::C)))
+            .and(!SelectionQuery::in_ring());
+        assert_eq!(methyl.select(&graph).unwrap().atom_ids(), [0, 7, 8, 9]);
+        assert_eq!(
+            SelectionQuery::all().select(&graph).unwrap().len(),
+            graph.atoms.len()
+        );
+    }
+
+    #[test]
+    fn residue_names_are_read_from_metadata() {
+        let mut graph = parse_smiles("CCO").unwrap();
+        for id in [1, 2] {
+            graph
+                .set_atom_metadata(
+                    id,
+                    AtomMetadata::default().with_residue(ResidueId::new("LIG", 'A', 1)),
+                )
+                .unwrap();
+        }
+        let selection = SelectionQuery::residue_name("LIG").select(&graph).unwrap();
+        assert_eq!(selection.atom_ids(), [1, 2]);
+    }
+
+    #[test]
+    fn atom_types_need_a_topology() {
+        let graph = parse_smiles("c1ccccc1O").unwrap();
+        let query = SelectionQuery::atom_type("C_R");
+        assert_eq!(query.select(&graph), Err(SelectionError::TypesUnavailable));
+
+        let topology = crate::assign_topology(&graph).unwrap();
+        let selection = query.select_in_topology(&topology).unwrap();
+        assert_eq!(selection.atom_ids(), [0, 1, 2, 3, 4, 5]);
+        let ring = SelectionQuery::in_ring()
+            .select_in_topology(&topology)
+            .unwrap();
+        assert_eq!(ring, selection);
+    }
+
+    #[test]
+    fn distance_tests_reject_missing_anchors() {
+        let graph = parse_smiles("CC").unwrap();
+        assert_eq!(
+            SelectionQuery::within(9, 1).select(&graph),
+            Err(SelectionError::AtomOutOfRange {
+                atom_id: 9,
+                atom_count: graph.atoms.len(),
+            })
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Stereo descriptors of tetrahedral centers and double bonds.
+//!
+//! Both descriptors are defined relative to atom IDs rather than CIP priorities, so they can be
+//! read and written without ranking substituents and never depend on the assigned types. Whenever
+//! atoms are renumbered or removed, the descriptors are recomputed so that they keep describing
+//! the same spatial arrangement.
+
+use crate::compat::prelude::*;
+
+/// Configuration of a tetrahedral stereocenter.
+///
+/// The neighbors of the center are taken in ascending atom ID order; for a three-coordinate
+/// center the missing substituent (an implicit hydrogen or a lone pair) comes first. Looking
+/// from the first neighbor toward the center, the remaining neighbors run either clockwise or
+/// counterclockwise. When a SMILES string lists the neighbors in ascending ID order,
+/// `CounterClockwise` is `@` and `Clockwise` is `@@`.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::smiles::parse_smiles;
+/// use dreid_typer::Chirality;
+///
+/// // Neighbors of C1 in ID order: N0, C2, C3, then the hydrogen written in the bracket.
+/// let l_alanine = parse_smiles("N[C@@H](C)C(=O)O").unwrap();
+/// assert_eq!(l_alanine.atoms[1].chirality, Some(Chirality::Clockwise));
+/// assert_eq!(Chirality::Clockwise.inverted(), Chirality::CounterClockwise);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Chirality {
+    /// The remaining neighbors run clockwise.
+    Clockwise,
+    /// The remaining neighbors run counterclockwise.
+    CounterClockwise,
+}
+
+impl Chirality {
+    /// Returns the configuration of the mirror image.
+    pub fn inverted(self) -> Self {
+        match self {
+            Self::Clockwise => Self::CounterClockwise,
+            Self::CounterClockwise => Self::Clockwise,
+        }
+    }
+}
+
+/// Configuration of a double bond.
+///
+/// On each end of the bond, the reference substituent is the neighbor with the lowest atom ID
+/// other than the opposite end. The descriptor tells whether the two reference substituents lie
+/// on the same side of the bond.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::smiles::parse_smiles;
+/// use dreid_typer::BondStereo;
+///
+/// let trans = parse_smiles("F/C=C/F").unwrap();
+/// assert_eq!(trans.bonds[1].stereo, Some(BondStereo::Trans));
+/// assert_eq!(BondStereo::Trans.inverted(), BondStereo::Cis);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BondStereo {
+    /// The reference substituents lie on the same side.
+    Cis,
+    /// The reference substituents lie on opposite sides.
+    Trans,
+}
+
+impl BondStereo {
+    /// Returns the opposite configuration.
+    pub fn inverted(self) -> Self {
+        match self {
+            Self::Cis => Self::Trans,
+            Self::Trans => Self::Cis,
+        }
+    }
+}
+
+/// Returns `true` if sorting `keys` ascending takes an odd number of swaps.
+///
+/// `None` sorts before every ID, matching the position of a missing substituent.
+pub(crate) fn is_odd_permutation(keys: &[Option<usize>]) -> bool {
+    let mut inversions = 0;
+    for (i, a) in keys.iter().enumerate() {
+        inversions += keys[i + 1..].iter().filter(|&b| a > b).count();
+    }
+    inversions % 2 == 1
+}
+
+/// Re-expresses a chirality after renumbering the atoms around its center.
+///
+/// # Arguments
+///
+/// * `chirality` - Configuration relative to the old IDs.
+/// * `neighbors` - Old IDs of the center's neighbors, ascending.
+/// * `map` - New ID of an old atom, or `None` if the atom was removed.
+///
+/// # Returns
+///
+/// The configuration relative to the new IDs, or `None` if more than one substituent is
+/// missing afterwards.
+pub(crate) fn remap_chirality(
+    chirality: Chirality,
+    neighbors: &[usize],
+    map: impl Fn(usize) -> Option<usize>,
+) -> Option<Chirality> {
+    let mut keys = Vec::with_capacity(4);
+    if neighbors.len() == 3 {
+        keys.push(None);
+    }
+    keys.extend(neighbors.iter().map(|&neighbor| map(neighbor)));
+    if keys.iter().filter(|key| key.is_none()).count() > 1 {
+        return None;
+    }
+    Some(if is_odd_permutation(&keys) {
+        chirality.inverted()
+    } else {
+        chirality
+    })
+}
+
+/// Re-expresses a double-bond configuration after renumbering the atoms around it.
+///
+/// # Arguments
+///
+/// * `stereo` - Configuration relative to the old IDs.
+/// * `ends` - Old IDs of the two bonded atoms.
+/// * `neighbors` - Old IDs of every atom's neighbors, ascending.
+/// * `map` - New ID of an old atom, or `None` if the atom was removed.
+///
+/// # Returns
+///
+/// The configuration relative to the new IDs, or `None` if an end has no substituent left.
+pub(crate) fn remap_bond_stereo(
+    stereo: BondStereo,
+    ends: (usize, usize),
+    neighbors: &[Vec<usize>],
+    map: impl Fn(usize) -> Option<usize>,
+) -> Option<BondStereo> {
+    let mut remapped = stereo;
+    for (end, opposite) in [(ends.0, ends.1), (ends.1, ends.0)] {
+        let substituents = neighbors[end].iter().filter(|&&n| n != opposite);
+        let old_reference = *substituents.clone().next()?;
+        let (_, new_reference) = substituents
+            .filter_map(|&n| map(n).map(|new_id| (new_id, n)))
+            .min()?;
+        if new_reference != old_reference {
+            remapped = remapped.inverted();
+        }
+    }
+    Some(remapped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chirality_follows_neighbor_swaps_and_removals() {
+        let chirality = Chirality::Clockwise;
+        let swap = |id: usize| Some([0, 2, 1, 3, 4][id]);
+        assert_eq!(
+            remap_chirality(chirality, &[1, 2, 3, 4], swap),
+            Some(Chirality::CounterClockwise)
+        );
+        assert_eq!(
+            remap_chirality(chirality, &[1, 2, 3, 4], |id| Some(id + 10)),
+            Some(chirality)
+        );
+
+        // Removing the highest neighbor moves it in front of the other three.
+        let drop_last = |id: usize| (id != 4).then_some(id);
+        assert_eq!(
+            remap_chirality(chirality, &[1, 2, 3, 4], drop_last),
+            Some(Chirality::CounterClockwise)
+        );
+        assert_eq!(
+            remap_chirality(chirality, &[1, 2, 3], drop_last),
+            Some(chirality)
+        );
+        assert_eq!(remap_chirality(chirality, &[1, 2, 4], drop_last), None);
+    }
+
+    #[test]
+    fn bond_stereo_flips_when_a_reference_substituent_changes() {
+        // 0 and 1 on atom 2, double bond 2=3, 4 and 5 on atom 3.
+        let neighbors = vec![
+            vec![2],
+            vec![2],
+            vec![0, 1, 3],
+            vec![2, 4, 5],
+            vec![3],
+            vec![3],
+        ];
+        let stereo = BondStereo::Cis;
+        let identity = |id: usize| Some(id);
+        assert_eq!(
+            remap_bond_stereo(stereo, (2, 3), &neighbors, identity),
+            Some(stereo)
+        );
+
+        let swap_one_end = |id: usize| Some([1, 0, 2, 3, 4, 5][id]);
+        assert_eq!(
+            remap_bond_stereo(stereo, (2, 3), &neighbors, swap_one_end),
+            Some(BondStereo::Trans)
+        );
+
+        let swap_both_ends = |id: usize| Some([1, 0, 2, 3, 5, 4][id]);
+        assert_eq!(
+            remap_bond_stereo(stereo, (2, 3), &neighbors, swap_both_ends),
+            Some(stereo)
+        );
+
+        let drop_side = |id: usize| (id > 1).then_some(id);
+        assert_eq!(
+            remap_bond_stereo(stereo, (2, 3), &neighbors, drop_side),
+            None
+        );
+    }
+}
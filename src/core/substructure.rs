@@ -0,0 +1,265 @@
+//! Substructure search that locates a query graph inside a target graph.
+//!
+//! The search is a VF2-style backtracking over query atoms in breadth-first order: each query
+//! atom is tried only on neighbors of the target atom its already matched parent was mapped to,
+//! and every bond to an already matched query atom must exist in the target with the same order.
+//! Target atoms may have more neighbors than their query atoms, so a query without hydrogens
+//! matches a target with explicit hydrogens.
+
+use super::graph::MolecularGraph;
+use super::properties::GraphBondOrder;
+use crate::compat::prelude::*;
+
+/// One placement of a query graph inside a target graph.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mapping {
+    /// Target atom matched to each query atom, indexed by query atom ID.
+    pub atom_ids: Vec<usize>,
+}
+
+impl Mapping {
+    /// Returns the target atom matched to a query atom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `query_atom` is not an atom of the query.
+    pub fn target(&self, query_atom: usize) -> usize {
+        self.atom_ids[query_atom]
+    }
+}
+
+/// Finds every placement of `query` inside `target`.
+///
+/// A placement maps query atoms to distinct target atoms of the same element such that every
+/// query bond joins two mapped atoms by a target bond of the same order. Additional target atoms
+/// and bonds are allowed. Automorphisms of the query produce separate mappings, so a symmetric
+/// query such as C-C is reported once per orientation.
+///
+/// Topology atom IDs equal the IDs of the input graph, so searching the input graph locates
+/// functional groups in the typed output as well.
+///
+/// # Arguments
+///
+/// * `query` - Substructure to look for; may have several connected components.
+/// * `target` - Graph to search.
+///
+/// # Returns
+///
+/// Every mapping, in the order the search finds them; an empty query yields one empty mapping.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::smiles::parse_smiles;
+/// use dreid_typer::{assign_topology, match_substructure, Element, GraphBondOrder, MolecularGraph};
+///
+/// // Carbonyl group: C=O.
+/// let mut carbonyl = MolecularGraph::new();
+/// let c = carbonyl.add_atom(Element::C);
+/// let o = carbonyl.add_atom(Element::O);
+/// carbonyl.add_bond(c, o, GraphBondOrder::Double).unwrap();
+///
+/// let acetone = parse_smiles("CC(=O)C").unwrap();
+/// let found = match_substructure(&carbonyl, &acetone);
+/// assert_eq!(found.len(), 1);
+///
+/// let topology = assign_topology(&acetone).unwrap();
+/// assert_eq!(topology.atoms[found[0].target(o)].atom_type, "O_2");
+/// ```
+pub fn match_substructure(query: &MolecularGraph, target: &MolecularGraph) -> Vec<Mapping> {
+    let search = Search::new(query, target);
+    let mut atoms = vec![None; query.atoms.len()];
+    let mut used = vec![false; target.atoms.len()];
+    let mut found = Vec::new();
+    search.extend(0, &mut atoms, &mut used, &mut found);
+    found
+}
+
+/// Precomputed adjacency and visiting order for one search.
+struct Search<'a> {
+    query: &'a MolecularGraph,
+    target: &'a MolecularGraph,
+    /// Neighbors and bond orders of every query atom.
+    query_neighbors: Vec<Vec<(usize, GraphBondOrder)>>,
+    /// Neighbors and bond orders of every target atom.
+    target_neighbors: Vec<Vec<(usize, GraphBondOrder)>>,
+    /// Query atoms in breadth-first order, component by component.
+    order: Vec<usize>,
+    /// Query atom each entry of `order` is reached from; `None` for the first atom of a component.
+    parents: Vec<Option<usize>>,
+}
+
+impl<'a> Search<'a> {
+    fn new(query: &'a MolecularGraph, target: &'a MolecularGraph) -> Self {
+        let query_neighbors = neighbors(query);
+        let target_neighbors = neighbors(target);
+
+        let n = query.atoms.len();
+        let mut order = Vec::with_capacity(n);
+        let mut parents = Vec::with_capacity(n);
+        let mut visited = vec![false; n];
+        for root in 0..n {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let start = order.len();
+            order.push(root);
+            parents.push(None);
+            let mut next = start;
+            while next < order.len() {
+                let atom = order[next];
+                next += 1;
+                for &(neighbor, _) in &query_neighbors[atom] {
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        order.push(neighbor);
+                        parents.push(Some(atom));
+                    }
+                }
+            }
+        }
+
+        Self {
+            query,
+            target,
+            query_neighbors,
+            target_neighbors,
+            order,
+            parents,
+        }
+    }
+
+    /// Maps `order[depth..]` in every feasible way, recording each complete mapping.
+    fn extend(
+        &self,
+        depth: usize,
+        atoms: &mut [Option<usize>],
+        used: &mut [bool],
+        found: &mut Vec<Mapping>,
+    ) {
+        if depth == self.order.len() {
+            found.push(Mapping {
+                atom_ids: atoms.iter().map(|atom| atom.expect("mapped")).collect(),
+            });
+            return;
+        }
+
+        let atom = self.order[depth];
+        let mut try_candidate = |candidate: usize, atoms: &mut [Option<usize>]| {
+            if self.is_feasible(atom, candidate, atoms, used) {
+                atoms[atom] = Some(candidate);
+                used[candidate] = true;
+                self.extend(depth + 1, atoms, used, found);
+                atoms[atom] = None;
+                used[candidate] = false;
+            }
+        };
+        match self.parents[depth] {
+            Some(parent) => {
+                let parent = atoms[parent].expect("parents are mapped first");
+                for &(candidate, _) in &self.target_neighbors[parent] {
+                    try_candidate(candidate, atoms);
+                }
+            }
+            None => {
+                for candidate in 0..self.target.atoms.len() {
+                    try_candidate(candidate, atoms);
+                }
+            }
+        }
+    }
+
+    /// Checks element, degree, and the bonds to already mapped query atoms.
+    fn is_feasible(
+        &self,
+        atom: usize,
+        candidate: usize,
+        atoms: &[Option<usize>],
+        used: &[bool],
+    ) -> bool {
+        !used[candidate]
+            && self.query.atoms[atom].element == self.target.atoms[candidate].element
+            && self.target_neighbors[candidate].len() >= self.query_neighbors[atom].len()
+            && self.query_neighbors[atom].iter().all(|&(neighbor, order)| {
+                atoms[neighbor].is_none_or(|mapped| {
+                    self.target_neighbors[candidate].contains(&(mapped, order))
+                })
+            })
+    }
+}
+
+/// Builds the neighbor list of every atom, ignoring bonds to atoms outside the graph.
+pub(crate) fn neighbors(graph: &MolecularGraph) -> Vec<Vec<(usize, GraphBondOrder)>> {
+    let n = graph.atoms.len();
+    let mut neighbors = vec![Vec::new(); n];
+    for bond in &graph.bonds {
+        let (a, b) = bond.atom_ids;
+        if a < n && b < n {
+            neighbors[a].push((b, bond.order));
+            neighbors[b].push((a, bond.order));
+        }
+    }
+    neighbors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::properties::Element;
+    use crate::io::smiles::parse_smiles;
+
+    fn heavy(smiles: &str) -> MolecularGraph {
+        let graph = parse_smiles(smiles).unwrap();
+        let heavy: Vec<usize> = graph
+            .atoms
+            .iter()
+            .filter(|atom| atom.element != Element::H)
+            .map(|atom| atom.id)
+            .collect();
+        graph.subgraph(&heavy).0
+    }
+
+    #[test]
+    fn symmetric_queries_are_reported_once_per_orientation() {
+        let ethane = parse_smiles("CC").unwrap();
+        assert_eq!(match_substructure(&heavy("CC"), &ethane).len(), 2);
+
+        let found = match_substructure(&heavy("CO"), &parse_smiles("CCO").unwrap());
+        assert_eq!(
+            found,
+            [Mapping {
+                atom_ids: vec![1, 2]
+            }]
+        );
+    }
+
+    #[test]
+    fn bond_orders_and_elements_must_agree() {
+        let acetic_acid = parse_smiles("CC(=O)O").unwrap();
+        let carbonyl = heavy("C=O");
+        let found = match_substructure(&carbonyl, &acetic_acid);
+        assert_eq!(
+            found,
+            [Mapping {
+                atom_ids: vec![1, 2]
+            }]
+        );
+
+        assert!(match_substructure(&heavy("C=N"), &acetic_acid).is_empty());
+        assert!(match_substructure(&heavy("C#C"), &parse_smiles("C=C").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn disconnected_and_empty_queries_are_supported() {
+        let target = parse_smiles("OCCO").unwrap();
+        let two_oxygens = heavy("O.O");
+        assert_eq!(match_substructure(&two_oxygens, &target).len(), 2);
+
+        let empty = MolecularGraph::new();
+        assert_eq!(
+            match_substructure(&empty, &target),
+            [Mapping { atom_ids: vec![] }]
+        );
+    }
+}
@@ -0,0 +1,441 @@
+//! Expansion of a typed unit cell into a supercell.
+//!
+//! A periodic topology records on every bond that crosses a cell face which image of its second
+//! atom the bond reaches. [`MolecularTopology::replicate`](crate::MolecularTopology::replicate)
+//! follows these records to lay copies of the cell side by side: every term is placed once per
+//! copy, each of its atoms taken from the copy that the term's bonds lead to, and bonds leaving
+//! the supercell wrap around to the copy on the opposite face.
+
+use super::error::ReplicationError;
+use super::periodic::ImageFlags;
+use super::stereo;
+use super::topology::{Angle, Atom, Bond, HydrogenBondDonor, Inversion, MolecularTopology, Ring};
+use crate::compat::collections::HashMap;
+use crate::compat::prelude::*;
+
+impl MolecularTopology {
+    /// Expands the topology of a periodic unit cell into a supercell of `nx * ny * nz` cells.
+    ///
+    /// Atom `i` of the copy at lattice position `(x, y, z)` becomes atom `c * n + i`, where `n`
+    /// is the atom count of the cell and `c = x + nx * (y + ny * z)`. Bonds, angles, torsions,
+    /// inversions, hydrogen-bond sites, and rings are placed once per copy, following bond images
+    /// into the neighboring copies. Bonds that leave the supercell wrap around to the opposite
+    /// face and keep an image in units of the supercell vectors, so the result can be replicated
+    /// again or written out for a periodic simulation. Fragment IDs are recomputed over the
+    /// supercell, and stereo descriptors follow the new atom IDs.
+    ///
+    /// # Arguments
+    ///
+    /// * `nx` - Copies along the `a` cell vector.
+    /// * `ny` - Copies along the `b` cell vector.
+    /// * `nz` - Copies along the `c` cell vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReplicationError::EmptySupercell`] if a count is zero, and
+    /// [`ReplicationError::MissingBond`] if consecutive atoms of a term are not bonded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{Element, GraphBondOrder, PeriodicMolecularGraph, Typer};
+    ///
+    /// // Polyethylene: four CH2 groups per cell along x.
+    /// let mut cell =
+    ///     PeriodicMolecularGraph::new([[5.1, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]]);
+    /// let c: Vec<usize> = (0..4).map(|_| cell.add_atom(Element::C)).collect();
+    /// for i in 0..4 {
+    ///     let image = if i == 3 { [1, 0, 0] } else { [0, 0, 0] };
+    ///     cell.add_periodic_bond(c[i], c[(i + 1) % 4], GraphBondOrder::Single, image)
+    ///         .unwrap();
+    ///     for _ in 0..2 {
+    ///         let h = cell.add_atom(Element::H);
+    ///         cell.add_bond(c[i], h, GraphBondOrder::Single).unwrap();
+    ///     }
+    /// }
+    ///
+    /// let topology = Typer::new().assign_periodic_topology(&cell).unwrap();
+    /// let supercell = topology.replicate(3, 1, 1).unwrap();
+    /// assert_eq!(supercell.atoms.len(), 3 * 12);
+    /// assert_eq!(supercell.torsions.len(), 3 * topology.torsions.len());
+    /// // The last carbon of the third copy bonds across the supercell face to the first carbon.
+    /// let wrap = supercell.bonds.iter().find(|bond| bond.atom_ids == (0, 27)).unwrap();
+    /// assert_eq!(wrap.image, [-1, 0, 0]);
+    /// ```
+    pub fn replicate(
+        &self,
+        nx: usize,
+        ny: usize,
+        nz: usize,
+    ) -> Result<MolecularTopology, ReplicationError> {
+        let counts = [nx, ny, nz];
+        if counts.contains(&0) {
+            return Err(ReplicationError::EmptySupercell { counts });
+        }
+        let lattice = Lattice {
+            counts,
+            cell_atoms: self.atoms.len(),
+        };
+        let images = BondImages::new(&self.bonds);
+        let mut neighbors = vec![Vec::new(); self.atoms.len()];
+        for bond in &self.bonds {
+            let (a, b) = bond.atom_ids;
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+        for list in &mut neighbors {
+            list.sort_unstable();
+        }
+        let copies = lattice.copies();
+        let mut supercell = MolecularTopology::default();
+
+        for copy in 0..copies {
+            supercell.atoms.extend(self.atoms.iter().map(|atom| {
+                let chirality = atom.chirality.and_then(|chirality| {
+                    stereo::remap_chirality(chirality, &neighbors[atom.id], |neighbor| {
+                        let offset = images.between(atom.id, neighbor).ok()?;
+                        Some(lattice.id(copy, neighbor, offset))
+                    })
+                });
+                Atom {
+                    id: lattice.id(copy, atom.id, [0; 3]),
+                    chirality,
+                    ..atom.clone()
+                }
+            }));
+        }
+
+        for bond in &self.bonds {
+            let (a, b) = bond.atom_ids;
+            for copy in 0..copies {
+                let (id2, image) = lattice.place(copy, b, bond.image);
+                let stereo = bond.stereo.and_then(|configuration| {
+                    stereo::remap_bond_stereo(configuration, (a, b), &neighbors, |neighbor| {
+                        let offset = images
+                            .between(a, neighbor)
+                            .or_else(|_| images.between(b, neighbor).map(|o| add(bond.image, o)))
+                            .ok()?;
+                        Some(lattice.id(copy, neighbor, offset))
+                    })
+                });
+                supercell.bonds.push(
+                    Bond {
+                        stereo,
+                        image,
+                        ..bond.clone()
+                    }
+                    .renumbered(lattice.id(copy, a, [0; 3]), id2),
+                );
+            }
+        }
+        for angle in &self.angles {
+            let (a, center, b) = angle.atom_ids;
+            let offsets = images.path(&[a, center, b])?;
+            for copy in 0..copies {
+                let [a, center, b] = lattice.ids(copy, [a, center, b], &offsets);
+                supercell.angles.push(Angle::new(a, center, b, angle.class));
+            }
+        }
+        for (source, target) in [
+            (&self.torsions, &mut supercell.torsions),
+            (
+                &self.suppressed_torsions,
+                &mut supercell.suppressed_torsions,
+            ),
+        ] {
+            for torsion in source {
+                let (i, j, k, l) = torsion.atom_ids;
+                let offsets = images.path(&[i, j, k, l])?;
+                for copy in 0..copies {
+                    let [i, j, k, l] = lattice.ids(copy, [i, j, k, l], &offsets);
+                    target.push(torsion.renumbered(i, j, k, l));
+                }
+            }
+        }
+        for inversion in &self.inversions {
+            let (center, axis, p1, p2) = inversion.atom_ids;
+            let offsets = [
+                [0; 3],
+                images.between(center, axis)?,
+                images.between(center, p1)?,
+                images.between(center, p2)?,
+            ];
+            for copy in 0..copies {
+                let [center, axis, p1, p2] = lattice.ids(copy, [center, axis, p1, p2], &offsets);
+                supercell
+                    .inversions
+                    .push(Inversion::new(center, axis, p1, p2, inversion.class));
+            }
+        }
+        for donor in &self.hb_donors {
+            let offset = images.between(donor.donor_id, donor.hydrogen_id)?;
+            for copy in 0..copies {
+                supercell.hb_donors.push(HydrogenBondDonor {
+                    donor_id: lattice.id(copy, donor.donor_id, [0; 3]),
+                    hydrogen_id: lattice.id(copy, donor.hydrogen_id, offset),
+                });
+            }
+        }
+        for copy in 0..copies {
+            supercell.hb_acceptors.extend(
+                self.hb_acceptors
+                    .iter()
+                    .map(|&acceptor| lattice.id(copy, acceptor, [0; 3])),
+            );
+        }
+        for ring in &self.rings {
+            let offsets = images.path(&ring.atom_ids)?;
+            for copy in 0..copies {
+                supercell.rings.push(Ring {
+                    atom_ids: ring
+                        .atom_ids
+                        .iter()
+                        .zip(&offsets)
+                        .map(|(&atom, &offset)| lattice.id(copy, atom, offset))
+                        .collect(),
+                    is_aromatic: ring.is_aromatic,
+                    system_id: ring.system_id,
+                });
+            }
+        }
+
+        supercell.hb_donors.sort_unstable_by_key(|d| d.hydrogen_id);
+        supercell.hb_acceptors.sort_unstable();
+        supercell.normalize_rings();
+        let molecule_of = supercell.molecule_indices();
+        for atom in &mut supercell.atoms {
+            atom.fragment_id = molecule_of[atom.id];
+        }
+        Ok(supercell)
+    }
+}
+
+/// Arrangement of cell copies in a supercell.
+struct Lattice {
+    /// Copies along each cell vector.
+    counts: [usize; 3],
+    /// Atoms per cell.
+    cell_atoms: usize,
+}
+
+impl Lattice {
+    /// Returns the number of cell copies.
+    fn copies(&self) -> usize {
+        self.counts.iter().product()
+    }
+
+    /// Locates the image of `atom` reached from copy `copy` by the lattice translation `offset`.
+    ///
+    /// # Returns
+    ///
+    /// The supercell ID of the atom and the supercell image it lies in.
+    fn place(&self, copy: usize, atom: usize, offset: ImageFlags) -> (usize, ImageFlags) {
+        let mut rest = copy;
+        let mut stride = 1;
+        let mut target = 0;
+        let mut image = [0; 3];
+        for axis in 0..3 {
+            let count = self.counts[axis];
+            let position = (rest % count) as i64 + i64::from(offset[axis]);
+            rest /= count;
+            target += position.rem_euclid(count as i64) as usize * stride;
+            image[axis] = position.div_euclid(count as i64) as i32;
+            stride *= count;
+        }
+        (target * self.cell_atoms + atom, image)
+    }
+
+    /// Returns the supercell ID of the image of `atom` reached from copy `copy` by `offset`.
+    fn id(&self, copy: usize, atom: usize, offset: ImageFlags) -> usize {
+        self.place(copy, atom, offset).0
+    }
+
+    /// Returns the supercell IDs of the atoms of a term placed at copy `copy`.
+    fn ids<const N: usize>(
+        &self,
+        copy: usize,
+        atoms: [usize; N],
+        offsets: &[ImageFlags],
+    ) -> [usize; N] {
+        core::array::from_fn(|i| self.id(copy, atoms[i], offsets[i]))
+    }
+}
+
+/// Image flags of the bonds of a cell, keyed by canonical atom pair.
+struct BondImages(HashMap<(usize, usize), ImageFlags>);
+
+impl BondImages {
+    fn new(bonds: &[Bond]) -> Self {
+        Self(
+            bonds
+                .iter()
+                .map(|bond| (bond.atom_ids, bond.image))
+                .collect(),
+        )
+    }
+
+    /// Returns the image of `b` relative to a bonded atom `a`.
+    fn between(&self, a: usize, b: usize) -> Result<ImageFlags, ReplicationError> {
+        let image = if a < b {
+            self.0.get(&(a, b)).copied()
+        } else {
+            self.0.get(&(b, a)).map(|image| image.map(|t| -t))
+        };
+        image.ok_or(ReplicationError::MissingBond {
+            atom1_id: a,
+            atom2_id: b,
+        })
+    }
+
+    /// Returns the image of every atom of a bonded path relative to its first atom.
+    fn path(&self, atoms: &[usize]) -> Result<Vec<ImageFlags>, ReplicationError> {
+        let mut offsets = Vec::with_capacity(atoms.len());
+        offsets.push([0; 3]);
+        for pair in atoms.windows(2) {
+            let step = self.between(pair[0], pair[1])?;
+            let previous = offsets[offsets.len() - 1];
+            offsets.push(add(previous, step));
+        }
+        Ok(offsets)
+    }
+}
+
+/// Adds two lattice translations.
+fn add(a: ImageFlags, b: ImageFlags) -> ImageFlags {
+    core::array::from_fn(|axis| a[axis] + b[axis])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat::collections::HashSet;
+    use crate::core::periodic::PeriodicMolecularGraph;
+    use crate::core::properties::{Element, GraphBondOrder};
+    use crate::pipeline::Typer;
+    use std::hash::Hash;
+
+    fn as_set<T: Eq + Hash>(items: &[T]) -> HashSet<&T> {
+        items.iter().collect()
+    }
+
+    /// Builds the periodic graph of the supercell explicitly, numbered like `replicate`.
+    fn explicit_supercell(
+        cell: &PeriodicMolecularGraph,
+        counts: [usize; 3],
+    ) -> PeriodicMolecularGraph {
+        let lattice = Lattice {
+            counts,
+            cell_atoms: cell.graph.atoms.len(),
+        };
+        let mut supercell = PeriodicMolecularGraph::new(cell.cell);
+        for _ in 0..lattice.copies() {
+            for atom in &cell.graph.atoms {
+                supercell.add_atom(atom.element);
+            }
+        }
+        for copy in 0..lattice.copies() {
+            for (edge, &image) in cell.graph.bonds.iter().zip(&cell.bond_images) {
+                let (a, b) = edge.atom_ids;
+                let (b, image) = lattice.place(copy, b, image);
+                supercell
+                    .add_periodic_bond(lattice.id(copy, a, [0; 3]), b, edge.order, image)
+                    .unwrap();
+            }
+        }
+        supercell
+    }
+
+    /// Four CH2 groups per cell, bonded into an infinite chain along `a`.
+    fn polyethylene() -> PeriodicMolecularGraph {
+        let mut cell =
+            PeriodicMolecularGraph::new([[5.1, 0.0, 0.0], [0.0, 5.0, 0.0], [0.0, 0.0, 5.0]]);
+        let c: Vec<usize> = (0..4).map(|_| cell.add_atom(Element::C)).collect();
+        for i in 0..4 {
+            let image = if i == 3 { [1, 0, 0] } else { [0, 0, 0] };
+            cell.add_periodic_bond(c[i], c[(i + 1) % 4], GraphBondOrder::Single, image)
+                .unwrap();
+            for _ in 0..2 {
+                let h = cell.add_atom(Element::H);
+                cell.add_bond(c[i], h, GraphBondOrder::Single).unwrap();
+            }
+        }
+        cell
+    }
+
+    /// Phenol whose ring crosses the `a` and `c` faces of the cell.
+    fn phenol() -> PeriodicMolecularGraph {
+        let mut cell =
+            PeriodicMolecularGraph::new([[6.0, 0.0, 0.0], [0.0, 6.0, 0.0], [0.0, 0.0, 6.0]]);
+        let c: Vec<usize> = (0..6).map(|_| cell.add_atom(Element::C)).collect();
+        let images = [
+            [0, 0, 0],
+            [0, 0, 1],
+            [1, 0, 0],
+            [0, 0, 0],
+            [-1, 0, -1],
+            [0, 0, 0],
+        ];
+        for i in 0..6 {
+            cell.add_periodic_bond(c[i], c[(i + 1) % 6], GraphBondOrder::Aromatic, images[i])
+                .unwrap();
+        }
+        let o = cell.add_atom(Element::O);
+        cell.add_periodic_bond(c[0], o, GraphBondOrder::Single, [0, 1, 0])
+            .unwrap();
+        let h = cell.add_atom(Element::H);
+        cell.add_periodic_bond(o, h, GraphBondOrder::Single, [0, 0, -1])
+            .unwrap();
+        for &carbon in &c[1..] {
+            let h = cell.add_atom(Element::H);
+            cell.add_bond(carbon, h, GraphBondOrder::Single).unwrap();
+        }
+        cell
+    }
+
+    #[test]
+    fn replicated_cells_equal_typed_supercells() {
+        let typer = Typer::new();
+        for (cell, counts) in [
+            (polyethylene(), [3, 1, 1]),
+            (polyethylene(), [2, 2, 1]),
+            (phenol(), [2, 1, 2]),
+            (phenol(), [1, 1, 1]),
+        ] {
+            let replicated = typer
+                .assign_periodic_topology(&cell)
+                .unwrap()
+                .replicate(counts[0], counts[1], counts[2])
+                .unwrap();
+            let explicit = typer
+                .assign_periodic_topology(&explicit_supercell(&cell, counts))
+                .unwrap();
+
+            assert_eq!(replicated.atoms, explicit.atoms);
+            assert_eq!(as_set(&replicated.bonds), as_set(&explicit.bonds));
+            assert_eq!(as_set(&replicated.angles), as_set(&explicit.angles));
+            assert_eq!(as_set(&replicated.torsions), as_set(&explicit.torsions));
+            assert_eq!(as_set(&replicated.inversions), as_set(&explicit.inversions));
+            assert_eq!(replicated.hb_donors, explicit.hb_donors);
+            assert_eq!(replicated.hb_acceptors, explicit.hb_acceptors);
+            assert_eq!(replicated.rings, explicit.rings);
+        }
+    }
+
+    #[test]
+    fn invalid_supercells_are_rejected() {
+        let topology = Typer::new()
+            .assign_periodic_topology(&polyethylene())
+            .unwrap();
+        assert_eq!(
+            topology.replicate(2, 0, 1),
+            Err(ReplicationError::EmptySupercell { counts: [2, 0, 1] })
+        );
+
+        let mut broken = topology.clone();
+        broken.bonds.retain(|bond| bond.atom_ids != (0, 1));
+        assert!(matches!(
+            broken.replicate(2, 1, 1),
+            Err(ReplicationError::MissingBond { .. })
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+//! Per-atom and per-bond lookups of the terms stored in a typed topology.
+//!
+//! Analysis code often needs the terms around one atom, such as the angles that bend about a
+//! metal center or the torsions that turn a given bond. The term lists of a
+//! [`MolecularTopology`](crate::MolecularTopology) are flat, so each such query would scan them in
+//! full. A [`TermIndex`](crate::TermIndex) borrows a topology, builds an adjacency index for one
+//! kind of term on first use, and answers every later query from it. Because it borrows the
+//! topology, the topology cannot change while the index is alive, so its indices are never
+//! stale.
+
+use super::topology::{Angle, Bond, MolecularTopology, Torsion};
+use crate::compat::collections::HashMap;
+use crate::compat::prelude::*;
+use crate::compat::sync::OnceLock;
+use core::fmt;
+
+/// Lazily indexed view of the terms of a [`MolecularTopology`].
+///
+/// Created with [`MolecularTopology::term_index`]. Each kind of query builds its index on
+/// first use; keep the view around to reuse the indices across queries.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+///
+/// // Methanol.
+/// let mut graph = MolecularGraph::new();
+/// let c = graph.add_atom(Element::C);
+/// let o = graph.add_atom(Element::O);
+/// graph.add_bond(c, o, GraphBondOrder::Single).unwrap();
+/// for _ in 0..3 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+/// }
+/// let h = graph.add_atom(Element::H);
+/// graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+///
+/// let topology = assign_topology(&graph).unwrap();
+/// let index = topology.term_index();
+/// assert_eq!(index.bonds_of(c).count(), 4);
+/// assert_eq!(index.bonds_of(o).count(), 2);
+/// ```
+pub struct TermIndex<'a> {
+    topology: &'a MolecularTopology,
+    bonds_by_atom: OnceLock<Vec<Vec<usize>>>,
+    angles_by_atom: OnceLock<Vec<Vec<usize>>>,
+    torsions_by_bond: OnceLock<HashMap<(usize, usize), Vec<usize>>>,
+}
+
+impl fmt::Debug for TermIndex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TermIndex")
+            .field("bonds_by_atom", &self.bonds_by_atom.get().is_some())
+            .field("angles_by_atom", &self.angles_by_atom.get().is_some())
+            .field("torsions_by_bond", &self.torsions_by_bond.get().is_some())
+            .finish()
+    }
+}
+
+/// Groups term positions by the atoms each term contains.
+fn index_by_atom<T: IntoIterator<Item = usize>>(
+    atom_count: usize,
+    terms: impl Iterator<Item = T>,
+) -> Vec<Vec<usize>> {
+    let mut by_atom: Vec<Vec<usize>> = vec![Vec::new(); atom_count];
+    for (position, atom_ids) in terms.enumerate() {
+        for atom_id in atom_ids {
+            if atom_id >= by_atom.len() {
+                by_atom.resize_with(atom_id + 1, Vec::new);
+            }
+            by_atom[atom_id].push(position);
+        }
+    }
+    by_atom
+}
+
+/// Returns the bond `a-b` with its atom IDs in ascending order.
+fn bond_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+impl MolecularTopology {
+    /// Returns a view that answers per-atom and per-bond term queries from lazily built indices.
+    pub fn term_index(&self) -> TermIndex<'_> {
+        TermIndex {
+            topology: self,
+            bonds_by_atom: OnceLock::new(),
+            angles_by_atom: OnceLock::new(),
+            torsions_by_bond: OnceLock::new(),
+        }
+    }
+}
+
+impl<'a> TermIndex<'a> {
+    /// Returns the bonds that have `atom_id` as one of their atoms, in list order.
+    ///
+    /// The first call builds a per-atom index of [`bonds`](MolecularTopology::bonds) that later
+    /// calls reuse.
+    pub fn bonds_of(&self, atom_id: usize) -> impl Iterator<Item = &'a Bond> + '_ {
+        let topology = self.topology;
+        let index = self.bonds_by_atom.get_or_init(|| {
+            index_by_atom(
+                topology.atoms.len(),
+                topology.bonds.iter().map(|bond| {
+                    let (i, j) = bond.atom_ids;
+                    [i, j]
+                }),
+            )
+        });
+        index
+            .get(atom_id)
+            .into_iter()
+            .flatten()
+            .map(move |&position| &topology.bonds[position])
+    }
+
+    /// Returns the angles that contain `atom_id` at any position, in list order.
+    ///
+    /// Angles centered on the atom are those whose middle atom ID equals `atom_id`. The index
+    /// over [`angles`](MolecularTopology::angles) is built like that of
+    /// [`bonds_of`](Self::bonds_of).
+    pub fn angles_of(&self, atom_id: usize) -> impl Iterator<Item = &'a Angle> + '_ {
+        let topology = self.topology;
+        let index = self.angles_by_atom.get_or_init(|| {
+            index_by_atom(
+                topology.atoms.len(),
+                topology.angles.iter().map(|angle| {
+                    let (i, j, k) = angle.atom_ids;
+                    [i, j, k]
+                }),
+            )
+        });
+        index
+            .get(atom_id)
+            .into_iter()
+            .flatten()
+            .map(move |&position| &topology.angles[position])
+    }
+
+    /// Returns the torsions in [`torsions`](MolecularTopology::torsions) that run through the
+    /// bond between atoms `bond.0` and `bond.1`, in list order.
+    ///
+    /// A torsion `i-j-k-l` runs through its central bond `j-k` and its terminal bonds `i-j` and
+    /// `k-l`; the order of the two atom IDs does not matter. Suppressed torsions are not
+    /// indexed. The index is built like that of [`bonds_of`](Self::bonds_of).
+    pub fn torsions_containing(
+        &self,
+        bond: (usize, usize),
+    ) -> impl Iterator<Item = &'a Torsion> + '_ {
+        let topology = self.topology;
+        let index = self.torsions_by_bond.get_or_init(|| {
+            let mut by_bond: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+            for (position, torsion) in topology.torsions.iter().enumerate() {
+                let (i, j, k, l) = torsion.atom_ids;
+                for key in [bond_key(i, j), bond_key(j, k), bond_key(k, l)] {
+                    by_bond.entry(key).or_default().push(position);
+                }
+            }
+            by_bond
+        });
+        index
+            .get(&bond_key(bond.0, bond.1))
+            .into_iter()
+            .flatten()
+            .map(move |&position| &topology.torsions[position])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assign_topology;
+    use crate::compat::prelude::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::properties::{Element, GraphBondOrder};
+
+    fn butane() -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        let carbons: Vec<usize> = (0..4).map(|_| graph.add_atom(Element::C)).collect();
+        for pair in carbons.windows(2) {
+            graph
+                .add_bond(pair[0], pair[1], GraphBondOrder::Single)
+                .unwrap();
+        }
+        for (position, &carbon) in carbons.iter().enumerate() {
+            let hydrogens = if position == 0 || position == 3 { 3 } else { 2 };
+            for _ in 0..hydrogens {
+                let h = graph.add_atom(Element::H);
+                graph.add_bond(carbon, h, GraphBondOrder::Single).unwrap();
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn indexed_queries_match_a_full_scan() {
+        let topology = assign_topology(&butane()).unwrap();
+        let index = topology.term_index();
+
+        for atom_id in 0..topology.atoms.len() {
+            let bonds: Vec<_> = index.bonds_of(atom_id).collect();
+            let scanned: Vec<_> = topology
+                .bonds
+                .iter()
+                .filter(|b| b.atom_ids.0 == atom_id || b.atom_ids.1 == atom_id)
+                .collect();
+            assert_eq!(bonds, scanned);
+
+            let angles: Vec<_> = index.angles_of(atom_id).collect();
+            let scanned: Vec<_> = topology
+                .angles
+                .iter()
+                .filter(|a| {
+                    let (i, j, k) = a.atom_ids;
+                    [i, j, k].contains(&atom_id)
+                })
+                .collect();
+            assert_eq!(angles, scanned);
+        }
+
+        // The central C-C bond carries its own 9 torsions and the 3 about each outer C-C bond.
+        assert_eq!(index.torsions_containing((1, 2)).count(), 9 + 2 * 3);
+        assert_eq!(
+            index.torsions_containing((2, 1)).count(),
+            index.torsions_containing((1, 2)).count()
+        );
+        assert_eq!(index.angles_of(1).filter(|a| a.atom_ids.1 == 1).count(), 6);
+        assert_eq!(index.bonds_of(topology.atoms.len()).count(), 0);
+    }
+
+    #[test]
+    fn indices_follow_the_topology_they_are_built_from() {
+        let mut topology = assign_topology(&butane()).unwrap();
+        assert_eq!(topology.term_index().bonds_of(0).count(), 4);
+
+        let reversed: Vec<usize> = (0..topology.atoms.len()).rev().collect();
+        topology.permute(&reversed).unwrap();
+        assert_eq!(topology.term_index().bonds_of(0).count(), 1);
+
+        topology.bonds.clear();
+        assert_eq!(topology.term_index().bonds_of(0).count(), 0);
+    }
+}
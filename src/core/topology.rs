@@ -5,7 +5,14 @@
 //! These types use `TopologyBondOrder`, which includes physical properties like
 //! resonance, unlike the input graph.
 
-use super::properties::{Element, Hybridization, TopologyBondOrder};
+use super::error::PermutationError;
+use super::metadata::AtomMetadata;
+use super::periodic::ImageFlags;
+use super::properties::{DreidingType, Element, Hybridization, TopologyBondOrder};
+use super::selection::AtomSelection;
+use super::stereo::{self, BondStereo, Chirality};
+use crate::compat::collections::HashMap;
+use crate::compat::prelude::*;
 
 /// Canonical topology produced after the typer assigns atom types and torsions.
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -18,8 +25,669 @@ pub struct MolecularTopology {
     pub angles: Vec<Angle>,
     /// A list of all four-atom torsions around rotatable bonds.
     pub torsions: Vec<Torsion>,
+    /// Torsions left out of `torsions` because their central bond has no DREIDING barrier.
+    ///
+    /// Empty unless torsion suppression is enabled with
+    /// [`Typer::with_torsion_suppression`](crate::Typer::with_torsion_suppression).
+    pub suppressed_torsions: Vec<Torsion>,
     /// A list of all four-atom inversions for planar centers.
     pub inversions: Vec<Inversion>,
+    /// Hydrogen-bond donors (`H_HB` hydrogens and their heavy atoms), sorted by hydrogen ID.
+    pub hb_donors: Vec<HydrogenBondDonor>,
+    /// IDs of hydrogen-bond acceptors (N, O, or F carrying lone pairs), sorted ascending.
+    pub hb_acceptors: Vec<usize>,
+    /// Smallest set of smallest rings, sorted by atom IDs.
+    pub rings: Vec<Ring>,
+    /// Lone-pair virtual sites, sorted by site ID.
+    ///
+    /// Empty unless site generation is enabled with
+    /// [`Typer::with_lone_pair_sites`](crate::Typer::with_lone_pair_sites).
+    pub virtual_sites: Vec<VirtualSite>,
+}
+
+impl MolecularTopology {
+    /// Reorders atoms so that new position `i` holds the atom previously at `new_order[i]`.
+    ///
+    /// Atom IDs are rewritten to match their new positions, and every bond, angle, torsion, and
+    /// inversion is remapped and re-canonicalized so the topology stays internally consistent.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_order` - Permutation listing old atom IDs in their desired new order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PermutationError`] if `new_order` is not a permutation of `0..atoms.len()`; the
+    /// topology is left untouched in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+    ///
+    /// let mut graph = MolecularGraph::new();
+    /// let h = graph.add_atom(Element::H);
+    /// let f = graph.add_atom(Element::F);
+    /// graph.add_bond(h, f, GraphBondOrder::Single).unwrap();
+    ///
+    /// let mut topology = assign_topology(&graph).unwrap();
+    /// topology.permute(&[1, 0]).unwrap();
+    /// assert_eq!(topology.atoms[0].element, Element::F);
+    /// assert_eq!(topology.atoms[0].id, 0);
+    /// assert_eq!(topology.bonds[0].atom_ids, (0, 1));
+    /// ```
+    pub fn permute(&mut self, new_order: &[usize]) -> Result<(), PermutationError> {
+        let n = self.atoms.len();
+        if new_order.len() != n {
+            return Err(PermutationError::LengthMismatch {
+                expected: n,
+                found: new_order.len(),
+            });
+        }
+
+        let mut old_to_new = vec![usize::MAX; n];
+        for (new_id, &old_id) in new_order.iter().enumerate() {
+            if old_id >= n {
+                return Err(PermutationError::OutOfRange { index: old_id });
+            }
+            if old_to_new[old_id] != usize::MAX {
+                return Err(PermutationError::Duplicate { index: old_id });
+            }
+            old_to_new[old_id] = new_id;
+        }
+        self.remap_stereo(|id| Some(old_to_new[id]));
+
+        let mut old_atoms: Vec<Option<Atom>> = self.atoms.drain(..).map(Some).collect();
+        self.atoms = new_order
+            .iter()
+            .enumerate()
+            .map(|(new_id, &old_id)| {
+                let mut atom = old_atoms[old_id].take().expect("permutation is validated");
+                atom.id = new_id;
+                atom
+            })
+            .collect();
+
+        let map = |id: usize| old_to_new[id];
+        for bond in &mut self.bonds {
+            let (a, b) = bond.atom_ids;
+            *bond = bond.renumbered(map(a), map(b));
+        }
+        for angle in &mut self.angles {
+            let (a, center, b) = angle.atom_ids;
+            *angle = Angle::new(map(a), map(center), map(b), angle.class);
+        }
+        for torsion in self
+            .torsions
+            .iter_mut()
+            .chain(&mut self.suppressed_torsions)
+        {
+            let (i, j, k, l) = torsion.atom_ids;
+            *torsion = torsion.renumbered(map(i), map(j), map(k), map(l));
+        }
+        for inversion in &mut self.inversions {
+            let (center, axis, p1, p2) = inversion.atom_ids;
+            *inversion = Inversion::new(map(center), map(axis), map(p1), map(p2), inversion.class);
+        }
+        for donor in &mut self.hb_donors {
+            donor.donor_id = map(donor.donor_id);
+            donor.hydrogen_id = map(donor.hydrogen_id);
+        }
+        self.hb_donors.sort_unstable_by_key(|d| d.hydrogen_id);
+        for acceptor in &mut self.hb_acceptors {
+            *acceptor = map(*acceptor);
+        }
+        self.hb_acceptors.sort_unstable();
+        for ring in &mut self.rings {
+            ring.atom_ids.iter_mut().for_each(|id| *id = map(*id));
+        }
+        self.normalize_rings();
+        for site in &mut self.virtual_sites {
+            site.site_id = map(site.site_id);
+            site.parent_id = map(site.parent_id);
+            site.frame_ids.iter_mut().for_each(|id| *id = map(*id));
+            site.frame_ids.sort_unstable();
+        }
+        self.virtual_sites.sort_unstable_by_key(|site| site.site_id);
+
+        Ok(())
+    }
+
+    /// Sorts every term list by atom IDs.
+    ///
+    /// Terms are generated in an unspecified order; sorting them after a canonical
+    /// [`permute`](Self::permute) makes two topologies of the same molecule directly comparable.
+    pub fn sort_terms(&mut self) {
+        self.bonds.sort_unstable_by_key(|bond| bond.atom_ids);
+        self.angles.sort_unstable_by_key(|angle| angle.atom_ids);
+        self.torsions
+            .sort_unstable_by_key(|torsion| torsion.atom_ids);
+        self.suppressed_torsions
+            .sort_unstable_by_key(|torsion| torsion.atom_ids);
+        self.inversions
+            .sort_unstable_by_key(|inversion| inversion.atom_ids);
+    }
+
+    /// Stably sorts atoms by a caller-supplied key and remaps all terms accordingly.
+    ///
+    /// Use this for orderings that depend on data outside the topology, such as residue
+    /// membership taken from the input structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Function computing the sort key for each atom.
+    ///
+    /// # Returns
+    ///
+    /// The applied permutation (old atom ID at each new position), which callers can use to
+    /// reorder coordinates or other per-atom data in step.
+    pub fn sort_atoms_by_key<K, F>(&mut self, mut key: F) -> Vec<usize>
+    where
+        K: Ord,
+        F: FnMut(&Atom) -> K,
+    {
+        let mut order: Vec<usize> = (0..self.atoms.len()).collect();
+        order.sort_by_cached_key(|&id| key(&self.atoms[id]));
+        self.permute(&order)
+            .expect("sorting produces a valid permutation");
+        order
+    }
+
+    /// Extracts the selected atoms and the terms fully contained in the selection.
+    ///
+    /// Selected atoms keep their relative order and are renumbered contiguously, so atom `i` of
+    /// the result is atom `selection.atom_ids()[i]` of this topology. Bonds, angles, torsions,
+    /// inversions, hydrogen-bond sites, rings, and virtual sites with an atom outside the
+    /// selection are dropped.
+    /// Selected IDs beyond the last atom are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `selection` - Atoms to extract.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::io::smiles::parse_smiles;
+    /// use dreid_typer::{assign_topology, SelectionQuery};
+    ///
+    /// // Ethanol next to a water molecule; pull out the water's terms only.
+    /// let graph = parse_smiles("CCO.O").unwrap();
+    /// let complex = assign_topology(&graph).unwrap();
+    /// let water = SelectionQuery::within(3, 1).select(&graph).unwrap();
+    ///
+    /// let subset = complex.subset(&water);
+    /// assert_eq!(subset.atoms.len(), 3);
+    /// assert_eq!(subset.atoms[0].atom_type, "O_3");
+    /// assert_eq!(subset.bonds.len(), 2);
+    /// assert_eq!(subset.angles.len(), 1);
+    /// ```
+    pub fn subset(&self, selection: &AtomSelection) -> MolecularTopology {
+        let mut subset = self.clone();
+        subset.retain_atoms(|atom| selection.contains(atom.id));
+        subset
+    }
+
+    /// Removes every atom for which `keep` returns `false`, together with all terms touching it.
+    ///
+    /// Remaining atoms keep their relative order and are renumbered contiguously; bonds, angles,
+    /// torsions, inversions, hydrogen-bond sites, and virtual sites are remapped to the new IDs.
+    /// A virtual site is dropped with its parent or any atom of its frame, but its dummy atom
+    /// stays unless `keep` removes it as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - Predicate selecting the atoms to keep.
+    ///
+    /// # Returns
+    ///
+    /// The old atom ID of every remaining atom, in its new order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+    ///
+    /// let mut graph = MolecularGraph::new();
+    /// let o = graph.add_atom(Element::O);
+    /// for _ in 0..2 {
+    ///     let h = graph.add_atom(Element::H);
+    ///     graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+    /// }
+    ///
+    /// let mut topology = assign_topology(&graph).unwrap();
+    /// let kept = topology.retain_atoms(|atom| atom.id != 1);
+    /// assert_eq!(kept, [0, 2]);
+    /// assert_eq!(topology.bonds[0].atom_ids, (0, 1));
+    /// assert!(topology.angles.is_empty());
+    /// ```
+    pub fn retain_atoms<F>(&mut self, mut keep: F) -> Vec<usize>
+    where
+        F: FnMut(&Atom) -> bool,
+    {
+        let kept: Vec<usize> = self
+            .atoms
+            .iter()
+            .filter(|atom| keep(atom))
+            .map(|atom| atom.id)
+            .collect();
+        let mut old_to_new = vec![None; self.atoms.len()];
+        for (new_id, &old_id) in kept.iter().enumerate() {
+            old_to_new[old_id] = Some(new_id);
+        }
+        let map = |id: usize| old_to_new[id];
+        self.remap_stereo(map);
+
+        self.atoms.retain(|atom| old_to_new[atom.id].is_some());
+        for atom in &mut self.atoms {
+            atom.id = old_to_new[atom.id].expect("kept atom");
+        }
+        self.bonds = self
+            .bonds
+            .iter()
+            .filter_map(|bond| {
+                let (a, b) = bond.atom_ids;
+                Some(bond.renumbered(map(a)?, map(b)?))
+            })
+            .collect();
+        self.angles = self
+            .angles
+            .iter()
+            .filter_map(|angle| {
+                let (a, center, b) = angle.atom_ids;
+                Some(Angle::new(map(a)?, map(center)?, map(b)?, angle.class))
+            })
+            .collect();
+        let remap_torsions = |torsions: &[Torsion]| -> Vec<Torsion> {
+            torsions
+                .iter()
+                .filter_map(|torsion| {
+                    let (i, j, k, l) = torsion.atom_ids;
+                    Some(Torsion::new(
+                        map(i)?,
+                        map(j)?,
+                        map(k)?,
+                        map(l)?,
+                        torsion.class,
+                    ))
+                })
+                .collect()
+        };
+        self.torsions = remap_torsions(&self.torsions);
+        self.suppressed_torsions = remap_torsions(&self.suppressed_torsions);
+        count_shared_torsions(&mut self.torsions);
+        count_shared_torsions(&mut self.suppressed_torsions);
+        self.inversions = self
+            .inversions
+            .iter()
+            .filter_map(|inversion| {
+                let (center, axis, p1, p2) = inversion.atom_ids;
+                Some(Inversion::new(
+                    map(center)?,
+                    map(axis)?,
+                    map(p1)?,
+                    map(p2)?,
+                    inversion.class,
+                ))
+            })
+            .collect();
+        self.hb_donors = self
+            .hb_donors
+            .iter()
+            .filter_map(|donor| {
+                Some(HydrogenBondDonor {
+                    donor_id: map(donor.donor_id)?,
+                    hydrogen_id: map(donor.hydrogen_id)?,
+                })
+            })
+            .collect();
+        self.hb_acceptors = self.hb_acceptors.iter().filter_map(|&a| map(a)).collect();
+        self.rings = self
+            .rings
+            .iter()
+            .filter_map(|ring| {
+                Some(Ring {
+                    atom_ids: ring
+                        .atom_ids
+                        .iter()
+                        .map(|&id| map(id))
+                        .collect::<Option<_>>()?,
+                    ..ring.clone()
+                })
+            })
+            .collect();
+        self.normalize_rings();
+        self.virtual_sites = self
+            .virtual_sites
+            .iter()
+            .filter_map(|site| {
+                Some(VirtualSite {
+                    site_id: map(site.site_id)?,
+                    parent_id: map(site.parent_id)?,
+                    frame_ids: site
+                        .frame_ids
+                        .iter()
+                        .map(|&id| map(id))
+                        .collect::<Option<_>>()?,
+                    index: site.index,
+                })
+            })
+            .collect();
+        kept
+    }
+
+    /// Puts every ring in canonical cyclic order, sorts the rings, and renumbers ring systems.
+    ///
+    /// Ring systems are the connected groups of rings sharing at least one atom, numbered in
+    /// order of their first ring. Call this after changing ring atom IDs.
+    pub(crate) fn normalize_rings(&mut self) {
+        for ring in &mut self.rings {
+            ring.atom_ids = canonical_cycle(core::mem::take(&mut ring.atom_ids));
+        }
+        self.rings
+            .sort_unstable_by(|a, b| a.atom_ids.cmp(&b.atom_ids));
+
+        let mut parent: Vec<usize> = (0..self.rings.len()).collect();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+        let mut ring_of_atom = crate::compat::collections::HashMap::new();
+        for (index, ring) in self.rings.iter().enumerate() {
+            for &atom in &ring.atom_ids {
+                if let Some(&other) = ring_of_atom.get(&atom) {
+                    let (a, b) = (root(&mut parent, index), root(&mut parent, other));
+                    parent[a.max(b)] = a.min(b);
+                } else {
+                    ring_of_atom.insert(atom, index);
+                }
+            }
+        }
+
+        let mut system_of_root = crate::compat::collections::HashMap::new();
+        for index in 0..self.rings.len() {
+            let next = system_of_root.len();
+            let system = *system_of_root
+                .entry(root(&mut parent, index))
+                .or_insert(next);
+            self.rings[index].system_id = system;
+        }
+    }
+
+    /// Re-expresses every stereo descriptor for a renumbering, before the atoms and bonds move.
+    ///
+    /// Descriptors that lose their meaning because too many neighbors are removed are cleared.
+    fn remap_stereo(&mut self, map: impl Fn(usize) -> Option<usize> + Copy) {
+        let has_stereo = self.atoms.iter().any(|atom| atom.chirality.is_some())
+            || self.bonds.iter().any(|bond| bond.stereo.is_some());
+        if !has_stereo {
+            return;
+        }
+
+        let mut neighbors = vec![Vec::new(); self.atoms.len()];
+        for bond in &self.bonds {
+            let (a, b) = bond.atom_ids;
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+        for list in &mut neighbors {
+            list.sort_unstable();
+        }
+        for atom in &mut self.atoms {
+            if let Some(chirality) = atom.chirality {
+                atom.chirality = stereo::remap_chirality(chirality, &neighbors[atom.id], map);
+            }
+        }
+        for bond in &mut self.bonds {
+            if let Some(configuration) = bond.stereo {
+                bond.stereo =
+                    stereo::remap_bond_stereo(configuration, bond.atom_ids, &neighbors, map);
+            }
+        }
+    }
+
+    /// Groups atoms by element in ascending atomic number, keeping the original relative order
+    /// within each element.
+    ///
+    /// # Returns
+    ///
+    /// The applied permutation (old atom ID at each new position).
+    pub fn sort_atoms_by_element(&mut self) -> Vec<usize> {
+        self.sort_atoms_by_key(|atom| atom.element)
+    }
+
+    /// Makes the atoms of each covalently bonded molecule contiguous.
+    ///
+    /// Molecules are ordered by their lowest original atom ID, and atoms keep their original
+    /// relative order within a molecule.
+    ///
+    /// # Returns
+    ///
+    /// The applied permutation (old atom ID at each new position).
+    pub fn sort_atoms_by_molecule(&mut self) -> Vec<usize> {
+        let molecule_of = self.molecule_indices();
+        self.sort_atoms_by_key(|atom| molecule_of[atom.id])
+    }
+
+    /// Labels every atom with the index of the bonded molecule it belongs to.
+    ///
+    /// Molecules are numbered in order of their lowest atom ID. A virtual site belongs to the
+    /// molecule of its parent atom.
+    ///
+    /// # Returns
+    ///
+    /// Molecule index per atom, aligned with `atoms`.
+    pub fn molecule_indices(&self) -> Vec<usize> {
+        let n = self.atoms.len();
+        let mut adjacency = vec![Vec::new(); n];
+        for bond in &self.bonds {
+            let (a, b) = bond.atom_ids;
+            adjacency[a].push(b);
+            adjacency[b].push(a);
+        }
+        for site in &self.virtual_sites {
+            adjacency[site.site_id].push(site.parent_id);
+            adjacency[site.parent_id].push(site.site_id);
+        }
+
+        let mut molecule_of = vec![usize::MAX; n];
+        let mut next_molecule = 0;
+        let mut stack = Vec::new();
+        for start in 0..n {
+            if molecule_of[start] != usize::MAX {
+                continue;
+            }
+            molecule_of[start] = next_molecule;
+            stack.push(start);
+            while let Some(atom) = stack.pop() {
+                for &neighbor in &adjacency[atom] {
+                    if molecule_of[neighbor] == usize::MAX {
+                        molecule_of[neighbor] = next_molecule;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            next_molecule += 1;
+        }
+        molecule_of
+    }
+
+    /// Iterates over atoms whose type fell back to a generic type because no rule matched.
+    ///
+    /// These are the warnings of a degraded run: atoms typed by the UFF fallback or by the
+    /// element-based [`AssignmentPolicy::Fallback`](crate::AssignmentPolicy::Fallback), which
+    /// callers may want to review or parameterize by hand. Atoms whose type the caller forced
+    /// are not included.
+    pub fn fallback_atoms(&self) -> impl Iterator<Item = &Atom> {
+        self.atoms.iter().filter(|atom| {
+            matches!(
+                atom.provenance,
+                TypeProvenance::UffFallback | TypeProvenance::ElementFallback
+            )
+        })
+    }
+
+    /// Pairs every hydrogen-bond donor with every candidate acceptor, lazily.
+    ///
+    /// These are the donor-hydrogen-acceptor triples an engine needs to set up the explicit
+    /// DREIDING hydrogen-bond term; an acceptor is never paired with its own donor. Distance and
+    /// angle cutoffs are left to the engine. The number of pairs is the product of
+    /// [`hb_donors`](Self::hb_donors) and [`hb_acceptors`](Self::hb_acceptors), some 18 million
+    /// for a box of 3000 waters, so the sites are produced one at a time; engines with a neighbor
+    /// list should pair the two lists themselves instead of filtering every site by distance.
+    ///
+    /// # Returns
+    ///
+    /// Iterator over the sites, ordered by hydrogen ID, then acceptor ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+    ///
+    /// // Water: each H_HB hydrogen can bond to any other oxygen, but not to its own.
+    /// let mut graph = MolecularGraph::new();
+    /// let o = graph.add_atom(Element::O);
+    /// let h1 = graph.add_atom(Element::H);
+    /// let h2 = graph.add_atom(Element::H);
+    /// graph.add_bond(o, h1, GraphBondOrder::Single).unwrap();
+    /// graph.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+    ///
+    /// let topology = assign_topology(&graph).unwrap();
+    /// assert_eq!(topology.hb_donors.len(), 2);
+    /// assert_eq!(topology.hydrogen_bond_sites().count(), 0);
+    /// ```
+    pub fn hydrogen_bond_sites(&self) -> impl Iterator<Item = HydrogenBondSite> + '_ {
+        self.hb_donors.iter().flat_map(|donor| {
+            self.hb_acceptors
+                .iter()
+                .filter(move |&&acceptor_id| acceptor_id != donor.donor_id)
+                .map(move |&acceptor_id| HydrogenBondSite {
+                    donor_id: donor.donor_id,
+                    hydrogen_id: donor.hydrogen_id,
+                    acceptor_id,
+                })
+        })
+    }
+}
+
+/// Hydrogen-bond donor pair: a polar hydrogen and the heavy atom it is bonded to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HydrogenBondDonor {
+    /// Heavy atom (typically N or O) covalently bonded to the hydrogen.
+    pub donor_id: usize,
+    /// Hydrogen typed as `H_HB`.
+    pub hydrogen_id: usize,
+}
+
+/// Candidate hydrogen bond `D-H···A` between a donor pair and an acceptor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HydrogenBondSite {
+    /// Heavy atom covalently bonded to the hydrogen.
+    pub donor_id: usize,
+    /// Hydrogen typed as `H_HB`.
+    pub hydrogen_id: usize,
+    /// Atom carrying the lone pair that accepts the hydrogen bond.
+    pub acceptor_id: usize,
+}
+
+/// Massless site placed along a lone pair of a hydrogen-bond acceptor.
+///
+/// The site is an extra [`Atom`] of element [`Element::Dummy`]; its position is constructed
+/// from the parent and the frame atoms rather than integrated, so it carries no bonded terms.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, Typer};
+///
+/// let mut graph = MolecularGraph::new();
+/// let o = graph.add_atom(Element::O);
+/// let h1 = graph.add_atom(Element::H);
+/// let h2 = graph.add_atom(Element::H);
+/// graph.add_bond(o, h1, GraphBondOrder::Single).unwrap();
+/// graph.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+///
+/// let topology = Typer::new()
+///     .with_lone_pair_sites(true)
+///     .assign_topology(&graph)
+///     .unwrap();
+/// assert_eq!(topology.virtual_sites.len(), 2);
+/// let site = &topology.virtual_sites[1];
+/// assert_eq!((site.site_id, site.parent_id, site.index), (4, o, 1));
+/// assert_eq!(site.frame_ids, [h1, h2]);
+/// assert_eq!(topology.atoms[site.site_id].element, Element::Dummy);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VirtualSite {
+    /// ID of the dummy atom representing the site.
+    pub site_id: usize,
+    /// Acceptor carrying the lone pair.
+    pub parent_id: usize,
+    /// Atoms bonded to the parent, sorted ascending, whose positions define the site's frame.
+    pub frame_ids: Vec<usize>,
+    /// Position of the site among the lone pairs of its parent, starting at zero.
+    pub index: usize,
+}
+
+/// Ring of the smallest set of smallest rings.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::assign_topology;
+/// use dreid_typer::io::smiles::parse_smiles;
+///
+/// // Indane: a benzene ring fused to a saturated five-membered ring.
+/// let topology = assign_topology(&parse_smiles("c1ccc2c(c1)CCC2").unwrap()).unwrap();
+/// let sizes: Vec<_> = topology.rings.iter().map(|ring| ring.size()).collect();
+/// assert_eq!(sizes, [6, 5]);
+/// assert!(topology.rings[0].is_aromatic);
+/// assert!(!topology.rings[1].is_aromatic);
+/// assert_eq!(topology.rings[0].system_id, topology.rings[1].system_id);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ring {
+    /// Ring atoms in cyclic order, starting at the lowest ID and continuing toward the lower
+    /// of its two ring neighbors.
+    pub atom_ids: Vec<usize>,
+    /// Whether every ring atom was perceived as aromatic.
+    pub is_aromatic: bool,
+    /// Index of the ring system holding the ring; rings sharing an atom, whether fused, bridged,
+    /// or spiro-linked, belong to the same system.
+    pub system_id: usize,
+}
+
+impl Ring {
+    /// Returns the number of atoms in the ring.
+    pub fn size(&self) -> usize {
+        self.atom_ids.len()
+    }
+
+    /// Returns `true` if the atom belongs to the ring.
+    pub fn contains(&self, atom_id: usize) -> bool {
+        self.atom_ids.contains(&atom_id)
+    }
+}
+
+/// Rotates a cycle to start at its lowest atom ID and orients it toward the lower neighbor.
+pub(crate) fn canonical_cycle(mut atom_ids: Vec<usize>) -> Vec<usize> {
+    let Some(start) = atom_ids
+        .iter()
+        .enumerate()
+        .min_by_key(|&(_, id)| id)
+        .map(|(position, _)| position)
+    else {
+        return atom_ids;
+    };
+    atom_ids.rotate_left(start);
+    if atom_ids.len() > 2 && atom_ids[atom_ids.len() - 1] < atom_ids[1] {
+        atom_ids[1..].reverse();
+    }
+    atom_ids
 }
 
 /// Atom entry emitted in the final topology, combining identity and typing.
@@ -29,10 +697,39 @@ pub struct Atom {
     pub id: usize,
     /// The chemical element.
     pub element: Element,
-    /// The final, assigned DREIDING atom type string.
-    pub atom_type: String,
+    /// The final, assigned DREIDING atom type.
+    pub atom_type: DreidingType,
     /// The perceived hybridization state.
     pub hybridization: Hybridization,
+    /// How `atom_type` was obtained.
+    pub provenance: TypeProvenance,
+    /// Index of the connected fragment holding the atom.
+    ///
+    /// Fragments are numbered in order of their lowest atom ID when the topology is built, and
+    /// the index is kept when atoms are later reordered.
+    pub fragment_id: usize,
+    /// Configuration of the atom if the input marked it as a tetrahedral stereocenter.
+    ///
+    /// It is recomputed whenever atoms are reordered or removed, so it always refers to the
+    /// current atom IDs.
+    pub chirality: Option<Chirality>,
+    /// Name, residue, and tags of the input atom, copied unchanged.
+    pub metadata: AtomMetadata,
+}
+
+/// Origin of an atom's assigned type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TypeProvenance {
+    /// Assigned by a rule of the active rule deck.
+    #[default]
+    Rule,
+    /// Generic UFF type used because no rule matched the atom.
+    UffFallback,
+    /// Generic element-based DREIDING type used because no rule matched the atom and the
+    /// [`AssignmentPolicy::Fallback`](crate::AssignmentPolicy::Fallback) policy is active.
+    ElementFallback,
+    /// Forced by the caller through [`TypingOverrides`](crate::TypingOverrides).
+    Override,
 }
 
 /// Bond entry emitted in the final topology.
@@ -42,13 +739,89 @@ pub struct Bond {
     pub atom_ids: (usize, usize),
     /// The order of the bond.
     pub order: TopologyBondOrder,
+    /// Configuration of the bond if the input marked it as a stereogenic double bond.
+    ///
+    /// Like [`Atom::chirality`], it always refers to the current atom IDs.
+    pub stereo: Option<BondStereo>,
+    /// Lattice translation of the second atom relative to the first for a bond of a periodic
+    /// input that crosses a cell face, or `[0, 0, 0]`.
+    pub image: ImageFlags,
+    /// Whether the bond is an ordinary covalent bond, a disulfide, or a coordination bond.
+    pub class: BondClass,
 }
 
 impl Bond {
-    /// Creates a new bond with atom IDs sorted to a canonical order.
+    /// Creates a new covalent bond without stereo descriptor, with atom IDs sorted to a
+    /// canonical order.
     pub fn new(id1: usize, id2: usize, order: TopologyBondOrder) -> Self {
         let atom_ids = if id1 < id2 { (id1, id2) } else { (id2, id1) };
-        Self { atom_ids, order }
+        Self {
+            atom_ids,
+            order,
+            stereo: None,
+            image: [0; 3],
+            class: BondClass::Covalent,
+        }
+    }
+
+    /// Returns the bond with its first atom renumbered to `id1` and its second to `id2`.
+    ///
+    /// The order, stereo descriptor, class, and image are kept; the image is negated if the
+    /// renumbering swaps the canonical order of the atoms.
+    pub(crate) fn renumbered(&self, id1: usize, id2: usize) -> Self {
+        let image = if id1 < id2 {
+            self.image
+        } else {
+            self.image.map(|t| -t)
+        };
+        Self {
+            stereo: self.stereo,
+            image,
+            class: self.class,
+            ..Self::new(id1, id2, self.order)
+        }
+    }
+}
+
+/// Chemical nature of a bond, decided by the elements it joins.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{BondClass, Element};
+///
+/// assert_eq!(BondClass::of(Element::S, Element::S), BondClass::Disulfide);
+/// assert_eq!(BondClass::of(Element::N, Element::Zn), BondClass::Coordination);
+/// assert_eq!(BondClass::of(Element::C, Element::S), BondClass::Covalent);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BondClass {
+    /// Ordinary covalent bond.
+    #[default]
+    Covalent,
+    /// Sulfur–sulfur bond, such as a cystine bridge.
+    Disulfide,
+    /// Bond to a metal center, such as a histidine nitrogen or carboxylate oxygen ligating a
+    /// metal ion or a MOF node.
+    ///
+    /// Perception already treats these bonds as dative: ligands keep the electron bookkeeping of
+    /// the free species.
+    Coordination,
+}
+
+impl BondClass {
+    /// Returns the class of a bond between atoms of the given elements.
+    ///
+    /// A bond with a metal center (see [`Element::is_metal_center`]) at either end is a
+    /// coordination bond, and a bond between two sulfur atoms is a disulfide.
+    pub fn of(a: Element, b: Element) -> Self {
+        if a.is_metal_center() || b.is_metal_center() {
+            BondClass::Coordination
+        } else if a == Element::S && b == Element::S {
+            BondClass::Disulfide
+        } else {
+            BondClass::Covalent
+        }
     }
 }
 
@@ -57,17 +830,44 @@ impl Bond {
 pub struct Angle {
     /// The IDs of the three atoms (`end1`, `center`, `end2`), with end atoms sorted.
     pub atom_ids: (usize, usize, usize),
+    /// DREIDING angle form selected by the central atom.
+    pub class: AngleClass,
 }
 
 impl Angle {
     /// Creates a new angle with end atoms sorted to a canonical order.
-    pub fn new(id1: usize, center_id: usize, id2: usize) -> Self {
+    pub fn new(id1: usize, center_id: usize, id2: usize, class: AngleClass) -> Self {
         let atom_ids = if id1 < id2 {
             (id1, center_id, id2)
         } else {
             (id2, center_id, id1)
         };
-        Self { atom_ids }
+        Self { atom_ids, class }
+    }
+}
+
+/// DREIDING angle form, decided by the geometry of the central atom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AngleClass {
+    /// Linear (sp) center, bent with the `K[1 + cos θ]` form.
+    Linear,
+    /// Trigonal (sp² or resonant) center, bent with the harmonic cosine form.
+    Trigonal,
+    /// Tetrahedral (sp³) center, bent with the harmonic cosine form.
+    Tetrahedral,
+    /// Center without a hybridization, such as a metal or a hypervalent atom.
+    Other,
+}
+
+impl AngleClass {
+    /// Returns the angle form used for a central atom of the given hybridization.
+    pub fn of(center: Hybridization) -> Self {
+        match center {
+            Hybridization::SP => AngleClass::Linear,
+            Hybridization::SP2 | Hybridization::Resonant => AngleClass::Trigonal,
+            Hybridization::SP3 => AngleClass::Tetrahedral,
+            Hybridization::None | Hybridization::Unknown => AngleClass::Other,
+        }
     }
 }
 
@@ -77,15 +877,119 @@ pub struct Torsion {
     /// The IDs of the four atoms (`i`, `j`, `k`, `l`) where `j-k` is the rotatable bond,
     /// with `i-l` sorted.
     pub atom_ids: (usize, usize, usize, usize),
+    /// DREIDING torsion case of the central bond.
+    pub class: TorsionClass,
+    /// Number of torsions in the same list about the same central bond, this one included.
+    ///
+    /// DREIDING divides the barrier of a central bond among all torsions about it, so parameter
+    /// assignment scales `V` by this count. Topologies produced by the typer keep it up to date
+    /// whenever torsions are added or removed.
+    pub shared_count: usize,
 }
 
 impl Torsion {
     /// Creates a new torsion with terminal atoms sorted to a canonical order.
-    pub fn new(i: usize, j: usize, k: usize, l: usize) -> Self {
+    ///
+    /// The torsion starts out as the only one about its central bond (`shared_count` of 1).
+    pub fn new(i: usize, j: usize, k: usize, l: usize, class: TorsionClass) -> Self {
         let fwd = (i, j, k, l);
         let rev = (l, k, j, i);
         let atom_ids = if fwd <= rev { fwd } else { rev };
-        Self { atom_ids }
+        Self {
+            atom_ids,
+            class,
+            shared_count: 1,
+        }
+    }
+
+    /// Returns the central bond `j-k` with its atom IDs in ascending order.
+    pub fn central_bond(&self) -> (usize, usize) {
+        let (_, j, k, _) = self.atom_ids;
+        (j.min(k), j.max(k))
+    }
+
+    /// Returns the torsion with its atoms renumbered, keeping its class and shared count.
+    pub(crate) fn renumbered(&self, i: usize, j: usize, k: usize, l: usize) -> Self {
+        Self {
+            shared_count: self.shared_count,
+            ..Self::new(i, j, k, l, self.class)
+        }
+    }
+}
+
+/// Sets the shared count of every torsion to the number of torsions about its central bond.
+///
+/// # Arguments
+///
+/// * `torsions` - Complete torsion list of a topology.
+pub(crate) fn count_shared_torsions(torsions: &mut [Torsion]) {
+    let mut per_bond: HashMap<(usize, usize), usize> = HashMap::new();
+    for torsion in torsions.iter() {
+        *per_bond.entry(torsion.central_bond()).or_default() += 1;
+    }
+    for torsion in torsions {
+        torsion.shared_count = per_bond[&torsion.central_bond()];
+    }
+}
+
+/// DREIDING torsion case, cases (a)–(j) of the original paper.
+///
+/// The case depends only on the central bond `j-k`: the hybridization and element of both
+/// central atoms, the bond order, whether the bond lies in a ring for case (f), and, for case
+/// (j), the neighbors of the sp² atom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TorsionClass {
+    /// (a) Single bond between two sp³ centers: `V = 2`, `n = 3`, `φ₀ = 180°`.
+    Sp3Sp3,
+    /// (b) Single bond between an sp³ and an sp² center: `V = 1`, `n = 6`, `φ₀ = 0°`.
+    Sp3Sp2,
+    /// (c) Double bond between two sp² centers: `V = 45`, `n = 2`, `φ₀ = 180°`.
+    Double,
+    /// (d) Resonant bond between two sp² or resonant centers: `V = 25`, `n = 2`, `φ₀ = 180°`.
+    Resonant,
+    /// (e) Single bond between two sp² or resonant centers: `V = 5`, `n = 2`, `φ₀ = 180°`.
+    Sp2Sp2Single,
+    /// (f) Exocyclic single bond between two resonant centers, such as the bond joining the
+    /// rings of biphenyl: `V = 10`, `n = 2`, `φ₀ = 180°`.
+    Exocyclic,
+    /// (g) Central bond involving a linear (sp) center: no barrier.
+    Linear,
+    /// (g) Central bond involving a center without hybridization, such as a metal: no barrier.
+    Unhybridized,
+    /// (h) Single bond between two sp³ atoms of the oxygen column: `V = 2`, `n = 2`,
+    /// `φ₀ = 90°`.
+    OxygenPair,
+    /// (i) Single bond between an sp³ oxygen-column atom and an sp² center: `V = 2`, `n = 2`,
+    /// `φ₀ = 180°`.
+    OxygenSp2,
+    /// (j) Single bond between an sp³ center and an sp² center that is itself bonded to another
+    /// sp² or resonant atom: `V = 2`, `n = 3`, `φ₀ = 180°`.
+    ConjugatedSp3Sp2,
+}
+
+impl TorsionClass {
+    /// Returns the letter of the case in the DREIDING paper, from `'a'` to `'j'`.
+    ///
+    /// Both [`TorsionClass::Linear`] and [`TorsionClass::Unhybridized`] belong to case (g).
+    pub fn case(self) -> char {
+        match self {
+            TorsionClass::Sp3Sp3 => 'a',
+            TorsionClass::Sp3Sp2 => 'b',
+            TorsionClass::Double => 'c',
+            TorsionClass::Resonant => 'd',
+            TorsionClass::Sp2Sp2Single => 'e',
+            TorsionClass::Exocyclic => 'f',
+            TorsionClass::Linear => 'g',
+            TorsionClass::Unhybridized => 'g',
+            TorsionClass::OxygenPair => 'h',
+            TorsionClass::OxygenSp2 => 'i',
+            TorsionClass::ConjugatedSp3Sp2 => 'j',
+        }
+    }
+
+    /// Returns `true` for the classes of case (g), which carry no torsional barrier.
+    pub fn is_barrierless(self) -> bool {
+        matches!(self, TorsionClass::Linear | TorsionClass::Unhybridized)
     }
 }
 
@@ -96,11 +1000,19 @@ pub struct Inversion {
     /// where `center` is the inversion center, `axis` is the unique neighbor
     /// defining the axis, with `plane1` and `plane2` sorted.
     pub atom_ids: (usize, usize, usize, usize),
+    /// DREIDING inversion form of the center.
+    pub class: InversionClass,
 }
 
 impl Inversion {
     /// Creates a new inversion with plane atoms sorted to a canonical order.
-    pub fn new(center: usize, axis: usize, plane1: usize, plane2: usize) -> Self {
+    pub fn new(
+        center: usize,
+        axis: usize,
+        plane1: usize,
+        plane2: usize,
+        class: InversionClass,
+    ) -> Self {
         let (p1, p2) = if plane1 < plane2 {
             (plane1, plane2)
         } else {
@@ -108,6 +1020,71 @@ impl Inversion {
         };
         Self {
             atom_ids: (center, axis, p1, p2),
+            class,
+        }
+    }
+}
+
+/// Which inversion terms the builder emits for each planar center.
+///
+/// DREIDING places an umbrella term on every axis of a planar center and shares the force
+/// constant among them, which matches LAMMPS `improper_style umbrella`. Engines whose improper
+/// terms follow the CHARMM convention expect a single term per center instead.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::smiles::parse_smiles;
+/// use dreid_typer::{InversionPolicy, Typer};
+///
+/// let formaldehyde = parse_smiles("C=O").unwrap();
+/// let all = Typer::new().assign_topology(&formaldehyde).unwrap();
+/// assert_eq!(all.inversions.len(), 3);
+///
+/// let single = Typer::new()
+///     .with_inversion_policy(InversionPolicy::SingleCanonical)
+///     .assign_topology(&formaldehyde)
+///     .unwrap();
+/// // One term about the carbon, with the doubly bonded oxygen as its axis.
+/// assert_eq!(single.inversions.len(), 1);
+/// assert_eq!(single.inversions[0].atom_ids.1, 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum InversionPolicy {
+    /// Three terms per planar center, one with each neighbor as the axis.
+    #[default]
+    AllAxes,
+    /// One term per planar center.
+    ///
+    /// The axis is the neighbor across the highest-order bond (double, then resonant, then
+    /// single); ties go to the heavier element and then to the lower atom ID, so the axis of a
+    /// carbonyl carbon is its oxygen.
+    SingleCanonical,
+    /// Three terms per planar nitrogen center and none for other elements.
+    ///
+    /// Planar carbon, boron, and other centers are then held flat by their torsions alone, as
+    /// in force fields that use the umbrella term only against nitrogen pyramidalization.
+    UmbrellaOnlyForNitrogen,
+}
+
+/// DREIDING inversion form, decided by the geometry of the center.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InversionClass {
+    /// Planar (sp² or resonant) center: `K = 40` shared by its three inversions, `ψ₀ = 0°`.
+    Planar,
+    /// Non-planar center, which DREIDING leaves without an inversion barrier.
+    ///
+    /// The builder only emits inversions for planar centers; this class exists for terms added
+    /// by hand.
+    NonPlanar,
+}
+
+impl InversionClass {
+    /// Returns the inversion form used for a center of the given hybridization.
+    pub fn of(center: Hybridization) -> Self {
+        match center {
+            Hybridization::SP2 | Hybridization::Resonant => InversionClass::Planar,
+            _ => InversionClass::NonPlanar,
         }
     }
 }
@@ -115,7 +1092,6 @@ impl Inversion {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::properties::TopologyBondOrder;
 
     #[test]
     fn bond_new_sorts_atom_ids() {
@@ -126,14 +1102,14 @@ mod tests {
 
     #[test]
     fn angle_new_orders_terminal_atoms() {
-        let angle = Angle::new(7, 3, 2);
+        let angle = Angle::new(7, 3, 2, AngleClass::Tetrahedral);
         assert_eq!(angle.atom_ids, (2, 3, 7));
     }
 
     #[test]
     fn torsion_new_canonicalizes_orientation() {
-        let forward = Torsion::new(1, 2, 3, 4);
-        let reversed = Torsion::new(4, 3, 2, 1);
+        let forward = Torsion::new(1, 2, 3, 4, TorsionClass::Sp3Sp3);
+        let reversed = Torsion::new(4, 3, 2, 1, TorsionClass::Sp3Sp3);
 
         assert_eq!(forward.atom_ids, reversed.atom_ids);
         assert_eq!(forward.atom_ids, (1, 2, 3, 4));
@@ -141,19 +1117,19 @@ mod tests {
 
     #[test]
     fn inversion_new_sorts_only_plane_atoms() {
-        let inv = Inversion::new(5, 9, 4, 1);
+        let inv = Inversion::new(5, 9, 4, 1, InversionClass::Planar);
         assert_eq!(inv.atom_ids, (5, 9, 1, 4));
 
-        let inv2 = Inversion::new(5, 1, 9, 4);
+        let inv2 = Inversion::new(5, 1, 9, 4, InversionClass::Planar);
         assert_eq!(inv2.atom_ids, (5, 1, 4, 9));
         assert_ne!(inv.atom_ids, inv2.atom_ids);
     }
 
     #[test]
     fn inversion_three_terms_per_center_are_distinct() {
-        let inv1 = Inversion::new(0, 1, 2, 3);
-        let inv2 = Inversion::new(0, 2, 1, 3);
-        let inv3 = Inversion::new(0, 3, 1, 2);
+        let inv1 = Inversion::new(0, 1, 2, 3, InversionClass::Planar);
+        let inv2 = Inversion::new(0, 2, 1, 3, InversionClass::Planar);
+        let inv3 = Inversion::new(0, 3, 1, 2, InversionClass::Planar);
 
         assert_eq!(inv1.atom_ids, (0, 1, 2, 3));
         assert_eq!(inv2.atom_ids, (0, 2, 1, 3));
@@ -164,6 +1140,267 @@ mod tests {
         assert_ne!(inv1, inv3);
     }
 
+    fn atom(id: usize, element: Element) -> Atom {
+        Atom {
+            id,
+            element,
+            atom_type: format!("{element}_").into(),
+            hybridization: Hybridization::None,
+            provenance: TypeProvenance::Rule,
+            fragment_id: 0,
+            chirality: None,
+            metadata: AtomMetadata::default(),
+        }
+    }
+
+    /// Two fragments interleaved by ID: C0-O2-H4 and N1-H3.
+    fn interleaved_topology() -> MolecularTopology {
+        MolecularTopology {
+            atoms: vec![
+                atom(0, Element::C),
+                atom(1, Element::N),
+                atom(2, Element::O),
+                atom(3, Element::H),
+                atom(4, Element::H),
+            ],
+            bonds: vec![
+                Bond::new(0, 2, TopologyBondOrder::Single),
+                Bond::new(2, 4, TopologyBondOrder::Single),
+                Bond::new(1, 3, TopologyBondOrder::Single),
+            ],
+            angles: vec![Angle::new(0, 2, 4, AngleClass::Tetrahedral)],
+            torsions: vec![Torsion::new(0, 2, 4, 3, TorsionClass::Sp3Sp3)],
+            suppressed_torsions: Vec::new(),
+            inversions: vec![Inversion::new(2, 0, 4, 3, InversionClass::Planar)],
+            hb_donors: vec![
+                HydrogenBondDonor {
+                    donor_id: 1,
+                    hydrogen_id: 3,
+                },
+                HydrogenBondDonor {
+                    donor_id: 2,
+                    hydrogen_id: 4,
+                },
+            ],
+            hb_acceptors: vec![1, 2],
+            rings: Vec::new(),
+            virtual_sites: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn permute_remaps_and_recanonicalizes_terms() {
+        let mut topology = interleaved_topology();
+        topology.permute(&[4, 3, 2, 1, 0]).unwrap();
+
+        let elements: Vec<_> = topology.atoms.iter().map(|a| a.element).collect();
+        assert_eq!(
+            elements,
+            [Element::H, Element::H, Element::O, Element::N, Element::C]
+        );
+        assert!(topology.atoms.iter().enumerate().all(|(i, a)| a.id == i));
+        assert_eq!(topology.bonds[0].atom_ids, (2, 4));
+        assert_eq!(topology.bonds[2].atom_ids, (1, 3));
+        assert_eq!(topology.angles[0].atom_ids, (0, 2, 4));
+        assert_eq!(topology.torsions[0].atom_ids, (1, 0, 2, 4));
+        assert_eq!(topology.inversions[0].atom_ids, (2, 4, 0, 1));
+        assert_eq!(
+            topology.hb_donors,
+            [
+                HydrogenBondDonor {
+                    donor_id: 2,
+                    hydrogen_id: 0
+                },
+                HydrogenBondDonor {
+                    donor_id: 3,
+                    hydrogen_id: 1
+                },
+            ]
+        );
+        assert_eq!(topology.hb_acceptors, [2, 3]);
+    }
+
+    #[test]
+    fn subset_keeps_only_terms_inside_the_selection() {
+        let topology = interleaved_topology();
+        let subset = topology.subset(&AtomSelection::from_ids([0, 2, 4, 9]));
+
+        assert_eq!(subset.atoms.len(), 3);
+        assert!(subset.atoms.iter().enumerate().all(|(i, a)| a.id == i));
+        assert_eq!(subset.atoms[1].element, Element::O);
+        let bonds: Vec<_> = subset.bonds.iter().map(|b| b.atom_ids).collect();
+        assert_eq!(bonds, [(0, 1), (1, 2)]);
+        assert_eq!(subset.angles[0].atom_ids, (0, 1, 2));
+        assert!(subset.torsions.is_empty());
+        assert!(subset.inversions.is_empty());
+        assert_eq!(
+            subset.hb_donors,
+            [HydrogenBondDonor {
+                donor_id: 1,
+                hydrogen_id: 2
+            }]
+        );
+        assert_eq!(subset.hb_acceptors, [1]);
+        // The source topology is left untouched.
+        assert_eq!(topology, interleaved_topology());
+    }
+
+    #[test]
+    fn shared_torsion_counts_follow_removals() {
+        // Chain 0-1-2-3-5 with a branch 4 on atom 1: two torsions about the 1-2 bond.
+        let mut torsions = vec![
+            Torsion::new(0, 1, 2, 3, TorsionClass::Sp3Sp3),
+            Torsion::new(4, 1, 2, 3, TorsionClass::Sp3Sp3),
+            Torsion::new(1, 2, 3, 5, TorsionClass::Sp3Sp3),
+        ];
+        count_shared_torsions(&mut torsions);
+        let mut topology = MolecularTopology {
+            atoms: (0..6).map(|id| atom(id, Element::C)).collect(),
+            torsions,
+            ..MolecularTopology::default()
+        };
+        assert_eq!(topology.torsions[0].shared_count, 2);
+        assert_eq!(topology.torsions[2].shared_count, 1);
+
+        topology.permute(&[5, 4, 3, 2, 1, 0]).unwrap();
+        assert_eq!(topology.torsions[1].shared_count, 2);
+
+        topology.retain_atoms(|atom| atom.id != 1);
+        assert_eq!(topology.torsions.len(), 2);
+        assert!(topology.torsions.iter().all(|t| t.shared_count == 1));
+    }
+
+    #[test]
+    fn permute_rejects_invalid_orders_without_mutating() {
+        let mut topology = interleaved_topology();
+        let original = topology.clone();
+
+        assert_eq!(
+            topology.permute(&[0, 1]),
+            Err(PermutationError::LengthMismatch {
+                expected: 5,
+                found: 2
+            })
+        );
+        assert_eq!(
+            topology.permute(&[0, 1, 2, 3, 9]),
+            Err(PermutationError::OutOfRange { index: 9 })
+        );
+        assert_eq!(
+            topology.permute(&[0, 1, 2, 2, 4]),
+            Err(PermutationError::Duplicate { index: 2 })
+        );
+        assert_eq!(topology, original);
+    }
+
+    #[test]
+    fn rings_follow_permutations_and_removals() {
+        let ring = |atom_ids: Vec<usize>| Ring {
+            atom_ids,
+            is_aromatic: false,
+            system_id: 0,
+        };
+        // A triangle 0-1-2 and a square 3-4-5-6 sharing nothing, plus a triangle 2-7-8 spiro to
+        // the first one.
+        let mut topology = MolecularTopology {
+            atoms: (0..9).map(|id| atom(id, Element::C)).collect(),
+            rings: vec![
+                ring(vec![3, 6, 5, 4]),
+                ring(vec![8, 2, 7]),
+                ring(vec![1, 0, 2]),
+            ],
+            ..MolecularTopology::default()
+        };
+        topology.normalize_rings();
+        let rings: Vec<_> = topology
+            .rings
+            .iter()
+            .map(|ring| (ring.atom_ids.clone(), ring.system_id))
+            .collect();
+        assert_eq!(
+            rings,
+            [
+                (vec![0, 1, 2], 0),
+                (vec![2, 7, 8], 0),
+                (vec![3, 4, 5, 6], 1)
+            ]
+        );
+
+        topology.permute(&[8, 7, 6, 5, 4, 3, 2, 1, 0]).unwrap();
+        assert_eq!(topology.rings[0].atom_ids, [0, 1, 6]);
+        assert_eq!(topology.rings[1].atom_ids, [2, 3, 4, 5]);
+        assert_eq!(topology.rings[1].system_id, 1);
+
+        topology.retain_atoms(|atom| atom.id != 3);
+        assert_eq!(topology.rings.len(), 2);
+        assert!(topology.rings.iter().all(|ring| ring.size() == 3));
+        assert_eq!(topology.rings[1].atom_ids, [5, 6, 7]);
+        assert!(topology.rings[1].contains(6));
+    }
+
+    #[test]
+    fn sort_by_molecule_makes_fragments_contiguous() {
+        let mut topology = interleaved_topology();
+        assert_eq!(topology.molecule_indices(), [0, 1, 0, 1, 0]);
+
+        let order = topology.sort_atoms_by_molecule();
+        assert_eq!(order, [0, 2, 4, 1, 3]);
+        assert_eq!(topology.molecule_indices(), [0, 0, 0, 1, 1]);
+        assert_eq!(topology.bonds[2].atom_ids, (3, 4));
+    }
+
+    #[test]
+    fn virtual_sites_follow_permutations_and_removals() {
+        let mut topology = interleaved_topology();
+        topology.atoms.push(atom(5, Element::Dummy));
+        topology.virtual_sites.push(VirtualSite {
+            site_id: 5,
+            parent_id: 2,
+            frame_ids: vec![0, 4],
+            index: 0,
+        });
+        assert_eq!(topology.molecule_indices(), [0, 1, 0, 1, 0, 0]);
+
+        topology.sort_atoms_by_molecule();
+        assert_eq!(topology.atoms[3].element, Element::Dummy);
+        assert_eq!(
+            topology.virtual_sites,
+            [VirtualSite {
+                site_id: 3,
+                parent_id: 1,
+                frame_ids: vec![0, 2],
+                index: 0,
+            }]
+        );
+
+        let kept = topology.retain_atoms(|atom| atom.id != 2);
+        assert_eq!(kept, [0, 1, 3, 4, 5]);
+        assert!(topology.virtual_sites.is_empty());
+        assert_eq!(topology.atoms[2].element, Element::Dummy);
+    }
+
+    #[test]
+    fn sort_by_element_orders_by_atomic_number() {
+        let mut topology = interleaved_topology();
+        let order = topology.sort_atoms_by_element();
+        assert_eq!(order, [3, 4, 0, 1, 2]);
+        assert_eq!(topology.angles[0].atom_ids, (1, 4, 2));
+    }
+
+    #[test]
+    fn hydrogen_bond_sites_pair_donors_with_other_acceptors() {
+        let topology = interleaved_topology();
+        let site = |donor_id, hydrogen_id, acceptor_id| HydrogenBondSite {
+            donor_id,
+            hydrogen_id,
+            acceptor_id,
+        };
+        assert_eq!(
+            topology.hydrogen_bond_sites().collect::<Vec<_>>(),
+            [site(1, 3, 2), site(2, 4, 1)]
+        );
+    }
+
     #[test]
     fn molecular_topology_default_is_empty() {
         let topology = MolecularTopology::default();
@@ -173,5 +1410,9 @@ mod tests {
         assert!(topology.angles.is_empty());
         assert!(topology.torsions.is_empty());
         assert!(topology.inversions.is_empty());
+        assert!(topology.hb_donors.is_empty());
+        assert!(topology.hb_acceptors.is_empty());
+        assert_eq!(topology.hydrogen_bond_sites().count(), 0);
+        assert!(topology.virtual_sites.is_empty());
     }
 }
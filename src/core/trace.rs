@@ -0,0 +1,113 @@
+//! Optional instrumentation of the pipeline through the `tracing` crate.
+//!
+//! With the `tracing` feature enabled, every run opens `debug` spans for the molecule, each
+//! fragment, each perception step, typing, and building, and emits `debug` events with the counts
+//! a step produced (rings found, aromatic and resonant atoms, atoms typed per engine round).
+//! Without the feature both macros expand to nothing, so default builds pay no cost and need no
+//! subscriber.
+
+/// Opens a `debug`-level span and enters it until the returned guard is dropped.
+#[cfg(feature = "tracing")]
+macro_rules! span {
+    ($($arg:tt)*) => {
+        tracing::debug_span!($($arg)*).entered()
+    };
+}
+
+/// Opens a `debug`-level span and enters it until the returned guard is dropped.
+#[cfg(not(feature = "tracing"))]
+macro_rules! span {
+    ($($arg:tt)*) => {
+        $crate::core::trace::Disabled
+    };
+}
+
+/// Emits a `debug`-level event with structured fields.
+#[cfg(feature = "tracing")]
+macro_rules! event {
+    ($($arg:tt)*) => {
+        tracing::debug!($($arg)*)
+    };
+}
+
+/// Emits a `debug`-level event with structured fields.
+#[cfg(not(feature = "tracing"))]
+macro_rules! event {
+    ($($arg:tt)*) => {};
+}
+
+pub(crate) use {event, span};
+
+/// Guard returned by [`span!`] when the `tracing` feature is disabled.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Disabled;
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use crate::{Element, GraphBondOrder, MolecularGraph, Typer};
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Subscriber that records the names of opened spans and counts events.
+    #[derive(Default)]
+    struct Recorder {
+        spans: Mutex<Vec<&'static str>>,
+        events: AtomicUsize,
+        next_id: AtomicU64,
+    }
+
+    impl Subscriber for &'static Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            self.spans.lock().unwrap().push(span.metadata().name());
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {
+            self.events.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn typing_a_molecule_emits_stage_spans_and_counts() {
+        let recorder: &'static Recorder = Box::leak(Box::default());
+
+        // Two carbonyl fragments; a lone ion would be typed by lookup without a fragment span.
+        let mut graph = MolecularGraph::new();
+        for _ in 0..2 {
+            let c = graph.add_atom(Element::C);
+            let o = graph.add_atom(Element::O);
+            graph.add_bond(c, o, GraphBondOrder::Double).unwrap();
+        }
+
+        tracing::subscriber::with_default(recorder, || {
+            Typer::new().assign_topology(&graph).unwrap();
+        });
+
+        let spans = recorder.spans.lock().unwrap();
+        for name in [
+            "molecule",
+            "fragment",
+            "perception_step",
+            "typing",
+            "building",
+        ] {
+            assert!(spans.contains(&name), "missing span {name}: {spans:?}");
+        }
+        assert_eq!(spans.iter().filter(|&&name| name == "fragment").count(), 2);
+        assert!(recorder.events.load(Ordering::Relaxed) > 0);
+    }
+}
@@ -0,0 +1,578 @@
+//! Whole-graph diagnostics collected in a single pass.
+//!
+//! The pipeline stops at the first [`GraphValidationError`](crate::GraphValidationError) or
+//! perception failure, which is the right behavior for typing but a poor one for diagnosing large
+//! auto-generated structures. [`validate`](crate::validate) instead inspects every atom and bond
+//! and returns a [`ValidationReport`](crate::ValidationReport) listing all problems it finds, each
+//! tagged with the offending atom or bond IDs and a [`Severity`](crate::Severity).
+
+use super::graph::MolecularGraph;
+use super::properties::{Element, GraphBondOrder};
+use crate::compat::collections::HashMap;
+use crate::compat::prelude::*;
+use core::fmt;
+
+/// How serious a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Noteworthy but chemically plausible, such as an implied ionic charge.
+    Info,
+    /// Likely a modeling mistake, although typing may still succeed.
+    Warning,
+    /// Typing will fail or produce a meaningless topology.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// A single problem found in a molecular graph.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    /// A bond references an atom that does not exist.
+    MissingAtom {
+        /// Offending bond.
+        bond_id: usize,
+        /// Atom ID that is out of range.
+        atom_id: usize,
+    },
+    /// A bond connects an atom to itself.
+    SelfBond {
+        /// Offending bond.
+        bond_id: usize,
+        /// Atom bonded to itself.
+        atom_id: usize,
+    },
+    /// A bond repeats an earlier bond between the same two atoms.
+    DuplicateBond {
+        /// Repeated bond.
+        bond_id: usize,
+        /// Earlier bond between the same atoms.
+        first_bond_id: usize,
+    },
+    /// The bond orders around an atom exceed what its element can form.
+    OverValentAtom {
+        /// Offending atom.
+        atom_id: usize,
+        /// Sum of bond orders, counting aromatic bonds as 1.5.
+        valence: f64,
+        /// Largest bond-order sum accepted for the element.
+        max_valence: u8,
+    },
+    /// An aromatic bond is not part of any ring, so it cannot be Kekulé-expanded.
+    AromaticBondOutsideRing {
+        /// Offending bond.
+        bond_id: usize,
+    },
+    /// An atom has no bonds although its element rarely occurs as a free species.
+    IsolatedAtom {
+        /// Offending atom.
+        atom_id: usize,
+    },
+    /// The bond orders around a main-group atom imply a nonzero formal charge.
+    ImpliedCharge {
+        /// Offending atom.
+        atom_id: usize,
+        /// Formal charge implied by the octet rule.
+        charge: i8,
+    },
+}
+
+impl ValidationIssue {
+    /// Returns the severity this issue is reported with.
+    pub fn severity(&self) -> Severity {
+        match self {
+            ValidationIssue::MissingAtom { .. }
+            | ValidationIssue::SelfBond { .. }
+            | ValidationIssue::DuplicateBond { .. }
+            | ValidationIssue::OverValentAtom { .. }
+            | ValidationIssue::AromaticBondOutsideRing { .. } => Severity::Error,
+            ValidationIssue::IsolatedAtom { .. } => Severity::Warning,
+            ValidationIssue::ImpliedCharge { charge, .. } if charge.abs() > 1 => Severity::Warning,
+            ValidationIssue::ImpliedCharge { .. } => Severity::Info,
+        }
+    }
+
+    /// Returns the atom the issue is about, if any.
+    pub fn atom_id(&self) -> Option<usize> {
+        match *self {
+            ValidationIssue::MissingAtom { atom_id, .. }
+            | ValidationIssue::SelfBond { atom_id, .. }
+            | ValidationIssue::OverValentAtom { atom_id, .. }
+            | ValidationIssue::IsolatedAtom { atom_id }
+            | ValidationIssue::ImpliedCharge { atom_id, .. } => Some(atom_id),
+            ValidationIssue::DuplicateBond { .. }
+            | ValidationIssue::AromaticBondOutsideRing { .. } => None,
+        }
+    }
+
+    /// Returns the bond the issue is about, if any.
+    pub fn bond_id(&self) -> Option<usize> {
+        match *self {
+            ValidationIssue::MissingAtom { bond_id, .. }
+            | ValidationIssue::SelfBond { bond_id, .. }
+            | ValidationIssue::DuplicateBond { bond_id, .. }
+            | ValidationIssue::AromaticBondOutsideRing { bond_id } => Some(bond_id),
+            ValidationIssue::OverValentAtom { .. }
+            | ValidationIssue::IsolatedAtom { .. }
+            | ValidationIssue::ImpliedCharge { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::MissingAtom { bond_id, atom_id } => {
+                write!(f, "bond {bond_id} references non-existent atom {atom_id}")
+            }
+            ValidationIssue::SelfBond { bond_id, atom_id } => {
+                write!(f, "bond {bond_id} connects atom {atom_id} to itself")
+            }
+            ValidationIssue::DuplicateBond {
+                bond_id,
+                first_bond_id,
+            } => write!(f, "bond {bond_id} duplicates bond {first_bond_id}"),
+            ValidationIssue::OverValentAtom {
+                atom_id,
+                valence,
+                max_valence,
+            } => write!(
+                f,
+                "atom {atom_id} has bond-order sum {valence}, above the maximum of {max_valence}"
+            ),
+            ValidationIssue::AromaticBondOutsideRing { bond_id } => {
+                write!(f, "aromatic bond {bond_id} is not part of any ring")
+            }
+            ValidationIssue::IsolatedAtom { atom_id } => {
+                write!(f, "atom {atom_id} has no bonds")
+            }
+            ValidationIssue::ImpliedCharge { atom_id, charge } => {
+                write!(f, "atom {atom_id} implies a formal charge of {charge:+}")
+            }
+        }
+    }
+}
+
+/// All problems found in a molecular graph, in the order they were detected.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{validate, Element, GraphBondOrder, MolecularGraph, Severity};
+///
+/// let mut graph = MolecularGraph::new();
+/// let c = graph.add_atom(Element::C);
+/// let o = graph.add_atom(Element::O);
+/// graph.add_atom(Element::C);
+/// graph.add_bond(c, o, GraphBondOrder::Aromatic).unwrap();
+/// graph.add_bond(c, o, GraphBondOrder::Triple).unwrap();
+///
+/// let report = validate(&graph);
+/// assert!(!report.is_valid());
+/// assert_eq!(report.max_severity(), Some(Severity::Error));
+/// assert_eq!(report.errors().count(), 2); // duplicate bond, aromatic bond outside a ring
+/// assert_eq!(report.warnings().count(), 1); // isolated carbon
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    /// Every issue found.
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if the report contains no errors; warnings and notes are allowed.
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Returns `true` if no issue of any severity was found.
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the number of issues of any severity.
+    pub fn len(&self) -> usize {
+        self.issues.len()
+    }
+
+    /// Returns the highest severity in the report, or `None` if it is empty.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.issues.iter().map(ValidationIssue::severity).max()
+    }
+
+    /// Iterates over issues of exactly the given severity.
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(move |issue| issue.severity() == severity)
+    }
+
+    /// Iterates over issues of [`Severity::Error`].
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.with_severity(Severity::Error)
+    }
+
+    /// Iterates over issues of [`Severity::Warning`].
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.with_severity(Severity::Warning)
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}: {issue}", issue.severity())?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks a molecular graph and reports every problem found instead of stopping at the first.
+///
+/// The checks cover dangling and self bonds, duplicate bonds, over-valent atoms, aromatic bonds
+/// that lie on no ring, isolated atoms other than monatomic ions and noble gases, and formal
+/// charges implied by the octet rule on H, B, N, O, and F. Coordination bonds to metals are not
+/// counted towards a ligand's valence, and dummy atoms and their bonds are not checked at all.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to inspect; it may be arbitrarily malformed.
+///
+/// # Returns
+///
+/// A report whose issues are ordered by check, then by atom or bond ID.
+pub fn validate(graph: &MolecularGraph) -> ValidationReport {
+    let mut issues = Vec::new();
+    let n = graph.atoms.len();
+
+    // Bonds that survive the structural checks feed the per-atom checks.
+    let mut valid_bonds = Vec::new();
+    let mut first_bond: HashMap<(usize, usize), usize> = HashMap::new();
+    for (bond_id, bond) in graph.bonds.iter().enumerate() {
+        let (a, b) = bond.atom_ids;
+        if let Some(atom_id) = [a, b].into_iter().find(|&atom| atom >= n) {
+            issues.push(ValidationIssue::MissingAtom { bond_id, atom_id });
+            continue;
+        }
+        if a == b {
+            issues.push(ValidationIssue::SelfBond {
+                bond_id,
+                atom_id: a,
+            });
+            continue;
+        }
+        match first_bond.entry((a.min(b), a.max(b))) {
+            crate::compat::collections::hash_map::Entry::Occupied(entry) => {
+                issues.push(ValidationIssue::DuplicateBond {
+                    bond_id,
+                    first_bond_id: *entry.get(),
+                });
+                continue;
+            }
+            crate::compat::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(bond_id);
+            }
+        }
+        valid_bonds.push((bond_id, a, b, bond.order));
+    }
+
+    let mut neighbors: Vec<Vec<(usize, usize, GraphBondOrder)>> = vec![Vec::new(); n];
+    for &(bond_id, a, b, order) in &valid_bonds {
+        neighbors[a].push((b, bond_id, order));
+        neighbors[b].push((a, bond_id, order));
+    }
+
+    for atom in graph.atoms.iter().filter(|atom| !atom.element.is_dummy()) {
+        let element = atom.element;
+        let covalent: Vec<GraphBondOrder> = neighbors[atom.id]
+            .iter()
+            .filter(|&&(other, _, _)| {
+                let other = graph.atoms[other].element;
+                !other.is_dummy() && (element.is_metal_center() || !other.is_metal_center())
+            })
+            .map(|&(_, _, order)| order)
+            .collect();
+        let valence: f64 = covalent.iter().map(|&order| bond_order_value(order)).sum();
+
+        if let Some(max_valence) = max_valence(element)
+            && valence > f64::from(max_valence) + 1e-9
+        {
+            issues.push(ValidationIssue::OverValentAtom {
+                atom_id: atom.id,
+                valence,
+                max_valence,
+            });
+            continue;
+        }
+
+        if neighbors[atom.id].is_empty() {
+            if !is_free_species(element) {
+                issues.push(ValidationIssue::IsolatedAtom { atom_id: atom.id });
+            }
+            continue;
+        }
+
+        if !covalent.contains(&GraphBondOrder::Aromatic)
+            && let Some(charge) = implied_charge(element, valence as u8)
+            && charge != 0
+        {
+            issues.push(ValidationIssue::ImpliedCharge {
+                atom_id: atom.id,
+                charge,
+            });
+        }
+    }
+
+    for bond_id in ring_free_bonds(n, &valid_bonds) {
+        if graph.bonds[bond_id].order == GraphBondOrder::Aromatic {
+            issues.push(ValidationIssue::AromaticBondOutsideRing { bond_id });
+        }
+    }
+
+    ValidationReport { issues }
+}
+
+/// Returns the contribution of a bond to its atoms' bond-order sums.
+fn bond_order_value(order: GraphBondOrder) -> f64 {
+    match order {
+        GraphBondOrder::Single => 1.0,
+        GraphBondOrder::Double => 2.0,
+        GraphBondOrder::Triple => 3.0,
+        GraphBondOrder::Aromatic => 1.5,
+    }
+}
+
+/// Largest bond-order sum accepted for an element, or `None` if it is not checked.
+///
+/// Second-period atoms obey the octet rule (allowing onium ions and borates); heavier
+/// main-group atoms may expand their octet up to their valence-electron count. Metals and
+/// noble gases are not checked.
+fn max_valence(element: Element) -> Option<u8> {
+    match element {
+        Element::H | Element::F => Some(1),
+        Element::B | Element::C | Element::N => Some(4),
+        Element::O => Some(3),
+        _ if element.is_metal_center() => None,
+        _ => match element.valence_electrons()? {
+            8 => None,
+            v if v <= 4 => Some(6),
+            v => Some(v),
+        },
+    }
+}
+
+/// Returns `true` for elements commonly found as free monatomic species.
+fn is_free_species(element: Element) -> bool {
+    element.is_metal_center()
+        || element.valence_electrons() == Some(8)
+        || matches!(element, Element::F | Element::Cl | Element::Br | Element::I)
+}
+
+/// Formal charge implied by the octet rule for atoms with an unambiguous neutral valence.
+fn implied_charge(element: Element, valence: u8) -> Option<i8> {
+    let (neutral, sign) = match element {
+        Element::H => (1, -1),
+        Element::B => (3, -1),
+        Element::N => (3, 1),
+        Element::O => (2, 1),
+        Element::F => (1, 1),
+        _ => return None,
+    };
+    Some(sign * (valence as i8 - neutral))
+}
+
+/// Returns the IDs of bonds that lie on no cycle (graph bridges), in ascending order.
+fn ring_free_bonds(n: usize, bonds: &[(usize, usize, usize, GraphBondOrder)]) -> Vec<usize> {
+    let endpoints: Vec<(usize, usize)> = bonds.iter().map(|&(_, a, b, _)| (a, b)).collect();
+    let mut bond_ids: Vec<usize> = bridges(n, &endpoints)
+        .into_iter()
+        .map(|index| bonds[index].0)
+        .collect();
+    bond_ids.sort_unstable();
+    bond_ids
+}
+
+/// Returns the indices into `bonds` of the bonds that lie on no cycle, in ascending order.
+///
+/// # Arguments
+///
+/// * `n` - Number of atoms; every endpoint must be below it.
+/// * `bonds` - Endpoints of every bond.
+pub(crate) fn bridges(n: usize, bonds: &[(usize, usize)]) -> Vec<usize> {
+    let mut adjacency: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+    for (bond_id, &(a, b)) in bonds.iter().enumerate() {
+        adjacency[a].push((b, bond_id));
+        adjacency[b].push((a, bond_id));
+    }
+
+    let mut discovery = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut time = 0;
+    let mut bridges = Vec::new();
+    for root in 0..n {
+        if discovery[root] != usize::MAX {
+            continue;
+        }
+        discovery[root] = time;
+        low[root] = time;
+        time += 1;
+        // Iterative DFS frames: (atom, bond used to reach it, next neighbor index).
+        let mut stack = vec![(root, usize::MAX, 0)];
+        while let Some(frame) = stack.last_mut() {
+            let (atom, parent_bond, next) = *frame;
+            if let Some(&(neighbor, bond_id)) = adjacency[atom].get(next) {
+                frame.2 += 1;
+                if bond_id == parent_bond {
+                    continue;
+                }
+                if discovery[neighbor] == usize::MAX {
+                    discovery[neighbor] = time;
+                    low[neighbor] = time;
+                    time += 1;
+                    stack.push((neighbor, bond_id, 0));
+                } else {
+                    low[atom] = low[atom].min(discovery[neighbor]);
+                }
+            } else {
+                stack.pop();
+                if let Some(&(parent, _, _)) = stack.last() {
+                    low[parent] = low[parent].min(low[atom]);
+                    if low[atom] > discovery[parent] {
+                        bridges.push(parent_bond);
+                    }
+                }
+            }
+        }
+    }
+    bridges.sort_unstable();
+    bridges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph_with_atoms(elements: &[Element]) -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        for &element in elements {
+            graph.add_atom(element);
+        }
+        graph
+    }
+
+    #[test]
+    fn clean_molecules_produce_empty_reports() {
+        let mut benzene = graph_with_atoms(&[Element::C; 6]);
+        for i in 0..6 {
+            benzene
+                .add_bond(i, (i + 1) % 6, GraphBondOrder::Aromatic)
+                .unwrap();
+            let h = benzene.add_atom(Element::H);
+            benzene.add_bond(i, h, GraphBondOrder::Single).unwrap();
+        }
+        let report = validate(&benzene);
+        assert!(report.is_empty(), "unexpected issues:\n{report}");
+        assert_eq!(report.max_severity(), None);
+    }
+
+    #[test]
+    fn every_problem_is_collected_in_one_pass() {
+        let mut graph = graph_with_atoms(&[
+            Element::C,
+            Element::C,
+            Element::N,
+            Element::H,
+            Element::H,
+            Element::H,
+            Element::H,
+            Element::O,
+            Element::Na,
+        ]);
+        graph.add_bond(0, 1, GraphBondOrder::Aromatic).unwrap();
+        for h in 3..7 {
+            graph.add_bond(2, h, GraphBondOrder::Single).unwrap();
+        }
+        graph.add_bond(0, 1, GraphBondOrder::Single).unwrap();
+        graph.add_bond(7, 0, GraphBondOrder::Triple).unwrap();
+        graph.add_bond(7, 1, GraphBondOrder::Double).unwrap();
+        graph.bonds.push(crate::core::graph::BondEdge {
+            id: 8,
+            atom_ids: (3, 42),
+            order: GraphBondOrder::Single,
+            stereo: None,
+        });
+
+        let report = validate(&graph);
+        assert_eq!(
+            report.issues,
+            [
+                ValidationIssue::DuplicateBond {
+                    bond_id: 5,
+                    first_bond_id: 0,
+                },
+                ValidationIssue::MissingAtom {
+                    bond_id: 8,
+                    atom_id: 42,
+                },
+                ValidationIssue::OverValentAtom {
+                    atom_id: 0,
+                    valence: 4.5,
+                    max_valence: 4,
+                },
+                ValidationIssue::ImpliedCharge {
+                    atom_id: 2,
+                    charge: 1,
+                },
+                ValidationIssue::OverValentAtom {
+                    atom_id: 7,
+                    valence: 5.0,
+                    max_valence: 3,
+                },
+            ]
+        );
+        assert_eq!(report.errors().count(), 4);
+        assert_eq!(report.issues[3].severity(), Severity::Info);
+        assert!(report.to_string().starts_with("error: bond 5 duplicates"));
+    }
+
+    #[test]
+    fn ring_free_aromatic_bonds_and_isolated_atoms_are_reported() {
+        let mut graph = graph_with_atoms(&[Element::C, Element::C, Element::C, Element::Cl]);
+        graph.add_bond(0, 1, GraphBondOrder::Aromatic).unwrap();
+        graph.add_bond(1, 2, GraphBondOrder::Single).unwrap();
+
+        let report = validate(&graph);
+        assert_eq!(
+            report.issues,
+            [ValidationIssue::AromaticBondOutsideRing { bond_id: 0 }]
+        );
+
+        let mut lone_carbon = graph_with_atoms(&[Element::C, Element::Na, Element::Ar]);
+        lone_carbon.add_atom(Element::O);
+        let report = validate(&lone_carbon);
+        let isolated: Vec<_> = report.warnings().filter_map(|i| i.atom_id()).collect();
+        assert_eq!(isolated, [0, 3]);
+    }
+
+    #[test]
+    fn dummy_atoms_are_not_counted_or_checked() {
+        let mut water = graph_with_atoms(&[Element::O, Element::H, Element::H]);
+        water.add_bond(0, 1, GraphBondOrder::Single).unwrap();
+        water.add_bond(0, 2, GraphBondOrder::Single).unwrap();
+        for _ in 0..2 {
+            let site = water.add_atom(Element::Dummy);
+            water.add_bond(0, site, GraphBondOrder::Single).unwrap();
+        }
+        water.add_atom(Element::Dummy);
+        let report = validate(&water);
+        assert!(report.is_empty(), "unexpected issues:\n{report}");
+    }
+}
@@ -0,0 +1,213 @@
+//! GraphML export for graph analysis and visualization tools.
+//!
+//! [`GraphMlDocument`] writes the typed molecule as an undirected GraphML graph that Gephi,
+//! Cytoscape, yEd, and NetworkX read with their attributes intact. Nodes are named `n<ID>` and
+//! edges `e<index>`, and both carry typed data keys:
+//!
+//! | Key             | Domain | Type      | Content                                          |
+//! |-----------------|--------|-----------|--------------------------------------------------|
+//! | `name`          | node   | `string`  | atom name from the metadata, or element + counter |
+//! | `element`       | node   | `string`  | element symbol                                   |
+//! | `type`          | node   | `string`  | assigned DREIDING type                           |
+//! | `hybridization` | node   | `string`  | perceived hybridization                          |
+//! | `aromatic`      | node   | `boolean` | whether the atom lies on an aromatic ring        |
+//! | `provenance`    | node   | `string`  | `rule`, `uff_fallback`, `element_fallback`, or `override` |
+//! | `fragment`      | node   | `int`     | index of the connected fragment                  |
+//! | `order`         | edge   | `string`  | bond order                                       |
+//! | `image`         | edge   | `string`  | lattice translation of the bond, `0 0 0` by default |
+
+use super::graphviz::{aromatic_atoms, provenance_name};
+use super::gromacs::atom_names;
+use super::openmm::escape;
+use crate::compat::prelude::*;
+use crate::core::topology::MolecularTopology;
+use core::fmt;
+
+/// Default graph ID.
+const DEFAULT_NAME: &str = "MOL";
+
+/// Data keys declared in the document: ID, domain, and value type.
+const KEYS: [(&str, &str, &str); 9] = [
+    ("name", "node", "string"),
+    ("element", "node", "string"),
+    ("type", "node", "string"),
+    ("hybridization", "node", "string"),
+    ("aromatic", "node", "boolean"),
+    ("provenance", "node", "string"),
+    ("fragment", "node", "int"),
+    ("order", "edge", "string"),
+    ("image", "edge", "string"),
+];
+
+/// A topology prepared for writing as a GraphML document.
+///
+/// The document is produced through the [`Display`](fmt::Display) implementation; use
+/// [`MolecularTopology::to_graphml`] for the defaults.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::export::graphml::GraphMlDocument;
+/// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+///
+/// let mut graph = MolecularGraph::new();
+/// let h = graph.add_atom(Element::H);
+/// let f = graph.add_atom(Element::F);
+/// graph.add_bond(h, f, GraphBondOrder::Single).unwrap();
+/// let topology = assign_topology(&graph).unwrap();
+///
+/// let graphml = GraphMlDocument::new(&topology).with_name("HF").to_string();
+/// assert!(graphml.contains("<graph id=\"HF\" edgedefault=\"undirected\">"));
+/// assert!(graphml.contains("<data key=\"type\">F_</data>"));
+/// assert!(graphml.contains("<edge id=\"e0\" source=\"n0\" target=\"n1\">"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GraphMlDocument<'a> {
+    topology: &'a MolecularTopology,
+    name: String,
+}
+
+impl<'a> GraphMlDocument<'a> {
+    /// Prepares a topology for export.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Typed topology to export.
+    pub fn new(topology: &'a MolecularTopology) -> Self {
+        Self {
+            topology,
+            name: DEFAULT_NAME.to_string(),
+        }
+    }
+
+    /// Sets the ID of the graph element.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+}
+
+impl fmt::Display for GraphMlDocument<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let topology = self.topology;
+        let names = atom_names(topology);
+        let aromatic = aromatic_atoms(topology);
+
+        writeln!(f, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            f,
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" \
+             xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+             xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns \
+             http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">"
+        )?;
+        for (id, domain, value_type) in KEYS {
+            write!(
+                f,
+                "  <key id=\"{id}\" for=\"{domain}\" attr.name=\"{id}\" attr.type=\"{value_type}\""
+            )?;
+            if id == "image" {
+                writeln!(f, ">\n    <default>0 0 0</default>\n  </key>")?;
+            } else {
+                writeln!(f, "/>")?;
+            }
+        }
+        writeln!(
+            f,
+            "  <graph id=\"{}\" edgedefault=\"undirected\">",
+            escape(&self.name)
+        )?;
+
+        for atom in &topology.atoms {
+            writeln!(f, "    <node id=\"n{}\">", atom.id)?;
+            let values = [
+                ("name", escape(&names[atom.id])),
+                ("element", atom.element.to_string()),
+                ("type", escape(atom.atom_type.as_str())),
+                ("hybridization", atom.hybridization.to_string()),
+                ("aromatic", aromatic[atom.id].to_string()),
+                ("provenance", provenance_name(atom.provenance).to_string()),
+                ("fragment", atom.fragment_id.to_string()),
+            ];
+            for (key, value) in values {
+                writeln!(f, "      <data key=\"{key}\">{value}</data>")?;
+            }
+            writeln!(f, "    </node>")?;
+        }
+        for (index, bond) in topology.bonds.iter().enumerate() {
+            let (a, b) = bond.atom_ids;
+            writeln!(
+                f,
+                "    <edge id=\"e{index}\" source=\"n{a}\" target=\"n{b}\">"
+            )?;
+            writeln!(f, "      <data key=\"order\">{}</data>", bond.order)?;
+            if bond.image != [0; 3] {
+                let [x, y, z] = bond.image;
+                writeln!(f, "      <data key=\"image\">{x} {y} {z}</data>")?;
+            }
+            writeln!(f, "    </edge>")?;
+        }
+
+        writeln!(f, "  </graph>")?;
+        writeln!(f, "</graphml>")
+    }
+}
+
+impl MolecularTopology {
+    /// Renders the topology as a GraphML document with graph ID `MOL`.
+    ///
+    /// Use [`GraphMlDocument`] to set another ID.
+    ///
+    /// # Returns
+    ///
+    /// The complete GraphML document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::assign_topology;
+    /// use dreid_typer::io::smiles::parse_smiles;
+    ///
+    /// let graphml = assign_topology(&parse_smiles("c1ccccc1").unwrap()).unwrap().to_graphml();
+    /// assert!(graphml.contains("<data key=\"aromatic\">true</data>"));
+    /// assert!(graphml.contains("<data key=\"order\">Resonant</data>"));
+    /// ```
+    pub fn to_graphml(&self) -> String {
+        GraphMlDocument::new(self).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::metadata::AtomMetadata;
+    use crate::io::smiles::parse_smiles;
+
+    #[test]
+    fn writes_escaped_names_and_periodic_images() {
+        let mut topology = crate::assign_topology(&parse_smiles("CO").unwrap()).unwrap();
+        topology.atoms[1].metadata = AtomMetadata::named("O<1>");
+        topology.bonds[0].image = [0, 0, 1];
+
+        let graphml = GraphMlDocument::new(&topology).with_name("a&b").to_string();
+        assert!(graphml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml "));
+        assert!(graphml.contains("<graph id=\"a&amp;b\" edgedefault=\"undirected\">"));
+        assert!(graphml.contains(
+            "    <node id=\"n1\">\n      <data key=\"name\">O&lt;1&gt;</data>\n      \
+             <data key=\"element\">O</data>\n      <data key=\"type\">O_3</data>\n      \
+             <data key=\"hybridization\">SP3</data>\n      \
+             <data key=\"aromatic\">false</data>\n      \
+             <data key=\"provenance\">rule</data>\n      \
+             <data key=\"fragment\">0</data>\n    </node>"
+        ));
+        assert!(
+            graphml.contains(
+                "<data key=\"order\">Single</data>\n      <data key=\"image\">0 0 1</data>"
+            )
+        );
+        assert_eq!(graphml.matches("<node ").count(), topology.atoms.len());
+        assert_eq!(graphml.matches("<edge ").count(), topology.bonds.len());
+        assert_eq!(graphml.matches("<key ").count(), KEYS.len());
+        assert!(graphml.ends_with("  </graph>\n</graphml>\n"));
+    }
+}
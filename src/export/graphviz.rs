@@ -0,0 +1,234 @@
+//! Graphviz DOT export for auditing typing decisions.
+//!
+//! [`DotGraph`] writes the typed molecule as an undirected DOT graph that `dot` or `neato`
+//! renders directly. Every node is labeled with its atom name and type and carries the
+//! attributes `element`, `type`, `hybridization`, `aromatic`, `provenance`, and `fragment`;
+//! every edge carries its `order`. The drawing encodes the same data:
+//!
+//! | Feature               | Drawing                                              |
+//! |-----------------------|------------------------------------------------------|
+//! | Double, triple bond   | two or three parallel lines                          |
+//! | Resonant bond         | dashed line                                          |
+//! | Fallback-typed atom   | orange fill                                          |
+//! | Overridden atom       | light blue fill                                      |
+//! | Highlighted atom      | red, doubled outline                                 |
+//!
+//! Bonds of periodic topologies that cross a cell face also carry their `image`.
+
+use super::gromacs::atom_names;
+use crate::compat::prelude::*;
+use crate::core::properties::TopologyBondOrder;
+use crate::core::topology::{MolecularTopology, TypeProvenance};
+use core::fmt;
+
+/// Default graph name.
+const DEFAULT_NAME: &str = "MOL";
+
+/// A topology prepared for writing as a Graphviz DOT graph.
+///
+/// The graph is produced through the [`Display`](fmt::Display) implementation; use
+/// [`MolecularTopology::to_dot`] for the defaults.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::export::graphviz::DotGraph;
+/// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+///
+/// let mut graph = MolecularGraph::new();
+/// let c = graph.add_atom(Element::C);
+/// let o = graph.add_atom(Element::O);
+/// graph.add_bond(c, o, GraphBondOrder::Double).unwrap();
+/// for _ in 0..2 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+/// }
+/// let topology = assign_topology(&graph).unwrap();
+///
+/// let dot = DotGraph::new(&topology)
+///     .with_name("formaldehyde")
+///     .with_highlighted(&[o])
+///     .to_string();
+///
+/// assert!(dot.starts_with("graph \"formaldehyde\" {"));
+/// assert!(dot.contains("1 [label=\"O1\\nO_2\", element=\"O\", type=\"O_2\""));
+/// assert!(dot.contains("0 -- 1 [order=\"Double\", color=\"black:black\"];"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DotGraph<'a> {
+    topology: &'a MolecularTopology,
+    name: String,
+    highlighted: &'a [usize],
+}
+
+impl<'a> DotGraph<'a> {
+    /// Prepares a topology for export without highlighted atoms.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Typed topology to export.
+    pub fn new(topology: &'a MolecularTopology) -> Self {
+        Self {
+            topology,
+            name: DEFAULT_NAME.to_string(),
+            highlighted: &[],
+        }
+    }
+
+    /// Sets the graph name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Marks atoms to draw with a red, doubled outline, such as the atoms of a failed check.
+    ///
+    /// IDs that name no atom are ignored.
+    pub fn with_highlighted(mut self, atom_ids: &'a [usize]) -> Self {
+        self.highlighted = atom_ids;
+        self
+    }
+}
+
+impl fmt::Display for DotGraph<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let topology = self.topology;
+        let names = atom_names(topology);
+        let aromatic = aromatic_atoms(topology);
+
+        writeln!(f, "graph \"{}\" {{", escape(&self.name))?;
+        writeln!(f, "  graph [overlap=false];")?;
+        writeln!(
+            f,
+            "  node [shape=ellipse, style=filled, fillcolor=white, fontsize=10];"
+        )?;
+        for atom in &topology.atoms {
+            write!(
+                f,
+                "  {} [label=\"{}\\n{}\", element=\"{}\", type=\"{}\", hybridization=\"{}\", \
+                 aromatic={}, provenance=\"{}\", fragment={}",
+                atom.id,
+                escape(&names[atom.id]),
+                escape(atom.atom_type.as_str()),
+                atom.element,
+                escape(atom.atom_type.as_str()),
+                atom.hybridization,
+                aromatic[atom.id],
+                provenance_name(atom.provenance),
+                atom.fragment_id,
+            )?;
+            match atom.provenance {
+                TypeProvenance::Rule => {}
+                TypeProvenance::UffFallback | TypeProvenance::ElementFallback => {
+                    write!(f, ", fillcolor=orange")?
+                }
+                TypeProvenance::Override => write!(f, ", fillcolor=lightblue")?,
+            }
+            if self.highlighted.contains(&atom.id) {
+                write!(f, ", color=red, penwidth=2, peripheries=2")?;
+            }
+            writeln!(f, "];")?;
+        }
+        for bond in &topology.bonds {
+            let (a, b) = bond.atom_ids;
+            write!(f, "  {a} -- {b} [order=\"{}\"", bond.order)?;
+            match bond.order {
+                TopologyBondOrder::Single => {}
+                TopologyBondOrder::Double => write!(f, ", color=\"black:black\"")?,
+                TopologyBondOrder::Triple => write!(f, ", color=\"black:black:black\"")?,
+                TopologyBondOrder::Resonant => write!(f, ", style=dashed")?,
+            }
+            if bond.image != [0; 3] {
+                let [x, y, z] = bond.image;
+                write!(f, ", image=\"{x} {y} {z}\"")?;
+            }
+            writeln!(f, "];")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+impl MolecularTopology {
+    /// Renders the topology as a Graphviz DOT graph named `MOL`.
+    ///
+    /// Use [`DotGraph`] to rename the graph or highlight atoms.
+    ///
+    /// # Returns
+    ///
+    /// The complete DOT source, ready for `dot -Tsvg`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::assign_topology;
+    /// use dreid_typer::io::smiles::parse_smiles;
+    ///
+    /// let dot = assign_topology(&parse_smiles("c1ccccc1").unwrap()).unwrap().to_dot();
+    /// assert!(dot.contains("aromatic=true"));
+    /// assert!(dot.contains("0 -- 1 [order=\"Resonant\", style=dashed];"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        DotGraph::new(self).to_string()
+    }
+}
+
+/// Returns, for every atom, whether it lies on an aromatic ring.
+pub(super) fn aromatic_atoms(topology: &MolecularTopology) -> Vec<bool> {
+    let mut aromatic = vec![false; topology.atoms.len()];
+    for ring in topology.rings.iter().filter(|ring| ring.is_aromatic) {
+        for &atom_id in &ring.atom_ids {
+            aromatic[atom_id] = true;
+        }
+    }
+    aromatic
+}
+
+/// Returns the name written for a type provenance.
+pub(super) fn provenance_name(provenance: TypeProvenance) -> &'static str {
+    match provenance {
+        TypeProvenance::Rule => "rule",
+        TypeProvenance::UffFallback => "uff_fallback",
+        TypeProvenance::ElementFallback => "element_fallback",
+        TypeProvenance::Override => "override",
+    }
+}
+
+/// Escapes the characters DOT reserves inside quoted strings.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::smiles::parse_smiles;
+    use crate::{AssignmentPolicy, Typer};
+
+    #[test]
+    fn marks_fallback_atoms_and_images() {
+        let graph = parse_smiles("C[La]").unwrap();
+        let mut topology = Typer::new()
+            .with_assignment_policy(AssignmentPolicy::Fallback)
+            .assign_topology(&graph)
+            .unwrap();
+        topology.bonds[0].image = [1, 0, -1];
+        let fallback: Vec<usize> = topology.fallback_atoms().map(|atom| atom.id).collect();
+        assert!(!fallback.is_empty());
+
+        let dot = DotGraph::new(&topology)
+            .with_name("odd \"name\"")
+            .with_highlighted(&fallback)
+            .to_string();
+        assert!(dot.starts_with("graph \"odd \\\"name\\\"\" {\n"));
+        for id in &fallback {
+            let line = dot
+                .lines()
+                .find(|line| line.starts_with(&format!("  {id} [")))
+                .unwrap();
+            assert!(line.contains("provenance=\"element_fallback\", fragment=0, fillcolor=orange"));
+            assert!(line.ends_with("color=red, penwidth=2, peripheries=2];"));
+        }
+        assert!(dot.contains("image=\"1 0 -1\"];"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
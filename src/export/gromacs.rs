@@ -0,0 +1,466 @@
+//! GROMACS molecule topology (`.itp`) export.
+//!
+//! [`GromacsItp`] writes a `[ moleculetype ]` block followed by `[ atoms ]`, `[ bonds ]`,
+//! `[ angles ]`, and `[ dihedrals ]` sections, using the DREIDING labels directly as GROMACS
+//! atom type names. Function types follow the configured
+//! [`EnergyExpression`]:
+//!
+//! | Term       | Form            | GROMACS function type        |
+//! |------------|-----------------|------------------------------|
+//! | bond       | harmonic        | 1 (harmonic)                 |
+//! | bond       | Morse           | 3 (Morse)                    |
+//! | angle      | cosine-harmonic | 2 (GROMOS-96 cosine angle)   |
+//! | angle      | harmonic        | 1 (harmonic)                 |
+//! | torsion    | cosine          | 1 (periodic proper dihedral) |
+//! | inversion  | umbrella        | 2 (harmonic improper)        |
+//!
+//! GROMACS has no umbrella improper, so inversions are written as harmonic impropers with the
+//! central atom first, which matches the DREIDING planar umbrella near its minimum. Parameters
+//! are left for the caller's `[ *types ]` directives.
+
+use crate::compat::prelude::*;
+use crate::core::error::ExportError;
+use crate::core::topology::MolecularTopology;
+use crate::params::{AngleForm, BondForm, EnergyExpression, InversionForm, TorsionForm};
+use core::fmt;
+
+/// Default molecule name written to `[ moleculetype ]`.
+const DEFAULT_NAME: &str = "MOL";
+
+/// Number of bonds across which non-bonded interactions are excluded.
+///
+/// DREIDING excludes only 1-2 and 1-3 pairs and keeps 1-4 pairs at full strength, so they are
+/// left to the ordinary non-bonded kernel instead of a `[ pairs ]` section.
+const NREXCL: usize = 2;
+
+/// A topology prepared for writing as a GROMACS `.itp` include file.
+///
+/// The file is produced through the [`Display`](fmt::Display) implementation; use
+/// [`MolecularTopology::to_gromacs_itp`] for the defaults.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::export::gromacs::GromacsItp;
+/// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+///
+/// let mut graph = MolecularGraph::new();
+/// let o = graph.add_atom(Element::O);
+/// let h1 = graph.add_atom(Element::H);
+/// let h2 = graph.add_atom(Element::H);
+/// graph.add_bond(o, h1, GraphBondOrder::Single).unwrap();
+/// graph.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+/// let topology = assign_topology(&graph).unwrap();
+///
+/// let itp = GromacsItp::new(&topology)
+///     .with_name("SOL")
+///     .with_charges(&[-0.82, 0.41, 0.41])
+///     .unwrap()
+///     .to_string();
+///
+/// assert!(itp.contains("SOL  2"));
+/// assert!(itp.contains("[ angles ]"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GromacsItp<'a> {
+    topology: &'a MolecularTopology,
+    name: String,
+    charges: Option<&'a [f64]>,
+    expression: EnergyExpression,
+}
+
+impl<'a> GromacsItp<'a> {
+    /// Prepares a topology for export with zero charges and the DREIDING energy expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Typed topology to export.
+    pub fn new(topology: &'a MolecularTopology) -> Self {
+        Self {
+            topology,
+            name: DEFAULT_NAME.to_string(),
+            charges: None,
+            expression: EnergyExpression::DREIDING,
+        }
+    }
+
+    /// Sets the molecule name used in `[ moleculetype ]` and as the residue name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Supplies partial charges in elementary charges, one per atom.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExportError::LengthMismatch`] unless there is exactly one charge per atom.
+    pub fn with_charges(mut self, charges: &'a [f64]) -> Result<Self, ExportError> {
+        let expected = self.topology.atoms.len();
+        if charges.len() != expected {
+            return Err(ExportError::LengthMismatch {
+                field: "charges",
+                expected,
+                found: charges.len(),
+            });
+        }
+        self.charges = Some(charges);
+        Ok(self)
+    }
+
+    /// Selects the energy expression that determines the function type of every term.
+    pub fn with_energy_expression(mut self, expression: EnergyExpression) -> Self {
+        self.expression = expression;
+        self
+    }
+}
+
+impl fmt::Display for GromacsItp<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let topology = self.topology;
+        let label = |id: usize| topology.atoms[id].atom_type.as_str();
+
+        writeln!(f, "; generated by dreid-typer")?;
+        writeln!(f)?;
+        writeln!(f, "[ moleculetype ]")?;
+        writeln!(f, "; name  nrexcl")?;
+        writeln!(f, "{}  {NREXCL}", self.name)?;
+
+        writeln!(f)?;
+        writeln!(f, "[ atoms ]")?;
+        writeln!(
+            f,
+            ";   nr  type      resnr  residue  atom  cgnr  charge     mass"
+        )?;
+        let molecule_of = topology.molecule_indices();
+        let names = atom_names(topology);
+        for atom in &topology.atoms {
+            let charge = self.charges.map_or(0.0, |c| c[atom.id]);
+            writeln!(
+                f,
+                "{:>6}  {:<8}  {:>5}  {:<7}  {:<4}  {:>4}  {charge:>9.6}  {:>8.4}",
+                atom.id + 1,
+                atom.atom_type,
+                molecule_of[atom.id] + 1,
+                self.name,
+                names[atom.id],
+                atom.id + 1,
+                atom.element.atomic_mass(),
+            )?;
+        }
+
+        if !topology.bonds.is_empty() {
+            let funct = bond_function(self.expression.bond);
+            write!(f, "\n[ bonds ]\n;   ai     aj  funct\n")?;
+            for bond in &topology.bonds {
+                let (a, b) = bond.atom_ids;
+                writeln!(
+                    f,
+                    "{:>6} {:>6}  {funct:>5}  ; {}-{}",
+                    a + 1,
+                    b + 1,
+                    label(a),
+                    label(b)
+                )?;
+            }
+        }
+
+        if !topology.angles.is_empty() {
+            let funct = angle_function(self.expression.angle);
+            write!(f, "\n[ angles ]\n;   ai     aj     ak  funct\n")?;
+            for angle in &topology.angles {
+                let (i, j, k) = angle.atom_ids;
+                writeln!(
+                    f,
+                    "{:>6} {:>6} {:>6}  {funct:>5}  ; {}-{}-{}",
+                    i + 1,
+                    j + 1,
+                    k + 1,
+                    label(i),
+                    label(j),
+                    label(k)
+                )?;
+            }
+        }
+
+        if !topology.torsions.is_empty() {
+            let funct = torsion_function(self.expression.torsion);
+            write!(
+                f,
+                "\n[ dihedrals ]\n; propers\n;   ai     aj     ak     al  funct\n"
+            )?;
+            for torsion in &topology.torsions {
+                let (i, j, k, l) = torsion.atom_ids;
+                writeln!(
+                    f,
+                    "{:>6} {:>6} {:>6} {:>6}  {funct:>5}  ; {}-{}-{}-{}",
+                    i + 1,
+                    j + 1,
+                    k + 1,
+                    l + 1,
+                    label(i),
+                    label(j),
+                    label(k),
+                    label(l)
+                )?;
+            }
+        }
+
+        if !topology.inversions.is_empty() {
+            let funct = inversion_function(self.expression.inversion);
+            write!(
+                f,
+                "\n[ dihedrals ]\n; impropers\n;   ai     aj     ak     al  funct\n"
+            )?;
+            for inversion in &topology.inversions {
+                let (c, a, p1, p2) = inversion.atom_ids;
+                writeln!(
+                    f,
+                    "{:>6} {:>6} {:>6} {:>6}  {funct:>5}  ; {}-{}-{}-{}",
+                    c + 1,
+                    a + 1,
+                    p1 + 1,
+                    p2 + 1,
+                    label(c),
+                    label(a),
+                    label(p1),
+                    label(p2)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl MolecularTopology {
+    /// Renders the topology as a GROMACS `.itp` include file.
+    ///
+    /// The molecule is named `MOL`, every charge is zero, and function types follow the
+    /// DREIDING energy expression. Use [`GromacsItp`] to customize any of these.
+    ///
+    /// # Returns
+    ///
+    /// The complete `.itp` file contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+    ///
+    /// let mut graph = MolecularGraph::new();
+    /// let h = graph.add_atom(Element::H);
+    /// let f = graph.add_atom(Element::F);
+    /// graph.add_bond(h, f, GraphBondOrder::Single).unwrap();
+    ///
+    /// let itp = assign_topology(&graph).unwrap().to_gromacs_itp();
+    /// assert!(itp.contains("[ bonds ]"));
+    /// assert!(!itp.contains("[ angles ]"));
+    /// ```
+    pub fn to_gromacs_itp(&self) -> String {
+        GromacsItp::new(self).to_string()
+    }
+}
+
+/// Names every atom by its element symbol and a 1-based per-element counter (`C1`, `C2`, `H1`).
+///
+/// Atoms whose metadata carries a name, such as atoms read from PDB files, keep that name.
+pub(super) fn atom_names(topology: &MolecularTopology) -> Vec<String> {
+    let mut counters = [0usize; 256];
+    topology
+        .atoms
+        .iter()
+        .map(|atom| {
+            let counter = &mut counters[atom.element as usize];
+            *counter += 1;
+            match &atom.metadata.name {
+                Some(name) if !name.is_empty() => name.clone(),
+                _ => format!("{}{}", atom.element, counter),
+            }
+        })
+        .collect()
+}
+
+/// Maps a bond form to its GROMACS `[ bonds ]` function type.
+fn bond_function(form: BondForm) -> u8 {
+    match form {
+        BondForm::Harmonic => 1,
+        BondForm::Morse => 3,
+    }
+}
+
+/// Maps an angle form to its GROMACS `[ angles ]` function type.
+fn angle_function(form: AngleForm) -> u8 {
+    match form {
+        AngleForm::CosineHarmonic => 2,
+        AngleForm::Harmonic => 1,
+    }
+}
+
+/// Maps a torsion form to its GROMACS `[ dihedrals ]` function type.
+fn torsion_function(form: TorsionForm) -> u8 {
+    match form {
+        TorsionForm::Cosine => 1,
+    }
+}
+
+/// Maps an inversion form to its GROMACS improper `[ dihedrals ]` function type.
+fn inversion_function(form: InversionForm) -> u8 {
+    match form {
+        InversionForm::Umbrella => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::metadata::AtomMetadata;
+    use crate::core::properties::{Element, GraphBondOrder};
+    use crate::pipeline::Typer;
+
+    fn acetic_acid() -> MolecularTopology {
+        let mut graph = MolecularGraph::new();
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        let o1 = graph.add_atom(Element::O);
+        let o2 = graph.add_atom(Element::O);
+        graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+        graph.add_bond(c2, o1, GraphBondOrder::Double).unwrap();
+        graph.add_bond(c2, o2, GraphBondOrder::Single).unwrap();
+        let ho = graph.add_atom(Element::H);
+        graph.add_bond(o2, ho, GraphBondOrder::Single).unwrap();
+        for _ in 0..3 {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(c1, h, GraphBondOrder::Single).unwrap();
+        }
+        Typer::new().assign_topology(&graph).unwrap()
+    }
+
+    /// Returns the data lines of every section with the given header, skipping comments.
+    fn section<'t>(text: &'t str, header: &str) -> Vec<Vec<&'t str>> {
+        text.split(&format!("[ {header} ]\n"))
+            .skip(1)
+            .flat_map(|rest| rest.split("\n\n").next().unwrap_or("").lines())
+            .filter(|line| !line.starts_with(';'))
+            .map(|line| {
+                line.split(';')
+                    .next()
+                    .unwrap_or("")
+                    .split_whitespace()
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn itp_lists_every_term_with_dreiding_function_types() {
+        let topology = acetic_acid();
+        let itp = topology.to_gromacs_itp();
+
+        let atoms = section(&itp, "atoms");
+        assert_eq!(atoms.len(), topology.atoms.len());
+        assert_eq!(atoms[0][..5], ["1", "C_3", "1", "MOL", "C1"]);
+        assert_eq!(atoms[1][4], "C2");
+        assert_eq!(atoms[7][4], "H4");
+        assert_eq!(atoms[0][7], "12.0110");
+
+        let mut named = topology.clone();
+        named.atoms[1].metadata = AtomMetadata::named("CA");
+        let named_itp = named.to_gromacs_itp();
+        let named_atoms = section(&named_itp, "atoms");
+        assert_eq!(named_atoms[1][4], "CA");
+        assert_eq!(named_atoms[0][4], "C1");
+
+        let bonds = section(&itp, "bonds");
+        assert_eq!(bonds.len(), topology.bonds.len());
+        assert!(bonds.iter().all(|b| b[2] == "1"));
+
+        let angles = section(&itp, "angles");
+        assert_eq!(angles.len(), topology.angles.len());
+        assert!(angles.iter().all(|a| a[3] == "2"));
+
+        let dihedrals = section(&itp, "dihedrals");
+        assert_eq!(
+            dihedrals.len(),
+            topology.torsions.len() + topology.inversions.len()
+        );
+        let (propers, impropers) = dihedrals.split_at(topology.torsions.len());
+        assert!(propers.iter().all(|d| d[4] == "1"));
+        assert!(impropers.iter().all(|d| d[4] == "2"));
+        let (center, ..) = topology.inversions[0].atom_ids;
+        assert_eq!(impropers[0][0], (center + 1).to_string());
+    }
+
+    #[test]
+    fn one_four_pairs_are_not_excluded() {
+        let mut graph = MolecularGraph::new();
+        let carbons: Vec<usize> = (0..4).map(|_| graph.add_atom(Element::C)).collect();
+        for pair in carbons.windows(2) {
+            graph
+                .add_bond(pair[0], pair[1], GraphBondOrder::Single)
+                .unwrap();
+        }
+        let topology = Typer::new().assign_topology(&graph).unwrap();
+        let itp = topology.to_gromacs_itp();
+
+        let nrexcl: usize = section(&itp, "moleculetype")[0][1].parse().unwrap();
+        assert_eq!(nrexcl, 2);
+        assert!(!itp.contains("[ pairs ]"));
+
+        // Butane's terminal carbons are a 1-4 pair three bonds apart, beyond the exclusions.
+        let bonds = section(&itp, "bonds");
+        let mut hops = vec![usize::MAX; topology.atoms.len()];
+        hops[carbons[0]] = 0;
+        for depth in 0..topology.atoms.len() {
+            for bond in &bonds {
+                let (i, j) = (
+                    bond[0].parse::<usize>().unwrap() - 1,
+                    bond[1].parse::<usize>().unwrap() - 1,
+                );
+                for (from, to) in [(i, j), (j, i)] {
+                    if hops[from] == depth && hops[to] == usize::MAX {
+                        hops[to] = depth + 1;
+                    }
+                }
+            }
+        }
+        assert_eq!(hops[carbons[3]], 3);
+        assert!(hops[carbons[3]] > nrexcl);
+    }
+
+    #[test]
+    fn energy_expression_selects_function_types() {
+        let topology = acetic_acid();
+        let itp = GromacsItp::new(&topology)
+            .with_energy_expression(EnergyExpression::dreiding().with_bond(BondForm::Morse))
+            .to_string();
+        assert!(section(&itp, "bonds").iter().all(|b| b[2] == "3"));
+    }
+
+    #[test]
+    fn charges_must_match_atom_count() {
+        let topology = acetic_acid();
+        let err = GromacsItp::new(&topology)
+            .with_charges(&[0.0; 3])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ExportError::LengthMismatch {
+                field: "charges",
+                expected: 8,
+                found: 3,
+            }
+        );
+
+        let charges = [0.25; 8];
+        let itp = GromacsItp::new(&topology)
+            .with_name("ACE")
+            .with_charges(&charges)
+            .unwrap()
+            .to_string();
+        let atoms = section(&itp, "atoms");
+        assert_eq!(atoms[0][3], "ACE");
+        assert_eq!(atoms[0][6], "0.250000");
+    }
+}
@@ -0,0 +1,1274 @@
+//! JSON rendering of typed topologies and the versioned JSON interchange format.
+//!
+//! [`to_json`] renders a compact view of a [`MolecularTopology`]: an `atoms` array with element,
+//! type, and hybridization per atom, a `bonds` array with atom pairs and orders, and plain index
+//! tuples for angles, torsions, inversions, and hydrogen-bond sites. Terms are sorted first, so
+//! the same topology always renders to the same document. It is what the `dreid-typer` CLI and
+//! the WebAssembly bindings emit.
+//!
+//! # Interchange format
+//!
+//! The interchange documents carry every field of the crate's intermediate data, so other tools
+//! can produce input for any stage and read back its result without loss. Each document is a
+//! JSON object with a header
+//!
+//! ```json
+//! { "format": "dreid-typer", "version": 1, "kind": "graph" }
+//! ```
+//!
+//! where `kind` is one of:
+//!
+//! * `"graph"` ([`graph_to_json`], [`graph_from_json`]) — a [`MolecularGraph`]. `atoms` holds
+//!   the `id`, `element`, and optional `chirality` and `metadata` of every atom; `bonds` holds
+//!   the `atoms`, `order`, and optional `stereo` of every bond in bond ID order; the optional
+//!   `positions` holds coordinates in Å.
+//! * `"topology"` ([`topology_to_json`], [`topology_from_json`]) — a [`MolecularTopology`]. Atoms
+//!   add `type`, `hybridization`, `provenance`, and `fragment`; bonds add a non-zero `image` and a
+//!   `class` other than covalent; `angles`, `torsions`, `suppressed_torsions`, and `inversions`
+//!   hold `atoms` and `class`; `rings` hold `atoms`, `aromatic`, and `system`; the optional
+//!   `virtual_sites` hold the `site`, `parent`, `frame`, and `index` of every lone-pair site.
+//! * `"perception"` ([`perception_to_json`], [`perception_from_json`]) — an
+//!   [`AnnotatedMolecule`] as seen by a custom perception stage: the electron, ring,
+//!   aromaticity, hybridization, and functional-group annotations of every atom, the current
+//!   bond orders, `rings`, `macrocycles`, `resonance_systems` with their `atoms` and `bonds`,
+//!   and the optional `aromatic_bonds` given as aromatic in the input, `bond_images`, and
+//!   `positions`.
+//!
+//! Elements, bond orders, hybridizations, and functional groups are written by their `Display`
+//! names (`"C"`, `"Resonant"`, `"SP2"`, `"Carboxylate"`). The remaining enumerations use snake-case
+//! names: chirality `"clockwise"` or `"counterclockwise"`, bond stereo `"cis"` or `"trans"`,
+//! provenance `"rule"`, `"uff_fallback"`, `"element_fallback"`, or `"override"`, bond classes
+//! `"covalent"`, `"disulfide"`, or `"coordination"`, angle classes `"linear"`, `"trigonal"`,
+//! `"tetrahedral"`, or `"other"`, torsion classes named after the [`TorsionClass`] variants
+//! (`"sp3_sp3"`, `"resonant"`, `"oxygen_pair"`, ...), and inversion classes `"planar"` or
+//! `"non_planar"`. Optional fields are omitted when empty.
+//!
+//! Readers accept exactly [`FORMAT_VERSION`]; the version is raised whenever the format changes
+//! incompatibly. Terms keep their stored order, so writing a topology, reading it back, and
+//! writing it again yields the same document.
+
+use crate::core::error::{ExportError, InterchangeError};
+use crate::core::graph::{BondEdge, MolecularGraph};
+use crate::core::metadata::{AtomMetadata, ResidueId};
+use crate::core::periodic::ImageFlags;
+use crate::core::properties::{DreidingType, Element};
+use crate::core::stereo::{BondStereo, Chirality};
+use crate::core::topology::{
+    Angle, AngleClass, Atom, Bond, BondClass, HydrogenBondDonor, Inversion, InversionClass,
+    MolecularTopology, Ring, Torsion, TorsionClass, TypeProvenance, VirtualSite,
+    count_shared_torsions,
+};
+use crate::perception::{AnnotatedMolecule, ResonanceSystem};
+use alloc::collections::BTreeMap;
+use core::str::FromStr;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// Value of the `format` field that marks an interchange document.
+pub const FORMAT_NAME: &str = "dreid-typer";
+
+/// Version of the interchange format written and read by this crate.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Renders a topology as a JSON value.
+///
+/// # Arguments
+///
+/// * `topology` - Typed topology to render.
+/// * `positions` - Optional Cartesian coordinates in Å, one per atom, added to every atom as
+///   `position`.
+///
+/// # Returns
+///
+/// A JSON object with the keys `atoms`, `bonds`, `angles`, `torsions`, `inversions`,
+/// `hb_donors`, and `hb_acceptors`.
+///
+/// # Errors
+///
+/// Returns [`ExportError::LengthMismatch`] unless `positions` has exactly one entry per atom.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::export::json::to_json;
+/// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+///
+/// let mut graph = MolecularGraph::new();
+/// let o = graph.add_atom(Element::O);
+/// for _ in 0..2 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+/// }
+/// let topology = assign_topology(&graph).unwrap();
+///
+/// let document = to_json(&topology, None).unwrap();
+/// assert_eq!(document["atoms"][0]["type"], "O_3");
+/// assert_eq!(document["angles"][0], serde_json::json!([1, 0, 2]));
+/// ```
+pub fn to_json(
+    topology: &MolecularTopology,
+    positions: Option<&[[f64; 3]]>,
+) -> Result<Value, ExportError> {
+    if let Some(positions) = positions
+        && positions.len() != topology.atoms.len()
+    {
+        return Err(ExportError::LengthMismatch {
+            field: "positions",
+            expected: topology.atoms.len(),
+            found: positions.len(),
+        });
+    }
+
+    let mut topology = topology.clone();
+    topology.sort_terms();
+
+    let atoms: Vec<Value> = topology
+        .atoms
+        .iter()
+        .map(|atom| {
+            let mut entry = json!({
+                "id": atom.id,
+                "element": atom.element.to_string(),
+                "type": atom.atom_type,
+                "hybridization": atom.hybridization.to_string(),
+            });
+            if let Some(positions) = positions {
+                entry["position"] = json!(positions[atom.id]);
+            }
+            entry
+        })
+        .collect();
+    let bonds: Vec<Value> = topology
+        .bonds
+        .iter()
+        .map(|bond| {
+            json!({
+                "atoms": [bond.atom_ids.0, bond.atom_ids.1],
+                "order": bond.order.to_string(),
+            })
+        })
+        .collect();
+    let hb_donors: Vec<[usize; 2]> = topology
+        .hb_donors
+        .iter()
+        .map(|donor| [donor.donor_id, donor.hydrogen_id])
+        .collect();
+
+    Ok(json!({
+        "atoms": atoms,
+        "bonds": bonds,
+        "angles": topology.angles.iter().map(|angle| angle.atom_ids).collect::<Vec<_>>(),
+        "torsions": topology.torsions.iter().map(|torsion| torsion.atom_ids).collect::<Vec<_>>(),
+        "inversions": topology
+            .inversions
+            .iter()
+            .map(|inversion| inversion.atom_ids)
+            .collect::<Vec<_>>(),
+        "hb_donors": hb_donors,
+        "hb_acceptors": topology.hb_acceptors,
+    }))
+}
+
+/// Renders a molecular graph as an interchange document of kind `"graph"`.
+///
+/// # Arguments
+///
+/// * `graph` - Graph to render.
+///
+/// # Returns
+///
+/// A JSON object with the interchange header and the keys `atoms`, `bonds`, and, if the graph
+/// has coordinates, `positions`.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::export::json::{graph_from_json, graph_to_json};
+/// use dreid_typer::io::smiles::parse_smiles;
+///
+/// let graph = parse_smiles("F/C=C/F").unwrap();
+/// let document = graph_to_json(&graph);
+/// assert_eq!(document["kind"], "graph");
+/// assert_eq!(document["bonds"][1]["stereo"], "trans");
+///
+/// let read = graph_from_json(&document).unwrap();
+/// assert_eq!(read.bonds[1].stereo, graph.bonds[1].stereo);
+/// ```
+pub fn graph_to_json(graph: &MolecularGraph) -> Value {
+    let body = GraphBody {
+        atoms: graph
+            .atoms
+            .iter()
+            .map(|atom| GraphAtomEntry {
+                id: atom.id,
+                element: atom.element.to_string(),
+                chirality: atom.chirality.map(|c| chirality_name(c).to_string()),
+                formal_charge: atom.formal_charge,
+                radical_electrons: atom.radical_electrons,
+                isotope: atom.isotope,
+                metadata: MetadataEntry::from(&atom.metadata),
+            })
+            .collect(),
+        bonds: graph.bonds.iter().map(GraphBondEntry::from).collect(),
+        positions: graph.positions.clone(),
+    };
+    document("graph", &body)
+}
+
+/// Reads a molecular graph from an interchange document of kind `"graph"`.
+///
+/// # Arguments
+///
+/// * `document` - Document produced by [`graph_to_json`] or by another tool.
+///
+/// # Returns
+///
+/// The graph, with atoms and bonds numbered in document order.
+///
+/// # Errors
+///
+/// Returns [`InterchangeError`] if the header is missing or of another version or kind, if the
+/// document is malformed or names an unknown value, or if its bonds or positions do not fit its
+/// atoms.
+pub fn graph_from_json(document: &Value) -> Result<MolecularGraph, InterchangeError> {
+    let body: GraphBody = read_document(document, "graph")?;
+    let atoms: Vec<_> = body
+        .atoms
+        .iter()
+        .map(|atom| (atom.id, &atom.element, &atom.chirality, &atom.metadata))
+        .collect();
+    let mut graph = build_graph(&atoms, &body.bonds)?;
+    for atom in &body.atoms {
+        graph
+            .set_electron_state(atom.id, atom.formal_charge, atom.radical_electrons)
+            .and_then(|()| graph.set_isotope(atom.id, atom.isotope))
+            .map_err(InterchangeError::InvalidGraph)?;
+    }
+    if let Some(positions) = body.positions {
+        graph
+            .set_positions(positions)
+            .map_err(InterchangeError::InvalidGraph)?;
+    }
+    Ok(graph)
+}
+
+/// Renders a typed topology as an interchange document of kind `"topology"`.
+///
+/// Unlike [`to_json`], every field of the topology is kept and terms are written in their
+/// stored order.
+///
+/// # Arguments
+///
+/// * `topology` - Topology to render.
+///
+/// # Returns
+///
+/// A JSON object with the interchange header and the keys `atoms`, `bonds`, `angles`,
+/// `torsions`, `inversions`, `hb_donors`, `hb_acceptors`, `rings`, and, if any,
+/// `suppressed_torsions` and `virtual_sites`.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::assign_topology;
+/// use dreid_typer::export::json::{topology_from_json, topology_to_json};
+/// use dreid_typer::io::smiles::parse_smiles;
+///
+/// let topology = assign_topology(&parse_smiles("c1ccccc1O").unwrap()).unwrap();
+/// let document = topology_to_json(&topology);
+/// assert_eq!(document["rings"][0]["aromatic"], true);
+///
+/// let text = document.to_string();
+/// let read = topology_from_json(&serde_json::from_str(&text).unwrap()).unwrap();
+/// assert_eq!(read, topology);
+/// ```
+pub fn topology_to_json(topology: &MolecularTopology) -> Value {
+    let body = TopologyBody {
+        atoms: topology
+            .atoms
+            .iter()
+            .map(|atom| TopologyAtomEntry {
+                id: atom.id,
+                element: atom.element.to_string(),
+                atom_type: atom.atom_type.to_string(),
+                hybridization: atom.hybridization.to_string(),
+                provenance: provenance_name(atom.provenance).to_string(),
+                fragment: atom.fragment_id,
+                chirality: atom.chirality.map(|c| chirality_name(c).to_string()),
+                metadata: MetadataEntry::from(&atom.metadata),
+            })
+            .collect(),
+        bonds: topology
+            .bonds
+            .iter()
+            .map(|bond| TopologyBondEntry {
+                atoms: [bond.atom_ids.0, bond.atom_ids.1],
+                order: bond.order.to_string(),
+                stereo: bond.stereo.map(|s| stereo_name(s).to_string()),
+                image: bond.image,
+                class: (bond.class != BondClass::Covalent)
+                    .then(|| bond_class_name(bond.class).to_string()),
+            })
+            .collect(),
+        angles: topology
+            .angles
+            .iter()
+            .map(|angle| AngleEntry {
+                atoms: [angle.atom_ids.0, angle.atom_ids.1, angle.atom_ids.2],
+                class: angle_class_name(angle.class).to_string(),
+            })
+            .collect(),
+        torsions: topology.torsions.iter().map(torsion_entry).collect(),
+        suppressed_torsions: topology
+            .suppressed_torsions
+            .iter()
+            .map(torsion_entry)
+            .collect(),
+        inversions: topology
+            .inversions
+            .iter()
+            .map(|inversion| {
+                let (a, b, c, d) = inversion.atom_ids;
+                DihedralEntry {
+                    atoms: [a, b, c, d],
+                    class: inversion_class_name(inversion.class).to_string(),
+                }
+            })
+            .collect(),
+        hb_donors: topology
+            .hb_donors
+            .iter()
+            .map(|donor| [donor.donor_id, donor.hydrogen_id])
+            .collect(),
+        hb_acceptors: topology.hb_acceptors.clone(),
+        rings: topology
+            .rings
+            .iter()
+            .map(|ring| RingEntry {
+                atoms: ring.atom_ids.clone(),
+                aromatic: ring.is_aromatic,
+                system: ring.system_id,
+            })
+            .collect(),
+        virtual_sites: topology
+            .virtual_sites
+            .iter()
+            .map(|site| VirtualSiteEntry {
+                site: site.site_id,
+                parent: site.parent_id,
+                frame: site.frame_ids.clone(),
+                index: site.index,
+            })
+            .collect(),
+    };
+    document("topology", &body)
+}
+
+/// Reads a typed topology from an interchange document of kind `"topology"`.
+///
+/// Terms are taken as written; atom IDs inside each term are not re-sorted.
+///
+/// # Arguments
+///
+/// * `document` - Document produced by [`topology_to_json`] or by another tool.
+///
+/// # Returns
+///
+/// The topology described by the document.
+///
+/// # Errors
+///
+/// Returns [`InterchangeError`] if the header is missing or of another version or kind, if the
+/// document is malformed or names an unknown value, if an atom entry is out of place, or if a
+/// term, ring, or site refers to an atom the document does not define.
+pub fn topology_from_json(document: &Value) -> Result<MolecularTopology, InterchangeError> {
+    let body: TopologyBody = read_document(document, "topology")?;
+    let atom_count = body.atoms.len();
+    let check = |ids: &[usize]| check_atoms(ids, atom_count);
+
+    let atoms = body
+        .atoms
+        .into_iter()
+        .enumerate()
+        .map(|(index, atom)| {
+            check_id(index, atom.id)?;
+            Ok(Atom {
+                id: atom.id,
+                element: parse("element", &atom.element)?,
+                atom_type: DreidingType::from(atom.atom_type),
+                hybridization: parse("hybridization", &atom.hybridization)?,
+                provenance: parse_provenance(&atom.provenance)?,
+                fragment_id: atom.fragment,
+                chirality: atom.chirality.as_deref().map(parse_chirality).transpose()?,
+                metadata: atom.metadata.into(),
+            })
+        })
+        .collect::<Result<Vec<_>, InterchangeError>>()?;
+    let bonds = body
+        .bonds
+        .into_iter()
+        .map(|bond| {
+            check(&bond.atoms)?;
+            Ok(Bond {
+                atom_ids: (bond.atoms[0], bond.atoms[1]),
+                order: parse("bond order", &bond.order)?,
+                stereo: bond.stereo.as_deref().map(parse_stereo).transpose()?,
+                image: bond.image,
+                class: bond
+                    .class
+                    .as_deref()
+                    .map(parse_bond_class)
+                    .transpose()?
+                    .unwrap_or_default(),
+            })
+        })
+        .collect::<Result<Vec<_>, InterchangeError>>()?;
+    let angles = body
+        .angles
+        .into_iter()
+        .map(|angle| {
+            check(&angle.atoms)?;
+            let [a, b, c] = angle.atoms;
+            Ok(Angle {
+                atom_ids: (a, b, c),
+                class: parse_angle_class(&angle.class)?,
+            })
+        })
+        .collect::<Result<Vec<_>, InterchangeError>>()?;
+    let read_torsions = |entries: Vec<DihedralEntry>| {
+        entries
+            .into_iter()
+            .map(|torsion| {
+                check(&torsion.atoms)?;
+                let [a, b, c, d] = torsion.atoms;
+                Ok(Torsion {
+                    atom_ids: (a, b, c, d),
+                    class: parse_torsion_class(&torsion.class)?,
+                    shared_count: 1,
+                })
+            })
+            .collect::<Result<Vec<_>, InterchangeError>>()
+            .map(|mut torsions| {
+                count_shared_torsions(&mut torsions);
+                torsions
+            })
+    };
+    let torsions = read_torsions(body.torsions)?;
+    let suppressed_torsions = read_torsions(body.suppressed_torsions)?;
+    let inversions = body
+        .inversions
+        .into_iter()
+        .map(|inversion| {
+            check(&inversion.atoms)?;
+            let [a, b, c, d] = inversion.atoms;
+            Ok(Inversion {
+                atom_ids: (a, b, c, d),
+                class: parse_inversion_class(&inversion.class)?,
+            })
+        })
+        .collect::<Result<Vec<_>, InterchangeError>>()?;
+    let hb_donors = body
+        .hb_donors
+        .into_iter()
+        .map(|pair| {
+            check(&pair)?;
+            Ok(HydrogenBondDonor {
+                donor_id: pair[0],
+                hydrogen_id: pair[1],
+            })
+        })
+        .collect::<Result<Vec<_>, InterchangeError>>()?;
+    check(&body.hb_acceptors)?;
+    let rings = body
+        .rings
+        .into_iter()
+        .map(|ring| {
+            check(&ring.atoms)?;
+            Ok(Ring {
+                atom_ids: ring.atoms,
+                is_aromatic: ring.aromatic,
+                system_id: ring.system,
+            })
+        })
+        .collect::<Result<Vec<_>, InterchangeError>>()?;
+    let virtual_sites = body
+        .virtual_sites
+        .into_iter()
+        .map(|site| {
+            check(&[site.site, site.parent])?;
+            check(&site.frame)?;
+            Ok(VirtualSite {
+                site_id: site.site,
+                parent_id: site.parent,
+                frame_ids: site.frame,
+                index: site.index,
+            })
+        })
+        .collect::<Result<Vec<_>, InterchangeError>>()?;
+
+    Ok(MolecularTopology {
+        atoms,
+        bonds,
+        angles,
+        torsions,
+        suppressed_torsions,
+        inversions,
+        hb_donors,
+        hb_acceptors: body.hb_acceptors,
+        rings,
+        virtual_sites,
+    })
+}
+
+/// Renders the state of a perceived molecule as an interchange document of kind
+/// `"perception"`.
+///
+/// This is how a [custom stage](crate::PerceptionPipelineBuilder) can hand the annotations
+/// perceived so far to an external tool. Adjacency lists, degrees, and metal-center flags are
+/// derived from the bonds and not written.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule to render.
+///
+/// # Returns
+///
+/// A JSON object with the interchange header and the keys `atoms`, `bonds`, `rings`,
+/// `resonance_systems`, and, if present, `macrocycles`, `aromatic_bonds`, `bond_images`, and
+/// `positions`.
+pub fn perception_to_json(molecule: &AnnotatedMolecule) -> Value {
+    let body = PerceptionBody {
+        atoms: molecule
+            .atoms
+            .iter()
+            .map(|atom| PerceivedAtomEntry {
+                id: atom.id,
+                element: atom.element.to_string(),
+                chirality: atom.chirality.map(|c| chirality_name(c).to_string()),
+                formal_charge: atom.formal_charge,
+                lone_pairs: atom.lone_pairs,
+                radical_electrons: atom.radical_electrons,
+                steric_number: atom.steric_number,
+                hybridization: atom.hybridization.to_string(),
+                in_ring: atom.is_in_ring,
+                smallest_ring: atom.smallest_ring_size,
+                bridgehead: atom.is_bridgehead,
+                spiro: atom.is_spiro,
+                aromatic: atom.is_aromatic,
+                anti_aromatic: atom.is_anti_aromatic,
+                resonant: atom.is_resonant,
+                aromatic_edge: atom.has_aromatic_edge,
+                functional_groups: atom
+                    .functional_groups
+                    .iter()
+                    .map(|group| group.to_string())
+                    .collect(),
+            })
+            .collect(),
+        bonds: molecule.bonds.iter().map(GraphBondEntry::from).collect(),
+        rings: molecule.rings.clone(),
+        macrocycles: molecule.macrocycles.clone(),
+        resonance_systems: molecule
+            .resonance_systems
+            .iter()
+            .map(|system| ResonanceEntry {
+                atoms: system.atom_ids.clone(),
+                bonds: system.bond_ids.clone(),
+            })
+            .collect(),
+        aromatic_bonds: molecule.aromatic_input_bonds.clone(),
+        bond_images: molecule.bond_images.clone(),
+        positions: molecule.positions.clone(),
+    };
+    document("perception", &body)
+}
+
+/// Reads a perceived molecule from an interchange document of kind `"perception"`.
+///
+/// # Arguments
+///
+/// * `document` - Document produced by [`perception_to_json`] or by another tool.
+///
+/// # Returns
+///
+/// The annotated molecule, with adjacency lists, degrees, and metal-center flags rebuilt from
+/// its bonds.
+///
+/// # Errors
+///
+/// Returns [`InterchangeError`] if the header is missing or of another version or kind, if the
+/// document is malformed or names an unknown value, if its bonds, positions, or bond images do
+/// not fit its atoms, or if a ring or resonance system refers to an undefined atom or bond.
+pub fn perception_from_json(document: &Value) -> Result<AnnotatedMolecule, InterchangeError> {
+    let body: PerceptionBody = read_document(document, "perception")?;
+    let no_metadata = MetadataEntry::default();
+    let atoms: Vec<_> = body
+        .atoms
+        .iter()
+        .map(|atom| (atom.id, &atom.element, &atom.chirality, &no_metadata))
+        .collect();
+    let mut graph = build_graph(&atoms, &body.bonds)?;
+    graph.positions = body.positions;
+
+    let mut molecule = AnnotatedMolecule::new(&graph).map_err(InterchangeError::InvalidGraph)?;
+    let atom_count = molecule.atoms.len();
+    let bond_count = molecule.bonds.len();
+
+    for (annotated, entry) in molecule.atoms.iter_mut().zip(body.atoms) {
+        annotated.formal_charge = entry.formal_charge;
+        annotated.lone_pairs = entry.lone_pairs;
+        annotated.radical_electrons = entry.radical_electrons;
+        annotated.steric_number = entry.steric_number;
+        annotated.hybridization = parse("hybridization", &entry.hybridization)?;
+        annotated.is_in_ring = entry.in_ring;
+        annotated.smallest_ring_size = entry.smallest_ring;
+        annotated.is_bridgehead = entry.bridgehead;
+        annotated.is_spiro = entry.spiro;
+        annotated.is_aromatic = entry.aromatic;
+        annotated.is_anti_aromatic = entry.anti_aromatic;
+        annotated.is_resonant = entry.resonant;
+        annotated.has_aromatic_edge = entry.aromatic_edge;
+        annotated.functional_groups = entry
+            .functional_groups
+            .iter()
+            .map(|group| parse("functional group", group))
+            .collect::<Result<_, _>>()?;
+    }
+    for ring in body.rings.iter().chain(&body.macrocycles) {
+        check_atoms(ring, atom_count)?;
+    }
+    for system in &body.resonance_systems {
+        check_atoms(&system.atoms, atom_count)?;
+    }
+    let bond_lists = body.resonance_systems.iter().map(|system| &system.bonds);
+    for bonds in bond_lists.chain([&body.aromatic_bonds]) {
+        if let Some(&bond_id) = bonds.iter().find(|&&id| id >= bond_count) {
+            return Err(InterchangeError::BondOutOfRange {
+                bond_id,
+                bond_count,
+            });
+        }
+    }
+    if !body.bond_images.is_empty() && body.bond_images.len() != bond_count {
+        return Err(InterchangeError::LengthMismatch {
+            field: "bond_images",
+            expected: bond_count,
+            found: body.bond_images.len(),
+        });
+    }
+
+    molecule.rings = body.rings;
+    molecule.macrocycles = body.macrocycles;
+    molecule.resonance_systems = body
+        .resonance_systems
+        .into_iter()
+        .map(|system| ResonanceSystem {
+            atom_ids: system.atoms,
+            bond_ids: system.bonds,
+        })
+        .collect();
+    molecule.aromatic_input_bonds = body.aromatic_bonds;
+    molecule.bond_images = body.bond_images;
+    Ok(molecule)
+}
+
+/// Header fields shared by every interchange document.
+#[derive(Deserialize)]
+struct Header {
+    format: Option<String>,
+    version: Option<u64>,
+    kind: Option<String>,
+}
+
+/// Body of a `"graph"` document.
+#[derive(Serialize, Deserialize)]
+struct GraphBody {
+    atoms: Vec<GraphAtomEntry>,
+    bonds: Vec<GraphBondEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    positions: Option<Vec<[f64; 3]>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GraphAtomEntry {
+    id: usize,
+    element: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    chirality: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    formal_charge: Option<i8>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    radical_electrons: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    isotope: Option<u16>,
+    #[serde(default, skip_serializing_if = "MetadataEntry::is_empty")]
+    metadata: MetadataEntry,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GraphBondEntry {
+    atoms: [usize; 2],
+    order: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stereo: Option<String>,
+}
+
+impl From<&BondEdge> for GraphBondEntry {
+    fn from(bond: &BondEdge) -> Self {
+        Self {
+            atoms: [bond.atom_ids.0, bond.atom_ids.1],
+            order: bond.order.to_string(),
+            stereo: bond.stereo.map(|s| stereo_name(s).to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+struct MetadataEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    residue: Option<ResidueEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    tags: BTreeMap<String, String>,
+}
+
+impl MetadataEntry {
+    fn is_empty(&self) -> bool {
+        self.name.is_none() && self.residue.is_none() && self.tags.is_empty()
+    }
+}
+
+impl From<&AtomMetadata> for MetadataEntry {
+    fn from(metadata: &AtomMetadata) -> Self {
+        Self {
+            name: metadata.name.clone(),
+            residue: metadata.residue.as_ref().map(|residue| ResidueEntry {
+                name: residue.name.clone(),
+                chain: residue.chain_id,
+                number: residue.number,
+            }),
+            tags: metadata.tags.clone(),
+        }
+    }
+}
+
+impl From<MetadataEntry> for AtomMetadata {
+    fn from(entry: MetadataEntry) -> Self {
+        Self {
+            name: entry.name,
+            residue: entry
+                .residue
+                .map(|residue| ResidueId::new(residue.name, residue.chain, residue.number)),
+            tags: entry.tags,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct ResidueEntry {
+    name: String,
+    chain: char,
+    number: i32,
+}
+
+/// Body of a `"topology"` document.
+#[derive(Serialize, Deserialize)]
+struct TopologyBody {
+    atoms: Vec<TopologyAtomEntry>,
+    bonds: Vec<TopologyBondEntry>,
+    angles: Vec<AngleEntry>,
+    torsions: Vec<DihedralEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    suppressed_torsions: Vec<DihedralEntry>,
+    inversions: Vec<DihedralEntry>,
+    hb_donors: Vec<[usize; 2]>,
+    hb_acceptors: Vec<usize>,
+    rings: Vec<RingEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    virtual_sites: Vec<VirtualSiteEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TopologyAtomEntry {
+    id: usize,
+    element: String,
+    #[serde(rename = "type")]
+    atom_type: String,
+    hybridization: String,
+    provenance: String,
+    fragment: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    chirality: Option<String>,
+    #[serde(default, skip_serializing_if = "MetadataEntry::is_empty")]
+    metadata: MetadataEntry,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TopologyBondEntry {
+    atoms: [usize; 2],
+    order: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stereo: Option<String>,
+    #[serde(default, skip_serializing_if = "is_zero_image")]
+    image: ImageFlags,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    class: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AngleEntry {
+    atoms: [usize; 3],
+    class: String,
+}
+
+/// Four-atom term: a torsion or an inversion.
+#[derive(Serialize, Deserialize)]
+struct DihedralEntry {
+    atoms: [usize; 4],
+    class: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct VirtualSiteEntry {
+    site: usize,
+    parent: usize,
+    frame: Vec<usize>,
+    index: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RingEntry {
+    atoms: Vec<usize>,
+    aromatic: bool,
+    system: usize,
+}
+
+/// Body of a `"perception"` document.
+#[derive(Serialize, Deserialize)]
+struct PerceptionBody {
+    atoms: Vec<PerceivedAtomEntry>,
+    bonds: Vec<GraphBondEntry>,
+    rings: Vec<Vec<usize>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    macrocycles: Vec<Vec<usize>>,
+    resonance_systems: Vec<ResonanceEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    aromatic_bonds: Vec<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    bond_images: Vec<ImageFlags>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    positions: Option<Vec<[f64; 3]>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PerceivedAtomEntry {
+    id: usize,
+    element: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    chirality: Option<String>,
+    formal_charge: i8,
+    lone_pairs: u8,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    radical_electrons: u8,
+    steric_number: u8,
+    hybridization: String,
+    in_ring: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    smallest_ring: Option<u8>,
+    #[serde(default)]
+    bridgehead: bool,
+    #[serde(default)]
+    spiro: bool,
+    aromatic: bool,
+    anti_aromatic: bool,
+    resonant: bool,
+    aromatic_edge: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    functional_groups: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ResonanceEntry {
+    atoms: Vec<usize>,
+    bonds: Vec<usize>,
+}
+
+fn is_zero_image(image: &ImageFlags) -> bool {
+    *image == [0; 3]
+}
+
+fn is_zero(count: &u8) -> bool {
+    *count == 0
+}
+
+fn torsion_entry(torsion: &Torsion) -> DihedralEntry {
+    let (a, b, c, d) = torsion.atom_ids;
+    DihedralEntry {
+        atoms: [a, b, c, d],
+        class: torsion_class_name(torsion.class).to_string(),
+    }
+}
+
+/// Wraps a serialized body with the interchange header.
+fn document(kind: &str, body: &impl Serialize) -> Value {
+    let mut value = serde_json::to_value(body).expect("interchange bodies always serialize");
+    let object = value
+        .as_object_mut()
+        .expect("interchange bodies serialize to objects");
+    object.insert("format".into(), json!(FORMAT_NAME));
+    object.insert("version".into(), json!(FORMAT_VERSION));
+    object.insert("kind".into(), json!(kind));
+    value
+}
+
+/// Checks the header of a document and deserializes its body.
+fn read_document<T: DeserializeOwned>(
+    document: &Value,
+    kind: &'static str,
+) -> Result<T, InterchangeError> {
+    let header = Header::deserialize(document).map_err(|_| InterchangeError::MissingHeader)?;
+    let (Some(format), Some(version)) = (header.format, header.version) else {
+        return Err(InterchangeError::MissingHeader);
+    };
+    if format != FORMAT_NAME {
+        return Err(InterchangeError::MissingHeader);
+    }
+    if version != u64::from(FORMAT_VERSION) {
+        return Err(InterchangeError::UnsupportedVersion {
+            version,
+            supported: FORMAT_VERSION,
+        });
+    }
+    let found = header.kind.unwrap_or_default();
+    if found != kind {
+        return Err(InterchangeError::KindMismatch {
+            expected: kind,
+            found,
+        });
+    }
+    T::deserialize(document).map_err(|err| InterchangeError::Malformed {
+        message: err.to_string(),
+    })
+}
+
+/// Builds a graph from atom entries `(id, element, chirality, metadata)` and bond entries.
+fn build_graph(
+    atoms: &[(usize, &String, &Option<String>, &MetadataEntry)],
+    bonds: &[GraphBondEntry],
+) -> Result<MolecularGraph, InterchangeError> {
+    let mut graph = MolecularGraph::new();
+    for (index, &(id, element, _, _)) in atoms.iter().enumerate() {
+        check_id(index, id)?;
+        graph.add_atom(parse::<Element>("element", element)?);
+    }
+    for &(id, _, chirality, metadata) in atoms {
+        let chirality = chirality.as_deref().map(parse_chirality).transpose()?;
+        graph
+            .set_chirality(id, chirality)
+            .map_err(InterchangeError::InvalidGraph)?;
+        if !metadata.is_empty() {
+            graph
+                .set_atom_metadata(id, metadata.clone().into())
+                .map_err(InterchangeError::InvalidGraph)?;
+        }
+    }
+    for bond in bonds {
+        let [a, b] = bond.atoms;
+        let bond_id = graph
+            .add_bond(a, b, parse("bond order", &bond.order)?)
+            .map_err(InterchangeError::InvalidGraph)?;
+        let stereo = bond.stereo.as_deref().map(parse_stereo).transpose()?;
+        graph
+            .set_bond_stereo(bond_id, stereo)
+            .map_err(InterchangeError::InvalidGraph)?;
+    }
+    Ok(graph)
+}
+
+fn check_id(index: usize, id: usize) -> Result<(), InterchangeError> {
+    if index == id {
+        Ok(())
+    } else {
+        Err(InterchangeError::AtomIdMismatch { index, id })
+    }
+}
+
+fn check_atoms(ids: &[usize], atom_count: usize) -> Result<(), InterchangeError> {
+    match ids.iter().find(|&&id| id >= atom_count) {
+        Some(&atom_id) => Err(InterchangeError::AtomOutOfRange {
+            atom_id,
+            atom_count,
+        }),
+        None => Ok(()),
+    }
+}
+
+fn parse<T: FromStr>(field: &'static str, text: &str) -> Result<T, InterchangeError> {
+    text.parse().map_err(|_| invalid(field, text))
+}
+
+fn invalid(field: &'static str, text: &str) -> InterchangeError {
+    InterchangeError::InvalidValue {
+        field,
+        value: text.to_string(),
+    }
+}
+
+/// Defines the conversion of an enumeration to its interchange name and back.
+macro_rules! interchange_names {
+    ($ty:ty, $field:literal, $name:ident, $parse:ident, { $($variant:path => $text:literal),+ $(,)? }) => {
+        fn $name(value: $ty) -> &'static str {
+            match value {
+                $($variant => $text),+
+            }
+        }
+
+        fn $parse(text: &str) -> Result<$ty, InterchangeError> {
+            match text {
+                $($text => Ok($variant),)+
+                _ => Err(invalid($field, text)),
+            }
+        }
+    };
+}
+
+interchange_names!(Chirality, "chirality", chirality_name, parse_chirality, {
+    Chirality::Clockwise => "clockwise",
+    Chirality::CounterClockwise => "counterclockwise",
+});
+
+interchange_names!(BondStereo, "bond stereo", stereo_name, parse_stereo, {
+    BondStereo::Cis => "cis",
+    BondStereo::Trans => "trans",
+});
+
+interchange_names!(TypeProvenance, "provenance", provenance_name, parse_provenance, {
+    TypeProvenance::Rule => "rule",
+    TypeProvenance::UffFallback => "uff_fallback",
+    TypeProvenance::ElementFallback => "element_fallback",
+    TypeProvenance::Override => "override",
+});
+
+interchange_names!(BondClass, "bond class", bond_class_name, parse_bond_class, {
+    BondClass::Covalent => "covalent",
+    BondClass::Disulfide => "disulfide",
+    BondClass::Coordination => "coordination",
+});
+
+interchange_names!(AngleClass, "angle class", angle_class_name, parse_angle_class, {
+    AngleClass::Linear => "linear",
+    AngleClass::Trigonal => "trigonal",
+    AngleClass::Tetrahedral => "tetrahedral",
+    AngleClass::Other => "other",
+});
+
+interchange_names!(TorsionClass, "torsion class", torsion_class_name, parse_torsion_class, {
+    TorsionClass::Sp3Sp3 => "sp3_sp3",
+    TorsionClass::Sp3Sp2 => "sp3_sp2",
+    TorsionClass::Double => "double",
+    TorsionClass::Resonant => "resonant",
+    TorsionClass::Sp2Sp2Single => "sp2_sp2_single",
+    TorsionClass::Exocyclic => "exocyclic",
+    TorsionClass::Linear => "linear",
+    TorsionClass::Unhybridized => "unhybridized",
+    TorsionClass::OxygenPair => "oxygen_pair",
+    TorsionClass::OxygenSp2 => "oxygen_sp2",
+    TorsionClass::ConjugatedSp3Sp2 => "conjugated_sp3_sp2",
+});
+
+interchange_names!(InversionClass, "inversion class", inversion_class_name, parse_inversion_class, {
+    InversionClass::Planar => "planar",
+    InversionClass::NonPlanar => "non_planar",
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::limits::Budget;
+    use crate::core::properties::{Element, GraphBondOrder};
+    use crate::io::smiles::parse_smiles;
+    use crate::perception::{PerceptionOptions, PerceptionPipeline};
+
+    fn formaldehyde() -> MolecularTopology {
+        let mut graph = MolecularGraph::new();
+        let c = graph.add_atom(Element::C);
+        let o = graph.add_atom(Element::O);
+        graph.add_bond(c, o, GraphBondOrder::Double).unwrap();
+        for _ in 0..2 {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+        }
+        crate::assign_topology(&graph).unwrap()
+    }
+
+    #[test]
+    fn positions_are_attached_and_length_checked() {
+        let topology = formaldehyde();
+        let positions = [
+            [0.0; 3],
+            [1.2, 0.0, 0.0],
+            [-0.5, 0.9, 0.0],
+            [-0.5, -0.9, 0.0],
+        ];
+        let document = to_json(&topology, Some(&positions)).unwrap();
+        assert_eq!(document["atoms"][1]["position"], json!([1.2, 0.0, 0.0]));
+        assert_eq!(
+            document["bonds"][0],
+            json!({"atoms": [0, 1], "order": "Double"})
+        );
+        assert_eq!(document["inversions"].as_array().unwrap().len(), 3);
+
+        assert!(matches!(
+            to_json(&topology, Some(&positions[..2])),
+            Err(ExportError::LengthMismatch {
+                expected: 4,
+                found: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn graph_documents_round_trip() {
+        let mut graph = parse_smiles("N[C@@H](C)C(=O)O.F/C=C/F.[CH3+].[CH3].[13CH4]").unwrap();
+        let site = graph.add_atom(Element::Dummy);
+        graph.add_bond(4, site, GraphBondOrder::Single).unwrap();
+        graph
+            .set_atom_metadata(
+                0,
+                AtomMetadata::named("N")
+                    .with_residue(ResidueId::new("ALA", 'A', 1))
+                    .with_tag("source", "test"),
+            )
+            .unwrap();
+        let positions = (0..graph.atoms.len())
+            .map(|i| [i as f64 * 0.1, 1.0 / 3.0, -2.5])
+            .collect();
+        graph.set_positions(positions).unwrap();
+
+        let document = graph_to_json(&graph);
+        assert_eq!(document["format"], FORMAT_NAME);
+        assert_eq!(document["version"], FORMAT_VERSION);
+        assert_eq!(document["atoms"][1]["chirality"], "clockwise");
+        assert_eq!(document["atoms"][0]["metadata"]["residue"]["chain"], "A");
+        assert_eq!(document["atoms"][10]["formal_charge"], 1);
+        assert_eq!(document["atoms"][11]["radical_electrons"], 1);
+        assert!(document["atoms"][0].get("radical_electrons").is_none());
+        assert_eq!(document["atoms"][12]["isotope"], 13);
+        assert!(document["atoms"][0].get("isotope").is_none());
+        assert_eq!(document["atoms"][site]["element"], "Du");
+
+        let text = document.to_string();
+        let read = graph_from_json(&serde_json::from_str(&text).unwrap()).unwrap();
+        assert_eq!(read.atoms[0].metadata, graph.atoms[0].metadata);
+        assert_eq!(read.positions, graph.positions);
+        assert_eq!(graph_to_json(&read), document);
+    }
+
+    #[test]
+    fn topology_documents_round_trip() {
+        let graph = parse_smiles("N[C@@H](C)C(=O)O.F/C=C/F.c1ccccc1C#N.CSSC").unwrap();
+        let mut topology = crate::Typer::new()
+            .with_torsion_suppression(true)
+            .with_lone_pair_sites(true)
+            .assign_topology(&graph)
+            .unwrap();
+        topology.bonds[0].image = [0, 1, -1];
+        topology.atoms[2].metadata = AtomMetadata::named("CB");
+        assert!(!topology.suppressed_torsions.is_empty());
+
+        let document = topology_to_json(&topology);
+        assert_eq!(document["bonds"][0]["image"], json!([0, 1, -1]));
+        assert!(document["bonds"][1].get("image").is_none());
+        assert!(document["bonds"][1].get("class").is_none());
+        assert!(
+            document["bonds"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|bond| bond["class"] == "disulfide")
+        );
+        assert_eq!(document["rings"][0]["aromatic"], true);
+        assert_eq!(
+            document["virtual_sites"].as_array().unwrap().len(),
+            topology.virtual_sites.len()
+        );
+
+        let read = topology_from_json(&document).unwrap();
+        assert_eq!(read, topology);
+        assert_eq!(topology_to_json(&read), document);
+    }
+
+    #[test]
+    fn perception_documents_round_trip() {
+        let graph = parse_smiles("c1ccccc1C(=O)[O-]").unwrap();
+        let molecule = crate::perception::perceive(
+            &graph,
+            &[],
+            &PerceptionPipeline::standard(),
+            &PerceptionOptions::default(),
+            &Budget::default(),
+            &mut |_| {},
+        )
+        .unwrap();
+
+        let document = perception_to_json(&molecule);
+        assert_eq!(document["kind"], "perception");
+        assert_eq!(document["resonance_systems"].as_array().unwrap().len(), 2);
+        assert_eq!(document["atoms"][0]["aromatic"], true);
+        assert_eq!(document["aromatic_bonds"].as_array().unwrap().len(), 6);
+
+        let read = perception_from_json(&document).unwrap();
+        assert_eq!(read.adjacency, molecule.adjacency);
+        assert_eq!(read.atoms[6].degree, molecule.atoms[6].degree);
+        assert_eq!(perception_to_json(&read), document);
+    }
+
+    #[test]
+    fn readers_reject_foreign_and_inconsistent_documents() {
+        let topology = formaldehyde();
+        let document = topology_to_json(&topology);
+
+        assert!(matches!(
+            topology_from_json(&to_json(&topology, None).unwrap()),
+            Err(InterchangeError::MissingHeader)
+        ));
+        assert!(matches!(
+            graph_from_json(&document),
+            Err(InterchangeError::KindMismatch { expected: "graph", ref found }) if found == "topology"
+        ));
+
+        let mut newer = document.clone();
+        newer["version"] = json!(FORMAT_VERSION + 1);
+        assert!(matches!(
+            topology_from_json(&newer),
+            Err(InterchangeError::UnsupportedVersion { version: 2, .. })
+        ));
+
+        let mut unknown = document.clone();
+        unknown["atoms"][0]["element"] = json!("Xx");
+        assert!(matches!(
+            topology_from_json(&unknown),
+            Err(InterchangeError::InvalidValue { field: "element", ref value }) if value == "Xx"
+        ));
+
+        let mut dangling = document.clone();
+        dangling["angles"][0]["atoms"] = json!([0, 1, 9]);
+        assert!(matches!(
+            topology_from_json(&dangling),
+            Err(InterchangeError::AtomOutOfRange {
+                atom_id: 9,
+                atom_count: 4
+            })
+        ));
+
+        let mut truncated = document;
+        truncated.as_object_mut().unwrap().remove("bonds");
+        assert!(matches!(
+            topology_from_json(&truncated),
+            Err(InterchangeError::Malformed { .. })
+        ));
+    }
+}
@@ -0,0 +1,641 @@
+//! LAMMPS `data` file export.
+//!
+//! [`LammpsData`] writes a topology in `atom_style full` layout with `Masses`, `Atoms`, `Bonds`,
+//! `Angles`, `Dihedrals`, and `Impropers` sections. Numeric types are derived from the DREIDING
+//! labels by [`LammpsTypes`]: atom types are numbered in sorted label order, and every bonded
+//! term type is keyed by the labels of its atoms in a canonical orientation, so the same label
+//! combination always receives the same number regardless of how a term was enumerated.
+//! Dihedral types are further split by the DREIDING torsion case and the number of torsions
+//! sharing the central bond, which together fix `V`, `n`, and `φ₀`: the inter-ring bond of
+//! biphenyl (`V = 5`) and its ring bonds (`V = 25`) both join `C_R` atoms, yet need separate
+//! coefficients.
+//!
+//! Inversions are written as impropers with the central atom first, matching LAMMPS'
+//! `improper_style umbrella`.
+
+use crate::compat::prelude::*;
+use crate::core::error::ExportError;
+use crate::core::topology::{MolecularTopology, TorsionClass};
+use alloc::collections::BTreeSet;
+use core::fmt;
+
+/// Padding in Å added around the atoms when no simulation box is given.
+const DEFAULT_BOX_PADDING: f64 = 5.0;
+
+/// Numeric LAMMPS types derived from DREIDING labels.
+///
+/// Each list is sorted, and the LAMMPS type ID of an entry is its position plus one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LammpsTypes {
+    /// Atom type labels.
+    pub atom_types: Vec<String>,
+    /// Bond types as label pairs in ascending order.
+    pub bond_types: Vec<[String; 2]>,
+    /// Angle types as `[end, center, end]` with the ends in ascending order.
+    pub angle_types: Vec<[String; 3]>,
+    /// Dihedral types by labels, torsion case, and barrier share.
+    pub dihedral_types: Vec<DihedralType>,
+    /// Improper types as the central label followed by the three outer labels in ascending order.
+    pub improper_types: Vec<[String; 4]>,
+}
+
+/// A LAMMPS dihedral type: the labels of a torsion and the DREIDING barrier it carries.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DihedralType {
+    /// Labels in the lexicographically smaller of the two orientations.
+    pub labels: [String; 4],
+    /// DREIDING torsion case of the central bond, which sets `V`, `n`, and `φ₀`.
+    pub class: TorsionClass,
+    /// Number of torsions about the central bond, among which `V` is divided.
+    pub shared_count: usize,
+}
+
+impl DihedralType {
+    /// Returns the type label, the atom labels followed by the torsion case and barrier share,
+    /// such as `C_R-C_R-C_R-C_R:d/4`.
+    pub fn name(&self) -> String {
+        format!(
+            "{}:{}/{}",
+            self.labels.join("-"),
+            self.class.case(),
+            self.shared_count
+        )
+    }
+}
+
+impl LammpsTypes {
+    /// Collects every distinct atom and term type present in a topology.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Typed topology to enumerate.
+    pub fn from_topology(topology: &MolecularTopology) -> Self {
+        let label = |id: usize| topology.atoms[id].atom_type.to_string();
+
+        let atom_types: BTreeSet<String> = topology
+            .atoms
+            .iter()
+            .map(|a| a.atom_type.to_string())
+            .collect();
+        let bond_types: BTreeSet<[String; 2]> = topology
+            .bonds
+            .iter()
+            .map(|b| bond_key([label(b.atom_ids.0), label(b.atom_ids.1)]))
+            .collect();
+        let angle_types: BTreeSet<[String; 3]> = topology
+            .angles
+            .iter()
+            .map(|a| {
+                let (i, j, k) = a.atom_ids;
+                angle_key([label(i), label(j), label(k)])
+            })
+            .collect();
+        let dihedral_types: BTreeSet<DihedralType> = topology
+            .torsions
+            .iter()
+            .map(|t| {
+                let (i, j, k, l) = t.atom_ids;
+                DihedralType {
+                    labels: dihedral_key([label(i), label(j), label(k), label(l)]),
+                    class: t.class,
+                    shared_count: t.shared_count,
+                }
+            })
+            .collect();
+        let improper_types: BTreeSet<[String; 4]> = topology
+            .inversions
+            .iter()
+            .map(|inv| {
+                let (c, a, p1, p2) = inv.atom_ids;
+                improper_key([label(c), label(a), label(p1), label(p2)])
+            })
+            .collect();
+
+        Self {
+            atom_types: atom_types.into_iter().collect(),
+            bond_types: bond_types.into_iter().collect(),
+            angle_types: angle_types.into_iter().collect(),
+            dihedral_types: dihedral_types.into_iter().collect(),
+            improper_types: improper_types.into_iter().collect(),
+        }
+    }
+
+    /// Returns the 1-based LAMMPS atom type of a label.
+    pub fn atom_type_id(&self, label: &str) -> Option<usize> {
+        position(&self.atom_types, label)
+    }
+
+    /// Returns the 1-based LAMMPS bond type of a label pair, in either order.
+    pub fn bond_type_id(&self, labels: [&str; 2]) -> Option<usize> {
+        position(&self.bond_types, &bond_key(labels.map(str::to_string)))
+    }
+
+    /// Returns the 1-based LAMMPS angle type of an `[end, center, end]` label triple.
+    pub fn angle_type_id(&self, labels: [&str; 3]) -> Option<usize> {
+        position(&self.angle_types, &angle_key(labels.map(str::to_string)))
+    }
+
+    /// Returns the 1-based LAMMPS dihedral type of a label quadruple, in either direction, about
+    /// a central bond of the given torsion case shared by `shared_count` torsions.
+    pub fn dihedral_type_id(
+        &self,
+        labels: [&str; 4],
+        class: TorsionClass,
+        shared_count: usize,
+    ) -> Option<usize> {
+        let key = DihedralType {
+            labels: dihedral_key(labels.map(str::to_string)),
+            class,
+            shared_count,
+        };
+        position(&self.dihedral_types, &key)
+    }
+
+    /// Returns the 1-based LAMMPS improper type of a `[center, outer...]` label quadruple.
+    pub fn improper_type_id(&self, labels: [&str; 4]) -> Option<usize> {
+        position(
+            &self.improper_types,
+            &improper_key(labels.map(str::to_string)),
+        )
+    }
+}
+
+/// A topology prepared for writing as a LAMMPS `data` file.
+///
+/// The file is produced through the [`Display`](fmt::Display) implementation, so it can be
+/// written with `to_string()` or `write!` into any writer.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::export::lammps::LammpsData;
+/// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+///
+/// let mut graph = MolecularGraph::new();
+/// let o = graph.add_atom(Element::O);
+/// let h1 = graph.add_atom(Element::H);
+/// let h2 = graph.add_atom(Element::H);
+/// graph.add_bond(o, h1, GraphBondOrder::Single).unwrap();
+/// graph.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+/// let topology = assign_topology(&graph).unwrap();
+///
+/// let positions = [[0.0, 0.0, 0.0], [0.957, 0.0, 0.0], [-0.240, 0.927, 0.0]];
+/// let data = LammpsData::new(&topology)
+///     .with_positions(&positions)
+///     .unwrap()
+///     .to_string();
+///
+/// assert!(data.contains("3 atoms"));
+/// assert!(data.contains("2 atom types"));
+/// assert!(data.contains("1 angle types"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LammpsData<'a> {
+    topology: &'a MolecularTopology,
+    types: LammpsTypes,
+    title: String,
+    positions: Option<&'a [[f64; 3]]>,
+    charges: Option<&'a [f64]>,
+    bounds: Option<[[f64; 2]; 3]>,
+    type_labels: bool,
+}
+
+impl<'a> LammpsData<'a> {
+    /// Prepares a topology for export, placing every atom at the origin with zero charge.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Typed topology to export.
+    pub fn new(topology: &'a MolecularTopology) -> Self {
+        Self {
+            topology,
+            types: LammpsTypes::from_topology(topology),
+            title: String::new(),
+            positions: None,
+            charges: None,
+            bounds: None,
+            type_labels: false,
+        }
+    }
+
+    /// Sets the comment written on the first line of the file.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Supplies Cartesian coordinates in Å, one per atom.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExportError::LengthMismatch`] unless there is exactly one position per atom.
+    pub fn with_positions(mut self, positions: &'a [[f64; 3]]) -> Result<Self, ExportError> {
+        self.check_len("positions", positions.len())?;
+        self.positions = Some(positions);
+        Ok(self)
+    }
+
+    /// Supplies partial charges in elementary charges, one per atom.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExportError::LengthMismatch`] unless there is exactly one charge per atom.
+    pub fn with_charges(mut self, charges: &'a [f64]) -> Result<Self, ExportError> {
+        self.check_len("charges", charges.len())?;
+        self.charges = Some(charges);
+        Ok(self)
+    }
+
+    /// Sets the simulation box as `[[xlo, xhi], [ylo, yhi], [zlo, zhi]]` in Å.
+    ///
+    /// Without an explicit box, the bounding box of the atoms padded by 5 Å is used.
+    pub fn with_box(mut self, bounds: [[f64; 2]; 3]) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Enables the `Atom Type Labels` (and bonded term label) sections.
+    ///
+    /// Type label sections let LAMMPS (version 15Jun2023 or later) refer to types by their
+    /// DREIDING names in `pair_coeff` and similar commands. Older versions reject them.
+    pub fn with_type_labels(mut self, enabled: bool) -> Self {
+        self.type_labels = enabled;
+        self
+    }
+
+    /// Returns the numeric types used in the file.
+    pub fn types(&self) -> &LammpsTypes {
+        &self.types
+    }
+
+    /// Ensures per-atom data has one entry per atom.
+    fn check_len(&self, field: &'static str, found: usize) -> Result<(), ExportError> {
+        let expected = self.topology.atoms.len();
+        if found != expected {
+            return Err(ExportError::LengthMismatch {
+                field,
+                expected,
+                found,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the position of an atom, defaulting to the origin.
+    fn position(&self, atom_id: usize) -> [f64; 3] {
+        self.positions.map_or([0.0; 3], |p| p[atom_id])
+    }
+
+    /// Returns the explicit box or the padded bounding box of the atoms.
+    fn bounds(&self) -> [[f64; 2]; 3] {
+        if let Some(bounds) = self.bounds {
+            return bounds;
+        }
+        let mut bounds = [[0.0, 0.0]; 3];
+        for (axis, range) in bounds.iter_mut().enumerate() {
+            let values = (0..self.topology.atoms.len()).map(|id| self.position(id)[axis]);
+            let lo = values.clone().fold(f64::INFINITY, f64::min);
+            let hi = values.fold(f64::NEG_INFINITY, f64::max);
+            let (lo, hi) = if lo.is_finite() { (lo, hi) } else { (0.0, 0.0) };
+            *range = [lo - DEFAULT_BOX_PADDING, hi + DEFAULT_BOX_PADDING];
+        }
+        bounds
+    }
+}
+
+impl fmt::Display for LammpsData<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let topology = self.topology;
+        let types = &self.types;
+        let label = |id: usize| topology.atoms[id].atom_type.as_str();
+
+        writeln!(
+            f,
+            "LAMMPS data file generated by dreid-typer {}",
+            self.title
+        )?;
+        writeln!(f)?;
+        writeln!(f, "{} atoms", topology.atoms.len())?;
+        writeln!(f, "{} bonds", topology.bonds.len())?;
+        writeln!(f, "{} angles", topology.angles.len())?;
+        writeln!(f, "{} dihedrals", topology.torsions.len())?;
+        writeln!(f, "{} impropers", topology.inversions.len())?;
+        writeln!(f)?;
+        writeln!(f, "{} atom types", types.atom_types.len())?;
+        writeln!(f, "{} bond types", types.bond_types.len())?;
+        writeln!(f, "{} angle types", types.angle_types.len())?;
+        writeln!(f, "{} dihedral types", types.dihedral_types.len())?;
+        writeln!(f, "{} improper types", types.improper_types.len())?;
+        writeln!(f)?;
+        for (bounds, axis) in self.bounds().iter().zip(["x", "y", "z"]) {
+            writeln!(f, "{:.6} {:.6} {axis}lo {axis}hi", bounds[0], bounds[1])?;
+        }
+
+        if self.type_labels {
+            write_labels(f, "Atom Type Labels", &types.atom_types, |t| t.clone())?;
+            write_labels(f, "Bond Type Labels", &types.bond_types, |t| t.join("-"))?;
+            write_labels(f, "Angle Type Labels", &types.angle_types, |t| t.join("-"))?;
+            write_labels(
+                f,
+                "Dihedral Type Labels",
+                &types.dihedral_types,
+                DihedralType::name,
+            )?;
+            write_labels(f, "Improper Type Labels", &types.improper_types, |t| {
+                t.join("-")
+            })?;
+        }
+
+        if !types.atom_types.is_empty() {
+            write!(f, "\nMasses\n\n")?;
+            for (index, atom_type) in types.atom_types.iter().enumerate() {
+                let element = topology
+                    .atoms
+                    .iter()
+                    .find(|a| &a.atom_type == atom_type)
+                    .map(|a| a.element)
+                    .expect("every atom type comes from an atom");
+                writeln!(
+                    f,
+                    "{} {:.4} # {atom_type}",
+                    index + 1,
+                    element.atomic_mass()
+                )?;
+            }
+        }
+
+        if !topology.atoms.is_empty() {
+            let molecule_of = topology.molecule_indices();
+            write!(f, "\nAtoms # full\n\n")?;
+            for atom in &topology.atoms {
+                let [x, y, z] = self.position(atom.id);
+                let charge = self.charges.map_or(0.0, |c| c[atom.id]);
+                let atom_type = types
+                    .atom_type_id(atom.atom_type.as_str())
+                    .expect("atom type is listed");
+                writeln!(
+                    f,
+                    "{} {} {} {charge:.6} {x:.6} {y:.6} {z:.6}",
+                    atom.id + 1,
+                    molecule_of[atom.id] + 1,
+                    atom_type
+                )?;
+            }
+        }
+
+        if !topology.bonds.is_empty() {
+            write!(f, "\nBonds\n\n")?;
+            for (index, bond) in topology.bonds.iter().enumerate() {
+                let (a, b) = bond.atom_ids;
+                let id = types
+                    .bond_type_id([label(a), label(b)])
+                    .expect("bond type is listed");
+                writeln!(f, "{} {id} {} {}", index + 1, a + 1, b + 1)?;
+            }
+        }
+
+        if !topology.angles.is_empty() {
+            write!(f, "\nAngles\n\n")?;
+            for (index, angle) in topology.angles.iter().enumerate() {
+                let (i, j, k) = angle.atom_ids;
+                let id = types
+                    .angle_type_id([label(i), label(j), label(k)])
+                    .expect("angle type is listed");
+                writeln!(f, "{} {id} {} {} {}", index + 1, i + 1, j + 1, k + 1)?;
+            }
+        }
+
+        if !topology.torsions.is_empty() {
+            write!(f, "\nDihedrals\n\n")?;
+            for (index, torsion) in topology.torsions.iter().enumerate() {
+                let (i, j, k, l) = torsion.atom_ids;
+                let id = types
+                    .dihedral_type_id(
+                        [label(i), label(j), label(k), label(l)],
+                        torsion.class,
+                        torsion.shared_count,
+                    )
+                    .expect("dihedral type is listed");
+                writeln!(
+                    f,
+                    "{} {id} {} {} {} {}",
+                    index + 1,
+                    i + 1,
+                    j + 1,
+                    k + 1,
+                    l + 1
+                )?;
+            }
+        }
+
+        if !topology.inversions.is_empty() {
+            write!(f, "\nImpropers\n\n")?;
+            for (index, inversion) in topology.inversions.iter().enumerate() {
+                let (c, a, p1, p2) = inversion.atom_ids;
+                let id = types
+                    .improper_type_id([label(c), label(a), label(p1), label(p2)])
+                    .expect("improper type is listed");
+                writeln!(
+                    f,
+                    "{} {id} {} {} {} {}",
+                    index + 1,
+                    c + 1,
+                    a + 1,
+                    p1 + 1,
+                    p2 + 1
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a type label section if it has entries.
+fn write_labels<T>(
+    f: &mut fmt::Formatter<'_>,
+    header: &str,
+    entries: &[T],
+    name: impl Fn(&T) -> String,
+) -> fmt::Result {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    write!(f, "\n{header}\n\n")?;
+    for (index, entry) in entries.iter().enumerate() {
+        writeln!(f, "{} {}", index + 1, name(entry))?;
+    }
+    Ok(())
+}
+
+/// Returns the 1-based position of `key` in a sorted list.
+fn position<T: Ord + ?Sized, U: core::borrow::Borrow<T> + Ord>(
+    list: &[U],
+    key: &T,
+) -> Option<usize> {
+    list.binary_search_by(|entry| entry.borrow().cmp(key))
+        .ok()
+        .map(|index| index + 1)
+}
+
+/// Orders a bond's labels ascending.
+fn bond_key(mut labels: [String; 2]) -> [String; 2] {
+    labels.sort();
+    labels
+}
+
+/// Orders an angle's end labels ascending around the fixed center.
+fn angle_key(mut labels: [String; 3]) -> [String; 3] {
+    if labels[0] > labels[2] {
+        labels.swap(0, 2);
+    }
+    labels
+}
+
+/// Picks the lexicographically smaller direction of a dihedral.
+fn dihedral_key(labels: [String; 4]) -> [String; 4] {
+    let mut reversed = labels.clone();
+    reversed.reverse();
+    labels.min(reversed)
+}
+
+/// Orders an improper's outer labels ascending behind the fixed center.
+fn improper_key(mut labels: [String; 4]) -> [String; 4] {
+    labels[1..].sort();
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::properties::{Element, GraphBondOrder};
+    use crate::pipeline::Typer;
+
+    fn acetic_acid() -> MolecularTopology {
+        let mut graph = MolecularGraph::new();
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        let o1 = graph.add_atom(Element::O);
+        let o2 = graph.add_atom(Element::O);
+        graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+        graph.add_bond(c2, o1, GraphBondOrder::Double).unwrap();
+        graph.add_bond(c2, o2, GraphBondOrder::Single).unwrap();
+        let ho = graph.add_atom(Element::H);
+        graph.add_bond(o2, ho, GraphBondOrder::Single).unwrap();
+        for _ in 0..3 {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(c1, h, GraphBondOrder::Single).unwrap();
+        }
+        Typer::new().assign_topology(&graph).unwrap()
+    }
+
+    fn section<'t>(text: &'t str, header: &str) -> Vec<&'t str> {
+        text.split(&format!("\n{header}\n\n"))
+            .nth(1)
+            .map(|rest| rest.split("\n\n").next().unwrap_or("").lines().collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn types_are_keyed_by_canonical_label_orientation() {
+        let types = LammpsTypes::from_topology(&acetic_acid());
+        assert_eq!(
+            types.bond_type_id(["H_", "C_3"]),
+            types.bond_type_id(["C_3", "H_"])
+        );
+        let torsion = |labels| types.dihedral_type_id(labels, TorsionClass::ConjugatedSp3Sp2, 6);
+        assert!(torsion(["H_", "C_3", "C_2", "O_2"]).is_some());
+        assert_eq!(
+            torsion(["H_", "C_3", "C_2", "O_2"]),
+            torsion(["O_2", "C_2", "C_3", "H_"])
+        );
+        assert_eq!(
+            types.dihedral_type_id(["H_", "C_3", "C_2", "O_2"], TorsionClass::Sp3Sp2, 6),
+            None
+        );
+        assert!(types.atom_types.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(types.atom_type_id("missing"), None);
+    }
+
+    #[test]
+    fn dihedral_types_with_different_barriers_are_distinct() {
+        let graph = crate::io::smiles::parse_smiles("c1ccccc1-c1ccccc1").unwrap();
+        let topology = Typer::new().assign_topology(&graph).unwrap();
+        let types = LammpsTypes::from_topology(&topology);
+
+        let carbons: Vec<&DihedralType> = types
+            .dihedral_types
+            .iter()
+            .filter(|t| t.labels.iter().all(|label| label == "C_R"))
+            .collect();
+        assert!(carbons.iter().any(|t| t.class == TorsionClass::Resonant));
+        assert!(carbons.iter().any(|t| t.class == TorsionClass::Exocyclic));
+
+        let text = LammpsData::new(&topology)
+            .with_type_labels(true)
+            .to_string();
+        let names = section(&text, "Dihedral Type Labels");
+        assert_eq!(names.len(), types.dihedral_types.len());
+        assert!(
+            names
+                .iter()
+                .any(|name| name.ends_with(" C_R-C_R-C_R-C_R:f/4"))
+        );
+    }
+
+    #[test]
+    fn data_file_lists_every_term_with_consistent_types() {
+        let topology = acetic_acid();
+        let data = LammpsData::new(&topology).with_type_labels(true);
+        let text = data.to_string();
+        let types = data.types();
+
+        assert!(text.contains(&format!("{} atoms\n", topology.atoms.len())));
+        assert!(text.contains(&format!("{} dihedrals\n", topology.torsions.len())));
+        assert_eq!(section(&text, "Atoms # full").len(), topology.atoms.len());
+        assert_eq!(section(&text, "Bonds").len(), topology.bonds.len());
+        assert_eq!(section(&text, "Impropers").len(), topology.inversions.len());
+        assert_eq!(
+            section(&text, "Atom Type Labels"),
+            types
+                .atom_types
+                .iter()
+                .enumerate()
+                .map(|(i, t)| format!("{} {t}", i + 1))
+                .collect::<Vec<_>>()
+        );
+
+        let masses = section(&text, "Masses");
+        let carbon_row = types.atom_type_id("C_3").unwrap() - 1;
+        assert_eq!(
+            masses[carbon_row],
+            format!("{} 12.0110 # C_3", carbon_row + 1)
+        );
+    }
+
+    #[test]
+    fn per_atom_data_must_match_atom_count() {
+        let topology = acetic_acid();
+        let err = LammpsData::new(&topology)
+            .with_positions(&[[0.0; 3]])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ExportError::LengthMismatch {
+                field: "positions",
+                expected: 8,
+                found: 1,
+            }
+        );
+
+        let charges = vec![0.5; 8];
+        let text = LammpsData::new(&topology)
+            .with_charges(&charges)
+            .unwrap()
+            .with_box([[0.0, 10.0]; 3])
+            .to_string();
+        assert!(text.contains("0.000000 10.000000 zlo zhi"));
+        assert!(section(&text, "Atoms # full")[0].contains(" 0.500000 "));
+    }
+}
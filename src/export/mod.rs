@@ -0,0 +1,15 @@
+//! Writers that serialize typed topologies into simulation-engine input formats and graph
+//! visualization formats.
+//!
+//! Each engine or format lives in its own submodule. Writers never alter the topology; per-atom
+//! data the topology does not carry (coordinates, charges) is supplied by the caller and validated
+//! through [`ExportError`](crate::ExportError).
+
+pub mod graphml;
+pub mod graphviz;
+pub mod gromacs;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod lammps;
+pub mod openmm;
+pub mod psf;
@@ -0,0 +1,420 @@
+//! OpenMM force-field XML export.
+//!
+//! [`OpenMmForceField`] writes a `<ForceField>` document that Python OpenMM scripts load with
+//! `app.ForceField("molecule.xml")`. DREIDING parameters depend on more than the labels of a
+//! term's atoms (bond orders, and barriers shared among the torsions of a central bond), so every
+//! atom receives its own OpenMM atom type named after the residue and the atom, with the DREIDING
+//! label as its class. Each term then matches exactly the atoms it was derived for.
+//!
+//! | Term       | DREIDING form   | OpenMM element                                   |
+//! |------------|-----------------|--------------------------------------------------|
+//! | bond       | harmonic        | `HarmonicBondForce`                              |
+//! | angle      | cosine-harmonic | `HarmonicAngleForce`                             |
+//! | torsion    | cosine          | `PeriodicTorsionForce` proper                    |
+//! | inversion  | umbrella        | `PeriodicTorsionForce` improper, periodicity 1   |
+//! | van der Waals | Lennard-Jones | `NonbondedForce` with `σ = R₀ / 2^(1/6)`, `ε = D₀` |
+//!
+//! Angles are written with the force constant `K` as harmonic bends, and planar inversions as
+//! `K (1 + cos(φ − 180°))` impropers with the central atom first; both agree with the DREIDING
+//! forms near their minima. OpenMM applies a single improper to each center, so the inversions
+//! of a center are written as one improper carrying their summed force constant. Van der Waals
+//! terms are always written in Lennard-Jones form, and the explicit hydrogen-bond term is not
+//! written. Values are converted to OpenMM units (kJ/mol, nm, radians).
+
+use super::gromacs::atom_names;
+use crate::compat::collections::HashMap;
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::float::Float;
+use crate::compat::prelude::*;
+use crate::core::error::ExportError;
+use crate::params::{AngleUnit, EnergyUnit, LengthUnit, ParameterizedTopology};
+use core::fmt;
+
+/// Default residue name of the template.
+const DEFAULT_NAME: &str = "MOL";
+
+/// Scale of 1-4 non-bonded interactions.
+///
+/// DREIDING excludes only 1-2 and 1-3 pairs, and OpenMM excludes those from the template bonds,
+/// so 1-4 pairs are kept at full strength.
+const SCALE_14: f64 = 1.0;
+
+/// A parameterized topology prepared for writing as an OpenMM force-field XML file.
+///
+/// The whole topology is written as one residue template, so the OpenMM topology must hold the
+/// molecule as a single residue whose atoms carry the same names (element symbol and per-element
+/// counter, or the name from the atom metadata). The file is produced through the
+/// [`Display`](fmt::Display) implementation; use [`ParameterizedTopology::to_openmm_xml`] for
+/// the defaults.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::export::openmm::OpenMmForceField;
+/// use dreid_typer::params::assign_parameters;
+/// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+///
+/// let mut graph = MolecularGraph::new();
+/// let o = graph.add_atom(Element::O);
+/// for _ in 0..2 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+/// }
+/// let parameters = assign_parameters(&assign_topology(&graph).unwrap()).unwrap();
+///
+/// let xml = OpenMmForceField::new(&parameters)
+///     .with_name("HOH")
+///     .with_charges(&[-0.82, 0.41, 0.41])
+///     .unwrap()
+///     .to_string();
+///
+/// assert!(xml.contains(r#"<Residue name="HOH">"#));
+/// assert!(xml.contains(r#"<Type name="HOH-O1" class="O_3" element="O""#));
+/// assert!(xml.contains("<HarmonicAngleForce>"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenMmForceField<'a> {
+    parameters: &'a ParameterizedTopology,
+    name: String,
+    charges: Option<&'a [f64]>,
+}
+
+impl<'a> OpenMmForceField<'a> {
+    /// Prepares a parameterized topology for export with zero charges.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - Topology with DREIDING parameters for every term.
+    pub fn new(parameters: &'a ParameterizedTopology) -> Self {
+        Self {
+            parameters,
+            name: DEFAULT_NAME.to_string(),
+            charges: None,
+        }
+    }
+
+    /// Sets the residue name of the template, which also prefixes every atom type name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Supplies partial charges in elementary charges, one per atom.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExportError::LengthMismatch`] unless there is exactly one charge per atom.
+    pub fn with_charges(mut self, charges: &'a [f64]) -> Result<Self, ExportError> {
+        let expected = self.parameters.topology.atoms.len();
+        if charges.len() != expected {
+            return Err(ExportError::LengthMismatch {
+                field: "charges",
+                expected,
+                found: charges.len(),
+            });
+        }
+        self.charges = Some(charges);
+        Ok(self)
+    }
+}
+
+impl fmt::Display for OpenMmForceField<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parameters = self.parameters;
+        let topology = &parameters.topology;
+        let energy = EnergyUnit::KcalPerMol.factor_to(EnergyUnit::KjPerMol);
+        let length = LengthUnit::Angstrom.factor_to(LengthUnit::Nanometer);
+        let radians = AngleUnit::Degrees.factor_to(AngleUnit::Radians);
+
+        let names: Vec<String> = atom_names(topology).iter().map(|n| escape(n)).collect();
+        let residue = escape(&self.name);
+        let types: Vec<String> = names.iter().map(|n| format!("{residue}-{n}")).collect();
+        let type_attrs = |ids: &[usize]| {
+            ids.iter()
+                .enumerate()
+                .map(|(i, &id)| format!(r#"type{}="{}""#, i + 1, types[id]))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        writeln!(f, "<!-- generated by dreid-typer -->")?;
+        writeln!(f, "<ForceField>")?;
+
+        writeln!(f, " <AtomTypes>")?;
+        for atom in &topology.atoms {
+            writeln!(
+                f,
+                r#"  <Type name="{}" class="{}" element="{}" mass="{:.4}"/>"#,
+                types[atom.id],
+                escape(atom.atom_type.as_str()),
+                atom.element,
+                atom.element.atomic_mass()
+            )?;
+        }
+        writeln!(f, " </AtomTypes>")?;
+
+        writeln!(f, " <Residues>")?;
+        writeln!(f, r#"  <Residue name="{residue}">"#)?;
+        for atom in &topology.atoms {
+            let charge = self.charges.map_or(0.0, |c| c[atom.id]);
+            writeln!(
+                f,
+                r#"   <Atom name="{}" type="{}" charge="{charge:.6}"/>"#,
+                names[atom.id], types[atom.id]
+            )?;
+        }
+        for bond in &topology.bonds {
+            let (a, b) = bond.atom_ids;
+            writeln!(
+                f,
+                r#"   <Bond atomName1="{}" atomName2="{}"/>"#,
+                names[a], names[b]
+            )?;
+        }
+        writeln!(f, "  </Residue>")?;
+        writeln!(f, " </Residues>")?;
+
+        writeln!(f, " <HarmonicBondForce>")?;
+        for (bond, params) in topology.bonds.iter().zip(&parameters.bonds) {
+            let (a, b) = bond.atom_ids;
+            writeln!(
+                f,
+                r#"  <Bond {} length="{:.6}" k="{:.6}"/>"#,
+                type_attrs(&[a, b]),
+                params.r0 * length,
+                params.k * energy / (length * length)
+            )?;
+        }
+        writeln!(f, " </HarmonicBondForce>")?;
+
+        writeln!(f, " <HarmonicAngleForce>")?;
+        for (angle, params) in topology.angles.iter().zip(&parameters.angles) {
+            let (i, j, k) = angle.atom_ids;
+            writeln!(
+                f,
+                r#"  <Angle {} angle="{:.6}" k="{:.6}"/>"#,
+                type_attrs(&[i, j, k]),
+                params.theta0 * radians,
+                params.k * energy
+            )?;
+        }
+        writeln!(f, " </HarmonicAngleForce>")?;
+
+        writeln!(f, " <PeriodicTorsionForce>")?;
+        for (torsion, params) in topology.torsions.iter().zip(&parameters.torsions) {
+            let (i, j, k, l) = torsion.atom_ids;
+            // ½V [1 − cos(n(φ − φ₀))] = ½V [1 + cos(nφ − (nφ₀ + 180°))].
+            let phase = (f64::from(params.n) * params.phi0 + 180.0).rem_euclid(360.0);
+            writeln!(
+                f,
+                r#"  <Proper {} periodicity1="{}" phase1="{:.6}" k1="{:.6}"/>"#,
+                type_attrs(&[i, j, k, l]),
+                params.n,
+                phase * radians,
+                params.v / 2.0 * energy
+            )?;
+        }
+        // First inversion of each center, with the force constants of all its inversions.
+        let mut impropers: Vec<(usize, f64)> = Vec::new();
+        let mut improper_of_center = HashMap::new();
+        for (index, (inversion, params)) in topology
+            .inversions
+            .iter()
+            .zip(&parameters.inversions)
+            .enumerate()
+        {
+            let next = impropers.len();
+            let slot = *improper_of_center
+                .entry(inversion.atom_ids.0)
+                .or_insert(next);
+            if slot == next {
+                impropers.push((index, 0.0));
+            }
+            impropers[slot].1 += params.k;
+        }
+        for (index, k) in impropers {
+            let (center, axis, p1, p2) = topology.inversions[index].atom_ids;
+            writeln!(
+                f,
+                r#"  <Improper {} periodicity1="1" phase1="{:.6}" k1="{:.6}"/>"#,
+                type_attrs(&[center, axis, p1, p2]),
+                (parameters.inversions[index].psi0 + 180.0) * radians,
+                k * energy
+            )?;
+        }
+        writeln!(f, " </PeriodicTorsionForce>")?;
+
+        writeln!(
+            f,
+            r#" <NonbondedForce coulomb14scale="{SCALE_14}" lj14scale="{SCALE_14}">"#
+        )?;
+        writeln!(f, r#"  <UseAttributeFromResidue name="charge"/>"#)?;
+        for (atom, params) in topology.atoms.iter().zip(&parameters.atoms) {
+            writeln!(
+                f,
+                r#"  <Atom type="{}" sigma="{:.6}" epsilon="{:.6}"/>"#,
+                types[atom.id],
+                params.vdw.r0 * length / 2f64.powf(1.0 / 6.0),
+                params.vdw.d0 * energy
+            )?;
+        }
+        writeln!(f, " </NonbondedForce>")?;
+
+        writeln!(f, "</ForceField>")
+    }
+}
+
+impl ParameterizedTopology {
+    /// Renders the parameterized topology as an OpenMM force-field XML file.
+    ///
+    /// The residue is named `MOL` and every charge is zero. Use [`OpenMmForceField`] to
+    /// customize either.
+    ///
+    /// # Returns
+    ///
+    /// The complete XML document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::params::assign_parameters;
+    /// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+    ///
+    /// let mut graph = MolecularGraph::new();
+    /// let h = graph.add_atom(Element::H);
+    /// let f = graph.add_atom(Element::F);
+    /// graph.add_bond(h, f, GraphBondOrder::Single).unwrap();
+    ///
+    /// let parameters = assign_parameters(&assign_topology(&graph).unwrap()).unwrap();
+    /// let xml = parameters.to_openmm_xml();
+    /// assert!(xml.contains(r#"<Bond atomName1="H1" atomName2="F1"/>"#));
+    /// ```
+    pub fn to_openmm_xml(&self) -> String {
+        OpenMmForceField::new(self).to_string()
+    }
+}
+
+/// Escapes the characters XML reserves inside attribute values and text.
+pub(super) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::properties::{Element, GraphBondOrder};
+    use crate::params::assign_parameters;
+    use crate::pipeline::Typer;
+
+    fn ethylene() -> ParameterizedTopology {
+        let mut graph = MolecularGraph::new();
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        graph.add_bond(c1, c2, GraphBondOrder::Double).unwrap();
+        for carbon in [c1, c2] {
+            for _ in 0..2 {
+                let h = graph.add_atom(Element::H);
+                graph.add_bond(carbon, h, GraphBondOrder::Single).unwrap();
+            }
+        }
+        assign_parameters(&Typer::new().assign_topology(&graph).unwrap()).unwrap()
+    }
+
+    /// Returns the value of an attribute on the first line containing `tag`.
+    fn attribute(xml: &str, tag: &str, name: &str) -> f64 {
+        let line = xml.lines().find(|line| line.contains(tag)).unwrap();
+        let start = line.find(&format!(r#" {name}=""#)).unwrap() + name.len() + 3;
+        line[start..].split('"').next().unwrap().parse().unwrap()
+    }
+
+    #[test]
+    fn xml_lists_every_atom_and_term_in_openmm_units() {
+        let parameters = ethylene();
+        let xml = parameters.to_openmm_xml();
+        let count = |tag: &str| xml.matches(tag).count();
+
+        assert_eq!(count("<Type "), 6);
+        assert_eq!(count("<Atom name="), 6);
+        assert_eq!(count("<Bond atomName1"), 5);
+        assert_eq!(count("<Bond type1"), 5);
+        assert_eq!(count("<Angle "), parameters.topology.angles.len());
+        assert_eq!(count("<Proper "), parameters.topology.torsions.len());
+        assert_eq!(count("<Improper "), 2, "one improper per planar carbon");
+        assert_eq!(count("<Atom type="), 6);
+        assert!(xml.contains(r#"<Type name="MOL-C1" class="C_2" element="C" mass="12.0110"/>"#));
+
+        let cc = r#"<Bond type1="MOL-C1" type2="MOL-C2""#;
+        let cc_index = parameters
+            .topology
+            .bonds
+            .iter()
+            .position(|bond| bond.atom_ids == (0, 1))
+            .unwrap();
+        let r0 = parameters.bonds[cc_index].r0;
+        assert!((attribute(&xml, cc, "length") - r0 / 10.0).abs() < 1e-6);
+        assert!((attribute(&xml, cc, "k") - 1400.0 * 4.184 * 100.0).abs() < 1e-3);
+
+        // Double-bond torsion: V = 45 shared by four torsions, n = 2, φ₀ = 180°.
+        let proper = "<Proper ";
+        assert_eq!(attribute(&xml, proper, "periodicity1"), 2.0);
+        assert!((attribute(&xml, proper, "phase1") - std::f64::consts::PI).abs() < 1e-6);
+        assert!((attribute(&xml, proper, "k1") - 45.0 / 4.0 / 2.0 * 4.184).abs() < 1e-6);
+
+        // The three inversions of a carbon share K = 40 kcal/mol; OpenMM gets it in full.
+        let improper = "<Improper ";
+        let center_k: f64 = parameters
+            .topology
+            .inversions
+            .iter()
+            .zip(&parameters.inversions)
+            .filter(|(inversion, _)| inversion.atom_ids.0 == 0)
+            .map(|(_, params)| params.k)
+            .sum();
+        assert!((center_k - 40.0).abs() < 1e-9);
+        assert!((attribute(&xml, improper, "k1") - 40.0 * 4.184).abs() < 1e-6);
+
+        let nonbonded = "<NonbondedForce ";
+        assert_eq!(attribute(&xml, nonbonded, "coulomb14scale"), 1.0);
+        assert_eq!(attribute(&xml, nonbonded, "lj14scale"), 1.0);
+
+        let sigma = attribute(&xml, r#"<Atom type="MOL-C1""#, "sigma");
+        let r0 = parameters.atoms[0].vdw.r0;
+        assert!((sigma * 2f64.powf(1.0 / 6.0) * 10.0 - r0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn names_are_escaped_and_charges_must_match_atom_count() {
+        let parameters = ethylene();
+        assert_eq!(
+            OpenMmForceField::new(&parameters)
+                .with_charges(&[0.0; 2])
+                .unwrap_err(),
+            ExportError::LengthMismatch {
+                field: "charges",
+                expected: 6,
+                found: 2,
+            }
+        );
+
+        let charges = [-0.2, -0.2, 0.1, 0.1, 0.1, 0.1];
+        let xml = OpenMmForceField::new(&parameters)
+            .with_name("A&B")
+            .with_charges(&charges)
+            .unwrap()
+            .to_string();
+        assert!(xml.contains(r#"<Residue name="A&amp;B">"#));
+        assert!(xml.contains(r#"<Atom name="C1" type="A&amp;B-C1" charge="-0.200000"/>"#));
+    }
+}
@@ -0,0 +1,425 @@
+//! X-PLOR/CHARMM protein structure file (`.psf`) export.
+//!
+//! [`PsfFile`] writes the X-PLOR flavor of the format, which stores atom types as strings, so
+//! the DREIDING labels are used directly. The file holds the `!NATOM`, `!NBOND`, `!NTHETA`,
+//! `!NPHI`, `!NIMPHI`, `!NDON`, and `!NACC` sections that VMD and MDAnalysis read:
+//!
+//! | Section   | Source                                                   |
+//! |-----------|----------------------------------------------------------|
+//! | `!NATOM`  | atoms, with residues from the atom metadata if present   |
+//! | `!NBOND`  | bonds                                                    |
+//! | `!NTHETA` | angles                                                   |
+//! | `!NPHI`   | torsions                                                 |
+//! | `!NIMPHI` | inversions, central atom first                           |
+//! | `!NDON`   | hydrogen-bond donors and their hydrogens                 |
+//! | `!NACC`   | hydrogen-bond acceptors, without antecedent atoms        |
+//!
+//! Fields are written in the standard fixed-width layout when every name fits in four
+//! characters, and in the `EXT` layout otherwise.
+
+use super::gromacs::atom_names;
+use crate::compat::prelude::*;
+use crate::core::error::ExportError;
+use crate::core::topology::MolecularTopology;
+use core::fmt;
+
+/// Default segment and residue name.
+const DEFAULT_SEGMENT: &str = "MOL";
+
+/// Longest name that fits the standard layout.
+const STANDARD_NAME_WIDTH: usize = 4;
+
+/// Largest atom count that fits the standard layout.
+const STANDARD_MAX_ATOMS: usize = 99_999_999;
+
+/// A topology prepared for writing as an X-PLOR PSF file.
+///
+/// Atoms whose metadata carries a residue keep its name and number; the others are numbered by
+/// bonded molecule and named after the segment. The file is produced through the
+/// [`Display`](fmt::Display) implementation; use [`MolecularTopology::to_psf`] for the defaults.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::export::psf::PsfFile;
+/// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+///
+/// let mut graph = MolecularGraph::new();
+/// let o = graph.add_atom(Element::O);
+/// for _ in 0..2 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+/// }
+/// let topology = assign_topology(&graph).unwrap();
+///
+/// let psf = PsfFile::new(&topology)
+///     .with_segment("WAT")
+///     .with_charges(&[-0.82, 0.41, 0.41])
+///     .unwrap()
+///     .to_string();
+///
+/// assert!(psf.starts_with("PSF\n"));
+/// assert!(psf.contains("       3 !NATOM"));
+/// assert!(psf.contains("       1 !NTHETA: angles"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PsfFile<'a> {
+    topology: &'a MolecularTopology,
+    segment: String,
+    charges: Option<&'a [f64]>,
+}
+
+impl<'a> PsfFile<'a> {
+    /// Prepares a topology for export with zero charges.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Typed topology to export.
+    pub fn new(topology: &'a MolecularTopology) -> Self {
+        Self {
+            topology,
+            segment: DEFAULT_SEGMENT.to_string(),
+            charges: None,
+        }
+    }
+
+    /// Sets the segment name, which also names residues of atoms without residue metadata.
+    pub fn with_segment(mut self, segment: impl Into<String>) -> Self {
+        self.segment = segment.into();
+        self
+    }
+
+    /// Supplies partial charges in elementary charges, one per atom.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExportError::LengthMismatch`] unless there is exactly one charge per atom.
+    pub fn with_charges(mut self, charges: &'a [f64]) -> Result<Self, ExportError> {
+        let expected = self.topology.atoms.len();
+        if charges.len() != expected {
+            return Err(ExportError::LengthMismatch {
+                field: "charges",
+                expected,
+                found: charges.len(),
+            });
+        }
+        self.charges = Some(charges);
+        Ok(self)
+    }
+}
+
+impl fmt::Display for PsfFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let topology = self.topology;
+        let names = atom_names(topology);
+        let molecule_of = topology.molecule_indices();
+        let residues: Vec<(String, String)> = topology
+            .atoms
+            .iter()
+            .map(|atom| match &atom.metadata.residue {
+                Some(residue) => (residue.number.to_string(), residue.name.clone()),
+                None => ((molecule_of[atom.id] + 1).to_string(), self.segment.clone()),
+            })
+            .collect();
+
+        let extended = topology.atoms.len() > STANDARD_MAX_ATOMS
+            || self.segment.len() > STANDARD_NAME_WIDTH
+            || names.iter().any(|name| name.len() > STANDARD_NAME_WIDTH)
+            || residues.iter().any(|(id, name)| {
+                id.len() > STANDARD_NAME_WIDTH || name.len() > STANDARD_NAME_WIDTH
+            })
+            || topology
+                .atoms
+                .iter()
+                .any(|atom| atom.atom_type.as_str().len() > STANDARD_NAME_WIDTH);
+        let layout = if extended {
+            Layout::EXTENDED
+        } else {
+            Layout::STANDARD
+        };
+        let index = layout.index;
+
+        writeln!(f, "{}", if extended { "PSF EXT" } else { "PSF" })?;
+        writeln!(f)?;
+        writeln!(f, "{:>index$} !NTITLE", 1)?;
+        writeln!(f, " REMARKS generated by dreid-typer")?;
+        writeln!(f)?;
+
+        writeln!(f, "{:>index$} !NATOM", topology.atoms.len())?;
+        let name = layout.name;
+        for atom in &topology.atoms {
+            let (resid, resname) = &residues[atom.id];
+            let charge = self.charges.map_or(0.0, |c| c[atom.id]);
+            writeln!(
+                f,
+                "{:>index$} {:<name$} {resid:<name$} {resname:<name$} {:<name$} {:<type_width$} \
+                 {charge:>10.6}    {:>9.4}  {:>10}",
+                atom.id + 1,
+                self.segment,
+                names[atom.id],
+                atom.atom_type.as_str(),
+                atom.element.atomic_mass(),
+                0,
+                type_width = layout.atom_type,
+            )?;
+        }
+        writeln!(f)?;
+
+        layout.section(
+            f,
+            "!NBOND: bonds",
+            4,
+            topology.bonds.iter().map(|bond| {
+                let (a, b) = bond.atom_ids;
+                vec![a, b]
+            }),
+        )?;
+        layout.section(
+            f,
+            "!NTHETA: angles",
+            3,
+            topology.angles.iter().map(|angle| {
+                let (i, j, k) = angle.atom_ids;
+                vec![i, j, k]
+            }),
+        )?;
+        layout.section(
+            f,
+            "!NPHI: dihedrals",
+            2,
+            topology.torsions.iter().map(|torsion| {
+                let (i, j, k, l) = torsion.atom_ids;
+                vec![i, j, k, l]
+            }),
+        )?;
+        layout.section(
+            f,
+            "!NIMPHI: impropers",
+            2,
+            topology.inversions.iter().map(|inversion| {
+                let (center, axis, p1, p2) = inversion.atom_ids;
+                vec![center, axis, p1, p2]
+            }),
+        )?;
+        layout.section(
+            f,
+            "!NDON: donors",
+            4,
+            topology
+                .hb_donors
+                .iter()
+                .map(|donor| vec![donor.donor_id, donor.hydrogen_id]),
+        )?;
+        write_acceptors(f, index, &topology.hb_acceptors)
+    }
+}
+
+impl MolecularTopology {
+    /// Renders the topology as an X-PLOR PSF file.
+    ///
+    /// Segment and residue name default to `MOL`, and every charge is zero. Use [`PsfFile`] to
+    /// customize either.
+    ///
+    /// # Returns
+    ///
+    /// The complete `.psf` file contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+    ///
+    /// let mut graph = MolecularGraph::new();
+    /// let h = graph.add_atom(Element::H);
+    /// let f = graph.add_atom(Element::F);
+    /// graph.add_bond(h, f, GraphBondOrder::Single).unwrap();
+    ///
+    /// let psf = assign_topology(&graph).unwrap().to_psf();
+    /// assert!(psf.contains("       1 !NBOND: bonds\n       1       2\n"));
+    /// ```
+    pub fn to_psf(&self) -> String {
+        PsfFile::new(self).to_string()
+    }
+}
+
+/// Column widths of a PSF layout.
+struct Layout {
+    /// Width of atom indices and section counts.
+    index: usize,
+    /// Width of segment, residue, and atom names.
+    name: usize,
+    /// Width of atom types.
+    atom_type: usize,
+}
+
+impl Layout {
+    /// Widths of the standard layout.
+    const STANDARD: Layout = Layout {
+        index: 8,
+        name: 4,
+        atom_type: 4,
+    };
+
+    /// Widths of the `EXT` layout.
+    const EXTENDED: Layout = Layout {
+        index: 10,
+        name: 8,
+        atom_type: 6,
+    };
+
+    /// Writes a section of index tuples, `per_line` tuples to a line, followed by a blank line.
+    fn section(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        title: &str,
+        per_line: usize,
+        entries: impl ExactSizeIterator<Item = Vec<usize>>,
+    ) -> fmt::Result {
+        let index = self.index;
+        writeln!(f, "{:>index$} {title}", entries.len())?;
+        let mut on_line = 0;
+        for entry in entries {
+            for id in entry {
+                write!(f, "{:>index$}", id + 1)?;
+            }
+            on_line += 1;
+            if on_line == per_line {
+                writeln!(f)?;
+                on_line = 0;
+            }
+        }
+        if on_line > 0 {
+            writeln!(f)?;
+        }
+        writeln!(f)
+    }
+}
+
+/// Writes the acceptor section, pairing every acceptor with a zero antecedent.
+fn write_acceptors(f: &mut fmt::Formatter<'_>, index: usize, acceptors: &[usize]) -> fmt::Result {
+    writeln!(f, "{:>index$} !NACC: acceptors", acceptors.len())?;
+    for line in acceptors.chunks(4) {
+        for &acceptor in line {
+            write!(f, "{:>index$}{:>index$}", acceptor + 1, 0)?;
+        }
+        writeln!(f)?;
+    }
+    writeln!(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::metadata::{AtomMetadata, ResidueId};
+    use crate::core::properties::{Element, GraphBondOrder};
+    use crate::pipeline::Typer;
+
+    fn acetic_acid() -> MolecularTopology {
+        let mut graph = MolecularGraph::new();
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        let o1 = graph.add_atom(Element::O);
+        let o2 = graph.add_atom(Element::O);
+        graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+        graph.add_bond(c2, o1, GraphBondOrder::Double).unwrap();
+        graph.add_bond(c2, o2, GraphBondOrder::Single).unwrap();
+        let ho = graph.add_atom(Element::H);
+        graph.add_bond(o2, ho, GraphBondOrder::Single).unwrap();
+        for _ in 0..3 {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(c1, h, GraphBondOrder::Single).unwrap();
+        }
+        Typer::new().assign_topology(&graph).unwrap()
+    }
+
+    /// Returns the count and whitespace-separated fields of the section with the given title.
+    fn section<'t>(psf: &'t str, title: &str) -> (usize, Vec<&'t str>) {
+        let start = psf.find(title).unwrap();
+        let header_start = psf[..start].rfind('\n').map_or(0, |i| i + 1);
+        let count = psf[header_start..start].trim().parse().unwrap();
+        let body = psf[start..].split_once('\n').unwrap().1;
+        let fields = body
+            .split("\n\n")
+            .next()
+            .unwrap()
+            .split_whitespace()
+            .collect();
+        (count, fields)
+    }
+
+    #[test]
+    fn psf_lists_every_term_with_one_based_indices() {
+        let topology = acetic_acid();
+        let psf = topology.to_psf();
+        assert!(psf.starts_with("PSF\n"));
+
+        let (atoms, fields) = section(&psf, "!NATOM");
+        assert_eq!(atoms, 8);
+        assert_eq!(
+            fields[..9],
+            [
+                "1", "MOL", "1", "MOL", "C1", "C_3", "0.000000", "12.0110", "0"
+            ]
+        );
+
+        let (bonds, fields) = section(&psf, "!NBOND");
+        assert_eq!(bonds, topology.bonds.len());
+        assert_eq!(fields.len(), 2 * bonds);
+        let (a, b) = topology.bonds[0].atom_ids;
+        assert_eq!(fields[..2], [(a + 1).to_string(), (b + 1).to_string()]);
+        // Four bonds per line.
+        assert_eq!(
+            psf.split("!NBOND: bonds\n")
+                .nth(1)
+                .unwrap()
+                .lines()
+                .next()
+                .unwrap()
+                .len(),
+            64
+        );
+
+        assert_eq!(section(&psf, "!NTHETA").0, topology.angles.len());
+        assert_eq!(section(&psf, "!NPHI").0, topology.torsions.len());
+        let (impropers, fields) = section(&psf, "!NIMPHI");
+        assert_eq!(impropers, topology.inversions.len());
+        let (center, ..) = topology.inversions[0].atom_ids;
+        assert_eq!(fields[0], (center + 1).to_string());
+
+        let (donors, fields) = section(&psf, "!NDON");
+        assert_eq!(donors, 1);
+        assert_eq!(fields, ["4", "5"]);
+        let (acceptors, fields) = section(&psf, "!NACC");
+        assert_eq!(acceptors, topology.hb_acceptors.len());
+        assert_eq!(fields.len(), 2 * acceptors);
+    }
+
+    #[test]
+    fn residue_metadata_and_long_names_are_kept() {
+        let mut topology = acetic_acid();
+        topology.atoms[0].metadata =
+            AtomMetadata::named("CMETHYL").with_residue(ResidueId::new("ACE", 'A', 42));
+        let psf = PsfFile::new(&topology)
+            .with_charges(&[0.1; 8])
+            .unwrap()
+            .to_string();
+
+        assert!(psf.starts_with("PSF EXT\n"));
+        let (_, fields) = section(&psf, "!NATOM");
+        assert_eq!(
+            fields[..7],
+            ["1", "MOL", "42", "ACE", "CMETHYL", "C_3", "0.100000"]
+        );
+        assert_eq!(fields[11], "1");
+
+        assert_eq!(
+            PsfFile::new(&topology).with_charges(&[0.0]).unwrap_err(),
+            ExportError::LengthMismatch {
+                field: "charges",
+                expected: 8,
+                found: 1,
+            }
+        );
+    }
+}
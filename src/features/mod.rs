@@ -0,0 +1,283 @@
+//! Per-atom descriptor vectors for training graph machine-learning models.
+//!
+//! [`Typer::atom_descriptors`](crate::Typer::atom_descriptors) runs perception and typing and
+//! then encodes every atom as a fixed-length numeric vector whose layout is described by a
+//! [`DescriptorLayout`]. The vectors sit next to the assigned atom types in an
+//! [`AtomDescriptors`] table, so each row is a ready-made (features, label) training pair.
+//!
+//! Every row is laid out as:
+//!
+//! 1. element one-hot over the layout's vocabulary plus an "other" slot,
+//! 2. hybridization one-hot (sp, sp2, sp3, resonant, none),
+//! 3. degree, formal charge, and lone pairs,
+//! 4. ring, aromatic, anti-aromatic, and resonant flags,
+//! 5. smallest ring size and resonance-system size (0 when absent),
+//! 6. neighbor counts over the element vocabulary plus an "other" slot.
+
+use crate::compat::prelude::*;
+use crate::core::properties::{Element, Hybridization};
+use crate::perception::AnnotatedMolecule;
+
+/// Hybridization classes encoded by the one-hot block, in column order.
+const HYBRIDIZATIONS: [Hybridization; 5] = [
+    Hybridization::SP,
+    Hybridization::SP2,
+    Hybridization::SP3,
+    Hybridization::Resonant,
+    Hybridization::None,
+];
+
+/// Names of the scalar columns that follow the hybridization block, in column order.
+const SCALAR_FEATURES: [&str; 9] = [
+    "degree",
+    "formal_charge",
+    "lone_pairs",
+    "in_ring",
+    "aromatic",
+    "anti_aromatic",
+    "resonant",
+    "smallest_ring_size",
+    "resonance_system_size",
+];
+
+/// Element vocabulary that fixes the length and meaning of descriptor vectors.
+///
+/// Elements outside the vocabulary are counted in a shared "other" slot, so vectors from
+/// different molecules always have the same width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorLayout {
+    elements: Vec<Element>,
+}
+
+impl DescriptorLayout {
+    /// Creates a layout over the given element vocabulary.
+    ///
+    /// # Arguments
+    ///
+    /// * `elements` - Elements that receive dedicated one-hot and neighbor-count columns;
+    ///   duplicates are ignored.
+    pub fn new(elements: impl IntoIterator<Item = Element>) -> Self {
+        let mut unique = Vec::new();
+        for element in elements {
+            if !unique.contains(&element) {
+                unique.push(element);
+            }
+        }
+        Self { elements: unique }
+    }
+
+    /// Returns the element vocabulary.
+    pub fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+
+    /// Returns the number of values in every descriptor vector.
+    pub fn width(&self) -> usize {
+        2 * (self.elements.len() + 1) + HYBRIDIZATIONS.len() + SCALAR_FEATURES.len()
+    }
+
+    /// Returns a name for every column, in column order.
+    pub fn feature_names(&self) -> Vec<String> {
+        let mut names = Vec::with_capacity(self.width());
+        names.extend(self.elements.iter().map(|e| format!("element={e}")));
+        names.push("element=other".to_string());
+        names.extend(HYBRIDIZATIONS.iter().map(|h| format!("hybridization={h}")));
+        names.extend(SCALAR_FEATURES.iter().map(|name| name.to_string()));
+        names.extend(self.elements.iter().map(|e| format!("neighbors={e}")));
+        names.push("neighbors=other".to_string());
+        names
+    }
+
+    /// Returns the vocabulary slot of `element`, with the "other" slot last.
+    fn slot(&self, element: Element) -> usize {
+        self.elements
+            .iter()
+            .position(|&e| e == element)
+            .unwrap_or(self.elements.len())
+    }
+}
+
+impl Default for DescriptorLayout {
+    /// Vocabulary covering the elements of typical organic and drug-like molecules.
+    fn default() -> Self {
+        use Element::*;
+        Self::new([H, B, C, N, O, F, Si, P, S, Cl, Br, I])
+    }
+}
+
+/// Descriptor vectors for every atom of a molecule, aligned with the assigned types.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtomDescriptors {
+    /// Name of every column, as returned by [`DescriptorLayout::feature_names`].
+    pub feature_names: Vec<String>,
+    /// Assigned atom type of every atom.
+    pub atom_types: Vec<String>,
+    /// Row-major descriptor matrix with one row of `feature_names.len()` values per atom.
+    pub values: Vec<f32>,
+}
+
+impl AtomDescriptors {
+    /// Returns the number of atoms (rows).
+    pub fn len(&self) -> usize {
+        self.atom_types.len()
+    }
+
+    /// Returns `true` if the molecule has no atoms.
+    pub fn is_empty(&self) -> bool {
+        self.atom_types.is_empty()
+    }
+
+    /// Returns the number of values per row.
+    pub fn width(&self) -> usize {
+        self.feature_names.len()
+    }
+
+    /// Returns the descriptor vector of one atom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `atom_id` is out of range.
+    pub fn row(&self, atom_id: usize) -> &[f32] {
+        let width = self.width();
+        &self.values[atom_id * width..(atom_id + 1) * width]
+    }
+
+    /// Iterates over `(descriptor vector, atom type)` pairs in atom order.
+    pub fn rows(&self) -> impl Iterator<Item = (&[f32], &str)> + '_ {
+        self.values
+            .chunks_exact(self.width().max(1))
+            .zip(self.atom_types.iter().map(String::as_str))
+    }
+}
+
+/// Encodes every atom of a perceived molecule according to `layout`.
+pub(crate) fn describe(
+    molecule: &AnnotatedMolecule,
+    atom_types: Vec<String>,
+    layout: &DescriptorLayout,
+) -> AtomDescriptors {
+    let vocabulary = layout.elements.len() + 1;
+    let mut resonance_size = vec![0usize; molecule.atoms.len()];
+    for system in &molecule.resonance_systems {
+        for &atom_id in &system.atom_ids {
+            resonance_size[atom_id] = resonance_size[atom_id].max(system.atom_ids.len());
+        }
+    }
+
+    let mut values = Vec::with_capacity(molecule.atoms.len() * layout.width());
+    for atom in &molecule.atoms {
+        let mut element_one_hot = vec![0.0; vocabulary];
+        element_one_hot[layout.slot(atom.element)] = 1.0;
+        values.extend(element_one_hot);
+
+        values.extend(
+            HYBRIDIZATIONS
+                .iter()
+                .map(|&h| if atom.hybridization == h { 1.0 } else { 0.0 }),
+        );
+
+        values.extend([
+            atom.degree as f32,
+            atom.formal_charge as f32,
+            atom.lone_pairs as f32,
+            flag(atom.is_in_ring),
+            flag(atom.is_aromatic),
+            flag(atom.is_anti_aromatic),
+            flag(atom.is_resonant),
+            atom.smallest_ring_size.unwrap_or(0) as f32,
+            resonance_size[atom.id] as f32,
+        ]);
+
+        let mut neighbors = vec![0.0; vocabulary];
+        for &(neighbor_id, _) in &molecule.adjacency[atom.id] {
+            neighbors[layout.slot(molecule.atoms[neighbor_id].element)] += 1.0;
+        }
+        values.extend(neighbors);
+    }
+
+    AtomDescriptors {
+        feature_names: layout.feature_names(),
+        atom_types,
+        values,
+    }
+}
+
+/// Encodes a boolean flag as `0.0` or `1.0`.
+fn flag(value: bool) -> f32 {
+    if value { 1.0 } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::properties::GraphBondOrder;
+    use crate::pipeline::Typer;
+
+    fn column(descriptors: &AtomDescriptors, atom_id: usize, name: &str) -> f32 {
+        let index = descriptors
+            .feature_names
+            .iter()
+            .position(|n| n == name)
+            .unwrap_or_else(|| panic!("missing column {name}"));
+        descriptors.row(atom_id)[index]
+    }
+
+    fn phenol() -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        let ring: Vec<usize> = (0..6).map(|_| graph.add_atom(Element::C)).collect();
+        for i in 0..6 {
+            graph
+                .add_bond(ring[i], ring[(i + 1) % 6], GraphBondOrder::Aromatic)
+                .unwrap();
+        }
+        let o = graph.add_atom(Element::O);
+        graph.add_bond(ring[0], o, GraphBondOrder::Single).unwrap();
+        let ho = graph.add_atom(Element::H);
+        graph.add_bond(o, ho, GraphBondOrder::Single).unwrap();
+        for &c in &ring[1..] {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn layout_width_matches_feature_names() {
+        let layout = DescriptorLayout::new([Element::C, Element::O, Element::C]);
+        assert_eq!(layout.elements(), [Element::C, Element::O]);
+        assert_eq!(layout.feature_names().len(), layout.width());
+        assert_eq!(layout.width(), 2 * 3 + 5 + 9);
+    }
+
+    #[test]
+    fn phenol_descriptors_encode_ring_and_neighbor_environment() {
+        let descriptors = Typer::new()
+            .atom_descriptors(&phenol(), &DescriptorLayout::default())
+            .unwrap();
+        assert_eq!(descriptors.len(), 13);
+        assert_eq!(descriptors.values.len(), 13 * descriptors.width());
+
+        assert_eq!(descriptors.atom_types[0], "C_R");
+        assert_eq!(column(&descriptors, 0, "element=C"), 1.0);
+        assert_eq!(column(&descriptors, 0, "aromatic"), 1.0);
+        assert_eq!(column(&descriptors, 0, "smallest_ring_size"), 6.0);
+        assert_eq!(column(&descriptors, 0, "degree"), 3.0);
+        assert_eq!(column(&descriptors, 0, "neighbors=C"), 2.0);
+        assert_eq!(column(&descriptors, 0, "neighbors=O"), 1.0);
+        assert!(column(&descriptors, 0, "resonance_system_size") >= 6.0);
+
+        assert_eq!(descriptors.atom_types[7], "H_HB");
+        assert_eq!(column(&descriptors, 7, "in_ring"), 0.0);
+        assert_eq!(column(&descriptors, 7, "hybridization=None"), 1.0);
+    }
+
+    #[test]
+    fn elements_outside_vocabulary_use_other_slot() {
+        let layout = DescriptorLayout::new([Element::C]);
+        let descriptors = Typer::new().atom_descriptors(&phenol(), &layout).unwrap();
+        assert_eq!(column(&descriptors, 6, "element=other"), 1.0);
+        assert_eq!(column(&descriptors, 6, "neighbors=other"), 1.0);
+        assert_eq!(descriptors.rows().count(), 13);
+    }
+}
@@ -0,0 +1,1062 @@
+//! Reading of crystal structures from CIF and mmCIF files.
+//!
+//! Only the first data block is read. The cell comes from the `_cell_length_*` and
+//! `_cell_angle_*` items, and atom sites from the `_atom_site_*` loop, with fractional
+//! (`fract_x`) or, as in mmCIF, Cartesian (`Cartn_x`) coordinates. Elements are taken from
+//! `_atom_site_type_symbol` when present and from the site label otherwise. Tags are compared
+//! without regard to case, and the mmCIF spelling `_category.item` is accepted for every
+//! `_category_item`; values may carry standard uncertainties such as `1.234(5)`. Deuterium (`D`)
+//! and tritium (`T`) sites, common in neutron structures, are read as hydrogen with their isotope
+//! recorded.
+//!
+//! The symmetry operators of `_space_group_symop_operation_xyz` (or the older
+//! `_symmetry_equiv_pos_as_xyz`) can be applied to the listed sites to fill the unit cell;
+//! copies landing on an existing site of the same element, as on special positions, are
+//! merged. All sites are then wrapped into the cell, and two atoms are bonded, across cell faces
+//! if needed, when closer than the sum of their covalent radii plus a tolerance, as for
+//! [XYZ files](crate::io::xyz). Occupancies are ignored, so disordered sites should be resolved
+//! beforehand.
+
+use crate::compat::collections::HashMap;
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::float::Float;
+use crate::compat::prelude::*;
+use crate::core::error::CifError;
+use crate::core::metadata::AtomMetadata;
+use crate::core::periodic::{ImageFlags, PeriodicMolecularGraph};
+use crate::core::properties::{Element, GraphBondOrder};
+use crate::io::parse_element_symbol;
+use crate::perception::assign_bond_orders;
+#[cfg(feature = "std")]
+use std::io::Read;
+
+/// Settings for expanding the asymmetric unit and turning distances into bonds.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::cif::CifOptions;
+///
+/// let options = CifOptions {
+///     apply_symmetry: false,
+///     ..CifOptions::DEFAULT
+/// };
+/// assert!(options.infer_bond_orders);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CifOptions {
+    /// Distance in Å added to the sum of covalent radii below which two atoms are bonded.
+    pub bond_tolerance: f64,
+    /// Distance in Å below which two atoms are treated as overlapping rather than bonded.
+    pub min_bond_length: f64,
+    /// Whether to run [`assign_bond_orders`] on the detected connectivity.
+    pub infer_bond_orders: bool,
+    /// Whether to apply the symmetry operators of the file to the listed atom sites.
+    pub apply_symmetry: bool,
+    /// Distance in Å below which a symmetry copy coincides with a site of the same element.
+    pub merge_distance: f64,
+}
+
+impl CifOptions {
+    /// Default options: a 0.45 Å bond tolerance, a 0.4 Å overlap cutoff, bond-order inference,
+    /// and symmetry expansion merging copies within 0.1 Å.
+    pub const DEFAULT: Self = Self {
+        bond_tolerance: 0.45,
+        min_bond_length: 0.4,
+        infer_bond_orders: true,
+        apply_symmetry: true,
+        merge_distance: 0.1,
+    };
+}
+
+impl Default for CifOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Lengths and angles of a unit cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellParameters {
+    /// Cell lengths `a`, `b`, and `c` in Å.
+    pub lengths: [f64; 3],
+    /// Cell angles `α`, `β`, and `γ` in degrees.
+    pub angles: [f64; 3],
+}
+
+impl CellParameters {
+    /// Returns the cell vectors `a`, `b`, and `c` as rows, in Å.
+    ///
+    /// `a` lies along x and `b` in the xy plane, the usual crystallographic convention.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CifError::DegenerateCell`] if a length is not positive or the angles do not
+    /// span a cell of positive volume.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::io::cif::CellParameters;
+    ///
+    /// let hexagonal = CellParameters { lengths: [2.0, 2.0, 5.0], angles: [90.0, 90.0, 120.0] };
+    /// let [a, b, c] = hexagonal.vectors().unwrap();
+    /// assert_eq!(a, [2.0, 0.0, 0.0]);
+    /// assert!((b[0] + 1.0).abs() < 1e-12 && (b[1] - 3f64.sqrt()).abs() < 1e-12);
+    /// assert!((c[2] - 5.0).abs() < 1e-12);
+    /// ```
+    pub fn vectors(&self) -> Result<[[f64; 3]; 3], CifError> {
+        let degenerate = CifError::DegenerateCell {
+            lengths: self.lengths,
+            angles: self.angles,
+        };
+        let [a, b, c] = self.lengths;
+        let [cos_alpha, cos_beta, cos_gamma] = self.angles.map(|angle| angle.to_radians().cos());
+        let sin_gamma = self.angles[2].to_radians().sin();
+        if a <= 0.0 || b <= 0.0 || c <= 0.0 || sin_gamma.abs() < 1e-8 {
+            return Err(degenerate);
+        }
+        let cy = (cos_alpha - cos_beta * cos_gamma) / sin_gamma;
+        let cz_squared = 1.0 - cos_beta * cos_beta - cy * cy;
+        if cz_squared <= 1e-12 {
+            return Err(degenerate);
+        }
+        Ok([
+            [a, 0.0, 0.0],
+            [b * cos_gamma, b * sin_gamma, 0.0],
+            [c * cos_beta, c * cy, c * cz_squared.sqrt()],
+        ])
+    }
+}
+
+/// A crystal structure read from a CIF file.
+#[derive(Debug, Clone)]
+pub struct CifStructure {
+    /// Name of the data block (the text after `data_`).
+    pub name: String,
+    /// Cell lengths and angles as written in the file.
+    pub cell: CellParameters,
+    /// Atoms of the unit cell with periodic bonds, carrying the wrapped Cartesian coordinates as
+    /// [`MolecularGraph::positions`](crate::MolecularGraph::positions) and each site label as
+    /// the atom name.
+    pub graph: PeriodicMolecularGraph,
+}
+
+/// Parses the first data block of a CIF or mmCIF file into a periodic graph.
+///
+/// # Arguments
+///
+/// * `text` - Contents of the CIF file.
+/// * `options` - Symmetry expansion and bond detection settings.
+///
+/// # Returns
+///
+/// The data block name, the cell parameters, and a graph of the unit cell whose bonds connect
+/// every pair of atoms within bonding distance, each with the image of its second atom; a
+/// hydrogen keeps only its shortest bond.
+///
+/// # Errors
+///
+/// Returns a [`CifError`] for malformed syntax, a missing cell or atom-site item, an invalid
+/// number, element, or symmetry operator, or a degenerate cell.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::Typer;
+/// use dreid_typer::io::cif::{CifOptions, parse_cif};
+///
+/// // Diamond: two sites expanded by the face-centering translations.
+/// let cif = "\
+/// data_diamond
+/// _cell_length_a 3.567
+/// _cell_length_b 3.567
+/// _cell_length_c 3.567
+/// _cell_angle_alpha 90
+/// _cell_angle_beta 90
+/// _cell_angle_gamma 90
+/// loop_
+/// _symmetry_equiv_pos_as_xyz
+/// 'x, y, z'
+/// 'x, y+1/2, z+1/2'
+/// 'x+1/2, y, z+1/2'
+/// 'x+1/2, y+1/2, z'
+/// loop_
+/// _atom_site_label
+/// _atom_site_fract_x
+/// _atom_site_fract_y
+/// _atom_site_fract_z
+/// C1 0.00 0.00 0.00
+/// C2 0.25 0.25 0.25
+/// ";
+/// let structure = parse_cif(cif, &CifOptions::DEFAULT).unwrap();
+/// assert_eq!(structure.name, "diamond");
+/// assert_eq!(structure.graph.graph.atoms.len(), 8);
+/// assert_eq!(structure.graph.graph.bonds.len(), 16);
+///
+/// let topology = Typer::new().assign_periodic_topology(&structure.graph).unwrap();
+/// assert!(topology.atoms.iter().all(|atom| atom.atom_type == "C_3"));
+/// ```
+pub fn parse_cif(text: &str, options: &CifOptions) -> Result<CifStructure, CifError> {
+    let block = DataBlock::parse(text)?;
+
+    let number = |tag: &'static str| -> Result<f64, CifError> {
+        let value = block.item(tag).ok_or(CifError::MissingItem { tag })?;
+        parse_number(tag, value)
+    };
+    let cell = CellParameters {
+        lengths: [
+            number("_cell_length_a")?,
+            number("_cell_length_b")?,
+            number("_cell_length_c")?,
+        ],
+        angles: [
+            number("_cell_angle_alpha")?,
+            number("_cell_angle_beta")?,
+            number("_cell_angle_gamma")?,
+        ],
+    };
+    let vectors = cell.vectors()?;
+
+    let sites = read_sites(&block, &vectors)?;
+    let operators = if options.apply_symmetry {
+        read_operators(&block)?
+    } else {
+        Vec::new()
+    };
+
+    let mut atoms: Vec<(&Site<'_>, [f64; 3])> = Vec::new();
+    for site in &sites {
+        let copies = if operators.is_empty() {
+            vec![site.fractional]
+        } else {
+            operators
+                .iter()
+                .map(|operator| operator.apply(site.fractional))
+                .collect()
+        };
+        for fractional in copies {
+            let fractional = fractional.map(wrap);
+            let coincides = atoms.iter().any(|&(other_site, other)| {
+                other_site.element == site.element
+                    && image_distance(&vectors, fractional, other) < options.merge_distance
+            });
+            if !coincides {
+                atoms.push((site, fractional));
+            }
+        }
+    }
+
+    let mut graph = PeriodicMolecularGraph::new(vectors);
+    for &(site, _) in &atoms {
+        let id = graph.add_atom(site.element);
+        graph.graph.atoms[id].isotope = site.isotope;
+        if !site.label.is_empty() {
+            graph
+                .graph
+                .set_atom_metadata(id, AtomMetadata::named(site.label))
+                .expect("the atom was just added");
+        }
+    }
+    let elements: Vec<Element> = atoms.iter().map(|&(site, _)| site.element).collect();
+    let fractional: Vec<[f64; 3]> = atoms.iter().map(|&(_, position)| position).collect();
+    for (a, b, image) in detect_periodic_bonds(&elements, &fractional, &vectors, options) {
+        graph
+            .add_periodic_bond(a, b, GraphBondOrder::Single, image)
+            .expect("detected bonds join distinct existing atoms");
+    }
+    graph
+        .graph
+        .set_positions(
+            fractional
+                .iter()
+                .map(|&position| cartesian(&vectors, position))
+                .collect(),
+        )
+        .expect("one position was computed per atom");
+    if options.infer_bond_orders {
+        assign_bond_orders(&mut graph.graph);
+    }
+
+    Ok(CifStructure {
+        name: block.name,
+        cell,
+        graph,
+    })
+}
+
+impl PeriodicMolecularGraph {
+    /// Reads the first data block of a CIF or mmCIF file into a periodic graph.
+    ///
+    /// See [`parse_cif`] for how the cell is filled and bonds are assigned; the block name and
+    /// cell parameters are discarded.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the CIF text, such as an open file.
+    /// * `options` - Symmetry expansion and bond detection settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CifError::Io`] when reading fails, or any error raised by [`parse_cif`].
+    #[cfg(feature = "std")]
+    pub fn from_cif(mut reader: impl Read, options: &CifOptions) -> Result<Self, CifError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        parse_cif(&text, options).map(|structure| structure.graph)
+    }
+}
+
+/// A value or tag of the file together with its line.
+#[derive(Debug, Clone)]
+struct Token<'a> {
+    text: &'a str,
+    line: usize,
+    /// Whether the token was quoted or a text field, which makes it a value in any case.
+    quoted: bool,
+}
+
+impl Token<'_> {
+    fn is_tag(&self) -> bool {
+        !self.quoted && self.text.starts_with('_')
+    }
+
+    fn is_keyword(&self) -> bool {
+        !self.quoted && {
+            let lower = self.text.to_ascii_lowercase();
+            lower == "loop_"
+                || lower.starts_with("data_")
+                || lower.starts_with("save_")
+                || lower == "global_"
+                || lower == "stop_"
+        }
+    }
+}
+
+/// Splits CIF text into tokens, dropping comments.
+fn tokenize(text: &str) -> Result<Vec<Token<'_>>, CifError> {
+    let mut tokens = Vec::new();
+    let mut lines = text.lines().enumerate().peekable();
+    while let Some((index, line)) = lines.next() {
+        let line_number = index + 1;
+        if let Some(first) = line.strip_prefix(';') {
+            // A text field runs until the next line starting with a semicolon; its value is
+            // taken from the first line only, which is all this reader needs.
+            loop {
+                match lines.next() {
+                    Some((_, next)) if next.starts_with(';') => break,
+                    Some(_) => {}
+                    None => {
+                        return Err(CifError::UnterminatedTextField { line: line_number });
+                    }
+                }
+            }
+            tokens.push(Token {
+                text: first.trim(),
+                line: line_number,
+                quoted: true,
+            });
+            continue;
+        }
+
+        let mut rest = line;
+        loop {
+            rest = rest.trim_start();
+            let Some(first) = rest.chars().next() else {
+                break;
+            };
+            if first == '#' {
+                break;
+            }
+            if first == '\'' || first == '"' {
+                // A quote closes only when followed by whitespace or the end of the line.
+                let body = &rest[1..];
+                let end = body
+                    .char_indices()
+                    .find(|&(i, c)| {
+                        c == first && body[i + 1..].chars().next().is_none_or(char::is_whitespace)
+                    })
+                    .map(|(i, _)| i)
+                    .unwrap_or(body.len());
+                tokens.push(Token {
+                    text: &body[..end],
+                    line: line_number,
+                    quoted: true,
+                });
+                rest = body.get(end + 1..).unwrap_or("");
+            } else {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                tokens.push(Token {
+                    text: &rest[..end],
+                    line: line_number,
+                    quoted: false,
+                });
+                rest = &rest[end..];
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Loop of a data block: tag names and the values of every row.
+#[derive(Debug)]
+struct Loop<'a> {
+    tags: Vec<String>,
+    rows: Vec<Vec<Token<'a>>>,
+}
+
+impl<'a> Loop<'a> {
+    fn column(&self, tag: &str) -> Option<usize> {
+        self.tags.iter().position(|name| name == tag)
+    }
+}
+
+/// Items and loops of the first data block, keyed by normalized tag.
+#[derive(Debug)]
+struct DataBlock<'a> {
+    name: String,
+    items: HashMap<String, Token<'a>>,
+    loops: Vec<Loop<'a>>,
+}
+
+impl<'a> DataBlock<'a> {
+    fn parse(text: &'a str) -> Result<Self, CifError> {
+        let tokens = tokenize(text)?;
+        let mut block = DataBlock {
+            name: String::new(),
+            items: HashMap::new(),
+            loops: Vec::new(),
+        };
+
+        let mut i = 0;
+        let mut seen_block = false;
+        while i < tokens.len() {
+            let token = &tokens[i];
+            let lower = token.text.to_ascii_lowercase();
+            if !token.quoted && lower.starts_with("data_") {
+                if seen_block {
+                    break;
+                }
+                seen_block = true;
+                block.name = token.text[5..].to_string();
+                i += 1;
+            } else if !token.quoted && lower == "loop_" {
+                let loop_line = token.line;
+                i += 1;
+                let mut tags = Vec::new();
+                while i < tokens.len() && tokens[i].is_tag() {
+                    tags.push(normalize_tag(tokens[i].text));
+                    i += 1;
+                }
+                let start = i;
+                while i < tokens.len() && !tokens[i].is_tag() && !tokens[i].is_keyword() {
+                    i += 1;
+                }
+                let values = &tokens[start..i];
+                if tags.is_empty() || values.len() % tags.len() != 0 {
+                    return Err(CifError::IncompleteLoop {
+                        line: loop_line,
+                        tags: tags.len(),
+                        values: values.len(),
+                    });
+                }
+                let rows = values.chunks(tags.len()).map(|row| row.to_vec()).collect();
+                block.loops.push(Loop { tags, rows });
+            } else if token.is_tag() {
+                let value = tokens
+                    .get(i + 1)
+                    .filter(|value| !value.is_tag() && !value.is_keyword())
+                    .ok_or_else(|| CifError::MissingValue {
+                        line: token.line,
+                        tag: token.text.to_string(),
+                    })?;
+                block.items.insert(normalize_tag(token.text), value.clone());
+                i += 2;
+            } else {
+                i += 1;
+            }
+        }
+        Ok(block)
+    }
+
+    fn item(&self, tag: &str) -> Option<&Token<'a>> {
+        self.items.get(tag)
+    }
+
+    fn find_loop(&self, tag: &str) -> Option<&Loop<'a>> {
+        self.loops.iter().find(|table| table.column(tag).is_some())
+    }
+}
+
+/// Lowercases a tag and rewrites the mmCIF `_category.item` form as `_category_item`.
+fn normalize_tag(tag: &str) -> String {
+    tag.to_ascii_lowercase().replace('.', "_")
+}
+
+/// Parses a number, dropping a trailing standard uncertainty in parentheses.
+fn parse_number(tag: &'static str, token: &Token<'_>) -> Result<f64, CifError> {
+    let text = token.text;
+    let digits = text.split_once('(').map_or(text, |(value, _)| value);
+    digits
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| CifError::InvalidField {
+            line: token.line,
+            tag,
+            value: text.to_string(),
+        })
+}
+
+/// Atom site listed in the file.
+struct Site<'a> {
+    label: &'a str,
+    element: Element,
+    isotope: Option<u16>,
+    fractional: [f64; 3],
+}
+
+fn read_sites<'a>(
+    block: &DataBlock<'a>,
+    vectors: &[[f64; 3]; 3],
+) -> Result<Vec<Site<'a>>, CifError> {
+    const FRACTIONAL: [&str; 3] = [
+        "_atom_site_fract_x",
+        "_atom_site_fract_y",
+        "_atom_site_fract_z",
+    ];
+    const CARTESIAN: [&str; 3] = [
+        "_atom_site_cartn_x",
+        "_atom_site_cartn_y",
+        "_atom_site_cartn_z",
+    ];
+
+    let (table, tags, is_fractional) = match block.find_loop(FRACTIONAL[0]) {
+        Some(table) => (table, FRACTIONAL, true),
+        None => match block.find_loop(CARTESIAN[0]) {
+            Some(table) => (table, CARTESIAN, false),
+            None => {
+                return Err(CifError::MissingItem { tag: FRACTIONAL[0] });
+            }
+        },
+    };
+    let mut columns = [0; 3];
+    for (column, tag) in columns.iter_mut().zip(tags) {
+        *column = table.column(tag).ok_or(CifError::MissingItem { tag })?;
+    }
+    let label_column = table
+        .column("_atom_site_label")
+        .or_else(|| table.column("_atom_site_label_atom_id"));
+    let symbol_column = table.column("_atom_site_type_symbol");
+
+    table
+        .rows
+        .iter()
+        .map(|row| {
+            let label = label_column.map_or("", |column| row[column].text);
+            let symbol = symbol_column
+                .map(|column| &row[column])
+                .filter(|token| !is_unknown(token.text))
+                .or_else(|| label_column.map(|column| &row[column]))
+                .ok_or(CifError::MissingItem {
+                    tag: "_atom_site_type_symbol",
+                })?;
+            let (element, isotope) =
+                parse_element(symbol.text).ok_or_else(|| CifError::UnknownElement {
+                    line: symbol.line,
+                    symbol: symbol.text.to_string(),
+                })?;
+
+            let mut position = [0.0; 3];
+            for ((coordinate, column), tag) in position.iter_mut().zip(columns).zip(tags) {
+                *coordinate = parse_number(tag, &row[column])?;
+            }
+            let fractional = if is_fractional {
+                position
+            } else {
+                fractional(vectors, position)
+            };
+            Ok(Site {
+                label,
+                element,
+                isotope,
+                fractional,
+            })
+        })
+        .collect()
+}
+
+/// Returns `true` for the CIF placeholders of unknown and inapplicable values.
+fn is_unknown(text: &str) -> bool {
+    text == "?" || text == "."
+}
+
+/// Parses an element from a type symbol such as `Zn2+` or a site label such as `C12A`.
+///
+/// The leading letters are tried in any letter case, then shortened to two and to one letter,
+/// so that labels like `Ow1` still resolve.
+fn parse_element(symbol: &str) -> Option<(Element, Option<u16>)> {
+    let letters: String = symbol
+        .chars()
+        .take_while(char::is_ascii_alphabetic)
+        .collect();
+    (1..=letters.len().min(3)).rev().find_map(|length| {
+        let mut chars = letters[..length].chars();
+        let first = chars.next()?;
+        parse_element_symbol(
+            &(first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()),
+        )
+    })
+}
+
+/// Affine symmetry operator acting on fractional coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Operator {
+    rotation: [[f64; 3]; 3],
+    translation: [f64; 3],
+}
+
+impl Operator {
+    fn apply(&self, position: [f64; 3]) -> [f64; 3] {
+        core::array::from_fn(|row| {
+            (0..3)
+                .map(|column| self.rotation[row][column] * position[column])
+                .sum::<f64>()
+                + self.translation[row]
+        })
+    }
+
+    /// Parses an operator written as three comma-separated expressions such as `-x+1/2, y, z`.
+    fn parse(text: &str) -> Option<Self> {
+        let parts: Vec<&str> = text.split(',').collect();
+        if parts.len() != 3 {
+            return None;
+        }
+        let mut operator = Self {
+            rotation: [[0.0; 3]; 3],
+            translation: [0.0; 3],
+        };
+        for (row, part) in parts.iter().enumerate() {
+            let expression: String = part
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>()
+                .to_ascii_lowercase();
+            if expression.is_empty() {
+                return None;
+            }
+            let mut chars = expression.chars().peekable();
+            while chars.peek().is_some() {
+                let sign = match chars.peek() {
+                    Some('-') => {
+                        chars.next();
+                        -1.0
+                    }
+                    Some('+') => {
+                        chars.next();
+                        1.0
+                    }
+                    _ => 1.0,
+                };
+                let mut number = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_digit() || c == '.' || c == '/' {
+                        number.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                }
+                let variable = match chars.peek() {
+                    Some('x') => Some(0),
+                    Some('y') => Some(1),
+                    Some('z') => Some(2),
+                    _ => None,
+                };
+                if variable.is_some() {
+                    chars.next();
+                }
+                let value = match number.as_str() {
+                    "" if variable.is_some() => 1.0,
+                    "" => return None,
+                    _ => parse_fraction(&number)?,
+                };
+                match variable {
+                    Some(column) => operator.rotation[row][column] += sign * value,
+                    None => operator.translation[row] += sign * value,
+                }
+            }
+        }
+        Some(operator)
+    }
+}
+
+/// Parses a decimal number or a fraction such as `1/2`.
+fn parse_fraction(text: &str) -> Option<f64> {
+    match text.split_once('/') {
+        Some((numerator, denominator)) => {
+            let denominator: f64 = denominator.parse().ok()?;
+            (denominator != 0.0).then_some(numerator.parse::<f64>().ok()? / denominator)
+        }
+        None => text.parse().ok(),
+    }
+}
+
+fn read_operators(block: &DataBlock<'_>) -> Result<Vec<Operator>, CifError> {
+    const TAGS: [&str; 2] = [
+        "_space_group_symop_operation_xyz",
+        "_symmetry_equiv_pos_as_xyz",
+    ];
+    let tokens: Vec<&Token<'_>> = TAGS
+        .iter()
+        .find_map(|&tag| {
+            if let Some(table) = block.find_loop(tag) {
+                let column = table.column(tag).expect("the loop holds the tag");
+                Some(table.rows.iter().map(|row| &row[column]).collect())
+            } else {
+                block.item(tag).map(|token| vec![token])
+            }
+        })
+        .unwrap_or_default();
+    tokens
+        .into_iter()
+        .map(|token| {
+            Operator::parse(token.text).ok_or_else(|| CifError::InvalidSymmetryOperator {
+                line: token.line,
+                operator: token.text.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Wraps a fractional coordinate into `[0, 1)`.
+fn wrap(coordinate: f64) -> f64 {
+    let wrapped = coordinate - coordinate.floor();
+    if wrapped >= 1.0 { 0.0 } else { wrapped }
+}
+
+fn cartesian(vectors: &[[f64; 3]; 3], fractional: [f64; 3]) -> [f64; 3] {
+    core::array::from_fn(|k| (0..3).map(|i| fractional[i] * vectors[i][k]).sum())
+}
+
+/// Converts Cartesian coordinates to fractional ones for cell vectors in the form returned by
+/// [`CellParameters::vectors`].
+fn fractional(vectors: &[[f64; 3]; 3], position: [f64; 3]) -> [f64; 3] {
+    let [a, b, c] = vectors;
+    let fc = position[2] / c[2];
+    let fb = (position[1] - fc * c[1]) / b[1];
+    let fa = (position[0] - fb * b[0] - fc * c[0]) / a[0];
+    [fa, fb, fc]
+}
+
+/// Returns the Cartesian distance between two fractional positions under the nearest lattice
+/// translation.
+fn image_distance(vectors: &[[f64; 3]; 3], a: [f64; 3], b: [f64; 3]) -> f64 {
+    let delta = core::array::from_fn(|k| {
+        let d = b[k] - a[k];
+        d - d.round()
+    });
+    norm(cartesian(vectors, delta))
+}
+
+fn norm(vector: [f64; 3]) -> f64 {
+    vector.iter().map(|c| c * c).sum::<f64>().sqrt()
+}
+
+/// Returns every atom pair within bonding distance, with the image of the second atom, sorted
+/// by atom index.
+///
+/// Every atom and its images in the 26 neighboring cells are binned into cubic cells no smaller
+/// than the longest possible bond, so each atom of the reference cell is only compared with
+/// nearby images. Pairs are accepted from the shortest up, and a hydrogen that already has a
+/// bond takes no further ones.
+fn detect_periodic_bonds(
+    elements: &[Element],
+    fractional: &[[f64; 3]],
+    vectors: &[[f64; 3]; 3],
+    options: &CifOptions,
+) -> Vec<(usize, usize, ImageFlags)> {
+    let radii: Vec<Option<f64>> = elements
+        .iter()
+        .map(|element| element.covalent_radius())
+        .collect();
+    let Some(max_radius) = radii.iter().flatten().copied().reduce(f64::max) else {
+        return Vec::new();
+    };
+    let bin_size = (2.0 * max_radius + options.bond_tolerance).max(f64::EPSILON);
+    let bin_of = |position: [f64; 3]| position.map(|c| (c / bin_size).floor() as i64);
+
+    let mut points = Vec::new();
+    for (atom, position) in fractional.iter().enumerate() {
+        if radii[atom].is_none() {
+            continue;
+        }
+        for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    let image = [x, y, z];
+                    let shifted = core::array::from_fn(|k| position[k] + image[k] as f64);
+                    points.push((atom, image, cartesian(vectors, shifted)));
+                }
+            }
+        }
+    }
+    let mut bins: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+    for (index, &(_, _, position)) in points.iter().enumerate() {
+        bins.entry(bin_of(position)).or_default().push(index);
+    }
+
+    let mut pairs = Vec::new();
+    for (atom, position) in fractional.iter().enumerate() {
+        let Some(radius) = radii[atom] else {
+            continue;
+        };
+        let origin = cartesian(vectors, *position);
+        let [x, y, z] = bin_of(origin);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(members) = bins.get(&[x + dx, y + dy, z + dz]) else {
+                        continue;
+                    };
+                    for &index in members {
+                        let (other, image, other_position) = points[index];
+                        if other <= atom {
+                            continue;
+                        }
+                        let other_radius = radii[other].expect("binned atoms have a radius");
+                        let distance =
+                            norm(core::array::from_fn(|k| other_position[k] - origin[k]));
+                        if distance >= options.min_bond_length
+                            && distance <= radius + other_radius + options.bond_tolerance
+                        {
+                            pairs.push((distance, atom, other, image));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut hydrogen_bonded = vec![false; elements.len()];
+    let mut bonds = Vec::new();
+    for (_, a, b, image) in pairs {
+        let is_hydrogen = |atom: usize| elements[atom] == Element::H;
+        if (is_hydrogen(a) && hydrogen_bonded[a]) || (is_hydrogen(b) && hydrogen_bonded[b]) {
+            continue;
+        }
+        hydrogen_bonded[a] = true;
+        hydrogen_bonded[b] = true;
+        bonds.push((a, b, image));
+    }
+    bonds.sort_unstable();
+    bonds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Methanol in a cell small enough that its hydroxyl bond crosses the c-face; the second
+    /// site is generated by a mirror that maps the first methyl hydrogen onto itself.
+    const METHANOL: &str = "\
+# Hand-built test structure
+data_methanol
+_cell_length_a    8.000(2)
+_cell_length_b    8.000
+_cell_length_c    4.000
+_cell_angle_alpha 90.0
+_cell_angle_beta  90.0
+_cell_angle_gamma 90.0
+_symmetry_space_group_name_H-M 'P 1 m 1'
+_publ_section_comment
+;
+A text field with 'quotes', loop_ keywords, and _tags that must be skipped.
+;
+loop_
+_symmetry_equiv_pos_as_xyz
+ 'x, y, z'
+ \"x, -y, z\"
+loop_
+_atom_site_label
+_atom_site_type_symbol
+_atom_site_fract_x
+_atom_site_fract_y
+_atom_site_fract_z
+_atom_site_occupancy
+C1  C   0.5000  0.0000  0.6000  1
+O1  O   0.5000  0.0000  0.9550  1  # wraps around the c-face through the bond to H4
+H1  H   0.3730  0.0000  0.5100  1
+H2  H   0.5640  0.0890  0.5100  1
+H4  H   0.5000  0.0000  0.1950  1
+";
+
+    #[test]
+    fn expands_symmetry_and_bonds_across_cell_faces() {
+        let structure = parse_cif(METHANOL, &CifOptions::DEFAULT).unwrap();
+        assert_eq!(structure.name, "methanol");
+        assert_eq!(structure.cell.lengths, [8.0, 8.0, 4.0]);
+
+        let graph = &structure.graph.graph;
+        let labels: Vec<_> = graph
+            .atoms
+            .iter()
+            .map(|atom| atom.metadata.name.as_deref().unwrap())
+            .collect();
+        // The mirror copies of C1, O1, H1, and H4 lie on the mirror plane and are merged.
+        assert_eq!(labels, ["C1", "O1", "H1", "H2", "H2", "H4"]);
+        let positions = graph.positions.as_ref().unwrap();
+        assert!((positions[4][1] - (1.0 - 0.089) * 8.0).abs() < 1e-9);
+
+        let bonds: Vec<_> = graph
+            .bonds
+            .iter()
+            .zip(&structure.graph.bond_images)
+            .map(|(bond, &image)| (bond.atom_ids, image))
+            .collect();
+        assert_eq!(
+            bonds,
+            [
+                ((0, 1), [0, 0, 0]),
+                ((0, 2), [0, 0, 0]),
+                ((0, 3), [0, 0, 0]),
+                ((0, 4), [0, -1, 0]),
+                ((1, 5), [0, 0, 1]),
+            ]
+        );
+
+        let topology = crate::Typer::new()
+            .assign_periodic_topology(&structure.graph)
+            .unwrap();
+        let types: Vec<_> = topology
+            .atoms
+            .iter()
+            .map(|atom| atom.atom_type.as_str())
+            .collect();
+        assert_eq!(types, ["C_3", "O_3", "H_", "H_", "H_", "H_HB"]);
+
+        #[cfg(feature = "std")]
+        {
+            let options = CifOptions {
+                apply_symmetry: false,
+                ..CifOptions::DEFAULT
+            };
+            let asymmetric =
+                PeriodicMolecularGraph::from_cif(METHANOL.as_bytes(), &options).unwrap();
+            assert_eq!(asymmetric.graph.atoms.len(), 5);
+        }
+    }
+
+    #[test]
+    fn reads_mmcif_cartesian_coordinates() {
+        let mmcif = "\
+data_HOH
+_cell.length_a 20.0
+_cell.length_b 20.0
+_cell.length_c 20.0
+_cell.angle_alpha 90
+_cell.angle_beta 90
+_cell.angle_gamma 90
+loop_
+_atom_site.group_PDB
+_atom_site.id
+_atom_site.type_symbol
+_atom_site.label_atom_id
+_atom_site.Cartn_x
+_atom_site.Cartn_y
+_atom_site.Cartn_z
+HETATM 1 O  O   10.000 10.000 10.000
+HETATM 2 H  H1  10.957 10.000 10.000
+HETATM 3 H  H2   9.760 10.927 10.000
+";
+        let structure = parse_cif(mmcif, &CifOptions::DEFAULT).unwrap();
+        let graph = &structure.graph.graph;
+        assert_eq!(graph.atoms[0].element, Element::O);
+        assert_eq!(graph.atoms[1].metadata.name.as_deref(), Some("H1"));
+        assert_eq!(graph.bonds.len(), 2);
+        assert_eq!(structure.graph.bond_images, [[0, 0, 0]; 2]);
+        assert!((graph.positions.as_ref().unwrap()[1][0] - 10.957).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parses_symmetry_operators() {
+        let operator = Operator::parse("1/2-X, y-x, +Z+0.25").unwrap();
+        assert_eq!(
+            operator.rotation,
+            [[-1.0, 0.0, 0.0], [-1.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+        );
+        assert_eq!(operator.translation, [0.5, 0.0, 0.25]);
+        assert_eq!(
+            operator.apply([0.1, 0.3, 0.5]),
+            [0.4, 0.19999999999999998, 0.75]
+        );
+        assert!(Operator::parse("x, y").is_none());
+        assert!(Operator::parse("x, y, w").is_none());
+        assert!(Operator::parse("x, 1/0, z").is_none());
+    }
+
+    #[test]
+    fn reports_malformed_files() {
+        let options = CifOptions::DEFAULT;
+        let cell = "data_x\n_cell_length_a 5\n_cell_length_b 5\n_cell_length_c 5\n\
+                    _cell_angle_alpha 90\n_cell_angle_beta 90\n_cell_angle_gamma 90\n";
+        let sites = "loop_\n_atom_site_label\n_atom_site_fract_x\n_atom_site_fract_y\n\
+                     _atom_site_fract_z\n";
+
+        assert!(matches!(
+            parse_cif("data_x\n_cell_length_a 5\n", &options),
+            Err(CifError::MissingItem {
+                tag: "_cell_length_b"
+            })
+        ));
+        assert!(matches!(
+            parse_cif(cell, &options),
+            Err(CifError::MissingItem {
+                tag: "_atom_site_fract_x"
+            })
+        ));
+        assert!(matches!(
+            parse_cif(&format!("{cell}{sites}C1 0 0\n"), &options),
+            Err(CifError::IncompleteLoop {
+                line: 8,
+                tags: 4,
+                values: 3
+            })
+        ));
+        assert!(matches!(
+            parse_cif(&format!("{cell}{sites}Q1 0 0 0\n"), &options),
+            Err(CifError::UnknownElement { line: 13, .. })
+        ));
+        assert!(matches!(
+            parse_cif(&format!("{cell}{sites}C1 0 ? 0\n"), &options),
+            Err(CifError::InvalidField {
+                line: 13,
+                tag: "_atom_site_fract_y",
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_cif(
+                &format!("{cell}_symmetry_equiv_pos_as_xyz 'x,y'\n{sites}C1 0 0 0\n"),
+                &options
+            ),
+            Err(CifError::InvalidSymmetryOperator { line: 8, .. })
+        ));
+        assert!(matches!(
+            parse_cif(&cell.replace("gamma 90", "gamma 180"), &options),
+            Err(CifError::DegenerateCell { .. })
+        ));
+        assert!(matches!(
+            parse_cif("data_x\n;\nunterminated\n", &options),
+            Err(CifError::UnterminatedTextField { line: 2 })
+        ));
+        assert!(matches!(
+            parse_cif("data_x\n_cell_length_a\nloop_\n", &options),
+            Err(CifError::MissingValue { line: 2, .. })
+        ));
+    }
+}
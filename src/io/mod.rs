@@ -0,0 +1,26 @@
+//! Readers and writers that connect common chemical file formats with
+//! [`MolecularGraph`](crate::MolecularGraph)s and typed topologies.
+//!
+//! Each format lives in its own submodule and reports failures through a format-specific error
+//! type from the crate's error module.
+
+use crate::core::properties::Element;
+
+pub mod cif;
+pub mod msi;
+pub mod pdb;
+pub mod sdf;
+pub mod smiles;
+pub mod xyz;
+
+/// Parses a conventionally capitalized element symbol together with the isotope it implies.
+///
+/// The hydrogen isotope symbols `D` and `T` read as hydrogen with mass numbers 2 and 3; every
+/// other symbol, including the dummy symbols `Du` and `Lp`, names the natural element.
+pub(crate) fn parse_element_symbol(symbol: &str) -> Option<(Element, Option<u16>)> {
+    match symbol {
+        "D" => Some((Element::H, Some(2))),
+        "T" => Some((Element::H, Some(3))),
+        _ => symbol.parse().ok().map(|element| (element, None)),
+    }
+}
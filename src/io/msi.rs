@@ -0,0 +1,917 @@
+//! Reading and writing Materials Studio (MSI/Biosym) `.car` and `.mdf` files.
+//!
+//! The `.car` archive stores coordinates, residue labels, a force-field atom-type column, and
+//! partial charges; the companion `.mdf` file stores connectivity. Together they are the format
+//! historically paired with DREIDING in Cerius2 and Materials Studio. [`MsiSystem`] keeps the
+//! legacy atom-type column next to the parsed graph so existing typings can be cross-checked
+//! against this crate with [`MsiSystem::type_mismatches`], and typed topologies can be written
+//! back out for migration.
+//!
+//! Periodic image suffixes on `.mdf` connections (`C1%0-10`) are kept as the bond's image flags
+//! in [`MsiSystem::bond_images`], so [`MsiSystem::to_periodic_graph`] can hand a framework to
+//! [`Typer::assign_periodic_topology`](crate::Typer::assign_periodic_topology), and `/1.5`
+//! connections are read as aromatic bonds. Deuterium (`D`) and tritium (`T`) in the
+//! element column are read as hydrogen with their isotope recorded.
+
+use crate::compat::collections::{HashMap, HashSet};
+use crate::compat::prelude::*;
+use crate::core::error::MsiError;
+use crate::core::graph::MolecularGraph;
+use crate::core::periodic::{ImageFlags, PeriodicMolecularGraph};
+use crate::core::properties::{Element, GraphBondOrder, TopologyBondOrder};
+use crate::core::topology::MolecularTopology;
+use crate::io::cif::CellParameters;
+use crate::io::parse_element_symbol;
+use core::fmt::Write;
+
+/// First-line header of a `.car` archive.
+const CAR_HEADER: &str = "!BIOSYM archive";
+
+/// First-line header of an `.mdf` file.
+const MDF_HEADER: &str = "!BIOSYM molecular_data";
+
+/// Minimum number of columns on a `.car` atom line.
+const CAR_ATOM_COLUMNS: usize = 9;
+
+/// One atom as described by a `.car` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MsiAtom {
+    /// Atom name, unique within its residue (e.g., `"C1"`).
+    pub name: String,
+    /// Residue (subunit) name.
+    pub residue_name: String,
+    /// Residue (subunit) number.
+    pub residue_number: u32,
+    /// Chemical element.
+    pub element: Element,
+    /// Force-field atom type column (e.g., `"C_3"` for DREIDING).
+    pub atom_type: String,
+    /// Partial charge in elementary charges.
+    pub charge: f64,
+    /// Cartesian coordinates in Å.
+    pub position: [f64; 3],
+    /// Index into [`MsiSystem::molecules`] of the molecule containing the atom.
+    pub molecule: usize,
+}
+
+impl MsiAtom {
+    /// Returns the `.mdf` reference of the atom (`{residue_name}_{residue_number}:{name}`).
+    pub fn reference(&self) -> String {
+        format!(
+            "{}_{}:{}",
+            self.residue_name, self.residue_number, self.name
+        )
+    }
+}
+
+/// A system read from, or destined for, a `.car`/`.mdf` pair.
+#[derive(Debug, Clone)]
+pub struct MsiSystem {
+    /// Title line of the `.car` file.
+    pub title: String,
+    /// Cell parameters `[a, b, c, α, β, γ]` (Å and degrees) for periodic systems.
+    pub cell: Option<[f64; 6]>,
+    /// Molecule names in file order.
+    pub molecules: Vec<String>,
+    /// Atoms in file order; `atoms[i]` corresponds to atom `i` of [`MsiSystem::graph`].
+    pub atoms: Vec<MsiAtom>,
+    /// Connectivity taken from the `.mdf` file (empty when only a `.car` file was read).
+    pub graph: MolecularGraph,
+    /// Image flags of every bond of [`MsiSystem::graph`], indexed by bond ID: the lattice
+    /// translation of the bond's second atom, zero unless the bond crosses a cell face.
+    pub bond_images: Vec<ImageFlags>,
+}
+
+/// An atom whose legacy type differs from the type assigned by the typer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeMismatch {
+    /// Atom index.
+    pub atom_id: usize,
+    /// Type found in the `.car` atom-type column.
+    pub legacy: String,
+    /// Type assigned by the typer.
+    pub assigned: String,
+}
+
+impl MsiSystem {
+    /// Builds a system from a typed topology, using its atom types for the type column.
+    ///
+    /// Atoms are named after their element and a per-molecule counter, placed in residue
+    /// `XXXX 1` of one molecule per connected component, and given zero coordinates and
+    /// charges. Resonant bonds are written as `/1.5` connections.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Typed topology to export.
+    pub fn from_topology(topology: &MolecularTopology) -> Self {
+        let molecule_of = topology.molecule_indices();
+        let molecule_count = molecule_of.iter().max().map_or(0, |&m| m + 1);
+        let mut counters: Vec<HashMap<Element, usize>> = vec![HashMap::new(); molecule_count];
+
+        let atoms = topology
+            .atoms
+            .iter()
+            .map(|atom| {
+                let molecule = molecule_of[atom.id];
+                let counter = counters[molecule].entry(atom.element).or_default();
+                *counter += 1;
+                MsiAtom {
+                    name: format!("{}{}", atom.element, counter),
+                    residue_name: "XXXX".to_string(),
+                    residue_number: 1,
+                    element: atom.element,
+                    atom_type: atom.atom_type.to_string(),
+                    charge: 0.0,
+                    position: [0.0; 3],
+                    molecule,
+                }
+            })
+            .collect();
+
+        let mut graph = MolecularGraph::new();
+        for atom in &topology.atoms {
+            graph.add_atom(atom.element);
+        }
+        let bond_images = vec![[0; 3]; topology.bonds.len()];
+        for bond in &topology.bonds {
+            let order = match bond.order {
+                TopologyBondOrder::Single => GraphBondOrder::Single,
+                TopologyBondOrder::Double => GraphBondOrder::Double,
+                TopologyBondOrder::Triple => GraphBondOrder::Triple,
+                TopologyBondOrder::Resonant => GraphBondOrder::Aromatic,
+            };
+            graph
+                .add_bond(bond.atom_ids.0, bond.atom_ids.1, order)
+                .expect("topology bonds reference existing atoms");
+        }
+
+        Self {
+            title: "dreid-typer generated CAR file".to_string(),
+            cell: None,
+            molecules: (1..=molecule_count).map(|n| format!("MOL{n}")).collect(),
+            atoms,
+            graph,
+            bond_images,
+        }
+    }
+
+    /// Returns the atoms and bonds as one unit cell with periodic bonds.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MsiError::MissingCell`] if the `.car` file declared no cell, or
+    /// [`MsiError::InvalidCell`] if its parameters do not span a cell of positive volume.
+    pub fn to_periodic_graph(&self) -> Result<PeriodicMolecularGraph, MsiError> {
+        let [a, b, c, alpha, beta, gamma] = self.cell.ok_or(MsiError::MissingCell)?;
+        let parameters = CellParameters {
+            lengths: [a, b, c],
+            angles: [alpha, beta, gamma],
+        };
+        let vectors = parameters
+            .vectors()
+            .map_err(|source| MsiError::InvalidCell { source })?;
+        Ok(PeriodicMolecularGraph {
+            graph: self.graph.clone(),
+            cell: vectors,
+            bond_images: self.bond_images.clone(),
+        })
+    }
+
+    /// Overwrites the atom-type column with the types of a topology typed from this system.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Topology whose atoms correspond one-to-one with [`MsiSystem::atoms`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MsiError::AtomCountMismatch`] if the atom counts differ.
+    pub fn apply_types(&mut self, topology: &MolecularTopology) -> Result<(), MsiError> {
+        self.check_atom_count(topology)?;
+        for (atom, typed) in self.atoms.iter_mut().zip(&topology.atoms) {
+            atom.atom_type = typed.atom_type.to_string();
+        }
+        Ok(())
+    }
+
+    /// Lists atoms whose legacy type column disagrees with a topology typed from this system.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Topology whose atoms correspond one-to-one with [`MsiSystem::atoms`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MsiError::AtomCountMismatch`] if the atom counts differ.
+    pub fn type_mismatches(
+        &self,
+        topology: &MolecularTopology,
+    ) -> Result<Vec<TypeMismatch>, MsiError> {
+        self.check_atom_count(topology)?;
+        Ok(self
+            .atoms
+            .iter()
+            .zip(&topology.atoms)
+            .enumerate()
+            .filter(|(_, (legacy, typed))| legacy.atom_type != typed.atom_type)
+            .map(|(atom_id, (legacy, typed))| TypeMismatch {
+                atom_id,
+                legacy: legacy.atom_type.clone(),
+                assigned: typed.atom_type.to_string(),
+            })
+            .collect())
+    }
+
+    /// Serializes the system as a `.car` archive.
+    ///
+    /// Atoms are written grouped by molecule, so systems whose molecules are interleaved are
+    /// read back in a different order.
+    pub fn to_car(&self) -> String {
+        let mut out = String::new();
+        out.push_str("!BIOSYM archive 3\n");
+        match self.cell {
+            Some(_) => out.push_str("PBC=ON\n"),
+            None => out.push_str("PBC=OFF\n"),
+        }
+        let _ = writeln!(out, "{}", self.title);
+        out.push_str("!DATE\n");
+        if let Some([a, b, c, alpha, beta, gamma]) = self.cell {
+            let _ = writeln!(
+                out,
+                "PBC {a:>9.4} {b:>9.4} {c:>9.4} {alpha:>9.4} {beta:>9.4} {gamma:>9.4} (P1)"
+            );
+        }
+
+        for members in self.atoms_by_molecule() {
+            for atom in members.iter().map(|&id| &self.atoms[id]) {
+                let [x, y, z] = atom.position;
+                let _ = writeln!(
+                    out,
+                    "{:<5} {:>14.9} {:>14.9} {:>14.9} {:<4} {:<6} {:<7} {:<2} {:>6.3}",
+                    atom.name,
+                    x,
+                    y,
+                    z,
+                    atom.residue_name,
+                    atom.residue_number,
+                    atom.atom_type,
+                    atom.element,
+                    atom.charge
+                );
+            }
+            out.push_str("end\n");
+        }
+        out.push_str("end\n");
+        out
+    }
+
+    /// Serializes the system's connectivity as an `.mdf` file.
+    pub fn to_mdf(&self) -> String {
+        let mut neighbors: Vec<Vec<(usize, GraphBondOrder, ImageFlags)>> =
+            vec![Vec::new(); self.atoms.len()];
+        for (bond, image) in self.graph.bonds.iter().zip(self.images()) {
+            let (a, b) = bond.atom_ids;
+            neighbors[a].push((b, bond.order, image));
+            neighbors[b].push((a, bond.order, image.map(|axis| -axis)));
+        }
+
+        let mut out = String::new();
+        out.push_str("!BIOSYM molecular_data 4\n\n#topology\n\n");
+        for (index, column) in [
+            "element",
+            "atom_type",
+            "charge_group",
+            "isotope",
+            "formal_charge",
+            "charge",
+            "switching_atom",
+            "oop_flag",
+            "chirality_flag",
+            "occupancy",
+            "xray_temp_factor",
+            "connections",
+        ]
+        .iter()
+        .enumerate()
+        {
+            let _ = writeln!(out, "@column {} {column}", index + 1);
+        }
+
+        for (molecule, members) in self.atoms_by_molecule().into_iter().enumerate() {
+            let _ = write!(out, "\n@molecule {}\n\n", self.molecules[molecule]);
+            for id in members {
+                let atom = &self.atoms[id];
+                let _ = write!(
+                    out,
+                    "{:<20} {:<2} {:<7} ?     0  0 {:>9.4} 0 0 8 1.0000  0.0000",
+                    atom.reference(),
+                    atom.element,
+                    atom.atom_type,
+                    atom.charge
+                );
+                for &(neighbor, order, image) in &neighbors[id] {
+                    let other = &self.atoms[neighbor];
+                    if other.residue_name == atom.residue_name
+                        && other.residue_number == atom.residue_number
+                    {
+                        let _ = write!(out, " {}", other.name);
+                    } else {
+                        let _ = write!(out, " {}", other.reference());
+                    }
+                    if image != [0; 3] {
+                        let [a, b, c] = image;
+                        let _ = write!(out, "%{a}{b}{c}");
+                    }
+                    match order {
+                        GraphBondOrder::Single => {}
+                        GraphBondOrder::Double => out.push_str("/2.0"),
+                        GraphBondOrder::Triple => out.push_str("/3.0"),
+                        GraphBondOrder::Aromatic => out.push_str("/1.5"),
+                    }
+                }
+                out.push('\n');
+            }
+        }
+        out.push_str("\n!\n#end\n");
+        out
+    }
+
+    /// Returns the image flags of every bond, treating missing flags as zero.
+    fn images(&self) -> impl Iterator<Item = ImageFlags> + '_ {
+        (0..self.graph.bonds.len()).map(|id| self.bond_images.get(id).copied().unwrap_or([0; 3]))
+    }
+
+    /// Groups atom indices by molecule, preserving order within each molecule.
+    fn atoms_by_molecule(&self) -> Vec<Vec<usize>> {
+        let mut groups = vec![Vec::new(); self.molecules.len()];
+        for (id, atom) in self.atoms.iter().enumerate() {
+            groups[atom.molecule].push(id);
+        }
+        groups
+    }
+
+    /// Ensures `topology` has exactly one atom per atom of the system.
+    fn check_atom_count(&self, topology: &MolecularTopology) -> Result<(), MsiError> {
+        if topology.atoms.len() != self.atoms.len() {
+            return Err(MsiError::AtomCountMismatch {
+                expected: self.atoms.len(),
+                found: topology.atoms.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Parses a `.car` archive into a system without connectivity.
+///
+/// # Arguments
+///
+/// * `text` - Contents of the `.car` file.
+///
+/// # Returns
+///
+/// An [`MsiSystem`] whose graph contains the atoms but no bonds.
+///
+/// # Errors
+///
+/// Returns an [`MsiError`] describing the first malformed line encountered.
+pub fn parse_car(text: &str) -> Result<MsiSystem, MsiError> {
+    let mut lines = text.lines().enumerate();
+    match lines.next() {
+        Some((_, header)) if header.trim_start().starts_with(CAR_HEADER) => {}
+        _ => {
+            return Err(MsiError::MissingHeader {
+                expected: CAR_HEADER,
+            });
+        }
+    }
+
+    let periodic = lines
+        .next()
+        .is_some_and(|(_, line)| line.trim().eq_ignore_ascii_case("PBC=ON"));
+    let title = lines
+        .next()
+        .map(|(_, line)| line.trim().to_string())
+        .unwrap_or_default();
+
+    let mut system = MsiSystem {
+        title,
+        cell: None,
+        molecules: Vec::new(),
+        atoms: Vec::new(),
+        graph: MolecularGraph::new(),
+        bond_images: Vec::new(),
+    };
+    let mut molecule_open = false;
+
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('!') {
+            continue;
+        }
+        if trimmed == "end" {
+            if !molecule_open {
+                break;
+            }
+            molecule_open = false;
+            continue;
+        }
+        if periodic && system.cell.is_none() && trimmed.starts_with("PBC") {
+            system.cell = Some(parse_cell(trimmed, line_number)?);
+            continue;
+        }
+
+        let columns: Vec<&str> = trimmed.split_whitespace().collect();
+        if columns.len() < CAR_ATOM_COLUMNS {
+            return Err(MsiError::MissingColumns {
+                line: line_number,
+                expected: CAR_ATOM_COLUMNS,
+                found: columns.len(),
+            });
+        }
+        if !molecule_open {
+            system
+                .molecules
+                .push(format!("MOL{}", system.molecules.len() + 1));
+            molecule_open = true;
+        }
+
+        let (element, isotope) =
+            parse_element_symbol(columns[7]).ok_or_else(|| MsiError::UnknownElement {
+                line: line_number,
+                symbol: columns[7].to_string(),
+            })?;
+        let id = system.graph.add_atom(element);
+        system.graph.atoms[id].isotope = isotope;
+        system.atoms.push(MsiAtom {
+            name: columns[0].to_string(),
+            residue_name: columns[4].to_string(),
+            residue_number: parse_column(columns[5], line_number, "residue number")?,
+            element,
+            atom_type: columns[6].to_string(),
+            charge: parse_column(columns[8], line_number, "charge")?,
+            position: [
+                parse_column(columns[1], line_number, "x coordinate")?,
+                parse_column(columns[2], line_number, "y coordinate")?,
+                parse_column(columns[3], line_number, "z coordinate")?,
+            ],
+            molecule: system.molecules.len() - 1,
+        });
+    }
+
+    Ok(system)
+}
+
+/// Parses a `.car`/`.mdf` pair into a system with coordinates and connectivity.
+///
+/// Atoms are matched between the files by molecule and `{residue_name}_{residue_number}:{name}`
+/// reference; the molecule names declared in the `.mdf` file replace the generated ones. A
+/// connection to a periodic image (`C1%0-10`) becomes a bond to the in-cell atom whose image
+/// flags are recorded in [`MsiSystem::bond_images`].
+///
+/// # Arguments
+///
+/// * `car` - Contents of the `.car` file.
+/// * `mdf` - Contents of the `.mdf` file.
+///
+/// # Errors
+///
+/// Returns an [`MsiError`] if either file is malformed or the `.mdf` file references atoms that
+/// the `.car` file does not define.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::assign_topology;
+/// use dreid_typer::io::msi::parse_car_mdf;
+///
+/// let car = "\
+/// !BIOSYM archive 3
+/// PBC=OFF
+/// water
+/// !DATE
+/// O1    0.000 0.000 0.000 XXXX 1 O_3  O -0.820
+/// H1    0.957 0.000 0.000 XXXX 1 H_HB H  0.410
+/// H2   -0.240 0.927 0.000 XXXX 1 H_HB H  0.410
+/// end
+/// end
+/// ";
+/// let mdf = "\
+/// !BIOSYM molecular_data 4
+/// #topology
+/// @column 1 element
+/// @column 2 atom_type
+/// @column 3 connections
+/// @molecule water
+/// XXXX_1:O1 O O_3  H1 H2
+/// XXXX_1:H1 H H_HB O1
+/// XXXX_1:H2 H H_HB O1
+/// #end
+/// ";
+///
+/// let system = parse_car_mdf(car, mdf).unwrap();
+/// let topology = assign_topology(&system.graph).unwrap();
+/// assert!(system.type_mismatches(&topology).unwrap().is_empty());
+/// ```
+pub fn parse_car_mdf(car: &str, mdf: &str) -> Result<MsiSystem, MsiError> {
+    let mut system = parse_car(car)?;
+
+    let mut lines = mdf.lines().enumerate();
+    match lines.next() {
+        Some((_, header)) if header.trim_start().starts_with(MDF_HEADER) => {}
+        _ => {
+            return Err(MsiError::MissingHeader {
+                expected: MDF_HEADER,
+            });
+        }
+    }
+
+    let lookup: HashMap<(usize, String), usize> = system
+        .atoms
+        .iter()
+        .enumerate()
+        .map(|(id, atom)| ((atom.molecule, atom.reference()), id))
+        .collect();
+
+    let mut connections_column = None;
+    let mut in_topology = false;
+    let mut molecule: Option<usize> = None;
+    let mut pending: Vec<(usize, usize, Vec<String>)> = Vec::new();
+
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('!') {
+            continue;
+        }
+        if let Some(section) = trimmed.strip_prefix('#') {
+            in_topology = section.trim() == "topology";
+            continue;
+        }
+        if !in_topology {
+            continue;
+        }
+        if let Some(column) = trimmed.strip_prefix("@column") {
+            let mut parts = column.split_whitespace();
+            if let (Some(position), Some("connections")) = (parts.next(), parts.next()) {
+                connections_column = Some(parse_column(position, line_number, "column index")?);
+            }
+            continue;
+        }
+        if let Some(name) = trimmed.strip_prefix("@molecule") {
+            let next = molecule.map_or(0, |m| m + 1);
+            if let Some(slot) = system.molecules.get_mut(next) {
+                *slot = name.trim().to_string();
+            }
+            molecule = Some(next);
+            continue;
+        }
+        if trimmed.starts_with('@') {
+            continue;
+        }
+
+        let columns: Vec<&str> = trimmed.split_whitespace().collect();
+        let reference = columns[0];
+        let atom_id = molecule
+            .and_then(|m| lookup.get(&(m, reference.to_string())))
+            .copied()
+            .ok_or_else(|| MsiError::UnknownAtom {
+                line: line_number,
+                reference: reference.to_string(),
+            })?;
+        let start = connections_column.unwrap_or(columns.len());
+        let connections = columns
+            .get(start..)
+            .unwrap_or_default()
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        pending.push((line_number, atom_id, connections));
+    }
+
+    let mut seen = HashSet::new();
+    for (line_number, atom_id, connections) in pending {
+        let atom = &system.atoms[atom_id];
+        let residue = format!("{}_{}", atom.residue_name, atom.residue_number);
+        for connection in connections {
+            let (target, image, order) = parse_connection(&connection, line_number)?;
+            let reference = if target.contains(':') {
+                target.to_string()
+            } else {
+                format!("{residue}:{target}")
+            };
+            let neighbor = lookup
+                .get(&(atom.molecule, reference))
+                .copied()
+                .ok_or_else(|| MsiError::UnknownAtom {
+                    line: line_number,
+                    reference: connection.clone(),
+                })?;
+
+            // Both ends list the bond, each with the image of the other.
+            let key = if atom_id <= neighbor {
+                (atom_id, neighbor, image)
+            } else {
+                (neighbor, atom_id, image.map(|axis| -axis))
+            };
+            if !seen.insert(key) {
+                continue;
+            }
+            system
+                .graph
+                .add_bond(key.0, key.1, order)
+                .map_err(|source| MsiError::InvalidBond {
+                    line: line_number,
+                    source,
+                })?;
+            system.bond_images.push(key.2);
+        }
+    }
+
+    Ok(system)
+}
+
+/// Splits an `.mdf` connection into its target atom, periodic image, and bond order.
+fn parse_connection(
+    connection: &str,
+    line_number: usize,
+) -> Result<(&str, ImageFlags, GraphBondOrder), MsiError> {
+    let (target, order) = match connection.split_once('/') {
+        Some((target, order)) => (target, Some(order)),
+        None => (connection, None),
+    };
+    let (target, image) = match target.split_once('%') {
+        Some((atom, image)) => (atom, parse_image(image, line_number)?),
+        None => (target, [0; 3]),
+    };
+
+    let order = match order.map(str::parse::<f64>) {
+        None | Some(Ok(1.0)) => GraphBondOrder::Single,
+        Some(Ok(1.5)) => GraphBondOrder::Aromatic,
+        Some(Ok(2.0)) => GraphBondOrder::Double,
+        Some(Ok(3.0)) => GraphBondOrder::Triple,
+        Some(_) => {
+            return Err(MsiError::UnsupportedBondOrder {
+                line: line_number,
+                order: order.unwrap_or_default().to_string(),
+            });
+        }
+    };
+    Ok((target, image, order))
+}
+
+/// Parses the image suffix of a connection, one signed digit per cell vector (`0-10`).
+fn parse_image(raw: &str, line_number: usize) -> Result<ImageFlags, MsiError> {
+    let invalid = || MsiError::InvalidField {
+        line: line_number,
+        field: "periodic image",
+        value: raw.to_string(),
+    };
+    let mut chars = raw.chars();
+    let mut image = [0; 3];
+    for slot in &mut image {
+        let mut digit = chars.next().ok_or_else(invalid)?;
+        let negative = digit == '-';
+        if negative {
+            digit = chars.next().ok_or_else(invalid)?;
+        }
+        let value = digit.to_digit(10).ok_or_else(invalid)? as i32;
+        *slot = if negative { -value } else { value };
+    }
+    if chars.next().is_some() {
+        return Err(invalid());
+    }
+    Ok(image)
+}
+
+/// Parses a `PBC a b c alpha beta gamma (group)` cell line.
+fn parse_cell(line: &str, line_number: usize) -> Result<[f64; 6], MsiError> {
+    let columns: Vec<&str> = line.split_whitespace().skip(1).take(6).collect();
+    if columns.len() < 6 {
+        return Err(MsiError::MissingColumns {
+            line: line_number,
+            expected: 7,
+            found: columns.len() + 1,
+        });
+    }
+    let mut cell = [0.0; 6];
+    for (slot, column) in cell.iter_mut().zip(columns) {
+        *slot = parse_column(column, line_number, "cell parameter")?;
+    }
+    Ok(cell)
+}
+
+/// Parses a whitespace-delimited column.
+fn parse_column<T: core::str::FromStr>(
+    raw: &str,
+    line_number: usize,
+    name: &'static str,
+) -> Result<T, MsiError> {
+    raw.parse().map_err(|_| MsiError::InvalidField {
+        line: line_number,
+        field: name,
+        value: raw.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::Typer;
+
+    const FORMALDEHYDE_CAR: &str = "\
+!BIOSYM archive 3
+PBC=ON
+formaldehyde in a box
+!DATE Thu Jan 01 00:00:00 2004
+PBC   10.0000   10.0000   12.5000   90.0000   90.0000  120.0000 (P1)
+C1       0.000000000    0.000000000    0.000000000 FORM 1      C_2     C   0.000
+O1       1.200000000    0.000000000    0.000000000 FORM 1      O_2     O   0.000
+H1      -0.550000000    0.950000000    0.000000000 FORM 1      H_      H   0.000
+H2      -0.550000000   -0.950000000    0.000000000 FORM 1      H_      H   0.000
+end
+Na1      5.000000000    5.000000000    5.000000000 ION  1      Na      Na  1.000
+end
+end
+";
+
+    const FORMALDEHYDE_MDF: &str = "\
+!BIOSYM molecular_data 4
+
+!Date: Thu Jan 01 00:00:00 2004   Materials Studio Generated MDF file
+
+#topology
+
+@column 1 element
+@column 2 atom_type
+@column 3 charge_group
+@column 4 isotope
+@column 5 formal_charge
+@column 6 charge
+@column 7 switching_atom
+@column 8 oop_flag
+@column 9 chirality_flag
+@column 10 occupancy
+@column 11 xray_temp_factor
+@column 12 connections
+
+@molecule formaldehyde
+
+FORM_1:C1            C  C_2      ?     0  0    0.0000 0 0 8 1.0000  0.0000 O1/2.0 H1 H2
+FORM_1:O1            O  O_2      ?     0  0    0.0000 0 0 8 1.0000  0.0000 C1/2.0
+FORM_1:H1            H  H_       ?     0  0    0.0000 0 0 8 1.0000  0.0000 C1
+FORM_1:H2            H  H_       ?     0  0    0.0000 0 0 8 1.0000  0.0000 C1
+
+@molecule sodium
+
+ION_1:Na1            Na Na       ?     0  0    1.0000 0 0 8 1.0000  0.0000
+
+!
+#symmetry
+@periodicity 1 xyz
+@group (P1)
+
+#end
+";
+
+    #[test]
+    fn parses_car_mdf_pair_with_cell_and_bond_orders() {
+        let system = parse_car_mdf(FORMALDEHYDE_CAR, FORMALDEHYDE_MDF).unwrap();
+        assert_eq!(system.title, "formaldehyde in a box");
+        assert_eq!(system.cell, Some([10.0, 10.0, 12.5, 90.0, 90.0, 120.0]));
+        assert_eq!(system.molecules, ["formaldehyde", "sodium"]);
+        assert_eq!(system.atoms.len(), 5);
+        assert_eq!(system.atoms[4].molecule, 1);
+        assert_eq!(system.atoms[4].charge, 1.0);
+        assert_eq!(system.atoms[1].position, [1.2, 0.0, 0.0]);
+
+        assert_eq!(system.graph.bonds.len(), 3);
+        assert_eq!(system.graph.bonds[0].atom_ids, (0, 1));
+        assert_eq!(system.graph.bonds[0].order, GraphBondOrder::Double);
+    }
+
+    #[test]
+    fn legacy_types_can_be_cross_checked_and_replaced() {
+        let mut system = parse_car_mdf(FORMALDEHYDE_CAR, FORMALDEHYDE_MDF).unwrap();
+        system.atoms[0].atom_type = "C_3".to_string();
+
+        let topology = Typer::new().assign_topology(&system.graph).unwrap();
+        let mismatches = system.type_mismatches(&topology).unwrap();
+        assert_eq!(
+            mismatches,
+            [TypeMismatch {
+                atom_id: 0,
+                legacy: "C_3".to_string(),
+                assigned: "C_2".to_string(),
+            }]
+        );
+
+        system.apply_types(&topology).unwrap();
+        assert!(system.type_mismatches(&topology).unwrap().is_empty());
+    }
+
+    #[test]
+    fn written_files_round_trip() {
+        let system = parse_car_mdf(FORMALDEHYDE_CAR, FORMALDEHYDE_MDF).unwrap();
+        let reread = parse_car_mdf(&system.to_car(), &system.to_mdf()).unwrap();
+
+        assert_eq!(reread.cell, system.cell);
+        assert_eq!(reread.molecules, system.molecules);
+        assert_eq!(reread.atoms, system.atoms);
+        let bonds = |s: &MsiSystem| -> Vec<_> {
+            s.graph
+                .bonds
+                .iter()
+                .map(|b| (b.atom_ids, b.order))
+                .collect()
+        };
+        assert_eq!(bonds(&reread), bonds(&system));
+    }
+
+    #[test]
+    fn topology_export_names_atoms_per_molecule() {
+        let system = parse_car_mdf(FORMALDEHYDE_CAR, FORMALDEHYDE_MDF).unwrap();
+        let topology = Typer::new().assign_topology(&system.graph).unwrap();
+        let exported = MsiSystem::from_topology(&topology);
+
+        assert_eq!(exported.molecules.len(), 2);
+        assert_eq!(exported.atoms[2].name, "H1");
+        assert_eq!(exported.atoms[4].name, "Na1");
+        assert_eq!(exported.atoms[4].molecule, 1);
+
+        let reread = parse_car_mdf(&exported.to_car(), &exported.to_mdf()).unwrap();
+        assert!(reread.type_mismatches(&topology).unwrap().is_empty());
+        assert_eq!(reread.graph.bonds.len(), 3);
+    }
+
+    #[test]
+    fn image_suffixes_become_periodic_bonds() {
+        let car = "\
+!BIOSYM archive 3
+PBC=ON
+polyethylene
+!DATE
+PBC    2.5500   10.0000   10.0000   90.0000   90.0000   90.0000 (P1)
+C1    0.000 0.000 0.000 PE 1 C_3 C 0.000
+C2    1.275 0.900 0.000 PE 1 C_3 C 0.000
+end
+end
+";
+        let mdf = "\
+!BIOSYM molecular_data 4
+#topology
+@column 1 element
+@column 2 atom_type
+@column 3 connections
+@molecule polyethylene
+PE_1:C1 C C_3 C2 C2%-100
+PE_1:C2 C C_3 C1 C1%100
+#end
+";
+        let system = parse_car_mdf(car, mdf).unwrap();
+        assert_eq!(system.graph.bonds.len(), 2);
+        assert_eq!(system.bond_images, [[0, 0, 0], [-1, 0, 0]]);
+
+        let cell = system.to_periodic_graph().unwrap();
+        assert_eq!(cell.cell[0], [2.55, 0.0, 0.0]);
+        let topology = Typer::new().assign_periodic_topology(&cell).unwrap();
+        assert!(topology.rings.is_empty());
+        assert!(topology.atoms.iter().all(|atom| atom.atom_type == "C_3"));
+
+        let reread = parse_car_mdf(&system.to_car(), &system.to_mdf()).unwrap();
+        assert_eq!(reread.bond_images, system.bond_images);
+
+        let bad = mdf.replace("C1%100", "C1%1x0");
+        assert!(matches!(
+            parse_car_mdf(car, &bad),
+            Err(MsiError::InvalidField {
+                field: "periodic image",
+                ..
+            })
+        ));
+        let mut molecule = system;
+        molecule.cell = None;
+        assert!(matches!(
+            molecule.to_periodic_graph(),
+            Err(MsiError::MissingCell)
+        ));
+    }
+
+    #[test]
+    fn unknown_connection_targets_are_reported() {
+        let mdf = FORMALDEHYDE_MDF.replace("C1/2.0\n", "C9/2.0\n");
+        let err = parse_car_mdf(FORMALDEHYDE_CAR, &mdf).unwrap_err();
+        assert!(matches!(err, MsiError::UnknownAtom { reference, .. } if reference == "C9/2.0"));
+
+        let mdf = FORMALDEHYDE_MDF.replace("O1/2.0", "O1/2.5");
+        assert!(matches!(
+            parse_car_mdf(FORMALDEHYDE_CAR, &mdf),
+            Err(MsiError::UnsupportedBondOrder { .. })
+        ));
+        assert!(matches!(
+            parse_car("garbage"),
+            Err(MsiError::MissingHeader { .. })
+        ));
+    }
+}
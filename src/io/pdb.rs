@@ -0,0 +1,399 @@
+//! Reading of Protein Data Bank (PDB) files.
+//!
+//! Atoms come from `ATOM` and `HETATM` records of the first model; connectivity comes from
+//! `CONECT` records only, since standard residues in deposited structures carry no explicit
+//! bonds and inferring them would require residue templates. The element is read from columns
+//! 77–78 and, when those are blank, derived from the atom name using the PDB convention that
+//! two-letter symbols start in column 13. Deuterium (`D`) and tritium (`T`) are read as hydrogen
+//! with their isotope recorded.
+//!
+//! PDB files have no bond-order field. Following the convention of common writers, a partner
+//! listed two or three times in the `CONECT` records of an atom makes a double or triple bond;
+//! every other bond is single.
+//!
+//! Atom names and residues are attached to the graph as [`AtomMetadata`], so they reappear on
+//! the typed topology atoms.
+
+use crate::compat::collections::{HashMap, HashSet};
+use crate::compat::prelude::*;
+use crate::core::error::PdbError;
+use crate::core::graph::MolecularGraph;
+use crate::core::metadata::{AtomMetadata, ResidueId};
+use crate::core::properties::{Element, GraphBondOrder};
+use crate::io::parse_element_symbol;
+
+/// One atom as described by an `ATOM` or `HETATM` record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdbAtom {
+    /// Atom serial number.
+    pub serial: u32,
+    /// Atom name (e.g., `"CA"`).
+    pub name: String,
+    /// Residue name (e.g., `"ALA"`).
+    pub residue_name: String,
+    /// Chain identifier, or a space if blank.
+    pub chain_id: char,
+    /// Residue sequence number.
+    pub residue_number: i32,
+    /// Chemical element.
+    pub element: Element,
+    /// Mass number of the hydrogen isotope written as `D` or `T`, or `None`.
+    pub isotope: Option<u16>,
+    /// Cartesian coordinates in Å.
+    pub position: [f64; 3],
+}
+
+/// A structure read from a PDB file.
+#[derive(Debug, Clone)]
+pub struct PdbStructure {
+    /// Atoms in file order; `atoms[i]` corresponds to atom `i` of [`PdbStructure::graph`].
+    pub atoms: Vec<PdbAtom>,
+    /// Connectivity taken from the `CONECT` records, carrying the atom coordinates as
+    /// [`MolecularGraph::positions`] and the atom names and residues as
+    /// [`AtomNode::metadata`](crate::AtomNode::metadata).
+    pub graph: MolecularGraph,
+}
+
+impl PdbStructure {
+    /// Returns the coordinates of every atom, aligned with the graph.
+    pub fn positions(&self) -> Vec<[f64; 3]> {
+        self.atoms.iter().map(|atom| atom.position).collect()
+    }
+}
+
+/// Parses the first model of a PDB file.
+///
+/// # Arguments
+///
+/// * `text` - Contents of the PDB file.
+///
+/// # Returns
+///
+/// The atoms of the first model together with the graph built from `CONECT` records; bonds to
+/// atoms outside the first model are skipped.
+///
+/// # Errors
+///
+/// Returns a [`PdbError`] describing the first malformed record encountered.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::pdb::parse_pdb;
+/// use dreid_typer::{assign_topology, Element};
+///
+/// let pdb = "\
+/// HETATM    1  C1  FOR A   1       0.000   0.000   0.000  1.00  0.00           C
+/// HETATM    2  O1  FOR A   1       1.200   0.000   0.000  1.00  0.00           O
+/// HETATM    3  H1  FOR A   1      -0.500   0.900   0.000  1.00  0.00           H
+/// HETATM    4  H2  FOR A   1      -0.500  -0.900   0.000  1.00  0.00           H
+/// CONECT    1    2    2    3    4
+/// CONECT    2    1    1
+/// END
+/// ";
+/// let structure = parse_pdb(pdb).unwrap();
+/// assert_eq!(structure.atoms[1].element, Element::O);
+/// assert_eq!(structure.positions()[1], [1.2, 0.0, 0.0]);
+///
+/// let topology = assign_topology(&structure.graph).unwrap();
+/// assert_eq!(topology.atoms[0].atom_type, "C_2");
+/// assert_eq!(topology.atoms[1].atom_type, "O_2");
+/// assert_eq!(topology.atoms[1].metadata.name.as_deref(), Some("O1"));
+/// ```
+pub fn parse_pdb(text: &str) -> Result<PdbStructure, PdbError> {
+    let mut atoms = Vec::new();
+    let mut index_of: HashMap<u32, usize> = HashMap::new();
+    let mut listings: Vec<((usize, usize), usize)> = Vec::new();
+    let mut model_ended = false;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let record = field(line, 0, 6);
+        match record {
+            "ATOM" | "HETATM" if !model_ended => {
+                let atom = parse_atom(line, line_number)?;
+                index_of.insert(atom.serial, atoms.len());
+                atoms.push(atom);
+            }
+            "ENDMDL" => model_ended = true,
+            "CONECT" => {
+                let serial = |start: usize| -> Result<Option<u32>, PdbError> {
+                    let raw = field(line, start, start + 5);
+                    if raw.is_empty() {
+                        return Ok(None);
+                    }
+                    raw.parse().map(Some).map_err(|_| PdbError::InvalidField {
+                        line: line_number,
+                        field: "serial number",
+                        value: raw.to_string(),
+                    })
+                };
+                let lookup = |serial: u32| {
+                    index_of.get(&serial).copied().ok_or(PdbError::UnknownAtom {
+                        line: line_number,
+                        serial,
+                    })
+                };
+                let Some(origin) = serial(6)? else {
+                    continue;
+                };
+                if model_ended && !index_of.contains_key(&origin) {
+                    continue;
+                }
+                let origin = lookup(origin)?;
+                for start in [11, 16, 21, 26] {
+                    if let Some(partner) = serial(start)? {
+                        let partner = lookup(partner)?;
+                        listings.push(((origin, partner), line_number));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut graph = MolecularGraph::new();
+    for atom in &atoms {
+        let id = graph.add_atom(atom.element);
+        graph.atoms[id].isotope = atom.isotope;
+        graph.atoms[id].metadata = AtomMetadata::named(&atom.name).with_residue(ResidueId::new(
+            &atom.residue_name,
+            atom.chain_id,
+            atom.residue_number,
+        ));
+    }
+    graph.positions = Some(atoms.iter().map(|atom| atom.position).collect());
+
+    // Count how often each atom lists each partner; the larger count of the two directions
+    // gives the bond order.
+    let mut counts: HashMap<(usize, usize), usize> = HashMap::new();
+    for &(pair, _) in &listings {
+        *counts.entry(pair).or_default() += 1;
+    }
+    let mut added: HashSet<(usize, usize)> = HashSet::new();
+    for &((a, b), line) in &listings {
+        let key = (a.min(b), a.max(b));
+        if !added.insert(key) {
+            continue;
+        }
+        let multiplicity = counts
+            .get(&(a, b))
+            .copied()
+            .unwrap_or(0)
+            .max(counts.get(&(b, a)).copied().unwrap_or(0));
+        let order = match multiplicity {
+            0 | 1 => GraphBondOrder::Single,
+            2 => GraphBondOrder::Double,
+            _ => GraphBondOrder::Triple,
+        };
+        graph
+            .add_bond(a, b, order)
+            .map_err(|source| PdbError::InvalidBond { line, source })?;
+    }
+
+    Ok(PdbStructure { atoms, graph })
+}
+
+/// Parses an `ATOM` or `HETATM` record.
+fn parse_atom(line: &str, line_number: usize) -> Result<PdbAtom, PdbError> {
+    let number = |start: usize, end: usize, name: &'static str| {
+        let raw = field(line, start, end);
+        raw.parse::<f64>().map_err(|_| PdbError::InvalidField {
+            line: line_number,
+            field: name,
+            value: raw.to_string(),
+        })
+    };
+
+    let raw_serial = field(line, 6, 11);
+    let serial = raw_serial.parse().map_err(|_| PdbError::InvalidField {
+        line: line_number,
+        field: "serial number",
+        value: raw_serial.to_string(),
+    })?;
+    let raw_residue = field(line, 22, 26);
+    let residue_number = if raw_residue.is_empty() {
+        0
+    } else {
+        raw_residue.parse().map_err(|_| PdbError::InvalidField {
+            line: line_number,
+            field: "residue number",
+            value: raw_residue.to_string(),
+        })?
+    };
+
+    let name = field(line, 12, 16).to_string();
+    let (element, isotope) = parse_element(line).ok_or_else(|| PdbError::UnknownElement {
+        line: line_number,
+        symbol: match field(line, 76, 78) {
+            "" => name.clone(),
+            symbol => symbol.to_string(),
+        },
+    })?;
+
+    Ok(PdbAtom {
+        serial,
+        name,
+        residue_name: field(line, 17, 20).to_string(),
+        chain_id: line
+            .get(21..22)
+            .and_then(|c| c.chars().next())
+            .unwrap_or(' '),
+        residue_number,
+        element,
+        isotope,
+        position: [
+            number(30, 38, "x coordinate")?,
+            number(38, 46, "y coordinate")?,
+            number(46, 54, "z coordinate")?,
+        ],
+    })
+}
+
+/// Determines the element and isotope from columns 77–78, falling back to the atom name.
+fn parse_element(line: &str) -> Option<(Element, Option<u16>)> {
+    let symbol = field(line, 76, 78);
+    if !symbol.is_empty() {
+        return parse_element_symbol(&normalize_symbol(symbol));
+    }
+
+    let name = line.get(12..16).unwrap_or("");
+    let letters: String = name
+        .chars()
+        .skip_while(|c| !c.is_ascii_alphabetic())
+        .take_while(char::is_ascii_alphabetic)
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && letters.len() >= 2
+        && let Ok(element) = normalize_symbol(&letters[..2]).parse()
+    {
+        return Some((element, None));
+    }
+    parse_element_symbol(&normalize_symbol(letters.get(..1)?))
+}
+
+/// Converts an element symbol of any case to its conventional capitalization.
+fn normalize_symbol(symbol: &str) -> String {
+    let mut chars = symbol.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+/// Returns the trimmed contents of the fixed-width column range `[start, end)`.
+fn field(line: &str, start: usize, end: usize) -> &str {
+    let end = end.min(line.len());
+    line.get(start.min(end)..end).unwrap_or("").trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACETONITRILE_AND_ION: &str = "\
+MODEL        1
+HETATM    1  C1  ACN     1       0.000   0.000   0.000  1.00  0.00
+HETATM    2  C2  ACN     1       1.460   0.000   0.000  1.00  0.00
+HETATM    3  N1  ACN     1       2.620   0.000   0.000  1.00  0.00
+HETATM    4 NA    NA     2       5.000   5.000   5.000  1.00  0.00
+ENDMDL
+MODEL        2
+HETATM    1  C1  ACN     1       9.000   0.000   0.000  1.00  0.00
+ENDMDL
+CONECT    1    2
+CONECT    2    1    3    3    3
+CONECT    3    2    2    2
+";
+
+    #[test]
+    fn reads_first_model_with_conect_bond_orders() {
+        let structure = parse_pdb(ACETONITRILE_AND_ION).unwrap();
+        assert_eq!(structure.atoms.len(), 4);
+        assert_eq!(structure.atoms[0].position, [0.0, 0.0, 0.0]);
+        assert_eq!(structure.atoms[2].element, Element::N);
+        assert_eq!(structure.atoms[3].element, Element::Na);
+        assert_eq!(structure.atoms[3].residue_name, "NA");
+
+        let metadata = &structure.graph.atoms[3].metadata;
+        assert_eq!(
+            metadata.name.as_deref(),
+            Some(structure.atoms[3].name.as_str())
+        );
+        assert_eq!(
+            metadata
+                .residue
+                .as_ref()
+                .map(|residue| residue.name.as_str()),
+            Some("NA")
+        );
+
+        let orders: Vec<_> = structure
+            .graph
+            .bonds
+            .iter()
+            .map(|bond| (bond.atom_ids, bond.order))
+            .collect();
+        assert_eq!(
+            orders,
+            [
+                ((0, 1), GraphBondOrder::Single),
+                ((1, 2), GraphBondOrder::Triple)
+            ]
+        );
+    }
+
+    #[test]
+    fn deuterium_is_read_as_hydrogen_with_its_isotope() {
+        let text = [("O", "O"), ("D1", "D"), ("D2", "")]
+            .map(|(name, element)| {
+                let record = format!(
+                    "HETATM    1 {name:^4} DOD     1       0.000   0.000   0.000  1.00  0.00"
+                );
+                format!("{record:<76}{element:>2}")
+            })
+            .join("\n");
+        let structure = parse_pdb(&text).unwrap();
+        let read: Vec<_> = structure
+            .atoms
+            .iter()
+            .map(|atom| (atom.element, atom.isotope))
+            .collect();
+        assert_eq!(
+            read,
+            [
+                (Element::O, None),
+                (Element::H, Some(2)),
+                (Element::H, Some(2))
+            ]
+        );
+        assert_eq!(structure.graph.atoms[1].isotope, Some(2));
+    }
+
+    #[test]
+    fn malformed_records_are_reported_with_line_numbers() {
+        let bad_coordinate = "ATOM      1  CA  ALA A   1       x.000   0.000   0.000";
+        assert!(matches!(
+            parse_pdb(bad_coordinate),
+            Err(PdbError::InvalidField {
+                line: 1,
+                field: "x coordinate",
+                ..
+            })
+        ));
+
+        let unknown_partner = "\
+ATOM      1  CA  ALA A   1       0.000   0.000   0.000
+CONECT    1    7";
+        assert!(matches!(
+            parse_pdb(unknown_partner),
+            Err(PdbError::UnknownAtom { line: 2, serial: 7 })
+        ));
+
+        let unknown_element = "ATOM      1  QQ  ALA A   1       0.000   0.000   0.000";
+        assert!(matches!(
+            parse_pdb(unknown_element),
+            Err(PdbError::UnknownElement { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,730 @@
+//! Indexed, random-access reading of SDF (MDL structure-data) archives.
+//!
+//! Large screening libraries routinely ship as multi-gigabyte SDF files. Instead of parsing such a
+//! file front to back, [`SdfIndex`] performs a single byte scan that records where every record
+//! starts and ends. [`SdfArchive`] pairs that index with any byte buffer — an owned `Vec<u8>`,
+//! a borrowed slice, or (with the `mmap` feature) a memory-mapped file — so individual records
+//! can be parsed on demand, skipped, or fanned out across threads without copying the file into
+//! memory.
+//!
+//! Only the V2000 connection table is supported. Atom coordinates are stored on the graph when
+//! every atom line carries readable ones, so that perception can refine hybridization from 3D
+//! structures; unreadable coordinates are ignored because typing works on connectivity alone.
+//! `M  CHG` and `M  RAD` property lines are stored as stated formal charges and unpaired
+//! electrons, which perception needs for carbocations, carbanions, and radicals. `M  ISO` lines
+//! and the `D` and `T` atom symbols are stored as isotopes.
+
+use crate::compat::prelude::*;
+use crate::core::error::SdfError;
+use crate::core::graph::MolecularGraph;
+use crate::core::properties::GraphBondOrder;
+use crate::io::parse_element_symbol;
+use core::ops::Range;
+// Threads and the filesystem are unavailable in the browser and without the standard library.
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use {
+    core::num::NonZeroUsize,
+    core::sync::atomic::{AtomicUsize, Ordering},
+    std::{fs, io, path::Path, thread},
+};
+
+/// Line that terminates every record in an SDF file.
+const RECORD_DELIMITER: &[u8] = b"$$$$";
+
+/// A single parsed SDF record.
+#[derive(Debug, Clone)]
+pub struct SdfRecord {
+    /// Molecule name from the first header line.
+    pub title: String,
+    /// Connectivity described by the connection table.
+    pub graph: MolecularGraph,
+    /// Associated data items (`> <NAME>` blocks) in file order.
+    pub properties: Vec<(String, String)>,
+}
+
+impl SdfRecord {
+    /// Looks up the value of a data item by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Data item name without the surrounding angle brackets.
+    ///
+    /// # Returns
+    ///
+    /// The first value stored under `name`, if any.
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Byte ranges of every record in an SDF buffer.
+///
+/// Ranges exclude the `$$$$` delimiter line. A trailing record without a delimiter is still
+/// indexed as long as it contains non-whitespace content.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SdfIndex {
+    records: Vec<Range<usize>>,
+}
+
+impl SdfIndex {
+    /// Scans `data` once and records the boundaries of every record.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Complete contents of an SDF file.
+    ///
+    /// # Returns
+    ///
+    /// An index whose ranges can be used to slice `data`.
+    pub fn build(data: &[u8]) -> Self {
+        let mut records = Vec::new();
+        let mut record_start = 0;
+        let mut line_start = 0;
+
+        while line_start < data.len() {
+            let line_end = data[line_start..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(data.len(), |offset| line_start + offset + 1);
+
+            if data[line_start..line_end].trim_ascii_end() == RECORD_DELIMITER {
+                records.push(record_start..line_start);
+                record_start = line_end;
+            }
+            line_start = line_end;
+        }
+
+        if data[record_start..]
+            .iter()
+            .any(|b| !b.is_ascii_whitespace())
+        {
+            records.push(record_start..data.len());
+        }
+
+        Self { records }
+    }
+
+    /// Returns the number of indexed records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no records were found.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the byte range of the record at `index`, if present.
+    pub fn get(&self, index: usize) -> Option<Range<usize>> {
+        self.records.get(index).cloned()
+    }
+
+    /// Returns the byte ranges of all records in file order.
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.records
+    }
+}
+
+/// Random-access view over an SDF file held in any byte buffer.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::sdf::SdfArchive;
+///
+/// let sdf = "\
+/// water
+///
+///
+///   3  2  0  0  0  0  0  0  0  0999 V2000
+///     0.0000    0.0000    0.0000 O   0  0
+///     0.9572    0.0000    0.0000 H   0  0
+///    -0.2400    0.9266    0.0000 H   0  0
+///   1  2  1  0
+///   1  3  1  0
+/// M  END
+/// $$$$
+/// ";
+///
+/// let archive = SdfArchive::new(sdf.as_bytes());
+/// assert_eq!(archive.len(), 1);
+///
+/// let record = archive.parse(0).unwrap();
+/// assert_eq!(record.title, "water");
+/// assert_eq!(record.graph.atoms.len(), 3);
+/// assert_eq!(record.graph.bonds.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct SdfArchive<B> {
+    data: B,
+    index: SdfIndex,
+}
+
+impl<B: AsRef<[u8]>> SdfArchive<B> {
+    /// Indexes the records contained in `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Buffer holding the complete SDF file.
+    pub fn new(data: B) -> Self {
+        let index = SdfIndex::build(data.as_ref());
+        Self { data, index }
+    }
+
+    /// Returns the record index built for this archive.
+    pub fn index(&self) -> &SdfIndex {
+        &self.index
+    }
+
+    /// Returns the number of records in the archive.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns `true` if the archive contains no records.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Returns the raw bytes of the record at `index`, excluding its delimiter.
+    pub fn record_bytes(&self, index: usize) -> Option<&[u8]> {
+        self.index
+            .get(index)
+            .map(|range| &self.data.as_ref()[range])
+    }
+
+    /// Parses the record at `index` without touching any other record.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SdfError::RecordOutOfRange`] for an invalid index, or any error raised while
+    /// parsing the record's connection table.
+    pub fn parse(&self, index: usize) -> Result<SdfRecord, SdfError> {
+        let bytes = self.record_bytes(index).ok_or(SdfError::RecordOutOfRange {
+            index,
+            len: self.len(),
+        })?;
+        let text = core::str::from_utf8(bytes).map_err(|_| SdfError::InvalidEncoding)?;
+        parse_molfile(text)
+    }
+
+    /// Parses every record sequentially in file order.
+    pub fn iter(&self) -> impl Iterator<Item = Result<SdfRecord, SdfError>> + '_ {
+        (0..self.len()).map(|index| self.parse(index))
+    }
+
+    /// Parses every record using a pool of scoped worker threads.
+    ///
+    /// Not available on WebAssembly targets, which cannot spawn threads, or without the `std`
+    /// feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `threads` - Number of workers; `None` uses the available parallelism of the host.
+    ///
+    /// # Returns
+    ///
+    /// One result per record, in file order.
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn parse_parallel(&self, threads: Option<NonZeroUsize>) -> Vec<Result<SdfRecord, SdfError>>
+    where
+        B: Sync,
+    {
+        let len = self.len();
+        let workers = threads
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get)
+            .min(len)
+            .max(1);
+        let next = AtomicUsize::new(0);
+
+        let mut indexed: Vec<(usize, Result<SdfRecord, SdfError>)> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut local = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            if index >= len {
+                                break;
+                            }
+                            local.push((index, self.parse(index)));
+                        }
+                        local
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("SDF parser thread panicked"))
+                .collect()
+        });
+
+        indexed.sort_unstable_by_key(|(index, _)| *index);
+        indexed.into_iter().map(|(_, result)| result).collect()
+    }
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+impl SdfArchive<Vec<u8>> {
+    /// Reads an SDF file into memory and indexes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file cannot be read.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read(path).map(Self::new)
+    }
+}
+
+#[cfg(all(feature = "mmap", not(target_family = "wasm")))]
+impl SdfArchive<memmap2::Mmap> {
+    /// Memory-maps an SDF file and indexes it without reading it into RAM.
+    ///
+    /// The operating system pages records in as they are parsed, so archives larger than
+    /// physical memory can be processed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file cannot be opened or mapped.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = fs::File::open(path)?;
+        // SAFETY: the mapping is read-only. Callers must not truncate or rewrite the file while
+        // the archive is alive, which is the usual contract for memory-mapped inputs.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Ok(Self::new(map))
+    }
+}
+
+/// Parses a single V2000 molfile (optionally followed by SDF data items).
+///
+/// # Arguments
+///
+/// * `text` - Record text without the `$$$$` delimiter.
+///
+/// # Returns
+///
+/// The parsed [`SdfRecord`].
+///
+/// # Errors
+///
+/// Returns an [`SdfError`] describing the first malformed line encountered.
+pub fn parse_molfile(text: &str) -> Result<SdfRecord, SdfError> {
+    let lines: Vec<&str> = text.lines().collect();
+    let line_at = |index: usize| {
+        lines
+            .get(index)
+            .copied()
+            .ok_or(SdfError::Truncated { line: index + 1 })
+    };
+
+    let title = line_at(0)?.trim().to_string();
+    let counts = line_at(3)?;
+    let version = field(counts, 33, 39).to_string();
+    if !version.is_empty() && version != "V2000" {
+        return Err(SdfError::UnsupportedVersion { version });
+    }
+    let atom_count: usize = parse_field(counts, 0, 3, 4, "atom count")?;
+    let bond_count: usize = parse_field(counts, 3, 6, 4, "bond count")?;
+
+    let mut graph = MolecularGraph::new();
+    let mut positions = Some(Vec::with_capacity(atom_count));
+    for offset in 0..atom_count {
+        let index = 4 + offset;
+        let line = line_at(index)?;
+        let symbol = field(line, 31, 34);
+        let (element, isotope) =
+            parse_element_symbol(symbol).ok_or_else(|| SdfError::UnknownElement {
+                line: index + 1,
+                symbol: symbol.to_string(),
+            })?;
+        let id = graph.add_atom(element);
+        graph.atoms[id].isotope = isotope;
+
+        let coordinate = |start: usize| field(line, start, start + 10).parse::<f64>().ok();
+        positions = positions.and_then(|mut positions: Vec<[f64; 3]>| {
+            positions.push([coordinate(0)?, coordinate(10)?, coordinate(20)?]);
+            Some(positions)
+        });
+    }
+    graph.positions = positions;
+
+    for offset in 0..bond_count {
+        let index = 4 + atom_count + offset;
+        let line = line_at(index)?;
+        let first: usize = parse_field(line, 0, 3, index + 1, "bond atom")?;
+        let second: usize = parse_field(line, 3, 6, index + 1, "bond atom")?;
+        let code: u8 = parse_field(line, 6, 9, index + 1, "bond type")?;
+        let order = match code {
+            1 => GraphBondOrder::Single,
+            2 => GraphBondOrder::Double,
+            3 => GraphBondOrder::Triple,
+            4 => GraphBondOrder::Aromatic,
+            _ => {
+                return Err(SdfError::UnsupportedBondType {
+                    line: index + 1,
+                    code,
+                });
+            }
+        };
+        let (Some(first), Some(second)) = (first.checked_sub(1), second.checked_sub(1)) else {
+            return Err(SdfError::InvalidField {
+                line: index + 1,
+                field: "bond atom",
+                value: line.to_string(),
+            });
+        };
+        graph
+            .add_bond(first, second, order)
+            .map_err(|source| SdfError::InvalidBond {
+                line: index + 1,
+                source,
+            })?;
+    }
+
+    let block_start = 4 + atom_count + bond_count;
+    for (offset, line) in lines[block_start..].iter().enumerate() {
+        if line.starts_with("M  END") {
+            break;
+        }
+        if ["M  CHG", "M  RAD", "M  ISO"]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+        {
+            apply_atom_property(&mut graph, line, block_start + offset + 1)?;
+        }
+    }
+
+    let properties = parse_data_items(&lines[block_start..]);
+
+    Ok(SdfRecord {
+        title,
+        graph,
+        properties,
+    })
+}
+
+/// Applies an `M  CHG`, `M  RAD`, or `M  ISO` line, a count followed by `(atom, value)` pairs.
+///
+/// Radical values follow the MDL convention: 2 is a doublet with one unpaired electron and 3 a
+/// triplet with two; singlets (1) have none. Isotope values are absolute mass numbers.
+fn apply_atom_property(
+    graph: &mut MolecularGraph,
+    line: &str,
+    line_number: usize,
+) -> Result<(), SdfError> {
+    let count: usize = parse_field(line, 6, 9, line_number, "property count")?;
+    for entry in 0..count {
+        let start = 9 + entry * 8;
+        let atom: usize = parse_field(line, start, start + 4, line_number, "property atom")?;
+        let value: i16 = parse_field(line, start + 4, start + 8, line_number, "property value")?;
+        let invalid_value = || SdfError::InvalidField {
+            line: line_number,
+            field: "property value",
+            value: value.to_string(),
+        };
+        let Some(node) = atom.checked_sub(1).and_then(|id| graph.atoms.get_mut(id)) else {
+            return Err(SdfError::InvalidField {
+                line: line_number,
+                field: "property atom",
+                value: atom.to_string(),
+            });
+        };
+        if line.starts_with("M  CHG") {
+            node.formal_charge = Some(i8::try_from(value).map_err(|_| invalid_value())?);
+        } else if line.starts_with("M  ISO") {
+            node.isotope = Some(u16::try_from(value).map_err(|_| invalid_value())?);
+        } else {
+            node.radical_electrons = match value {
+                2 => 1,
+                3 => 2,
+                _ => 0,
+            };
+        }
+    }
+    Ok(())
+}
+
+/// Extracts `> <NAME>` data items that follow the connection table.
+fn parse_data_items(lines: &[&str]) -> Vec<(String, String)> {
+    let mut properties = Vec::new();
+    let mut iter = lines.iter();
+
+    while let Some(line) = iter.next() {
+        let Some(header) = line.strip_prefix('>') else {
+            continue;
+        };
+        let Some(name) = header
+            .split_once('<')
+            .and_then(|(_, rest)| rest.split_once('>'))
+            .map(|(name, _)| name.to_string())
+        else {
+            continue;
+        };
+
+        let value: Vec<&str> = iter
+            .by_ref()
+            .take_while(|line| !line.trim().is_empty())
+            .copied()
+            .collect();
+        properties.push((name, value.join("\n")));
+    }
+
+    properties
+}
+
+/// Returns the trimmed contents of the fixed-width column range `[start, end)`.
+fn field(line: &str, start: usize, end: usize) -> &str {
+    let end = end.min(line.len());
+    line.get(start.min(end)..end).unwrap_or("").trim()
+}
+
+/// Parses a fixed-width numeric field.
+fn parse_field<T: core::str::FromStr>(
+    line: &str,
+    start: usize,
+    end: usize,
+    line_number: usize,
+    name: &'static str,
+) -> Result<T, SdfError> {
+    let raw = field(line, start, end);
+    raw.parse().map_err(|_| SdfError::InvalidField {
+        line: line_number,
+        field: name,
+        value: raw.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::properties::Element;
+
+    const ETHENE: &str = "\
+ethene
+  test
+
+  2  1  0  0  0  0  0  0  0  0999 V2000
+    0.0000    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0
+    1.3300    0.0000    0.0000 C   0  0  0  0  0  0  0  0  0  0  0  0
+  1  2  2  0
+M  END
+> <ID>
+MOL-1
+
+> <NOTE>
+first line
+second line
+
+";
+
+    const BENZENE_CORE: &str = "\
+benzene
+
+
+  6  6  0  0  0  0  0  0  0  0999 V2000
+    0.0000    0.0000    0.0000 C   0  0
+    0.0000    0.0000    0.0000 C   0  0
+    0.0000    0.0000    0.0000 C   0  0
+    0.0000    0.0000    0.0000 C   0  0
+    0.0000    0.0000    0.0000 C   0  0
+    0.0000    0.0000    0.0000 C   0  0
+  1  2  4  0
+  2  3  4  0
+  3  4  4  0
+  4  5  4  0
+  5  6  4  0
+  6  1  4  0
+M  END
+";
+
+    const ALLYL_CATION: &str = "\
+allyl cation
+
+
+  3  2  0  0  0  0  0  0  0  0999 V2000
+    0.0000    0.0000    0.0000 C   0  0
+    1.3300    0.0000    0.0000 C   0  0
+    2.0000    1.1500    0.0000 C   0  0
+  1  2  2  0
+  2  3  1  0
+M  CHG  1   3   1
+M  RAD  1   1   2
+M  END
+";
+
+    const HEAVY_WATER: &str = "\
+heavy water with a lone-pair site
+
+
+  4  3  0  0  0  0  0  0  0  0999 V2000
+    0.0000    0.0000    0.0000 O   0  0
+    0.9600    0.0000    0.0000 D   0  0
+   -0.2400    0.9300    0.0000 T   0  0
+   -0.2000   -0.2000    0.5000 Lp  0  0
+  1  2  1  0
+  1  3  1  0
+  1  4  1  0
+M  ISO  1   1  18
+M  END
+";
+
+    fn archive_text() -> String {
+        format!("{ETHENE}$$$$\n{BENZENE_CORE}$$$$\r\n{ETHENE}")
+    }
+
+    #[test]
+    fn index_finds_record_boundaries_including_unterminated_tail() {
+        let text = archive_text();
+        let index = SdfIndex::build(text.as_bytes());
+        assert_eq!(index.len(), 3);
+        assert_eq!(&text[index.get(0).unwrap()], ETHENE);
+        assert_eq!(&text[index.get(1).unwrap()], BENZENE_CORE);
+        assert_eq!(&text[index.get(2).unwrap()], ETHENE);
+    }
+
+    #[test]
+    fn index_ignores_trailing_whitespace_after_last_delimiter() {
+        let text = format!("{ETHENE}$$$$\n\n  \n");
+        assert_eq!(SdfIndex::build(text.as_bytes()).len(), 1);
+        assert!(SdfIndex::build(b"").is_empty());
+    }
+
+    #[test]
+    fn parse_reads_connection_table_and_data_items() {
+        let record = parse_molfile(ETHENE).unwrap();
+        assert_eq!(record.title, "ethene");
+        assert_eq!(record.graph.atoms.len(), 2);
+        assert_eq!(record.graph.bonds[0].atom_ids, (0, 1));
+        assert_eq!(record.graph.bonds[0].order, GraphBondOrder::Double);
+        assert_eq!(
+            record.graph.positions,
+            Some(vec![[0.0, 0.0, 0.0], [1.33, 0.0, 0.0]])
+        );
+        assert_eq!(record.property("ID"), Some("MOL-1"));
+        assert_eq!(record.property("NOTE"), Some("first line\nsecond line"));
+        assert_eq!(record.property("MISSING"), None);
+    }
+
+    #[test]
+    fn parse_reads_charge_and_radical_properties() {
+        let record = parse_molfile(ALLYL_CATION).unwrap();
+        let atoms = &record.graph.atoms;
+        assert_eq!(atoms[2].formal_charge, Some(1));
+        assert_eq!(atoms[0].radical_electrons, 1);
+        assert_eq!(
+            (atoms[1].formal_charge, atoms[1].radical_electrons),
+            (None, 0)
+        );
+
+        let out_of_range = ALLYL_CATION.replace("M  CHG  1   3", "M  CHG  1   9");
+        assert!(matches!(
+            parse_molfile(&out_of_range),
+            Err(SdfError::InvalidField { line: 10, .. })
+        ));
+    }
+
+    #[test]
+    fn parse_reads_isotopes_and_dummy_atoms() {
+        let record = parse_molfile(HEAVY_WATER).unwrap();
+        let atoms = &record.graph.atoms;
+        assert_eq!(
+            atoms.iter().map(|atom| atom.element).collect::<Vec<_>>(),
+            [Element::O, Element::H, Element::H, Element::Dummy]
+        );
+        assert_eq!(
+            atoms.iter().map(|atom| atom.isotope).collect::<Vec<_>>(),
+            [Some(18), Some(2), Some(3), None]
+        );
+
+        let negative = HEAVY_WATER.replace("M  ISO  1   1  18", "M  ISO  1   1  -1");
+        assert!(matches!(
+            parse_molfile(&negative),
+            Err(SdfError::InvalidField {
+                field: "property value",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn archive_parses_records_out_of_order_and_in_parallel() {
+        let archive = SdfArchive::new(archive_text().into_bytes());
+        let benzene = archive.parse(1).unwrap();
+        assert!(
+            benzene
+                .graph
+                .bonds
+                .iter()
+                .all(|b| b.order == GraphBondOrder::Aromatic)
+        );
+
+        #[cfg(feature = "std")]
+        {
+            let parallel = archive.parse_parallel(NonZeroUsize::new(2));
+            let titles: Vec<_> = parallel.into_iter().map(|r| r.unwrap().title).collect();
+            assert_eq!(titles, ["ethene", "benzene", "ethene"]);
+        }
+        assert_eq!(archive.iter().filter(Result::is_ok).count(), 3);
+
+        assert!(matches!(
+            archive.parse(3),
+            Err(SdfError::RecordOutOfRange { index: 3, len: 3 })
+        ));
+    }
+
+    #[test]
+    fn parse_reports_malformed_records() {
+        assert!(matches!(
+            parse_molfile("title\n\n"),
+            Err(SdfError::Truncated { line: 4 })
+        ));
+
+        let v3000 = "t\n\n\n  0  0  0     0  0            999 V3000\n";
+        assert!(matches!(
+            parse_molfile(v3000),
+            Err(SdfError::UnsupportedVersion { .. })
+        ));
+
+        let bad_symbol = ETHENE.replacen(" C ", " Xx", 1);
+        assert!(matches!(
+            parse_molfile(&bad_symbol),
+            Err(SdfError::UnknownElement { line: 5, .. })
+        ));
+
+        let bad_bond = ETHENE.replace("  1  2  2  0", "  1  2  8  0");
+        assert!(matches!(
+            parse_molfile(&bad_bond),
+            Err(SdfError::UnsupportedBondType { line: 7, code: 8 })
+        ));
+
+        let missing_atom = ETHENE.replace("  1  2  2  0", "  1  5  2  0");
+        assert!(matches!(
+            parse_molfile(&missing_atom),
+            Err(SdfError::InvalidBond { line: 7, .. })
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn archive_reads_files_from_disk() {
+        let path = std::env::temp_dir().join(format!("dreid-sdf-{}.sdf", std::process::id()));
+        fs::write(&path, archive_text()).unwrap();
+
+        let owned = SdfArchive::read(&path).unwrap();
+        assert_eq!(owned.len(), 3);
+
+        #[cfg(feature = "mmap")]
+        {
+            let mapped = SdfArchive::open(&path).unwrap();
+            assert_eq!(mapped.index(), owned.index());
+            assert_eq!(mapped.parse(1).unwrap().title, "benzene");
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,813 @@
+//! Parsing of SMILES strings into molecular graphs.
+//!
+//! The parser covers the connectivity subset of OpenSMILES: organic-subset and bracket atoms,
+//! branches, ring closures (including `%nn`), dot-disconnected components, and the `-`, `=`,
+//! `#`, `:`, `/`, and `\` bond symbols. Tetrahedral `@`/`@@` centers and `/`/`\` double-bond
+//! configurations are recorded as [`Chirality`] and [`BondStereo`] relative to the resulting
+//! atom IDs. Charges of bracket atoms are recorded as stated formal charges, and a non-aromatic
+//! bracket carbon, nitrogen, or oxygen written with fewer bonds and hydrogens than its default
+//! valence (adjusted for its charge) is recorded as a radical, as in `[CH3]` or `[N]=O`. Isotopes
+//! such as `[2H]` are recorded on their atoms; other stereo classes and atom classes are accepted
+//! and discarded.
+//!
+//! Hydrogens are always made explicit: organic-subset atoms receive the implicit hydrogens of
+//! their lowest default valence that fits, bracket atoms receive exactly the hydrogens written in
+//! the bracket. Heavy atoms keep their order of appearance and the added hydrogens follow them.
+
+use crate::compat::collections::HashMap;
+use crate::compat::prelude::*;
+use crate::core::error::SmilesError;
+use crate::core::graph::MolecularGraph;
+use crate::core::properties::{Element, GraphBondOrder};
+use crate::core::stereo::{self, BondStereo, Chirality};
+
+/// Heavy atom parsed from the string.
+struct SmilesAtom {
+    /// Chemical element.
+    element: Element,
+    /// Whether the atom was written in lowercase aromatic form.
+    aromatic: bool,
+    /// Hydrogen count written in a bracket atom; `None` for organic-subset atoms.
+    hydrogens: Option<u8>,
+    /// Tetrahedral configuration relative to the neighbors in order of appearance.
+    chirality: Option<Chirality>,
+    /// Charge written in a bracket atom; zero for organic-subset atoms.
+    charge: i8,
+    /// Mass number written in a bracket atom, if any.
+    isotope: Option<u16>,
+}
+
+/// Ring closure waiting for its partner.
+struct OpenRing {
+    /// Atom that opened the ring.
+    atom: usize,
+    /// Bond symbol written at the opening digit.
+    bond: Option<char>,
+    /// Position of the ring bond among the opening atom's neighbors.
+    slot: usize,
+}
+
+/// Parses a SMILES string into a molecular graph with explicit hydrogens.
+///
+/// # Arguments
+///
+/// * `smiles` - SMILES string; surrounding whitespace and anything after the first internal
+///   whitespace (such as a title) are ignored.
+///
+/// # Returns
+///
+/// The molecular graph, with heavy atoms in order of appearance followed by hydrogens.
+///
+/// # Errors
+///
+/// Returns a [`SmilesError`] describing the first syntax error, or
+/// [`SmilesError::InvalidBond`] if a ring closure connects an atom to itself or duplicates a
+/// bond.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::smiles::parse_smiles;
+/// use dreid_typer::{assign_topology, Element};
+///
+/// let graph = parse_smiles("c1ccccc1O").unwrap();
+/// assert_eq!(graph.atoms.len(), 13);
+/// assert_eq!(graph.atoms[6].element, Element::O);
+///
+/// let topology = assign_topology(&graph).unwrap();
+/// assert_eq!(topology.atoms[0].atom_type, "C_R");
+/// assert_eq!(topology.atoms[6].atom_type, "O_R");
+/// ```
+pub fn parse_smiles(smiles: &str) -> Result<MolecularGraph, SmilesError> {
+    let smiles = smiles.split_whitespace().next().unwrap_or("");
+    let bytes = smiles.as_bytes();
+
+    let mut atoms: Vec<SmilesAtom> = Vec::new();
+    let mut bonds: Vec<(usize, usize, Option<char>, usize)> = Vec::new();
+    let mut previous: Option<usize> = None;
+    let mut branches: Vec<Option<usize>> = Vec::new();
+    let mut pending_bond: Option<(char, usize)> = None;
+    let mut rings: HashMap<u32, OpenRing> = HashMap::new();
+    let mut neighbors: Vec<Vec<Option<usize>>> = Vec::new();
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let position = i;
+        let c = bytes[i] as char;
+        match c {
+            '(' => {
+                if previous.is_none() {
+                    return Err(SmilesError::UnbalancedBranch { position });
+                }
+                branches.push(previous);
+                i += 1;
+            }
+            ')' => {
+                let atom = branches
+                    .pop()
+                    .ok_or(SmilesError::UnbalancedBranch { position })?;
+                if pending_bond.is_some() {
+                    return Err(SmilesError::DanglingBond { position });
+                }
+                previous = atom;
+                i += 1;
+            }
+            '.' => {
+                if pending_bond.is_some() {
+                    return Err(SmilesError::DanglingBond { position });
+                }
+                previous = None;
+                i += 1;
+            }
+            '-' | '=' | '#' | ':' | '/' | '\\' => {
+                if previous.is_none() || pending_bond.is_some() {
+                    return Err(SmilesError::DanglingBond { position });
+                }
+                pending_bond = Some((c, position));
+                i += 1;
+            }
+            '%' | '0'..='9' => {
+                let atom = previous.ok_or(SmilesError::DanglingBond { position })?;
+                let (label, next) = if c == '%' {
+                    let digits = smiles.get(i + 1..i + 3).filter(|d| d.len() == 2);
+                    let label = digits.and_then(|d| d.parse().ok()).ok_or(
+                        SmilesError::UnexpectedCharacter {
+                            position,
+                            character: c,
+                        },
+                    )?;
+                    (label, i + 3)
+                } else {
+                    (u32::from(bytes[i] - b'0'), i + 1)
+                };
+                let bond = pending_bond.take().map(|(symbol, _)| symbol);
+                match rings.remove(&label) {
+                    Some(open) => {
+                        let symbol = match (open.bond, bond) {
+                            (Some(a), Some(b)) if a != b => {
+                                return Err(SmilesError::ConflictingRingBond { position, label });
+                            }
+                            (a, b) => a.or(b),
+                        };
+                        bonds.push((open.atom, atom, symbol, position));
+                        neighbors[open.atom][open.slot] = Some(atom);
+                        neighbors[atom].push(Some(open.atom));
+                    }
+                    None => {
+                        let slot = neighbors[atom].len();
+                        neighbors[atom].push(None);
+                        rings.insert(label, OpenRing { atom, bond, slot });
+                    }
+                }
+                i = next;
+            }
+            '[' => {
+                let end = smiles[i..]
+                    .find(']')
+                    .map(|offset| i + offset)
+                    .ok_or(SmilesError::UnclosedBracket { position })?;
+                let atom = parse_bracket_atom(&smiles[i + 1..end], i + 1)?;
+                add_atom(
+                    &mut atoms,
+                    &mut bonds,
+                    &mut neighbors,
+                    &mut previous,
+                    &mut pending_bond,
+                    atom,
+                    position,
+                );
+                i = end + 1;
+            }
+            _ => {
+                let (atom, length) = parse_organic_atom(smiles, i)?;
+                add_atom(
+                    &mut atoms,
+                    &mut bonds,
+                    &mut neighbors,
+                    &mut previous,
+                    &mut pending_bond,
+                    atom,
+                    position,
+                );
+                i += length;
+            }
+        }
+    }
+
+    if let Some((_, position)) = pending_bond {
+        return Err(SmilesError::DanglingBond { position });
+    }
+    if !branches.is_empty() {
+        return Err(SmilesError::UnbalancedBranch {
+            position: smiles.len(),
+        });
+    }
+    if let Some(&label) = rings.keys().min() {
+        return Err(SmilesError::UnclosedRing { label });
+    }
+    if atoms.is_empty() {
+        return Err(SmilesError::Empty);
+    }
+
+    let mut graph = build_graph(&atoms, &bonds)?;
+    assign_chirality(&mut graph, &atoms, &neighbors);
+    assign_bond_stereo(&mut graph, &bonds);
+    Ok(graph)
+}
+
+/// Appends an atom written at `position` and bonds it to the previous atom.
+///
+/// `neighbors` receives the neighbors of every atom in order of appearance, where `None` holds
+/// the place of the implicit hydrogen or lone pair of a stereocenter.
+fn add_atom(
+    atoms: &mut Vec<SmilesAtom>,
+    bonds: &mut Vec<(usize, usize, Option<char>, usize)>,
+    neighbors: &mut Vec<Vec<Option<usize>>>,
+    previous: &mut Option<usize>,
+    pending_bond: &mut Option<(char, usize)>,
+    atom: SmilesAtom,
+    position: usize,
+) {
+    let id = atoms.len();
+    let is_stereocenter = atom.chirality.is_some();
+    atoms.push(atom);
+    neighbors.push(Vec::new());
+    if let Some(prev) = *previous {
+        let symbol = pending_bond.take().map(|(symbol, _)| symbol);
+        bonds.push((prev, id, symbol, position));
+        neighbors[prev].push(Some(id));
+        neighbors[id].push(Some(prev));
+    }
+    if is_stereocenter {
+        neighbors[id].push(None);
+    }
+    *previous = Some(id);
+}
+
+/// Parses an organic-subset atom starting at `start` and returns it with its length in bytes.
+fn parse_organic_atom(smiles: &str, start: usize) -> Result<(SmilesAtom, usize), SmilesError> {
+    let rest = &smiles[start..];
+    let (symbol, aromatic) = if rest.starts_with("Cl") || rest.starts_with("Br") {
+        (&rest[..2], false)
+    } else {
+        let symbol = &rest[..rest.chars().next().map_or(0, char::len_utf8)];
+        match symbol {
+            "B" | "C" | "N" | "O" | "P" | "S" | "F" | "I" => (symbol, false),
+            "b" | "c" | "n" | "o" | "p" | "s" => (symbol, true),
+            _ => {
+                return Err(SmilesError::UnexpectedCharacter {
+                    position: start,
+                    character: symbol.chars().next().unwrap_or(' '),
+                });
+            }
+        }
+    };
+    let element = parse_element(symbol, start)?;
+    Ok((
+        SmilesAtom {
+            element,
+            aromatic,
+            hydrogens: None,
+            chirality: None,
+            charge: 0,
+            isotope: None,
+        },
+        symbol.len(),
+    ))
+}
+
+/// Parses the contents of a bracket atom (without the brackets).
+fn parse_bracket_atom(content: &str, offset: usize) -> Result<SmilesAtom, SmilesError> {
+    let unexpected = |index: usize| SmilesError::UnexpectedCharacter {
+        position: offset + index,
+        character: content[index..].chars().next().unwrap_or(']'),
+    };
+
+    let bytes = content.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+        i += 1;
+    }
+    let isotope = if i == 0 {
+        None
+    } else {
+        Some(content[..i].parse().map_err(|_| unexpected(0))?)
+    };
+
+    let symbol_start = i;
+    let first = *bytes.get(i).ok_or_else(|| unexpected(i))?;
+    if !first.is_ascii_alphabetic() {
+        return Err(unexpected(i));
+    }
+    let aromatic = first.is_ascii_lowercase();
+    let two_letter = content
+        .get(i..i + 2)
+        .filter(|s| s.as_bytes()[1].is_ascii_lowercase());
+    let (symbol, element) = match two_letter.and_then(|s| {
+        let canonical = capitalize(s);
+        let allowed = !aromatic || matches!(s, "se" | "as");
+        canonical
+            .parse::<Element>()
+            .ok()
+            .filter(|_| allowed)
+            .map(|e| (s, e))
+    }) {
+        Some(found) => found,
+        None => {
+            let s = &content[i..i + 1];
+            (s, parse_element(s, offset + i)?)
+        }
+    };
+    i += symbol.len();
+    if aromatic && !matches!(symbol, "b" | "c" | "n" | "o" | "p" | "s" | "se" | "as") {
+        return Err(SmilesError::UnknownElement {
+            position: offset + symbol_start,
+            symbol: symbol.to_string(),
+        });
+    }
+
+    // Chirality: `@`, `@@`, or `@TH1`-style classes, of which only tetrahedral ones are kept.
+    let mut chirality = None;
+    if bytes.get(i) == Some(&b'@') {
+        let start = i;
+        while bytes.get(i) == Some(&b'@') {
+            i += 1;
+        }
+        chirality = match i - start {
+            1 => Some(Chirality::CounterClockwise),
+            _ => Some(Chirality::Clockwise),
+        };
+        if matches!(
+            content.get(i..i + 2),
+            Some("TH" | "AL" | "SP" | "TB" | "OH")
+        ) {
+            let class = &content[i..i + 2];
+            i += 2;
+            let digits = i;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            chirality = match (class, &content[digits..i]) {
+                ("TH", "1") => Some(Chirality::CounterClockwise),
+                ("TH", "2") => Some(Chirality::Clockwise),
+                _ => None,
+            };
+        }
+    }
+
+    let mut hydrogens = 0;
+    if bytes.get(i) == Some(&b'H') {
+        i += 1;
+        let digits = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        hydrogens = if digits == i {
+            1
+        } else {
+            content[digits..i].parse().map_err(|_| unexpected(digits))?
+        };
+    }
+
+    // Charge: `+`, `++`, or `+2`, and likewise for `-`.
+    let mut charge: i8 = 0;
+    if let Some(&sign) = bytes.get(i).filter(|&&b| matches!(b, b'+' | b'-')) {
+        let unit = if sign == b'+' { 1 } else { -1 };
+        let start = i;
+        while bytes.get(i) == Some(&sign) {
+            i += 1;
+        }
+        let digits = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        let magnitude: i8 = if digits == i {
+            (digits - start) as i8
+        } else {
+            content[digits..i].parse().map_err(|_| unexpected(digits))?
+        };
+        charge = unit * magnitude;
+    }
+
+    if bytes.get(i) == Some(&b':') {
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+    }
+
+    if i != bytes.len() {
+        return Err(unexpected(i));
+    }
+
+    Ok(SmilesAtom {
+        element,
+        aromatic,
+        hydrogens: Some(hydrogens),
+        chirality,
+        charge,
+        isotope,
+    })
+}
+
+/// Parses an element symbol written in upper- or lowercase.
+fn parse_element(symbol: &str, position: usize) -> Result<Element, SmilesError> {
+    capitalize(symbol)
+        .parse()
+        .map_err(|_| SmilesError::UnknownElement {
+            position,
+            symbol: symbol.to_string(),
+        })
+}
+
+/// Uppercases the first letter of a symbol.
+fn capitalize(symbol: &str) -> String {
+    let mut chars = symbol.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+/// Resolves bond symbols, adds implicit hydrogens, and assembles the graph.
+fn build_graph(
+    atoms: &[SmilesAtom],
+    bonds: &[(usize, usize, Option<char>, usize)],
+) -> Result<MolecularGraph, SmilesError> {
+    let mut graph = MolecularGraph::new();
+    for atom in atoms {
+        graph.add_atom(atom.element);
+    }
+
+    let mut valence = vec![0u8; atoms.len()];
+    for &(a, b, symbol, position) in bonds {
+        let order = match symbol {
+            Some('=') => GraphBondOrder::Double,
+            Some('#') => GraphBondOrder::Triple,
+            Some(':') => GraphBondOrder::Aromatic,
+            Some(_) => GraphBondOrder::Single,
+            None if atoms[a].aromatic && atoms[b].aromatic => GraphBondOrder::Aromatic,
+            None => GraphBondOrder::Single,
+        };
+        graph
+            .add_bond(a, b, order)
+            .map_err(|source| SmilesError::InvalidBond { position, source })?;
+        let contribution = match order {
+            GraphBondOrder::Double => 2,
+            GraphBondOrder::Triple => 3,
+            GraphBondOrder::Single | GraphBondOrder::Aromatic => 1,
+        };
+        valence[a] += contribution;
+        valence[b] += contribution;
+    }
+
+    for (id, atom) in atoms.iter().enumerate() {
+        let hydrogens = match atom.hydrogens {
+            Some(count) => count,
+            None => implicit_hydrogens(atom, valence[id]),
+        };
+        if atom.hydrogens.is_some() {
+            let node = &mut graph.atoms[id];
+            node.isotope = atom.isotope;
+            node.formal_charge = (atom.charge != 0).then_some(atom.charge);
+            node.radical_electrons = radical_electrons(atom, valence[id] + hydrogens);
+        }
+        for _ in 0..hydrogens {
+            let h = graph.add_atom(Element::H);
+            graph
+                .add_bond(id, h, GraphBondOrder::Single)
+                .expect("hydrogen bonds to an existing atom");
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Records the tetrahedral centers relative to the atom IDs of the finished graph.
+///
+/// The written configuration refers to the neighbors in order of appearance, with a bracket
+/// hydrogen or lone pair in the place of `None`; it is flipped whenever sorting that order by
+/// atom ID takes an odd number of swaps. Centers that do not end up with three or four
+/// neighbors are ignored.
+fn assign_chirality(
+    graph: &mut MolecularGraph,
+    atoms: &[SmilesAtom],
+    neighbors: &[Vec<Option<usize>>],
+) {
+    let mut hydrogens = vec![Vec::new(); atoms.len()];
+    for bond in &graph.bonds {
+        let (heavy, h) = bond.atom_ids;
+        if h >= atoms.len() {
+            hydrogens[heavy].push(h);
+        }
+    }
+
+    for (id, atom) in atoms.iter().enumerate() {
+        let Some(chirality) = atom.chirality else {
+            continue;
+        };
+        let mut written = neighbors[id].clone();
+        if let [h] = hydrogens[id][..]
+            && let Some(slot) = written.iter().position(Option::is_none)
+        {
+            written[slot] = Some(h);
+        }
+        if written.len() == 5 {
+            written.retain(Option::is_some);
+        }
+        let explicit = written.iter().filter(|neighbor| neighbor.is_some()).count();
+        if written.len() != 4 || explicit < 3 {
+            continue;
+        }
+        graph.atoms[id].chirality = Some(if stereo::is_odd_permutation(&written) {
+            chirality.inverted()
+        } else {
+            chirality
+        });
+    }
+}
+
+/// Records double-bond configurations written with `/` and `\` bond symbols.
+///
+/// A substituent written as `X/C` lies below its double-bond carbon, and one written as `C/X`
+/// lies above it; `\` reverses both. Substituents on the same side make the bond cis, which is
+/// then re-expressed relative to the lowest-ID substituent on each end.
+fn assign_bond_stereo(graph: &mut MolecularGraph, bonds: &[(usize, usize, Option<char>, usize)]) {
+    let mut directional = vec![Vec::new(); graph.atoms.len()];
+    for &(first, second, symbol, _) in bonds {
+        let up = match symbol {
+            Some('/') => true,
+            Some('\\') => false,
+            _ => continue,
+        };
+        directional[first].push((second, up));
+        directional[second].push((first, !up));
+    }
+
+    let mut substituents = vec![Vec::new(); graph.atoms.len()];
+    for bond in &graph.bonds {
+        let (a, b) = bond.atom_ids;
+        substituents[a].push(b);
+        substituents[b].push(a);
+    }
+
+    for (id, &(a, b, symbol, _)) in bonds.iter().enumerate() {
+        if symbol != Some('=') {
+            continue;
+        }
+        let side = |end: usize, opposite: usize| {
+            let &(substituent, up) = directional[end]
+                .iter()
+                .find(|&&(substituent, _)| substituent != opposite)?;
+            let reference = substituents[end]
+                .iter()
+                .filter(|&&neighbor| neighbor != opposite)
+                .min()?;
+            Some(up == (substituent == *reference))
+        };
+        if let (Some(up_a), Some(up_b)) = (side(a, b), side(b, a)) {
+            graph.bonds[id].stereo = Some(if up_a == up_b {
+                BondStereo::Cis
+            } else {
+                BondStereo::Trans
+            });
+        }
+    }
+}
+
+/// Computes the implicit hydrogen count of an organic-subset atom.
+///
+/// Aromatic B, C, N, and P atoms contribute one electron to the π system, which uses up one
+/// unit of valence; aromatic O and S donate a lone pair instead. Aromatic atoms only take their
+/// lowest default valence, so a substituted pyrrole-type `n` gets no hydrogen.
+fn implicit_hydrogens(atom: &SmilesAtom, bond_valence: u8) -> u8 {
+    let default_valences: &[u8] = match atom.element {
+        Element::B => &[3],
+        Element::C => &[4],
+        Element::N | Element::P => &[3, 5],
+        Element::O => &[2],
+        Element::S => &[2, 4, 6],
+        _ => &[1],
+    };
+    let pi_electron = u8::from(
+        atom.aromatic
+            && matches!(
+                atom.element,
+                Element::B | Element::C | Element::N | Element::P
+            ),
+    );
+    let used = bond_valence + pi_electron;
+    let candidates = if atom.aromatic {
+        &default_valences[..1]
+    } else {
+        default_valences
+    };
+    candidates
+        .iter()
+        .find(|&&valence| valence >= used)
+        .map_or(0, |&valence| valence - used)
+}
+
+/// Counts the unpaired electrons of a bracket atom from its bonds, hydrogens, and charge.
+///
+/// Only non-aromatic carbon, nitrogen, and oxygen are considered, whose default valences of 4,
+/// 3, and 2 change predictably with charge: a charged carbon has three bonds, while a cation of
+/// nitrogen or oxygen gains a bond and an anion loses one.
+fn radical_electrons(atom: &SmilesAtom, used: u8) -> u8 {
+    if atom.aromatic {
+        return 0;
+    }
+    let charge = i16::from(atom.charge);
+    let expected = match atom.element {
+        Element::C => 4 - charge.abs(),
+        Element::N => 3 + charge,
+        Element::O => 2 + charge,
+        _ => return 0,
+    };
+    u8::try_from(expected - i16::from(used)).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hydrogen_counts(graph: &MolecularGraph, heavy_atoms: usize) -> Vec<usize> {
+        (0..heavy_atoms)
+            .map(|id| {
+                graph
+                    .bonds
+                    .iter()
+                    .filter(|bond| {
+                        let (a, b) = bond.atom_ids;
+                        (a == id && graph.atoms[b].element == Element::H)
+                            || (b == id && graph.atoms[a].element == Element::H)
+                    })
+                    .count()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn implicit_hydrogens_follow_default_valences() {
+        let acetic_acid = parse_smiles("CC(=O)O").unwrap();
+        assert_eq!(acetic_acid.atoms.len(), 8);
+        assert_eq!(hydrogen_counts(&acetic_acid, 4), [3, 0, 0, 1]);
+        assert_eq!(acetic_acid.bonds[1].order, GraphBondOrder::Double);
+
+        let pyrrole = parse_smiles("c1cc[nH]c1").unwrap();
+        assert_eq!(hydrogen_counts(&pyrrole, 5), [1, 1, 1, 1, 1]);
+        assert!(
+            pyrrole.bonds[..5]
+                .iter()
+                .all(|bond| bond.order == GraphBondOrder::Aromatic)
+        );
+
+        let pyridine = parse_smiles("n1ccccc1").unwrap();
+        assert_eq!(hydrogen_counts(&pyridine, 6), [0, 1, 1, 1, 1, 1]);
+
+        let methylimidazole = parse_smiles("Cn1ccnc1").unwrap();
+        assert_eq!(hydrogen_counts(&methylimidazole, 6), [3, 0, 1, 1, 0, 1]);
+
+        let sulfate = parse_smiles("OS(=O)(=O)O").unwrap();
+        assert_eq!(hydrogen_counts(&sulfate, 5), [1, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn bracket_atoms_and_components_are_parsed() {
+        let salt = parse_smiles("[Na+].[Cl-] ignored title").unwrap();
+        assert_eq!(salt.atoms.len(), 2);
+        assert!(salt.bonds.is_empty());
+
+        let ammonium = parse_smiles("[15NH4+]").unwrap();
+        assert_eq!(ammonium.atoms.len(), 5);
+
+        let chiral = parse_smiles("N[C@@H](C)C(=O)O").unwrap();
+        assert_eq!(parse_smiles("N[C@TH2H](C)C(=O)O").unwrap().atoms.len(), 13);
+        assert_eq!(hydrogen_counts(&chiral, 6), [2, 1, 3, 0, 0, 1]);
+
+        let selenophene = parse_smiles("[se]1cccc1").unwrap();
+        assert_eq!(selenophene.atoms[0].element, Element::Se);
+
+        let cyclohexane = parse_smiles("C%10CCCCC%10").unwrap();
+        assert_eq!(cyclohexane.bonds[5].atom_ids, (0, 5));
+    }
+
+    #[test]
+    fn bracket_charges_and_radicals_are_recorded() {
+        let state = |smiles: &str, id: usize| {
+            let graph = parse_smiles(smiles).unwrap();
+            (
+                graph.atoms[id].formal_charge,
+                graph.atoms[id].radical_electrons,
+            )
+        };
+        assert_eq!(state("[CH3+]", 0), (Some(1), 0));
+        assert_eq!(state("[CH3-]", 0), (Some(-1), 0));
+        assert_eq!(state("[CH3]", 0), (None, 1));
+        assert_eq!(state("[CH2]", 0), (None, 2));
+        assert_eq!(state("[N]=O", 0), (None, 1));
+        assert_eq!(state("[NH4+]", 0), (Some(1), 0));
+        assert_eq!(state("[Fe++]", 0), (Some(2), 0));
+        assert_eq!(state("[O--]", 0), (Some(-2), 0));
+        assert_eq!(state("CC(=O)[O-]", 3), (Some(-1), 0));
+        assert_eq!(state("[cH-]1cccc1", 0), (Some(-1), 0));
+        assert_eq!(state("C", 0), (None, 0));
+    }
+
+    #[test]
+    fn bracket_isotopes_are_recorded() {
+        let heavy_water = parse_smiles("[2H]O[2H]").unwrap();
+        let isotopes: Vec<_> = heavy_water.atoms.iter().map(|atom| atom.isotope).collect();
+        assert_eq!(isotopes, [Some(2), None, Some(2)]);
+
+        let labeled = parse_smiles("[13CH3]C").unwrap();
+        assert_eq!(labeled.atoms[0].isotope, Some(13));
+        assert!(labeled.atoms[1..].iter().all(|atom| atom.isotope.is_none()));
+
+        assert!(matches!(
+            parse_smiles("[99999C]"),
+            Err(SmilesError::UnexpectedCharacter { position: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn stereocenters_are_expressed_relative_to_atom_ids() {
+        let chirality = |smiles: &str, id: usize| parse_smiles(smiles).unwrap().atoms[id].chirality;
+        assert_eq!(chirality("N[C@@H](C)C(=O)O", 1), Some(Chirality::Clockwise));
+        assert_eq!(
+            chirality("N[C@H](C)C(=O)O", 1),
+            Some(Chirality::CounterClockwise)
+        );
+        // The same L-alanine with the methyl group ahead of the nitrogen in ID order.
+        assert_eq!(
+            chirality("C[C@H](N)C(=O)O", 1),
+            Some(Chirality::CounterClockwise)
+        );
+        assert_eq!(
+            chirality("[C@@H](N)(C)C(=O)O", 0),
+            Some(Chirality::CounterClockwise)
+        );
+        assert_eq!(
+            chirality("F[C@](Cl)(Br)I", 1),
+            Some(Chirality::CounterClockwise)
+        );
+        assert_eq!(chirality("C[S@](=O)CC", 1), Some(Chirality::Clockwise));
+        // Ring closures take the place where the ring bond digit is written.
+        assert_eq!(chirality("C1C[C@H]1F", 2), chirality("C1C[C@@H](F)1", 2));
+        assert_eq!(chirality("F[C@SP1](Cl)(Br)I", 1), None);
+        assert_eq!(chirality("N[C@@H2]C", 1), None);
+    }
+
+    #[test]
+    fn double_bond_configurations_use_lowest_id_substituents() {
+        let stereo = |smiles: &str, bond: usize| parse_smiles(smiles).unwrap().bonds[bond].stereo;
+        assert_eq!(stereo("F/C=C/F", 1), Some(BondStereo::Trans));
+        assert_eq!(stereo("F/C=C\\F", 1), Some(BondStereo::Cis));
+        assert_eq!(stereo("C(\\F)=C/F", 1), Some(BondStereo::Trans));
+        assert_eq!(stereo("F/C=C(/F)C", 1), Some(BondStereo::Trans));
+        // The methyl C3 outranks F4 as the reference substituent on the far end.
+        assert_eq!(stereo("F/C=C(C)/F", 1), Some(BondStereo::Cis));
+        assert_eq!(stereo("FC=CF", 1), None);
+    }
+
+    #[test]
+    fn syntax_errors_report_their_position() {
+        let error = |smiles| parse_smiles(smiles).unwrap_err();
+        assert!(matches!(error(""), SmilesError::Empty));
+        assert!(matches!(
+            error("C(C"),
+            SmilesError::UnbalancedBranch { position: 3 }
+        ));
+        assert!(matches!(
+            error("CC)"),
+            SmilesError::UnbalancedBranch { position: 2 }
+        ));
+        assert!(matches!(
+            error("C1CC"),
+            SmilesError::UnclosedRing { label: 1 }
+        ));
+        assert!(matches!(
+            error("CC="),
+            SmilesError::DanglingBond { position: 2 }
+        ));
+        assert!(matches!(
+            error("C[Xx]"),
+            SmilesError::UnknownElement { position: 2, symbol } if symbol == "X"
+        ));
+        assert!(matches!(
+            error("C[CH4"),
+            SmilesError::UnclosedBracket { position: 1 }
+        ));
+        assert!(matches!(
+            error("C=1CC-1"),
+            SmilesError::ConflictingRingBond {
+                position: 6,
+                label: 1
+            }
+        ));
+        assert!(matches!(
+            error("C11"),
+            SmilesError::InvalidBond { position: 2, .. }
+        ));
+    }
+}
@@ -0,0 +1,384 @@
+//! Reading of XYZ coordinate files.
+//!
+//! An XYZ file gives an atom count, a comment line, and one `symbol x y z` line per atom, but no
+//! bonds. As in most visualization programs, two atoms are bonded when they are closer than the
+//! sum of their covalent radii plus a tolerance; double and triple bonds are then inferred from
+//! element valences with [`assign_bond_orders`]. Hydrogens must therefore be present in the file.
+//! Only the first frame of a multi-frame trajectory is read, and columns after the coordinates
+//! (as written by extended XYZ writers) are ignored. Deuterium (`D`) and tritium (`T`) are read as
+//! hydrogen with their isotope recorded.
+
+use crate::compat::collections::HashMap;
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::float::Float;
+use crate::compat::prelude::*;
+use crate::core::error::XyzError;
+use crate::core::graph::MolecularGraph;
+use crate::core::properties::{Element, GraphBondOrder};
+use crate::io::parse_element_symbol;
+use crate::perception::assign_bond_orders;
+#[cfg(feature = "std")]
+use std::io::Read;
+
+/// Settings for turning interatomic distances into bonds.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::xyz::XyzOptions;
+///
+/// let options = XyzOptions {
+///     bond_tolerance: 0.3,
+///     ..XyzOptions::DEFAULT
+/// };
+/// assert!(options.infer_bond_orders);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XyzOptions {
+    /// Distance in Å added to the sum of covalent radii below which two atoms are bonded.
+    pub bond_tolerance: f64,
+    /// Distance in Å below which two atoms are treated as overlapping rather than bonded.
+    pub min_bond_length: f64,
+    /// Whether to run [`assign_bond_orders`] on the detected connectivity.
+    pub infer_bond_orders: bool,
+}
+
+impl XyzOptions {
+    /// Default options: a 0.45 Å tolerance, a 0.4 Å overlap cutoff, and bond-order inference.
+    pub const DEFAULT: Self = Self {
+        bond_tolerance: 0.45,
+        min_bond_length: 0.4,
+        infer_bond_orders: true,
+    };
+}
+
+impl Default for XyzOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A structure read from an XYZ file.
+#[derive(Debug, Clone)]
+pub struct XyzStructure {
+    /// Contents of the comment line, trimmed.
+    pub title: String,
+    /// Detected connectivity, carrying the coordinates as [`MolecularGraph::positions`].
+    pub graph: MolecularGraph,
+}
+
+/// Parses the first frame of an XYZ file and assigns bonds from interatomic distances.
+///
+/// # Arguments
+///
+/// * `text` - Contents of the XYZ file.
+/// * `options` - Bond detection settings.
+///
+/// # Returns
+///
+/// The comment line and a graph whose bonds connect every pair of atoms within bonding
+/// distance; a hydrogen keeps only its shortest bond.
+///
+/// # Errors
+///
+/// Returns an [`XyzError`] describing the first malformed line, or
+/// [`XyzError::Truncated`] when the file holds fewer atom lines than announced.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::xyz::{XyzOptions, parse_xyz};
+/// use dreid_typer::{GraphBondOrder, assign_topology};
+///
+/// let xyz = "\
+/// 4
+/// formaldehyde
+/// C   0.000   0.000   0.000
+/// O   1.210   0.000   0.000
+/// H  -0.550   0.940   0.000
+/// H  -0.550  -0.940   0.000
+/// ";
+/// let structure = parse_xyz(xyz, &XyzOptions::DEFAULT).unwrap();
+/// assert_eq!(structure.title, "formaldehyde");
+/// assert_eq!(structure.graph.bonds.len(), 3);
+/// assert_eq!(structure.graph.bonds[0].order, GraphBondOrder::Double);
+///
+/// let topology = assign_topology(&structure.graph).unwrap();
+/// assert_eq!(topology.atoms[0].atom_type, "C_2");
+/// assert_eq!(topology.atoms[1].atom_type, "O_2");
+/// ```
+pub fn parse_xyz(text: &str, options: &XyzOptions) -> Result<XyzStructure, XyzError> {
+    let mut lines = text.lines();
+    let count_line = lines.next().ok_or(XyzError::Truncated { line: 1 })?;
+    let raw_count = count_line.split_whitespace().next().unwrap_or("");
+    let atom_count: usize = raw_count.parse().map_err(|_| XyzError::InvalidField {
+        line: 1,
+        field: "atom count",
+        value: raw_count.to_string(),
+    })?;
+    let title = lines
+        .next()
+        .ok_or(XyzError::Truncated { line: 2 })?
+        .trim()
+        .to_string();
+
+    let mut graph = MolecularGraph::new();
+    let mut positions = Vec::with_capacity(atom_count);
+    for offset in 0..atom_count {
+        let line_number = offset + 3;
+        let line = lines
+            .next()
+            .ok_or(XyzError::Truncated { line: line_number })?;
+        let mut tokens = line.split_whitespace();
+        let symbol = tokens.next().unwrap_or("");
+        let (element, isotope) = parse_element(symbol).ok_or_else(|| XyzError::UnknownElement {
+            line: line_number,
+            symbol: symbol.to_string(),
+        })?;
+
+        let mut position = [0.0; 3];
+        for (coordinate, field) in
+            position
+                .iter_mut()
+                .zip(["x coordinate", "y coordinate", "z coordinate"])
+        {
+            let raw = tokens.next().unwrap_or("");
+            *coordinate = raw
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .ok_or_else(|| XyzError::InvalidField {
+                    line: line_number,
+                    field,
+                    value: raw.to_string(),
+                })?;
+        }
+
+        let id = graph.add_atom(element);
+        graph.atoms[id].isotope = isotope;
+        positions.push(position);
+    }
+
+    let elements: Vec<Element> = graph.atoms.iter().map(|atom| atom.element).collect();
+    for (a, b) in detect_bonds(&elements, &positions, options) {
+        graph
+            .add_bond(a, b, GraphBondOrder::Single)
+            .expect("detected bonds join distinct existing atoms");
+    }
+    graph
+        .set_positions(positions)
+        .expect("one position was read per atom");
+    if options.infer_bond_orders {
+        assign_bond_orders(&mut graph);
+    }
+
+    Ok(XyzStructure { title, graph })
+}
+
+impl MolecularGraph {
+    /// Reads the first frame of an XYZ file into a graph with detected bonds and coordinates.
+    ///
+    /// See [`parse_xyz`] for how bonds are assigned; the comment line is discarded.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the XYZ text, such as an open file.
+    /// * `options` - Bond detection settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`XyzError::Io`] when reading fails, or any error raised by [`parse_xyz`].
+    #[cfg(feature = "std")]
+    pub fn from_xyz(mut reader: impl Read, options: &XyzOptions) -> Result<Self, XyzError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        parse_xyz(&text, options).map(|structure| structure.graph)
+    }
+}
+
+/// Parses an element symbol written in any letter case, ignoring trailing label digits.
+fn parse_element(symbol: &str) -> Option<(Element, Option<u16>)> {
+    let letters: String = symbol
+        .chars()
+        .take_while(char::is_ascii_alphabetic)
+        .collect();
+    let mut chars = letters.chars();
+    let first = chars.next()?;
+    parse_element_symbol(
+        &(first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()),
+    )
+}
+
+/// Returns every atom pair within bonding distance, sorted by atom index.
+///
+/// Atoms are binned into cubic cells no smaller than the longest possible bond, so only atoms in
+/// neighboring cells are compared. Pairs are accepted from the shortest up, and a hydrogen that
+/// already has a bond takes no further ones.
+fn detect_bonds(
+    elements: &[Element],
+    positions: &[[f64; 3]],
+    options: &XyzOptions,
+) -> Vec<(usize, usize)> {
+    let radii: Vec<Option<f64>> = elements
+        .iter()
+        .map(|element| element.covalent_radius())
+        .collect();
+    let Some(max_radius) = radii.iter().flatten().copied().reduce(f64::max) else {
+        return Vec::new();
+    };
+    let cell_size = (2.0 * max_radius + options.bond_tolerance).max(f64::EPSILON);
+    let cell_of = |position: &[f64; 3]| position.map(|c| (c / cell_size).floor() as i64);
+
+    let mut cells: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+    for (atom, position) in positions.iter().enumerate() {
+        if radii[atom].is_some() {
+            cells.entry(cell_of(position)).or_default().push(atom);
+        }
+    }
+
+    let mut pairs = Vec::new();
+    for (atom, position) in positions.iter().enumerate() {
+        let Some(radius) = radii[atom] else {
+            continue;
+        };
+        let [x, y, z] = cell_of(position);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(members) = cells.get(&[x + dx, y + dy, z + dz]) else {
+                        continue;
+                    };
+                    for &other in members.iter().filter(|&&other| other > atom) {
+                        let other_radius = radii[other].expect("binned atoms have a radius");
+                        let distance = (0..3)
+                            .map(|k| (positions[other][k] - position[k]).powi(2))
+                            .sum::<f64>()
+                            .sqrt();
+                        if distance >= options.min_bond_length
+                            && distance <= radius + other_radius + options.bond_tolerance
+                        {
+                            pairs.push((distance, atom, other));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut hydrogen_bonded = vec![false; elements.len()];
+    let mut bonds = Vec::new();
+    for (_, a, b) in pairs {
+        let is_hydrogen = |atom: usize| elements[atom] == Element::H;
+        if (is_hydrogen(a) && hydrogen_bonded[a]) || (is_hydrogen(b) && hydrogen_bonded[b]) {
+            continue;
+        }
+        hydrogen_bonded[a] = true;
+        hydrogen_bonded[b] = true;
+        bonds.push((a, b));
+    }
+    bonds.sort_unstable();
+    bonds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACETONITRILE: &str = "\
+6
+acetonitrile  energy=-132.5
+C   0.000000   0.000000   0.000000
+c   1.460000   0.000000   0.000000
+N1  2.620000   0.000000   0.000000   0.1 0.2 0.3
+H  -0.390000   1.030000   0.000000
+H  -0.390000  -0.515000   0.892000
+H  -0.390000  -0.515000  -0.892000
+3
+second frame is ignored
+He  0.0 0.0 0.0
+";
+
+    #[test]
+    fn reads_first_frame_and_infers_the_nitrile_triple_bond() {
+        let structure = parse_xyz(ACETONITRILE, &XyzOptions::DEFAULT).unwrap();
+        assert_eq!(structure.title, "acetonitrile  energy=-132.5");
+        assert_eq!(structure.graph.atoms.len(), 6);
+        assert_eq!(structure.graph.atoms[1].element, Element::C);
+        assert_eq!(structure.graph.atoms[2].element, Element::N);
+        assert_eq!(
+            structure.graph.positions.as_ref().unwrap()[2],
+            [2.62, 0.0, 0.0]
+        );
+
+        let orders: Vec<_> = structure
+            .graph
+            .bonds
+            .iter()
+            .map(|bond| (bond.atom_ids, bond.order))
+            .collect();
+        assert_eq!(
+            orders,
+            vec![
+                ((0, 1), GraphBondOrder::Single),
+                ((0, 3), GraphBondOrder::Single),
+                ((0, 4), GraphBondOrder::Single),
+                ((0, 5), GraphBondOrder::Single),
+                ((1, 2), GraphBondOrder::Triple),
+            ]
+        );
+
+        #[cfg(feature = "std")]
+        {
+            let options = XyzOptions {
+                infer_bond_orders: false,
+                ..XyzOptions::DEFAULT
+            };
+            let graph = MolecularGraph::from_xyz(ACETONITRILE.as_bytes(), &options).unwrap();
+            assert!(
+                graph
+                    .bonds
+                    .iter()
+                    .all(|bond| bond.order == GraphBondOrder::Single)
+            );
+        }
+    }
+
+    #[test]
+    fn hydrogen_keeps_only_its_shortest_bond() {
+        // A hydrogen squeezed between two oxygens bonds only to the nearer one.
+        let xyz = "3\n\nO 0.0 0.0 0.0\nH 0.97 0.0 0.0\nO 2.05 0.0 0.0\n";
+        let options = XyzOptions {
+            infer_bond_orders: false,
+            ..XyzOptions::DEFAULT
+        };
+        let structure = parse_xyz(xyz, &options).unwrap();
+        let pairs: Vec<_> = structure.graph.bonds.iter().map(|b| b.atom_ids).collect();
+        assert_eq!(pairs, vec![(0, 1)]);
+    }
+
+    #[test]
+    fn reports_malformed_files() {
+        let options = XyzOptions::DEFAULT;
+        assert!(matches!(
+            parse_xyz("two\n\n", &options),
+            Err(XyzError::InvalidField { line: 1, .. })
+        ));
+        assert!(matches!(
+            parse_xyz("2\ntitle\nC 0 0 0\n", &options),
+            Err(XyzError::Truncated { line: 4 })
+        ));
+        assert!(matches!(
+            parse_xyz("1\n\nXx 0 0 0\n", &options),
+            Err(XyzError::UnknownElement { line: 3, .. })
+        ));
+        assert!(matches!(
+            parse_xyz("1\n\nC 0 nan 0\n", &options),
+            Err(XyzError::InvalidField {
+                line: 3,
+                field: "y coordinate",
+                ..
+            })
+        ));
+    }
+}
@@ -60,26 +60,123 @@
 //! assert_eq!(topology.atoms[h_o].atom_type, "H_HB");  // Hydrogen-bonding Hydrogen
 //! assert_eq!(topology.atoms[h_c1_1].atom_type, "H_"); // Standard Hydrogen
 //! ```
+//!
+//! # Feature flags
+//!
+//! The default `std` feature enables everything that needs an operating system: reading from
+//! files and `io::Read` sources, batch processing, and time budgets. Building with
+//! `default-features = false` and the `alloc` feature instead makes the crate `no_std`, keeping
+//! perception, typing, topology building, and the string-based parsers and exporters for plugin
+//! environments that only provide an allocator. The `json`, `mmap`, `tracing`, and `watch`
+//! features imply `std`.
+//!
+//! # Thread safety
+//!
+//! A [`Typer`] or compiled [`rules::RuleSet`] is immutable and `Send + Sync`, and clones share
+//! their compiled rules, so one instance can serve every worker of a thread pool such as
+//! `rayon`'s. Perception scratch buffers come from pools that hand each running call its own
+//! buffer, so concurrent calls neither contend for nor reallocate them per molecule. Every
+//! public result type, including [`MolecularTopology`], [`AnnotatedMolecule`], and
+//! [`TyperError`], is `Send + Sync` as well, which the crate checks at compile time.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+compile_error!("dreid-typer requires either the `std` or the `alloc` feature");
+
+#[cfg_attr(not(feature = "std"), macro_use)]
+extern crate alloc;
+
+// Unit tests use the standard library (threads, temporary files) even in `no_std` builds.
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
 
 mod builder;
+mod compat;
 mod core;
 mod perception;
+mod pipeline;
 mod typing;
 
-pub use crate::core::error::{AssignmentError, GraphValidationError, PerceptionError, TyperError};
+pub mod analytics;
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+pub mod batch;
+pub mod export;
+pub mod features;
+pub mod io;
+pub mod materials;
+pub mod params;
+pub mod polymer;
+pub mod templates;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "watch")]
+pub mod watch;
+
+pub use crate::builder::{TopologySink, build_topology_streaming};
+use crate::compat::prelude::*;
+pub use crate::core::canon::canonicalize;
+pub use crate::core::compact::CompactTopology;
+pub use crate::core::diff::{AtomChange, BondOrderChange, TopologyDiff};
+#[cfg(feature = "watch")]
+pub use crate::core::error::RuleWatchError;
+pub use crate::core::error::{
+    AssignmentError, AtomContext, BatchFileError, BoxedError, CifError, CompactTopologyError,
+    ExportError, GraphValidationError, InterchangeError, LimitExceeded, MsiError, OverrideError,
+    ParameterError, PdbError, PerceptionError, PermutationError, PolymerError, RegistryError,
+    ReplicationError, RuleParseError, SdfError, SelectionError, SmilesError, TemplateError,
+    TyperError, UnmappedTypesError, UnsettledAtom, XyzError,
+};
 pub use crate::core::graph::{AtomNode, BondEdge, MolecularGraph};
+pub use crate::core::limits::{CancellationToken, ResourceLimits};
+pub use crate::core::metadata::{AtomMetadata, ResidueId};
+pub use crate::core::periodic::{ImageFlags, PeriodicMolecularGraph};
 pub use crate::core::properties::{
-    Element, GraphBondOrder, Hybridization, ParseBondOrderError, ParseElementError,
-    ParseHybridizationError, TopologyBondOrder,
+    DreidingType, Element, FunctionalGroup, GraphBondOrder, Hybridization, ParseBondOrderError,
+    ParseElementError, ParseFunctionalGroupError, ParseHybridizationError, TopologyBondOrder,
+};
+pub use crate::core::selection::{AtomSelection, SelectionQuery};
+pub use crate::core::stereo::{BondStereo, Chirality};
+pub use crate::core::substructure::{Mapping, match_substructure};
+pub use crate::core::term_index::TermIndex;
+pub use crate::core::topology::{
+    Angle, AngleClass, Atom, Bond, BondClass, HydrogenBondDonor, HydrogenBondSite, Inversion,
+    InversionClass, InversionPolicy, MolecularTopology, Ring, Torsion, TorsionClass,
+    TypeProvenance, VirtualSite,
+};
+pub use crate::core::validation::{Severity, ValidationIssue, ValidationReport, validate};
+pub use crate::perception::{
+    AdjacencyList, AnnotatedAtom, AnnotatedMolecule, AromaticityModel, CustomStage, MacrocycleMode,
+    NeighborBond, PerceptionOptions, PerceptionPipeline, PerceptionPipelineBuilder,
+    ResonanceSystem, assign_bond_orders,
 };
-pub use crate::core::topology::{Angle, Atom, Bond, Inversion, MolecularTopology, Torsion};
+pub use crate::pipeline::{
+    AssignmentPolicy, IncrementalTyper, Progress, ProgressObserver, Stage, Typer, TypingCache,
+    TypingMode,
+};
+pub use crate::typing::labels::TypeLabelMap;
+pub use crate::typing::overrides::TypingOverrides;
 
 /// Rule parsing and customization utilities.
 ///
 /// The core types needed to parse and inspect DREIDING
 /// atom-typing rules from TOML configuration files.
 pub mod rules {
-    pub use crate::typing::rules::{Conditions, Rule, get_default_rules, parse_rules};
+    pub use crate::typing::diagnostics::{AmbiguousAssignment, AssignmentDiagnostics, RuleMatch};
+    pub use crate::typing::lint::{RuleCoverage, RuleLint, RuleUsage, coverage, lint};
+    pub use crate::typing::packs::{DEFAULT_NAMESPACE, RulePack, compose_rule_packs};
+    pub use crate::typing::registry::{
+        DREIDING_A, DREIDING_DEFAULT, DREIDING_X6, ForceFieldProfile, RuleRegistry,
+    };
+    #[cfg(feature = "json")]
+    pub use crate::typing::rules::parse_rules_json;
+    #[cfg(feature = "yaml")]
+    pub use crate::typing::rules::parse_rules_yaml;
+    pub use crate::typing::rules::{
+        BondCondition, Conditions, NeighborCondition, Rule, RuleFormat, get_default_rules,
+        parse_rules, parse_rules_as,
+    };
+    pub use crate::typing::ruleset::{ConflictResolution, RuleSet, RulesetBuilder};
 }
 
 /// Assigns a full molecular topology using the default embedded DREIDING ruleset.
@@ -110,8 +207,87 @@ pub mod rules {
 /// Panics if the embedded default rules file is malformed, which indicates a
 /// critical library bug.
 pub fn assign_topology(graph: &MolecularGraph) -> Result<MolecularTopology, TyperError> {
-    let default_rules = typing::rules::get_default_rules();
-    assign_topology_internal(graph, default_rules)
+    let default_rules = typing::ruleset::RuleSet::default_dreiding();
+    pipeline::run(graph, &pipeline::RunOptions::new(&default_rules))
+}
+
+/// Assigns DREIDING atom types using the default embedded ruleset, without building a topology.
+///
+/// This runs the perception and typing phases of [`assign_topology`] and stops before bonds,
+/// angles, torsions, and inversions are enumerated. Tools that only need the type labels, such
+/// as charge-assignment schemes, save the time and memory of the topology build on large systems.
+///
+/// # Arguments
+///
+/// * `graph` - A reference to the [`MolecularGraph`] to be processed.
+///
+/// # Returns
+///
+/// The atom type of every atom, indexed like `graph.atoms` and identical to the
+/// [`Atom::atom_type`] values [`assign_topology`] would produce.
+///
+/// # Errors
+///
+/// Returns a [`TyperError`] under the same conditions as [`assign_topology`].
+///
+/// # Panics
+///
+/// Panics if the embedded default rules file is malformed, which indicates a
+/// critical library bug.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, assign_atom_types};
+///
+/// let mut graph = MolecularGraph::new();
+/// let c = graph.add_atom(Element::C);
+/// let o = graph.add_atom(Element::O);
+/// graph.add_bond(c, o, GraphBondOrder::Double).unwrap();
+/// for _ in 0..2 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+/// }
+///
+/// assert_eq!(assign_atom_types(&graph).unwrap(), ["C_2", "O_2", "H_", "H_"]);
+/// ```
+pub fn assign_atom_types(graph: &MolecularGraph) -> Result<Vec<String>, TyperError> {
+    let default_rules = typing::ruleset::RuleSet::default_dreiding();
+    pipeline::run_atom_types(graph, &pipeline::RunOptions::new(&default_rules))
+}
+
+/// Assigns DREIDING types and a topology to a selected sub-region using the default ruleset.
+///
+/// Only the selected atoms and the context within three bonds of them are perceived, with
+/// hydrogens capping the bonds that leave the context, so re-typing a ligand or binding pocket
+/// does not pay for the whole system. Use [`Typer::assign_topology_for_selection`] to change the
+/// depth of the context or any other setting.
+///
+/// # Arguments
+///
+/// * `graph` - A reference to the full [`MolecularGraph`].
+/// * `selection` - The atoms to type.
+///
+/// # Returns
+///
+/// A [`MolecularTopology`] of the selected atoms, numbered in ascending order of their graph IDs,
+/// with the bonded terms whose atoms are all selected.
+///
+/// # Errors
+///
+/// Returns [`TyperError::InvalidInput`] if the selection names an atom missing from `graph`,
+/// and otherwise a [`TyperError`] under the same conditions as [`assign_topology`].
+pub fn assign_topology_for_selection(
+    graph: &MolecularGraph,
+    selection: &AtomSelection,
+) -> Result<MolecularTopology, TyperError> {
+    let default_rules = typing::ruleset::RuleSet::default_dreiding();
+    pipeline::selection::run(
+        graph,
+        selection,
+        pipeline::selection::DEFAULT_CONTEXT_BONDS,
+        &pipeline::RunOptions::new(&default_rules),
+    )
 }
 
 /// Assigns a full molecular topology using a custom set of typing rules.
@@ -129,24 +305,98 @@ pub fn assign_topology(graph: &MolecularGraph) -> Result<MolecularTopology, Type
 /// # Errors
 ///
 /// Returns a [`TyperError`] under the same conditions as [`assign_topology`].
+///
+/// The rules are copied and compiled on every call. When typing many molecules with the same
+/// rules, compile them once into a [`rules::RuleSet`] and call
+/// [`assign_topology_with_rule_set`], or configure a [`Typer`].
 pub fn assign_topology_with_rules(
     graph: &MolecularGraph,
     rules: &[rules::Rule],
 ) -> Result<MolecularTopology, TyperError> {
-    assign_topology_internal(graph, rules)
+    let rules = typing::ruleset::RuleSet::new(rules.to_vec());
+    pipeline::run(graph, &pipeline::RunOptions::new(&rules))
 }
 
-/// Internal core function that executes the perception, typing, and building pipeline.
-fn assign_topology_internal(
+/// Assigns a full molecular topology using a compiled rule set.
+///
+/// Unlike [`assign_topology_with_rules`], which compiles its rule slice on every call, this
+/// borrows a [`rules::RuleSet`] compiled once. A rule set is `Send + Sync` and clones share their
+/// rules, so worker threads can type molecules against one set without copying it per task.
+///
+/// # Arguments
+///
+/// * `graph` - A reference to the [`MolecularGraph`] to be processed.
+/// * `rules` - Compiled rule set that the typing engine will use.
+///
+/// # Errors
+///
+/// Returns a [`TyperError`] under the same conditions as [`assign_topology`].
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::rules::RuleSet;
+/// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, assign_topology_with_rule_set};
+///
+/// let rules = RuleSet::default_dreiding();
+///
+/// let handles: Vec<_> = [Element::O, Element::S]
+///     .into_iter()
+///     .map(|element| {
+///         let rules = rules.clone();
+///         std::thread::spawn(move || {
+///             let mut graph = MolecularGraph::new();
+///             let center = graph.add_atom(element);
+///             for _ in 0..2 {
+///                 let h = graph.add_atom(Element::H);
+///                 graph.add_bond(center, h, GraphBondOrder::Single).unwrap();
+///             }
+///             assign_topology_with_rule_set(&graph, &rules).unwrap()
+///         })
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     assert_eq!(handle.join().unwrap().atoms.len(), 3);
+/// }
+/// ```
+pub fn assign_topology_with_rule_set(
     graph: &MolecularGraph,
-    rules: &[rules::Rule],
+    rules: &rules::RuleSet,
 ) -> Result<MolecularTopology, TyperError> {
-    let annotated_molecule = perception::perceive(graph)?;
-
-    let atom_types = typing::engine::assign_types(&annotated_molecule, rules)
-        .map_err(TyperError::AssignmentFailed)?;
+    pipeline::run(graph, &pipeline::RunOptions::new(rules))
+}
 
-    let topology = builder::build_topology(&annotated_molecule, &atom_types);
+// Typers, rule sets, and everything they return may be moved to and shared between threads;
+// this fails to compile if a change makes any of them `!Send` or `!Sync`.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
 
-    Ok(topology)
-}
+    assert_send_sync::<Typer>();
+    assert_send_sync::<IncrementalTyper>();
+    assert_send_sync::<TypingCache>();
+    assert_send_sync::<PerceptionPipeline>();
+    assert_send_sync::<rules::RuleSet>();
+    assert_send_sync::<rules::RuleRegistry>();
+    assert_send_sync::<rules::AssignmentDiagnostics>();
+    assert_send_sync::<rules::RuleCoverage>();
+    assert_send_sync::<MolecularGraph>();
+    assert_send_sync::<PeriodicMolecularGraph>();
+    assert_send_sync::<AnnotatedMolecule>();
+    assert_send_sync::<MolecularTopology>();
+    assert_send_sync::<CompactTopology>();
+    assert_send_sync::<TopologyDiff>();
+    assert_send_sync::<ValidationReport>();
+    assert_send_sync::<Progress>();
+    assert_send_sync::<Mapping>();
+    assert_send_sync::<TyperError>();
+    assert_send_sync::<AssignmentError>();
+    assert_send_sync::<PerceptionError>();
+    assert_send_sync::<GraphValidationError>();
+    assert_send_sync::<LimitExceeded>();
+    assert_send_sync::<ExportError>();
+    assert_send_sync::<ParameterError>();
+    assert_send_sync::<SmilesError>();
+    assert_send_sync::<PdbError>();
+    assert_send_sync::<SdfError>();
+};
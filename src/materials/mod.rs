@@ -0,0 +1,270 @@
+//! Typing presets for metal-organic frameworks and other porous materials.
+//!
+//! DREIDING is heavily used for metal-organic frameworks (MOFs), but the default organic rules
+//! read a linker carboxylate bound to a metal cluster as an ester-like C(=O)-O-M fragment: one
+//! oxygen types `O_2`, the other `O_3`, and the carboxylate carbon loses its resonance. A preset
+//! pairs [`PerceptionOptions`] suited to the material with a [`RulePack`] for the environments
+//! the default deck does not name, and a typer function such as [`mof_typer`] bundles both with
+//! the other settings models of the material usually want.
+//!
+//! The MOF pack, in the [`MOF_NAMESPACE`], types
+//!
+//! * carboxylate and phosphonate linker oxygens `O_2`, whether terminal, chelating, or bridging,
+//! * oxygens bonded only to metals (μ2-, μ3-, and μ4-oxo bridges) `O_3`, except the planar
+//!   μ3-oxo at the center of the Cr, Fe, Sc, and V trimers of MIL-88, MIL-100, and MIL-101,
+//!   which types `O_2`.
+//!
+//! Metals are typed by element alone, so open metal sites, such as the axial vacancy of a
+//! desolvated copper paddlewheel, type like saturated ones.
+//!
+//! The zeolite pack, in the [`ZEOLITE_NAMESPACE`], names the tetrahedral Si, Al, and P atoms of
+//! silicas, zeolites, and aluminophosphates (`Si3`, `Al3`, `P_3`) and their bridging, Brønsted,
+//! and terminal hydroxyl oxygens (`O_3`), so a deck with framework-specific types can remap them
+//! by name.
+
+use crate::compat::sync::OnceLock;
+use crate::perception::PerceptionOptions;
+use crate::pipeline::Typer;
+use crate::typing::packs::{RulePack, compose_rule_packs};
+use crate::typing::ruleset::RuleSet;
+
+/// Namespace of the MOF rule pack.
+pub const MOF_NAMESPACE: &str = "mof";
+
+/// Perception settings of the MOF preset: functional groups are detected through coordination
+/// bonds, so linker carboxylates keep their resonance.
+pub const MOF_PERCEPTION: PerceptionOptions = PerceptionOptions {
+    metal_bound_groups: true,
+    ..PerceptionOptions::DEFAULT
+};
+
+/// Embedded TOML source of the MOF rule pack.
+const MOF_RULES_TOML: &str = include_str!("../../resources/materials/mof.rules.toml");
+
+/// Lazily parsed MOF rule pack.
+static MOF_RULES: OnceLock<RulePack> = OnceLock::new();
+
+/// Lazily composed default and MOF rules shared by every [`mof_typer`].
+static MOF_RULE_SET: OnceLock<RuleSet> = OnceLock::new();
+
+/// Namespace of the zeolite rule pack.
+pub const ZEOLITE_NAMESPACE: &str = "zeolite";
+
+/// Perception settings of the zeolite preset: carbon-free frameworks skip aromaticity and
+/// resonance perception.
+pub const ZEOLITE_PERCEPTION: PerceptionOptions = PerceptionOptions {
+    skip_inorganic_conjugation: true,
+    ..PerceptionOptions::DEFAULT
+};
+
+/// Embedded TOML source of the zeolite rule pack.
+const ZEOLITE_RULES_TOML: &str = include_str!("../../resources/materials/zeolite.rules.toml");
+
+/// Lazily parsed zeolite rule pack.
+static ZEOLITE_RULES: OnceLock<RulePack> = OnceLock::new();
+
+/// Lazily composed default and zeolite rules shared by every [`zeolite_typer`].
+static ZEOLITE_RULE_SET: OnceLock<RuleSet> = OnceLock::new();
+
+/// Returns the MOF rule pack, meant to be composed above the default DREIDING rules.
+///
+/// # Panics
+///
+/// Panics if the embedded MOF rules file is malformed, which indicates a critical library bug.
+pub fn mof_rule_pack() -> RulePack {
+    embedded_pack(&MOF_RULES, MOF_RULES_TOML)
+}
+
+/// Creates a typer for metal-organic frameworks.
+///
+/// The typer composes [`mof_rule_pack`] above the default DREIDING rules, perceives with
+/// [`MOF_PERCEPTION`], gives metals without a DREIDING type (Zr, Cr, Hf, ...) their generic UFF
+/// type, and leaves out torsions through coordination bonds. Every setting can still be changed
+/// with the usual `Typer::with_*` methods.
+///
+/// # Panics
+///
+/// Panics if an embedded rules file is malformed, which indicates a critical library bug.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::materials::mof_typer;
+/// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, Typer};
+///
+/// // Zinc bound to one oxygen of a formate.
+/// let mut graph = MolecularGraph::new();
+/// let zn = graph.add_atom(Element::Zn);
+/// let c = graph.add_atom(Element::C);
+/// let o_bound = graph.add_atom(Element::O);
+/// let o_free = graph.add_atom(Element::O);
+/// let h = graph.add_atom(Element::H);
+/// graph.add_bond(zn, o_bound, GraphBondOrder::Single).unwrap();
+/// graph.add_bond(c, o_bound, GraphBondOrder::Single).unwrap();
+/// graph.add_bond(c, o_free, GraphBondOrder::Double).unwrap();
+/// graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+///
+/// let organic = Typer::new().assign_topology(&graph).unwrap();
+/// assert_eq!(organic.atoms[o_bound].atom_type, "O_3");
+///
+/// let mof = mof_typer().assign_topology(&graph).unwrap();
+/// assert_eq!(mof.atoms[c].atom_type, "C_R");
+/// assert_eq!(mof.atoms[o_bound].atom_type, "O_2");
+/// assert_eq!(mof.atoms[o_free].atom_type, "O_2");
+/// ```
+pub fn mof_typer() -> Typer {
+    Typer::with_rule_set(composed_rule_set(&MOF_RULE_SET, mof_rule_pack))
+        .with_perception_options(MOF_PERCEPTION)
+        .with_uff_fallback(true)
+        .with_coordination_torsion_exclusion(true)
+}
+
+/// Returns the zeolite rule pack, meant to be composed above the default DREIDING rules.
+///
+/// # Panics
+///
+/// Panics if the embedded zeolite rules file is malformed, which indicates a critical library
+/// bug.
+pub fn zeolite_rule_pack() -> RulePack {
+    embedded_pack(&ZEOLITE_RULES, ZEOLITE_RULES_TOML)
+}
+
+/// Creates a typer for zeolites, silicas, and aluminophosphates.
+///
+/// The typer composes [`zeolite_rule_pack`] above the default DREIDING rules and perceives with
+/// [`ZEOLITE_PERCEPTION`], so large periodic frameworks do not pay for a Hückel analysis of
+/// every T-O ring. Organic templates and other guests with carbon are still perceived in full.
+///
+/// # Panics
+///
+/// Panics if an embedded rules file is malformed, which indicates a critical library bug.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::materials::zeolite_typer;
+/// use dreid_typer::{Element, GraphBondOrder, MolecularGraph};
+///
+/// // Disilicic acid, (HO)3Si-O-Si(OH)3.
+/// let mut graph = MolecularGraph::new();
+/// let bridge = graph.add_atom(Element::O);
+/// for _ in 0..2 {
+///     let si = graph.add_atom(Element::Si);
+///     graph.add_bond(si, bridge, GraphBondOrder::Single).unwrap();
+///     for _ in 0..3 {
+///         let o = graph.add_atom(Element::O);
+///         let h = graph.add_atom(Element::H);
+///         graph.add_bond(si, o, GraphBondOrder::Single).unwrap();
+///         graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+///     }
+/// }
+///
+/// let topology = zeolite_typer().assign_topology(&graph).unwrap();
+/// assert_eq!(topology.atoms[bridge].atom_type, "O_3");
+/// assert_eq!(topology.atoms[1].atom_type, "Si3");
+/// ```
+pub fn zeolite_typer() -> Typer {
+    Typer::with_rule_set(composed_rule_set(&ZEOLITE_RULE_SET, zeolite_rule_pack))
+        .with_perception_options(ZEOLITE_PERCEPTION)
+}
+
+/// Parses an embedded rule pack on first use and returns a copy.
+fn embedded_pack(cell: &OnceLock<RulePack>, toml: &str) -> RulePack {
+    cell.get_or_init(|| {
+        RulePack::parse(toml)
+            .expect("Failed to parse embedded materials rules. This is a library bug.")
+    })
+    .clone()
+}
+
+/// Composes a preset pack above the default rules on first use and returns the shared set.
+fn composed_rule_set(cell: &OnceLock<RuleSet>, pack: fn() -> RulePack) -> RuleSet {
+    cell.get_or_init(|| RuleSet::new(compose_rule_packs(&[RulePack::default_dreiding(), pack()])))
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat::prelude::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::properties::{Element, GraphBondOrder};
+
+    #[test]
+    fn mof_pack_parses_into_its_namespace() {
+        let pack = mof_rule_pack();
+        assert_eq!(pack.namespace, MOF_NAMESPACE);
+        assert!(pack.rules.iter().any(|rule| rule.name == "O_Mu4_Oxo"));
+    }
+
+    #[test]
+    fn mof_typers_share_one_rule_set() {
+        let first = mof_typer();
+        let second = mof_typer();
+        assert!(first.rule_set().ptr_eq(second.rule_set()));
+        assert_eq!(first.perception_options(), &MOF_PERCEPTION);
+        assert!(first.uff_fallback());
+        assert!(first.coordination_torsion_exclusion());
+    }
+
+    #[test]
+    fn oxo_bridges_follow_cluster_geometry() {
+        let bridge = |metal: Element, count: usize| {
+            let mut graph = MolecularGraph::new();
+            let o = graph.add_atom(Element::O);
+            for _ in 0..count {
+                let m = graph.add_atom(metal);
+                graph.add_bond(o, m, GraphBondOrder::Single).unwrap();
+            }
+            let topology = mof_typer().assign_topology(&graph).unwrap();
+            topology.atoms[o].atom_type.to_string()
+        };
+
+        assert_eq!(bridge(Element::Zn, 4), "O_3");
+        assert_eq!(bridge(Element::Zr, 3), "O_3");
+        assert_eq!(bridge(Element::Cr, 3), "O_2");
+        assert_eq!(bridge(Element::Ti, 2), "O_3");
+    }
+
+    #[test]
+    fn zeolite_rules_type_and_name_aluminosilicate_sites() {
+        // Bridging hydroxyl of a Brønsted acid site between (HO)3Si and Al(OH)3.
+        let mut graph = MolecularGraph::new();
+        let si = graph.add_atom(Element::Si);
+        let al = graph.add_atom(Element::Al);
+        let bridge = graph.add_atom(Element::O);
+        let proton = graph.add_atom(Element::H);
+        graph.add_bond(si, bridge, GraphBondOrder::Single).unwrap();
+        graph.add_bond(al, bridge, GraphBondOrder::Single).unwrap();
+        graph
+            .add_bond(bridge, proton, GraphBondOrder::Single)
+            .unwrap();
+        for t in [si, al, si] {
+            let o = graph.add_atom(Element::O);
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(t, o, GraphBondOrder::Single).unwrap();
+            graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+        }
+
+        let topology = zeolite_typer().assign_topology(&graph).unwrap();
+        let types: Vec<String> = topology
+            .atoms
+            .iter()
+            .map(|atom| atom.atom_type.to_string())
+            .collect();
+        assert_eq!(&types[..5], ["Si3", "Al3", "O_3", "H_HB", "O_3"]);
+
+        let mut pack = zeolite_rule_pack();
+        for rule in &mut pack.rules {
+            if rule.name == "O_Bronsted_Acid_Site" {
+                rule.result_type = "O_3z".to_string();
+            }
+        }
+        let remapped = Typer::with_rule_packs(&[RulePack::default_dreiding(), pack])
+            .with_perception_options(ZEOLITE_PERCEPTION)
+            .assign_topology(&graph)
+            .unwrap();
+        assert_eq!(remapped.atoms[bridge].atom_type, "O_3z");
+        assert_eq!(remapped.atoms[4].atom_type, "O_3");
+    }
+}
@@ -0,0 +1,569 @@
+//! Derivation of numeric DREIDING parameters for every term of a typed topology.
+//!
+//! Bonded parameters are built from the per-type values of a [`ParameterTable`] with the
+//! combination rules of the original paper:
+//!
+//! * bonds: `R₀ = Rᵢ + Rⱼ − 0.01 Å`, `k = 700 n`, `D = 70 n` for bond order `n` (1.5 if
+//!   resonant);
+//! * angles: `θ₀` of the central type and `K = 100`;
+//! * torsions: barrier, periodicity, and phase of the torsion's [`TorsionClass`] (cases
+//!   (a)–(j) of the paper), divided by the number of torsions sharing the central bond;
+//! * inversions: `K = 40` shared by the inversions of a planar center (three by default),
+//!   `ψ₀ = 0`.
+
+use super::forms::EnergyExpression;
+use super::tables::{
+    ANGLE_FORCE_CONSTANT, AtomParameters, BOND_DISSOCIATION_ENERGY, BOND_FORCE_CONSTANT,
+    BOND_RADIUS_CORRECTION, HYDROGEN_BOND_DISTANCE, HYDROGEN_BOND_WELL_DEPTH,
+    INVERSION_FORCE_CONSTANT, ParameterTable,
+};
+use super::variant::ForceFieldVariant;
+use crate::compat::collections::HashMap;
+use crate::compat::prelude::*;
+use crate::core::error::ParameterError;
+use crate::core::properties::TopologyBondOrder;
+use crate::core::topology::{InversionClass, MolecularTopology, TorsionClass};
+
+/// Parameters of one bond-stretch term.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BondParameters {
+    /// Equilibrium length `R₀` in Å.
+    pub r0: f64,
+    /// Harmonic force constant `k` in kcal/mol/Å².
+    pub k: f64,
+    /// Morse dissociation energy `D` in kcal/mol.
+    pub d0: f64,
+}
+
+/// Parameters of one angle-bend term.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AngleParameters {
+    /// Natural angle `θ₀` in degrees.
+    pub theta0: f64,
+    /// Force constant `K` in kcal/mol/rad².
+    pub k: f64,
+}
+
+/// Parameters of one proper torsion term.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TorsionParameters {
+    /// Barrier `V` in kcal/mol, already divided among the torsions of the central bond.
+    pub v: f64,
+    /// Periodicity `n`.
+    pub n: u8,
+    /// Phase `φ₀` in degrees.
+    pub phi0: f64,
+}
+
+/// Parameters of one inversion term.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InversionParameters {
+    /// Equilibrium out-of-plane angle `ψ₀` in degrees.
+    pub psi0: f64,
+    /// Force constant `K` in kcal/mol/rad², already divided among the inversions of the center.
+    pub k: f64,
+}
+
+/// Parameters of the explicit hydrogen-bond term.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HydrogenBondParameters {
+    /// Well depth `D_hb` in kcal/mol.
+    pub d_hb: f64,
+    /// Equilibrium donor-acceptor distance `R_hb` in Å.
+    pub r_hb: f64,
+}
+
+/// A typed topology together with numeric parameters for every term.
+///
+/// Every parameter list is aligned with the corresponding list of [`Self::topology`]: entry `i`
+/// of `bonds` parameterizes `topology.bonds[i]`, and so on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterizedTopology {
+    /// The topology the parameters refer to.
+    pub topology: MolecularTopology,
+    /// Energy expression and units the parameter values are meant for.
+    pub expression: EnergyExpression,
+    /// Per-atom parameters, including van der Waals terms.
+    pub atoms: Vec<AtomParameters>,
+    /// Bond-stretch parameters.
+    pub bonds: Vec<BondParameters>,
+    /// Angle-bend parameters.
+    pub angles: Vec<AngleParameters>,
+    /// Proper torsion parameters.
+    pub torsions: Vec<TorsionParameters>,
+    /// Inversion parameters.
+    pub inversions: Vec<InversionParameters>,
+    /// Explicit hydrogen-bond parameters for donor-acceptor pairs.
+    pub hydrogen_bond: HydrogenBondParameters,
+}
+
+/// Assigns DREIDING parameters to every term of a typed topology.
+///
+/// # Arguments
+///
+/// * `topology` - Topology typed with DREIDING labels.
+///
+/// # Returns
+///
+/// The topology paired with per-atom and per-term parameters in DREIDING units.
+///
+/// # Errors
+///
+/// Returns [`ParameterError::UnknownAtomType`] if an atom carries a label outside the embedded
+/// DREIDING tables (for example a UFF fallback label); use [`ParameterTable::assign`] with an
+/// extended table to cover such types.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::params::assign_parameters;
+/// use dreid_typer::{assign_topology, Element, GraphBondOrder, MolecularGraph};
+///
+/// let mut graph = MolecularGraph::new();
+/// let c1 = graph.add_atom(Element::C);
+/// let c2 = graph.add_atom(Element::C);
+/// graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+/// for carbon in [c1, c2] {
+///     for _ in 0..3 {
+///         let h = graph.add_atom(Element::H);
+///         graph.add_bond(carbon, h, GraphBondOrder::Single).unwrap();
+///     }
+/// }
+///
+/// let params = assign_parameters(&assign_topology(&graph).unwrap()).unwrap();
+/// let cc = params.topology.bonds.iter().position(|b| b.atom_ids == (0, 1)).unwrap();
+/// assert!((params.bonds[cc].r0 - 1.53).abs() < 1e-9);
+/// assert_eq!(params.torsions.len(), 9);
+/// assert!((params.torsions[0].v - 2.0 / 9.0).abs() < 1e-9);
+/// ```
+pub fn assign_parameters(
+    topology: &MolecularTopology,
+) -> Result<ParameterizedTopology, ParameterError> {
+    ParameterTable::dreiding().assign(topology)
+}
+
+/// Assigns parameters of a specific DREIDING variant to every term of a typed topology.
+///
+/// The topology should be typed with the same variant (see
+/// [`Typer::with_variant`](crate::Typer::with_variant)); the variant decides the reported
+/// energy expression and whether an explicit hydrogen-bond term is parameterized.
+///
+/// # Arguments
+///
+/// * `topology` - Topology typed with DREIDING labels.
+/// * `variant` - Variant whose energy expression the parameters are meant for.
+///
+/// # Errors
+///
+/// Returns the same errors as [`assign_parameters`].
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::params::{assign_variant_parameters, ForceFieldVariant, HydrogenBondForm};
+/// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, Typer};
+///
+/// let mut graph = MolecularGraph::new();
+/// let o = graph.add_atom(Element::O);
+/// for _ in 0..2 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+/// }
+///
+/// let variant = ForceFieldVariant::DreidingA;
+/// let topology = Typer::new().with_variant(variant).assign_topology(&graph).unwrap();
+/// assert_eq!(topology.atoms[1].atom_type, "H_");
+///
+/// let params = assign_variant_parameters(&topology, variant).unwrap();
+/// assert_eq!(params.expression.hydrogen_bond, HydrogenBondForm::Disabled);
+/// assert_eq!(params.hydrogen_bond.d_hb, 0.0);
+/// ```
+pub fn assign_variant_parameters(
+    topology: &MolecularTopology,
+    variant: ForceFieldVariant,
+) -> Result<ParameterizedTopology, ParameterError> {
+    ParameterTable::dreiding().assign_variant(topology, variant)
+}
+
+impl ParameterTable {
+    /// Assigns parameters from this table to every term of a typed topology.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Topology whose labels are all defined in this table.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError::UnknownAtomType`] for the first atom whose label is missing.
+    pub fn assign(
+        &self,
+        topology: &MolecularTopology,
+    ) -> Result<ParameterizedTopology, ParameterError> {
+        self.assign_variant(topology, ForceFieldVariant::Dreiding)
+    }
+
+    /// Assigns parameters from this table for a specific DREIDING variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` - Topology whose labels are all defined in this table.
+    /// * `variant` - Variant whose energy expression the parameters are meant for.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParameterError::UnknownAtomType`] for the first atom whose label is missing.
+    pub fn assign_variant(
+        &self,
+        topology: &MolecularTopology,
+        variant: ForceFieldVariant,
+    ) -> Result<ParameterizedTopology, ParameterError> {
+        let atoms = topology
+            .atoms
+            .iter()
+            .map(|atom| {
+                self.get(atom.atom_type.as_str()).copied().ok_or_else(|| {
+                    ParameterError::UnknownAtomType {
+                        atom_id: atom.id,
+                        atom_type: atom.atom_type.to_string(),
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let bonds = topology
+            .bonds
+            .iter()
+            .map(|bond| {
+                let (a, b) = bond.atom_ids;
+                let order = bond_order(bond.order);
+                BondParameters {
+                    r0: atoms[a].bond_radius + atoms[b].bond_radius - BOND_RADIUS_CORRECTION,
+                    k: BOND_FORCE_CONSTANT * order,
+                    d0: BOND_DISSOCIATION_ENERGY * order,
+                }
+            })
+            .collect();
+
+        let angles = topology
+            .angles
+            .iter()
+            .map(|angle| AngleParameters {
+                theta0: atoms[angle.atom_ids.1].bond_angle,
+                k: ANGLE_FORCE_CONSTANT,
+            })
+            .collect();
+
+        let torsions = torsion_parameters(topology);
+        let inversions = inversion_parameters(topology);
+
+        let expression = variant.expression();
+        let hydrogen_bond = HydrogenBondParameters {
+            d_hb: if variant.uses_hydrogen_bond_type() {
+                HYDROGEN_BOND_WELL_DEPTH
+            } else {
+                0.0
+            },
+            r_hb: HYDROGEN_BOND_DISTANCE,
+        };
+
+        Ok(ParameterizedTopology {
+            topology: topology.clone(),
+            expression,
+            atoms,
+            bonds,
+            angles,
+            torsions,
+            inversions,
+            hydrogen_bond,
+        })
+    }
+}
+
+/// Returns the bond order used to scale force constants.
+fn bond_order(order: TopologyBondOrder) -> f64 {
+    match order {
+        TopologyBondOrder::Single => 1.0,
+        TopologyBondOrder::Double => 2.0,
+        TopologyBondOrder::Triple => 3.0,
+        TopologyBondOrder::Resonant => 1.5,
+    }
+}
+
+/// Splits the DREIDING torsion barrier of each central bond among its torsions.
+fn torsion_parameters(topology: &MolecularTopology) -> Vec<TorsionParameters> {
+    topology
+        .torsions
+        .iter()
+        .map(|torsion| {
+            let (v, n, phi0) = torsion_rule(torsion.class);
+            TorsionParameters {
+                v: v / torsion.shared_count.max(1) as f64,
+                n,
+                phi0,
+            }
+        })
+        .collect()
+}
+
+/// Returns the total barrier, periodicity, and phase of a torsion case.
+fn torsion_rule(class: TorsionClass) -> (f64, u8, f64) {
+    match class {
+        TorsionClass::Sp3Sp3 => (2.0, 3, 180.0),
+        TorsionClass::Sp3Sp2 => (1.0, 6, 0.0),
+        TorsionClass::Double => (45.0, 2, 180.0),
+        TorsionClass::Resonant => (25.0, 2, 180.0),
+        TorsionClass::Sp2Sp2Single => (5.0, 2, 180.0),
+        TorsionClass::Exocyclic => (10.0, 2, 180.0),
+        TorsionClass::Linear | TorsionClass::Unhybridized => (0.0, 1, 0.0),
+        TorsionClass::OxygenPair => (2.0, 2, 90.0),
+        TorsionClass::OxygenSp2 => (2.0, 2, 180.0),
+        TorsionClass::ConjugatedSp3Sp2 => (2.0, 3, 180.0),
+    }
+}
+
+/// Shares the planar inversion constant among the inversions of each center.
+fn inversion_parameters(topology: &MolecularTopology) -> Vec<InversionParameters> {
+    let mut per_center: HashMap<usize, usize> = HashMap::new();
+    for inversion in &topology.inversions {
+        *per_center.entry(inversion.atom_ids.0).or_default() += 1;
+    }
+
+    topology
+        .inversions
+        .iter()
+        .map(|inversion| {
+            let center = inversion.atom_ids.0;
+            InversionParameters {
+                psi0: 0.0,
+                k: match inversion.class {
+                    InversionClass::Planar => INVERSION_FORCE_CONSTANT / per_center[&center] as f64,
+                    InversionClass::NonPlanar => 0.0,
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::properties::{Element, GraphBondOrder};
+    use crate::pipeline::{Typer, TypingMode};
+
+    fn typed(build: impl FnOnce(&mut MolecularGraph)) -> MolecularTopology {
+        let mut graph = MolecularGraph::new();
+        build(&mut graph);
+        Typer::new().assign_topology(&graph).unwrap()
+    }
+
+    fn add_hydrogens(graph: &mut MolecularGraph, atom: usize, count: usize) {
+        for _ in 0..count {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(atom, h, GraphBondOrder::Single).unwrap();
+        }
+    }
+
+    fn torsion_about(params: &ParameterizedTopology, a: usize, b: usize) -> TorsionParameters {
+        let index = params
+            .topology
+            .torsions
+            .iter()
+            .position(|t| {
+                let (_, j, k, _) = t.atom_ids;
+                (j, k) == (a, b) || (j, k) == (b, a)
+            })
+            .expect("torsion about bond");
+        params.torsions[index]
+    }
+
+    #[test]
+    fn ethylene_double_bond_uses_rule_c_and_planar_inversions() {
+        let topology = typed(|g| {
+            let c1 = g.add_atom(Element::C);
+            let c2 = g.add_atom(Element::C);
+            g.add_bond(c1, c2, GraphBondOrder::Double).unwrap();
+            add_hydrogens(g, c1, 2);
+            add_hydrogens(g, c2, 2);
+        });
+        let params = assign_parameters(&topology).unwrap();
+
+        let cc = params
+            .topology
+            .bonds
+            .iter()
+            .position(|b| b.atom_ids == (0, 1))
+            .unwrap();
+        assert!((params.bonds[cc].r0 - 1.33).abs() < 1e-9);
+        assert_eq!(params.bonds[cc].k, 1400.0);
+        assert_eq!(params.bonds[cc].d0, 140.0);
+
+        let torsion = torsion_about(&params, 0, 1);
+        assert_eq!((torsion.v, torsion.n, torsion.phi0), (45.0 / 4.0, 2, 180.0));
+
+        assert_eq!(params.inversions.len(), 6);
+        assert!(
+            params
+                .inversions
+                .iter()
+                .all(|i| (i.k - 40.0 / 3.0).abs() < 1e-9)
+        );
+        assert!(
+            params
+                .angles
+                .iter()
+                .all(|a| a.theta0 == 120.0 && a.k == 100.0)
+        );
+    }
+
+    #[test]
+    fn methanol_and_propene_follow_special_torsion_rules() {
+        let methanol = typed(|g| {
+            let c = g.add_atom(Element::C);
+            let o = g.add_atom(Element::O);
+            g.add_bond(c, o, GraphBondOrder::Single).unwrap();
+            add_hydrogens(g, c, 3);
+            add_hydrogens(g, o, 1);
+        });
+        let params = assign_parameters(&methanol).unwrap();
+        let torsion = torsion_about(&params, 0, 1);
+        assert_eq!((torsion.n, torsion.phi0), (3, 180.0));
+        assert!((torsion.v - 2.0 / 3.0).abs() < 1e-9);
+
+        let propene = typed(|g| {
+            let c1 = g.add_atom(Element::C);
+            let c2 = g.add_atom(Element::C);
+            let c3 = g.add_atom(Element::C);
+            g.add_bond(c1, c2, GraphBondOrder::Double).unwrap();
+            g.add_bond(c2, c3, GraphBondOrder::Single).unwrap();
+            add_hydrogens(g, c1, 2);
+            add_hydrogens(g, c2, 1);
+            add_hydrogens(g, c3, 3);
+        });
+        let params = assign_parameters(&propene).unwrap();
+        let torsion = torsion_about(&params, 1, 2);
+        assert_eq!((torsion.n, torsion.phi0), (3, 180.0));
+    }
+
+    #[test]
+    fn biphenyl_link_uses_the_exocyclic_rule() {
+        let biphenyl = typed(|g| {
+            let rings: Vec<Vec<_>> = (0..2)
+                .map(|_| (0..6).map(|_| g.add_atom(Element::C)).collect())
+                .collect();
+            for ring in &rings {
+                for i in 0..6 {
+                    let order = if i % 2 == 0 {
+                        GraphBondOrder::Double
+                    } else {
+                        GraphBondOrder::Single
+                    };
+                    g.add_bond(ring[i], ring[(i + 1) % 6], order).unwrap();
+                }
+                for &c in &ring[1..] {
+                    add_hydrogens(g, c, 1);
+                }
+            }
+            g.add_bond(rings[0][0], rings[1][0], GraphBondOrder::Single)
+                .unwrap();
+        });
+        let class_about = |a: usize, b: usize| {
+            biphenyl
+                .torsions
+                .iter()
+                .find(|t| t.central_bond() == (a, b))
+                .unwrap()
+                .class
+        };
+        assert_eq!(class_about(0, 6), TorsionClass::Exocyclic);
+        assert_eq!(TorsionClass::Exocyclic.case(), 'f');
+        assert_eq!(class_about(0, 1), TorsionClass::Resonant);
+
+        let params = assign_parameters(&biphenyl).unwrap();
+        let torsion = torsion_about(&params, 0, 6);
+        assert_eq!((torsion.n, torsion.phi0), (2, 180.0));
+        assert!((torsion.v - 10.0 / 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unknown_labels_are_reported() {
+        let mut topology = typed(|g| {
+            let f = g.add_atom(Element::F);
+            add_hydrogens(g, f, 1);
+        });
+        topology.atoms[0].atom_type = "F_custom".into();
+
+        let err = assign_parameters(&topology).unwrap_err();
+        assert_eq!(
+            err,
+            ParameterError::UnknownAtomType {
+                atom_id: 0,
+                atom_type: "F_custom".to_string(),
+            }
+        );
+
+        let mut table = ParameterTable::dreiding().clone();
+        table.insert("F_custom", *table.get("F_").unwrap());
+        let params = table.assign(&topology).unwrap();
+        assert!((params.bonds[0].r0 - 0.931).abs() < 1e-9);
+    }
+
+    #[test]
+    fn united_atom_topologies_are_parameterized() {
+        let mut graph = MolecularGraph::new();
+        let c = graph.add_atom(Element::C);
+        let o = graph.add_atom(Element::O);
+        let ring: Vec<_> = (0..6).map(|_| graph.add_atom(Element::C)).collect();
+        graph.add_bond(c, o, GraphBondOrder::Single).unwrap();
+        graph.add_bond(c, ring[0], GraphBondOrder::Single).unwrap();
+        for i in 0..6 {
+            let order = if i % 2 == 0 {
+                GraphBondOrder::Double
+            } else {
+                GraphBondOrder::Single
+            };
+            graph.add_bond(ring[i], ring[(i + 1) % 6], order).unwrap();
+        }
+        add_hydrogens(&mut graph, c, 2);
+        add_hydrogens(&mut graph, o, 1);
+        for &atom in &ring[1..] {
+            add_hydrogens(&mut graph, atom, 1);
+        }
+
+        let topology = Typer::new()
+            .with_typing_mode(TypingMode::UnitedAtom)
+            .assign_topology(&graph)
+            .unwrap();
+        assert_eq!(topology.atoms[c].atom_type, "C_32");
+        assert_eq!(topology.atoms[ring[1]].atom_type, "C_R1");
+
+        let params = assign_parameters(&topology).unwrap();
+        assert_eq!(params.atoms.len(), topology.atoms.len());
+        assert_eq!(params.atoms[c].vdw.d0, 0.1984);
+        assert_eq!(params.atoms[ring[1]].vdw.r0, 4.23);
+        let bond = params
+            .topology
+            .bonds
+            .iter()
+            .position(|bond| bond.atom_ids == (c, ring[0]))
+            .unwrap();
+        assert!((params.bonds[bond].r0 - (0.770 + 0.700 - 0.01)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn variants_select_expression_and_hydrogen_bond_term() {
+        let topology = typed(|g| {
+            let o = g.add_atom(Element::O);
+            add_hydrogens(g, o, 2);
+        });
+
+        let x6 = assign_variant_parameters(&topology, ForceFieldVariant::X6).unwrap();
+        assert_eq!(x6.expression, ForceFieldVariant::X6.expression());
+        assert_eq!(x6.hydrogen_bond.d_hb, HYDROGEN_BOND_WELL_DEPTH);
+        assert_eq!(x6.atoms, assign_parameters(&topology).unwrap().atoms);
+
+        let a = assign_variant_parameters(&topology, ForceFieldVariant::DreidingA).unwrap();
+        assert_eq!(a.hydrogen_bond.d_hb, 0.0);
+        assert_eq!(a.bonds, x6.bonds);
+    }
+}
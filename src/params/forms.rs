@@ -0,0 +1,435 @@
+//! Functional forms and units of the DREIDING energy expression.
+//!
+//! The defaults follow Mayo, Olafson & Goddard (1990): harmonic bonds, cosine-harmonic angles,
+//! cosine torsions, umbrella inversions, 12-6 Lennard-Jones van der Waals, and a 12-10
+//! hydrogen-bond term, with energies in kcal/mol, lengths in Å, and angles in degrees.
+
+use crate::compat::prelude::*;
+use core::f64::consts::PI;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// Functional form of bond-stretch terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BondForm {
+    /// `E = ½ k (R − R₀)²`.
+    Harmonic,
+    /// `E = D [e^{−α(R − R₀)} − 1]²` with `α = √(k / 2D)`.
+    Morse,
+}
+
+impl BondForm {
+    /// Returns the energy expression as a human-readable formula.
+    pub fn formula(self) -> &'static str {
+        match self {
+            BondForm::Harmonic => "E = 1/2 k (R - R0)^2",
+            BondForm::Morse => "E = D [exp(-a (R - R0)) - 1]^2, a = sqrt(k / 2D)",
+        }
+    }
+}
+
+/// Functional form of angle-bend terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AngleForm {
+    /// `E = ½ C (cos θ − cos θ₀)²` with `C = K / sin² θ₀`, or `E = K (1 + cos θ)` for
+    /// linear centers.
+    CosineHarmonic,
+    /// `E = ½ K (θ − θ₀)²`.
+    Harmonic,
+}
+
+impl AngleForm {
+    /// Returns the energy expression as a human-readable formula.
+    pub fn formula(self) -> &'static str {
+        match self {
+            AngleForm::CosineHarmonic => {
+                "E = 1/2 C (cos(theta) - cos(theta0))^2, C = K / sin^2(theta0)"
+            }
+            AngleForm::Harmonic => "E = 1/2 K (theta - theta0)^2",
+        }
+    }
+}
+
+/// Functional form of proper torsion terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TorsionForm {
+    /// `E = ½ V [1 − cos(n (φ − φ₀))]`, with the barrier divided by the number of torsions
+    /// sharing the central bond.
+    Cosine,
+}
+
+impl TorsionForm {
+    /// Returns the energy expression as a human-readable formula.
+    pub fn formula(self) -> &'static str {
+        match self {
+            TorsionForm::Cosine => "E = 1/2 V [1 - cos(n (phi - phi0))]",
+        }
+    }
+}
+
+/// Functional form of inversion (out-of-plane) terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InversionForm {
+    /// `E = ½ C (cos ψ − cos ψ₀)²`, reducing to `E = K (1 − cos ψ)` for planar centers.
+    Umbrella,
+}
+
+impl InversionForm {
+    /// Returns the energy expression as a human-readable formula.
+    pub fn formula(self) -> &'static str {
+        match self {
+            InversionForm::Umbrella => {
+                "E = 1/2 C (cos(psi) - cos(psi0))^2, E = K (1 - cos(psi)) if psi0 = 0"
+            }
+        }
+    }
+}
+
+/// Functional form of van der Waals terms.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VdwForm {
+    /// `E = D₀ [(R₀/R)¹² − 2 (R₀/R)⁶]`.
+    LennardJones,
+    /// `E = D₀ [6/(ζ − 6) e^{ζ(1 − R/R₀)} − ζ/(ζ − 6) (R₀/R)⁶]`,
+    /// where the shape parameter `ζ` sets the steepness of the repulsive wall.
+    Exponential6 {
+        /// Dimensionless steepness of the repulsive wall.
+        zeta: f64,
+    },
+}
+
+impl VdwForm {
+    /// Exponential-6 form with the DREIDING default shape parameter `ζ = 12.0`.
+    pub const DREIDING_X6: VdwForm = VdwForm::Exponential6 { zeta: 12.0 };
+
+    /// Returns the energy expression as a human-readable formula.
+    pub fn formula(self) -> &'static str {
+        match self {
+            VdwForm::LennardJones => "E = D0 [(R0/R)^12 - 2 (R0/R)^6]",
+            VdwForm::Exponential6 { .. } => {
+                "E = D0 [6/(zeta-6) exp(zeta (1 - R/R0)) - zeta/(zeta-6) (R0/R)^6]"
+            }
+        }
+    }
+}
+
+/// Functional form of the explicit hydrogen-bond term.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HydrogenBondForm {
+    /// `E = D_hb [5 (R_hb/R)¹² − 6 (R_hb/R)¹⁰] cos⁴ θ`, where `R` is the donor-acceptor
+    /// distance and `θ` the donor-hydrogen-acceptor angle.
+    TwelveTen,
+    /// No explicit hydrogen-bond term; hydrogen bonding is left to electrostatics and vdW.
+    Disabled,
+}
+
+impl HydrogenBondForm {
+    /// Returns the energy expression as a human-readable formula.
+    pub fn formula(self) -> &'static str {
+        match self {
+            HydrogenBondForm::TwelveTen => {
+                "E = D_hb [5 (R_hb/R)^12 - 6 (R_hb/R)^10] cos^4(theta_DHA)"
+            }
+            HydrogenBondForm::Disabled => "E = 0",
+        }
+    }
+}
+
+/// Unit of energies and energy-valued parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnergyUnit {
+    /// Kilocalories per mole.
+    KcalPerMol,
+    /// Kilojoules per mole.
+    KjPerMol,
+}
+
+impl EnergyUnit {
+    /// Returns the conventional unit symbol.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            EnergyUnit::KcalPerMol => "kcal/mol",
+            EnergyUnit::KjPerMol => "kJ/mol",
+        }
+    }
+
+    /// Returns the factor that converts a value in `self` into `target`.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Unit to convert into.
+    pub fn factor_to(self, target: EnergyUnit) -> f64 {
+        const KJ_PER_KCAL: f64 = 4.184;
+        match (self, target) {
+            (EnergyUnit::KcalPerMol, EnergyUnit::KjPerMol) => KJ_PER_KCAL,
+            (EnergyUnit::KjPerMol, EnergyUnit::KcalPerMol) => 1.0 / KJ_PER_KCAL,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Unit of lengths and distance-valued parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LengthUnit {
+    /// Ångström (10⁻¹⁰ m).
+    Angstrom,
+    /// Nanometer (10⁻⁹ m).
+    Nanometer,
+}
+
+impl LengthUnit {
+    /// Returns the conventional unit symbol.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            LengthUnit::Angstrom => "Å",
+            LengthUnit::Nanometer => "nm",
+        }
+    }
+
+    /// Returns the factor that converts a value in `self` into `target`.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Unit to convert into.
+    pub fn factor_to(self, target: LengthUnit) -> f64 {
+        match (self, target) {
+            (LengthUnit::Angstrom, LengthUnit::Nanometer) => 0.1,
+            (LengthUnit::Nanometer, LengthUnit::Angstrom) => 10.0,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Unit of angles and angle-valued parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AngleUnit {
+    /// Degrees.
+    Degrees,
+    /// Radians.
+    Radians,
+}
+
+impl AngleUnit {
+    /// Returns the conventional unit symbol.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            AngleUnit::Degrees => "deg",
+            AngleUnit::Radians => "rad",
+        }
+    }
+
+    /// Returns the factor that converts a value in `self` into `target`.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Unit to convert into.
+    pub fn factor_to(self, target: AngleUnit) -> f64 {
+        match (self, target) {
+            (AngleUnit::Degrees, AngleUnit::Radians) => PI / 180.0,
+            (AngleUnit::Radians, AngleUnit::Degrees) => 180.0 / PI,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Unit system in which parameter values are expressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Units {
+    /// Unit of energies, well depths, and barriers.
+    pub energy: EnergyUnit,
+    /// Unit of equilibrium distances and radii.
+    pub length: LengthUnit,
+    /// Unit of equilibrium angles.
+    pub angle: AngleUnit,
+}
+
+impl Units {
+    /// Units used by the original DREIDING tables: kcal/mol, Å, and degrees.
+    pub const DREIDING: Units = Units {
+        energy: EnergyUnit::KcalPerMol,
+        length: LengthUnit::Angstrom,
+        angle: AngleUnit::Degrees,
+    };
+
+    /// Units of GROMACS topologies: kJ/mol, nm, and degrees.
+    pub const GROMACS: Units = Units {
+        energy: EnergyUnit::KjPerMol,
+        length: LengthUnit::Nanometer,
+        angle: AngleUnit::Degrees,
+    };
+
+    /// Units of OpenMM force fields: kJ/mol, nm, and radians.
+    pub const OPENMM: Units = Units {
+        energy: EnergyUnit::KjPerMol,
+        length: LengthUnit::Nanometer,
+        angle: AngleUnit::Radians,
+    };
+
+    /// Returns the unit of bond force constants (energy per length squared).
+    pub fn bond_force_constant(&self) -> String {
+        format!("{}/{}^2", self.energy.symbol(), self.length.symbol())
+    }
+
+    /// Returns the unit of angle and inversion force constants (energy per radian squared).
+    pub fn angle_force_constant(&self) -> String {
+        format!("{}/rad^2", self.energy.symbol())
+    }
+}
+
+impl Default for Units {
+    fn default() -> Self {
+        Self::DREIDING
+    }
+}
+
+impl fmt::Display for Units {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, {}, {}",
+            self.energy.symbol(),
+            self.length.symbol(),
+            self.angle.symbol()
+        )
+    }
+}
+
+/// Complete description of the energy expression that parameter values refer to.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::params::{EnergyExpression, VdwForm};
+///
+/// let x6 = EnergyExpression::dreiding().with_vdw(VdwForm::DREIDING_X6);
+/// assert_eq!(x6.vdw, VdwForm::Exponential6 { zeta: 12.0 });
+/// assert_eq!(x6.units.to_string(), "kcal/mol, Å, deg");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EnergyExpression {
+    /// Form of bond-stretch terms.
+    pub bond: BondForm,
+    /// Form of angle-bend terms.
+    pub angle: AngleForm,
+    /// Form of proper torsion terms.
+    pub torsion: TorsionForm,
+    /// Form of inversion terms.
+    pub inversion: InversionForm,
+    /// Form of van der Waals terms.
+    pub vdw: VdwForm,
+    /// Form of the explicit hydrogen-bond term.
+    pub hydrogen_bond: HydrogenBondForm,
+    /// Units of every parameter value.
+    pub units: Units,
+}
+
+impl EnergyExpression {
+    /// The original DREIDING energy expression with 12-6 Lennard-Jones van der Waals.
+    pub const DREIDING: EnergyExpression = EnergyExpression {
+        bond: BondForm::Harmonic,
+        angle: AngleForm::CosineHarmonic,
+        torsion: TorsionForm::Cosine,
+        inversion: InversionForm::Umbrella,
+        vdw: VdwForm::LennardJones,
+        hydrogen_bond: HydrogenBondForm::TwelveTen,
+        units: Units::DREIDING,
+    };
+
+    /// Returns the original DREIDING energy expression.
+    pub fn dreiding() -> Self {
+        Self::DREIDING
+    }
+
+    /// Replaces the bond-stretch form.
+    pub fn with_bond(mut self, bond: BondForm) -> Self {
+        self.bond = bond;
+        self
+    }
+
+    /// Replaces the van der Waals form.
+    pub fn with_vdw(mut self, vdw: VdwForm) -> Self {
+        self.vdw = vdw;
+        self
+    }
+
+    /// Replaces the hydrogen-bond form.
+    pub fn with_hydrogen_bond(mut self, hydrogen_bond: HydrogenBondForm) -> Self {
+        self.hydrogen_bond = hydrogen_bond;
+        self
+    }
+
+    /// Replaces the unit system.
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+}
+
+impl Default for EnergyExpression {
+    fn default() -> Self {
+        Self::DREIDING
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_factors_round_trip() {
+        let pairs = [
+            EnergyUnit::KcalPerMol.factor_to(EnergyUnit::KjPerMol)
+                * EnergyUnit::KjPerMol.factor_to(EnergyUnit::KcalPerMol),
+            LengthUnit::Angstrom.factor_to(LengthUnit::Nanometer)
+                * LengthUnit::Nanometer.factor_to(LengthUnit::Angstrom),
+            AngleUnit::Degrees.factor_to(AngleUnit::Radians)
+                * AngleUnit::Radians.factor_to(AngleUnit::Degrees),
+        ];
+        for product in pairs {
+            assert!((product - 1.0).abs() < 1e-12);
+        }
+        assert_eq!(
+            EnergyUnit::KcalPerMol.factor_to(EnergyUnit::KjPerMol),
+            4.184
+        );
+    }
+
+    #[test]
+    fn force_constant_units_follow_unit_system() {
+        assert_eq!(Units::DREIDING.bond_force_constant(), "kcal/mol/Å^2");
+        assert_eq!(Units::GROMACS.bond_force_constant(), "kJ/mol/nm^2");
+        assert_eq!(Units::GROMACS.angle_force_constant(), "kJ/mol/rad^2");
+        assert_eq!(Units::GROMACS.to_string(), "kJ/mol, nm, deg");
+        assert_eq!(Units::OPENMM.to_string(), "kJ/mol, nm, rad");
+    }
+
+    #[test]
+    fn dreiding_expression_matches_paper_forms() {
+        let expr = EnergyExpression::default();
+        assert_eq!(expr.bond, BondForm::Harmonic);
+        assert_eq!(expr.angle, AngleForm::CosineHarmonic);
+        assert_eq!(expr.vdw, VdwForm::LennardJones);
+        assert_eq!(expr.hydrogen_bond, HydrogenBondForm::TwelveTen);
+        assert_eq!(expr.units, Units::DREIDING);
+        assert!(expr.hydrogen_bond.formula().contains("cos^4"));
+    }
+
+    #[test]
+    fn expression_serializes_with_snake_case_tags() {
+        let expr = EnergyExpression::dreiding().with_vdw(VdwForm::DREIDING_X6);
+        let text = toml::to_string(&expr).expect("expression should serialize");
+        assert!(text.contains("bond = \"harmonic\""));
+        assert!(text.contains("zeta = 12.0"));
+        let back: EnergyExpression = toml::from_str(&text).expect("expression should parse");
+        assert_eq!(back, expr);
+    }
+}
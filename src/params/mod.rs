@@ -0,0 +1,31 @@
+//! DREIDING force-field parameters and the energy expression they belong to.
+//!
+//! [`assign_parameters`] turns a typed [`MolecularTopology`](crate::MolecularTopology) into a
+//! [`ParameterizedTopology`] carrying numeric parameters for every atom, bond, angle, torsion,
+//! and inversion, derived from the embedded tables of the original DREIDING paper.
+//!
+//! Numeric force constants are meaningless without the energy expression they plug into: a bond
+//! constant for a harmonic term differs from one for a Morse term, an angle constant for a
+//! cosine-harmonic bend differs from one for a harmonic bend, and the same well depth means
+//! different things in Lennard-Jones and exponential-6 van der Waals. This module describes the
+//! functional form of every DREIDING term family together with the units its parameters use, so
+//! exporters and downstream evaluators can interpret parameter values unambiguously.
+//!
+//! [`ForceFieldVariant`] selects between the original DREIDING, DREIDING/A, and the
+//! exponential-6 variant, which differ in hydrogen types and nonbonded terms.
+
+mod assign;
+mod forms;
+mod tables;
+mod variant;
+
+pub use assign::{
+    AngleParameters, BondParameters, HydrogenBondParameters, InversionParameters,
+    ParameterizedTopology, TorsionParameters, assign_parameters, assign_variant_parameters,
+};
+pub use forms::{
+    AngleForm, AngleUnit, BondForm, EnergyExpression, EnergyUnit, HydrogenBondForm, InversionForm,
+    LengthUnit, TorsionForm, Units, VdwForm,
+};
+pub use tables::{AtomParameters, ParameterTable, VdwParameters};
+pub use variant::{ForceFieldVariant, ParseForceFieldVariantError};
@@ -0,0 +1,273 @@
+//! Embedded DREIDING parameter tables.
+//!
+//! Values follow Tables I and II of Mayo, Olafson & Goddard, *J. Phys. Chem.* **94**, 8897
+//! (1990): per-type bond radii and natural angles, per-element van der Waals parameters, and the
+//! universal force constants shared by every term of a family. Everything is expressed in
+//! [`Units::DREIDING`](crate::params::Units::DREIDING).
+
+use crate::compat::collections::HashMap;
+use crate::compat::prelude::*;
+use crate::compat::sync::OnceLock;
+
+/// Force constant of a bond of order one, in kcal/mol/Å²; scaled by the bond order.
+pub const BOND_FORCE_CONSTANT: f64 = 700.0;
+
+/// Dissociation energy of a bond of order one, in kcal/mol; scaled by the bond order.
+pub const BOND_DISSOCIATION_ENERGY: f64 = 70.0;
+
+/// Correction δ subtracted from the sum of bond radii, in Å.
+pub const BOND_RADIUS_CORRECTION: f64 = 0.01;
+
+/// Force constant of every angle bend, in kcal/mol/rad².
+pub const ANGLE_FORCE_CONSTANT: f64 = 100.0;
+
+/// Total force constant of a planar center, in kcal/mol/rad², shared among its inversions.
+pub const INVERSION_FORCE_CONSTANT: f64 = 40.0;
+
+/// Well depth of the explicit hydrogen bond without atomic charges, in kcal/mol.
+pub const HYDROGEN_BOND_WELL_DEPTH: f64 = 9.0;
+
+/// Equilibrium donor-acceptor distance of the explicit hydrogen bond, in Å.
+pub const HYDROGEN_BOND_DISTANCE: f64 = 2.75;
+
+/// Lazily constructed table of the embedded DREIDING parameters.
+static DREIDING_TABLE: OnceLock<ParameterTable> = OnceLock::new();
+
+/// Van der Waals parameters of one atom type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VdwParameters {
+    /// Equilibrium distance `R₀` in Å.
+    pub r0: f64,
+    /// Well depth `D₀` in kcal/mol.
+    pub d0: f64,
+    /// Exponential-6 shape parameter `ζ`.
+    pub zeta: f64,
+}
+
+/// Per-type parameters from which every bonded and non-bonded term is derived.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtomParameters {
+    /// Covalent bond radius in Å.
+    pub bond_radius: f64,
+    /// Natural angle in degrees of bends centered on this type.
+    pub bond_angle: f64,
+    /// Van der Waals parameters.
+    pub vdw: VdwParameters,
+}
+
+impl AtomParameters {
+    /// Creates a parameter record.
+    ///
+    /// # Arguments
+    ///
+    /// * `bond_radius` - Covalent bond radius in Å.
+    /// * `bond_angle` - Natural bend angle in degrees.
+    /// * `vdw` - Van der Waals parameters.
+    pub fn new(bond_radius: f64, bond_angle: f64, vdw: VdwParameters) -> Self {
+        Self {
+            bond_radius,
+            bond_angle,
+            vdw,
+        }
+    }
+}
+
+/// Atom-type parameters keyed by type label.
+///
+/// [`ParameterTable::dreiding`] holds the all-atom and united-atom types of the original paper;
+/// further types (for example `S_R` or transition metals) can be added with
+/// [`ParameterTable::insert`].
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::params::{AtomParameters, ParameterTable, VdwParameters};
+///
+/// let mut table = ParameterTable::dreiding().clone();
+/// assert_eq!(table.get("C_3").unwrap().bond_radius, 0.770);
+/// assert!(table.get("S_R").is_none());
+///
+/// let s3 = *table.get("S_3").unwrap();
+/// table.insert("S_R", AtomParameters::new(1.000, 120.0, s3.vdw));
+/// assert!(table.get("S_R").is_some());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParameterTable {
+    atoms: HashMap<String, AtomParameters>,
+}
+
+impl ParameterTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the shared table of embedded DREIDING parameters.
+    pub fn dreiding() -> &'static ParameterTable {
+        DREIDING_TABLE.get_or_init(|| {
+            let mut table = Self::new();
+            for &(label, bond_radius, bond_angle, element) in DREIDING_TYPES {
+                let (_, r0, d0, zeta) = *DREIDING_VDW
+                    .iter()
+                    .find(|entry| entry.0 == element)
+                    .expect("every DREIDING type has van der Waals parameters");
+                table.insert(
+                    label,
+                    AtomParameters::new(bond_radius, bond_angle, VdwParameters { r0, d0, zeta }),
+                );
+            }
+            table
+        })
+    }
+
+    /// Returns the parameters of a type label.
+    pub fn get(&self, atom_type: &str) -> Option<&AtomParameters> {
+        self.atoms.get(atom_type)
+    }
+
+    /// Adds or replaces the parameters of a type label.
+    ///
+    /// # Returns
+    ///
+    /// The previous parameters of the label, if any.
+    pub fn insert(
+        &mut self,
+        atom_type: impl Into<String>,
+        parameters: AtomParameters,
+    ) -> Option<AtomParameters> {
+        self.atoms.insert(atom_type.into(), parameters)
+    }
+
+    /// Returns the number of type labels in the table.
+    pub fn len(&self) -> usize {
+        self.atoms.len()
+    }
+
+    /// Returns `true` if the table defines no types.
+    pub fn is_empty(&self) -> bool {
+        self.atoms.is_empty()
+    }
+}
+
+/// Bond radius (Å), natural angle (degrees), and van der Waals key of every DREIDING type.
+#[rustfmt::skip]
+const DREIDING_TYPES: &[(&str, f64, f64, &str)] = &[
+    ("H_",   0.330, 180.0,   "H_"),
+    ("H_HB", 0.330, 180.0,   "H_HB"),
+    ("H_b",  0.510,  90.0,   "H_"),
+    ("B_3",  0.880, 109.471, "B"),
+    ("B_2",  0.790, 120.0,   "B"),
+    ("C_3",  0.770, 109.471, "C"),
+    ("C_R",  0.700, 120.0,   "C"),
+    ("C_2",  0.670, 120.0,   "C"),
+    ("C_1",  0.602, 180.0,   "C"),
+    ("C_31", 0.770, 109.471, "C_31"),
+    ("C_32", 0.770, 109.471, "C_32"),
+    ("C_33", 0.770, 109.471, "C_33"),
+    ("C_34", 0.770, 109.471, "C_34"),
+    ("C_R1", 0.700, 120.0,   "C_R1"),
+    ("C_R2", 0.700, 120.0,   "C_R2"),
+    ("C_21", 0.670, 120.0,   "C_21"),
+    ("C_22", 0.670, 120.0,   "C_22"),
+    ("C_11", 0.602, 180.0,   "C_11"),
+    ("N_3",  0.702, 106.7,   "N"),
+    ("N_R",  0.650, 120.0,   "N"),
+    ("N_2",  0.615, 120.0,   "N"),
+    ("N_1",  0.556, 180.0,   "N"),
+    ("O_3",  0.660, 104.51,  "O"),
+    ("O_R",  0.660, 120.0,   "O"),
+    ("O_2",  0.560, 120.0,   "O"),
+    ("O_1",  0.528, 180.0,   "O"),
+    ("F_",   0.611, 180.0,   "F"),
+    ("Al3",  1.047, 109.471, "Al"),
+    ("Si3",  0.937, 109.471, "Si"),
+    ("P_3",  0.890,  93.3,   "P"),
+    ("S_3",  1.040,  92.1,   "S"),
+    ("Cl",   0.997, 180.0,   "Cl"),
+    ("Ga3",  1.210, 109.471, "Ga"),
+    ("Ge3",  1.210, 109.471, "Ge"),
+    ("As3",  1.210,  92.1,   "As"),
+    ("Se3",  1.210,  90.6,   "Se"),
+    ("Br",   1.167, 180.0,   "Br"),
+    ("In3",  1.390, 109.471, "In"),
+    ("Sn3",  1.373, 109.471, "Sn"),
+    ("Sb3",  1.432,  91.6,   "Sb"),
+    ("Te3",  1.280,  90.3,   "Te"),
+    ("I_",   1.360, 180.0,   "I"),
+    ("Na",   1.860,  90.0,   "Na"),
+    ("Ca",   1.940,  90.0,   "Ca"),
+    ("Fe",   1.285,  90.0,   "Fe"),
+    ("Zn",   1.330, 109.471, "Zn"),
+];
+
+/// Van der Waals `R₀` (Å), `D₀` (kcal/mol), and `ζ` keyed by element (or hydrogen or
+/// united-atom type).
+///
+/// United-atom carbons take the values of the matching group: `C_21` and `C_11` those of the
+/// resonant CH group, and `C_22` and `C_R2` those of the sp3 CH2 group.
+#[rustfmt::skip]
+const DREIDING_VDW: &[(&str, f64, f64, f64)] = &[
+    ("H_",   3.195,  0.0152, 12.382),
+    ("H_HB", 3.195,  0.0001, 12.0),
+    ("B",    4.02,   0.095,  14.23),
+    ("C",    3.8983, 0.0951, 14.034),
+    ("C_31", 3.9830, 0.1467, 14.034),
+    ("C_32", 4.0677, 0.1984, 14.034),
+    ("C_33", 4.1524, 0.2500, 14.034),
+    ("C_34", 4.2370, 0.3016, 14.034),
+    ("C_R1", 4.2300, 0.1356, 14.034),
+    ("C_R2", 4.0677, 0.1984, 14.034),
+    ("C_21", 4.2300, 0.1356, 14.034),
+    ("C_22", 4.0677, 0.1984, 14.034),
+    ("C_11", 4.2300, 0.1356, 14.034),
+    ("N",    3.6621, 0.0774, 13.843),
+    ("O",    3.4046, 0.0957, 13.483),
+    ("F",    3.4720, 0.0725, 14.444),
+    ("Al",   4.39,   0.31,   12.0),
+    ("Si",   4.27,   0.31,   12.0),
+    ("P",    4.15,   0.32,   12.0),
+    ("S",    4.03,   0.344,  12.0),
+    ("Cl",   3.9503, 0.2833, 13.861),
+    ("Ga",   4.39,   0.40,   12.0),
+    ("Ge",   4.27,   0.40,   12.0),
+    ("As",   4.15,   0.41,   12.0),
+    ("Se",   4.03,   0.43,   12.0),
+    ("Br",   3.95,   0.37,   12.0),
+    ("In",   4.59,   0.55,   12.0),
+    ("Sn",   4.47,   0.55,   12.0),
+    ("Sb",   4.35,   0.55,   12.0),
+    ("Te",   4.23,   0.57,   12.0),
+    ("I",    4.15,   0.51,   12.0),
+    ("Na",   3.144,  0.5,    12.0),
+    ("Ca",   3.472,  0.05,   12.0),
+    ("Fe",   4.54,   0.055,  12.0),
+    ("Zn",   4.54,   0.055,  12.0),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dreiding_table_covers_every_paper_type() {
+        let table = ParameterTable::dreiding();
+        assert_eq!(table.len(), DREIDING_TYPES.len());
+        assert!(std::ptr::eq(table, ParameterTable::dreiding()));
+
+        let c_r = table.get("C_R").unwrap();
+        assert_eq!(c_r.bond_radius, 0.700);
+        assert_eq!(c_r.bond_angle, 120.0);
+        assert_eq!(c_r.vdw.r0, 3.8983);
+        assert_eq!(table.get("H_HB").unwrap().vdw.d0, 0.0001);
+        assert_eq!(table.get("H_b").unwrap().vdw, table.get("H_").unwrap().vdw);
+
+        let c_33 = table.get("C_33").unwrap();
+        assert_eq!(c_33.bond_radius, 0.770);
+        assert_eq!((c_33.vdw.r0, c_33.vdw.d0), (4.1524, 0.2500));
+        for label in [
+            "C_31", "C_32", "C_34", "C_R1", "C_R2", "C_21", "C_22", "C_11",
+        ] {
+            assert!(table.get(label).is_some(), "{label}");
+        }
+    }
+}
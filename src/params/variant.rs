@@ -0,0 +1,150 @@
+//! Published variants of the DREIDING force field.
+//!
+//! The variants share the bonded rules and per-type tables but differ in how hydrogens and
+//! nonbonded interactions are treated. Selecting a variant on the [`Typer`](crate::Typer) fixes
+//! the hydrogen types it emits, and selecting the same variant during parameter assignment fixes
+//! the energy expression the parameters are reported for.
+
+use super::forms::{EnergyExpression, HydrogenBondForm, VdwForm};
+use crate::compat::prelude::*;
+use core::fmt;
+use core::str::FromStr;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// DREIDING type of hydrogens that take part in explicit hydrogen bonds.
+const HYDROGEN_BOND_TYPE: &str = "H_HB";
+
+/// DREIDING type of ordinary hydrogens.
+const HYDROGEN_TYPE: &str = "H_";
+
+/// Flavor of DREIDING used for typing and parameter assignment.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::params::{ForceFieldVariant, HydrogenBondForm, VdwForm};
+///
+/// let a = ForceFieldVariant::DreidingA;
+/// assert!(!a.uses_hydrogen_bond_type());
+/// assert_eq!(a.expression().hydrogen_bond, HydrogenBondForm::Disabled);
+/// assert_eq!(a.canonical_type("H_HB"), "H_");
+///
+/// assert_eq!(ForceFieldVariant::X6.expression().vdw, VdwForm::DREIDING_X6);
+/// assert_eq!("x6".parse::<ForceFieldVariant>(), Ok(ForceFieldVariant::X6));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForceFieldVariant {
+    /// Original DREIDING: `H_HB` hydrogens with the explicit 12-10 hydrogen-bond term and 12-6
+    /// Lennard-Jones van der Waals.
+    #[default]
+    Dreiding,
+    /// DREIDING/A: every hydrogen is `H_` and hydrogen bonding is left to electrostatics and van
+    /// der Waals, so no explicit hydrogen-bond term is emitted.
+    DreidingA,
+    /// DREIDING with exponential-6 van der Waals and the per-element `ζ` of the original tables;
+    /// hydrogen typing matches [`ForceFieldVariant::Dreiding`].
+    X6,
+}
+
+impl ForceFieldVariant {
+    /// Every variant, in declaration order.
+    pub const ALL: [ForceFieldVariant; 3] = [
+        ForceFieldVariant::Dreiding,
+        ForceFieldVariant::DreidingA,
+        ForceFieldVariant::X6,
+    ];
+
+    /// Returns the conventional name of the variant.
+    pub fn name(self) -> &'static str {
+        match self {
+            ForceFieldVariant::Dreiding => "dreiding",
+            ForceFieldVariant::DreidingA => "dreiding_a",
+            ForceFieldVariant::X6 => "x6",
+        }
+    }
+
+    /// Returns `true` if the variant distinguishes hydrogen-bonding hydrogens as `H_HB`.
+    pub fn uses_hydrogen_bond_type(self) -> bool {
+        !matches!(self, ForceFieldVariant::DreidingA)
+    }
+
+    /// Returns the energy expression the variant's parameters refer to.
+    pub fn expression(self) -> EnergyExpression {
+        match self {
+            ForceFieldVariant::Dreiding => EnergyExpression::DREIDING,
+            ForceFieldVariant::DreidingA => {
+                EnergyExpression::DREIDING.with_hydrogen_bond(HydrogenBondForm::Disabled)
+            }
+            ForceFieldVariant::X6 => EnergyExpression::DREIDING.with_vdw(VdwForm::DREIDING_X6),
+        }
+    }
+
+    /// Maps a canonical DREIDING type onto the type this variant uses for the same atom.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_type` - Type assigned by the default rules.
+    pub fn canonical_type(self, atom_type: &str) -> &str {
+        if !self.uses_hydrogen_bond_type() && atom_type == HYDROGEN_BOND_TYPE {
+            HYDROGEN_TYPE
+        } else {
+            atom_type
+        }
+    }
+}
+
+impl fmt::Display for ForceFieldVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned when parsing an unrecognized force-field variant name.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("unknown force-field variant: '{0}'")]
+pub struct ParseForceFieldVariantError(String);
+
+impl FromStr for ForceFieldVariant {
+    type Err = ParseForceFieldVariantError;
+
+    /// Parses a variant name such as `"dreiding"`, `"DREIDING/A"`, or `"x6"`, ignoring case.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseForceFieldVariantError`] if the name matches no variant.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace(['/', '-'], "_").as_str() {
+            "dreiding" => Ok(ForceFieldVariant::Dreiding),
+            "dreiding_a" => Ok(ForceFieldVariant::DreidingA),
+            "x6" | "dreiding_x6" => Ok(ForceFieldVariant::X6),
+            _ => Err(ParseForceFieldVariantError(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip_through_from_str() {
+        for variant in ForceFieldVariant::ALL {
+            assert_eq!(variant.name().parse(), Ok(variant));
+        }
+        assert_eq!(
+            "DREIDING/A".parse::<ForceFieldVariant>(),
+            Ok(ForceFieldVariant::DreidingA)
+        );
+        assert!("amber".parse::<ForceFieldVariant>().is_err());
+    }
+
+    #[test]
+    fn only_dreiding_a_folds_hydrogen_bond_types() {
+        assert_eq!(ForceFieldVariant::Dreiding.canonical_type("H_HB"), "H_HB");
+        assert_eq!(ForceFieldVariant::X6.canonical_type("H_HB"), "H_HB");
+        assert_eq!(ForceFieldVariant::DreidingA.canonical_type("H_HB"), "H_");
+        assert_eq!(ForceFieldVariant::DreidingA.canonical_type("C_3"), "C_3");
+    }
+}
@@ -1,58 +1,211 @@
 //! Evaluates fused ring systems to determine whether the atoms are aromatic, anti-aromatic, or neither.
 //!
 //! The module groups rings, builds localized models that count π-electrons under planarity
-//! assumptions, and sets per-atom flags. Importantly, it also registers aromatic rings
+//! assumptions, and sets per-atom flags. How borderline atoms are counted follows the selected
+//! [`AromaticityModel`]. Importantly, it also registers aromatic rings
 //! as `ResonanceSystem`s so that their bonds are treated as resonant in the final topology.
 
 use super::model::{AnnotatedAtom, AnnotatedMolecule, ResonanceSystem, Ring};
+use super::options::AromaticityModel;
+use super::rings;
+use crate::compat::collections::{HashMap, HashSet};
+use crate::compat::prelude::*;
 use crate::core::error::PerceptionError;
-use crate::core::properties::GraphBondOrder;
-use std::collections::{HashMap, HashSet};
+use crate::core::periodic::ImageFlags;
+use crate::core::properties::{Element, GraphBondOrder};
+
+/// Largest fused system, in rings, whose π electrons are counted as a whole.
+///
+/// Larger systems are graphene sheets, nanotubes, and fullerenes, whose Hückel count over
+/// thousands of atoms says nothing about their rings; they are judged ring by ring, which keeps
+/// every resonance system to a single ring.
+const MAX_WHOLE_SYSTEM_RINGS: usize = 32;
+
+/// Largest fused system, in rings, that a 4n count as a whole marks anti-aromatic.
+///
+/// Pentalene and heptalene are anti-aromatic, but polycyclic 4n systems such as pyrene,
+/// coronene, and C60 are not, and are judged ring by ring.
+const MAX_ANTI_AROMATIC_SYSTEM_RINGS: usize = 2;
 
 /// Runs aromaticity perception over all ring systems present in the molecule.
 ///
 /// The procedure clusters rings that share atoms, evaluates each cluster as a whole, falls back to
 /// ring-by-ring evaluation when mixed behavior occurs, and annotates atoms as aromatic or
-/// anti-aromatic accordingly. Confirmed aromatic systems are added to the molecule's
-/// resonance systems list.
+/// anti-aromatic accordingly. Confirmed aromatic systems are added to the molecule's resonance
+/// systems list. Under [`AromaticityModel::Mdl`] clusters are only ever judged ring by ring, as are
+/// clusters of more than [`MAX_WHOLE_SYSTEM_RINGS`] rings, and only clusters of up to
+/// [`MAX_ANTI_AROMATIC_SYSTEM_RINGS`] rings are anti-aromatic as a whole. Every sp2 carbon of a
+/// fullerene or nanotube therefore lies in an aromatic six-membered ring, however curved. Systems
+/// that extend through the lattice of a periodic cell, such as a graphene sheet, are likewise
+/// judged ring by ring, counting an atom once for every image of it a ring passes through.
+/// Macrocycle envelopes take part exactly like rings, so limiting the ring size does not change
+/// which atoms are aromatic. When a ring closes through a metal center, the cycles of the ligand
+/// without its metals are evaluated instead, so the conjugated macrocycle of a metalloporphyrin or
+/// metallophthalocyanine is judged as in the free ligand.
 ///
 /// # Arguments
 ///
 /// * `molecule` - Annotated molecule whose atom flags should be updated.
+/// * `model` - Convention used to count π electrons.
 ///
 /// # Returns
 ///
 /// `Ok(())` once every ring system has been processed or when no rings exist.
-pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError> {
-    if molecule.rings.is_empty() {
+pub fn perceive(
+    molecule: &mut AnnotatedMolecule,
+    model: AromaticityModel,
+) -> Result<(), PerceptionError> {
+    let mut cycles: Vec<Ring> = molecule
+        .rings
+        .iter()
+        .chain(&molecule.macrocycles)
+        .cloned()
+        .collect();
+    if cycles
+        .iter()
+        .flatten()
+        .any(|&atom_id| molecule.atoms[atom_id].is_metal_center)
+    {
+        cycles = rings::ligand_cycles(molecule);
+    }
+    if cycles.is_empty() {
         return Ok(());
     }
 
-    let ring_systems_indices = find_ring_systems(&molecule.rings);
+    let ring_systems_indices = find_ring_systems(&cycles);
 
     for system_indices in ring_systems_indices {
         let system_atoms: HashSet<usize> = system_indices
             .iter()
-            .flat_map(|&i| molecule.rings[i].iter())
+            .flat_map(|&i| cycles[i].iter())
             .copied()
             .collect();
 
-        let model = AromaticityModel::new(molecule, &system_atoms);
+        if model == AromaticityModel::Mdl
+            || system_indices.len() > MAX_WHOLE_SYSTEM_RINGS
+            || is_extended(molecule, &system_atoms)
+        {
+            evaluate_rings_individually(molecule, &cycles, &system_indices, &system_atoms, model);
+            continue;
+        }
+
+        let system_vertices: Vec<usize> = system_atoms.iter().copied().collect();
+        let system_model = RingSystemModel::new(molecule, &system_vertices, &system_atoms, model);
 
-        if model.is_aromatic() {
+        if system_model.is_aromatic() {
             apply_aromaticity(molecule, &system_atoms);
-        } else if model.is_anti_aromatic() {
+        } else if system_indices.len() <= MAX_ANTI_AROMATIC_SYSTEM_RINGS
+            && system_model.is_anti_aromatic()
+        {
             for &atom_id in &system_atoms {
                 molecule.atoms[atom_id].is_anti_aromatic = true;
             }
         } else {
-            evaluate_rings_individually(molecule, &system_indices);
+            evaluate_rings_individually(molecule, &cycles, &system_indices, &system_atoms, model);
         }
     }
 
     Ok(())
 }
 
+/// Checks whether a fused system of a periodic molecule extends without end through the lattice.
+///
+/// Walking the system's bonds reaches some atom under two different lattice images exactly when
+/// the system is an infinite chain, sheet, or framework. Its π electrons per cell say nothing
+/// about its rings.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule carrying bond images.
+/// * `system_atoms` - Atom IDs of the fused system.
+fn is_extended(molecule: &AnnotatedMolecule, system_atoms: &HashSet<usize>) -> bool {
+    if molecule.bond_images.is_empty() {
+        return false;
+    }
+    let mut offsets: HashMap<usize, ImageFlags> = HashMap::new();
+    for &start in system_atoms {
+        if offsets.contains_key(&start) {
+            continue;
+        }
+        offsets.insert(start, [0; 3]);
+        let mut stack = vec![start];
+        while let Some(atom_id) = stack.pop() {
+            let image = offsets[&atom_id];
+            for neighbor in &molecule.adjacency_with_bonds[atom_id] {
+                if !system_atoms.contains(&neighbor.neighbor_id) {
+                    continue;
+                }
+                let next = rings::step_image(molecule, atom_id, neighbor.bond_id, image);
+                match offsets.get(&neighbor.neighbor_id) {
+                    Some(&seen) if seen != next => return true,
+                    Some(_) => {}
+                    None => {
+                        offsets.insert(neighbor.neighbor_id, next);
+                        stack.push(neighbor.neighbor_id);
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Takes aromaticity from the bonds the input gave as aromatic, without counting π electrons.
+///
+/// Each connected system of aromatic input bonds is marked aromatic and resonant and
+/// registered as a resonance system holding exactly those bonds, so a single bond joining two
+/// aromatic rings (as in biphenylene) stays outside it. Atoms without aromatic input bonds are
+/// left untouched.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule whose
+///   [`aromatic_input_bonds`](AnnotatedMolecule::aromatic_input_bonds) were recorded by Kekulé
+///   expansion.
+pub fn trust_input(molecule: &mut AnnotatedMolecule) {
+    let mut incident = vec![Vec::new(); molecule.atoms.len()];
+    for &bond_id in &molecule.aromatic_input_bonds {
+        let (u, v) = molecule.bonds[bond_id].atom_ids;
+        incident[u].push(bond_id);
+        incident[v].push(bond_id);
+    }
+
+    let mut visited = vec![false; molecule.atoms.len()];
+    for start in 0..molecule.atoms.len() {
+        if visited[start] || incident[start].is_empty() {
+            continue;
+        }
+        visited[start] = true;
+        let mut stack = vec![start];
+        let mut atom_ids = Vec::new();
+        let mut bond_ids = Vec::new();
+        while let Some(atom_id) = stack.pop() {
+            atom_ids.push(atom_id);
+            for &bond_id in &incident[atom_id] {
+                bond_ids.push(bond_id);
+                let (u, v) = molecule.bonds[bond_id].atom_ids;
+                let neighbor = if u == atom_id { v } else { u };
+                if !visited[neighbor] {
+                    visited[neighbor] = true;
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        for &atom_id in &atom_ids {
+            let atom = &mut molecule.atoms[atom_id];
+            atom.is_aromatic = true;
+            atom.is_resonant = true;
+        }
+        atom_ids.sort_unstable();
+        bond_ids.sort_unstable();
+        bond_ids.dedup();
+        molecule
+            .resonance_systems
+            .push(ResonanceSystem { atom_ids, bond_ids });
+    }
+}
+
 /// Marks all atoms and bonds in the system as aromatic and resonant.
 ///
 /// # Arguments
@@ -66,16 +219,17 @@ fn apply_aromaticity(molecule: &mut AnnotatedMolecule, system_atoms: &HashSet<us
         atom.is_resonant = true;
     }
 
-    let mut bond_ids = Vec::new();
-    for bond in &molecule.bonds {
-        if system_atoms.contains(&bond.atom_ids.0) && system_atoms.contains(&bond.atom_ids.1) {
-            bond_ids.push(bond.id);
-        }
-    }
+    let mut bond_ids: Vec<usize> = system_atoms
+        .iter()
+        .flat_map(|&atom_id| &molecule.adjacency_with_bonds[atom_id])
+        .filter(|neighbor| system_atoms.contains(&neighbor.neighbor_id))
+        .map(|neighbor| neighbor.bond_id)
+        .collect();
 
     let mut atom_ids: Vec<usize> = system_atoms.iter().copied().collect();
     atom_ids.sort_unstable();
     bond_ids.sort_unstable();
+    bond_ids.dedup();
 
     molecule
         .resonance_systems
@@ -84,48 +238,161 @@ fn apply_aromaticity(molecule: &mut AnnotatedMolecule, system_atoms: &HashSet<us
 
 /// Evaluates each ring independently when a fused system lacks uniform behavior.
 ///
+/// Atoms of an anti-aromatic ring that also lie in an aromatic ring stay aromatic, as the rim
+/// atoms of a narrow nanotube do when its 4n circumference enters the ring basis.
+///
 /// # Arguments
 ///
 /// * `molecule` - Annotated molecule to mutate.
-/// * `system_indices` - Indices of rings belonging to the fused system.
-fn evaluate_rings_individually(molecule: &mut AnnotatedMolecule, system_indices: &[usize]) {
+/// * `cycles` - Rings followed by macrocycle envelopes.
+/// * `system_indices` - Indices into `cycles` of the members of the fused system.
+/// * `system_atoms` - Atom IDs of the whole fused system.
+/// * `model` - Convention used to count π electrons.
+fn evaluate_rings_individually(
+    molecule: &mut AnnotatedMolecule,
+    cycles: &[Ring],
+    system_indices: &[usize],
+    system_atoms: &HashSet<usize>,
+    model: AromaticityModel,
+) {
+    let mut anti_aromatic_atoms = Vec::new();
     for &ring_idx in system_indices {
-        let ring_atoms: HashSet<_> = molecule.rings[ring_idx].iter().copied().collect();
-        let ring_model = AromaticityModel::new(molecule, &ring_atoms);
+        let ring_atoms: HashSet<_> = cycles[ring_idx].iter().copied().collect();
+        let ring_model = RingSystemModel::new(molecule, &cycles[ring_idx], system_atoms, model);
 
         if ring_model.is_aromatic() {
             apply_aromaticity(molecule, &ring_atoms);
         } else if ring_model.is_anti_aromatic() {
-            for &atom_id in &ring_atoms {
-                molecule.atoms[atom_id].is_anti_aromatic = true;
-            }
+            anti_aromatic_atoms.extend(ring_atoms);
         }
     }
+
+    close_uncovered_hexagons(molecule, system_atoms, model);
+
+    for atom_id in anti_aromatic_atoms {
+        let atom = &mut molecule.atoms[atom_id];
+        atom.is_anti_aromatic = !atom.is_aromatic;
+    }
+}
+
+/// Evaluates six-membered cycles that the ring basis left out of a fused system.
+///
+/// A closed cage has one face more than its cycle basis has rings, so one hexagon of C60 is
+/// never judged on its own, and the bonds it shares with pentagons would stay localized. Every
+/// bond joining two aromatic atoms of the system outside all resonance systems is searched for
+/// a six-membered cycle of system atoms, which is then judged like a ring.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule to mutate.
+/// * `system_atoms` - Atom IDs of the fused system evaluated ring by ring.
+/// * `model` - Convention used to count π electrons.
+fn close_uncovered_hexagons(
+    molecule: &mut AnnotatedMolecule,
+    system_atoms: &HashSet<usize>,
+    model: AromaticityModel,
+) {
+    let mut covered: HashSet<usize> = molecule
+        .resonance_systems
+        .iter()
+        .flat_map(|system| system.bond_ids.iter().copied())
+        .collect();
+
+    for bond_id in 0..molecule.bonds.len() {
+        let (u, v) = molecule.bonds[bond_id].atom_ids;
+        if covered.contains(&bond_id)
+            || !system_atoms.contains(&u)
+            || !system_atoms.contains(&v)
+            || !molecule.atoms[u].is_aromatic
+            || !molecule.atoms[v].is_aromatic
+        {
+            continue;
+        }
+        let mut path = vec![u, v];
+        if !extend_to_hexagon(molecule, system_atoms, &mut path) {
+            continue;
+        }
+
+        let is_aromatic = RingSystemModel::new(molecule, &path, system_atoms, model).is_aromatic();
+        let hexagon: HashSet<usize> = path.into_iter().collect();
+        if is_aromatic {
+            let first_new_system = molecule.resonance_systems.len();
+            apply_aromaticity(molecule, &hexagon);
+            covered.extend(
+                molecule.resonance_systems[first_new_system..]
+                    .iter()
+                    .flat_map(|system| system.bond_ids.iter().copied()),
+            );
+        }
+    }
+}
+
+/// Extends a simple path of system atoms to six atoms whose ends are bonded, if possible.
+fn extend_to_hexagon(
+    molecule: &AnnotatedMolecule,
+    system_atoms: &HashSet<usize>,
+    path: &mut Vec<usize>,
+) -> bool {
+    let last = path[path.len() - 1];
+    if path.len() == 6 {
+        return molecule.adjacency[last]
+            .iter()
+            .any(|&(neighbor_id, _)| neighbor_id == path[0]);
+    }
+    for &(neighbor_id, _) in &molecule.adjacency[last] {
+        if !system_atoms.contains(&neighbor_id) || path.contains(&neighbor_id) {
+            continue;
+        }
+        path.push(neighbor_id);
+        if extend_to_hexagon(molecule, system_atoms, path) {
+            return true;
+        }
+        path.pop();
+    }
+    false
 }
 
 /// Local model capturing the atoms and π-electron count for a ring system.
-struct AromaticityModel<'a> {
+struct RingSystemModel<'a> {
     /// Annotated molecule providing adjacency information.
     molecule: &'a AnnotatedMolecule,
     /// Atom IDs forming the current system under evaluation.
     atoms: HashSet<usize>,
+    /// Atoms of the system, listing an atom once per lattice image a periodic ring passes
+    /// through it.
+    vertices: Vec<usize>,
+    /// Atom IDs of the fused system containing [`RingSystemModel::atoms`].
+    fused_atoms: &'a HashSet<usize>,
+    /// Convention used to count π electrons.
+    model: AromaticityModel,
     /// Computed π-electron count, if evaluation succeeded.
     pi_electrons: Option<u32>,
     /// Flag describing whether the atoms satisfy the planarity heuristic.
     is_potentially_planar: bool,
 }
 
-impl<'a> AromaticityModel<'a> {
+impl<'a> RingSystemModel<'a> {
     /// Constructs the model and immediately evaluates planarity and π-electrons.
     ///
     /// # Arguments
     ///
     /// * `molecule` - Annotated molecule backing the model.
-    /// * `system_atoms` - Atom IDs representing a ring system.
-    fn new(molecule: &'a AnnotatedMolecule, system_atoms: &HashSet<usize>) -> Self {
+    /// * `system_vertices` - Atom IDs representing a ring system, with an atom repeated for
+    ///   every image of it that a ring of a periodic cell passes through.
+    /// * `fused_atoms` - Atom IDs of the fused system the ring system belongs to.
+    /// * `aromaticity` - Convention used to count π electrons.
+    fn new(
+        molecule: &'a AnnotatedMolecule,
+        system_vertices: &[usize],
+        fused_atoms: &'a HashSet<usize>,
+        aromaticity: AromaticityModel,
+    ) -> Self {
         let mut model = Self {
             molecule,
-            atoms: system_atoms.clone(),
+            atoms: system_vertices.iter().copied().collect(),
+            vertices: system_vertices.to_vec(),
+            fused_atoms,
+            model: aromaticity,
             pi_electrons: None,
             is_potentially_planar: false,
         };
@@ -139,15 +406,16 @@ impl<'a> AromaticityModel<'a> {
             return false;
         }
 
-        let all_from_aromatic_input = self
-            .atoms
-            .iter()
-            .all(|&id| self.molecule.atoms[id].has_aromatic_edge);
+        let all_from_aromatic_input = self.model != AromaticityModel::Mdl
+            && self
+                .atoms
+                .iter()
+                .all(|&id| self.molecule.atoms[id].has_aromatic_edge);
         if all_from_aromatic_input {
             return true;
         }
 
-        matches!(self.pi_electrons, Some(pi) if pi > 0 && (pi - 2) % 4 == 0)
+        matches!(self.pi_electrons, Some(pi) if pi >= 2 && (pi - 2) % 4 == 0)
     }
 
     /// Returns `true` when the Huckel 4n rule indicates anti-aromaticity.
@@ -171,7 +439,7 @@ impl<'a> AromaticityModel<'a> {
         self.is_potentially_planar = true;
 
         let mut pi_count = 0;
-        for &atom_id in &self.atoms {
+        for &atom_id in &self.vertices {
             if let Some(contribution) = self.count_pi_contribution(atom_id) {
                 pi_count += contribution;
             } else {
@@ -204,6 +472,18 @@ impl<'a> AromaticityModel<'a> {
             .any(|&atom_id| self.atom_has_exocyclic_double(atom_id))
     }
 
+    /// Checks if an atom carries a double bond to an electronegative atom outside the fused
+    /// system, such as the oxygen of a ring carbonyl.
+    fn atom_has_polar_exocyclic_double(&self, atom_id: usize) -> bool {
+        self.molecule.adjacency[atom_id]
+            .iter()
+            .any(|&(n_id, order)| {
+                order == GraphBondOrder::Double
+                    && !self.fused_atoms.contains(&n_id)
+                    && self.molecule.atoms[n_id].element != Element::C
+            })
+    }
+
     /// Computes each atom's π contribution using bond, lone-pair, and resonance flags.
     fn count_pi_contribution(&self, atom_id: usize) -> Option<u32> {
         let atom = &self.molecule.atoms[atom_id];
@@ -214,6 +494,24 @@ impl<'a> AromaticityModel<'a> {
             return Some(1);
         }
 
+        match self.model {
+            AromaticityModel::Huckel => {}
+            AromaticityModel::Daylight => {
+                if self.atom_has_polar_exocyclic_double(atom_id) {
+                    return Some(0);
+                }
+            }
+            AromaticityModel::Mdl => {
+                let has_fused_double_bond =
+                    self.molecule.adjacency[atom_id]
+                        .iter()
+                        .any(|&(n_id, order)| {
+                            order == GraphBondOrder::Double && self.fused_atoms.contains(&n_id)
+                        });
+                return has_fused_double_bond.then_some(1);
+            }
+        }
+
         if !has_exocyclic_double_bond && atom.lone_pairs > 0 {
             return Some(2);
         }
@@ -242,8 +540,11 @@ impl<'a> AromaticityModel<'a> {
 }
 
 /// Heuristic planarity test derived from steric number rules.
+///
+/// Coordination bonds are left out, since a ligand atom binds a metal through one of the lone
+/// pairs already counted.
 fn is_potentially_planar(atom: &AnnotatedAtom) -> bool {
-    let steric_number = atom.degree + atom.lone_pairs;
+    let steric_number = atom.covalent_degree() + atom.lone_pairs;
     match steric_number {
         0..=3 => true,
         4 => atom.lone_pairs > 0,
@@ -394,8 +695,15 @@ mod tests {
         }
     }
 
-    fn perceive_aromaticity(mut molecule: AnnotatedMolecule) -> AnnotatedMolecule {
-        perceive(&mut molecule).expect("aromaticity perception should succeed");
+    fn perceive_aromaticity(molecule: AnnotatedMolecule) -> AnnotatedMolecule {
+        perceive_with_model(molecule, AromaticityModel::Huckel)
+    }
+
+    fn perceive_with_model(
+        mut molecule: AnnotatedMolecule,
+        model: AromaticityModel,
+    ) -> AnnotatedMolecule {
+        perceive(&mut molecule, model).expect("aromaticity perception should succeed");
         molecule
     }
 
@@ -404,7 +712,7 @@ mod tests {
         expected_aromatic: &[usize],
         expected_anti: &[usize],
     ) {
-        use std::collections::HashSet;
+        use crate::compat::collections::HashSet;
         let aromatic: HashSet<_> = molecule
             .atoms
             .iter()
@@ -739,6 +1047,24 @@ mod tests {
         build_test_molecule(&atoms, &bonds, &[&[0, 1, 2, 3, 4]])
     }
 
+    fn azulene() -> AnnotatedMolecule {
+        let atoms = vec![c(); 10];
+        let bonds = vec![
+            (0, 1, GraphBondOrder::Double),
+            (1, 2, GraphBondOrder::Single),
+            (2, 3, GraphBondOrder::Double),
+            (3, 4, GraphBondOrder::Single),
+            (4, 0, GraphBondOrder::Single),
+            (4, 5, GraphBondOrder::Double),
+            (5, 6, GraphBondOrder::Single),
+            (6, 7, GraphBondOrder::Double),
+            (7, 8, GraphBondOrder::Single),
+            (8, 9, GraphBondOrder::Double),
+            (9, 0, GraphBondOrder::Single),
+        ];
+        build_test_molecule(&atoms, &bonds, &[&[0, 1, 2, 3, 4], &[0, 4, 5, 6, 7, 8, 9]])
+    }
+
     #[test]
     fn benzene_ring_is_aromatic() {
         let molecule = perceive_aromaticity(benzene());
@@ -818,6 +1144,54 @@ mod tests {
         assert_flag_sets(&molecule, &[0, 1, 2, 3, 4], &[]);
     }
 
+    #[test]
+    fn daylight_model_withdraws_exocyclic_carbonyl_electrons() {
+        let molecule = perceive_with_model(alpha_pyrone(), AromaticityModel::Daylight);
+        assert_flag_sets(&molecule, &[0, 1, 2, 3, 4, 5], &[]);
+
+        let molecule = perceive_with_model(naphthalene(), AromaticityModel::Daylight);
+        assert_flag_sets(&molecule, &(0..10).collect::<Vec<_>>(), &[]);
+    }
+
+    #[test]
+    fn mdl_model_requires_endocyclic_double_bonds() {
+        let molecule = perceive_with_model(benzene(), AromaticityModel::Mdl);
+        assert_flag_sets(&molecule, &[0, 1, 2, 3, 4, 5], &[]);
+        let molecule = perceive_with_model(naphthalene(), AromaticityModel::Mdl);
+        assert_flag_sets(&molecule, &(0..10).collect::<Vec<_>>(), &[]);
+
+        for molecule in [pyrrole(), pyrazole(), alpha_pyrone()] {
+            let molecule = perceive_with_model(molecule, AromaticityModel::Mdl);
+            assert_flag_sets(&molecule, &[], &[]);
+        }
+    }
+
+    #[test]
+    fn fused_perimeter_aromaticity_depends_on_model() {
+        let molecule = perceive_with_model(azulene(), AromaticityModel::Huckel);
+        assert_flag_sets(&molecule, &(0..10).collect::<Vec<_>>(), &[]);
+
+        let molecule = perceive_with_model(azulene(), AromaticityModel::Mdl);
+        assert_flag_sets(&molecule, &[], &[]);
+    }
+
+    #[test]
+    fn trusted_input_follows_the_given_aromatic_bonds() {
+        // Alpha-pyrone written with aromatic ring bonds, although Hückel counting rejects it.
+        let mut molecule = alpha_pyrone();
+        molecule.aromatic_input_bonds = (0..6).collect();
+        trust_input(&mut molecule);
+        assert_flag_sets(&molecule, &[0, 1, 2, 3, 4, 5], &[]);
+        assert_eq!(molecule.resonance_systems.len(), 1);
+        assert_eq!(molecule.resonance_systems[0].bond_ids, [0, 1, 2, 3, 4, 5]);
+
+        // Kekulé benzene carries no aromatic input bonds and stays non-aromatic.
+        let mut molecule = benzene();
+        trust_input(&mut molecule);
+        assert_flag_sets(&molecule, &[], &[]);
+        assert!(molecule.resonance_systems.is_empty());
+    }
+
     #[test]
     fn biphenyl_registers_two_separate_resonance_systems() {
         let atoms = vec![
@@ -0,0 +1,442 @@
+//! Infers double and triple bonds for graphs that supply connectivity only.
+//!
+//! XYZ files, and PDB files without duplicated `CONECT` entries, say which atoms are bonded but
+//! not how. In the spirit of Antechamber's bond-type perception, every atom receives a short list
+//! of acceptable valences, each with a penalty (zero for the neutral valence, small for onium
+//! forms such as the nitro nitrogen), and every unit of valence left unfilled costs
+//! [`DEFICIT_PENALTY`]. A branch-and-bound search then picks the bond orders with the lowest total
+//! penalty, one conjugated fragment at a time. Formal charges are not stored: the electron
+//! perception stage derives them from the resulting valences as it does for any other input.
+
+use crate::compat::prelude::*;
+use crate::core::graph::MolecularGraph;
+use crate::core::properties::{Element, GraphBondOrder};
+use alloc::collections::VecDeque;
+
+/// Penalty for each unit of an atom's valence that is left without a multiple bond.
+///
+/// Larger than the penalty of any onium valence, so that a nitro group is drawn as `N(=O)O`
+/// rather than with two unsatisfied oxygens.
+const DEFICIT_PENALTY: u32 = 2;
+
+/// Search steps allowed per fragment before the best assignment found so far is accepted.
+const MAX_SEARCH_STEPS: u64 = 1_000_000;
+
+/// Upgrades single bonds to double or triple bonds so that atoms reach their usual valences.
+///
+/// Hydrogens must be explicit, as they fix how many bonds each heavy atom still needs. Bonds
+/// that already carry a double or triple order are kept and count toward the valence of their
+/// atoms; atoms on aromatic bonds are left to Kekulé expansion, and bonds to metal centers
+/// stay single coordination bonds. When several assignments are equally good, as for the two
+/// Kekulé structures of benzene or the two oxygens of a carboxylate, any one of them is chosen;
+/// perception treats them alike.
+///
+/// # Arguments
+///
+/// * `graph` - Molecular graph whose bond orders are updated in place.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, assign_bond_orders};
+///
+/// // Acetonitrile given as connectivity only.
+/// let mut graph = MolecularGraph::new();
+/// let c1 = graph.add_atom(Element::C);
+/// let c2 = graph.add_atom(Element::C);
+/// let n = graph.add_atom(Element::N);
+/// graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+/// let nitrile = graph.add_bond(c2, n, GraphBondOrder::Single).unwrap();
+/// for _ in 0..3 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(c1, h, GraphBondOrder::Single).unwrap();
+/// }
+///
+/// assign_bond_orders(&mut graph);
+/// assert_eq!(graph.bonds[nitrile].order, GraphBondOrder::Triple);
+/// ```
+pub fn assign_bond_orders(graph: &mut MolecularGraph) {
+    let atom_count = graph.atoms.len();
+    let is_metal: Vec<bool> = graph
+        .atoms
+        .iter()
+        .map(|atom| atom.element.is_metal_center())
+        .collect();
+
+    let mut degree = vec![0u8; atom_count];
+    let mut extra_units = vec![0u8; atom_count];
+    let mut locked = vec![false; atom_count];
+    for bond in &graph.bonds {
+        let (u, v) = bond.atom_ids;
+        if is_metal[u] || is_metal[v] {
+            continue;
+        }
+        let extra = match bond.order {
+            GraphBondOrder::Double => 1,
+            GraphBondOrder::Triple => 2,
+            GraphBondOrder::Aromatic => {
+                locked[u] = true;
+                locked[v] = true;
+                0
+            }
+            _ => 0,
+        };
+        for atom in [u, v] {
+            degree[atom] = degree[atom].saturating_add(1);
+            extra_units[atom] = extra_units[atom].saturating_add(extra);
+        }
+    }
+
+    let penalties: Vec<Vec<u32>> = (0..atom_count)
+        .map(|atom| {
+            if is_metal[atom] || locked[atom] {
+                vec![0]
+            } else {
+                penalty_table(
+                    graph.atoms[atom].element,
+                    degree[atom],
+                    degree[atom].saturating_add(extra_units[atom]),
+                )
+            }
+        })
+        .collect();
+    let capacity = |atom: usize| (penalties[atom].len() - 1) as u8;
+
+    let mut candidates = Vec::new();
+    let mut candidate_adjacency = vec![Vec::new(); atom_count];
+    for bond in &graph.bonds {
+        let (u, v) = bond.atom_ids;
+        if bond.order == GraphBondOrder::Single
+            && !is_metal[u]
+            && !is_metal[v]
+            && capacity(u) > 0
+            && capacity(v) > 0
+        {
+            candidate_adjacency[u].push((v, candidates.len()));
+            candidate_adjacency[v].push((u, candidates.len()));
+            candidates.push((bond.id, u, v));
+        }
+    }
+
+    let mut visited = vec![false; atom_count];
+    for start in 0..atom_count {
+        if visited[start] || candidate_adjacency[start].is_empty() {
+            continue;
+        }
+
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        visited[start] = true;
+        while let Some(atom) = queue.pop_front() {
+            order.push(atom);
+            for &(neighbor, _) in &candidate_adjacency[atom] {
+                if !visited[neighbor] {
+                    visited[neighbor] = true;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        let increments = solve_fragment(&order, &candidate_adjacency, &candidates, &penalties);
+        for (candidate, increment) in increments {
+            let bond_id = candidates[candidate].0;
+            graph.bonds[bond_id].order = match increment {
+                0 => GraphBondOrder::Single,
+                1 => GraphBondOrder::Double,
+                _ => GraphBondOrder::Triple,
+            };
+        }
+    }
+}
+
+/// Acceptable `(valence, penalty)` pairs for an atom with `degree` covalent neighbors.
+fn valence_options(element: Element, degree: u8) -> &'static [(u8, u32)] {
+    use Element::*;
+    match (element, degree) {
+        (Cl | Br | I, 2) => &[(3, 0)],
+        (Cl | Br | I, 3) => &[(5, 0)],
+        (Cl | Br | I, 4) => &[(7, 0)],
+        (H | F | Cl | Br | I, _) => &[(1, 0)],
+        (B, 0..=3) => &[(3, 0)],
+        (B, _) => &[(4, 0)],
+        (C | Si | Ge, _) => &[(4, 0)],
+        (N, 0..=3) => &[(3, 0), (4, 1)],
+        (N, _) => &[(4, 0)],
+        (P | As, 0..=3) => &[(3, 0)],
+        (P | As, 4) => &[(5, 0), (4, 1)],
+        (P | As, _) => &[(5, 0)],
+        (O, 0 | 1) => &[(2, 0)],
+        (O, 2) => &[(2, 0), (3, 1)],
+        (O, _) => &[(3, 0)],
+        (S | Se | Te, 0..=2) => &[(2, 0)],
+        (S | Se | Te, 3) => &[(4, 0), (3, 1)],
+        (S | Se | Te, _) => &[(6, 0)],
+        _ => &[],
+    }
+}
+
+/// Tabulates the penalty of every number of additional bond-order units an atom can take.
+///
+/// Index `k` of the returned table is the lowest penalty of any valence option that leaves the
+/// atom with `units + k` bond-order units, counting each unit short of that valence at
+/// [`DEFICIT_PENALTY`]. A table of length one means the atom takes no multiple bonds.
+fn penalty_table(element: Element, degree: u8, units: u8) -> Vec<u32> {
+    let options: Vec<(usize, u32)> = valence_options(element, degree)
+        .iter()
+        .filter_map(|&(valence, penalty)| Some((usize::from(valence.checked_sub(units)?), penalty)))
+        .collect();
+    let Some(max_excess) = options.iter().map(|&(excess, _)| excess).max() else {
+        return vec![0];
+    };
+
+    (0..=max_excess)
+        .map(|k| {
+            options
+                .iter()
+                .filter(|&&(excess, _)| excess >= k)
+                .map(|&(excess, penalty)| penalty + DEFICIT_PENALTY * (excess - k) as u32)
+                .min()
+                .expect("the option with the largest excess covers every k")
+        })
+        .collect()
+}
+
+/// Step in the depth-first search over one fragment.
+#[derive(Debug, Clone, Copy)]
+enum Event {
+    /// Choose the extra order (0–2) of a candidate bond.
+    Bond(usize),
+    /// All bonds of an atom are decided; charge its penalty.
+    Close(usize),
+}
+
+/// Finds the extra bond order of every candidate bond in a fragment with the lowest penalty.
+///
+/// Atoms are visited in breadth-first `order`; each atom's bonds to later atoms are decided just
+/// before the atom is closed, so its penalty is known as early as possible. Orders are tried
+/// from triple down to single, which makes the first complete assignment a greedy one and lets
+/// the search stop after [`MAX_SEARCH_STEPS`] with a usable answer.
+fn solve_fragment(
+    order: &[usize],
+    candidate_adjacency: &[Vec<(usize, usize)>],
+    candidates: &[(usize, usize, usize)],
+    penalties: &[Vec<u32>],
+) -> Vec<(usize, u8)> {
+    let mut position = vec![usize::MAX; penalties.len()];
+    for (index, &atom) in order.iter().enumerate() {
+        position[atom] = index;
+    }
+
+    let mut events = Vec::new();
+    for &atom in order {
+        for &(neighbor, candidate) in &candidate_adjacency[atom] {
+            if position[neighbor] > position[atom] {
+                events.push(Event::Bond(candidate));
+            }
+        }
+        events.push(Event::Close(atom));
+    }
+
+    // Lower bound on the penalty still to come after each event.
+    let mut remaining_bound = vec![0u32; events.len() + 1];
+    for (index, event) in events.iter().enumerate().rev() {
+        let minimum = match *event {
+            Event::Close(atom) => penalties[atom].iter().copied().min().unwrap_or(0),
+            Event::Bond(_) => 0,
+        };
+        remaining_bound[index] = remaining_bound[index + 1].saturating_add(minimum);
+    }
+
+    let capacity = |atom: usize| (penalties[atom].len() - 1) as u8;
+    let mut used = vec![0u8; penalties.len()];
+    let mut increments = vec![0u8; candidates.len()];
+    let mut cost = vec![0u32; events.len() + 1];
+    let mut next_choice = vec![-1i8; events.len()];
+    let mut best: Option<(u32, Vec<u8>)> = None;
+    let mut steps = 0u64;
+
+    let mut level = 0;
+    let mut entering = true;
+    loop {
+        if entering {
+            steps += 1;
+            if steps > MAX_SEARCH_STEPS && best.is_some() {
+                break;
+            }
+            let bound = cost[level].saturating_add(remaining_bound[level]);
+            let pruned = best
+                .as_ref()
+                .is_some_and(|(best_cost, _)| bound >= *best_cost);
+            if !pruned && level == events.len() {
+                best = Some((cost[level], increments.clone()));
+            }
+            if pruned || level == events.len() {
+                if level == 0 {
+                    break;
+                }
+                level -= 1;
+                entering = false;
+                continue;
+            }
+            next_choice[level] = match events[level] {
+                Event::Bond(candidate) => {
+                    let (_, u, v) = candidates[candidate];
+                    (capacity(u) - used[u]).min(capacity(v) - used[v]).min(2) as i8
+                }
+                Event::Close(_) => 0,
+            };
+        } else if let Event::Bond(candidate) = events[level] {
+            let (_, u, v) = candidates[candidate];
+            used[u] -= increments[candidate];
+            used[v] -= increments[candidate];
+            increments[candidate] = 0;
+        }
+
+        if next_choice[level] < 0 {
+            if level == 0 {
+                break;
+            }
+            level -= 1;
+            entering = false;
+            continue;
+        }
+        let choice = next_choice[level] as u8;
+        next_choice[level] -= 1;
+        cost[level + 1] = match events[level] {
+            Event::Bond(candidate) => {
+                let (_, u, v) = candidates[candidate];
+                increments[candidate] = choice;
+                used[u] += choice;
+                used[v] += choice;
+                cost[level]
+            }
+            Event::Close(atom) => cost[level].saturating_add(penalties[atom][used[atom] as usize]),
+        };
+        level += 1;
+        entering = true;
+    }
+
+    let (_, best_increments) = best.expect("the greedy descent always completes");
+    events
+        .iter()
+        .filter_map(|event| match *event {
+            Event::Bond(candidate) => Some((candidate, best_increments[candidate])),
+            Event::Close(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_hydrogens(graph: &mut MolecularGraph, atom: usize, count: usize) {
+        for _ in 0..count {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(atom, h, GraphBondOrder::Single).unwrap();
+        }
+    }
+
+    fn count_orders(graph: &MolecularGraph, order: GraphBondOrder) -> usize {
+        graph
+            .bonds
+            .iter()
+            .filter(|bond| bond.order == order)
+            .count()
+    }
+
+    #[test]
+    fn benzene_connectivity_gets_a_kekule_structure() {
+        let mut graph = MolecularGraph::new();
+        let ring: Vec<usize> = (0..6).map(|_| graph.add_atom(Element::C)).collect();
+        for i in 0..6 {
+            graph
+                .add_bond(ring[i], ring[(i + 1) % 6], GraphBondOrder::Single)
+                .unwrap();
+            add_hydrogens(&mut graph, ring[i], 1);
+        }
+
+        assign_bond_orders(&mut graph);
+
+        assert_eq!(count_orders(&graph, GraphBondOrder::Double), 3);
+        for &atom in &ring {
+            let doubles = graph
+                .bonds
+                .iter()
+                .filter(|bond| {
+                    bond.order == GraphBondOrder::Double
+                        && (bond.atom_ids.0 == atom || bond.atom_ids.1 == atom)
+                })
+                .count();
+            assert_eq!(doubles, 1);
+        }
+    }
+
+    #[test]
+    fn nitro_group_gets_one_double_bond() {
+        let mut graph = MolecularGraph::new();
+        let c = graph.add_atom(Element::C);
+        let n = graph.add_atom(Element::N);
+        let o1 = graph.add_atom(Element::O);
+        let o2 = graph.add_atom(Element::O);
+        graph.add_bond(c, n, GraphBondOrder::Single).unwrap();
+        graph.add_bond(n, o1, GraphBondOrder::Single).unwrap();
+        graph.add_bond(n, o2, GraphBondOrder::Single).unwrap();
+        add_hydrogens(&mut graph, c, 3);
+
+        assign_bond_orders(&mut graph);
+
+        assert_eq!(count_orders(&graph, GraphBondOrder::Double), 1);
+        assert_eq!(graph.bonds[0].order, GraphBondOrder::Single);
+    }
+
+    #[test]
+    fn given_orders_and_aromatic_atoms_are_kept() {
+        // Acrolein with its C=O given, attached to an aromatic carbon.
+        let mut graph = MolecularGraph::new();
+        let o = graph.add_atom(Element::O);
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        let c3 = graph.add_atom(Element::C);
+        let aromatic = graph.add_atom(Element::C);
+        let carbonyl = graph.add_bond(o, c1, GraphBondOrder::Double).unwrap();
+        let alkene = graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+        let vinyl = graph.add_bond(c2, c3, GraphBondOrder::Single).unwrap();
+        let link = graph
+            .add_bond(c3, aromatic, GraphBondOrder::Single)
+            .unwrap();
+        let partner = graph.add_atom(Element::C);
+        graph
+            .add_bond(aromatic, partner, GraphBondOrder::Aromatic)
+            .unwrap();
+        add_hydrogens(&mut graph, c1, 1);
+        add_hydrogens(&mut graph, c2, 1);
+        add_hydrogens(&mut graph, c3, 1);
+
+        assign_bond_orders(&mut graph);
+
+        assert_eq!(graph.bonds[carbonyl].order, GraphBondOrder::Double);
+        assert_eq!(graph.bonds[alkene].order, GraphBondOrder::Single);
+        assert_eq!(graph.bonds[vinyl].order, GraphBondOrder::Double);
+        assert_eq!(graph.bonds[link].order, GraphBondOrder::Single);
+    }
+
+    #[test]
+    fn bonds_to_metal_centers_stay_single() {
+        // Acetonitrile bound to iron: the nitrogen still takes a triple bond to carbon.
+        let mut graph = MolecularGraph::new();
+        let fe = graph.add_atom(Element::Fe);
+        let n = graph.add_atom(Element::N);
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        let coordination = graph.add_bond(fe, n, GraphBondOrder::Single).unwrap();
+        let nitrile = graph.add_bond(n, c1, GraphBondOrder::Single).unwrap();
+        graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+        add_hydrogens(&mut graph, c2, 3);
+
+        assign_bond_orders(&mut graph);
+
+        assert_eq!(graph.bonds[coordination].order, GraphBondOrder::Single);
+        assert_eq!(graph.bonds[nitrile].order, GraphBondOrder::Triple);
+    }
+}
@@ -6,6 +6,7 @@
 //! remaining atoms.
 
 use super::model::AnnotatedMolecule;
+use crate::compat::prelude::*;
 use crate::core::error::PerceptionError;
 use crate::core::properties::{Element, GraphBondOrder};
 
@@ -29,6 +30,8 @@ use crate::core::properties::{Element, GraphBondOrder};
 pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError> {
     let mut processed = vec![false; molecule.atoms.len()];
 
+    assign_metal_centers(molecule, &mut processed);
+    assign_stated_states(molecule, &mut processed);
     assign_nitrone_groups(molecule, &mut processed)?;
     assign_nitro_groups(molecule, &mut processed)?;
     assign_sulfur_oxides(molecule, &mut processed)?;
@@ -45,6 +48,79 @@ pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError>
     Ok(())
 }
 
+/// Tags metal centers and gives isolated main-group metal ions their ionic charge.
+///
+/// Metals carry no lone pairs. An isolated alkali or alkaline-earth atom is a free cation
+/// (`Na⁺`, `Ca²⁺`); every other metal keeps a zero formal charge because its oxidation state
+/// cannot be read off the graph.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule to inspect and mutate.
+/// * `processed` - Scratch mask indicating atoms already assigned by previous passes.
+fn assign_metal_centers(molecule: &mut AnnotatedMolecule, processed: &mut [bool]) {
+    for atom in molecule
+        .atoms
+        .iter_mut()
+        .filter(|atom| atom.is_metal_center)
+    {
+        atom.lone_pairs = 0;
+        atom.formal_charge = match atom.element.valence_electrons() {
+            Some(valence) if atom.degree == 0 => valence as i8,
+            _ => 0,
+        };
+        processed[atom.id] = true;
+    }
+}
+
+/// Applies the charges and unpaired electrons stated by the input where connectivity is silent.
+///
+/// A carbon with three single bonds may be a carbocation, a carbanion, or a radical, and an atom
+/// with unpaired electrons holds fewer lone pairs than an octet count assumes. Carbons with a
+/// stated charge and atoms with unpaired electrons receive
+/// `(valence - bonding - charge - unpaired) / 2` lone pairs when that is a whole, non-negative
+/// number and keeps second-period atoms within an octet. Other atoms, atoms of aromatic input
+/// rings, and states that do not fit the bonds are left to the later passes.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule whose atoms carry the stated charge in `formal_charge`.
+/// * `processed` - Scratch mask indicating atoms already assigned by previous passes.
+fn assign_stated_states(molecule: &mut AnnotatedMolecule, processed: &mut [bool]) {
+    for (i, done) in processed.iter_mut().enumerate() {
+        let atom = &molecule.atoms[i];
+        let is_stated =
+            atom.radical_electrons > 0 || (atom.element == Element::C && atom.formal_charge != 0);
+        if *done || !is_stated || atom.has_aromatic_edge {
+            continue;
+        }
+        let Some(valence) = atom.element.valence_electrons() else {
+            continue;
+        };
+
+        let bonding_electrons: i16 = molecule.adjacency[i]
+            .iter()
+            .filter(|&&(neighbor, _)| !molecule.atoms[neighbor].is_metal_center)
+            .map(|&(_, order)| i16::from(bond_order_to_valence(order)))
+            .sum();
+        let nonbonding = i16::from(valence)
+            - bonding_electrons
+            - i16::from(atom.formal_charge)
+            - i16::from(atom.radical_electrons);
+        if nonbonding < 0 || nonbonding % 2 != 0 {
+            continue;
+        }
+        let shell = 2 * bonding_electrons + nonbonding + i16::from(atom.radical_electrons);
+        let is_second_period = matches!(atom.element, Element::C | Element::N | Element::O);
+        if is_second_period && shell > 8 {
+            continue;
+        }
+
+        molecule.atoms[i].lone_pairs = (nonbonding / 2) as u8;
+        *done = true;
+    }
+}
+
 /// Detects nitrones and applies the canonical charge distribution.
 ///
 /// # Arguments
@@ -62,7 +138,7 @@ fn assign_nitrone_groups(
     for n_idx in 0..molecule.atoms.len() {
         if processed[n_idx]
             || molecule.atoms[n_idx].element != Element::N
-            || molecule.atoms[n_idx].degree != 3
+            || molecule.atoms[n_idx].covalent_degree() != 3
         {
             continue;
         }
@@ -113,7 +189,7 @@ fn assign_nitro_groups(
     for n_idx in 0..molecule.atoms.len() {
         if processed[n_idx]
             || molecule.atoms[n_idx].element != Element::N
-            || molecule.atoms[n_idx].degree != 3
+            || molecule.atoms[n_idx].covalent_degree() != 3
         {
             continue;
         }
@@ -161,7 +237,8 @@ fn assign_nitro_groups(
     Ok(())
 }
 
-/// Handles sulfoxide and sulfone motifs, setting sulfur and oxygen charges.
+/// Handles sulfur oxides with three or four neighbors: sulfoxides, sulfinates, sulfones,
+/// sulfonates, and sulfates.
 ///
 /// # Arguments
 ///
@@ -176,46 +253,11 @@ fn assign_sulfur_oxides(
     processed: &mut [bool],
 ) -> Result<(), PerceptionError> {
     for s_idx in 0..molecule.atoms.len() {
-        if processed[s_idx] || molecule.atoms[s_idx].element != Element::S {
-            continue;
-        }
-
-        let oxygen_neighbors: Vec<(usize, GraphBondOrder)> = molecule.adjacency[s_idx]
-            .iter()
-            .filter(|&&(id, _)| molecule.atoms[id].element == Element::O)
-            .cloned()
-            .collect();
-
-        let double_bonded_oxygens: Vec<usize> = oxygen_neighbors
-            .iter()
-            .filter(|&&(_, order)| order == GraphBondOrder::Double)
-            .map(|&(id, _)| id)
-            .collect();
-
-        if molecule.atoms[s_idx].degree == 3 && oxygen_neighbors.len() == 1 {
-            let (o_idx, _) = oxygen_neighbors[0];
-            if !processed[o_idx] {
-                molecule.atoms[s_idx].formal_charge = 0;
-                molecule.atoms[s_idx].lone_pairs = 1;
-                molecule.atoms[o_idx].formal_charge = 0;
-                molecule.atoms[o_idx].lone_pairs = 2;
-                processed[s_idx] = true;
-                processed[o_idx] = true;
-            }
-        } else if molecule.atoms[s_idx].degree == 4 && double_bonded_oxygens.len() == 2 {
-            let o1_idx = double_bonded_oxygens[0];
-            let o2_idx = double_bonded_oxygens[1];
-            if !processed[o1_idx] && !processed[o2_idx] {
-                molecule.atoms[s_idx].formal_charge = 0;
-                molecule.atoms[s_idx].lone_pairs = 0;
-                molecule.atoms[o1_idx].formal_charge = 0;
-                molecule.atoms[o1_idx].lone_pairs = 2;
-                molecule.atoms[o2_idx].formal_charge = 0;
-                molecule.atoms[o2_idx].lone_pairs = 2;
-                processed[s_idx] = true;
-                processed[o1_idx] = true;
-                processed[o2_idx] = true;
-            }
+        if !processed[s_idx]
+            && molecule.atoms[s_idx].element == Element::S
+            && matches!(molecule.atoms[s_idx].covalent_degree(), 3 | 4)
+        {
+            assign_oxo_center(molecule, processed, s_idx);
         }
     }
     Ok(())
@@ -258,7 +300,8 @@ fn assign_halogen_oxyanions(
         }
 
         for &(oxygen_idx, order) in &oxygen_neighbors {
-            if processed[oxygen_idx] {
+            // Hydroxyl and ester oxygens, as in perchloric acid, keep the general count.
+            if processed[oxygen_idx] || molecule.atoms[oxygen_idx].degree != 1 {
                 continue;
             }
 
@@ -278,7 +321,7 @@ fn assign_halogen_oxyanions(
     Ok(())
 }
 
-/// Captures phosphoryl species with a single P=O double bond and applies +1/-1 charges.
+/// Handles tetracoordinate phosphorus oxides: phosphine oxides, phosphonates, and phosphates.
 ///
 /// # Arguments
 ///
@@ -293,34 +336,70 @@ fn assign_phosphorus_oxides(
     processed: &mut [bool],
 ) -> Result<(), PerceptionError> {
     for p_idx in 0..molecule.atoms.len() {
-        if processed[p_idx]
-            || molecule.atoms[p_idx].element != Element::P
-            || molecule.atoms[p_idx].degree != 4
+        if !processed[p_idx]
+            && molecule.atoms[p_idx].element == Element::P
+            && molecule.atoms[p_idx].covalent_degree() == 4
         {
-            continue;
+            assign_oxo_center(molecule, processed, p_idx);
         }
+    }
+    Ok(())
+}
 
-        let double_bonded_oxygens: Vec<usize> = molecule.adjacency[p_idx]
-            .iter()
-            .filter(|&&(id, order)| {
-                molecule.atoms[id].element == Element::O && order == GraphBondOrder::Double
-            })
-            .map(|&(id, _)| id)
-            .collect();
+/// Applies expanded-octet bookkeeping to a sulfur or phosphorus center bearing terminal oxygens.
+///
+/// The center keeps a lone pair for each position short of four neighbors, as far as its valence
+/// allows, and carries whatever charge is left. Terminal oxygens are neutral when doubly bonded and
+/// anionic when singly bonded. A sulfone is thus neutral as drawn with `S=O` bonds and `S²⁺`
+/// with two `O⁻` when drawn charge-separated, and the sulfur of a sulfoxide keeps its lone pair
+/// either way. Centers without a terminal oxygen are left to later passes.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule to mutate.
+/// * `processed` - Mask updated for the center and its terminal oxygens.
+/// * `center` - ID of the sulfur or phosphorus atom.
+fn assign_oxo_center(molecule: &mut AnnotatedMolecule, processed: &mut [bool], center: usize) {
+    let oxygens: Vec<(usize, GraphBondOrder)> = molecule.adjacency[center]
+        .iter()
+        .filter(|&&(id, order)| {
+            let atom = &molecule.atoms[id];
+            atom.element == Element::O
+                && atom.degree == 1
+                && !processed[id]
+                && matches!(order, GraphBondOrder::Single | GraphBondOrder::Double)
+        })
+        .copied()
+        .collect();
+    let Some(valence) = molecule.atoms[center].element.valence_electrons() else {
+        return;
+    };
+    if oxygens.is_empty() {
+        return;
+    }
 
-        if double_bonded_oxygens.len() == 1 {
-            let o_idx = double_bonded_oxygens[0];
-            if !processed[o_idx] {
-                molecule.atoms[p_idx].formal_charge = 1;
-                molecule.atoms[p_idx].lone_pairs = 0;
-                molecule.atoms[o_idx].formal_charge = -1;
-                molecule.atoms[o_idx].lone_pairs = 3;
-                processed[p_idx] = true;
-                processed[o_idx] = true;
-            }
-        }
+    let bonding_electrons: u8 = molecule.adjacency[center]
+        .iter()
+        .filter(|&&(id, _)| !molecule.atoms[id].is_metal_center)
+        .map(|&(_, order)| bond_order_to_valence(order))
+        .sum();
+    let free_electrons = valence.saturating_sub(bonding_electrons);
+    let lone_pairs =
+        (4u8.saturating_sub(molecule.atoms[center].covalent_degree())).min(free_electrons / 2);
+
+    let atom = &mut molecule.atoms[center];
+    atom.lone_pairs = lone_pairs;
+    atom.formal_charge = valence as i8 - bonding_electrons as i8 - 2 * lone_pairs as i8;
+    processed[center] = true;
+
+    for (o_idx, order) in oxygens {
+        let oxygen = &mut molecule.atoms[o_idx];
+        (oxygen.formal_charge, oxygen.lone_pairs) = match order {
+            GraphBondOrder::Double => (0, 2),
+            _ => (-1, 3),
+        };
+        processed[o_idx] = true;
     }
-    Ok(())
 }
 
 /// Detects carboxylate anions and assigns the single-bonded oxygen a -1 charge.
@@ -340,7 +419,7 @@ fn assign_carboxylate_anions(
     for c_idx in 0..molecule.atoms.len() {
         if processed[c_idx]
             || molecule.atoms[c_idx].element != Element::C
-            || molecule.atoms[c_idx].degree != 3
+            || molecule.atoms[c_idx].covalent_degree() != 3
         {
             continue;
         }
@@ -364,7 +443,9 @@ fn assign_carboxylate_anions(
 
         match (double_bond_o_idx, single_bond_o_idx) {
             (Some(o1), Some(o2))
-                if !processed[o1] && !processed[o2] && molecule.atoms[o2].degree == 1 =>
+                if !processed[o1]
+                    && !processed[o2]
+                    && molecule.atoms[o2].covalent_degree() == 1 =>
             {
                 molecule.atoms[c_idx].formal_charge = 0;
                 molecule.atoms[c_idx].lone_pairs = 0;
@@ -404,7 +485,7 @@ fn assign_ammonium_and_iminium(
             continue;
         }
 
-        let degree = molecule.atoms[n_idx].degree;
+        let degree = molecule.atoms[n_idx].covalent_degree();
         let has_double_bond = molecule.adjacency[n_idx]
             .iter()
             .any(|&(_, order)| order == GraphBondOrder::Double);
@@ -443,7 +524,7 @@ fn assign_onium_ions(
         }
 
         let element = molecule.atoms[idx].element;
-        let degree = molecule.atoms[idx].degree;
+        let degree = molecule.atoms[idx].covalent_degree();
         let has_pi_bond = molecule.adjacency[idx]
             .iter()
             .any(|&(_, order)| order != GraphBondOrder::Single);
@@ -475,7 +556,7 @@ fn assign_phosphonium_ions(
     for (p_idx, processed_flag) in processed.iter_mut().enumerate() {
         if *processed_flag
             || molecule.atoms[p_idx].element != Element::P
-            || molecule.atoms[p_idx].degree != 4
+            || molecule.atoms[p_idx].covalent_degree() != 4
         {
             continue;
         }
@@ -511,7 +592,7 @@ fn assign_enolate_phenate_anions(
     for (o_idx, processed_flag) in processed.iter_mut().enumerate() {
         if *processed_flag
             || molecule.atoms[o_idx].element != Element::O
-            || molecule.atoms[o_idx].degree != 1
+            || molecule.atoms[o_idx].covalent_degree() != 1
         {
             continue;
         }
@@ -552,7 +633,8 @@ fn assign_enolate_phenate_anions(
 ///
 /// # Errors
 ///
-/// Returns [`PerceptionError::Other`] if an element lacks a `valence_electrons` definition.
+/// Returns [`PerceptionError::MissingValence`] if a bonded non-metal lacks a
+/// `valence_electrons` definition.
 fn assign_general(
     molecule: &mut AnnotatedMolecule,
     processed: &[bool],
@@ -567,29 +649,31 @@ fn assign_general(
             Some(v) => v,
             None if molecule.atoms[i].degree == 0 => 0,
             None => {
-                return Err(PerceptionError::Other(format!(
-                    "valence electrons not defined for element {:?}",
-                    element
-                )));
+                return Err(PerceptionError::MissingValence {
+                    atom: molecule.atom_context(i),
+                });
             }
         };
 
-        let bonding_electrons: u8 = molecule.adjacency[i]
-            .iter()
+        // Coordination bonds donate ligand lone pairs and leave the ligand's own count intact.
+        let covalent = || {
+            molecule.adjacency[i]
+                .iter()
+                .filter(|&&(neighbor, _)| !molecule.atoms[neighbor].is_metal_center)
+        };
+        let bonding_electrons: u8 = covalent()
             .map(|&(_, order)| bond_order_to_valence(order))
             .sum();
 
-        let double_bond_count = molecule.adjacency[i]
-            .iter()
+        let double_bond_count = covalent()
             .filter(|&&(_, order)| order == GraphBondOrder::Double)
             .count();
 
         let mut lone_pairs = 0;
 
-        let is_second_period = matches!(
-            element,
-            Element::B | Element::C | Element::N | Element::O | Element::F
-        );
+        // Boron is electron deficient: three-coordinate boron keeps an empty p orbital instead
+        // of completing an octet, so it stays with the valence-based count below.
+        let is_second_period = matches!(element, Element::C | Element::N | Element::O | Element::F);
 
         if element == Element::H {
             let bonded_electrons = bonding_electrons.saturating_mul(2);
@@ -613,7 +697,7 @@ fn assign_general(
 
         if element == Element::N
             && atom_mut.is_in_ring
-            && atom_mut.degree == 3
+            && atom_mut.covalent_degree() == 3
             && bonding_electrons == 4
             && double_bond_count == 1
         {
@@ -624,7 +708,7 @@ fn assign_general(
         if element == Element::C
             && atom_mut.has_aromatic_edge
             && atom_mut.is_in_ring
-            && atom_mut.degree == 3
+            && atom_mut.covalent_degree() == 3
             && double_bond_count == 0
         {
             atom_mut.lone_pairs = 0;
@@ -817,7 +901,7 @@ mod tests {
     }
 
     #[test]
-    fn phosphorus_oxide_assigns_positive_phosphorus() {
+    fn phosphoryl_groups_keep_a_neutral_phosphorus() {
         let elements = vec![Element::P, Element::O, Element::H, Element::H, Element::H];
         let bonds = vec![
             (0, 1, GraphBondOrder::Double),
@@ -826,11 +910,49 @@ mod tests {
             (0, 4, GraphBondOrder::Single),
         ];
 
+        let molecule = run_perception(&elements, &bonds);
+        assert_atom_state(&molecule, 0, 0, 0);
+        assert_atom_state(&molecule, 1, 0, 2);
+
+        // The charge-separated drawing H3P+-O- keeps its charges.
+        let bonds = [[(0, 1, GraphBondOrder::Single)].as_slice(), &bonds[1..]].concat();
         let molecule = run_perception(&elements, &bonds);
         assert_atom_state(&molecule, 0, 1, 0);
         assert_atom_state(&molecule, 1, -1, 3);
     }
 
+    #[test]
+    fn charge_separated_sulfur_oxides_keep_their_lone_pairs_honest() {
+        // Dimethyl sulfone drawn as (CH3)2S2+(O-)2 and dimethyl sulfoxide as (CH3)2S+O-.
+        let mut elements = vec![Element::S, Element::O, Element::O, Element::C, Element::C];
+        elements.extend([Element::H; 6]);
+        let mut bonds = vec![
+            (0, 1, GraphBondOrder::Single),
+            (0, 2, GraphBondOrder::Single),
+            (0, 3, GraphBondOrder::Single),
+            (0, 4, GraphBondOrder::Single),
+        ];
+        for h in 5..8 {
+            bonds.push((3, h, GraphBondOrder::Single));
+            bonds.push((4, h + 3, GraphBondOrder::Single));
+        }
+
+        let sulfone = run_perception(&elements, &bonds);
+        assert_atom_state(&sulfone, 0, 2, 0);
+        assert_atom_state(&sulfone, 1, -1, 3);
+        assert_atom_state(&sulfone, 2, -1, 3);
+
+        elements.remove(2);
+        let bonds: Vec<_> = bonds
+            .into_iter()
+            .filter(|&(a, b, _)| a != 2 && b != 2)
+            .map(|(a, b, order)| (a - usize::from(a > 2), b - usize::from(b > 2), order))
+            .collect();
+        let sulfoxide = run_perception(&elements, &bonds);
+        assert_atom_state(&sulfoxide, 0, 1, 1);
+        assert_atom_state(&sulfoxide, 1, -1, 3);
+    }
+
     #[test]
     fn halogen_oxyanions_force_trigonal_oxygens() {
         let elements = vec![Element::Cl, Element::O, Element::O, Element::O, Element::O];
@@ -849,6 +971,29 @@ mod tests {
         assert_atom_state(&molecule, 4, -1, 3);
     }
 
+    #[test]
+    fn halogen_oxyacid_hydroxyls_keep_general_counts() {
+        let elements = vec![
+            Element::Cl,
+            Element::O,
+            Element::O,
+            Element::O,
+            Element::O,
+            Element::H,
+        ];
+        let bonds = vec![
+            (0, 1, GraphBondOrder::Double),
+            (0, 2, GraphBondOrder::Double),
+            (0, 3, GraphBondOrder::Double),
+            (0, 4, GraphBondOrder::Single),
+            (4, 5, GraphBondOrder::Single),
+        ];
+
+        let molecule = run_perception(&elements, &bonds);
+
+        assert_atom_state(&molecule, 4, 0, 2);
+    }
+
     #[test]
     fn carboxylate_anion_marks_single_bonded_oxygen() {
         let elements = vec![
@@ -1083,6 +1228,30 @@ mod tests {
         assert_atom_state(&molecule, 2, 0, 0);
     }
 
+    #[test]
+    fn general_rules_leave_three_coordinate_boron_electron_deficient() {
+        let boron_trifluoride = run_perception(
+            &[Element::B, Element::F, Element::F, Element::F],
+            &[
+                (0, 1, GraphBondOrder::Single),
+                (0, 2, GraphBondOrder::Single),
+                (0, 3, GraphBondOrder::Single),
+            ],
+        );
+        assert_atom_state(&boron_trifluoride, 0, 0, 0);
+
+        let tetrafluoroborate = run_perception(
+            &[Element::B, Element::F, Element::F, Element::F, Element::F],
+            &[
+                (0, 1, GraphBondOrder::Single),
+                (0, 2, GraphBondOrder::Single),
+                (0, 3, GraphBondOrder::Single),
+                (0, 4, GraphBondOrder::Single),
+            ],
+        );
+        assert_atom_state(&tetrafluoroborate, 0, -1, 0);
+    }
+
     #[test]
     fn isolated_unknown_valence_metal_defaults_to_zero() {
         let elements = vec![Element::Au];
@@ -1094,18 +1263,71 @@ mod tests {
     }
 
     #[test]
-    fn bonded_unknown_valence_metal_errors() {
-        let elements = vec![Element::Au, Element::H];
-        let bonds = vec![(0, 1, GraphBondOrder::Single)];
+    fn isolated_alkali_and_alkaline_earth_ions_are_charged() {
+        let elements = vec![Element::Na, Element::Ca, Element::Fe];
+        let bonds: Vec<(usize, usize, GraphBondOrder)> = vec![];
 
-        let mut molecule = build_molecule(&elements, &bonds);
-        let err = perceive(&mut molecule).expect_err("bonded unknown-valence metals should error");
+        let molecule = run_perception(&elements, &bonds);
 
-        match err {
-            PerceptionError::Other(msg) => {
-                assert!(msg.contains("valence electrons not defined"));
-            }
-            _ => panic!("unexpected error variant: {err:?}"),
-        }
+        assert_atom_state(&molecule, 0, 1, 0);
+        assert_atom_state(&molecule, 1, 2, 0);
+        assert_atom_state(&molecule, 2, 0, 0);
+        assert!(molecule.atoms.iter().all(|atom| atom.is_metal_center));
+    }
+
+    #[test]
+    fn coordinated_ligands_keep_free_electron_counts() {
+        // Zn bound to a water oxygen and an ammonia nitrogen.
+        let elements = vec![
+            Element::Zn,
+            Element::O,
+            Element::H,
+            Element::H,
+            Element::N,
+            Element::H,
+            Element::H,
+            Element::H,
+        ];
+        let bonds = vec![
+            (0, 1, GraphBondOrder::Single),
+            (1, 2, GraphBondOrder::Single),
+            (1, 3, GraphBondOrder::Single),
+            (0, 4, GraphBondOrder::Single),
+            (4, 5, GraphBondOrder::Single),
+            (4, 6, GraphBondOrder::Single),
+            (4, 7, GraphBondOrder::Single),
+        ];
+
+        let molecule = run_perception(&elements, &bonds);
+
+        assert_atom_state(&molecule, 0, 0, 0);
+        assert_atom_state(&molecule, 1, 0, 2);
+        assert_atom_state(&molecule, 4, 0, 1);
+        assert_eq!(molecule.atoms[1].coordination_bonds, 1);
+        assert_eq!(molecule.atoms[4].covalent_degree(), 3);
+    }
+
+    #[test]
+    fn stated_carbon_charges_and_radicals_set_lone_pairs() {
+        let elements = [Element::C, Element::H, Element::H, Element::H];
+        let bonds = [
+            (0, 1, GraphBondOrder::Single),
+            (0, 2, GraphBondOrder::Single),
+            (0, 3, GraphBondOrder::Single),
+        ];
+        let methyl = |charge: i8, radicals: u8| {
+            let mut molecule = build_molecule(&elements, &bonds);
+            molecule.atoms[0].formal_charge = charge;
+            molecule.atoms[0].radical_electrons = radicals;
+            perceive(&mut molecule).expect("perception should succeed");
+            molecule
+        };
+
+        assert_atom_state(&methyl(1, 0), 0, 1, 0);
+        assert_atom_state(&methyl(-1, 0), 0, -1, 1);
+        assert_atom_state(&methyl(0, 1), 0, 0, 0);
+        // A state that does not fit the bonds falls back to the octet count.
+        assert_atom_state(&methyl(2, 0), 0, -1, 1);
+        assert_atom_state(&methyl(0, 0), 0, -1, 1);
     }
 }
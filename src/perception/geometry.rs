@@ -0,0 +1,261 @@
+//! Refines hybridization from measured 3D geometry when the input graph carries coordinates.
+//!
+//! Graph-based perception has to guess whether a three-coordinate atom with a lone pair, such
+//! as an amine or aniline nitrogen, is pyramidal or flattened by conjugation. When coordinates
+//! are available this stage measures the sum of the three bond angles instead: a planar center
+//! becomes sp2 (resonant when bonded to a π system), and a clearly pyramidal center that the
+//! conjugation heuristics promoted is returned to sp3. Aromatic atoms and members of detected
+//! resonance systems keep their graph-based assignment so that the topology stays consistent.
+
+use super::model::AnnotatedMolecule;
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::float::Float;
+use crate::compat::prelude::*;
+use crate::core::error::PerceptionError;
+use crate::core::properties::Hybridization;
+
+/// Bond-angle sum (degrees) at or above which a three-coordinate center counts as planar.
+pub(super) const PLANAR_ANGLE_SUM: f64 = 350.0;
+
+/// Bond-angle sum (degrees) at or below which a three-coordinate center counts as pyramidal.
+///
+/// An ideal tetrahedral center gives 328.4°; the gap to [`PLANAR_ANGLE_SUM`] leaves
+/// ambiguous geometries with their graph-based assignment.
+pub(super) const PYRAMIDAL_ANGLE_SUM: f64 = 340.0;
+
+/// Spread of z coordinates below which positions are treated as a 2D depiction.
+const FLAT_DEPICTION_TOLERANCE: f64 = 1e-4;
+
+/// Adjusts hybridization and steric numbers of three-coordinate lone-pair centers.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule after hybridization inference; unchanged when it has no
+///   coordinates or only a 2D depiction.
+///
+/// # Returns
+///
+/// `Ok(())` always, as this process is infallible.
+pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError> {
+    let Some(positions) = molecule.positions.as_deref() else {
+        return Ok(());
+    };
+    if is_flat_depiction(positions) {
+        return Ok(());
+    }
+
+    let mut in_resonance_system = vec![false; molecule.atoms.len()];
+    for system in &molecule.resonance_systems {
+        for &atom_id in &system.atom_ids {
+            in_resonance_system[atom_id] = true;
+        }
+    }
+
+    let mut updates = Vec::new();
+    for atom in &molecule.atoms {
+        if atom.is_aromatic
+            || in_resonance_system[atom.id]
+            || atom.is_metal_center
+            || atom.lone_pairs != 1
+            || molecule.adjacency[atom.id].len() != 3
+        {
+            continue;
+        }
+
+        let neighbors: Vec<usize> = molecule.adjacency[atom.id]
+            .iter()
+            .map(|&(id, _)| id)
+            .collect();
+        let Some(angle_sum) = bond_angle_sum(positions, atom.id, &neighbors) else {
+            continue;
+        };
+
+        let refined = if angle_sum >= PLANAR_ANGLE_SUM && atom.hybridization == Hybridization::SP3 {
+            let is_conjugated = neighbors.iter().any(|&id| {
+                let neighbor = &molecule.atoms[id];
+                neighbor.is_aromatic
+                    || matches!(
+                        neighbor.hybridization,
+                        Hybridization::SP2 | Hybridization::SP | Hybridization::Resonant
+                    )
+            });
+            if is_conjugated {
+                Hybridization::Resonant
+            } else {
+                Hybridization::SP2
+            }
+        } else if angle_sum <= PYRAMIDAL_ANGLE_SUM && atom.hybridization == Hybridization::Resonant
+        {
+            Hybridization::SP3
+        } else {
+            continue;
+        };
+        updates.push((atom.id, refined));
+    }
+
+    for (atom_id, hybridization) in updates {
+        let atom = &mut molecule.atoms[atom_id];
+        atom.hybridization = hybridization;
+        atom.is_resonant = hybridization == Hybridization::Resonant;
+        atom.steric_number = if hybridization == Hybridization::SP3 {
+            4
+        } else {
+            3
+        };
+    }
+
+    Ok(())
+}
+
+/// Returns `true` when every z coordinate is the same, as in a 2D structure drawing.
+pub(super) fn is_flat_depiction(positions: &[[f64; 3]]) -> bool {
+    let (min, max) = positions
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), p| {
+            (min.min(p[2]), max.max(p[2]))
+        });
+    max - min < FLAT_DEPICTION_TOLERANCE
+}
+
+/// Sums the bond angles (degrees) between every pair of `neighbors` at `center`.
+///
+/// Returns `None` when a bond has zero or non-finite length.
+pub(super) fn bond_angle_sum(
+    positions: &[[f64; 3]],
+    center: usize,
+    neighbors: &[usize],
+) -> Option<f64> {
+    let bond = |id: usize| -> Option<[f64; 3]> {
+        let v = [0, 1, 2].map(|k| positions[id][k] - positions[center][k]);
+        let length = v.iter().map(|c| c * c).sum::<f64>().sqrt();
+        (length.is_finite() && length > 0.0).then(|| v.map(|c| c / length))
+    };
+    let bonds: Vec<[f64; 3]> = neighbors
+        .iter()
+        .map(|&id| bond(id))
+        .collect::<Option<_>>()?;
+
+    let mut sum = 0.0;
+    for i in 0..bonds.len() {
+        for j in (i + 1)..bonds.len() {
+            let cosine: f64 = (0..3).map(|k| bonds[i][k] * bonds[j][k]).sum();
+            sum += cosine.clamp(-1.0, 1.0).acos().to_degrees();
+        }
+    }
+    Some(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::limits::Budget;
+    use crate::core::properties::{Element, GraphBondOrder};
+    use crate::perception::{PerceptionOptions, PerceptionPipeline};
+
+    /// Vinylamine, `C=C-N(H)H`, with the nitrogen (atom 2) at the origin.
+    fn vinylamine(nitrogen_bonds: [[f64; 3]; 3]) -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        let n = graph.add_atom(Element::N);
+        graph.add_bond(c1, c2, GraphBondOrder::Double).unwrap();
+        graph.add_bond(c2, n, GraphBondOrder::Single).unwrap();
+        for c in [c1, c1, c2] {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+        }
+        for _ in 0..2 {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(n, h, GraphBondOrder::Single).unwrap();
+        }
+
+        let [to_c2, to_h1, to_h2] = nitrogen_bonds;
+        let positions = vec![
+            [to_c2[0] * 2.0, 1.0, 0.3],
+            to_c2,
+            [0.0; 3],
+            [3.0, 1.5, 0.2],
+            [3.0, 0.5, -0.2],
+            [1.5, -1.0, 0.1],
+            to_h1,
+            to_h2,
+        ];
+        graph.set_positions(positions).unwrap();
+        graph
+    }
+
+    fn nitrogen_hybridization(graph: &MolecularGraph) -> Hybridization {
+        let molecule = crate::perception::perceive(
+            graph,
+            &[],
+            PerceptionPipeline::standard_ref(),
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+            &mut |_| {},
+        )
+        .unwrap();
+        molecule.atoms[2].hybridization
+    }
+
+    const PYRAMIDAL: [[f64; 3]; 3] = [
+        [1.4, 0.0, 0.0],
+        [-0.337, 0.953, 0.0],
+        [-0.337, -0.477, 0.825],
+    ];
+
+    const PLANAR: [[f64; 3]; 3] = [[1.4, 0.0, 0.0], [-0.5, 0.87, 0.0], [-0.5, -0.87, 0.0]];
+
+    #[test]
+    fn pyramidal_enamine_nitrogen_is_returned_to_sp3() {
+        let mut graph = vinylamine(PYRAMIDAL);
+        assert_eq!(nitrogen_hybridization(&graph), Hybridization::SP3);
+
+        graph.positions = None;
+        assert_eq!(nitrogen_hybridization(&graph), Hybridization::Resonant);
+    }
+
+    #[test]
+    fn planar_enamine_nitrogen_stays_resonant() {
+        let graph = vinylamine(PLANAR);
+        assert_eq!(nitrogen_hybridization(&graph), Hybridization::Resonant);
+    }
+
+    #[test]
+    fn planar_amine_without_pi_neighbor_becomes_sp2() {
+        let mut graph = MolecularGraph::new();
+        let n = graph.add_atom(Element::N);
+        for _ in 0..3 {
+            let si = graph.add_atom(Element::Si);
+            graph.add_bond(n, si, GraphBondOrder::Single).unwrap();
+        }
+        graph
+            .set_positions(vec![
+                [0.0, 0.0, 0.0],
+                [1.7, 0.0, 0.0],
+                [-0.85, 1.47, 0.0],
+                [-0.85, -1.47, 0.01],
+            ])
+            .unwrap();
+        let mut molecule = AnnotatedMolecule::new(&graph).unwrap();
+        molecule.atoms[0].lone_pairs = 1;
+        molecule.atoms[0].hybridization = Hybridization::SP3;
+
+        perceive(&mut molecule).unwrap();
+        assert_eq!(molecule.atoms[0].hybridization, Hybridization::SP2);
+        assert_eq!(molecule.atoms[0].steric_number, 3);
+    }
+
+    #[test]
+    fn flat_depictions_are_ignored() {
+        let mut flat = PYRAMIDAL;
+        for bond in &mut flat {
+            bond[2] = 0.0;
+        }
+        let mut graph = vinylamine(flat);
+        for position in graph.positions.as_mut().unwrap() {
+            position[2] = 0.0;
+        }
+        assert_eq!(nitrogen_hybridization(&graph), Hybridization::Resonant);
+    }
+}
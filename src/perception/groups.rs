@@ -0,0 +1,477 @@
+//! Labels common functional groups on the atoms that form them.
+//!
+//! Each group is recognized from elements and Kekulé bond orders around a central atom: the
+//! carbonyl carbon of carboxyl, ester, and amide groups, the nitrogen of a nitro group, the
+//! sulfur of a sulfoxide, sulfonyl, or sulfonamide, the phosphorus of a phosphoryl or phosphate,
+//! the carbon of a guanidine, and the central nitrogen of an azide. The
+//! center, its defining heteroatoms, and the hydrogens on those heteroatoms are labeled, so rules
+//! can single out, for example, the acidic hydrogen of a carboxylic acid.
+
+use super::model::AnnotatedMolecule;
+use crate::compat::prelude::*;
+use crate::core::error::PerceptionError;
+use crate::core::properties::{Element, FunctionalGroup, GraphBondOrder};
+
+/// Labels every atom with the functional groups it belongs to.
+///
+/// Labels are recomputed from scratch, so running the stage twice yields the same result.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule after Kekulé expansion and aromaticity perception.
+/// * `metal_bound_groups` - Whether atoms bonded to metal centers still count as terminal.
+///
+/// # Returns
+///
+/// `Ok(())` always, as this process is infallible.
+pub fn perceive(
+    molecule: &mut AnnotatedMolecule,
+    metal_bound_groups: bool,
+) -> Result<(), PerceptionError> {
+    for atom in &mut molecule.atoms {
+        atom.functional_groups.clear();
+    }
+
+    for center in 0..molecule.atoms.len() {
+        match molecule.atoms[center].element {
+            Element::C => {
+                detect_carbonyl_groups(molecule, center, metal_bound_groups);
+                detect_guanidine(molecule, center);
+            }
+            Element::N => {
+                detect_nitro(molecule, center, metal_bound_groups);
+                detect_azide(molecule, center, metal_bound_groups);
+            }
+            Element::S => {
+                detect_sulfur_oxide(molecule, center, metal_bound_groups);
+                detect_sulfonamide(molecule, center, metal_bound_groups);
+            }
+            Element::P => {
+                detect_phosphoryl(molecule, center, metal_bound_groups);
+                detect_phosphate(molecule, center);
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Detects the carboxyl, ester, and amide groups around a carbonyl carbon.
+///
+/// The carbon needs a terminal oxygen bonded by a double bond. Singly bonded nitrogens make it
+/// an amide; singly bonded oxygens make it a carboxyl when they are terminal and an ester when
+/// they bridge to another heavy atom.
+fn detect_carbonyl_groups(
+    molecule: &mut AnnotatedMolecule,
+    carbon: usize,
+    metal_bound_groups: bool,
+) {
+    let oxo = molecule.adjacency[carbon]
+        .iter()
+        .find(|&&(neighbor, order)| {
+            order == GraphBondOrder::Double
+                && molecule.atoms[neighbor].element == Element::O
+                && is_terminal(molecule, neighbor, metal_bound_groups)
+        })
+        .map(|&(neighbor, _)| neighbor);
+    let Some(oxo) = oxo else {
+        return;
+    };
+
+    let mut nitrogens = Vec::new();
+    let mut hydroxyls = Vec::new();
+    let mut bridges = Vec::new();
+    for &(neighbor, order) in &molecule.adjacency[carbon] {
+        if order != GraphBondOrder::Single {
+            continue;
+        }
+        match molecule.atoms[neighbor].element {
+            Element::N => nitrogens.push(neighbor),
+            Element::O if is_terminal(molecule, neighbor, metal_bound_groups) => {
+                hydroxyls.push(neighbor)
+            }
+            Element::O => bridges.push(neighbor),
+            _ => {}
+        }
+    }
+
+    if !nitrogens.is_empty() {
+        label(molecule, carbon, &[oxo], &nitrogens, FunctionalGroup::Amide);
+    }
+    if !hydroxyls.is_empty() {
+        label(
+            molecule,
+            carbon,
+            &[oxo],
+            &hydroxyls,
+            FunctionalGroup::Carboxyl,
+        );
+    }
+    if !bridges.is_empty() {
+        label(molecule, carbon, &[oxo], &bridges, FunctionalGroup::Ester);
+    }
+}
+
+/// Detects a guanidine or guanidinium carbon: three nitrogens, one of them doubly bonded.
+///
+/// Aromatic carbons such as C2 of 2-aminopyrimidine share this pattern but are left alone.
+fn detect_guanidine(molecule: &mut AnnotatedMolecule, carbon: usize) {
+    let neighbors = &molecule.adjacency[carbon];
+    if molecule.atoms[carbon].is_aromatic
+        || neighbors.len() != 3
+        || neighbors
+            .iter()
+            .any(|&(neighbor, _)| molecule.atoms[neighbor].element != Element::N)
+        || !neighbors
+            .iter()
+            .any(|&(_, order)| order == GraphBondOrder::Double)
+    {
+        return;
+    }
+    let nitrogens: Vec<usize> = neighbors.iter().map(|&(neighbor, _)| neighbor).collect();
+    label(
+        molecule,
+        carbon,
+        &[],
+        &nitrogens,
+        FunctionalGroup::Guanidinium,
+    );
+}
+
+/// Detects a nitro nitrogen: three neighbors, two of them terminal oxygens.
+fn detect_nitro(molecule: &mut AnnotatedMolecule, nitrogen: usize, metal_bound_groups: bool) {
+    let oxygens: Vec<usize> = molecule.adjacency[nitrogen]
+        .iter()
+        .map(|&(neighbor, _)| neighbor)
+        .filter(|&neighbor| molecule.atoms[neighbor].element == Element::O)
+        .collect();
+    if molecule.adjacency[nitrogen].len() != 3
+        || oxygens.len() != 2
+        || !oxygens
+            .iter()
+            .all(|&oxygen| molecule.atoms[oxygen].group_degree(metal_bound_groups) == 1)
+    {
+        return;
+    }
+    label(molecule, nitrogen, &oxygens, &[], FunctionalGroup::Nitro);
+}
+
+/// Detects the central nitrogen of an azide.
+fn detect_azide(molecule: &mut AnnotatedMolecule, nitrogen: usize, metal_bound_groups: bool) {
+    if !is_azide_center(molecule, nitrogen, metal_bound_groups) {
+        return;
+    }
+    let ends = [
+        molecule.adjacency[nitrogen][0].0,
+        molecule.adjacency[nitrogen][1].0,
+    ];
+    label(molecule, nitrogen, &ends, &[], FunctionalGroup::Azide);
+}
+
+/// Returns `true` if `nitrogen` is the central atom of an azide: two nitrogen neighbors, one of
+/// them terminal, bonded as N=N=N or N-N#N.
+pub(super) fn is_azide_center(
+    molecule: &AnnotatedMolecule,
+    nitrogen: usize,
+    metal_bound_groups: bool,
+) -> bool {
+    let neighbors = &molecule.adjacency[nitrogen];
+    if molecule.atoms[nitrogen].element != Element::N
+        || neighbors.len() != 2
+        || neighbors
+            .iter()
+            .any(|&(neighbor, _)| molecule.atoms[neighbor].element != Element::N)
+    {
+        return false;
+    }
+    let is_cumulated = matches!(
+        [neighbors[0].1, neighbors[1].1],
+        [GraphBondOrder::Double, GraphBondOrder::Double]
+            | [GraphBondOrder::Single, GraphBondOrder::Triple]
+            | [GraphBondOrder::Triple, GraphBondOrder::Single]
+    );
+    is_cumulated
+        && neighbors
+            .iter()
+            .any(|&(end, _)| molecule.atoms[end].group_degree(metal_bound_groups) == 1)
+}
+
+/// Detects a sulfoxide or sulfonyl sulfur from its number of neighbors and terminal oxygens.
+fn detect_sulfur_oxide(molecule: &mut AnnotatedMolecule, sulfur: usize, metal_bound_groups: bool) {
+    let oxygens = terminal_oxygens(molecule, sulfur, metal_bound_groups);
+    let group = match (molecule.adjacency[sulfur].len(), oxygens.len()) {
+        (3, 1) => FunctionalGroup::Sulfoxide,
+        (4, 2..) => FunctionalGroup::Sulfonyl,
+        _ => return,
+    };
+    label(molecule, sulfur, &oxygens, &[], group);
+}
+
+/// Detects a sulfonamide sulfur: two terminal oxygens and at least one nitrogen.
+fn detect_sulfonamide(molecule: &mut AnnotatedMolecule, sulfur: usize, metal_bound_groups: bool) {
+    let mut oxygens = Vec::new();
+    let mut nitrogens = Vec::new();
+    for &(neighbor, _) in &molecule.adjacency[sulfur] {
+        match molecule.atoms[neighbor].element {
+            Element::O if molecule.atoms[neighbor].group_degree(metal_bound_groups) == 1 => {
+                oxygens.push(neighbor)
+            }
+            Element::N => nitrogens.push(neighbor),
+            _ => {}
+        }
+    }
+    if oxygens.len() != 2 || nitrogens.is_empty() {
+        return;
+    }
+    label(
+        molecule,
+        sulfur,
+        &oxygens,
+        &nitrogens,
+        FunctionalGroup::Sulfonamide,
+    );
+}
+
+/// Detects a phosphoryl phosphorus: four neighbors, at least one of them a terminal oxygen.
+fn detect_phosphoryl(
+    molecule: &mut AnnotatedMolecule,
+    phosphorus: usize,
+    metal_bound_groups: bool,
+) {
+    let oxygens = terminal_oxygens(molecule, phosphorus, metal_bound_groups);
+    if molecule.adjacency[phosphorus].len() != 4 || oxygens.is_empty() {
+        return;
+    }
+    label(
+        molecule,
+        phosphorus,
+        &oxygens,
+        &[],
+        FunctionalGroup::Phosphoryl,
+    );
+}
+
+/// Detects a phosphate phosphorus: four oxygens, bridging or terminal.
+fn detect_phosphate(molecule: &mut AnnotatedMolecule, phosphorus: usize) {
+    let neighbors = &molecule.adjacency[phosphorus];
+    if neighbors.len() != 4
+        || neighbors
+            .iter()
+            .any(|&(neighbor, _)| molecule.atoms[neighbor].element != Element::O)
+    {
+        return;
+    }
+    let oxygens: Vec<usize> = neighbors.iter().map(|&(neighbor, _)| neighbor).collect();
+    label(
+        molecule,
+        phosphorus,
+        &[],
+        &oxygens,
+        FunctionalGroup::Phosphate,
+    );
+}
+
+/// Returns the oxygens bonded to `center` and to nothing else, whatever the bond order.
+///
+/// With `metal_bound_groups` set, bonds to metal centers are ignored, as everywhere in this module.
+fn terminal_oxygens(
+    molecule: &AnnotatedMolecule,
+    center: usize,
+    metal_bound_groups: bool,
+) -> Vec<usize> {
+    molecule.adjacency[center]
+        .iter()
+        .map(|&(neighbor, _)| neighbor)
+        .filter(|&neighbor| {
+            molecule.atoms[neighbor].element == Element::O
+                && molecule.atoms[neighbor].group_degree(metal_bound_groups) == 1
+        })
+        .collect()
+}
+
+/// Returns `true` if every neighbor of the atom except one heavy atom is a hydrogen, leaving
+/// out metal centers when `metal_bound_groups` is set.
+fn is_terminal(molecule: &AnnotatedMolecule, atom: usize, metal_bound_groups: bool) -> bool {
+    molecule.adjacency[atom]
+        .iter()
+        .filter(|&&(neighbor, _)| {
+            let neighbor = &molecule.atoms[neighbor];
+            neighbor.element != Element::H && !(metal_bound_groups && neighbor.is_metal_center)
+        })
+        .count()
+        == 1
+}
+
+/// Adds `group` to the center, the `members`, and the `protic` members with their hydrogens.
+fn label(
+    molecule: &mut AnnotatedMolecule,
+    center: usize,
+    members: &[usize],
+    protic: &[usize],
+    group: FunctionalGroup,
+) {
+    let mut atoms = vec![center];
+    atoms.extend_from_slice(members);
+    for &atom in protic {
+        atoms.push(atom);
+        atoms.extend(
+            molecule.adjacency[atom]
+                .iter()
+                .map(|&(neighbor, _)| neighbor)
+                .filter(|&neighbor| molecule.atoms[neighbor].element == Element::H),
+        );
+    }
+    for atom in atoms {
+        let groups = &mut molecule.atoms[atom].functional_groups;
+        if !groups.contains(&group) {
+            groups.push(group);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::limits::Budget;
+    use crate::io::smiles::parse_smiles;
+    use crate::perception::{PerceptionOptions, PerceptionPipeline};
+
+    /// Perceives a SMILES string and returns the groups of every atom.
+    fn groups(smiles: &str) -> Vec<Vec<FunctionalGroup>> {
+        let graph = parse_smiles(smiles).unwrap();
+        let molecule = crate::perception::perceive(
+            &graph,
+            &[],
+            PerceptionPipeline::standard_ref(),
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+            &mut |_| {},
+        )
+        .unwrap();
+        molecule
+            .atoms
+            .into_iter()
+            .map(|atom| atom.functional_groups)
+            .collect()
+    }
+
+    /// Returns the IDs of the atoms labeled with `group`.
+    fn members(groups: &[Vec<FunctionalGroup>], group: FunctionalGroup) -> Vec<usize> {
+        (0..groups.len())
+            .filter(|&id| groups[id].contains(&group))
+            .collect()
+    }
+
+    #[test]
+    fn carbonyl_groups_include_their_heteroatoms_and_protons() {
+        // Acetic acid: C0 C1(=O2) O3, with the hydroxyl hydrogen last.
+        let acid = groups("CC(=O)O");
+        assert_eq!(members(&acid, FunctionalGroup::Carboxyl), [1, 2, 3, 7]);
+
+        let acetate = groups("CC(=O)[O-]");
+        assert_eq!(members(&acetate, FunctionalGroup::Carboxyl), [1, 2, 3]);
+
+        // Methyl acetate: the alkoxy carbon C4 is not part of the ester.
+        let ester = groups("CC(=O)OC");
+        assert_eq!(members(&ester, FunctionalGroup::Ester), [1, 2, 3]);
+        assert!(members(&ester, FunctionalGroup::Carboxyl).is_empty());
+
+        // N-methylacetamide: C1(=O2) N3 and the amide hydrogen.
+        let amide = groups("CC(=O)NC");
+        assert_eq!(members(&amide, FunctionalGroup::Amide), [1, 2, 3, 8]);
+
+        // Methyl carbamate is both an amide and an ester.
+        let carbamate = groups("NC(=O)OC");
+        assert_eq!(
+            carbamate[1],
+            [FunctionalGroup::Amide, FunctionalGroup::Ester]
+        );
+    }
+
+    #[test]
+    fn heteroatom_centered_groups_are_labeled() {
+        let nitro = groups("C[N+](=O)[O-]");
+        assert_eq!(members(&nitro, FunctionalGroup::Nitro), [1, 2, 3]);
+
+        // Methanesulfonamide: S1 with O2, O3, N4 and its two hydrogens.
+        let sulfonamide = groups("CS(=O)(=O)N");
+        assert_eq!(
+            members(&sulfonamide, FunctionalGroup::Sulfonamide),
+            [1, 2, 3, 4, 8, 9]
+        );
+
+        // Methyl phosphate: P2 and its four oxygens, not the methyl carbon.
+        let phosphate = groups("COP(=O)([O-])[O-]");
+        assert_eq!(
+            members(&phosphate, FunctionalGroup::Phosphoryl),
+            [2, 3, 4, 5]
+        );
+        assert_eq!(
+            members(&phosphate, FunctionalGroup::Phosphate),
+            [1, 2, 3, 4, 5]
+        );
+
+        // Methyl azide in both forms: the three nitrogens, not the methyl carbon.
+        for smiles in ["CN=[N+]=[N-]", "C[N-][N+]#N"] {
+            assert_eq!(members(&groups(smiles), FunctionalGroup::Azide), [1, 2, 3]);
+        }
+        assert!(members(&groups("CN=NC"), FunctionalGroup::Azide).is_empty());
+    }
+
+    #[test]
+    fn hypervalent_sulfur_and_phosphorus_oxides_are_labeled() {
+        // Sulfoxide and sulfinic acid: the sulfur and its terminal oxygen.
+        assert_eq!(
+            members(&groups("CS(C)=O"), FunctionalGroup::Sulfoxide),
+            [1, 3]
+        );
+        assert_eq!(
+            members(&groups("CS(=O)O"), FunctionalGroup::Sulfoxide),
+            [1, 2]
+        );
+
+        // Sulfones, sulfonates, and sulfates are sulfonyls however they are drawn; the sulfonamide
+        // sulfur is a sulfonyl as well.
+        for smiles in ["CS(C)(=O)=O", "C[S+2](C)([O-])[O-]"] {
+            assert_eq!(
+                members(&groups(smiles), FunctionalGroup::Sulfonyl),
+                [1, 3, 4]
+            );
+        }
+        assert_eq!(
+            members(&groups("OS(=O)(=O)[O-]"), FunctionalGroup::Sulfonyl),
+            [1, 2, 3, 4]
+        );
+        assert_eq!(
+            members(&groups("CS(=O)(=O)N"), FunctionalGroup::Sulfonyl),
+            [1, 2, 3]
+        );
+        assert!(members(&groups("CSC"), FunctionalGroup::Sulfoxide).is_empty());
+
+        // Trimethylphosphine oxide, charge-separated or not, and methylphosphonic acid.
+        for smiles in ["CP(C)(C)=O", "C[P+](C)(C)[O-]"] {
+            assert_eq!(
+                members(&groups(smiles), FunctionalGroup::Phosphoryl),
+                [1, 4]
+            );
+        }
+        assert_eq!(
+            members(&groups("CP(=O)(O)O"), FunctionalGroup::Phosphoryl),
+            [1, 2]
+        );
+        assert!(members(&groups("CP(C)C"), FunctionalGroup::Phosphoryl).is_empty());
+    }
+
+    #[test]
+    fn guanidinium_is_labeled_outside_aromatic_rings() {
+        let guanidinium = groups("NC(N)=[NH2+]");
+        assert_eq!(
+            members(&guanidinium, FunctionalGroup::Guanidinium).len(),
+            4 + 6
+        );
+
+        let aminopyrimidine = groups("Nc1ncccn1");
+        assert!(members(&aminopyrimidine, FunctionalGroup::Guanidinium).is_empty());
+        assert!(groups("CCCC").iter().all(|atom| atom.is_empty()));
+    }
+}
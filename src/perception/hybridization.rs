@@ -4,14 +4,16 @@
 //! (degree, lone pairs, conjugation, aromatic flags) into concrete `Hybridization` labels and
 //! the corresponding steric numbers required by later typing decisions.
 
-use super::model::{AnnotatedAtom, AnnotatedMolecule};
+use super::model::{AnnotatedAtom, AnnotatedMolecule, ResonanceSystem};
+use crate::compat::prelude::*;
 use crate::core::error::PerceptionError;
 use crate::core::properties::{Element, GraphBondOrder, Hybridization};
 
 /// Updates every atom with its final hybridization and steric number assignments.
 ///
 /// The procedure evaluates the VSEPR steric number for each annotated atom, reconciles it
-/// with conjugation and aromatic flags, and records the resulting `Hybridization` along with
+/// with conjugation and aromatic flags, decides the planarity of lone-pair nitrogens, promotes
+/// oxygen donors adjacent to π systems, and records the resulting `Hybridization` along with
 /// the adjusted steric number in-place.
 ///
 /// # Arguments
@@ -23,16 +25,28 @@ use crate::core::properties::{Element, GraphBondOrder, Hybridization};
 /// Returns [`PerceptionError::HybridizationInference`] when an atom presents an unsupported
 /// steric environment.
 pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError> {
-    for atom in &mut molecule.atoms {
-        atom.hybridization = initial_hybridization(atom)?;
+    for i in 0..molecule.atoms.len() {
+        let atom = &molecule.atoms[i];
+        let Some(hybridization) = initial_hybridization(atom) else {
+            return Err(PerceptionError::HybridizationInference {
+                atom: molecule.atom_context(i),
+                steric_number: atom.covalent_degree() + atom.lone_pairs,
+            });
+        };
+        molecule.atoms[i].hybridization = hybridization;
     }
 
+    promote_conjugated_carbons(molecule);
+    super::nitrogen::classify(molecule);
+
+    // Oxygen donors next to a π system delocalize as well; a newly promoted oxygen can in turn
+    // promote another through `supports_delocalization`, so the scan repeats to a fixed point.
     loop {
         let mut changes = 0;
         for i in 0..molecule.atoms.len() {
             if molecule.atoms[i].hybridization == Hybridization::SP3
                 && molecule.atoms[i].lone_pairs > 0
-                && matches!(molecule.atoms[i].element, Element::O | Element::N)
+                && molecule.atoms[i].element == Element::O
             {
                 let is_adjacent_to_pi_system = molecule.adjacency[i]
                     .iter()
@@ -62,27 +76,148 @@ pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError>
     Ok(())
 }
 
+/// Promotes charged and radical carbons next to a π system to resonant hybridization.
+///
+/// The empty p orbital of a carbocation, the lone pair of a carbanion, and the unpaired electron
+/// of a carbon radical overlap with an adjacent π system, as in the allyl cation or the trityl
+/// radical, which flattens the center. A non-aromatic π system spreads the charge or spin over
+/// its whole conjugated chain, so its atoms become resonant too and the chain is recorded as a
+/// resonance system; an aromatic ring is already resonant.
+fn promote_conjugated_carbons(molecule: &mut AnnotatedMolecule) {
+    let promoted: Vec<usize> = molecule
+        .atoms
+        .iter()
+        .filter(|atom| {
+            atom.element == Element::C
+                && (atom.formal_charge != 0 || atom.radical_electrons > 0)
+                && matches!(atom.hybridization, Hybridization::SP2 | Hybridization::SP3)
+                && molecule.adjacency[atom.id]
+                    .iter()
+                    .all(|&(_, order)| order == GraphBondOrder::Single)
+                && molecule.adjacency[atom.id]
+                    .iter()
+                    .any(|&(neighbor_id, _)| super::nitrogen::is_pi_acceptor(molecule, neighbor_id))
+        })
+        .map(|atom| atom.id)
+        .collect();
+
+    for &atom_id in &promoted {
+        molecule.atoms[atom_id].hybridization = Hybridization::Resonant;
+        molecule.atoms[atom_id].is_resonant = true;
+    }
+    for atom_id in promoted {
+        delocalize_over_conjugated_chain(molecule, atom_id);
+    }
+}
+
+/// Makes the non-aromatic π bonds conjugated with a promoted carbon resonant, following
+/// alternating single and double bonds away from it.
+fn delocalize_over_conjugated_chain(molecule: &mut AnnotatedMolecule, center: usize) {
+    let mut atoms = vec![center];
+    let mut bonds = Vec::new();
+    let mut frontier = vec![center];
+    while let Some(atom_id) = frontier.pop() {
+        for &edge in &molecule.adjacency_with_bonds[atom_id] {
+            let neighbor_id = edge.neighbor_id;
+            if edge.order != GraphBondOrder::Single
+                || atoms.contains(&neighbor_id)
+                || !is_open_pi_center(molecule, neighbor_id)
+            {
+                continue;
+            }
+            let partners: Vec<_> = molecule.adjacency_with_bonds[neighbor_id]
+                .iter()
+                .filter(|partner| {
+                    partner.order == GraphBondOrder::Double
+                        && !atoms.contains(&partner.neighbor_id)
+                        && !molecule.atoms[partner.neighbor_id].is_aromatic
+                        && !molecule.atoms[partner.neighbor_id].is_resonant
+                })
+                .copied()
+                .collect();
+            if partners.is_empty() {
+                continue;
+            }
+            atoms.push(neighbor_id);
+            bonds.push(edge.bond_id);
+            for partner in partners {
+                atoms.push(partner.neighbor_id);
+                bonds.push(partner.bond_id);
+                frontier.push(partner.neighbor_id);
+            }
+        }
+    }
+    if bonds.is_empty() {
+        return;
+    }
+
+    for &atom_id in &atoms {
+        molecule.atoms[atom_id].hybridization = Hybridization::Resonant;
+        molecule.atoms[atom_id].is_resonant = true;
+    }
+    atoms.sort_unstable();
+    bonds.sort_unstable();
+    molecule.resonance_systems.push(ResonanceSystem {
+        atom_ids: atoms,
+        bond_ids: bonds,
+    });
+}
+
+/// Returns `true` for a trigonal, non-aromatic atom not yet claimed by a resonance system.
+fn is_open_pi_center(molecule: &AnnotatedMolecule, atom_id: usize) -> bool {
+    let atom = &molecule.atoms[atom_id];
+    atom.hybridization == Hybridization::SP2
+        && !atom.is_aromatic
+        && !atom.is_resonant
+        && !atom.is_metal_center
+}
+
 /// Determines the initial hybridization for a given atom, respecting resonance flags
 /// before applying pure VSEPR steric-number logic.
-fn initial_hybridization(atom: &AnnotatedAtom) -> Result<Hybridization, PerceptionError> {
-    if is_non_hybridized_element(atom.element) {
-        return Ok(Hybridization::None);
+///
+/// Returns `None` when the steric number has no hybridization class.
+fn initial_hybridization(atom: &AnnotatedAtom) -> Option<Hybridization> {
+    if atom.is_metal_center || is_non_hybridized_element(atom.element) {
+        return Some(Hybridization::None);
     }
 
     if atom.is_resonant && !atom.is_anti_aromatic {
-        return Ok(Hybridization::Resonant);
+        return Some(Hybridization::Resonant);
     }
 
-    let steric_number = atom.degree + atom.lone_pairs;
+    // A singly occupied orbital is an electron domain of its own (alkoxy radicals, NO), except
+    // on a carbon-like center with three bonds and no lone pair, where it takes the p orbital as
+    // in the methyl radical.
+    let mut steric_number = atom.covalent_degree() + atom.lone_pairs;
+    if atom.radical_electrons > 0 && (atom.lone_pairs > 0 || steric_number < 3) {
+        steric_number += 1;
+    }
     match steric_number {
-        4 => Ok(Hybridization::SP3),
-        3 => Ok(Hybridization::SP2),
-        2 => Ok(Hybridization::SP),
-        0 | 1 => Ok(Hybridization::None),
-        _ => Err(PerceptionError::HybridizationInference { atom_id: atom.id }),
+        4 => Some(Hybridization::SP3),
+        3 => Some(Hybridization::SP2),
+        2 => Some(Hybridization::SP),
+        0 | 1 => Some(Hybridization::None),
+        // Hypervalent centers such as SiF6(2-) or PF5 have no sp-hybrid description.
+        5 | 6 if !is_second_period(atom.element) => Some(Hybridization::None),
+        _ => None,
     }
 }
 
+/// Returns `true` for the second-period elements, which cannot expand their octet.
+fn is_second_period(element: Element) -> bool {
+    matches!(
+        element,
+        Element::Li
+            | Element::Be
+            | Element::B
+            | Element::C
+            | Element::N
+            | Element::O
+            | Element::F
+            | Element::Ne
+    )
+}
+
 /// Detects elements that should stay in the `None` hybridization state regardless of geometry.
 fn is_non_hybridized_element(element: Element) -> bool {
     matches!(
@@ -316,18 +451,35 @@ mod tests {
     }
 
     #[test]
-    fn steric_numbers_above_four_raise_an_error() {
-        let mut molecule = build_molecule(&[Element::S], &[], |mol| {
-            mol.atoms[0].degree = 6;
+    fn steric_numbers_above_four_raise_an_error_on_second_period_atoms() {
+        let mut molecule = build_molecule(&[Element::C], &[], |mol| {
+            mol.atoms[0].degree = 5;
         });
-        let err = perceive(&mut molecule).expect_err("steric 6 should fail");
+        let err = perceive(&mut molecule).expect_err("steric 5 on carbon should fail");
 
         match err {
-            PerceptionError::HybridizationInference { atom_id } => assert_eq!(atom_id, 0),
+            PerceptionError::HybridizationInference {
+                atom,
+                steric_number,
+            } => {
+                assert_eq!(atom.atom_id, 0);
+                assert_eq!(atom.element, Element::C);
+                assert_eq!(steric_number, 5);
+            }
             other => panic!("unexpected error returned: {other:?}"),
         }
     }
 
+    #[test]
+    fn hypervalent_heavier_atoms_have_no_hybridization() {
+        let mut molecule = build_molecule(&[Element::S], &[], |mol| {
+            mol.atoms[0].degree = 6;
+        });
+        perceive(&mut molecule).expect("hypervalent sulfur should be accepted");
+
+        assert_eq!(molecule.atoms[0].hybridization, Hybridization::None);
+    }
+
     #[test]
     fn carbonyl_carbon_does_not_propagate_resonance_to_adjacent_oxygen() {
         let mut molecule = build_molecule(
@@ -1,22 +1,31 @@
 //! Resolves aromatic bonds into concrete single/double assignments via a Kekulé solver.
 //!
 //! The logic here isolates aromatic systems, validates that they sit inside rings, and runs a
-//! backtracking search that respects valence limits and heteroatom allowances before updating the
-//! annotated molecule in-place.
+//! backtracking search for a structure in which every atom that needs a double bond gets exactly
+//! one. Systems without such a neutral structure (pyridinium, imidazolium, the cyclopentadienyl
+//! anion) fall back to a charge-aware search that admits the fewest formal charges before the
+//! annotated molecule is updated in-place.
 
 use super::model::AnnotatedMolecule;
-use crate::core::error::PerceptionError;
+use crate::compat::collections::HashMap;
+use crate::compat::collections::hash_map::Entry;
+use crate::compat::prelude::*;
+use crate::core::error::{LimitExceeded, PerceptionError};
+use crate::core::limits::Budget;
 use crate::core::properties::{Element, GraphBondOrder};
-use std::collections::{HashMap, VecDeque, hash_map::Entry};
+use alloc::collections::VecDeque;
 
 /// Converts aromatic bonds inside the molecule to alternating single/double assignments.
 ///
 /// The pass validates that every aromatic bond belongs to a ring, partitions the bonds into
-/// connected systems, and then runs a Kekulé solver per system.
+/// connected systems, and then runs a Kekulé solver per system. A system is first solved as a
+/// neutral structure; if none exists, onium heteroatoms and unpaired ring atoms are admitted in
+/// order of increasing charge cost.
 ///
 /// # Arguments
 ///
 /// * `molecule` - Annotated molecule whose bond orders and adjacency lists are mutated in place.
+/// * `budget` - Resource budget bounding the backtracking search.
 ///
 /// # Returns
 ///
@@ -25,8 +34,9 @@ use std::collections::{HashMap, VecDeque, hash_map::Entry};
 /// # Errors
 ///
 /// Returns [`PerceptionError::KekulizationFailed`] when an aromatic bond lies outside a ring or no
-/// valid alternating assignment exists for a system.
-pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError> {
+/// valid alternating assignment exists for a system, and [`PerceptionError::LimitExceeded`] when
+/// the search exhausts its step or time budget.
+pub fn perceive(molecule: &mut AnnotatedMolecule, budget: &Budget) -> Result<(), PerceptionError> {
     let mut aromatic_bonds = Vec::new();
     let mut aromatic_atom_flags = vec![false; molecule.atoms.len()];
     for bond in &molecule.bonds {
@@ -37,16 +47,13 @@ pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError>
         }
     }
 
-    for (atom, flag) in molecule
-        .atoms
-        .iter_mut()
-        .zip(aromatic_atom_flags.into_iter())
-    {
+    for (atom, flag) in molecule.atoms.iter_mut().zip(aromatic_atom_flags) {
         if flag {
             atom.has_aromatic_edge = true;
         }
     }
 
+    molecule.aromatic_input_bonds.clone_from(&aromatic_bonds);
     if aromatic_bonds.is_empty() {
         return Ok(());
     }
@@ -56,10 +63,13 @@ pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError>
     let aromatic_systems = find_aromatic_systems(molecule, &aromatic_bonds);
 
     let mut new_bond_orders = HashMap::new();
+    let mut steps = 0;
 
     for system_bonds in aromatic_systems {
-        let mut solver = KekuleSolver::new(molecule, &system_bonds);
-        match solver.solve() {
+        let mut solver = KekuleSolver::new(molecule, &system_bonds, budget, steps);
+        let solution = solver.solve()?;
+        steps = solver.steps;
+        match solution {
             Some(solution) => {
                 new_bond_orders.extend(solution);
             }
@@ -67,38 +77,66 @@ pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError>
                 return Err(PerceptionError::KekulizationFailed {
                     message: "could not find a valid Kekulé structure for an aromatic system"
                         .to_string(),
+                    atom_ids: bond_atom_ids(molecule, &system_bonds),
                 });
             }
         }
     }
 
     for (bond_id, new_order) in new_bond_orders {
-        let bond = molecule.bonds.iter_mut().find(|b| b.id == bond_id).unwrap();
-        let (u, v) = bond.atom_ids;
-        bond.order = new_order;
-
-        for (neighbor_id, order) in molecule.adjacency[u].iter_mut() {
-            if *neighbor_id == v {
-                *order = new_order;
-                break;
-            }
-        }
-        for (neighbor_id, order) in molecule.adjacency[v].iter_mut() {
-            if *neighbor_id == u {
-                *order = new_order;
-                break;
-            }
-        }
+        molecule.set_bond_order(bond_id, new_order);
     }
 
     Ok(())
 }
 
+/// π bookkeeping for one atom of an aromatic system.
+#[derive(Debug, Clone, Copy, Default)]
+struct PiSite {
+    /// Whether the neutral atom needs exactly one double bond among its aromatic bonds.
+    needs_double: bool,
+    /// Charge cost of leaving an atom that needs a double bond without one, if permitted.
+    unmatched_cost: Option<u32>,
+    /// Charge cost of giving a double bond to an atom that does not need one, if permitted.
+    onium_cost: Option<u32>,
+    /// Double bonds assigned so far.
+    doubles: u8,
+    /// Aromatic bonds of the atom still unassigned.
+    open: u8,
+}
+
+impl PiSite {
+    /// Returns the charge cost of the atom's final assignment, or `None` if it is not allowed.
+    fn closing_cost(&self) -> Option<u32> {
+        match (self.needs_double, self.doubles) {
+            (true, 1) | (false, 0) => Some(0),
+            (true, _) => self.unmatched_cost,
+            (false, _) => self.onium_cost,
+        }
+    }
+}
+
 /// Backtracking assignment helper that finds valid bond orders for one aromatic system.
+///
+/// The solver first looks for a neutral Kekulé structure, in which every atom that needs a
+/// double bond receives exactly one and no other atom receives any. Charged aromatics such as
+/// pyridinium, imidazolium, or the cyclopentadienyl anion have no such structure, so the solver
+/// then admits formal charges: a heteroatom taking an extra double bond (onium) or an atom left
+/// without one (carbocation, carbanion, azanide). The structure with the lowest total charge cost
+/// wins.
 struct KekuleSolver<'a> {
     molecule: &'a AnnotatedMolecule,
     bond_indices: Vec<usize>,
+    /// Atom IDs at both ends of each system bond, aligned with `bond_indices`.
+    endpoints: Vec<(usize, usize)>,
     assignments: Vec<Option<GraphBondOrder>>,
+    /// π bookkeeping indexed by atom ID; only atoms of the system are used.
+    sites: Vec<PiSite>,
+    /// Largest total charge cost the current search may spend.
+    charge_allowance: u32,
+    budget: &'a Budget,
+    /// Backtracking steps taken so far, accumulated across systems of the same molecule.
+    steps: u64,
 }
 
 impl<'a> KekuleSolver<'a> {
@@ -108,40 +146,83 @@ impl<'a> KekuleSolver<'a> {
     ///
     /// * `molecule` - Annotated molecule providing bond/atom metadata.
     /// * `system_bond_ids` - Aromatic bond IDs belonging to one connected system.
-    fn new(molecule: &'a AnnotatedMolecule, system_bond_ids: &[usize]) -> Self {
-        let bond_indices: Vec<usize> = system_bond_ids
+    /// * `budget` - Resource budget bounding the search.
+    /// * `steps` - Steps already spent on earlier systems of the same molecule.
+    fn new(
+        molecule: &'a AnnotatedMolecule,
+        system_bond_ids: &[usize],
+        budget: &'a Budget,
+        steps: u64,
+    ) -> Self {
+        let bond_indices = system_bond_ids.to_vec();
+        let endpoints: Vec<(usize, usize)> = bond_indices
             .iter()
-            .map(|id| molecule.bonds.iter().position(|b| b.id == *id).unwrap())
+            .map(|&idx| molecule.bonds[idx].atom_ids)
             .collect();
 
+        let mut sites = vec![PiSite::default(); molecule.atoms.len()];
+        for &(u, v) in &endpoints {
+            sites[u].open += 1;
+            sites[v].open += 1;
+        }
+        for (atom_id, site) in sites.iter_mut().enumerate() {
+            if site.open > 0 {
+                *site = pi_site(molecule, atom_id, site.open);
+            }
+        }
+
         Self {
             molecule,
-            bond_indices: bond_indices.clone(),
             assignments: vec![None; bond_indices.len()],
+            bond_indices,
+            endpoints,
+            sites,
+            charge_allowance: 0,
+            budget,
+            steps,
         }
     }
 
     /// Attempts to assign single/double orders to every bond in the system.
     ///
+    /// Searches with a charge allowance of zero first and raises it one unit at a time, so the
+    /// first structure found carries the fewest formal charges.
+    ///
     /// # Returns
     ///
     /// Map of bond IDs to resolved orders, or `None` if no assignment satisfies the constraints.
-    fn solve(&mut self) -> Option<HashMap<usize, GraphBondOrder>> {
-        if self.backtrack(0) {
-            let solution = self
-                .bond_indices
-                .iter()
-                .enumerate()
-                .map(|(i, &bond_idx)| {
-                    let bond_id = self.molecule.bonds[bond_idx].id;
-                    let order = self.assignments[i].unwrap();
-                    (bond_id, order)
-                })
-                .collect();
-            Some(solution)
-        } else {
-            None
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LimitExceeded`] when the search runs out of steps or time.
+    fn solve(&mut self) -> Result<Option<HashMap<usize, GraphBondOrder>>, LimitExceeded> {
+        let initial_sites = self.sites.clone();
+        let max_allowance: u32 = self
+            .sites
+            .iter()
+            .filter(|site| site.open > 0)
+            .map(|site| {
+                site.unmatched_cost
+                    .unwrap_or(0)
+                    .max(site.onium_cost.unwrap_or(0))
+            })
+            .sum();
+
+        for allowance in 0..=max_allowance {
+            self.charge_allowance = allowance;
+            self.sites.clone_from(&initial_sites);
+            self.assignments.fill(None);
+            if self.backtrack(0, 0)? {
+                let solution = self
+                    .bond_indices
+                    .iter()
+                    .zip(&self.assignments)
+                    .map(|(&bond_idx, order)| (self.molecule.bonds[bond_idx].id, order.unwrap()))
+                    .collect();
+                return Ok(Some(solution));
+            }
         }
+        Ok(None)
     }
 
     /// Recursively assigns orders while pruning inconsistent branches.
@@ -149,83 +230,132 @@ impl<'a> KekuleSolver<'a> {
     /// # Arguments
     ///
     /// * `k` - Index of the bond currently being assigned.
+    /// * `cost` - Charge cost of the atoms whose bonds are all assigned.
     ///
     /// # Returns
     ///
     /// `true` when a full assignment is found downstream.
-    fn backtrack(&mut self, k: usize) -> bool {
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LimitExceeded`] when the search runs out of steps or time.
+    fn backtrack(&mut self, k: usize, cost: u32) -> Result<bool, LimitExceeded> {
         if k == self.assignments.len() {
-            return true;
+            return Ok(true);
+        }
+
+        self.steps += 1;
+        if let Some(limit) = self.budget.limits.max_kekule_steps
+            && self.steps > limit
+        {
+            return Err(LimitExceeded::KekuleSteps { limit });
+        }
+        if self.steps.is_multiple_of(1024) {
+            self.budget.check_time()?;
         }
 
-        for &order_choice in &[GraphBondOrder::Double, GraphBondOrder::Single] {
+        let (u, v) = self.endpoints[k];
+        for order_choice in [GraphBondOrder::Double, GraphBondOrder::Single] {
+            let is_double = order_choice == GraphBondOrder::Double;
+            if is_double && (self.sites[u].doubles > 0 || self.sites[v].doubles > 0) {
+                continue;
+            }
+
             self.assignments[k] = Some(order_choice);
+            for atom_id in [u, v] {
+                let site = &mut self.sites[atom_id];
+                site.open -= 1;
+                site.doubles += u8::from(is_double);
+            }
 
-            if self.is_consistent(k) && self.backtrack(k + 1) {
-                return true;
+            let new_cost = [u, v]
+                .into_iter()
+                .filter(|&atom_id| self.sites[atom_id].open == 0)
+                .try_fold(cost, |total, atom_id| {
+                    Some(total + self.sites[atom_id].closing_cost()?)
+                });
+            let found = match new_cost {
+                Some(new_cost) if new_cost <= self.charge_allowance => {
+                    self.backtrack(k + 1, new_cost)?
+                }
+                _ => false,
+            };
+
+            for atom_id in [u, v] {
+                let site = &mut self.sites[atom_id];
+                site.open += 1;
+                site.doubles -= u8::from(is_double);
+            }
+            if found {
+                return Ok(true);
             }
         }
 
         self.assignments[k] = None;
-        false
+        Ok(false)
     }
+}
 
-    /// Checks whether the partial assignment at index `k` respects valence constraints.
-    fn is_consistent(&self, k: usize) -> bool {
-        let bond_idx = self.bond_indices[k];
-        let (u, v) = self.molecule.bonds[bond_idx].atom_ids;
-
-        self.is_valence_ok(u) && self.is_valence_ok(v)
+/// Derives the π bookkeeping of an atom from its neutral valence and its other bonds.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule providing adjacency and atom metadata.
+/// * `atom_id` - Atom inside the aromatic system.
+/// * `aromatic_bonds` - Number of aromatic bonds of the atom within the system.
+///
+/// # Returns
+///
+/// A [`PiSite`] with no bonds assigned yet.
+fn pi_site(molecule: &AnnotatedMolecule, atom_id: usize, aromatic_bonds: u8) -> PiSite {
+    let element = molecule.atoms[atom_id].element;
+    let fixed_valence: u8 = molecule.adjacency[atom_id]
+        .iter()
+        .filter(|&&(neighbor, order)| {
+            order != GraphBondOrder::Aromatic && !molecule.atoms[neighbor].is_metal_center
+        })
+        .map(|&(_, order)| bond_order_to_valence(order))
+        .sum();
+
+    let Some(valence) = neutral_valence(element) else {
+        // Without valence data any single double bond is accepted free of charge.
+        return PiSite {
+            needs_double: false,
+            unmatched_cost: None,
+            onium_cost: Some(0),
+            doubles: 0,
+            open: aromatic_bonds,
+        };
+    };
+
+    let (unmatched_cost, onium_cost) = match element {
+        Element::C | Element::Si | Element::Ge => (Some(2), None),
+        Element::B => (Some(2), Some(1)),
+        Element::N | Element::P | Element::As => (Some(1), Some(1)),
+        Element::O | Element::S | Element::Se | Element::Te => (Some(1), Some(1)),
+        _ => (None, None),
+    };
+
+    PiSite {
+        needs_double: valence > fixed_valence + aromatic_bonds,
+        unmatched_cost,
+        onium_cost,
+        doubles: 0,
+        open: aromatic_bonds,
     }
+}
 
-    /// Verifies that the atom's accumulated valence does not exceed its maximum allowed value.
-    ///
-    /// Lone allowances let aromatic nitrogens/phosphorus carry at most one double bond while still
-    /// being counted as having valence one toward aromatic contributions.
-    fn is_valence_ok(&self, atom_id: usize) -> bool {
-        let max_valence = get_max_valence(self.molecule.atoms[atom_id].element);
-        let mut current_valence = 0;
-        let mut aromatic_double_allowance = 0u8;
-        let element = self.molecule.atoms[atom_id].element;
-
-        for (neighbor_id, initial_order) in &self.molecule.adjacency[atom_id] {
-            let bond = self
-                .molecule
-                .bonds
-                .iter()
-                .find(|b| {
-                    (b.atom_ids.0 == atom_id && b.atom_ids.1 == *neighbor_id)
-                        || (b.atom_ids.0 == *neighbor_id && b.atom_ids.1 == atom_id)
-                })
-                .unwrap();
-
-            if *initial_order == GraphBondOrder::Aromatic {
-                if let Some(assigned_order) = self
-                    .bond_indices
-                    .iter()
-                    .position(|&idx| self.molecule.bonds[idx].id == bond.id)
-                    .and_then(|pos| self.assignments[pos])
-                {
-                    let contribution = if matches!(element, Element::N | Element::P)
-                        && assigned_order == GraphBondOrder::Double
-                    {
-                        if aromatic_double_allowance >= 1 {
-                            return false;
-                        }
-                        aromatic_double_allowance += 1;
-                        1
-                    } else {
-                        bond_order_to_valence(assigned_order)
-                    };
-                    current_valence += contribution;
-                }
-            } else {
-                current_valence += bond_order_to_valence(*initial_order);
-            }
-        }
-
-        current_valence <= max_valence
-    }
+/// Collects the sorted, deduplicated atom IDs spanned by a set of bonds.
+fn bond_atom_ids(molecule: &AnnotatedMolecule, bond_ids: &[usize]) -> Vec<usize> {
+    let mut atom_ids: Vec<usize> = molecule
+        .bonds
+        .iter()
+        .filter(|bond| bond_ids.contains(&bond.id))
+        .flat_map(|bond| [bond.atom_ids.0, bond.atom_ids.1])
+        .collect();
+    atom_ids.sort_unstable();
+    atom_ids.dedup();
+    atom_ids
 }
 
 /// Groups aromatic bonds into connected systems for independent solving.
@@ -258,25 +388,15 @@ fn find_aromatic_systems(
 
         while let Some(bond_id) = queue.pop_front() {
             current_system.push(bond_id);
-            let bond = molecule.bonds.iter().find(|b| b.id == bond_id).unwrap();
-            let (u, v) = bond.atom_ids;
+            let (u, v) = molecule.bonds[bond_id].atom_ids;
 
             for atom_id in [u, v] {
-                for (neighbor_id, order) in &molecule.adjacency[atom_id] {
-                    if *order == GraphBondOrder::Aromatic {
-                        let neighbor_bond = molecule
-                            .bonds
-                            .iter()
-                            .find(|b| {
-                                (b.atom_ids.0 == atom_id && b.atom_ids.1 == *neighbor_id)
-                                    || (b.atom_ids.0 == *neighbor_id && b.atom_ids.1 == atom_id)
-                            })
-                            .unwrap();
-
-                        if let Entry::Vacant(entry) = visited_bonds.entry(neighbor_bond.id) {
-                            entry.insert(true);
-                            queue.push_back(neighbor_bond.id);
-                        }
+                for neighbor in &molecule.adjacency_with_bonds[atom_id] {
+                    if neighbor.order == GraphBondOrder::Aromatic
+                        && let Entry::Vacant(entry) = visited_bonds.entry(neighbor.bond_id)
+                    {
+                        entry.insert(true);
+                        queue.push_back(neighbor.bond_id);
                     }
                 }
             }
@@ -301,37 +421,37 @@ fn validate_aromatic_bonds_in_rings(
     aromatic_bonds: &[usize],
 ) -> Result<(), PerceptionError> {
     for &bond_id in aromatic_bonds {
-        let bond = molecule.bonds.iter().find(|b| b.id == bond_id).unwrap();
-        let (u, v) = bond.atom_ids;
+        let (u, v) = molecule.bonds[bond_id].atom_ids;
         if !molecule.atoms[u].is_in_ring || !molecule.atoms[v].is_in_ring {
             return Err(PerceptionError::KekulizationFailed {
                 message: format!(
                     "aromatic bond (ID {}) found with at least one atom not in a ring",
                     bond_id
                 ),
+                atom_ids: vec![u.min(v), u.max(v)],
             });
         }
     }
     Ok(())
 }
 
-/// Returns the maximum valence allowed for the provided element.
+/// Returns the valence of the neutral element in its lowest common state.
 ///
 /// # Arguments
 ///
-/// * `element` - Element whose typical valence limit should be enforced.
+/// * `element` - Element of an aromatic atom.
 ///
 /// # Returns
 ///
-/// Maximum valence counted toward aromatic assignments.
-fn get_max_valence(element: Element) -> u8 {
+/// The number of bonds the uncharged atom forms, or `None` for elements (such as metals) whose
+/// valence cannot be assumed.
+fn neutral_valence(element: Element) -> Option<u8> {
     match element {
-        Element::H | Element::F | Element::Cl | Element::Br | Element::I => 1,
-        Element::O | Element::S => 2,
-        Element::N | Element::P => 3,
-        Element::C | Element::Si => 4,
-        Element::B => 3,
-        _ => 8,
+        Element::H | Element::F | Element::Cl | Element::Br | Element::I => Some(1),
+        Element::O | Element::S | Element::Se | Element::Te => Some(2),
+        Element::B | Element::N | Element::P | Element::As => Some(3),
+        Element::C | Element::Si | Element::Ge => Some(4),
+        _ => None,
     }
 }
 
@@ -381,6 +501,16 @@ mod tests {
     const PYRIDINE_RING: [usize; 6] = BENZENE_RING;
     const PYRIDINE_H_POSITIONS: [usize; 5] = [1, 2, 3, 4, 5];
 
+    const PYRROLE_ELEMENTS: [Element; 5] =
+        [Element::N, Element::C, Element::C, Element::C, Element::C];
+    const PYRROLE_BONDS: [(usize, usize); 5] = [(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)];
+    const PYRROLE_RING: [usize; 5] = [0, 1, 2, 3, 4];
+    const PYRROLE_H_POSITIONS: [usize; 5] = [0, 1, 2, 3, 4];
+
+    const PYRIDINIUM_H_POSITIONS: [usize; 6] = [0, 1, 2, 3, 4, 5];
+
+    const CYCLOPENTADIENYL_ELEMENTS: [Element; 5] = [Element::C; 5];
+
     const NAPHTHALENE_ELEMENTS: [Element; 10] = [Element::C; 10];
     const NAPHTHALENE_BONDS: [(usize, usize); 11] = [
         (0, 1),
@@ -465,7 +595,7 @@ mod tests {
     }
 
     fn assert_kekule_solution(molecule: &mut AnnotatedMolecule, rings: &[&[usize]]) {
-        perceive(molecule).expect("kekulization should succeed");
+        perceive(molecule, &Budget::default()).expect("kekulization should succeed");
         assert_no_aromatic_bonds(molecule);
         for &ring in rings {
             assert_alternating_cycle(molecule, ring);
@@ -508,6 +638,18 @@ mod tests {
         }
     }
 
+    fn double_bond_counts(molecule: &AnnotatedMolecule, atoms: &[usize]) -> Vec<usize> {
+        atoms
+            .iter()
+            .map(|&atom_id| {
+                molecule.adjacency[atom_id]
+                    .iter()
+                    .filter(|&&(_, order)| order == GraphBondOrder::Double)
+                    .count()
+            })
+            .collect()
+    }
+
     fn bond_order_between(molecule: &AnnotatedMolecule, u: usize, v: usize) -> GraphBondOrder {
         molecule
             .bonds
@@ -530,10 +672,12 @@ mod tests {
             &rings,
         );
 
-        let err = perceive(&mut molecule).expect_err("atoms must be flagged as ring members");
+        let err = perceive(&mut molecule, &Budget::default())
+            .expect_err("atoms must be flagged as ring members");
         match err {
-            PerceptionError::KekulizationFailed { message } => {
+            PerceptionError::KekulizationFailed { message, atom_ids } => {
                 assert!(message.contains("not in a ring"));
+                assert_eq!(atom_ids.len(), 2);
             }
             other => panic!("unexpected error: {other:?}"),
         }
@@ -612,4 +756,51 @@ mod tests {
         );
         assert_kekule_solution(&mut molecule, &rings);
     }
+
+    #[test]
+    fn pyrrole_nitrogen_receives_no_double_bond() {
+        let rings = [&PYRROLE_RING[..]];
+        let mut molecule = aromatic_fixture(
+            &PYRROLE_ELEMENTS,
+            &PYRROLE_BONDS,
+            &PYRROLE_H_POSITIONS,
+            &rings,
+        );
+        perceive(&mut molecule, &Budget::default()).expect("kekulization should succeed");
+        assert_no_aromatic_bonds(&molecule);
+        assert_eq!(
+            double_bond_counts(&molecule, &PYRROLE_RING),
+            [0, 1, 1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn pyridinium_places_the_positive_charge_on_nitrogen() {
+        let rings = [&PYRIDINE_RING[..]];
+        let mut molecule = aromatic_fixture(
+            &PYRIDINE_ELEMENTS,
+            &PYRIDINE_BONDS,
+            &PYRIDINIUM_H_POSITIONS,
+            &rings,
+        );
+        assert_kekule_solution(&mut molecule, &rings);
+        assert_eq!(double_bond_counts(&molecule, &PYRIDINE_RING), [1; 6]);
+    }
+
+    #[test]
+    fn cyclopentadienyl_anion_leaves_exactly_one_carbon_unpaired() {
+        let rings = [&PYRROLE_RING[..]];
+        let mut molecule = aromatic_fixture(
+            &CYCLOPENTADIENYL_ELEMENTS,
+            &PYRROLE_BONDS,
+            &PYRROLE_H_POSITIONS,
+            &rings,
+        );
+        perceive(&mut molecule, &Budget::default()).expect("kekulization should succeed");
+        assert_no_aromatic_bonds(&molecule);
+
+        let mut counts = double_bond_counts(&molecule, &PYRROLE_RING);
+        counts.sort_unstable();
+        assert_eq!(counts, [0, 1, 1, 1, 1]);
+    }
 }
@@ -1,70 +1,101 @@
 //! Coordinates the sequential perception pipeline that annotates molecules prior to typing.
 //!
 //! This module wires the specialized perception stages—ring detection, Kekulé expansion,
-//! electron bookkeeping, aromaticity, resonance, and hybridization—into a single pass that
-//! populates an [`AnnotatedMolecule`] for downstream typing.
+//! electron bookkeeping, aromaticity, resonance, hybridization, coordinate-based
+//! refinement, and functional group labeling—into a single pass that
+//! populates an [`AnnotatedMolecule`] for downstream typing. The sequence itself is a
+//! [`PerceptionPipeline`], which callers may customize.
 
 mod aromaticity;
+mod bond_orders;
 mod electrons;
+mod geometry;
+mod groups;
 mod hybridization;
 mod kekulize;
 mod model;
+mod nitrogen;
+mod options;
+mod pipeline;
 mod resonance;
-mod rings;
+pub(crate) mod rings;
+mod scratch;
 
-pub use model::{AnnotatedAtom, AnnotatedMolecule, ResonanceSystem};
+pub use bond_orders::assign_bond_orders;
+pub use model::{AdjacencyList, AnnotatedAtom, AnnotatedMolecule, NeighborBond, ResonanceSystem};
+pub use options::{AromaticityModel, MacrocycleMode, PerceptionOptions};
+pub use pipeline::{CustomStage, PerceptionPipeline, PerceptionPipelineBuilder};
 
-use crate::core::error::{PerceptionError, TyperError};
+use crate::core::error::TyperError;
 use crate::core::graph::MolecularGraph;
-
-type PerceptionStepFn = fn(&mut AnnotatedMolecule) -> Result<(), PerceptionError>;
-type PerceptionStep = (&'static str, PerceptionStepFn);
+use crate::core::limits::Budget;
+use crate::core::periodic::ImageFlags;
+
+/// Names of the perception stages in execution order.
+pub const STEP_NAMES: [&str; 8] = [
+    "Rings",
+    "Kekulization",
+    "Electrons",
+    "Aromaticity",
+    "Resonance",
+    "Hybridization",
+    "Geometry",
+    "Groups",
+];
 
 /// Runs the full perception pipeline and returns an annotated molecule.
 ///
-/// The function constructs an [`AnnotatedMolecule`] from the input graph, executes the fixed set
-/// of perception stages in order, and reports any failure with the offending step name folded
+/// The function constructs an [`AnnotatedMolecule`] from the input graph, executes the stages of
+/// `pipeline` in order, and reports any failure with the offending step name folded
 /// into the [`TyperError`].
 ///
 /// # Arguments
 ///
 /// * `graph` - Validated molecular graph containing atoms and bonds.
+/// * `bond_images` - Image flags of every bond for a periodic unit cell, indexed by bond ID, or
+///   empty for an ordinary molecule. Ring detection follows every bond into the image it
+///   reaches, so rings spanning a cell face are found while chains that only close by wrapping
+///   around the cell are not mistaken for rings.
+/// * `pipeline` - Stages to run, normally [`PerceptionPipeline::standard`].
+/// * `options` - Settings such as the aromaticity model.
+/// * `budget` - Resource budget enforced by the stages and checked between them.
+/// * `on_step` - Callback receiving the zero-based index into [`STEP_NAMES`] of the stage about
+///   to run.
 ///
 /// # Returns
 ///
 /// Fully annotated molecule that records ring membership, electron bookkeeping, aromaticity,
-/// resonance, and hybridization properties for every atom.
+/// resonance, hybridization, and functional group properties for every atom.
 ///
 /// # Errors
 ///
 /// Returns [`TyperError::InvalidInput`] when the graph contains invalid bonding, or
-/// [`TyperError::PerceptionFailed`] when any perception stage emits a [`PerceptionError`].
-pub fn perceive(graph: &MolecularGraph) -> Result<AnnotatedMolecule, TyperError> {
+/// [`TyperError::PerceptionFailed`] when any perception stage emits a
+/// [`PerceptionError`](crate::core::error::PerceptionError), and [`TyperError::LimitExceeded`] when
+/// the wall-clock budget runs out between stages.
+pub fn perceive(
+    graph: &MolecularGraph,
+    bond_images: &[ImageFlags],
+    pipeline: &PerceptionPipeline,
+    options: &PerceptionOptions,
+    budget: &Budget,
+    on_step: &mut dyn FnMut(usize),
+) -> Result<AnnotatedMolecule, TyperError> {
     let mut molecule = AnnotatedMolecule::new(graph).map_err(TyperError::InvalidInput)?;
+    molecule.bond_images = bond_images.to_vec();
 
-    let pipeline: [PerceptionStep; 6] = [
-        ("Rings", rings::perceive),
-        ("Kekulization", kekulize::perceive),
-        ("Electrons", electrons::perceive),
-        ("Aromaticity", aromaticity::perceive),
-        ("Resonance", resonance::perceive),
-        ("Hybridization", hybridization::perceive),
-    ];
-
-    for (name, step_fn) in pipeline {
-        step_fn(&mut molecule).map_err(|source| TyperError::PerceptionFailed {
-            step: name.to_string(),
-            source,
-        })?;
-    }
-
+    pipeline.run(&mut molecule, options, budget, on_step)?;
     Ok(molecule)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compat::prelude::*;
+    use crate::core::error::PerceptionError;
     use crate::core::properties::{Element, GraphBondOrder, Hybridization};
+    use crate::perception::options::MacrocycleMode;
+    use crate::pipeline::Stage;
 
     fn benzene_graph() -> MolecularGraph {
         let mut graph = MolecularGraph::new();
@@ -219,7 +250,15 @@ mod tests {
     #[test]
     fn perception_pipeline_assigns_benzene_properties() {
         let graph = benzene_graph();
-        let molecule = perceive(&graph).expect("perception pipeline should succeed");
+        let molecule = perceive(
+            &graph,
+            &[],
+            PerceptionPipeline::standard_ref(),
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+            &mut |_| {},
+        )
+        .expect("perception pipeline should succeed");
 
         assert_eq!(molecule.rings.len(), 1, "benzene must yield a single ring");
         for (idx, atom) in molecule.atoms.iter().enumerate() {
@@ -260,7 +299,15 @@ mod tests {
     #[test]
     fn perception_pipeline_marks_acridine_as_aromatic() {
         let graph = acridine_graph();
-        let molecule = perceive(&graph).expect("perception pipeline should succeed");
+        let molecule = perceive(
+            &graph,
+            &[],
+            PerceptionPipeline::standard_ref(),
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+            &mut |_| {},
+        )
+        .expect("perception pipeline should succeed");
 
         assert!(
             molecule.rings.len() >= 3,
@@ -345,13 +392,26 @@ mod tests {
     #[test]
     fn pipeline_reports_step_name_when_kekulization_fails() {
         let graph = aromatic_bond_outside_ring_graph();
-        let err = perceive(&graph).expect_err("pipeline should fail before completion");
+        let err = perceive(
+            &graph,
+            &[],
+            PerceptionPipeline::standard_ref(),
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+            &mut |_| {},
+        )
+        .expect_err("pipeline should fail before completion");
 
         match err {
-            TyperError::PerceptionFailed { step, source } => {
+            TyperError::PerceptionFailed {
+                step,
+                stage,
+                source,
+            } => {
                 assert_eq!(step, "Kekulization");
+                assert_eq!(stage, Stage::Kekulization);
                 match source {
-                    PerceptionError::KekulizationFailed { message } => {
+                    PerceptionError::KekulizationFailed { message, .. } => {
                         assert!(
                             message.contains("not in a ring"),
                             "error message should describe the missing ring context"
@@ -367,7 +427,15 @@ mod tests {
     #[test]
     fn pyrimidine_aromatic_input_is_detected() {
         let graph = pyrimidine_aromatic_graph();
-        let molecule = perceive(&graph).expect("perception pipeline should succeed");
+        let molecule = perceive(
+            &graph,
+            &[],
+            PerceptionPipeline::standard_ref(),
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+            &mut |_| {},
+        )
+        .expect("perception pipeline should succeed");
 
         let ring_atoms = [0usize, 1, 2, 3, 4, 5];
 
@@ -387,4 +455,221 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn periodic_ring_spanning_cell_face_is_aromatic() {
+        let mut graph = MolecularGraph::new();
+        let carbons: Vec<usize> = (0..6).map(|_| graph.add_atom(Element::C)).collect();
+        let mut images = Vec::new();
+        for (i, image) in [
+            [0, 0, 0],
+            [0, 0, 0],
+            [1, 0, 0],
+            [0, 0, 0],
+            [-1, 0, 0],
+            [0, 0, 0],
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            graph
+                .add_bond(carbons[i], carbons[(i + 1) % 6], GraphBondOrder::Aromatic)
+                .expect("valid ring bond");
+            images.push(image);
+        }
+        for &carbon in &carbons {
+            let hydrogen = graph.add_atom(Element::H);
+            graph
+                .add_bond(carbon, hydrogen, GraphBondOrder::Single)
+                .expect("valid C-H bond");
+            images.push([0, 0, 0]);
+        }
+
+        let molecule = perceive(
+            &graph,
+            &images,
+            PerceptionPipeline::standard_ref(),
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+            &mut |_| {},
+        )
+        .expect("perception pipeline should succeed");
+
+        assert_eq!(molecule.rings.len(), 1);
+        for &carbon in &carbons {
+            assert!(molecule.atoms[carbon].is_aromatic);
+            assert_eq!(
+                molecule.atoms[carbon].hybridization,
+                Hybridization::Resonant
+            );
+        }
+    }
+
+    #[test]
+    fn periodic_chain_wrapping_the_cell_has_no_rings() {
+        let mut graph = MolecularGraph::new();
+        let carbons: Vec<usize> = (0..4).map(|_| graph.add_atom(Element::C)).collect();
+        let mut images = Vec::new();
+        for i in 0..4 {
+            let image = if i == 3 { [1, 0, 0] } else { [0, 0, 0] };
+            graph
+                .add_bond(carbons[i], carbons[(i + 1) % 4], GraphBondOrder::Single)
+                .expect("valid backbone bond");
+            images.push(image);
+            for _ in 0..2 {
+                let hydrogen = graph.add_atom(Element::H);
+                graph
+                    .add_bond(carbons[i], hydrogen, GraphBondOrder::Single)
+                    .expect("valid C-H bond");
+                images.push([0, 0, 0]);
+            }
+        }
+
+        let molecule = perceive(
+            &graph,
+            &images,
+            PerceptionPipeline::standard_ref(),
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+            &mut |_| {},
+        )
+        .expect("perception pipeline should succeed");
+
+        assert!(molecule.rings.is_empty());
+        assert!(molecule.atoms.iter().all(|atom| !atom.is_in_ring));
+        assert_eq!(molecule.atoms[0].hybridization, Hybridization::SP3);
+
+        let molecule = perceive(
+            &graph,
+            &[],
+            PerceptionPipeline::standard_ref(),
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+            &mut |_| {},
+        )
+        .expect("perception pipeline should succeed");
+        assert_eq!(
+            molecule.rings.len(),
+            1,
+            "without images the chain closes a ring"
+        );
+    }
+
+    /// Perceives a SMILES string with the given ring size limit and macrocycle treatment.
+    fn perceive_macrocycle(
+        smiles: &str,
+        max_ring_size: Option<usize>,
+        macrocycles: MacrocycleMode,
+    ) -> AnnotatedMolecule {
+        let graph = crate::io::smiles::parse_smiles(smiles).unwrap();
+        let options = PerceptionOptions {
+            max_ring_size,
+            macrocycles,
+            ..PerceptionOptions::DEFAULT
+        };
+        perceive(
+            &graph,
+            &[],
+            PerceptionPipeline::standard_ref(),
+            &options,
+            &Budget::default(),
+            &mut |_| {},
+        )
+        .expect("perception pipeline should succeed")
+    }
+
+    /// Returns the IDs of the atoms flagged aromatic.
+    fn aromatic_atoms(molecule: &AnnotatedMolecule) -> Vec<usize> {
+        (0..molecule.atoms.len())
+            .filter(|&id| molecule.atoms[id].is_aromatic)
+            .collect()
+    }
+
+    /// Porphine in Kekulé form; its SSSR holds four pyrroles and a 16-membered ring.
+    const PORPHINE: &str = "C1=CC2=NC1=CC3=CC=C(N3)C=C4C=CC(=N4)C=C5C=CC(=C2)N5";
+    /// \[18\]Annulene in Kekulé form.
+    const ANNULENE_18: &str = "C1=CC=CC=CC=CC=CC=CC=CC=C1";
+
+    #[test]
+    fn macrocycles_of_12_to_24_atoms_are_rings_by_default() {
+        for size in [12, 15, 18, 21, 24] {
+            let smiles = format!("C1{}1", "C".repeat(size - 1));
+            let molecule = perceive_macrocycle(&smiles, None, MacrocycleMode::Envelope);
+            assert_eq!(molecule.rings.len(), 1);
+            assert_eq!(molecule.rings[0].len(), size);
+            assert!(molecule.macrocycles.is_empty());
+            assert!(
+                molecule.atoms[..size]
+                    .iter()
+                    .all(|atom| { atom.is_in_ring && atom.smallest_ring_size == Some(size as u8) })
+            );
+        }
+
+        // 18-crown-6 and cyclo(Gly)6 are 18-membered rings as well.
+        for smiles in [
+            "C1COCCOCCOCCOCCOCCO1",
+            "O=C1CNC(=O)CNC(=O)CNC(=O)CNC(=O)CNC(=O)CN1",
+        ] {
+            let molecule = perceive_macrocycle(smiles, None, MacrocycleMode::Envelope);
+            assert_eq!(molecule.rings.len(), 1);
+            assert_eq!(molecule.rings[0].len(), 18);
+        }
+    }
+
+    #[test]
+    fn macrocycle_envelopes_keep_ring_membership_without_a_ring_size() {
+        let crown = "C1COCCOCCOCCOCCOCCO1";
+        let envelope = perceive_macrocycle(crown, Some(12), MacrocycleMode::Envelope);
+        assert!(envelope.rings.is_empty());
+        assert_eq!(envelope.macrocycles.len(), 1);
+        assert!(
+            envelope
+                .atoms
+                .iter()
+                .filter(|atom| atom.element != Element::H)
+                .all(|atom| atom.is_in_ring && atom.smallest_ring_size.is_none())
+        );
+
+        let chain = perceive_macrocycle(crown, Some(12), MacrocycleMode::Chain);
+        assert!(chain.rings.is_empty() && chain.macrocycles.is_empty());
+        assert!(chain.atoms.iter().all(|atom| !atom.is_in_ring));
+    }
+
+    #[test]
+    fn macrocycle_envelopes_keep_aromaticity() {
+        for smiles in [PORPHINE, ANNULENE_18] {
+            let expected = perceive_macrocycle(smiles, None, MacrocycleMode::Envelope);
+            let limited = perceive_macrocycle(smiles, Some(8), MacrocycleMode::Envelope);
+            assert!(limited.rings.iter().all(|ring| ring.len() <= 8));
+            assert_eq!(aromatic_atoms(&limited), aromatic_atoms(&expected));
+        }
+
+        // Only the four pyrroles of porphine are reported, yet all 24 core atoms stay aromatic.
+        let porphine = perceive_macrocycle(PORPHINE, Some(8), MacrocycleMode::Envelope);
+        assert_eq!(porphine.rings.len(), 4);
+        assert_eq!(aromatic_atoms(&porphine).len(), 24);
+
+        let annulene = perceive_macrocycle(ANNULENE_18, Some(8), MacrocycleMode::Chain);
+        assert!(aromatic_atoms(&annulene).is_empty());
+    }
+
+    #[test]
+    fn only_bicyclic_4n_systems_are_anti_aromatic_as_a_whole() {
+        // Pyrene counts 16 π electrons over its four rings, yet every ring is benzenoid.
+        let pyrene = perceive_macrocycle(
+            "C1=CC2=CC=C3C=CC=C4C=CC(=C1)C2=C34",
+            None,
+            MacrocycleMode::Envelope,
+        );
+        assert_eq!(aromatic_atoms(&pyrene), (0..16).collect::<Vec<_>>());
+        assert!(pyrene.atoms.iter().all(|atom| !atom.is_anti_aromatic));
+
+        let pentalene = perceive_macrocycle("C1=CC2=CC=CC2=C1", None, MacrocycleMode::Envelope);
+        assert!(aromatic_atoms(&pentalene).is_empty());
+        assert!(
+            pentalene.atoms[..8]
+                .iter()
+                .all(|atom| atom.is_anti_aromatic)
+        );
+    }
 }
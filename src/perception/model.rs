@@ -1,11 +1,17 @@
-//! Internal model shared across perception stages to annotate atoms, bonds, rings, and adjacency.
+//! Model shared across perception stages to annotate atoms, bonds, rings, and adjacency.
 //!
 //! The structures defined here wrap the raw `MolecularGraph` with mutable fields that each
-//! perception pass enriches before the typing engine consumes them.
-
-use crate::core::error::GraphValidationError;
-use crate::core::graph::{BondEdge, MolecularGraph};
-use crate::core::properties::{Element, GraphBondOrder, Hybridization};
+//! perception pass enriches before the typing engine consumes them. They are public so that
+//! custom stages of a [`PerceptionPipeline`](crate::PerceptionPipeline) can read and set the
+//! same annotations.
+
+use crate::compat::prelude::*;
+use crate::core::error::{AtomContext, GraphValidationError};
+use crate::core::graph::{BondEdge, MolecularGraph, check_position_count};
+use crate::core::periodic::ImageFlags;
+use crate::core::properties::{Element, FunctionalGroup, GraphBondOrder, Hybridization};
+use crate::core::stereo::Chirality;
+use core::ops::{Index, IndexMut};
 
 /// Neighbor descriptor bundling atom connectivity with the originating bond ID.
 #[derive(Debug, Clone, Copy)]
@@ -19,25 +25,127 @@ pub struct NeighborBond {
     pub order: GraphBondOrder,
 }
 
+/// Neighbor lists of every atom stored contiguously in compressed sparse row form.
+///
+/// The entries of atom `i` occupy one slice of a single shared buffer, so walking the neighbors
+/// of consecutive atoms touches consecutive memory instead of one heap allocation per atom.
+/// Indexing with an atom ID yields that atom's neighbor slice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdjacencyList<T> {
+    /// Start of every atom's slice in `entries`, followed by the total entry count.
+    offsets: Vec<usize>,
+    /// Neighbor entries of all atoms, grouped by atom.
+    entries: Vec<T>,
+}
+
+impl<T: Copy> AdjacencyList<T> {
+    /// Groups `(atom_id, entry)` pairs by atom, keeping their relative order within each atom.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_count` - Number of atoms; every atom ID in `pairs` must be below it.
+    /// * `pairs` - Neighbor entries tagged with the atom they belong to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pair names an atom ID of `atom_count` or above.
+    pub fn from_pairs(atom_count: usize, pairs: &[(usize, T)]) -> Self {
+        let mut offsets = vec![0; atom_count + 1];
+        for &(atom_id, _) in pairs {
+            offsets[atom_id + 1] += 1;
+        }
+        for i in 0..atom_count {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let mut entries = match pairs.first() {
+            Some(&(_, filler)) => vec![filler; pairs.len()],
+            None => Vec::new(),
+        };
+        let mut cursor = offsets[..atom_count].to_vec();
+        for &(atom_id, entry) in pairs {
+            entries[cursor[atom_id]] = entry;
+            cursor[atom_id] += 1;
+        }
+        Self { offsets, entries }
+    }
+}
+
+impl<T> AdjacencyList<T> {
+    /// Returns the number of atoms.
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// Returns `true` if the list covers no atoms.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the neighbor slices of all atoms in ID order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &[T]> + '_ {
+        self.offsets
+            .windows(2)
+            .map(|bounds| &self.entries[bounds[0]..bounds[1]])
+    }
+}
+
+impl<T> Default for AdjacencyList<T> {
+    fn default() -> Self {
+        Self {
+            offsets: vec![0],
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T> Index<usize> for AdjacencyList<T> {
+    type Output = [T];
+
+    fn index(&self, atom_id: usize) -> &[T] {
+        &self.entries[self.offsets[atom_id]..self.offsets[atom_id + 1]]
+    }
+}
+
+impl<T> IndexMut<usize> for AdjacencyList<T> {
+    fn index_mut(&mut self, atom_id: usize) -> &mut [T] {
+        &mut self.entries[self.offsets[atom_id]..self.offsets[atom_id + 1]]
+    }
+}
+
 /// Perception-friendly atom record that stores both graph identity and inferred properties.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AnnotatedAtom {
     /// Zero-based identifier matching the source [`MolecularGraph`].
     pub id: usize,
     /// Chemical element of the atom.
     pub element: Element,
+    /// Tetrahedral configuration copied from the source graph; perception never changes it.
+    pub chirality: Option<Chirality>,
 
-    /// Current formal charge assigned by electron perception.
+    /// Current formal charge assigned by electron perception; until then, the charge stated by
+    /// the input graph, or zero.
     pub formal_charge: i8,
     /// Number of lone pairs tracked for hybridization and resonance logic.
     pub lone_pairs: u8,
+    /// Number of unpaired electrons stated by the input graph.
+    pub radical_electrons: u8,
     /// Graph degree computed during adjacency building.
     pub degree: u8,
+    /// Whether the atom is a metal center whose bonds are coordination bonds.
+    pub is_metal_center: bool,
+    /// Number of coordination bonds from a ligand atom to metal centers; zero for metals.
+    pub coordination_bonds: u8,
 
     /// Whether the atom lies on any ring identified so far.
     pub is_in_ring: bool,
     /// Size of the smallest ring containing the atom, if any.
     pub smallest_ring_size: Option<u8>,
+    /// Whether the atom is a bridgehead: an end of the path shared by two rings that have at
+    /// least three atoms in common, as in norbornane.
+    pub is_bridgehead: bool,
+    /// Whether the atom is the only atom shared by two rings, as in spiro[4.5]decane.
+    pub is_spiro: bool,
 
     /// Flag set once aromaticity perception confirms Huckel criteria for this atom.
     pub is_aromatic: bool,
@@ -53,9 +161,31 @@ pub struct AnnotatedAtom {
     pub steric_number: u8,
     /// Current hybridization assignment, defaulting to [`Hybridization::Unknown`].
     pub hybridization: Hybridization,
+
+    /// Functional groups the atom belongs to, in the order they were detected.
+    pub functional_groups: Vec<FunctionalGroup>,
+}
+
+impl AnnotatedAtom {
+    /// Returns the number of covalent bonds, excluding coordination bonds to metal centers.
+    pub fn covalent_degree(&self) -> u8 {
+        self.degree - self.coordination_bonds
+    }
+
+    /// Returns the degree used to judge whether the atom ends a functional group.
+    ///
+    /// With `metal_bound_groups`, coordination bonds are left out, so an oxygen bonded to a
+    /// carboxylate carbon and to metals still counts as terminal.
+    pub fn group_degree(&self, metal_bound_groups: bool) -> u8 {
+        if metal_bound_groups {
+            self.covalent_degree()
+        } else {
+            self.degree
+        }
+    }
 }
 
-/// Convenience alias representing a ring as a list of atom identifiers.
+/// Convenience alias representing a ring as a list of atom identifiers in cyclic order.
 pub type Ring = Vec<usize>;
 
 /// Represents a subset of atoms and bonds that form a delocalized electron system.
@@ -76,24 +206,48 @@ pub struct ResonanceSystem {
 #[derive(Debug, Clone)]
 pub struct AnnotatedMolecule {
     /// All atoms with perception-specific annotations.
+    ///
+    /// Annotations stay one record per atom rather than one vector per field: every stage,
+    /// custom stages included, reads and updates them as `atoms[id].field`, and splitting them
+    /// would change that interface. Only the neighbor lists, which the ring, aromaticity, and
+    /// resonance passes walk most, are stored contiguously in [`AdjacencyList`]s.
     pub atoms: Vec<AnnotatedAtom>,
     /// Copy of the graph bonds to provide stable IDs and connectivity.
     pub bonds: Vec<BondEdge>,
     /// Adjacency list capturing neighbor IDs and bond orders.
-    pub adjacency: Vec<Vec<(usize, GraphBondOrder)>>,
-    /// Adjacency list that also records the bond ID for each neighbor edge.
-    pub adjacency_with_bonds: Vec<Vec<NeighborBond>>,
-    /// Collection of rings discovered during perception.
+    ///
+    /// Bonds to [`Element::Dummy`] atoms are left out, so dummy atoms have no neighbors and
+    /// never count toward the degree or valence of the atoms they are attached to.
+    pub adjacency: AdjacencyList<(usize, GraphBondOrder)>,
+    /// Adjacency list that also records the bond ID for each neighbor edge, in the same order
+    /// as `adjacency`.
+    pub adjacency_with_bonds: AdjacencyList<NeighborBond>,
+    /// Smallest set of smallest rings discovered during perception, each in cyclic order
+    /// starting at its lowest atom ID. In a periodic cell smaller than its rings, as in graphene,
+    /// a ring may pass through two images of one atom and then lists that atom twice.
     pub rings: Vec<Ring>,
+    /// Cycles of the smallest set of smallest rings that exceed
+    /// [`PerceptionOptions::max_ring_size`](crate::PerceptionOptions::max_ring_size) and are
+    /// kept as envelopes, in the same cyclic order as `rings`.
+    pub macrocycles: Vec<Ring>,
     /// Collection of all identified resonance systems.
     pub resonance_systems: Vec<ResonanceSystem>,
+    /// IDs of the bonds given as aromatic in the source graph, recorded by Kekulé expansion
+    /// before it assigns them single or double orders.
+    pub aromatic_input_bonds: Vec<usize>,
+    /// Lattice translation of every bond's second atom, indexed by bond ID; empty for
+    /// non-periodic molecules.
+    pub bond_images: Vec<ImageFlags>,
+    /// Cartesian coordinates of every atom in Å, copied from the source graph.
+    pub positions: Option<Vec<[f64; 3]>>,
 }
 
 impl AnnotatedMolecule {
     /// Builds an annotated molecule from a validated [`MolecularGraph`].
     ///
     /// Initializes adjacency lists for every atom, clones the bond list, and seeds default
-    /// annotations that later perception passes will populate.
+    /// annotations that later perception passes will populate. Bonds to dummy atoms stay in the
+    /// bond list but are left out of the adjacency lists.
     ///
     /// # Arguments
     ///
@@ -106,55 +260,81 @@ impl AnnotatedMolecule {
     /// # Errors
     ///
     /// Returns [`GraphValidationError::MissingAtom`] if any bond endpoint references an atom index
-    /// outside the graph's atom list.
+    /// outside the graph's atom list, or [`GraphValidationError::PositionCountMismatch`] if the
+    /// graph carries coordinates for a different number of atoms.
     pub fn new(graph: &MolecularGraph) -> Result<Self, GraphValidationError> {
-        let mut adjacency = vec![vec![]; graph.atoms.len()];
-        let mut adjacency_with_bonds = vec![vec![]; graph.atoms.len()];
+        if let Some(positions) = &graph.positions {
+            check_position_count(graph.atoms.len(), positions.len())?;
+        }
+
+        let atom_count = graph.atoms.len();
+        let mut pairs = Vec::with_capacity(2 * graph.bonds.len());
         for bond in &graph.bonds {
             let (u, v) = bond.atom_ids;
 
-            if u >= graph.atoms.len() {
+            if u >= atom_count {
                 return Err(GraphValidationError::MissingAtom { atom_id: u });
             }
-            if v >= graph.atoms.len() {
+            if v >= atom_count {
                 return Err(GraphValidationError::MissingAtom { atom_id: v });
             }
+            if graph.atoms[u].element.is_dummy() || graph.atoms[v].element.is_dummy() {
+                continue;
+            }
 
-            adjacency[u].push((v, bond.order));
-            adjacency[v].push((u, bond.order));
-
-            let neighbor_entry_u = NeighborBond {
-                neighbor_id: v,
-                bond_id: bond.id,
-                order: bond.order,
-            };
-            let neighbor_entry_v = NeighborBond {
-                neighbor_id: u,
-                bond_id: bond.id,
-                order: bond.order,
-            };
-
-            adjacency_with_bonds[u].push(neighbor_entry_u);
-            adjacency_with_bonds[v].push(neighbor_entry_v);
+            for (atom_id, neighbor_id) in [(u, v), (v, u)] {
+                let entry = NeighborBond {
+                    neighbor_id,
+                    bond_id: bond.id,
+                    order: bond.order,
+                };
+                pairs.push((atom_id, entry));
+            }
         }
+        let adjacency_with_bonds = AdjacencyList::from_pairs(atom_count, &pairs);
+        let adjacency = AdjacencyList::from_pairs(
+            atom_count,
+            &pairs
+                .iter()
+                .map(|&(atom_id, entry)| (atom_id, (entry.neighbor_id, entry.order)))
+                .collect::<Vec<_>>(),
+        );
 
         let atoms = graph
             .atoms
             .iter()
-            .map(|node| AnnotatedAtom {
-                id: node.id,
-                element: node.element,
-                degree: adjacency[node.id].len() as u8,
-                formal_charge: 0,
-                lone_pairs: 0,
-                is_in_ring: false,
-                smallest_ring_size: None,
-                is_aromatic: false,
-                is_anti_aromatic: false,
-                is_resonant: false,
-                has_aromatic_edge: false,
-                steric_number: 0,
-                hybridization: Hybridization::Unknown,
+            .map(|node| {
+                let is_metal_center = node.element.is_metal_center();
+                let coordination_bonds = if is_metal_center {
+                    0
+                } else {
+                    adjacency[node.id]
+                        .iter()
+                        .filter(|&&(neighbor, _)| graph.atoms[neighbor].element.is_metal_center())
+                        .count() as u8
+                };
+                AnnotatedAtom {
+                    id: node.id,
+                    element: node.element,
+                    chirality: node.chirality,
+                    degree: adjacency[node.id].len() as u8,
+                    is_metal_center,
+                    coordination_bonds,
+                    formal_charge: node.formal_charge.unwrap_or(0),
+                    lone_pairs: 0,
+                    radical_electrons: node.radical_electrons,
+                    is_in_ring: false,
+                    smallest_ring_size: None,
+                    is_bridgehead: false,
+                    is_spiro: false,
+                    is_aromatic: false,
+                    is_anti_aromatic: false,
+                    is_resonant: false,
+                    has_aromatic_edge: false,
+                    steric_number: 0,
+                    hybridization: Hybridization::Unknown,
+                    functional_groups: Vec::new(),
+                }
             })
             .collect();
 
@@ -164,9 +344,62 @@ impl AnnotatedMolecule {
             adjacency,
             adjacency_with_bonds,
             rings: Vec::new(),
+            macrocycles: Vec::new(),
             resonance_systems: Vec::new(),
+            aromatic_input_bonds: Vec::new(),
+            bond_images: Vec::new(),
+            positions: graph.positions.clone(),
         })
     }
+
+    /// Changes the order of one bond, keeping both adjacency lists in sync with `bonds`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bond_id` - ID of the bond to update, which is also its index in `bonds`.
+    /// * `order` - New bond order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bond_id` is not a bond of this molecule.
+    pub fn set_bond_order(&mut self, bond_id: usize, order: GraphBondOrder) {
+        let bond = &mut self.bonds[bond_id];
+        bond.order = order;
+        let (u, v) = bond.atom_ids;
+        for atom_id in [u, v] {
+            for (entry, neighbor) in self.adjacency[atom_id]
+                .iter_mut()
+                .zip(&mut self.adjacency_with_bonds[atom_id])
+            {
+                if neighbor.bond_id == bond_id {
+                    neighbor.order = order;
+                    entry.1 = order;
+                }
+            }
+        }
+    }
+
+    /// Summarizes an atom and its neighbors for error reporting.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_id` - ID of the atom to describe.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `atom_id` is not an atom of this molecule.
+    pub fn atom_context(&self, atom_id: usize) -> AtomContext {
+        let mut neighbors: Vec<Element> = self.adjacency[atom_id]
+            .iter()
+            .map(|&(neighbor, _)| self.atoms[neighbor].element)
+            .collect();
+        neighbors.sort_unstable();
+        AtomContext {
+            atom_id,
+            element: self.atoms[atom_id].element,
+            neighbors,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -248,18 +481,89 @@ mod tests {
         assert!(molecule.resonance_systems.is_empty());
     }
 
+    #[test]
+    fn adjacency_list_groups_entries_by_atom_in_order() {
+        let adjacency = AdjacencyList::from_pairs(4, &[(2, 'a'), (0, 'b'), (2, 'c'), (0, 'd')]);
+
+        assert_eq!(adjacency.len(), 4);
+        assert_eq!(&adjacency[0], ['b', 'd']);
+        assert!(adjacency[1].is_empty());
+        assert_eq!(&adjacency[2], ['a', 'c']);
+        assert!(adjacency[3].is_empty());
+        assert_eq!(
+            adjacency.iter().map(<[char]>::len).collect::<Vec<_>>(),
+            [2, 0, 2, 0]
+        );
+
+        let empty = AdjacencyList::<char>::from_pairs(0, &[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty, AdjacencyList::default());
+    }
+
+    #[test]
+    fn bonds_to_dummy_atoms_are_left_out_of_adjacency() {
+        let mut graph = water_like_graph();
+        for _ in 0..2 {
+            let site = graph.add_atom(Element::Dummy);
+            graph.add_bond(0, site, GraphBondOrder::Single).unwrap();
+        }
+        let molecule = AnnotatedMolecule::new(&graph).expect("graph should be valid");
+
+        assert_eq!(molecule.bonds.len(), 4);
+        assert_eq!(molecule.atoms[0].degree, 2);
+        assert_eq!(
+            molecule
+                .adjacency
+                .iter()
+                .map(<[_]>::len)
+                .collect::<Vec<_>>(),
+            [2, 1, 1, 0, 0]
+        );
+        assert!(molecule.adjacency_with_bonds[3].is_empty());
+    }
+
+    #[test]
+    fn set_bond_order_updates_bonds_and_both_adjacency_lists() {
+        let graph = water_like_graph();
+        let mut molecule = AnnotatedMolecule::new(&graph).expect("graph should be valid");
+
+        molecule.set_bond_order(1, GraphBondOrder::Double);
+
+        assert_eq!(molecule.bonds[1].order, GraphBondOrder::Double);
+        assert_eq!(
+            molecule.adjacency[0],
+            [(1, GraphBondOrder::Single), (2, GraphBondOrder::Double)]
+        );
+        assert_eq!(molecule.adjacency[2], [(0, GraphBondOrder::Double)]);
+        assert_eq!(
+            molecule.adjacency_with_bonds[2][0].order,
+            GraphBondOrder::Double
+        );
+        assert_eq!(
+            molecule.adjacency_with_bonds[0][0].order,
+            GraphBondOrder::Single
+        );
+    }
+
     #[test]
     fn annotated_molecule_new_detects_invalid_bond_endpoints() {
         let graph = MolecularGraph {
             atoms: vec![AtomNode {
                 id: 0,
                 element: Element::C,
+                chirality: None,
+                formal_charge: None,
+                radical_electrons: 0,
+                isotope: None,
+                metadata: Default::default(),
             }],
             bonds: vec![BondEdge {
                 id: 0,
                 atom_ids: (0, 2),
                 order: GraphBondOrder::Single,
+                stereo: None,
             }],
+            positions: None,
         };
 
         let err = AnnotatedMolecule::new(&graph).expect_err("invalid bond must fail");
@@ -0,0 +1,158 @@
+//! Decides whether lone-pair nitrogens are planar and conjugated or pyramidal.
+//!
+//! An sp3 nitrogen whose lone pair can delocalize into an adjacent π system (amides, anilines,
+//! enamines, ureas) flattens and types as resonant, while an amine nitrogen stays pyramidal.
+//! The lone pair only delocalizes into a π acceptor: an aromatic atom, an sp2 or sp center, or
+//! a resonant atom that carries a multiple bond. A neighbor that is itself a resonant lone-pair
+//! donor, such as the amide nitrogen of a hydrazide or the oxygen of an ester, offers no empty
+//! π orbital, so the second nitrogen of a hydrazine or hydroxylamine stays pyramidal. When the
+//! molecule carries 3D coordinates, the measured bond-angle sum of a three-coordinate nitrogen
+//! overrides the graph rule.
+
+use super::geometry::{PLANAR_ANGLE_SUM, PYRAMIDAL_ANGLE_SUM, bond_angle_sum, is_flat_depiction};
+use super::model::AnnotatedMolecule;
+use crate::compat::prelude::*;
+use crate::core::properties::{Element, GraphBondOrder, Hybridization};
+
+/// Promotes conjugated sp3 nitrogens to resonant hybridization.
+///
+/// Runs on initial hybridization states, before the resonance promotion of oxygen donors. A
+/// nitrogen measured planar without a π-acceptor neighbor becomes sp2; one measured pyramidal
+/// stays sp3 whatever its neighbors.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule whose atoms carry their initial hybridization.
+pub(super) fn classify(molecule: &mut AnnotatedMolecule) {
+    let positions = molecule
+        .positions
+        .as_deref()
+        .filter(|positions| !is_flat_depiction(positions));
+
+    let mut updates = Vec::new();
+    for atom in &molecule.atoms {
+        if atom.element != Element::N
+            || atom.hybridization != Hybridization::SP3
+            || atom.lone_pairs == 0
+            || atom.is_metal_center
+        {
+            continue;
+        }
+
+        let neighbors: Vec<usize> = molecule.adjacency[atom.id]
+            .iter()
+            .map(|&(id, _)| id)
+            .collect();
+        let is_conjugated = neighbors.iter().any(|&id| is_pi_acceptor(molecule, id));
+        let angle_sum = positions
+            .filter(|_| atom.lone_pairs == 1 && neighbors.len() == 3)
+            .and_then(|positions| bond_angle_sum(positions, atom.id, &neighbors));
+
+        let hybridization = match angle_sum {
+            Some(sum) if sum >= PLANAR_ANGLE_SUM && !is_conjugated => Hybridization::SP2,
+            Some(sum) if sum <= PYRAMIDAL_ANGLE_SUM => continue,
+            _ if is_conjugated => Hybridization::Resonant,
+            _ => continue,
+        };
+        updates.push((atom.id, hybridization));
+    }
+
+    for (atom_id, hybridization) in updates {
+        let atom = &mut molecule.atoms[atom_id];
+        atom.hybridization = hybridization;
+        atom.is_resonant = hybridization == Hybridization::Resonant;
+    }
+}
+
+/// Returns `true` when the atom offers a π orbital that a neighboring lone pair can enter.
+pub(super) fn is_pi_acceptor(molecule: &AnnotatedMolecule, atom_id: usize) -> bool {
+    let atom = &molecule.atoms[atom_id];
+    if atom.is_metal_center {
+        return false;
+    }
+    if atom.is_aromatic {
+        return true;
+    }
+    match atom.hybridization {
+        Hybridization::SP2 | Hybridization::SP => true,
+        Hybridization::Resonant => {
+            let has_multiple_bond = molecule.adjacency[atom_id].iter().any(|&(_, order)| {
+                matches!(order, GraphBondOrder::Double | GraphBondOrder::Triple)
+            });
+            has_multiple_bond || atom.lone_pairs == 0
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compat::prelude::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::properties::{Element, GraphBondOrder};
+    use crate::io::smiles::parse_smiles;
+
+    /// Returns the DREIDING types of the nitrogens of `smiles`, in atom order.
+    fn nitrogen_types(smiles: &str) -> Vec<String> {
+        let graph = parse_smiles(smiles).unwrap();
+        nitrogen_types_of(&graph)
+    }
+
+    fn nitrogen_types_of(graph: &MolecularGraph) -> Vec<String> {
+        let types = crate::assign_atom_types(graph).unwrap();
+        graph
+            .atoms
+            .iter()
+            .zip(types)
+            .filter(|(atom, _)| atom.element == Element::N)
+            .map(|(_, atom_type)| atom_type)
+            .collect()
+    }
+
+    #[test]
+    fn nitrogens_next_to_pi_acceptors_are_planar() {
+        assert_eq!(nitrogen_types("c1ccccc1N"), ["N_R"]);
+        assert_eq!(nitrogen_types("CC(=O)N"), ["N_R"]);
+        assert_eq!(nitrogen_types("CC(=S)N"), ["N_R"]);
+        assert_eq!(nitrogen_types("NC(=O)N"), ["N_R", "N_R"]);
+        assert_eq!(nitrogen_types("C=CN(C)C"), ["N_R"]);
+        assert_eq!(nitrogen_types("NC#N")[0], "N_R");
+        assert_eq!(nitrogen_types("CN(C)N=O")[0], "N_R");
+    }
+
+    #[test]
+    fn amines_stay_pyramidal() {
+        assert_eq!(nitrogen_types("CN(C)C"), ["N_3"]);
+        assert_eq!(nitrogen_types("C1CCNCC1"), ["N_3"]);
+        assert_eq!(nitrogen_types("CS(=O)(=O)N"), ["N_3"]);
+    }
+
+    #[test]
+    fn lone_pair_donors_do_not_conjugate_their_neighbors() {
+        assert_eq!(nitrogen_types("c1ccccc1NN"), ["N_R", "N_3"]);
+        assert_eq!(nitrogen_types("CC(=O)NN"), ["N_R", "N_3"]);
+        assert_eq!(nitrogen_types("C(=O)N(C)N(C)C"), ["N_R", "N_3"]);
+        assert_eq!(nitrogen_types("c1ccccc1ON"), ["N_3"]);
+    }
+
+    #[test]
+    fn measured_planar_amine_without_pi_neighbors_is_sp2() {
+        // Trimethylamine flattened to a trigonal plane.
+        let mut graph = MolecularGraph::new();
+        let n = graph.add_atom(Element::N);
+        let mut positions = vec![[0.0, 0.0, 0.0]];
+        for direction in [[1.0, 0.0], [-0.5, 0.866], [-0.5, -0.866]] {
+            let c = graph.add_atom(Element::C);
+            graph.add_bond(n, c, GraphBondOrder::Single).unwrap();
+            let center = [direction[0] * 1.47, direction[1] * 1.47, 0.0];
+            positions.push(center);
+            for offset in [[0.3, 0.3, 0.9], [0.3, -0.9, -0.3], [0.9, 0.3, -0.3]] {
+                let h = graph.add_atom(Element::H);
+                graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+                positions.push([0, 1, 2].map(|k| center[k] + offset[k]));
+            }
+        }
+        graph.set_positions(positions).unwrap();
+        assert_eq!(nitrogen_types_of(&graph), ["N_2"]);
+    }
+}
@@ -0,0 +1,111 @@
+//! User-selectable settings that change how the perception stages interpret a molecule.
+
+/// Convention deciding which rings perception treats as aromatic.
+///
+/// Toolkits disagree on borderline rings, so a topology built for compatibility with another
+/// program may need that program's convention. The models differ in three places:
+///
+/// | Case                                         | `Huckel` | `Daylight` | `Mdl` |
+/// |----------------------------------------------|----------|------------|-------|
+/// | Ring atom with an exocyclic `C=O`, `C=N`, … | 1 π      | 0 π        | never |
+/// | Lone-pair donors (pyrrole, furan, thiophene) | 2 π      | 2 π        | never |
+/// | Fused system aromatic only as a whole        | yes      | yes        | no    |
+///
+/// Under `Huckel`, 2-pyridone is not aromatic (7 π); under `Daylight` it is (6 π). `Mdl`
+/// accepts only rings whose every atom carries a double bond within the ring system, so
+/// pyrrole, furan, and thiophene stay non-aromatic, and it evaluates fused systems ring by ring
+/// so that, for example, azulene is not aromatic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AromaticityModel {
+    /// Hückel 4n+2 counting over whole fused systems with ring-by-ring fallback, in which an
+    /// exocyclic double bond contributes one π electron. Rings given with aromatic bonds are
+    /// trusted.
+    #[default]
+    Huckel,
+    /// Permissive counting in the style of the Daylight toolkit: exocyclic double bonds to
+    /// electronegative atoms withdraw their π electron, so rings such as 2-pyridone and uracil
+    /// are aromatic.
+    Daylight,
+    /// Strict counting in the style of MDL toolkits: only rings of alternating endocyclic double
+    /// bonds are aromatic, each ring is judged on its own, and aromatic input bonds are not
+    /// trusted.
+    Mdl,
+}
+
+/// Treatment of cycles longer than [`PerceptionOptions::max_ring_size`].
+///
+/// Such macrocycles, as in crown ethers, cyclic peptides, or the porphyrin periphery, are never
+/// reported as rings: they are left out of the ring list, the smallest ring size, and the
+/// topology's [`rings`](crate::MolecularTopology::rings).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MacrocycleMode {
+    /// Macrocycles are kept as envelopes: their atoms are still ring atoms, and they join the
+    /// rings they share atoms with into one system for aromaticity, so a porphyrin core or an
+    /// \[18\]annulene is still aromatic.
+    #[default]
+    Envelope,
+    /// Macrocycles are treated as open chains, so atoms found only in them are not ring atoms.
+    Chain,
+}
+
+/// Settings for the perception stages run before typing.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{AromaticityModel, PerceptionOptions, Typer};
+///
+/// let options = PerceptionOptions {
+///     aromaticity: AromaticityModel::Daylight,
+///     ..PerceptionOptions::DEFAULT
+/// };
+/// let typer = Typer::new().with_perception_options(options);
+/// assert_eq!(typer.perception_options().aromaticity, AromaticityModel::Daylight);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PerceptionOptions {
+    /// Convention used to decide which rings are aromatic.
+    pub aromaticity: AromaticityModel,
+    /// Largest cycle, in atoms, reported as a ring; `None` reports every ring of the smallest
+    /// set of smallest rings.
+    pub max_ring_size: Option<usize>,
+    /// Treatment of cycles longer than `max_ring_size`.
+    pub macrocycles: MacrocycleMode,
+    /// Takes aromaticity from the input's aromatic bonds instead of re-deriving it.
+    ///
+    /// Every system of connected aromatic input bonds becomes aromatic and resonant as given,
+    /// and no ring is judged by `aromaticity`, so rings drawn with alternating single and double
+    /// bonds stay non-aromatic. Aromatic bonds are still Kekulé-expanded for electron
+    /// bookkeeping. Useful for inputs whose aromaticity a toolkit such as RDKit has already
+    /// curated.
+    pub trust_input_aromaticity: bool,
+    /// Recognizes resonance systems and functional groups through bonds to metal centers.
+    ///
+    /// By default an oxygen bonded to a metal is no longer terminal, so a carboxylate or
+    /// phosphonate that coordinates a metal loses its delocalized π system and its
+    /// functional-group label. With this set, coordination bonds are ignored when those groups
+    /// are detected, and the linker carboxylates of a metal-organic framework type like free
+    /// carboxylates.
+    pub metal_bound_groups: bool,
+    /// Skips aromaticity and resonance perception for molecules without carbon.
+    ///
+    /// Inorganic frameworks such as zeolites and aluminophosphates have no π systems, yet their
+    /// four- and six-membered T-O rings would still be counted for Hückel aromaticity and their
+    /// T-O bonds scanned for oxo groups. With this set, both stages leave carbon-free molecules
+    /// untouched. The typer perceives every connected component on its own, so organic guests
+    /// in a framework are still perceived in full. Carbon-free π systems such as borazine lose
+    /// their aromaticity under this option.
+    pub skip_inorganic_conjugation: bool,
+}
+
+impl PerceptionOptions {
+    /// Default options: the Hückel aromaticity model and no ring size limit.
+    pub const DEFAULT: Self = Self {
+        aromaticity: AromaticityModel::Huckel,
+        max_ring_size: None,
+        macrocycles: MacrocycleMode::Envelope,
+        trust_input_aromaticity: false,
+        metal_bound_groups: false,
+        skip_inorganic_conjugation: false,
+    };
+}
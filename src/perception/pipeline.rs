@@ -0,0 +1,553 @@
+//! Configurable sequence of perception stages.
+//!
+//! The standard pipeline runs the eight built-in stages in [`STEP_NAMES`] order. A
+//! [`PerceptionPipeline`] built through [`PerceptionPipeline::builder`] can skip built-in stages,
+//! replace them with caller-supplied functions, or insert additional functions between them, for
+//! inputs whose annotations are already known and must not be recomputed or overridden.
+
+use super::model::AnnotatedMolecule;
+use super::options::PerceptionOptions;
+use super::{
+    STEP_NAMES, aromaticity, electrons, geometry, groups, hybridization, kekulize, resonance, rings,
+};
+use crate::compat::prelude::*;
+use crate::compat::sync::OnceLock;
+use crate::core::error::{PerceptionError, TyperError};
+use crate::core::limits::Budget;
+use crate::core::properties::Element;
+use crate::core::trace;
+use crate::pipeline::Stage;
+use alloc::sync::Arc;
+use core::fmt;
+
+/// Lazily constructed pipeline running every built-in stage.
+static STANDARD_PIPELINE: OnceLock<PerceptionPipeline> = OnceLock::new();
+
+/// Signature of a caller-supplied perception stage.
+///
+/// The function receives the molecule as annotated by the preceding stages and may change any
+/// annotation. Returning an error aborts perception with
+/// [`TyperError::PerceptionFailed`] naming the stage.
+pub type CustomStage = dyn Fn(&mut AnnotatedMolecule) -> Result<(), PerceptionError> + Send + Sync;
+
+/// What a pipeline step does when it runs.
+#[derive(Clone)]
+enum StepAction {
+    /// Runs the built-in implementation of the step's stage.
+    Builtin,
+    /// Does nothing; the step only keeps its position for later insertions.
+    Skipped,
+    /// Runs a caller-supplied function.
+    Custom(Arc<CustomStage>),
+}
+
+/// One entry of a [`PerceptionPipeline`].
+#[derive(Clone)]
+struct PipelineStep {
+    /// Name reported in errors.
+    name: String,
+    /// Built-in stage this step stands in for, or `None` for an inserted step.
+    slot: Option<Stage>,
+    /// Stage reported to progress observers while the step runs; for an inserted step, the
+    /// built-in stage it was inserted next to.
+    progress: Stage,
+    /// Whether an inserted step precedes, rather than follows, its built-in stage.
+    before: bool,
+    /// Work performed by the step.
+    action: StepAction,
+}
+
+/// Ordered, immutable list of perception stages, cheap to clone.
+///
+/// The [`Default`] pipeline is [`PerceptionPipeline::standard`]. Custom pipelines are assembled
+/// with [`PerceptionPipeline::builder`] and installed with
+/// [`Typer::with_perception_pipeline`](crate::Typer::with_perception_pipeline).
+///
+/// # Examples
+///
+/// Replacing a stage with a closure, here a bare-bones version of
+/// [`PerceptionOptions::trust_input_aromaticity`] that flags atoms with aromatic input bonds:
+///
+/// ```
+/// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, PerceptionPipeline, Stage, Typer};
+///
+/// let pipeline = PerceptionPipeline::builder()
+///     .replace(Stage::Aromaticity, |molecule| {
+///         for atom in &mut molecule.atoms {
+///             atom.is_aromatic = atom.has_aromatic_edge;
+///             atom.is_resonant |= atom.has_aromatic_edge;
+///         }
+///         Ok(())
+///     })
+///     .build();
+/// assert_eq!(
+///     pipeline.step_names(),
+///     [
+///         "Rings",
+///         "Kekulization",
+///         "Electrons",
+///         "Aromaticity",
+///         "Resonance",
+///         "Hybridization",
+///         "Geometry",
+///         "Groups",
+///     ]
+/// );
+///
+/// let mut graph = MolecularGraph::new();
+/// let c: Vec<_> = (0..6).map(|_| graph.add_atom(Element::C)).collect();
+/// for i in 0..6 {
+///     graph.add_bond(c[i], c[(i + 1) % 6], GraphBondOrder::Aromatic).unwrap();
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(c[i], h, GraphBondOrder::Single).unwrap();
+/// }
+///
+/// let topology = Typer::new()
+///     .with_perception_pipeline(pipeline)
+///     .assign_topology(&graph)
+///     .unwrap();
+/// assert_eq!(topology.atoms[0].atom_type, "C_R");
+/// ```
+#[derive(Clone)]
+pub struct PerceptionPipeline {
+    steps: Arc<[PipelineStep]>,
+}
+
+impl PerceptionPipeline {
+    /// Returns the pipeline running all eight built-in stages in their standard order.
+    pub fn standard() -> Self {
+        Self::standard_ref().clone()
+    }
+
+    /// Borrows the shared standard pipeline.
+    pub(crate) fn standard_ref() -> &'static Self {
+        STANDARD_PIPELINE.get_or_init(|| PerceptionPipelineBuilder::new().build())
+    }
+
+    /// Returns whether this pipeline runs the shared steps of [`PerceptionPipeline::standard`].
+    pub(crate) fn is_standard(&self) -> bool {
+        Arc::ptr_eq(&self.steps, &Self::standard_ref().steps)
+    }
+
+    /// Starts a builder from the standard pipeline.
+    pub fn builder() -> PerceptionPipelineBuilder {
+        PerceptionPipelineBuilder::new()
+    }
+
+    /// Returns the names of the steps that run, in execution order.
+    ///
+    /// Skipped built-in stages are omitted; replaced ones keep their built-in name.
+    pub fn step_names(&self) -> Vec<&str> {
+        self.steps
+            .iter()
+            .filter(|step| !matches!(step.action, StepAction::Skipped))
+            .map(|step| step.name.as_str())
+            .collect()
+    }
+
+    /// Runs every step on `molecule` in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `molecule` - Molecule to annotate.
+    /// * `options` - Settings consumed by the built-in stages.
+    /// * `budget` - Resource budget enforced by the built-in stages and checked between steps.
+    /// * `on_step` - Callback receiving the zero-based index into [`STEP_NAMES`] of the stage
+    ///   each step reports as.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TyperError::PerceptionFailed`] naming the failing step, or
+    /// [`TyperError::LimitExceeded`] when the wall-clock budget runs out between steps.
+    pub(crate) fn run(
+        &self,
+        molecule: &mut AnnotatedMolecule,
+        options: &PerceptionOptions,
+        budget: &Budget,
+        on_step: &mut dyn FnMut(usize),
+    ) -> Result<(), TyperError> {
+        for step in self.steps.iter() {
+            let _span = trace::span!("perception_step", step = %step.name);
+            let result = match &step.action {
+                StepAction::Skipped => continue,
+                StepAction::Builtin => {
+                    budget.check_time()?;
+                    on_step(step.progress.index());
+                    let stage = step.slot.expect("built-in steps occupy a stage slot");
+                    run_builtin(stage, molecule, options, budget)
+                }
+                StepAction::Custom(function) => {
+                    budget.check_time()?;
+                    on_step(step.progress.index());
+                    function(molecule)
+                }
+            };
+            result.map_err(|source| TyperError::PerceptionFailed {
+                step: step.name.clone(),
+                stage: step.progress,
+                source,
+            })?;
+            trace::event!(
+                rings = molecule.rings.len(),
+                aromatic_atoms = molecule.atoms.iter().filter(|a| a.is_aromatic).count(),
+                resonant_atoms = molecule.atoms.iter().filter(|a| a.is_resonant).count(),
+                resonance_systems = molecule.resonance_systems.len(),
+                "perception step finished"
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Default for PerceptionPipeline {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl fmt::Debug for PerceptionPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PerceptionPipeline")
+            .field("steps", &self.step_names())
+            .finish()
+    }
+}
+
+/// Runs the built-in implementation of a perception stage.
+fn run_builtin(
+    stage: Stage,
+    molecule: &mut AnnotatedMolecule,
+    options: &PerceptionOptions,
+    budget: &Budget,
+) -> Result<(), PerceptionError> {
+    match stage {
+        Stage::Rings => rings::perceive(molecule, options, budget),
+        Stage::Kekulization => kekulize::perceive(molecule, budget),
+        Stage::Electrons => electrons::perceive(molecule),
+        Stage::Aromaticity | Stage::Resonance
+            if options.skip_inorganic_conjugation && is_inorganic(molecule) =>
+        {
+            Ok(())
+        }
+        Stage::Aromaticity if options.trust_input_aromaticity => {
+            aromaticity::trust_input(molecule);
+            Ok(())
+        }
+        Stage::Aromaticity => aromaticity::perceive(molecule, options.aromaticity),
+        Stage::Resonance => resonance::perceive(molecule, options.metal_bound_groups),
+        Stage::Hybridization => hybridization::perceive(molecule),
+        Stage::Geometry => geometry::perceive(molecule),
+        Stage::Groups => groups::perceive(molecule, options.metal_bound_groups),
+        Stage::Typing | Stage::Building => unreachable!("only perception stages occupy slots"),
+    }
+}
+
+/// Returns `true` if the molecule contains no carbon atom.
+fn is_inorganic(molecule: &AnnotatedMolecule) -> bool {
+    molecule.atoms.iter().all(|atom| atom.element != Element::C)
+}
+
+/// Builder assembling a [`PerceptionPipeline`] from the standard stages.
+///
+/// Built-in stages keep their slots even when skipped, so steps can still be inserted relative
+/// to them. Later perception stages and the typing rules rely on the annotations of earlier
+/// ones; a pipeline that skips a stage must supply those annotations through the input or a
+/// custom stage.
+pub struct PerceptionPipelineBuilder {
+    steps: Vec<PipelineStep>,
+}
+
+impl PerceptionPipelineBuilder {
+    /// Creates a builder holding the standard stages.
+    fn new() -> Self {
+        let steps = Stage::ALL[..STEP_NAMES.len()]
+            .iter()
+            .map(|&stage| PipelineStep {
+                name: stage.name().to_string(),
+                slot: Some(stage),
+                progress: stage,
+                before: false,
+                action: StepAction::Builtin,
+            })
+            .collect();
+        Self { steps }
+    }
+
+    /// Disables a built-in stage.
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - Perception stage to skip.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stage` is [`Stage::Typing`] or [`Stage::Building`].
+    pub fn skip(mut self, stage: Stage) -> Self {
+        let position = self.slot_position(stage);
+        self.steps[position].action = StepAction::Skipped;
+        self
+    }
+
+    /// Runs `function` in place of a built-in stage.
+    ///
+    /// The replacement is reported under the built-in stage's name.
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - Perception stage to replace.
+    /// * `function` - Stage implementation to run instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stage` is [`Stage::Typing`] or [`Stage::Building`].
+    pub fn replace<F>(mut self, stage: Stage, function: F) -> Self
+    where
+        F: Fn(&mut AnnotatedMolecule) -> Result<(), PerceptionError> + Send + Sync + 'static,
+    {
+        let position = self.slot_position(stage);
+        self.steps[position].action = StepAction::Custom(Arc::new(function));
+        self
+    }
+
+    /// Inserts a custom stage immediately before a built-in stage.
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - Perception stage the new step precedes.
+    /// * `name` - Name reported if the step fails.
+    /// * `function` - Stage implementation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stage` is [`Stage::Typing`] or [`Stage::Building`].
+    pub fn insert_before<F>(mut self, stage: Stage, name: &str, function: F) -> Self
+    where
+        F: Fn(&mut AnnotatedMolecule) -> Result<(), PerceptionError> + Send + Sync + 'static,
+    {
+        let position = self.slot_position(stage);
+        self.steps
+            .insert(position, custom_step(name, stage, true, Arc::new(function)));
+        self
+    }
+
+    /// Inserts a custom stage immediately after a built-in stage.
+    ///
+    /// Steps inserted after the same stage run in insertion order.
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - Perception stage the new step follows.
+    /// * `name` - Name reported if the step fails.
+    /// * `function` - Stage implementation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stage` is [`Stage::Typing`] or [`Stage::Building`].
+    pub fn insert_after<F>(mut self, stage: Stage, name: &str, function: F) -> Self
+    where
+        F: Fn(&mut AnnotatedMolecule) -> Result<(), PerceptionError> + Send + Sync + 'static,
+    {
+        let mut position = self.slot_position(stage) + 1;
+        while self
+            .steps
+            .get(position)
+            .is_some_and(|step| step.slot.is_none() && step.progress == stage && !step.before)
+        {
+            position += 1;
+        }
+        self.steps.insert(
+            position,
+            custom_step(name, stage, false, Arc::new(function)),
+        );
+        self
+    }
+
+    /// Finishes the pipeline.
+    pub fn build(self) -> PerceptionPipeline {
+        PerceptionPipeline {
+            steps: self.steps.into(),
+        }
+    }
+
+    /// Returns the index of the step occupying a built-in stage's slot.
+    fn slot_position(&self, stage: Stage) -> usize {
+        self.steps
+            .iter()
+            .position(|step| step.slot == Some(stage))
+            .unwrap_or_else(|| panic!("{stage} is not a perception stage"))
+    }
+}
+
+impl fmt::Debug for PerceptionPipelineBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PerceptionPipelineBuilder")
+            .field("steps", &self.steps.len())
+            .finish()
+    }
+}
+
+/// Creates a step inserted next to the built-in stage `progress`.
+fn custom_step(
+    name: &str,
+    progress: Stage,
+    before: bool,
+    function: Arc<CustomStage>,
+) -> PipelineStep {
+    PipelineStep {
+        name: name.to_string(),
+        slot: None,
+        progress,
+        before,
+        action: StepAction::Custom(function),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::graph::MolecularGraph;
+    use crate::core::properties::{Element, GraphBondOrder, Hybridization};
+    use std::sync::Mutex;
+
+    fn ethene() -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        graph.add_bond(c1, c2, GraphBondOrder::Double).unwrap();
+        for c in [c1, c2] {
+            for _ in 0..2 {
+                let h = graph.add_atom(Element::H);
+                graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+            }
+        }
+        graph
+    }
+
+    fn run(pipeline: &PerceptionPipeline) -> Result<AnnotatedMolecule, TyperError> {
+        let mut molecule = AnnotatedMolecule::new(&ethene()).unwrap();
+        pipeline.run(
+            &mut molecule,
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+            &mut |_| {},
+        )?;
+        Ok(molecule)
+    }
+
+    #[test]
+    fn inserted_steps_run_in_order_around_their_anchor() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let record = |label: &'static str| {
+            let log = Arc::clone(&log);
+            move |_: &mut AnnotatedMolecule| {
+                log.lock().unwrap().push(label);
+                Ok(())
+            }
+        };
+        let pipeline = PerceptionPipeline::builder()
+            .skip(Stage::Resonance)
+            .insert_after(Stage::Electrons, "first", record("first"))
+            .insert_before(Stage::Aromaticity, "third", record("third"))
+            .insert_after(Stage::Electrons, "second", record("second"))
+            .insert_before(Stage::Hybridization, "fourth", record("fourth"))
+            .insert_before(Stage::Rings, "zeroth", record("zeroth"))
+            .build();
+
+        assert_eq!(
+            pipeline.step_names(),
+            [
+                "zeroth",
+                "Rings",
+                "Kekulization",
+                "Electrons",
+                "first",
+                "second",
+                "third",
+                "Aromaticity",
+                "fourth",
+                "Hybridization",
+                "Geometry",
+                "Groups"
+            ]
+        );
+        run(&pipeline).unwrap();
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["zeroth", "first", "second", "third", "fourth"]
+        );
+    }
+
+    #[test]
+    fn replaced_and_skipped_stages_leave_annotations_alone() {
+        let pipeline = PerceptionPipeline::builder()
+            .replace(Stage::Hybridization, |molecule| {
+                for atom in &mut molecule.atoms {
+                    atom.hybridization = Hybridization::SP3;
+                }
+                Ok(())
+            })
+            .build();
+        let molecule = run(&pipeline).unwrap();
+        assert_eq!(molecule.atoms[0].hybridization, Hybridization::SP3);
+
+        let molecule = run(&PerceptionPipeline::standard()).unwrap();
+        assert_eq!(molecule.atoms[0].hybridization, Hybridization::SP2);
+    }
+
+    #[test]
+    fn failing_custom_stage_is_reported_by_name() {
+        let pipeline = PerceptionPipeline::builder()
+            .insert_after(Stage::Rings, "Validate", |_| {
+                Err(PerceptionError::Other("rejected".to_string()))
+            })
+            .build();
+        match run(&pipeline) {
+            Err(TyperError::PerceptionFailed { step, .. }) => assert_eq!(step, "Validate"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn inorganic_molecules_can_skip_conjugation() {
+        // Phosphate dianion: its terminal oxygens form an oxo resonance system.
+        let mut graph = MolecularGraph::new();
+        let p = graph.add_atom(Element::P);
+        let oxo = graph.add_atom(Element::O);
+        graph.add_bond(p, oxo, GraphBondOrder::Double).unwrap();
+        for _ in 0..2 {
+            let o = graph.add_atom(Element::O);
+            graph.add_bond(p, o, GraphBondOrder::Single).unwrap();
+        }
+        let hydroxyl = graph.add_atom(Element::O);
+        let h = graph.add_atom(Element::H);
+        graph.add_bond(p, hydroxyl, GraphBondOrder::Single).unwrap();
+        graph.add_bond(hydroxyl, h, GraphBondOrder::Single).unwrap();
+
+        let perceive = |options: &PerceptionOptions| {
+            let mut molecule = AnnotatedMolecule::new(&graph).unwrap();
+            PerceptionPipeline::standard()
+                .run(&mut molecule, options, &Budget::default(), &mut |_| {})
+                .unwrap();
+            molecule
+        };
+
+        assert_eq!(
+            perceive(&PerceptionOptions::DEFAULT)
+                .resonance_systems
+                .len(),
+            1
+        );
+        let skipped = perceive(&PerceptionOptions {
+            skip_inorganic_conjugation: true,
+            ..PerceptionOptions::DEFAULT
+        });
+        assert!(skipped.resonance_systems.is_empty());
+        assert!(!skipped.atoms[oxo].is_resonant);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a perception stage")]
+    fn typing_stage_cannot_be_skipped() {
+        let _ = PerceptionPipeline::builder().skip(Stage::Typing);
+    }
+}
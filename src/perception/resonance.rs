@@ -1,11 +1,13 @@
 //! Detects specific, strong resonance systems via strict substructure matching.
 //!
 //! Unlike generalized conjugation detection, this module uses an allowlist of
-//! chemically significant motifs (Carboxylate, Nitro, Guanidinium, Amide).
+//! chemically significant motifs (Carboxylate, Guanidinium, Thiourea, Amide, Azide) and a
+//! table of oxo-group templates (Nitro/Nitrate, Sulfonate/Sulfate, Phosphate).
 //! When a motif is found, its atoms are marked `is_resonant`, and the system
 //! (atoms + bonds) is recorded to ensure the correct bond order in the topology.
 
 use super::model::{AnnotatedMolecule, ResonanceSystem};
+use crate::compat::prelude::*;
 use crate::core::error::PerceptionError;
 use crate::core::properties::{Element, GraphBondOrder};
 
@@ -22,28 +24,59 @@ use crate::core::properties::{Element, GraphBondOrder};
 /// # Arguments
 ///
 /// * `molecule` - Annotated molecule whose atoms will be tagged and systems recorded.
+/// * `metal_bound_groups` - Whether oxygens bonded to metal centers still count as terminal.
 ///
 /// # Returns
 ///
 /// `Ok(())` always, as this process is infallible.
-pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError> {
-    detect_core_functional_groups(molecule);
+pub fn perceive(
+    molecule: &mut AnnotatedMolecule,
+    metal_bound_groups: bool,
+) -> Result<(), PerceptionError> {
+    detect_core_functional_groups(molecule, metal_bound_groups);
     propagate_resonance_to_periphery(molecule);
     Ok(())
 }
 
 /// Detects core resonance systems via substructure matching.
-fn detect_core_functional_groups(molecule: &mut AnnotatedMolecule) {
+fn detect_core_functional_groups(molecule: &mut AnnotatedMolecule, metal_bound_groups: bool) {
     let mut processed = vec![false; molecule.atoms.len()];
 
-    detect_carboxylate_groups(molecule, &mut processed);
-    detect_nitro_groups(molecule, &mut processed);
+    detect_carboxylate_groups(molecule, metal_bound_groups, &mut processed);
+    for template in &OXO_GROUP_TEMPLATES {
+        detect_oxo_groups(molecule, template, metal_bound_groups, &mut processed);
+    }
+    detect_azide_groups(molecule, metal_bound_groups, &mut processed);
     detect_guanidinium_groups(molecule, &mut processed);
     detect_thiourea_groups(molecule, &mut processed);
     detect_amide_groups(molecule, &mut processed);
-    detect_phosphate_groups(molecule, &mut processed);
 }
 
+/// Template for a central atom whose terminal oxygens share a delocalized π bond and charge.
+struct OxoGroupTemplate {
+    /// Element of the central atom.
+    center: Element,
+    /// Whether the center is trigonal and joins the π system itself (nitro), rather than
+    /// staying tetrahedral (sulfonate, phosphate).
+    planar_center: bool,
+}
+
+/// Oxo groups detected in order: nitro and nitrate, sulfonate and sulfate, phosphate.
+const OXO_GROUP_TEMPLATES: [OxoGroupTemplate; 3] = [
+    OxoGroupTemplate {
+        center: Element::N,
+        planar_center: true,
+    },
+    OxoGroupTemplate {
+        center: Element::S,
+        planar_center: false,
+    },
+    OxoGroupTemplate {
+        center: Element::P,
+        planar_center: false,
+    },
+];
+
 /// Propagates resonance flags to peripheral heteroatoms bonded to resonant systems.
 fn propagate_resonance_to_periphery(molecule: &mut AnnotatedMolecule) {
     let mut newly_resonant = Vec::new();
@@ -51,9 +84,13 @@ fn propagate_resonance_to_periphery(molecule: &mut AnnotatedMolecule) {
     for i in 0..molecule.atoms.len() {
         let atom = &molecule.atoms[i];
 
+        // Sulfur with three or more bonds (sulfoxides, sulfonium ions) is pyramidal and keeps
+        // its lone pair out of conjugation. Nitrogen planarity is decided by its own pass
+        // during hybridization, which tells π acceptors from other lone-pair donors.
         if atom.is_resonant
-            || !matches!(atom.element, Element::O | Element::N | Element::S)
+            || !matches!(atom.element, Element::O | Element::S)
             || atom.lone_pairs == 0
+            || (atom.element == Element::S && atom.covalent_degree() >= 3)
         {
             continue;
         }
@@ -98,7 +135,11 @@ fn find_bond_id(molecule: &AnnotatedMolecule, u: usize, v: usize) -> usize {
 }
 
 /// Detects Carboxylate groups: C(=O)O-
-fn detect_carboxylate_groups(molecule: &mut AnnotatedMolecule, processed: &mut [bool]) {
+fn detect_carboxylate_groups(
+    molecule: &mut AnnotatedMolecule,
+    metal_bound_groups: bool,
+    processed: &mut [bool],
+) {
     for c_idx in 0..molecule.atoms.len() {
         if processed[c_idx] || molecule.atoms[c_idx].element != Element::C {
             continue;
@@ -111,7 +152,9 @@ fn detect_carboxylate_groups(molecule: &mut AnnotatedMolecule, processed: &mut [
             if molecule.atoms[neighbor_id].element == Element::O {
                 match order {
                     GraphBondOrder::Double => double_o = Some(neighbor_id),
-                    GraphBondOrder::Single if molecule.atoms[neighbor_id].degree == 1 => {
+                    GraphBondOrder::Single
+                        if molecule.atoms[neighbor_id].group_degree(metal_bound_groups) == 1 =>
+                    {
                         single_o = Some(neighbor_id);
                     }
                     _ => {}
@@ -131,31 +174,82 @@ fn detect_carboxylate_groups(molecule: &mut AnnotatedMolecule, processed: &mut [
     }
 }
 
-/// Detects Nitro groups: N(=O)O-
-fn detect_nitro_groups(molecule: &mut AnnotatedMolecule, processed: &mut [bool]) {
-    for n_idx in 0..molecule.atoms.len() {
-        if processed[n_idx] || molecule.atoms[n_idx].element != Element::N {
+/// Detects oxo groups matching `template`: a center bearing at least two terminal oxygens.
+///
+/// Tetrahedral centers additionally need a singly bonded terminal oxygen, the charge carrier,
+/// so that neutral sulfones and sulfonamides with two `S=O` bonds are left alone. Every
+/// terminal oxygen joins the system, which keeps the three oxygens of a sulfonate or nitrate
+/// equivalent.
+fn detect_oxo_groups(
+    molecule: &mut AnnotatedMolecule,
+    template: &OxoGroupTemplate,
+    metal_bound_groups: bool,
+    processed: &mut [bool],
+) {
+    for center in 0..molecule.atoms.len() {
+        if processed[center] || molecule.atoms[center].element != template.center {
             continue;
         }
 
-        let mut oxygen_neighbors = Vec::new();
-        for &(neighbor_id, _) in &molecule.adjacency[n_idx] {
-            if molecule.atoms[neighbor_id].element == Element::O {
-                oxygen_neighbors.push(neighbor_id);
-            }
+        let terminal_oxygens: Vec<(usize, GraphBondOrder)> = molecule.adjacency[center]
+            .iter()
+            .filter(|&&(neighbor_id, _)| {
+                let neighbor = &molecule.atoms[neighbor_id];
+                neighbor.element == Element::O && neighbor.group_degree(metal_bound_groups) == 1
+            })
+            .copied()
+            .collect();
+        let has_charged_oxygen = terminal_oxygens
+            .iter()
+            .any(|&(_, order)| order == GraphBondOrder::Single);
+
+        if terminal_oxygens.len() < 2 || !(template.planar_center || has_charged_oxygen) {
+            continue;
         }
 
-        if oxygen_neighbors.len() == 2 {
-            let o1 = oxygen_neighbors[0];
-            let o2 = oxygen_neighbors[1];
-            let b1 = find_bond_id(molecule, n_idx, o1);
-            let b2 = find_bond_id(molecule, n_idx, o2);
-            for &atom_id in &[n_idx, o1, o2] {
-                molecule.atoms[atom_id].is_resonant = true;
-                processed[atom_id] = true;
-            }
-            push_resonance_system(molecule, &[n_idx, o1, o2], &[b1, b2]);
+        let mut atoms = vec![center];
+        let mut bonds = Vec::with_capacity(terminal_oxygens.len());
+        for &(oxygen, _) in &terminal_oxygens {
+            bonds.push(find_bond_id(molecule, center, oxygen));
+            atoms.push(oxygen);
+            molecule.atoms[oxygen].is_resonant = true;
+            processed[oxygen] = true;
         }
+        molecule.atoms[center].is_resonant = template.planar_center;
+        processed[center] = true;
+        push_resonance_system(molecule, &atoms, &bonds);
+    }
+}
+
+/// Detects Azide groups: N=[N+]=[N-] or [N-]-[N+]#N
+///
+/// The outer nitrogens share the delocalized π bond, while the central nitrogen stays linear.
+fn detect_azide_groups(
+    molecule: &mut AnnotatedMolecule,
+    metal_bound_groups: bool,
+    processed: &mut [bool],
+) {
+    for center in 0..molecule.atoms.len() {
+        if processed[center]
+            || !super::groups::is_azide_center(molecule, center, metal_bound_groups)
+            || molecule.adjacency[center]
+                .iter()
+                .any(|&(id, _)| processed[id])
+        {
+            continue;
+        }
+
+        let outer = [
+            molecule.adjacency[center][0].0,
+            molecule.adjacency[center][1].0,
+        ];
+        let bonds = outer.map(|n_id| find_bond_id(molecule, center, n_id));
+        for n_id in outer {
+            molecule.atoms[n_id].is_resonant = true;
+            processed[n_id] = true;
+        }
+        processed[center] = true;
+        push_resonance_system(molecule, &[center, outer[0], outer[1]], &bonds);
     }
 }
 
@@ -258,47 +352,12 @@ fn detect_amide_groups(molecule: &mut AnnotatedMolecule, processed: &mut [bool])
     }
 }
 
-/// Detects Phosphate groups: P(=O)(O-)
-fn detect_phosphate_groups(molecule: &mut AnnotatedMolecule, processed: &mut [bool]) {
-    for p_idx in 0..molecule.atoms.len() {
-        if molecule.atoms[p_idx].element != Element::P {
-            continue;
-        }
-
-        let mut terminal_oxygens = Vec::new();
-
-        for &(neighbor_id, _) in &molecule.adjacency[p_idx] {
-            if molecule.atoms[neighbor_id].element == Element::O
-                && molecule.atoms[neighbor_id].degree == 1
-            {
-                terminal_oxygens.push(neighbor_id);
-            }
-        }
-
-        if terminal_oxygens.len() >= 2 {
-            let o1 = terminal_oxygens[0];
-            let o2 = terminal_oxygens[1];
-
-            let b1 = find_bond_id(molecule, p_idx, o1);
-            let b2 = find_bond_id(molecule, p_idx, o2);
-
-            molecule.atoms[o1].is_resonant = true;
-            molecule.atoms[o2].is_resonant = true;
-            processed[o1] = true;
-            processed[o2] = true;
-            processed[p_idx] = true;
-
-            push_resonance_system(molecule, &[p_idx, o1, o2], &[b1, b2]);
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compat::collections::HashSet;
     use crate::core::graph::MolecularGraph;
     use crate::core::properties::Element;
-    use std::collections::HashSet;
 
     fn build_molecule(
         elements: &[Element],
@@ -320,7 +379,7 @@ mod tests {
     }
 
     fn run_resonance_perception(mut molecule: AnnotatedMolecule) -> AnnotatedMolecule {
-        perceive(&mut molecule).expect("resonance perception should succeed");
+        perceive(&mut molecule, false).expect("resonance perception should succeed");
         molecule
     }
 
@@ -653,6 +712,102 @@ mod tests {
         assert_resonance_system_count(&molecule, 0);
     }
 
+    #[test]
+    fn phosphate_includes_every_terminal_oxygen() {
+        let elements = [Element::P, Element::O, Element::O, Element::O, Element::O];
+        let bonds = [
+            (0, 1, GraphBondOrder::Double),
+            (0, 2, GraphBondOrder::Single),
+            (0, 3, GraphBondOrder::Single),
+            (0, 4, GraphBondOrder::Single),
+        ];
+        let molecule = run_resonance_perception(build_molecule(&elements, &bonds, &[]));
+
+        assert_resonant_atoms(&molecule, &[1, 2, 3, 4]);
+        assert_system_contains_atoms(&molecule, 0, &[0, 1, 2, 3, 4]);
+        assert_eq!(molecule.resonance_systems[0].bond_ids.len(), 4);
+    }
+
+    #[test]
+    fn sulfonate_group_is_detected() {
+        let elements = [Element::C, Element::S, Element::O, Element::O, Element::O];
+        let bonds = [
+            (0, 1, GraphBondOrder::Single),
+            (1, 2, GraphBondOrder::Double),
+            (1, 3, GraphBondOrder::Double),
+            (1, 4, GraphBondOrder::Single),
+        ];
+        let molecule = run_resonance_perception(build_molecule(&elements, &bonds, &[]));
+
+        assert_resonant_atoms(&molecule, &[2, 3, 4]);
+        assert_resonance_system_count(&molecule, 1);
+        assert_system_contains_atoms(&molecule, 0, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn sulfone_without_charged_oxygen_is_not_detected() {
+        let elements = [Element::C, Element::S, Element::O, Element::O, Element::C];
+        let bonds = [
+            (0, 1, GraphBondOrder::Single),
+            (1, 2, GraphBondOrder::Double),
+            (1, 3, GraphBondOrder::Double),
+            (1, 4, GraphBondOrder::Single),
+        ];
+        let molecule = run_resonance_perception(build_molecule(&elements, &bonds, &[]));
+
+        assert_resonance_system_count(&molecule, 0);
+    }
+
+    #[test]
+    fn nitrate_keeps_all_three_oxygens_equivalent() {
+        let elements = [Element::N, Element::O, Element::O, Element::O];
+        let bonds = [
+            (0, 1, GraphBondOrder::Double),
+            (0, 2, GraphBondOrder::Single),
+            (0, 3, GraphBondOrder::Single),
+        ];
+        let molecule = run_resonance_perception(build_molecule(&elements, &bonds, &[]));
+
+        assert_resonant_atoms(&molecule, &[0, 1, 2, 3]);
+        assert_resonance_system_count(&molecule, 1);
+    }
+
+    #[test]
+    fn azide_outer_nitrogens_are_resonant_in_either_form() {
+        let elements = [Element::C, Element::N, Element::N, Element::N];
+        for (smiles, first, second) in [
+            (
+                "CN=[N+]=[N-]",
+                GraphBondOrder::Double,
+                GraphBondOrder::Double,
+            ),
+            (
+                "C[N-][N+]#N",
+                GraphBondOrder::Single,
+                GraphBondOrder::Triple,
+            ),
+        ] {
+            let bonds = [
+                (0, 1, GraphBondOrder::Single),
+                (1, 2, first),
+                (2, 3, second),
+            ];
+            let molecule = run_resonance_perception(build_molecule(&elements, &bonds, &[]));
+
+            assert_resonant_atoms(&molecule, &[1, 3]);
+            assert_resonance_system_count(&molecule, 1);
+            assert_system_contains_atoms(&molecule, 0, &[1, 2, 3]);
+
+            let graph = crate::io::smiles::parse_smiles(smiles).expect("valid SMILES");
+            let topology = crate::Typer::new().assign_topology(&graph).expect("typing");
+            let types: Vec<_> = topology.atoms[..4]
+                .iter()
+                .map(|atom| atom.atom_type.to_string())
+                .collect();
+            assert_eq!(types, ["C_3", "N_R", "N_1", "N_R"], "{smiles}");
+        }
+    }
+
     #[test]
     fn peripheral_oxygen_with_lone_pairs_is_promoted() {
         let elements = [Element::C, Element::O, Element::O, Element::O];
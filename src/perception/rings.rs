@@ -1,27 +1,53 @@
 //! Detects rings and records small-set cycle representatives for subsequent perception stages.
 //!
 //! This module builds a minimal cycle basis from the molecular graph so aromaticity, resonance,
-//! and hybridization passes can quickly determine ring membership and sizes.
-
-use super::model::{AnnotatedMolecule, NeighborBond, Ring};
-use crate::core::error::PerceptionError;
+//! and hybridization passes can quickly determine ring membership and sizes. Rings keep their
+//! atoms in cyclic order, so they can also be reported in the output topology.
+//!
+//! In a periodic cell the search runs over atom images: a ring is a cycle that returns to the
+//! image it started from, and when rings are larger than the cell, as in graphene or diamond, one
+//! ring may pass through two images of the same cell atom.
+
+use super::model::{AdjacencyList, AnnotatedMolecule, NeighborBond, Ring};
+use super::options::{MacrocycleMode, PerceptionOptions};
+use super::scratch::ScratchPool;
+use crate::compat::collections::{HashMap, HashSet};
+use crate::compat::prelude::*;
+use crate::core::error::{LimitExceeded, PerceptionError};
+use crate::core::limits::Budget;
+use crate::core::periodic::ImageFlags;
 use crate::core::properties::GraphBondOrder;
-use std::collections::{HashMap, VecDeque};
+use crate::core::topology::canonical_cycle;
+use alloc::collections::VecDeque;
 
 /// Computes ring information for the supplied annotated molecule.
 ///
 /// Runs connected-component counting, enumerates simple cycle candidates, chooses a minimal cycle
-/// basis, and marks atoms with ring membership metadata.
+/// basis, and marks atoms with ring membership metadata. Basis cycles longer than
+/// `options.max_ring_size` become envelopes in [`AnnotatedMolecule::macrocycles`] or are
+/// dropped, as `options.macrocycles` selects.
 ///
 /// # Arguments
 ///
 /// * `molecule` - Mutable annotated molecule that will receive ring annotations.
+/// * `options` - Perception settings providing the ring size limit.
+/// * `budget` - Resource budget bounding the number of cycle candidates.
 ///
 /// # Returns
 ///
-/// `Ok(())` whether rings exist or not; the step keeps the `Result` signature to match the broader
-/// perception pipeline but currently never emits [`PerceptionError`].
-pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError> {
+/// `Ok(())` whether rings exist or not.
+///
+/// # Errors
+///
+/// Returns [`PerceptionError::LimitExceeded`] when more cycle candidates are found than the budget
+/// allows.
+pub fn perceive(
+    molecule: &mut AnnotatedMolecule,
+    options: &PerceptionOptions,
+    budget: &Budget,
+) -> Result<(), PerceptionError> {
+    molecule.rings.clear();
+    molecule.macrocycles.clear();
     let num_atoms = molecule.atoms.len();
     if num_atoms == 0 {
         return Ok(());
@@ -42,32 +68,123 @@ pub fn perceive(molecule: &mut AnnotatedMolecule) -> Result<(), PerceptionError>
         .map(|(i, id)| (id, i))
         .collect();
 
-    let mut workspace = RingSearchWorkspace::new(num_atoms);
-    let candidates = enumerate_cycle_candidates(molecule, &mut workspace);
-
-    let sssr_candidates =
-        select_minimal_cycle_basis(candidates, cyclomatic_number as usize, &bond_id_to_index);
+    let sssr_candidates = RingSearchWorkspace::with_pooled(num_atoms, |workspace| {
+        let mut candidates = enumerate_cycle_candidates(
+            molecule,
+            workspace,
+            budget.limits.max_ring_candidates,
+            false,
+        )?;
+
+        // Once a ring wraps the cell, the shortest cycle through each bond may keep finding the
+        // same ring; every angle of such a cell lies on exactly one smallest ring.
+        if candidates.iter().any(RingCandidate::wraps) {
+            enumerate_periodic_wedge_candidates(
+                molecule,
+                budget.limits.max_ring_candidates,
+                &mut candidates,
+            )?;
+            return Ok(select_rings(
+                candidates,
+                cyclomatic_number as usize,
+                &bond_id_to_index,
+            ));
+        }
 
-    let final_rings: Vec<Ring> = sssr_candidates
-        .into_iter()
-        .map(|c| {
-            let mut atom_ids = c.atom_ids;
-            atom_ids.sort_unstable();
-            atom_ids
-        })
-        .collect();
-    molecule.rings = final_rings;
+        let sssr_candidates =
+            select_rings(candidates, cyclomatic_number as usize, &bond_id_to_index);
+
+        // The shortest cycle through each bond need not span the cycle space: in C60, each bond
+        // shared by two hexagons yields only one of them. Shortest cycles through each pair of
+        // adjacent bonds fill such gaps.
+        if sssr_candidates.len() < cyclomatic_number as usize && molecule.bond_images.is_empty() {
+            let mut candidates = enumerate_cycle_candidates(
+                molecule,
+                workspace,
+                budget.limits.max_ring_candidates,
+                false,
+            )?;
+            enumerate_wedge_candidates(
+                molecule,
+                workspace,
+                budget.limits.max_ring_candidates,
+                &mut candidates,
+            )?;
+            return Ok(select_rings(
+                candidates,
+                cyclomatic_number as usize,
+                &bond_id_to_index,
+            ));
+        }
+        Ok::<_, PerceptionError>(sssr_candidates)
+    })?;
+
+    for candidate in sssr_candidates {
+        let ring = canonical_cycle(candidate.atom_ids);
+        if options.max_ring_size.is_none_or(|max| ring.len() <= max) {
+            molecule.rings.push(ring);
+        } else if options.macrocycles == MacrocycleMode::Envelope {
+            molecule.macrocycles.push(ring);
+        }
+    }
 
     annotate_atoms_with_ring_info(molecule);
+    annotate_ring_junctions(molecule);
 
     Ok(())
 }
 
+/// Computes the minimal cycle basis of the molecule with its metal centers removed.
+///
+/// Rings that close through a metal are chelate rings rather than π systems, and they can displace
+/// ligand cycles from the basis: with zinc bound to its four nitrogens, the inner 16-membered
+/// cycle of a porphyrin is no longer among the smallest rings. The ligand basis restores such
+/// cycles for aromaticity perception. The number of candidates is bounded by the full ring search
+/// that already ran under the resource budget.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule whose rings were perceived.
+///
+/// # Returns
+///
+/// Cycles free of metal centers, in cyclic order starting at their lowest atom ID.
+pub(super) fn ligand_cycles(molecule: &AnnotatedMolecule) -> Vec<Ring> {
+    let bond_id_to_index: HashMap<usize, usize> = molecule
+        .bonds
+        .iter()
+        .map(|b| b.id)
+        .enumerate()
+        .map(|(i, id)| (id, i))
+        .collect();
+
+    let candidates = RingSearchWorkspace::with_pooled(molecule.atoms.len(), |workspace| {
+        enumerate_cycle_candidates(molecule, workspace, None, true)
+    })
+    .expect("candidate search without a limit cannot fail");
+    let max_cycles = candidates.len();
+
+    select_rings(candidates, max_cycles, &bond_id_to_index)
+        .into_iter()
+        .map(|candidate| canonical_cycle(candidate.atom_ids))
+        .collect()
+}
+
+/// Fewest bonds a path over atom images may span, so that rings larger than a small cell are
+/// still found.
+const MIN_PERIODIC_SEARCH_DEPTH: usize = 24;
+
+/// Ring search buffers released by earlier runs, shared by all threads.
+static WORKSPACES: ScratchPool<RingSearchWorkspace> = ScratchPool::new();
+
 /// Reusable scratch buffers to avoid per-bond allocations during ring search.
 struct RingSearchWorkspace {
     queue: VecDeque<usize>,
     visited: Vec<bool>,
     parent: Vec<Option<(usize, usize)>>,
+    /// Atoms visited since the last reset, so a reset costs the size of the last search rather
+    /// than of the molecule; ring closures in large fused systems are found within a few bonds.
+    touched: Vec<usize>,
 }
 
 impl RingSearchWorkspace {
@@ -76,67 +193,386 @@ impl RingSearchWorkspace {
             queue: VecDeque::with_capacity(num_atoms),
             visited: vec![false; num_atoms],
             parent: vec![None; num_atoms],
+            touched: Vec::new(),
         }
     }
 
+    /// Runs `f` with a workspace from [`WORKSPACES`], sized and cleared for `num_atoms` atoms.
+    fn with_pooled<R>(num_atoms: usize, f: impl FnOnce(&mut Self) -> R) -> R {
+        WORKSPACES.with(
+            || Self::new(num_atoms),
+            |workspace| {
+                workspace.prepare(num_atoms);
+                f(workspace)
+            },
+        )
+    }
+
+    /// Clears the buffers left by a previous molecule and sizes them for `num_atoms` atoms.
+    fn prepare(&mut self, num_atoms: usize) {
+        self.reset();
+        self.visited.resize(num_atoms, false);
+        self.parent.resize(num_atoms, None);
+    }
+
     fn reset(&mut self) {
         self.queue.clear();
-        self.visited.fill(false);
-        self.parent.fill(None);
+        for atom_id in self.touched.drain(..) {
+            self.visited[atom_id] = false;
+            self.parent[atom_id] = None;
+        }
+    }
+
+    fn block(&mut self, atom_id: usize) {
+        self.visited[atom_id] = true;
+        self.touched.push(atom_id);
+    }
+
+    fn visit(&mut self, atom_id: usize, parent: Option<(usize, usize)>) {
+        self.visited[atom_id] = true;
+        self.parent[atom_id] = parent;
+        self.touched.push(atom_id);
+        self.queue.push_back(atom_id);
     }
 }
 
+/// An atom of an infinite periodic structure: an atom of the cell and the lattice image it lies
+/// in.
+type CellVertex = (usize, ImageFlags);
+
 /// Cycle descriptor storing both atom and bond identifiers.
 struct RingCandidate {
     /// Ordered atom identifiers along the candidate cycle.
     atom_ids: Vec<usize>,
+    /// Lattice image of every atom in `atom_ids`; empty for non-periodic molecules.
+    images: Vec<ImageFlags>,
     /// Ordered bond identifiers along the candidate cycle.
     bond_ids: Vec<usize>,
     /// Cycle length measured in edges.
     len: usize,
 }
 
+impl RingCandidate {
+    /// Whether the cycle passes through two images of the same cell atom.
+    fn wraps(&self) -> bool {
+        !self.images.is_empty() && has_repeated_atom(&self.atom_ids)
+    }
+
+    /// Canonical form of the cycle, shared by all its lattice translates, rotations, and
+    /// reflections.
+    fn orbit_key(&self) -> Vec<CellVertex> {
+        let vertices: Vec<CellVertex> = self
+            .atom_ids
+            .iter()
+            .copied()
+            .zip(self.images.iter().copied())
+            .collect();
+        let len = vertices.len();
+        let mut best: Option<Vec<CellVertex>> = None;
+        for start in 0..len {
+            for reversed in [false, true] {
+                let at = |k: usize| {
+                    if reversed {
+                        vertices[(start + len - k) % len]
+                    } else {
+                        vertices[(start + k) % len]
+                    }
+                };
+                let origin = at(0).1;
+                let sequence: Vec<CellVertex> = (0..len)
+                    .map(|k| {
+                        let (atom_id, image) = at(k);
+                        (
+                            atom_id,
+                            core::array::from_fn(|axis| image[axis] - origin[axis]),
+                        )
+                    })
+                    .collect();
+                if best.as_ref().is_none_or(|best| sequence < *best) {
+                    best = Some(sequence);
+                }
+            }
+        }
+        best.unwrap_or_default()
+    }
+}
+
+/// Whether a cycle lists some atom more than once, which only happens for rings that pass
+/// through two images of one atom of a periodic cell.
+fn has_repeated_atom(atom_ids: &[usize]) -> bool {
+    let mut seen = HashSet::new();
+    !atom_ids.iter().all(|atom_id| seen.insert(atom_id))
+}
+
+/// Image of the atom reached by crossing a bond from `atom_id` in `image`.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule carrying bond images.
+/// * `atom_id` - Atom the bond is crossed from.
+/// * `bond_id` - Bond to cross.
+/// * `image` - Lattice image of `atom_id`.
+pub(super) fn step_image(
+    molecule: &AnnotatedMolecule,
+    atom_id: usize,
+    bond_id: usize,
+    image: ImageFlags,
+) -> ImageFlags {
+    let bond_image = molecule.bond_images[bond_id];
+    let sign = if molecule.bonds[bond_id].atom_ids.0 == atom_id {
+        1
+    } else {
+        -1
+    };
+    core::array::from_fn(|axis| image[axis] + sign * bond_image[axis])
+}
+
 /// Enumerates simple cycles by removing each bond and searching for alternate paths.
 ///
+/// For periodic molecules the search runs over atom images, so a candidate always closes on the
+/// same image it started from, possibly passing through two images of one cell atom on the way;
+/// cycles that only close by wrapping around the cell are skipped.
+///
 /// # Arguments
 ///
 /// * `molecule` - Annotated molecule whose adjacency and bonds will be analyzed.
 /// * `workspace` - Reusable BFS buffers to avoid per-bond allocations.
+/// * `max_candidates` - Optional cap on the number of candidates collected.
+/// * `exclude_metals` - Whether cycles may not pass through metal centers.
 ///
 /// # Returns
 ///
 /// Collection of candidate rings containing atom and bond identifiers.
+///
+/// # Errors
+///
+/// Returns [`LimitExceeded::RingCandidates`] once the candidate count passes `max_candidates`.
 fn enumerate_cycle_candidates(
     molecule: &AnnotatedMolecule,
     workspace: &mut RingSearchWorkspace,
-) -> Vec<RingCandidate> {
+    max_candidates: Option<usize>,
+    exclude_metals: bool,
+) -> Result<Vec<RingCandidate>, LimitExceeded> {
     let mut candidates = Vec::new();
+    let is_excluded = |atom_id: usize| exclude_metals && molecule.atoms[atom_id].is_metal_center;
 
     for bond_to_remove in &molecule.bonds {
-        if let Some(path) = shortest_path_bfs(
-            molecule,
-            bond_to_remove.atom_ids.0,
-            bond_to_remove.atom_ids.1,
-            Some(bond_to_remove.id),
-            workspace,
-        ) {
+        let (u, v) = bond_to_remove.atom_ids;
+        if is_excluded(u) || is_excluded(v) {
+            continue;
+        }
+        let path = if molecule.bond_images.is_empty() {
+            shortest_path_bfs(
+                molecule,
+                u,
+                v,
+                Some(bond_to_remove.id),
+                None,
+                exclude_metals,
+                workspace,
+            )
+        } else {
+            shortest_cover_path(
+                molecule,
+                (u, [0; 3]),
+                (v, molecule.bond_images[bond_to_remove.id]),
+                Some(bond_to_remove.id),
+                None,
+                exclude_metals,
+            )
+        };
+        if let Some(path) = path {
             let mut atom_ids = path.atom_ids;
+            let mut images = path.images;
             let mut bond_ids = path.bond_ids;
-            atom_ids.push(bond_to_remove.atom_ids.1);
+            atom_ids.push(v);
+            if !images.is_empty() {
+                images.push(molecule.bond_images[bond_to_remove.id]);
+            }
             bond_ids.push(bond_to_remove.id);
 
             candidates.push(RingCandidate {
                 atom_ids,
+                images,
                 bond_ids,
                 len: path.len + 1,
             });
+
+            if let Some(limit) = max_candidates
+                && candidates.len() > limit
+            {
+                return Err(LimitExceeded::RingCandidates { limit });
+            }
+        }
+    }
+    Ok(candidates)
+}
+
+/// Adds the shortest cycle through each pair of adjacent bonds to the candidates.
+///
+/// For every atom `u` and two of its bonds, to `v` and `w`, the shortest path from `w` to `v`
+/// that avoids `u` closes a cycle through both bonds. Only used for non-periodic molecules.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule whose adjacency and bonds will be analyzed.
+/// * `workspace` - Reusable BFS buffers to avoid per-bond allocations.
+/// * `max_candidates` - Optional cap on the total number of candidates collected.
+/// * `candidates` - Candidates found so far, extended in place.
+///
+/// # Errors
+///
+/// Returns [`LimitExceeded::RingCandidates`] once the candidate count passes `max_candidates`.
+fn enumerate_wedge_candidates(
+    molecule: &AnnotatedMolecule,
+    workspace: &mut RingSearchWorkspace,
+    max_candidates: Option<usize>,
+    candidates: &mut Vec<RingCandidate>,
+) -> Result<(), LimitExceeded> {
+    for (center, neighbors) in molecule.adjacency_with_bonds.iter().enumerate() {
+        for (i, first) in neighbors.iter().enumerate() {
+            for second in &neighbors[i + 1..] {
+                let Some(path) = shortest_path_bfs(
+                    molecule,
+                    second.neighbor_id,
+                    first.neighbor_id,
+                    None,
+                    Some(center),
+                    false,
+                    workspace,
+                ) else {
+                    continue;
+                };
+                let mut atom_ids = path.atom_ids;
+                let mut bond_ids = path.bond_ids;
+                atom_ids.extend([first.neighbor_id, center]);
+                bond_ids.extend([first.bond_id, second.bond_id]);
+
+                candidates.push(RingCandidate {
+                    atom_ids,
+                    images: Vec::new(),
+                    bond_ids,
+                    len: path.len + 2,
+                });
+
+                if let Some(limit) = max_candidates
+                    && candidates.len() > limit
+                {
+                    return Err(LimitExceeded::RingCandidates { limit });
+                }
+            }
         }
     }
-    candidates
+    Ok(())
+}
+
+/// Adds the shortest cycle through each pair of adjacent bonds of a periodic molecule.
+///
+/// The periodic counterpart of [`enumerate_wedge_candidates`]: for every atom `u` of the
+/// reference cell and two of its bonds, to images of `v` and `w`, the shortest path between those
+/// images that avoids `u` closes a cycle through both bonds. Each angle of graphene or diamond
+/// lies on exactly one smallest ring, so these candidates reach every ring of the cell.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule carrying bond images.
+/// * `max_candidates` - Optional cap on the total number of candidates collected.
+/// * `candidates` - Candidates found so far, extended in place.
+///
+/// # Errors
+///
+/// Returns [`LimitExceeded::RingCandidates`] once the candidate count passes `max_candidates`.
+fn enumerate_periodic_wedge_candidates(
+    molecule: &AnnotatedMolecule,
+    max_candidates: Option<usize>,
+    candidates: &mut Vec<RingCandidate>,
+) -> Result<(), LimitExceeded> {
+    for (center, neighbors) in molecule.adjacency_with_bonds.iter().enumerate() {
+        for (i, first) in neighbors.iter().enumerate() {
+            for second in &neighbors[i + 1..] {
+                let first_image = step_image(molecule, center, first.bond_id, [0; 3]);
+                let second_image = step_image(molecule, center, second.bond_id, [0; 3]);
+                if (first.neighbor_id, first_image) == (second.neighbor_id, second_image) {
+                    continue;
+                }
+                let Some(path) = shortest_cover_path(
+                    molecule,
+                    (second.neighbor_id, second_image),
+                    (first.neighbor_id, first_image),
+                    None,
+                    Some((center, [0; 3])),
+                    false,
+                ) else {
+                    continue;
+                };
+                let mut atom_ids = path.atom_ids;
+                let mut images = path.images;
+                let mut bond_ids = path.bond_ids;
+                atom_ids.extend([first.neighbor_id, center]);
+                images.extend([first_image, [0; 3]]);
+                bond_ids.extend([first.bond_id, second.bond_id]);
+
+                candidates.push(RingCandidate {
+                    atom_ids,
+                    images,
+                    bond_ids,
+                    len: path.len + 2,
+                });
+
+                if let Some(limit) = max_candidates
+                    && candidates.len() > limit
+                {
+                    return Err(LimitExceeded::RingCandidates { limit });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Chooses the rings among the candidate cycles.
+///
+/// Cycles that visit every cell atom at most once form a minimal cycle basis, exactly as in an
+/// ordinary molecule. Cycles through two images of one cell atom cannot be told apart by their
+/// bonds, since they may cross the same bond of the cell twice; each of them is kept once,
+/// whichever cell image it was found in.
+///
+/// # Arguments
+///
+/// * `candidates` - Candidate cycles.
+/// * `cyclomatic_number` - Number of independent cycles to keep among the ordinary candidates.
+/// * `bond_id_to_index` - Mapping from bond IDs to dense indices for bit-vector math.
+///
+/// # Returns
+///
+/// Basis cycles ordered by length, followed by the distinct wrapping cycles ordered by length.
+fn select_rings(
+    candidates: Vec<RingCandidate>,
+    cyclomatic_number: usize,
+    bond_id_to_index: &HashMap<usize, usize>,
+) -> Vec<RingCandidate> {
+    let (mut wrapping, ordinary): (Vec<_>, Vec<_>) =
+        candidates.into_iter().partition(RingCandidate::wraps);
+    let mut selected = select_minimal_cycle_basis(ordinary, cyclomatic_number, bond_id_to_index);
+
+    wrapping.sort_by_key(|candidate| candidate.len);
+    let mut seen = HashSet::new();
+    selected.extend(
+        wrapping
+            .into_iter()
+            .filter(|candidate| seen.insert(candidate.orbit_key())),
+    );
+    selected
 }
 
 /// Selects up to `cyclomatic_number` cycles forming a minimal basis using Gaussian elimination.
 ///
+/// The basis is kept in echelon form, every vector owning the position of its leading one as
+/// its pivot. A candidate is reduced by the vector owning its current leading one until it
+/// vanishes or leads at an unowned position; it is independent exactly when something remains.
+/// Only the pivots a candidate actually reaches are visited, which keeps the selection fast for
+/// fused systems with thousands of rings.
+///
 /// # Arguments
 ///
 /// * `candidates` - Candidate cycles sorted by length.
@@ -154,25 +590,26 @@ fn select_minimal_cycle_basis(
     candidates.sort_by_key(|c| c.len);
 
     let mut selected_rings = Vec::new();
-    let mut basis: Vec<(BitVec, usize)> = Vec::new();
+    let mut basis: Vec<BitVec> = Vec::new();
+    let mut pivot_owner: Vec<Option<usize>> = vec![None; bond_id_to_index.len()];
 
     for ring in candidates {
         let mut bitvec = BitVec::from_bond_ids(&ring.bond_ids, bond_id_to_index);
 
-        for (basis_vec, pivot) in &basis {
-            if bitvec.test(*pivot) {
-                bitvec.xor(basis_vec);
+        while let Some(lead) = bitvec.leading_one() {
+            match pivot_owner[lead] {
+                Some(owner) => bitvec.xor(&basis[owner]),
+                None => {
+                    pivot_owner[lead] = Some(basis.len());
+                    basis.push(bitvec);
+                    selected_rings.push(ring);
+                    break;
+                }
             }
         }
 
-        if let Some(pivot) = bitvec.leading_one() {
-            basis.push((bitvec, pivot));
-            basis.sort_by_key(|&(_, p)| p);
-            selected_rings.push(ring);
-
-            if selected_rings.len() == cyclomatic_number {
-                break;
-            }
+        if selected_rings.len() == cyclomatic_number {
+            break;
         }
     }
     selected_rings
@@ -180,12 +617,18 @@ fn select_minimal_cycle_basis(
 
 /// Marks atoms as ring members and records their smallest ring size.
 ///
+/// Atoms of macrocycle envelopes are ring members without a ring size; sizes beyond `u8::MAX`
+/// saturate.
+///
 /// # Arguments
 ///
 /// * `molecule` - Annotated molecule updated in-place.
 fn annotate_atoms_with_ring_info(molecule: &mut AnnotatedMolecule) {
+    for &atom_id in molecule.macrocycles.iter().flatten() {
+        molecule.atoms[atom_id].is_in_ring = true;
+    }
     for ring in &molecule.rings {
-        let ring_size = ring.len() as u8;
+        let ring_size = u8::try_from(ring.len()).unwrap_or(u8::MAX);
         for &atom_id in ring {
             if let Some(props) = molecule.atoms.get_mut(atom_id) {
                 props.is_in_ring = true;
@@ -199,17 +642,84 @@ fn annotate_atoms_with_ring_info(molecule: &mut AnnotatedMolecule) {
     }
 }
 
+/// Marks bridgehead and spiro atoms from the intersections of overlapping rings.
+///
+/// Two rings sharing exactly one atom make it a spiro atom. Two rings sharing three or more atoms
+/// are bridged, and the ends of their shared path are bridgeheads; fused rings, which share a
+/// single bond, mark neither. Macrocycle envelopes are not considered, nor are rings that pass
+/// through two images of one atom of a periodic cell, whose junctions lie between images rather
+/// than between the cell's atoms.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule whose ring atoms are updated in-place.
+fn annotate_ring_junctions(molecule: &mut AnnotatedMolecule) {
+    let mut rings_of_atom: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (ring_index, ring) in molecule.rings.iter().enumerate() {
+        if has_repeated_atom(ring) {
+            continue;
+        }
+        for &atom_id in ring {
+            rings_of_atom.entry(atom_id).or_default().push(ring_index);
+        }
+    }
+
+    let mut pairs: Vec<(usize, usize)> = rings_of_atom
+        .values()
+        .flat_map(|rings| {
+            rings
+                .iter()
+                .enumerate()
+                .flat_map(move |(i, &a)| rings[i + 1..].iter().map(move |&b| (a, b)))
+        })
+        .collect();
+    pairs.sort_unstable();
+    pairs.dedup();
+
+    for (a, b) in pairs {
+        let (first, second) = (&molecule.rings[a], &molecule.rings[b]);
+        let shared: Vec<usize> = first
+            .iter()
+            .copied()
+            .filter(|atom_id| second.contains(atom_id))
+            .collect();
+        match shared.len() {
+            1 => molecule.atoms[shared[0]].is_spiro = true,
+            2 => {}
+            _ => {
+                // The shared atoms form a path along the first ring; its ends have a ring
+                // neighbor outside the shared set.
+                let len = first.len();
+                let bridgeheads: Vec<usize> = (0..len)
+                    .filter(|&i| shared.contains(&first[i]))
+                    .filter(|&i| {
+                        !shared.contains(&first[(i + 1) % len])
+                            || !shared.contains(&first[(i + len - 1) % len])
+                    })
+                    .map(|i| first[i])
+                    .collect();
+                for atom_id in bridgeheads {
+                    molecule.atoms[atom_id].is_bridgehead = true;
+                }
+            }
+        }
+    }
+}
+
 /// Stores the path discovered between two atoms when a bond is removed.
 struct PathData {
     /// Atom identifiers along the path (excluding the destination, which is implied).
     atom_ids: Vec<usize>,
+    /// Lattice image of every atom in `atom_ids`; empty for non-periodic searches.
+    images: Vec<ImageFlags>,
     /// Bond identifiers traversed along the path.
     bond_ids: Vec<usize>,
     /// Path length measured in edges.
     len: usize,
 }
 
-/// BFS-based shortest path search that optionally excludes one bond from consideration.
+/// BFS-based shortest path search that optionally excludes one bond and one atom from
+/// consideration.
 ///
 /// # Arguments
 ///
@@ -217,6 +727,8 @@ struct PathData {
 /// * `start_id` - Starting atom identifier.
 /// * `end_id` - Destination atom identifier.
 /// * `excluded_bond_id` - Optional bond ID to ignore, simulating its removal.
+/// * `excluded_atom_id` - Optional atom ID the path may not pass through.
+/// * `exclude_metals` - Whether the path may not pass through metal centers.
 /// * `workspace` - Reusable BFS buffers to avoid per-call allocations.
 ///
 /// # Returns
@@ -227,6 +739,8 @@ fn shortest_path_bfs(
     start_id: usize,
     end_id: usize,
     excluded_bond_id: Option<usize>,
+    excluded_atom_id: Option<usize>,
+    exclude_metals: bool,
     workspace: &mut RingSearchWorkspace,
 ) -> Option<PathData> {
     workspace.reset();
@@ -237,8 +751,10 @@ fn shortest_path_bfs(
         return None;
     }
 
-    workspace.visited[start_id] = true;
-    workspace.queue.push_back(start_id);
+    workspace.visit(start_id, None);
+    if let Some(atom_id) = excluded_atom_id {
+        workspace.block(atom_id);
+    }
 
     'outer: while let Some(current_id) = workspace.queue.pop_front() {
         for NeighborBond {
@@ -247,13 +763,13 @@ fn shortest_path_bfs(
             ..
         } in &molecule.adjacency_with_bonds[current_id]
         {
-            if Some(*bond_id) == excluded_bond_id {
+            if Some(*bond_id) == excluded_bond_id
+                || (exclude_metals && molecule.atoms[*neighbor_id].is_metal_center)
+            {
                 continue;
             }
             if !workspace.visited[*neighbor_id] {
-                workspace.visited[*neighbor_id] = true;
-                workspace.parent[*neighbor_id] = Some((current_id, *bond_id));
-                workspace.queue.push_back(*neighbor_id);
+                workspace.visit(*neighbor_id, Some((current_id, *bond_id)));
 
                 if *neighbor_id == end_id {
                     break 'outer;
@@ -285,6 +801,99 @@ fn shortest_path_bfs(
 
     Some(PathData {
         atom_ids,
+        images: Vec::new(),
+        bond_ids,
+        len,
+    })
+}
+
+/// Shortest path between two atom images of a periodic structure.
+///
+/// The search runs over [`CellVertex`] states, so two images of the same cell atom are distinct
+/// vertices and a path may pass through both, as a ring of graphene does in a cell of four
+/// atoms. Paths are limited to the larger of the cell's atom count and
+/// [`MIN_PERIODIC_SEARCH_DEPTH`] bonds, which bounds the search along chains that never close.
+///
+/// # Arguments
+///
+/// * `molecule` - Annotated molecule carrying bond images.
+/// * `start` - Atom image the path starts from.
+/// * `target` - Atom image the path ends at.
+/// * `excluded_bond_id` - Optional bond joining `start` to `target` whose crossing between the
+///   two is ignored, simulating the removal of that one bond of the infinite structure; its
+///   other images stay usable.
+/// * `excluded_vertex` - Optional atom image the path may not pass through.
+/// * `exclude_metals` - Whether the path may not pass through metal centers.
+///
+/// # Returns
+///
+/// A [`PathData`] instance if a path exists.
+fn shortest_cover_path(
+    molecule: &AnnotatedMolecule,
+    start: CellVertex,
+    target: CellVertex,
+    excluded_bond_id: Option<usize>,
+    excluded_vertex: Option<CellVertex>,
+    exclude_metals: bool,
+) -> Option<PathData> {
+    let max_len = molecule.atoms.len().max(MIN_PERIODIC_SEARCH_DEPTH);
+
+    let mut parent: HashMap<CellVertex, Option<(CellVertex, usize)>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    parent.insert(start, None);
+    if let Some(vertex) = excluded_vertex {
+        parent.insert(vertex, None);
+    }
+    queue.push_back((start, 0usize));
+
+    'outer: while let Some(((current_id, image), depth)) = queue.pop_front() {
+        if depth >= max_len {
+            continue;
+        }
+        for neighbor in &molecule.adjacency_with_bonds[current_id] {
+            if exclude_metals && molecule.atoms[neighbor.neighbor_id].is_metal_center {
+                continue;
+            }
+            let next: CellVertex = (
+                neighbor.neighbor_id,
+                step_image(molecule, current_id, neighbor.bond_id, image),
+            );
+            let is_excluded_crossing = Some(neighbor.bond_id) == excluded_bond_id
+                && (current_id, image) == start
+                && next == target;
+            if is_excluded_crossing || parent.contains_key(&next) {
+                continue;
+            }
+            parent.insert(next, Some(((current_id, image), neighbor.bond_id)));
+            if next == target {
+                break 'outer;
+            }
+            queue.push_back((next, depth + 1));
+        }
+    }
+
+    let mut atom_ids = Vec::new();
+    let mut images = Vec::new();
+    let mut bond_ids = Vec::new();
+    let mut cursor = target;
+    let mut len = 0;
+    while let Some(&Some((previous, via_bond))) = parent.get(&cursor) {
+        atom_ids.push(previous.0);
+        images.push(previous.1);
+        bond_ids.push(via_bond);
+        len += 1;
+        cursor = previous;
+    }
+    if len == 0 {
+        return None;
+    }
+    atom_ids.reverse();
+    images.reverse();
+    bond_ids.reverse();
+
+    Some(PathData {
+        atom_ids,
+        images,
         bond_ids,
         len,
     })
@@ -300,7 +909,7 @@ fn shortest_path_bfs(
 /// # Returns
 ///
 /// Count of disjoint components.
-fn count_components(num_atoms: usize, adjacency: &[Vec<(usize, GraphBondOrder)>]) -> usize {
+fn count_components(num_atoms: usize, adjacency: &AdjacencyList<(usize, GraphBondOrder)>) -> usize {
     let mut visited = vec![false; num_atoms];
     let mut components = 0;
     for i in 0..num_atoms {
@@ -381,6 +990,7 @@ impl BitVec {
     /// # Arguments
     ///
     /// * `index` - Position to inspect.
+    #[cfg(test)]
     fn test(&self, index: usize) -> bool {
         if index >= self.size {
             return false;
@@ -411,6 +1021,7 @@ mod tests {
     use super::*;
     use crate::core::graph::MolecularGraph;
     use crate::core::properties::{Element, GraphBondOrder};
+    use crate::perception::model::AnnotatedAtom;
 
     fn chain_graph(len: usize) -> MolecularGraph {
         let mut graph = MolecularGraph::new();
@@ -462,7 +1073,12 @@ mod tests {
         let chain = chain_graph(4);
         let mut molecule = AnnotatedMolecule::new(&chain).expect("graph is valid");
 
-        perceive(&mut molecule).expect("perception should succeed");
+        perceive(
+            &mut molecule,
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+        )
+        .expect("perception should succeed");
 
         assert!(
             molecule
@@ -477,7 +1093,12 @@ mod tests {
         let square = cycle_graph(4);
         let mut molecule = AnnotatedMolecule::new(&square).expect("graph is valid");
 
-        perceive(&mut molecule).expect("perception should succeed");
+        perceive(
+            &mut molecule,
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+        )
+        .expect("perception should succeed");
 
         for atom in &molecule.atoms {
             assert!(atom.is_in_ring, "atom {} should be in ring", atom.id);
@@ -490,7 +1111,12 @@ mod tests {
         let graph = fused_square_graph();
         let mut molecule = AnnotatedMolecule::new(&graph).expect("graph is valid");
 
-        perceive(&mut molecule).expect("perception should succeed");
+        perceive(
+            &mut molecule,
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+        )
+        .expect("perception should succeed");
 
         assert_eq!(molecule.rings.len(), 2, "expected two 4-cycles in basis");
         for ring in &molecule.rings {
@@ -503,6 +1129,137 @@ mod tests {
         }
     }
 
+    #[test]
+    fn perceive_records_smallest_ring_size_of_strained_rings() {
+        let sizes = |smiles: &str| {
+            let graph = crate::io::smiles::parse_smiles(smiles).unwrap();
+            let mut molecule = AnnotatedMolecule::new(&graph).unwrap();
+            perceive(
+                &mut molecule,
+                &PerceptionOptions::DEFAULT,
+                &Budget::default(),
+            )
+            .unwrap();
+            molecule
+                .atoms
+                .iter()
+                .filter(|atom| atom.element != Element::H)
+                .map(|atom| atom.smallest_ring_size)
+                .collect::<Vec<_>>()
+        };
+
+        // Oxirane, cyclopropene, and methylcyclobutane.
+        assert_eq!(sizes("C1CO1"), [Some(3); 3]);
+        assert_eq!(sizes("C1=CC1"), [Some(3); 3]);
+        assert_eq!(sizes("CC1CCC1"), [None, Some(4), Some(4), Some(4), Some(4)]);
+        // Bicyclo[2.1.0]pentane: the shared bond belongs to both rings.
+        assert_eq!(
+            sizes("C1CC2CC12"),
+            [Some(4), Some(4), Some(3), Some(3), Some(3)]
+        );
+        // Spiropentane.
+        assert_eq!(sizes("C1CC12CC2"), [Some(3); 5]);
+    }
+
+    #[test]
+    fn perceive_marks_bridgehead_and_spiro_atoms() {
+        let junctions = |smiles: &str| {
+            let graph = crate::io::smiles::parse_smiles(smiles).unwrap();
+            let mut molecule = AnnotatedMolecule::new(&graph).unwrap();
+            perceive(
+                &mut molecule,
+                &PerceptionOptions::DEFAULT,
+                &Budget::default(),
+            )
+            .unwrap();
+            let ids = |flag: fn(&AnnotatedAtom) -> bool| -> Vec<usize> {
+                molecule
+                    .atoms
+                    .iter()
+                    .filter(|atom| flag(atom))
+                    .map(|atom| atom.id)
+                    .collect()
+            };
+            (ids(|atom| atom.is_bridgehead), ids(|atom| atom.is_spiro))
+        };
+
+        // Norbornane, bicyclo[2.2.2]octane, and adamantane.
+        assert_eq!(junctions("C1CC2CCC1C2"), (vec![2, 5], vec![]));
+        assert_eq!(junctions("C12CCC(CC1)CC2"), (vec![0, 3], vec![]));
+        assert_eq!(junctions("C1C2CC3CC1CC(C2)C3"), (vec![1, 3, 5, 7], vec![]));
+        // Spiro[4.5]decane and decalin.
+        assert_eq!(junctions("C1CCC2(CC1)CCCC2"), (vec![], vec![3]));
+        assert_eq!(junctions("C1CCC2CCCCC2C1"), (vec![], vec![]));
+    }
+
+    #[test]
+    fn perceive_reports_rings_in_cyclic_order() {
+        let mut graph = MolecularGraph::new();
+        for _ in 0..5 {
+            graph.add_atom(Element::C);
+        }
+        for (u, v) in [(0, 3), (3, 1), (1, 4), (4, 2), (2, 0)] {
+            graph.add_bond(u, v, GraphBondOrder::Single).unwrap();
+        }
+        let mut molecule = AnnotatedMolecule::new(&graph).expect("graph is valid");
+
+        perceive(
+            &mut molecule,
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+        )
+        .expect("perception should succeed");
+
+        assert_eq!(molecule.rings, [vec![0, 2, 4, 1, 3]]);
+    }
+
+    #[test]
+    fn dependent_candidates_are_rejected_whatever_their_pivots() {
+        let bond_map: HashMap<usize, usize> = [(10, 0), (20, 1), (30, 2)].into_iter().collect();
+        let candidate = |bond_ids: Vec<usize>| RingCandidate {
+            atom_ids: Vec::new(),
+            images: Vec::new(),
+            len: bond_ids.len(),
+            bond_ids,
+        };
+        // The third candidate is the sum of the first two; reducing it by the pivot-1 vector
+        // before the pivot-2 vector would leave bit 1 set and accept it.
+        let candidates = vec![
+            candidate(vec![20, 30]),
+            candidate(vec![10, 20]),
+            candidate(vec![10, 30]),
+        ];
+
+        let basis = select_minimal_cycle_basis(candidates, 3, &bond_map);
+        assert_eq!(basis.len(), 2);
+    }
+
+    #[test]
+    fn ligand_cycles_skip_rings_closed_through_a_metal() {
+        // Zinc porphyrin: chelate rings displace the 16-membered ligand cycle from the SSSR.
+        let mut graph = crate::io::smiles::parse_smiles(
+            "C1=CC2=CC3=CC=C([N-]3)C=C4C=CC(=N4)C=C5C=CC(=N5)C=C1[N-]2",
+        )
+        .unwrap();
+        let zn = graph.add_atom(Element::Zn);
+        for n in [8, 14, 20, 23] {
+            graph.add_bond(zn, n, GraphBondOrder::Single).unwrap();
+        }
+        let mut molecule = AnnotatedMolecule::new(&graph).unwrap();
+        perceive(
+            &mut molecule,
+            &PerceptionOptions::DEFAULT,
+            &Budget::default(),
+        )
+        .unwrap();
+        assert!(molecule.rings.iter().any(|ring| ring.contains(&zn)));
+        assert!(molecule.rings.iter().all(|ring| ring.len() < 16));
+
+        let mut sizes: Vec<usize> = ligand_cycles(&molecule).iter().map(Vec::len).collect();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [5, 5, 5, 5, 16]);
+    }
+
     #[test]
     fn shortest_path_bfs_finds_alternative_route_when_edge_removed() {
         let triangle = cycle_graph(3);
@@ -517,8 +1274,16 @@ mod tests {
             .map(|bond| bond.id)
             .expect("triangle should contain 0-1 bond");
 
-        let path = shortest_path_bfs(&molecule, 0, 1, Some(removed_bond_id), &mut workspace)
-            .expect("path exists through third atom");
+        let path = shortest_path_bfs(
+            &molecule,
+            0,
+            1,
+            Some(removed_bond_id),
+            None,
+            false,
+            &mut workspace,
+        )
+        .expect("path exists through third atom");
 
         assert_eq!(path.len, 2);
         assert_eq!(path.atom_ids, vec![0, 2]);
@@ -527,12 +1292,15 @@ mod tests {
 
     #[test]
     fn count_components_detects_disconnected_fragments() {
-        let adjacency = vec![
-            vec![(1, GraphBondOrder::Single)],
-            vec![(0, GraphBondOrder::Single)],
-            vec![(3, GraphBondOrder::Single)],
-            vec![(2, GraphBondOrder::Single)],
-        ];
+        let adjacency = AdjacencyList::from_pairs(
+            4,
+            &[
+                (0, (1, GraphBondOrder::Single)),
+                (1, (0, GraphBondOrder::Single)),
+                (2, (3, GraphBondOrder::Single)),
+                (3, (2, GraphBondOrder::Single)),
+            ],
+        );
 
         assert_eq!(count_components(4, &adjacency), 2);
     }
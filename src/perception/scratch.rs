@@ -0,0 +1,104 @@
+//! Pools of scratch buffers reused across perception runs.
+//!
+//! Perception stages allocate working buffers sized to the molecule, and a batch of small
+//! molecules would otherwise pay for those allocations once per molecule. A [`ScratchPool`] keeps
+//! released buffers for the next run. Each run checks a buffer out for its whole duration, so
+//! threads typing molecules in parallel never share one; the lock is held only while a buffer is
+//! taken or returned.
+
+use crate::compat::prelude::*;
+use crate::compat::sync::Mutex;
+
+/// Number of idle buffers a pool keeps; more are dropped when returned.
+///
+/// One buffer per concurrently running thread suffices, so this only needs to cover the widest
+/// thread pool in use.
+const MAX_IDLE: usize = 64;
+
+/// Shared free list of scratch buffers.
+pub(crate) struct ScratchPool<T> {
+    /// Buffers not currently checked out by any run.
+    idle: Mutex<Vec<T>>,
+}
+
+impl<T> ScratchPool<T> {
+    /// Creates an empty pool.
+    pub(crate) const fn new() -> Self {
+        Self {
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Runs `f` with a buffer taken from the pool, or created by `create` when none is idle, and
+    /// returns the buffer to the pool afterwards.
+    ///
+    /// The buffer is dropped instead of returned if `f` panics, so a half-updated buffer never
+    /// reaches a later run.
+    ///
+    /// # Arguments
+    ///
+    /// * `create` - Constructor for a fresh buffer.
+    /// * `f` - Work to run with exclusive access to the buffer. It receives the buffer in
+    ///   whatever state the previous run left it and must prepare it before use.
+    pub(crate) fn with<R>(&self, create: impl FnOnce() -> T, f: impl FnOnce(&mut T) -> R) -> R {
+        let taken = self.idle.lock().pop();
+        let mut buffer = taken.unwrap_or_else(create);
+        let result = f(&mut buffer);
+        let mut idle = self.idle.lock();
+        if idle.len() < MAX_IDLE {
+            idle.push(buffer);
+        }
+        result
+    }
+
+    /// Number of idle buffers currently held.
+    #[cfg(test)]
+    fn idle_len(&self) -> usize {
+        self.idle.lock().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn released_buffers_are_reused() {
+        let pool = ScratchPool::<Vec<u8>>::new();
+        pool.with(Vec::new, |buffer| buffer.extend_from_slice(&[1, 2, 3]));
+        assert_eq!(pool.idle_len(), 1);
+
+        let reused = pool.with(
+            || unreachable!("an idle buffer exists"),
+            |buffer| buffer.len(),
+        );
+        assert_eq!(reused, 3);
+        assert_eq!(pool.idle_len(), 1);
+    }
+
+    #[test]
+    fn concurrent_runs_check_out_distinct_buffers() {
+        let pool = Arc::new(ScratchPool::<Vec<usize>>::new());
+        let handles: Vec<_> = (0..8)
+            .map(|worker| {
+                let pool = Arc::clone(&pool);
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        pool.with(Vec::new, |buffer| {
+                            buffer.clear();
+                            buffer.extend(std::iter::repeat_n(worker, 16));
+                            thread::yield_now();
+                            assert!(buffer.iter().all(|&owner| owner == worker));
+                        });
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(pool.idle_len() <= 8);
+    }
+}
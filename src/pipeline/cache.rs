@@ -0,0 +1,292 @@
+//! Memoized typing of repeated fragments.
+//!
+//! Large systems are dominated by copies of a few small molecules: solvent, counterions, and
+//! the same residues over and over. [`TypingCache`] splits every input into connected fragments,
+//! keys each one by [`MolecularGraph::structure_hash`], and types a fragment only the first time
+//! its structure is seen. Cached topologies are stored in canonical numbering and mapped back onto
+//! the atom numbering of every later copy, which receives its own stereo descriptors, since the
+//! structure hash ignores stereochemistry. Graphs that carry coordinates bypass the cache, since
+//! perception may refine their types from geometry that differs between copies.
+
+use super::{Typer, assemble, finish, merge_fragments};
+use crate::compat::collections::HashMap;
+use crate::compat::prelude::*;
+use crate::compat::sync::{Mutex, MutexGuard};
+use crate::core::canon::canonicalize;
+use crate::core::error::TyperError;
+use crate::core::graph::MolecularGraph;
+use crate::core::topology::MolecularTopology;
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Typer front-end that reuses the topology of every fragment structure it has typed before.
+///
+/// The cache takes `&self` and is `Send + Sync`, so one instance can serve all workers of a
+/// batch run. Entries are keyed by a 128-bit structure hash; a hash collision between different
+/// fragments is astronomically unlikely and is not checked for.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, Typer, TypingCache};
+///
+/// // Three waters: H-O-H, O-H-H, and H-O-H again.
+/// let mut graph = MolecularGraph::new();
+/// for oxygen_first in [false, true, false] {
+///     let (o, h1, h2) = if oxygen_first {
+///         (graph.add_atom(Element::O), graph.add_atom(Element::H), graph.add_atom(Element::H))
+///     } else {
+///         let h1 = graph.add_atom(Element::H);
+///         (graph.add_atom(Element::O), h1, graph.add_atom(Element::H))
+///     };
+///     graph.add_bond(o, h1, GraphBondOrder::Single).unwrap();
+///     graph.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+/// }
+///
+/// let cache = TypingCache::new(Typer::new());
+/// let topology = cache.assign_topology(&graph).unwrap();
+/// assert_eq!(topology.atoms[3].atom_type, "O_3");
+/// assert_eq!(topology.atoms[4].atom_type, "H_HB");
+/// assert_eq!((cache.misses(), cache.hits(), cache.len()), (1, 2, 1));
+/// ```
+pub struct TypingCache {
+    /// Typer whose settings produce every cached topology.
+    typer: Typer,
+    /// Fragment topologies in canonical atom numbering, keyed by structure hash.
+    entries: Mutex<HashMap<u128, MolecularTopology>>,
+    /// Number of fragments served from the cache.
+    hits: AtomicUsize,
+    /// Number of fragments that had to be typed.
+    misses: AtomicUsize,
+}
+
+impl TypingCache {
+    /// Creates an empty cache around a typer.
+    ///
+    /// # Arguments
+    ///
+    /// * `typer` - Typer whose rules, limits, labels, and variant apply to every fragment.
+    pub fn new(typer: Typer) -> Self {
+        Self {
+            typer,
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the typer used for cache misses.
+    pub fn typer(&self) -> &Typer {
+        &self.typer
+    }
+
+    /// Types a molecular graph, reusing cached results for fragments seen before.
+    ///
+    /// The result equals [`Typer::assign_topology`] on the same graph, up to the order of terms.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Molecular graph to type.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TyperError`] under the same conditions as [`Typer::assign_topology`]; fragments
+    /// that fail are not cached.
+    pub fn assign_topology(&self, graph: &MolecularGraph) -> Result<MolecularTopology, TyperError> {
+        let options = self.typer.run_options();
+        let components = graph.connected_components();
+        let mut fragments = Vec::with_capacity(components.len());
+        for atom_ids in &components {
+            let (subgraph, _) = graph.subgraph(atom_ids);
+            if subgraph.positions.is_some() {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                fragments.push((atom_ids.as_slice(), assemble(&subgraph, &options)?));
+                continue;
+            }
+            let order = canonicalize(&subgraph);
+            let mut canonical_position = vec![0; order.len()];
+            for (position, &atom) in order.iter().enumerate() {
+                canonical_position[atom] = position;
+            }
+
+            let key = subgraph.structure_hash();
+            let cached = self.lock().get(&key).cloned();
+            let topology = match cached {
+                Some(mut topology) => {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    topology
+                        .permute(&canonical_position)
+                        .expect("canonical positions form a permutation");
+                    copy_stereo(&mut topology, &subgraph);
+                    topology
+                }
+                None => {
+                    self.misses.fetch_add(1, Ordering::Relaxed);
+                    let topology = assemble(&subgraph, &options)?;
+                    let mut canonical = topology.clone();
+                    canonical
+                        .permute(&order)
+                        .expect("canonical order is a permutation");
+                    self.lock().insert(key, canonical);
+                    topology
+                }
+            };
+            fragments.push((atom_ids.as_slice(), topology));
+        }
+        finish(
+            merge_fragments(graph.atoms.len(), fragments),
+            graph,
+            &options,
+        )
+    }
+
+    /// Returns the number of fragments served from the cache so far.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of fragments that had to be typed so far.
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of distinct fragment structures cached.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if nothing has been cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Drops every cached topology and resets the hit and miss counters.
+    pub fn clear(&self) {
+        self.lock().clear();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// Locks the entry map, recovering it if another thread panicked while holding the lock.
+    fn lock(&self) -> MutexGuard<'_, HashMap<u128, MolecularTopology>> {
+        self.entries.lock()
+    }
+}
+
+/// Overwrites the stereo descriptors of a reused topology with those of the fragment it types.
+pub(crate) fn copy_stereo(topology: &mut MolecularTopology, graph: &MolecularGraph) {
+    for (atom, node) in topology.atoms.iter_mut().zip(&graph.atoms) {
+        atom.chirality = node.chirality;
+    }
+    let stereo: HashMap<(usize, usize), _> = graph
+        .bonds
+        .iter()
+        .map(|bond| {
+            let (a, b) = bond.atom_ids;
+            ((a.min(b), a.max(b)), bond.stereo)
+        })
+        .collect();
+    for bond in &mut topology.bonds {
+        bond.stereo = stereo.get(&bond.atom_ids).copied().flatten();
+    }
+}
+
+impl fmt::Debug for TypingCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypingCache")
+            .field("typer", &self.typer)
+            .field("entries", &self.len())
+            .field("hits", &self.hits())
+            .field("misses", &self.misses())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::properties::{Element, GraphBondOrder};
+
+    fn add_ethanol(graph: &mut MolecularGraph, reversed: bool) {
+        let elements = [Element::C, Element::C, Element::O];
+        let order: Vec<usize> = if reversed {
+            vec![2, 1, 0]
+        } else {
+            vec![0, 1, 2]
+        };
+        let mut ids = [0; 3];
+        for &i in &order {
+            ids[i] = graph.add_atom(elements[i]);
+        }
+        graph
+            .add_bond(ids[0], ids[1], GraphBondOrder::Single)
+            .unwrap();
+        graph
+            .add_bond(ids[1], ids[2], GraphBondOrder::Single)
+            .unwrap();
+        for (heavy, count) in [(ids[0], 3), (ids[1], 2), (ids[2], 1)] {
+            for _ in 0..count {
+                let h = graph.add_atom(Element::H);
+                graph.add_bond(heavy, h, GraphBondOrder::Single).unwrap();
+            }
+        }
+    }
+
+    fn sorted_terms(mut topology: MolecularTopology) -> MolecularTopology {
+        topology.sort_terms();
+        topology
+    }
+
+    #[test]
+    fn cached_fragments_match_uncached_typing() {
+        let mut graph = MolecularGraph::new();
+        add_ethanol(&mut graph, false);
+        let na = graph.add_atom(Element::Na);
+        add_ethanol(&mut graph, true);
+        let cl = graph.add_atom(Element::Cl);
+
+        let typer = Typer::new();
+        let cache = TypingCache::new(typer.clone());
+        let cached = cache.assign_topology(&graph).expect("graph should type");
+        let direct = typer.assign_topology(&graph).expect("graph should type");
+        assert_eq!(sorted_terms(cached), sorted_terms(direct));
+        assert_eq!((cache.misses(), cache.hits(), cache.len()), (3, 1, 3));
+
+        let again = cache.assign_topology(&graph).expect("graph should type");
+        assert_eq!(again.atoms[na].atom_type, "Na");
+        assert_eq!(again.atoms[cl].atom_type, "Cl");
+        assert_eq!((cache.misses(), cache.hits()), (3, 5));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.hits(), 0);
+    }
+
+    #[test]
+    fn cache_hits_keep_the_stereo_of_their_own_fragment() {
+        let graph = crate::io::smiles::parse_smiles("F[C@H](Cl)Br.F[C@@H](Cl)Br").unwrap();
+        let cache = TypingCache::new(Typer::new());
+        let topology = cache.assign_topology(&graph).expect("graph should type");
+        assert_eq!(cache.hits(), 1);
+        for atom in [1, 5] {
+            assert!(graph.atoms[atom].chirality.is_some());
+            assert_eq!(topology.atoms[atom].chirality, graph.atoms[atom].chirality);
+        }
+        assert_ne!(topology.atoms[1].chirality, topology.atoms[5].chirality);
+    }
+
+    #[test]
+    fn graphs_with_positions_bypass_the_cache() {
+        let mut graph = MolecularGraph::new();
+        add_ethanol(&mut graph, false);
+        add_ethanol(&mut graph, false);
+        let positions = (0..graph.atoms.len())
+            .map(|i| [i as f64, (i % 3) as f64, (i % 2) as f64])
+            .collect();
+        graph.set_positions(positions).unwrap();
+
+        let cache = TypingCache::new(Typer::new());
+        cache.assign_topology(&graph).expect("graph should type");
+        assert_eq!((cache.misses(), cache.hits(), cache.len()), (2, 0, 0));
+    }
+}
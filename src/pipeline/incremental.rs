@@ -0,0 +1,1160 @@
+//! Incremental re-typing of a molecular graph under small edits.
+//!
+//! An edit only changes what perception and typing see within a few bonds of the edited atoms
+//! and across the ring and π systems those atoms belong to. [`IncrementalTyper`] keeps the
+//! adjacency, the connected fragments, and the perceived state of every atom, and after each
+//! edit re-runs the pipeline on a window around the edit, cut the way
+//! [`Typer::assign_topology_for_selection`] cuts a context region: the window holds every atom
+//! within [`Typer::selection_context`] bonds of the atoms being re-typed, never cuts a multiple,
+//! hydrogen, metal, ring, or conjugated bond, and caps the bonds it does cut with hydrogens.
+//! Atoms between the re-typed atoms and the caps keep their stored types. Whenever re-typing
+//! changes an atom on the rim of the re-typed region, or finds a ring or resonance system that
+//! leaves it, the region grows and the window is typed again, until the region settles or
+//! covers whole fragments. Reactive molecular dynamics and ligand-growing workflows that mutate
+//! a few atoms per step therefore pay for the neighborhood of those atoms rather than for the
+//! fragments they belong to.
+
+use super::progress::ProgressTracker;
+use super::selection::{cap_position, copy_atom};
+use super::{RunOptions, TypedMolecule, Typer, build, finish, perceive_and_type};
+use crate::compat::collections::{HashMap, HashSet};
+use crate::compat::prelude::*;
+use crate::core::error::{GraphValidationError, TyperError};
+use crate::core::graph::MolecularGraph;
+use crate::core::limits::Budget;
+use crate::core::properties::{Element, GraphBondOrder, Hybridization};
+use crate::core::topology::{MolecularTopology, TypeProvenance};
+use crate::perception::{AnnotatedAtom, AnnotatedMolecule, ResonanceSystem};
+use alloc::collections::VecDeque;
+
+/// Part of the graph cut out for re-typing, with capping hydrogens in place of cut neighbors.
+struct Window {
+    /// Window graph; atoms keep the relative order of the atoms they stand for.
+    graph: MolecularGraph,
+    /// Graph ID of every window atom; a capping hydrogen maps to the atom it replaces.
+    origin: Vec<usize>,
+    /// Whether each window atom is a capping hydrogen.
+    is_cap: Vec<bool>,
+    /// Graph ID of every window bond; `None` for the bonds to capping hydrogens.
+    bond_origin: Vec<Option<usize>>,
+    /// Whether any bond leaves the window.
+    is_cut: bool,
+}
+
+/// Re-perceived state of a region of the graph, ready to replace the stored state.
+struct Patch {
+    /// Atoms whose stored state the patch replaces, in ascending order.
+    atom_ids: Vec<usize>,
+    /// Window the state was perceived on.
+    window: Window,
+    /// Perceived and typed window.
+    typed: TypedMolecule,
+}
+
+/// Typer that keeps a graph typed while bonds and elements are edited.
+///
+/// Every edit is transactional: if re-typing fails, the error is returned and both the graph
+/// and the assigned types stay as they were before the edit.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{Element, GraphBondOrder, IncrementalTyper, MolecularGraph, Typer};
+///
+/// // Methane and a separate water molecule.
+/// let mut graph = MolecularGraph::new();
+/// let c = graph.add_atom(Element::C);
+/// for _ in 0..4 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+/// }
+/// let o = graph.add_atom(Element::O);
+/// for _ in 0..2 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+/// }
+///
+/// let mut typer = IncrementalTyper::new(Typer::new(), graph).unwrap();
+/// assert_eq!(typer.atom_types()[c], "C_3");
+///
+/// // Turning the carbon into silicon only re-types the methane fragment.
+/// typer.mutate_element(c, Element::Si).unwrap();
+/// assert_eq!(typer.atom_types()[c], "Si3");
+/// assert_eq!(typer.last_retyped(), [0, 1, 2, 3, 4]);
+/// assert_eq!(typer.topology().unwrap().atoms[o].atom_type, "O_3");
+/// ```
+pub struct IncrementalTyper {
+    /// Typer whose settings drive every re-typing run.
+    typer: Typer,
+    /// Current molecular graph.
+    graph: MolecularGraph,
+    /// Neighbors of every atom, each paired with the ID of the bond leading to it.
+    neighbors: Vec<Vec<(usize, usize)>>,
+    /// Label of the connected fragment holding each atom.
+    fragment_of: Vec<usize>,
+    /// Number of atoms carrying each fragment label; zero for unused labels.
+    fragment_sizes: Vec<usize>,
+    /// Unused fragment labels, available for fragments split off by a bond removal.
+    free_labels: Vec<usize>,
+    /// Perceived annotations of every atom, with graph IDs.
+    atoms: Vec<AnnotatedAtom>,
+    /// Perceived order of every bond, after Kekulé expansion.
+    bond_orders: Vec<GraphBondOrder>,
+    /// Whether each bond was given as aromatic in the graph.
+    aromatic_input: Vec<bool>,
+    /// Rings of every fragment, in the cyclic order perception reports them.
+    rings: Vec<Vec<usize>>,
+    /// Cycles of every fragment kept as macrocycle envelopes.
+    macrocycles: Vec<Vec<usize>>,
+    /// Resonance systems of every fragment, with graph atom and bond IDs.
+    resonance_systems: Vec<ResonanceSystem>,
+    /// Canonical atom type of every atom.
+    atom_types: Vec<String>,
+    /// Origin of every atom type.
+    provenance: Vec<TypeProvenance>,
+    /// Atoms re-typed by the most recent edit.
+    last_retyped: Vec<usize>,
+}
+
+impl IncrementalTyper {
+    /// Types every fragment of `graph` and keeps the results for later edits.
+    ///
+    /// # Arguments
+    ///
+    /// * `typer` - Typer whose rules, limits, labels, and variant apply to every run.
+    /// * `graph` - Initial molecular graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TyperError`] if any fragment fails to type.
+    pub fn new(typer: Typer, graph: MolecularGraph) -> Result<Self, TyperError> {
+        let atoms = AnnotatedMolecule::new(&graph)
+            .map_err(TyperError::InvalidInput)?
+            .atoms;
+        let n = graph.atoms.len();
+        let mut neighbors = vec![Vec::new(); n];
+        for bond in &graph.bonds {
+            let (a, b) = bond.atom_ids;
+            neighbors[a].push((b, bond.id));
+            neighbors[b].push((a, bond.id));
+        }
+
+        let mut incremental = Self {
+            typer,
+            neighbors,
+            fragment_of: vec![usize::MAX; n],
+            fragment_sizes: Vec::new(),
+            free_labels: Vec::new(),
+            atoms,
+            bond_orders: graph.bonds.iter().map(|bond| bond.order).collect(),
+            aromatic_input: vec![false; graph.bonds.len()],
+            rings: Vec::new(),
+            macrocycles: Vec::new(),
+            resonance_systems: Vec::new(),
+            atom_types: vec![String::new(); n],
+            provenance: vec![TypeProvenance::Rule; n],
+            last_retyped: Vec::new(),
+            graph,
+        };
+        for start in 0..n {
+            if incremental.fragment_of[start] == usize::MAX {
+                let label = incremental.fragment_sizes.len();
+                incremental.fragment_sizes.push(0);
+                incremental.relabel(start, usize::MAX, label);
+            }
+        }
+
+        let all_atoms: Vec<usize> = (0..n).collect();
+        let options = incremental.typer.run_options();
+        let patches = incremental.retype_fragments(&all_atoms, &options, &options.budget())?;
+        incremental.apply(patches);
+        Ok(incremental)
+    }
+
+    /// Returns the current molecular graph.
+    pub fn graph(&self) -> &MolecularGraph {
+        &self.graph
+    }
+
+    /// Returns the canonical atom type of every atom, indexed by atom ID.
+    ///
+    /// These are the types before any output label map of the typer is applied.
+    pub fn atom_types(&self) -> &[String] {
+        &self.atom_types
+    }
+
+    /// Returns the IDs of the atoms re-typed by the most recent edit, in ascending order.
+    pub fn last_retyped(&self) -> &[usize] {
+        &self.last_retyped
+    }
+
+    /// Adds a bond and re-types the neighborhood of both atoms.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom1_id` - Identifier of the first atom.
+    /// * `atom2_id` - Identifier of the second atom.
+    /// * `order` - Bond multiplicity to record.
+    ///
+    /// # Returns
+    ///
+    /// The identifier assigned to the new bond.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TyperError::InvalidInput`] if the bond is invalid, or any other
+    /// [`TyperError`] if the atoms around it fail to type.
+    pub fn add_bond(
+        &mut self,
+        atom1_id: usize,
+        atom2_id: usize,
+        order: GraphBondOrder,
+    ) -> Result<usize, TyperError> {
+        let bond_id = self.graph.add_bond(atom1_id, atom2_id, order)?;
+        self.neighbors[atom1_id].push((atom2_id, bond_id));
+        self.neighbors[atom2_id].push((atom1_id, bond_id));
+
+        // A bond inside one fragment closes a ring through the path it shortcuts.
+        let mut seeds = vec![atom1_id, atom2_id];
+        if self.fragment_of[atom1_id] == self.fragment_of[atom2_id] {
+            seeds.extend(self.shortest_path(atom1_id, atom2_id, bond_id));
+        }
+
+        match self.retype(&seeds, &|id| (id < bond_id).then_some(id)) {
+            Ok(patches) => {
+                self.bond_orders.push(order);
+                self.aromatic_input.push(false);
+                self.join_fragments(atom1_id, atom2_id);
+                self.apply(patches);
+                Ok(bond_id)
+            }
+            Err(error) => {
+                self.neighbors[atom1_id].pop();
+                self.neighbors[atom2_id].pop();
+                self.graph.bonds.pop();
+                Err(error)
+            }
+        }
+    }
+
+    /// Removes a bond and re-types the neighborhood of its atoms, whose fragment may split in
+    /// two.
+    ///
+    /// Bonds after the removed one shift down by one, as in [`MolecularGraph::remove_bond`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bond_id` - Identifier of the bond to remove.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TyperError::InvalidInput`] if no bond has this ID, or any other
+    /// [`TyperError`] if the atoms around it fail to type.
+    pub fn remove_bond(&mut self, bond_id: usize) -> Result<(), TyperError> {
+        let removed = self.graph.remove_bond(bond_id)?;
+        let (a, b) = removed.atom_ids;
+        for atom in [a, b] {
+            self.neighbors[atom].retain(|&(_, id)| id != bond_id);
+        }
+        self.shift_bond_ids(bond_id, |id| id - 1);
+
+        match self.retype(&[a, b], &|id| Some(if id < bond_id { id } else { id + 1 })) {
+            Ok(patches) => {
+                self.bond_orders.remove(bond_id);
+                self.aromatic_input.remove(bond_id);
+                for system in &mut self.resonance_systems {
+                    system.bond_ids.retain(|&id| id != bond_id);
+                    for id in &mut system.bond_ids {
+                        if *id > bond_id {
+                            *id -= 1;
+                        }
+                    }
+                }
+                self.split_fragments(a, b);
+                self.apply(patches);
+                Ok(())
+            }
+            Err(error) => {
+                self.shift_bond_ids(bond_id, |id| id + 1);
+                self.neighbors[a].push((b, bond_id));
+                self.neighbors[b].push((a, bond_id));
+                self.graph.bonds.insert(bond_id, removed);
+                for bond in &mut self.graph.bonds[bond_id + 1..] {
+                    bond.id += 1;
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Changes the element of an atom and re-types its neighborhood.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_id` - Identifier of the atom to change.
+    /// * `element` - New element of the atom.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TyperError::InvalidInput`] if the atom does not exist, or any other
+    /// [`TyperError`] if the atoms around it fail to type.
+    pub fn mutate_element(&mut self, atom_id: usize, element: Element) -> Result<(), TyperError> {
+        let node = self
+            .graph
+            .atoms
+            .get_mut(atom_id)
+            .ok_or(GraphValidationError::MissingAtom { atom_id })?;
+        let previous = core::mem::replace(&mut node.element, element);
+
+        match self.retype(&[atom_id], &Some) {
+            Ok(patches) => {
+                self.apply(patches);
+                Ok(())
+            }
+            Err(error) => {
+                self.graph.atoms[atom_id].element = previous;
+                Err(error)
+            }
+        }
+    }
+
+    /// Builds the topology of the whole graph from the stored perceived state.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TyperError::LabelMapping`] if the typer's label map does not cover an assigned
+    /// type.
+    pub fn topology(&self) -> Result<MolecularTopology, TyperError> {
+        let options = self.typer.run_options();
+        let mut molecule =
+            AnnotatedMolecule::new(&self.graph).expect("the graph was validated on construction");
+        molecule.atoms.clone_from(&self.atoms);
+        for (bond_id, &order) in self.bond_orders.iter().enumerate() {
+            if molecule.bonds[bond_id].order != order {
+                molecule.set_bond_order(bond_id, order);
+            }
+        }
+        molecule.aromatic_input_bonds = (0..self.aromatic_input.len())
+            .filter(|&id| self.aromatic_input[id])
+            .collect();
+        molecule.rings.clone_from(&self.rings);
+        molecule.macrocycles.clone_from(&self.macrocycles);
+        molecule
+            .resonance_systems
+            .clone_from(&self.resonance_systems);
+
+        let typed = TypedMolecule {
+            molecule,
+            atom_types: self.atom_types.clone(),
+            provenance: self.provenance.clone(),
+        };
+        let mut topology = build(&typed, &options);
+
+        // Number fragments in order of their lowest atom ID, as a full run does.
+        let mut fragment_ids = HashMap::new();
+        for atom in &mut topology.atoms {
+            let next = fragment_ids.len();
+            atom.fragment_id = *fragment_ids
+                .entry(self.fragment_of[atom.id])
+                .or_insert(next);
+        }
+        topology.hb_donors.sort_unstable_by_key(|d| d.hydrogen_id);
+        topology.hb_acceptors.sort_unstable();
+        finish(topology, &self.graph, &options)
+    }
+
+    /// Re-perceives the region around `seeds` until the atoms outside it are unaffected.
+    ///
+    /// The core of the region starts as `seeds` plus the bonds that must not be cut around
+    /// them; the re-typed atoms are those within the context depth of the core, and the window
+    /// adds as much context again. Atoms of the window outside the re-typed region are pinned
+    /// to their stored types. Every re-typed atom outside the core whose state changes, and
+    /// every ring or resonance system leaving the re-typed region, joins the core for another
+    /// round. Custom perception pipelines and whole-fragment options re-type whole fragments.
+    ///
+    /// # Arguments
+    ///
+    /// * `seeds` - Atoms touched by the edit, in the edited graph.
+    /// * `stored_bond` - Maps a bond ID of the edited graph to the ID its stored state is kept
+    ///   under, or `None` for a new bond.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`TyperError`] of typing the fragments of `seeds` whole, if a window fails.
+    fn retype(
+        &self,
+        seeds: &[usize],
+        stored_bond: &dyn Fn(usize) -> Option<usize>,
+    ) -> Result<Vec<Patch>, TyperError> {
+        let options = self.typer.run_options();
+        let budget = options.budget();
+        if !options.pipeline.is_standard() || options.perception.skip_inorganic_conjugation {
+            return self.retype_fragments(seeds, &options, &budget);
+        }
+
+        let context = self.typer.selection_context().max(1);
+        let mut core = self.expand(seeds, 0);
+        loop {
+            let retyped = self.expand(&core, context);
+            let window = self.cut(&self.expand(&retyped, context));
+            if !window.is_cut {
+                let typed = self.perceive(&window, &[], &options, &budget)?;
+                let atom_ids = window.origin.clone();
+                return Ok(vec![Patch {
+                    atom_ids,
+                    window,
+                    typed,
+                }]);
+            }
+
+            let in_core: HashSet<usize> = core.iter().copied().collect();
+            let in_retyped: HashSet<usize> = retyped.iter().copied().collect();
+            let pinned: Vec<Option<String>> = window
+                .origin
+                .iter()
+                .map(|&id| (!in_retyped.contains(&id)).then(|| self.atom_types[id].clone()))
+                .collect();
+            let Ok(typed) = self.perceive(&window, &pinned, &options, &budget) else {
+                return self.retype_fragments(seeds, &options, &budget);
+            };
+
+            let unsettled = self.unsettled(&window, &typed, &in_retyped, stored_bond);
+            let grown: Vec<usize> = unsettled
+                .into_iter()
+                .filter(|id| !in_core.contains(id))
+                .collect();
+            if grown.is_empty() {
+                return Ok(vec![Patch {
+                    atom_ids: retyped,
+                    window,
+                    typed,
+                }]);
+            }
+            core.extend(grown);
+            core.sort_unstable();
+            core.dedup();
+        }
+    }
+
+    /// Re-types the whole fragments holding `seeds`.
+    fn retype_fragments(
+        &self,
+        seeds: &[usize],
+        options: &RunOptions<'_>,
+        budget: &Budget,
+    ) -> Result<Vec<Patch>, TyperError> {
+        let mut seen = HashSet::new();
+        let mut patches = Vec::new();
+        for &start in seeds {
+            if !seen.insert(start) {
+                continue;
+            }
+            let mut atom_ids = vec![start];
+            let mut cursor = 0;
+            while let Some(&atom) = atom_ids.get(cursor) {
+                cursor += 1;
+                for &(neighbor, _) in &self.neighbors[atom] {
+                    if seen.insert(neighbor) {
+                        atom_ids.push(neighbor);
+                    }
+                }
+            }
+            atom_ids.sort_unstable();
+
+            let window = self.cut(&atom_ids);
+            let typed = self.perceive(&window, &[], options, budget)?;
+            patches.push(Patch {
+                atom_ids,
+                window,
+                typed,
+            });
+        }
+        Ok(patches)
+    }
+
+    /// Perceives and types a window, reporting errors with graph atom IDs.
+    fn perceive(
+        &self,
+        window: &Window,
+        pinned: &[Option<String>],
+        options: &RunOptions<'_>,
+        budget: &Budget,
+    ) -> Result<TypedMolecule, TyperError> {
+        let options = RunOptions { pinned, ..*options };
+        let tracker = ProgressTracker::new(options.observer, window.graph.atoms.len());
+        perceive_and_type(&window.graph, &options, budget, &tracker).map_err(|mut error| {
+            error.map_atom_ids(&|id| window.origin[id]);
+            error
+        })
+    }
+
+    /// Returns the atoms whose perceived state in the window differs from the stored state.
+    ///
+    /// Covers the re-typed atoms whose annotations, type, or provenance changed, the atoms of
+    /// bonds touching the re-typed region whose perceived order changed, and the atoms of rings
+    /// and resonance systems that touch the re-typed region without lying inside it.
+    fn unsettled(
+        &self,
+        window: &Window,
+        typed: &TypedMolecule,
+        retyped: &HashSet<usize>,
+        stored_bond: &dyn Fn(usize) -> Option<usize>,
+    ) -> Vec<usize> {
+        let mut unsettled = Vec::new();
+        for (local, atom) in typed.molecule.atoms.iter().enumerate() {
+            let id = window.origin[local];
+            if window.is_cap[local] || !retyped.contains(&id) {
+                continue;
+            }
+            let annotation = AnnotatedAtom { id, ..atom.clone() };
+            if annotation != self.atoms[id]
+                || typed.atom_types[local] != self.atom_types[id]
+                || typed.provenance[local] != self.provenance[id]
+            {
+                unsettled.push(id);
+            }
+        }
+
+        let aromatic: HashSet<usize> = typed
+            .molecule
+            .aromatic_input_bonds
+            .iter()
+            .copied()
+            .collect();
+        for (local, bond) in typed.molecule.bonds.iter().enumerate() {
+            let Some(bond_id) = window.bond_origin[local] else {
+                continue;
+            };
+            let (a, b) = self.graph.bonds[bond_id].atom_ids;
+            if !retyped.contains(&a) && !retyped.contains(&b) {
+                continue;
+            }
+            let stored =
+                stored_bond(bond_id).map(|id| (self.bond_orders[id], self.aromatic_input[id]));
+            if stored != Some((bond.order, aromatic.contains(&local))) {
+                unsettled.extend([a, b]);
+            }
+        }
+
+        let systems = typed
+            .molecule
+            .rings
+            .iter()
+            .chain(&typed.molecule.macrocycles)
+            .chain(typed.molecule.resonance_systems.iter().map(|s| &s.atom_ids));
+        for system in systems {
+            let touches = system.iter().any(|&l| retyped.contains(&window.origin[l]));
+            let leaves = system.iter().any(|&l| !retyped.contains(&window.origin[l]));
+            if touches && leaves {
+                unsettled.extend(system.iter().map(|&l| window.origin[l]));
+            }
+        }
+        unsettled
+    }
+
+    /// Replaces the stored state of every patched atom and of the bonds and systems among them.
+    fn apply(&mut self, patches: Vec<Patch>) {
+        let mut last_retyped = Vec::new();
+        for Patch {
+            atom_ids,
+            window,
+            typed,
+        } in patches
+        {
+            let patched: HashSet<usize> = atom_ids.iter().copied().collect();
+            let origin = |local: usize| window.origin[local];
+
+            for (local, atom) in typed.molecule.atoms.into_iter().enumerate() {
+                let id = origin(local);
+                if window.is_cap[local] || !patched.contains(&id) {
+                    continue;
+                }
+                self.atoms[id] = AnnotatedAtom { id, ..atom };
+                self.atom_types[id].clone_from(&typed.atom_types[local]);
+                self.provenance[id] = typed.provenance[local];
+            }
+
+            for (local, bond) in typed.molecule.bonds.iter().enumerate() {
+                let Some(bond_id) = window.bond_origin[local] else {
+                    continue;
+                };
+                let (a, b) = self.graph.bonds[bond_id].atom_ids;
+                if patched.contains(&a) && patched.contains(&b) {
+                    self.bond_orders[bond_id] = bond.order;
+                    self.aromatic_input[bond_id] =
+                        typed.molecule.aromatic_input_bonds.contains(&local);
+                }
+            }
+
+            let touches = |ids: &[usize]| ids.iter().any(|id| patched.contains(id));
+            let to_graph = |ids: &[usize]| ids.iter().map(|&l| origin(l)).collect::<Vec<_>>();
+            self.rings.retain(|ring| !touches(ring));
+            self.rings.extend(
+                typed
+                    .molecule
+                    .rings
+                    .iter()
+                    .map(|ring| to_graph(ring))
+                    .filter(|ring| touches(ring)),
+            );
+            self.macrocycles.retain(|ring| !touches(ring));
+            self.macrocycles.extend(
+                typed
+                    .molecule
+                    .macrocycles
+                    .iter()
+                    .map(|ring| to_graph(ring))
+                    .filter(|ring| touches(ring)),
+            );
+            self.resonance_systems
+                .retain(|system| !touches(&system.atom_ids));
+            self.resonance_systems.extend(
+                typed
+                    .molecule
+                    .resonance_systems
+                    .iter()
+                    .map(|system| ResonanceSystem {
+                        atom_ids: to_graph(&system.atom_ids),
+                        bond_ids: system
+                            .bond_ids
+                            .iter()
+                            .filter_map(|&l| window.bond_origin[l])
+                            .collect(),
+                    })
+                    .filter(|system| touches(&system.atom_ids)),
+            );
+            last_retyped.extend(atom_ids);
+        }
+        last_retyped.sort_unstable();
+        self.last_retyped = last_retyped;
+    }
+
+    /// Returns the atoms within `depth` bonds of `start`, closed over bonds that must not be cut.
+    ///
+    /// As for a selection's context region, a metal center reached at the full depth is not
+    /// expanded further.
+    fn expand(&self, start: &[usize], depth: usize) -> Vec<usize> {
+        let mut depth_of: HashMap<usize, usize> = start.iter().map(|&id| (id, 0)).collect();
+        let mut queue: VecDeque<usize> = start.iter().copied().collect();
+        while let Some(atom) = queue.pop_front() {
+            let atom_depth = depth_of[&atom];
+            if self.graph.atoms[atom].element.is_metal_center()
+                && atom_depth > 0
+                && atom_depth >= depth
+            {
+                continue;
+            }
+            for &(neighbor, bond_id) in &self.neighbors[atom] {
+                if depth_of.contains_key(&neighbor) {
+                    continue;
+                }
+                if atom_depth < depth || self.holds_together(atom, neighbor, bond_id) {
+                    depth_of.insert(neighbor, atom_depth + 1);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        let mut atoms: Vec<usize> = depth_of.into_keys().collect();
+        atoms.sort_unstable();
+        atoms
+    }
+
+    /// Returns whether the bond from `atom` to `neighbor` must stay inside any window holding
+    /// `atom`.
+    ///
+    /// Beyond the multiple, hydrogen, and metal bonds a selection never cuts, this keeps dummy
+    /// bonds, bonds between ring atoms, and bonds between π atoms, judged by the stored state,
+    /// so rings and conjugated systems are always perceived whole.
+    fn holds_together(&self, atom: usize, neighbor: usize, bond_id: usize) -> bool {
+        let is_pi = |atom: &AnnotatedAtom| {
+            atom.is_aromatic
+                || atom.is_resonant
+                || matches!(
+                    atom.hybridization,
+                    Hybridization::SP | Hybridization::SP2 | Hybridization::Resonant
+                )
+        };
+        let element = self.graph.atoms[neighbor].element;
+        let (stored, stored_neighbor) = (&self.atoms[atom], &self.atoms[neighbor]);
+        self.graph.bonds[bond_id].order != GraphBondOrder::Single
+            || element == Element::H
+            || element.is_metal_center()
+            || element.is_dummy()
+            || self.graph.atoms[atom].element.is_dummy()
+            || (stored.is_in_ring && stored_neighbor.is_in_ring)
+            || (is_pi(stored) && is_pi(stored_neighbor))
+    }
+
+    /// Cuts the window holding `atom_ids`, sorted, and caps the bonds leaving it.
+    fn cut(&self, atom_ids: &[usize]) -> Window {
+        let members: HashSet<usize> = atom_ids.iter().copied().collect();
+        let mut is_cut = false;
+        let mut entries: Vec<(usize, Option<usize>)> = Vec::new();
+        for &atom in atom_ids {
+            entries.push((atom, None));
+            let is_metal = self.graph.atoms[atom].element.is_metal_center();
+            for &(neighbor, _) in &self.neighbors[atom] {
+                if !members.contains(&neighbor) {
+                    is_cut = true;
+                    if !is_metal {
+                        entries.push((neighbor, Some(atom)));
+                    }
+                }
+            }
+        }
+        entries.sort_unstable();
+
+        let mut graph = MolecularGraph::new();
+        let mut local = HashMap::new();
+        let mut origin = Vec::with_capacity(entries.len());
+        let mut is_cap = Vec::with_capacity(entries.len());
+        let mut caps = Vec::new();
+        for &(atom, capped) in &entries {
+            match capped {
+                None => {
+                    local.insert(atom, copy_atom(&mut graph, &self.graph.atoms[atom]));
+                }
+                Some(parent) => caps.push((graph.add_atom(Element::H), parent)),
+            }
+            origin.push(atom);
+            is_cap.push(capped.is_some());
+        }
+
+        let mut bond_ids: Vec<usize> = atom_ids
+            .iter()
+            .flat_map(|&atom| {
+                self.neighbors[atom]
+                    .iter()
+                    .filter(move |&&(neighbor, _)| atom < neighbor)
+                    .map(|&(_, bond_id)| bond_id)
+            })
+            .filter(|&bond_id| {
+                let (a, b) = self.graph.bonds[bond_id].atom_ids;
+                members.contains(&a) && members.contains(&b)
+            })
+            .collect();
+        bond_ids.sort_unstable();
+        for &bond_id in &bond_ids {
+            let bond = &self.graph.bonds[bond_id];
+            let (a, b) = bond.atom_ids;
+            let id = graph
+                .add_bond(local[&a], local[&b], bond.order)
+                .expect("endpoints exist in the window");
+            graph.bonds[id].stereo = bond.stereo;
+        }
+        let mut bond_origin: Vec<Option<usize>> = bond_ids.into_iter().map(Some).collect();
+        for (cap, parent) in caps {
+            graph
+                .add_bond(local[&parent], cap, GraphBondOrder::Single)
+                .expect("endpoints exist in the window");
+            bond_origin.push(None);
+        }
+        graph.positions = self.graph.positions.as_ref().and_then(|coords| {
+            entries
+                .iter()
+                .map(|&(atom, capped)| match capped {
+                    None => coords.get(atom).copied(),
+                    Some(parent) => Some(cap_position(*coords.get(parent)?, *coords.get(atom)?)),
+                })
+                .collect()
+        });
+
+        Window {
+            graph,
+            origin,
+            is_cap,
+            bond_origin,
+            is_cut,
+        }
+    }
+
+    /// Returns the atoms strictly between `from` and `to` on a shortest path avoiding
+    /// `skipped_bond`.
+    fn shortest_path(&self, from: usize, to: usize, skipped_bond: usize) -> Vec<usize> {
+        let mut parent: HashMap<usize, usize> = HashMap::from([(from, from)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(atom) = queue.pop_front() {
+            if atom == to {
+                break;
+            }
+            for &(neighbor, bond_id) in &self.neighbors[atom] {
+                if bond_id != skipped_bond && !parent.contains_key(&neighbor) {
+                    parent.insert(neighbor, atom);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut atom = parent[&to];
+        while atom != from {
+            path.push(atom);
+            atom = parent[&atom];
+        }
+        path
+    }
+
+    /// Applies `shift` to every stored neighbor bond ID at or above `bond_id`.
+    fn shift_bond_ids(&mut self, bond_id: usize, shift: impl Fn(usize) -> usize) {
+        for neighbors in &mut self.neighbors {
+            for (_, id) in neighbors.iter_mut() {
+                if *id >= bond_id {
+                    *id = shift(*id);
+                }
+            }
+        }
+    }
+
+    /// Merges the fragments of two atoms that a new bond joins, relabeling the smaller one.
+    fn join_fragments(&mut self, a: usize, b: usize) {
+        let (label_a, label_b) = (self.fragment_of[a], self.fragment_of[b]);
+        if label_a == label_b {
+            return;
+        }
+        let (start, from, to) = if self.fragment_sizes[label_a] < self.fragment_sizes[label_b] {
+            (a, label_a, label_b)
+        } else {
+            (b, label_b, label_a)
+        };
+        self.relabel(start, from, to);
+        self.free_labels.push(from);
+    }
+
+    /// Splits off the fragment of `a` or `b` after the bond between them is removed, if they
+    /// are no longer connected.
+    ///
+    /// Both searches advance one atom at a time, so the cost follows the smaller side.
+    fn split_fragments(&mut self, a: usize, b: usize) {
+        let mut seen: [HashSet<usize>; 2] = [HashSet::from([a]), HashSet::from([b])];
+        let mut queues = [VecDeque::from([a]), VecDeque::from([b])];
+        loop {
+            for side in 0..2 {
+                let Some(atom) = queues[side].pop_front() else {
+                    let label = self.free_labels.pop().unwrap_or_else(|| {
+                        self.fragment_sizes.push(0);
+                        self.fragment_sizes.len() - 1
+                    });
+                    let start = [a, b][side];
+                    self.relabel(start, self.fragment_of[start], label);
+                    return;
+                };
+                for &(neighbor, _) in &self.neighbors[atom] {
+                    if seen[1 - side].contains(&neighbor) {
+                        return;
+                    }
+                    if seen[side].insert(neighbor) {
+                        queues[side].push_back(neighbor);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Moves the atoms labeled `from` that are connected to `start` to label `to`.
+    fn relabel(&mut self, start: usize, from: usize, to: usize) {
+        self.fragment_of[start] = to;
+        let mut queue = VecDeque::from([start]);
+        let mut moved = 0;
+        while let Some(atom) = queue.pop_front() {
+            moved += 1;
+            for &(neighbor, _) in &self.neighbors[atom] {
+                if self.fragment_of[neighbor] == from {
+                    self.fragment_of[neighbor] = to;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        if let Some(size) = self.fragment_sizes.get_mut(from) {
+            *size -= moved;
+        }
+        self.fragment_sizes[to] += moved;
+    }
+}
+
+impl core::fmt::Debug for IncrementalTyper {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("IncrementalTyper")
+            .field("typer", &self.typer)
+            .field("atoms", &self.graph.atoms.len())
+            .field(
+                "fragments",
+                &self.fragment_sizes.iter().filter(|&&size| size > 0).count(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::smiles::parse_smiles;
+    use crate::typing::rules::parse_rules;
+
+    /// Ethylene (atoms 0-5) followed by a separate water molecule (atoms 6-8).
+    fn ethylene_and_water() -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        graph.add_bond(c1, c2, GraphBondOrder::Double).unwrap();
+        for carbon in [c1, c2] {
+            for _ in 0..2 {
+                let h = graph.add_atom(Element::H);
+                graph.add_bond(carbon, h, GraphBondOrder::Single).unwrap();
+            }
+        }
+        let o = graph.add_atom(Element::O);
+        for _ in 0..2 {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+        }
+        graph
+    }
+
+    /// Sorts every term list so topologies built in different orders compare equal.
+    fn normalized(mut topology: MolecularTopology) -> MolecularTopology {
+        topology.bonds.sort_by_key(|b| b.atom_ids);
+        topology.angles.sort_by_key(|a| a.atom_ids);
+        topology.torsions.sort_by_key(|t| t.atom_ids);
+        topology.inversions.sort_by_key(|i| i.atom_ids);
+        topology
+    }
+
+    fn assert_matches_full_run(incremental: &IncrementalTyper) {
+        let full = incremental
+            .typer
+            .assign_topology(incremental.graph())
+            .expect("full run should succeed");
+        assert_eq!(
+            normalized(incremental.topology().expect("topology should build")),
+            normalized(full)
+        );
+    }
+
+    fn bond_between(incremental: &IncrementalTyper, a: usize, b: usize) -> usize {
+        incremental
+            .graph()
+            .bonds
+            .iter()
+            .position(|bond| bond.atom_ids == (a, b) || bond.atom_ids == (b, a))
+            .expect("atoms should be bonded")
+    }
+
+    #[test]
+    fn edits_retype_only_affected_fragments() {
+        let mut typer = IncrementalTyper::new(Typer::new(), ethylene_and_water()).unwrap();
+        assert_eq!(typer.last_retyped(), (0..9).collect::<Vec<_>>());
+        assert_matches_full_run(&typer);
+
+        typer.remove_bond(0).unwrap();
+        assert_eq!(typer.last_retyped(), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(typer.atom_types()[0], "C_3");
+        assert_matches_full_run(&typer);
+
+        let bond = typer.add_bond(0, 1, GraphBondOrder::Double).unwrap();
+        assert_eq!(bond, typer.graph().bonds.len() - 1);
+        assert_eq!(typer.last_retyped(), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(typer.atom_types()[0], "C_2");
+        assert_matches_full_run(&typer);
+
+        typer.mutate_element(6, Element::S).unwrap();
+        assert_eq!(typer.last_retyped(), [6, 7, 8]);
+        assert_eq!(typer.atom_types()[6], "S_3");
+        assert_matches_full_run(&typer);
+
+        let c2_h5 = bond_between(&typer, 1, 5);
+        typer.remove_bond(c2_h5).unwrap();
+        assert_eq!(typer.last_retyped(), [0, 1, 2, 3, 4, 5]);
+        typer.add_bond(1, 6, GraphBondOrder::Single).unwrap();
+        assert_eq!(typer.last_retyped(), [0, 1, 2, 3, 4, 6, 7, 8]);
+        assert_matches_full_run(&typer);
+        typer.mutate_element(5, Element::F).unwrap();
+        assert_eq!(typer.last_retyped(), [5]);
+        assert_matches_full_run(&typer);
+    }
+
+    #[test]
+    fn edits_retype_only_the_neighborhood_of_large_fragments() {
+        let graph = parse_smiles("CCCCCCCCCCCCCCCCCCCC").unwrap();
+        let mut typer = IncrementalTyper::new(Typer::new(), graph).unwrap();
+
+        // Turning the first methyl group into a fluoromethyl group.
+        let h = typer.neighbors[0]
+            .iter()
+            .map(|&(neighbor, _)| neighbor)
+            .find(|&neighbor| typer.graph().atoms[neighbor].element == Element::H)
+            .unwrap();
+        typer.mutate_element(h, Element::F).unwrap();
+        assert!(typer.last_retyped().contains(&0));
+        assert!(!typer.last_retyped().contains(&19));
+        assert_eq!(typer.atom_types()[h], "F_");
+        assert_matches_full_run(&typer);
+
+        // Swapping a methylene group in the middle for an ether oxygen.
+        let c10_h: Vec<usize> = typer.neighbors[10]
+            .iter()
+            .map(|&(neighbor, _)| neighbor)
+            .filter(|&neighbor| typer.graph().atoms[neighbor].element == Element::H)
+            .collect();
+        for &h in &c10_h {
+            typer.remove_bond(bond_between(&typer, 10, h)).unwrap();
+        }
+        typer.mutate_element(10, Element::O).unwrap();
+        assert!(!typer.last_retyped().contains(&0));
+        assert!(!typer.last_retyped().contains(&19));
+        assert_eq!(typer.atom_types()[10], "O_3");
+        assert_matches_full_run(&typer);
+    }
+
+    #[test]
+    fn ring_closures_and_openings_match_a_full_run() {
+        // Hexadecane, closed into decylcyclohexane between its first and sixth carbon and
+        // opened again.
+        let graph = parse_smiles("CCCCCCCCCCCCCCCC").unwrap();
+        let mut typer = IncrementalTyper::new(Typer::new(), graph).unwrap();
+        let hydrogen_on = |typer: &IncrementalTyper, carbon: usize| {
+            typer.neighbors[carbon]
+                .iter()
+                .map(|&(neighbor, _)| neighbor)
+                .find(|&neighbor| typer.graph().atoms[neighbor].element == Element::H)
+                .unwrap()
+        };
+        for carbon in [0, 5] {
+            let h = hydrogen_on(&typer, carbon);
+            typer.remove_bond(bond_between(&typer, carbon, h)).unwrap();
+        }
+        let closure = typer.add_bond(0, 5, GraphBondOrder::Single).unwrap();
+        assert!(typer.last_retyped().contains(&3));
+        assert!(!typer.last_retyped().contains(&15));
+        assert_matches_full_run(&typer);
+        let topology = typer.topology().unwrap();
+        assert_eq!(topology.rings.len(), 1);
+        assert_eq!(topology.rings[0].atom_ids.len(), 6);
+
+        typer.remove_bond(closure).unwrap();
+        assert_matches_full_run(&typer);
+        assert!(typer.topology().unwrap().rings.is_empty());
+    }
+
+    #[test]
+    fn conjugated_and_aromatic_edits_match_a_full_run() {
+        let graph = parse_smiles("c1ccccc1C=CC=CC=CCCCCCCCCCCCC").unwrap();
+        let mut typer = IncrementalTyper::new(Typer::new(), graph).unwrap();
+
+        // A ring atom becomes a pyridine nitrogen once its hydrogen is gone.
+        let h = typer.neighbors[2]
+            .iter()
+            .map(|&(neighbor, _)| neighbor)
+            .find(|&neighbor| typer.graph().atoms[neighbor].element == Element::H)
+            .unwrap();
+        typer.remove_bond(bond_between(&typer, 2, h)).unwrap();
+        typer.mutate_element(2, Element::N).unwrap();
+        assert_eq!(typer.atom_types()[2], "N_R");
+        assert!(typer.last_retyped().contains(&5));
+        assert_matches_full_run(&typer);
+
+        // Saturating the end of the polyene leaves the far alkyl tail alone.
+        let double = bond_between(&typer, 10, 11);
+        typer.remove_bond(double).unwrap();
+        typer.add_bond(10, 11, GraphBondOrder::Single).unwrap();
+        assert!(!typer.last_retyped().contains(&23));
+        assert_matches_full_run(&typer);
+    }
+
+    #[test]
+    fn changes_spreading_through_neighbor_types_grow_the_region() {
+        let rules = parse_rules(
+            r#"
+            [[rule]]
+            name = "Carbon"
+            priority = 1
+            type = "C_3"
+            conditions = { element = "C" }
+
+            [[rule]]
+            name = "FluorinatedCarbon"
+            priority = 3
+            type = "C_R"
+            conditions = { element = "C", neighbor_elements = { F = 1 } }
+
+            [[rule]]
+            name = "CarbonNextToMarked"
+            priority = 2
+            type = "C_R"
+            conditions = { element = "C", neighbor_types = { "C_R" = 1 } }
+
+            [[rule]]
+            name = "Hydrogen"
+            priority = 1
+            type = "H_"
+            conditions = { element = "H" }
+
+            [[rule]]
+            name = "Fluorine"
+            priority = 1
+            type = "F_"
+            conditions = { element = "F" }
+            "#,
+        )
+        .unwrap();
+        let graph = parse_smiles("CCCCCCCCCCCCCCCCCCCC").unwrap();
+        let mut typer = IncrementalTyper::new(Typer::with_rules(rules), graph).unwrap();
+        assert_eq!(typer.atom_types()[19], "C_3");
+
+        let h = typer.neighbors[0]
+            .iter()
+            .map(|&(neighbor, _)| neighbor)
+            .find(|&neighbor| typer.graph().atoms[neighbor].element == Element::H)
+            .unwrap();
+        typer.mutate_element(h, Element::F).unwrap();
+        assert!(typer.atom_types()[..20].iter().all(|t| t == "C_R"));
+        assert!(typer.last_retyped().contains(&19));
+        assert_matches_full_run(&typer);
+    }
+
+    #[test]
+    fn failed_edits_leave_state_untouched() {
+        let rules = parse_rules(
+            r#"
+            [[rule]]
+            name = "AnyCarbon"
+            priority = 1
+            type = "C_3"
+            conditions = { element = "C" }
+
+            [[rule]]
+            name = "AnyHydrogen"
+            priority = 1
+            type = "H_"
+            conditions = { element = "H" }
+            "#,
+        )
+        .unwrap();
+        let mut graph = MolecularGraph::new();
+        let c = graph.add_atom(Element::C);
+        for _ in 0..4 {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+        }
+
+        let mut typer = IncrementalTyper::new(Typer::with_rules(rules), graph).unwrap();
+        let before = typer.atom_types().to_vec();
+
+        assert!(matches!(
+            typer.mutate_element(c, Element::Si),
+            Err(TyperError::AssignmentFailed(_))
+        ));
+        assert!(matches!(
+            typer.remove_bond(9),
+            Err(TyperError::InvalidInput(
+                GraphValidationError::MissingBond { bond_id: 9 }
+            ))
+        ));
+        assert!(matches!(
+            typer.mutate_element(9, Element::O),
+            Err(TyperError::InvalidInput(_))
+        ));
+        assert_eq!(typer.graph().atoms[c].element, Element::C);
+        assert_eq!(typer.atom_types(), before);
+        assert_eq!(typer.last_retyped(), [0, 1, 2, 3, 4]);
+    }
+}
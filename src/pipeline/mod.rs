@@ -0,0 +1,2648 @@
+//! Reusable front-end that binds a typing ruleset to the Perceive-Type-Build pipeline.
+//!
+//! The free functions in the crate root cover one-off calls. Services and batch tools that type
+//! many molecules instead hold a [`Typer`], which owns its ruleset once and can be shared by
+//! reference across worker threads.
+
+mod cache;
+mod incremental;
+mod progress;
+pub(crate) mod selection;
+mod solvent;
+
+use crate::compat::prelude::*;
+pub use cache::TypingCache;
+pub(crate) use cache::copy_stereo;
+pub use incremental::IncrementalTyper;
+pub use progress::{Progress, ProgressObserver, Stage};
+
+use crate::builder;
+use crate::compat::collections::HashMap;
+use crate::core::error::TyperError;
+use crate::core::graph::MolecularGraph;
+use crate::core::limits::{Budget, CancellationToken, ResourceLimits};
+use crate::core::periodic::{ImageFlags, PeriodicMolecularGraph};
+use crate::core::selection::AtomSelection;
+use crate::core::topology::{
+    Angle, HydrogenBondDonor, Inversion, InversionPolicy, MolecularTopology, Ring, Torsion,
+    TypeProvenance,
+};
+use crate::core::trace;
+use crate::features::{self, AtomDescriptors, DescriptorLayout};
+use crate::params::ForceFieldVariant;
+use crate::perception;
+use crate::perception::{AnnotatedMolecule, PerceptionOptions, PerceptionPipeline};
+use crate::polymer::{self, RepeatUnit};
+use crate::typing;
+use crate::typing::diagnostics::AssignmentDiagnostics;
+use crate::typing::engine::TypeAssignment;
+use crate::typing::fallback;
+use crate::typing::labels::TypeLabelMap;
+use crate::typing::overrides::TypingOverrides;
+use crate::typing::packs::RulePack;
+use crate::typing::registry::ForceFieldProfile;
+use crate::typing::rules::Rule;
+use crate::typing::ruleset::RuleSet;
+use crate::typing::uff;
+use alloc::sync::Arc;
+use core::fmt;
+use progress::ProgressTracker;
+
+/// Configured typing pipeline that can be reused for any number of molecules.
+///
+/// A `Typer` is immutable once constructed, so a single instance may be shared between threads
+/// (it is `Send + Sync`) without cloning its rules for every task.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, Typer};
+///
+/// let typer = Typer::new();
+///
+/// let mut graph = MolecularGraph::new();
+/// let o = graph.add_atom(Element::O);
+/// let h1 = graph.add_atom(Element::H);
+/// let h2 = graph.add_atom(Element::H);
+/// graph.add_bond(o, h1, GraphBondOrder::Single).unwrap();
+/// graph.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+///
+/// let topology = typer.assign_topology(&graph).unwrap();
+/// assert_eq!(topology.atoms[o].atom_type, "O_3");
+/// assert_eq!(topology.atoms[h1].atom_type, "H_HB");
+/// ```
+#[derive(Clone)]
+pub struct Typer {
+    /// Compiled rules evaluated by the typing engine, shared between clones.
+    rules: RuleSet,
+    /// Resource limits applied to every molecule.
+    limits: ResourceLimits,
+    /// Settings for the perception stages, such as the aromaticity model.
+    perception: PerceptionOptions,
+    /// Perception stages run before typing.
+    pipeline: PerceptionPipeline,
+    /// Optional renaming of canonical types applied before the topology is built.
+    labels: Option<TypeLabelMap>,
+    /// Whether atoms without a matching rule receive generic UFF types.
+    uff_fallback: bool,
+    /// Whether atoms left untyped fail the molecule or receive element-based types.
+    policy: AssignmentPolicy,
+    /// DREIDING variant whose hydrogen types are emitted.
+    variant: ForceFieldVariant,
+    /// Whether torsions without a DREIDING barrier are moved to `suppressed_torsions`.
+    suppress_torsions: bool,
+    /// Which inversion terms are emitted for each planar center.
+    inversion_policy: InversionPolicy,
+    /// Whether torsions through coordination bonds to metal centers are left out.
+    exclude_coordination_torsions: bool,
+    /// Whether lone-pair virtual sites are added to hydrogen-bond acceptors.
+    lone_pair_sites: bool,
+    /// Whether hydrogens are explicit or folded into united-atom carbons.
+    mode: TypingMode,
+    /// Optional observer notified as each molecule moves through the pipeline.
+    observer: Option<Arc<dyn ProgressObserver>>,
+    /// Optional token that aborts runs when cancelled.
+    cancellation: Option<CancellationToken>,
+    /// Bonds of context kept around a selection typed on its own.
+    selection_context: usize,
+}
+
+impl Typer {
+    /// Creates a typer backed by the embedded default DREIDING ruleset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the embedded default rules file is malformed, which indicates a critical
+    /// library bug.
+    pub fn new() -> Self {
+        Self {
+            rules: RuleSet::default_dreiding(),
+            limits: ResourceLimits::default(),
+            perception: PerceptionOptions::default(),
+            pipeline: PerceptionPipeline::standard(),
+            labels: None,
+            uff_fallback: false,
+            policy: AssignmentPolicy::Strict,
+            variant: ForceFieldVariant::Dreiding,
+            suppress_torsions: false,
+            inversion_policy: InversionPolicy::AllAxes,
+            exclude_coordination_torsions: false,
+            lone_pair_sites: false,
+            mode: TypingMode::AllAtom,
+            observer: None,
+            cancellation: None,
+            selection_context: selection::DEFAULT_CONTEXT_BONDS,
+        }
+    }
+
+    /// Creates a typer that evaluates the supplied rules instead of the defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - Complete rule list; priorities are enforced by the engine.
+    pub fn with_rules(rules: Vec<Rule>) -> Self {
+        Self::with_rule_set(RuleSet::new(rules))
+    }
+
+    /// Creates a typer that shares an already compiled rule set.
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - Rule set handle; cloning it does not copy the rules.
+    pub fn with_rule_set(rules: RuleSet) -> Self {
+        Self {
+            rules,
+            ..Self::new()
+        }
+    }
+
+    /// Creates a typer for a force-field profile, using its rules and variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - Profile obtained from a [`RuleRegistry`](crate::rules::RuleRegistry).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::Typer;
+    /// use dreid_typer::params::ForceFieldVariant;
+    /// use dreid_typer::rules::{DREIDING_X6, RuleRegistry};
+    ///
+    /// let profile = RuleRegistry::global().profile(DREIDING_X6).unwrap();
+    /// let typer = Typer::with_profile(&profile);
+    /// assert_eq!(typer.variant(), ForceFieldVariant::X6);
+    /// ```
+    pub fn with_profile(profile: &ForceFieldProfile) -> Self {
+        Self::with_rule_set(profile.rules.clone()).with_variant(profile.variant)
+    }
+
+    /// Creates a typer from namespaced rule packs composed with explicit precedence.
+    ///
+    /// See [`compose_rule_packs`](crate::rules::compose_rule_packs) for how priorities and rule
+    /// names are rewritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `packs` - Packs in ascending precedence; rules of later packs win over earlier ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::Typer;
+    /// use dreid_typer::rules::RulePack;
+    ///
+    /// let custom = RulePack::parse(r#"
+    ///     namespace = "lab-custom"
+    ///
+    ///     [[rule]]
+    ///     name = "Silanol_O"
+    ///     priority = 0
+    ///     type = "O_3"
+    ///     conditions = { element = "O", neighbor_elements = { Si = 1, H = 1 } }
+    /// "#).unwrap();
+    ///
+    /// let typer = Typer::with_rule_packs(&[RulePack::default_dreiding(), custom]);
+    /// assert!(typer.rules().iter().any(|rule| rule.name == "lab-custom::Silanol_O"));
+    /// ```
+    pub fn with_rule_packs(packs: &[RulePack]) -> Self {
+        Self::with_rules(typing::packs::compose_rule_packs(packs))
+    }
+
+    /// Replaces the resource limits applied to every molecule.
+    ///
+    /// # Arguments
+    ///
+    /// * `limits` - Bounds on Kekulé search, ring enumeration, typing rounds, and wall-clock time.
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the resource limits applied to every molecule.
+    pub fn limits(&self) -> &ResourceLimits {
+        &self.limits
+    }
+
+    /// Replaces the settings of the perception stages run before typing.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Perception settings, such as the aromaticity model.
+    pub fn with_perception_options(mut self, options: PerceptionOptions) -> Self {
+        self.perception = options;
+        self
+    }
+
+    /// Returns the settings of the perception stages run before typing.
+    pub fn perception_options(&self) -> &PerceptionOptions {
+        &self.perception
+    }
+
+    /// Replaces the perception stages run before typing.
+    ///
+    /// # Arguments
+    ///
+    /// * `pipeline` - Stage sequence built with [`PerceptionPipeline::builder`].
+    pub fn with_perception_pipeline(mut self, pipeline: PerceptionPipeline) -> Self {
+        self.pipeline = pipeline;
+        self
+    }
+
+    /// Returns the perception stages run before typing.
+    pub fn perception_pipeline(&self) -> &PerceptionPipeline {
+        &self.pipeline
+    }
+
+    /// Renames assigned types through `labels` before each topology is built.
+    ///
+    /// Typing fails with [`TyperError::LabelMapping`] for any molecule containing a type the map
+    /// does not cover, so no topology is ever emitted with a mix of canonical and custom labels.
+    ///
+    /// # Arguments
+    ///
+    /// * `labels` - Mapping from canonical DREIDING types to output labels.
+    pub fn with_label_map(mut self, labels: TypeLabelMap) -> Self {
+        self.labels = Some(labels);
+        self
+    }
+
+    /// Returns the output label map, if one is configured.
+    pub fn label_map(&self) -> Option<&TypeLabelMap> {
+        self.labels.as_ref()
+    }
+
+    /// Enables or disables the UFF fallback for atoms the rules cannot type.
+    ///
+    /// When enabled, atoms that no rule matches (exotic metals, lanthanides) receive the generic
+    /// UFF type for their element and are marked with [`TypeProvenance::UffFallback`] instead of
+    /// failing the molecule. Elements outside UFF still produce an [`AssignmentError`].
+    ///
+    /// [`AssignmentError`]: crate::AssignmentError
+    pub fn with_uff_fallback(mut self, enabled: bool) -> Self {
+        self.uff_fallback = enabled;
+        self
+    }
+
+    /// Returns whether the UFF fallback is enabled.
+    pub fn uff_fallback(&self) -> bool {
+        self.uff_fallback
+    }
+
+    /// Selects how atoms that no rule (and no enabled UFF fallback) can type are handled.
+    ///
+    /// Under [`AssignmentPolicy::Fallback`] such atoms receive a generic element-based DREIDING
+    /// type (`C_3` for an untypable sp³ carbon, `X_` for elements DREIDING does not cover) and
+    /// are marked with [`TypeProvenance::ElementFallback`], so one exotic atom no longer aborts
+    /// the whole molecule. [`MolecularTopology::fallback_atoms`] lists them as warnings.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Handling of atoms left untyped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{AssignmentPolicy, Element, MolecularGraph, TypeProvenance, Typer};
+    ///
+    /// let mut graph = MolecularGraph::new();
+    /// let la = graph.add_atom(Element::La);
+    ///
+    /// assert!(Typer::new().assign_topology(&graph).is_err());
+    ///
+    /// let topology = Typer::new()
+    ///     .with_assignment_policy(AssignmentPolicy::Fallback)
+    ///     .assign_topology(&graph)
+    ///     .unwrap();
+    /// assert_eq!(topology.atoms[la].atom_type, "X_");
+    /// assert_eq!(topology.atoms[la].provenance, TypeProvenance::ElementFallback);
+    /// assert_eq!(topology.fallback_atoms().count(), 1);
+    /// ```
+    pub fn with_assignment_policy(mut self, policy: AssignmentPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns how atoms that no rule can type are handled.
+    pub fn assignment_policy(&self) -> AssignmentPolicy {
+        self.policy
+    }
+
+    /// Selects the DREIDING variant whose atom types are emitted.
+    ///
+    /// Under [`ForceFieldVariant::DreidingA`] hydrogens the rules would type `H_HB` are emitted
+    /// as `H_`, so the topology lists no hydrogen-bond donors. The other variants share the
+    /// default typing and only differ in their parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - Variant to type for.
+    pub fn with_variant(mut self, variant: ForceFieldVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Returns the DREIDING variant this typer emits types for.
+    pub fn variant(&self) -> ForceFieldVariant {
+        self.variant
+    }
+
+    /// Enables or disables the suppression of torsions without a DREIDING barrier.
+    ///
+    /// When enabled, torsions about a central bond involving an sp center or an unhybridized
+    /// terminal heavy atom such as a halogen or metal (case (g)) are moved from
+    /// [`MolecularTopology::torsions`] to [`MolecularTopology::suppressed_torsions`], so force
+    /// fields do not carry zero-barrier terms while the skipped torsions stay available for
+    /// auditing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, Typer};
+    ///
+    /// // Acetonitrile: every torsion is about the bond to the sp carbon.
+    /// let mut graph = MolecularGraph::new();
+    /// let c1 = graph.add_atom(Element::C);
+    /// let c2 = graph.add_atom(Element::C);
+    /// let n = graph.add_atom(Element::N);
+    /// graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+    /// graph.add_bond(c2, n, GraphBondOrder::Triple).unwrap();
+    /// for _ in 0..3 {
+    ///     let h = graph.add_atom(Element::H);
+    ///     graph.add_bond(c1, h, GraphBondOrder::Single).unwrap();
+    /// }
+    ///
+    /// let topology = Typer::new()
+    ///     .with_torsion_suppression(true)
+    ///     .assign_topology(&graph)
+    ///     .unwrap();
+    /// assert!(topology.torsions.is_empty());
+    /// assert_eq!(topology.suppressed_torsions.len(), 3);
+    /// ```
+    pub fn with_torsion_suppression(mut self, enabled: bool) -> Self {
+        self.suppress_torsions = enabled;
+        self
+    }
+
+    /// Returns whether torsions without a DREIDING barrier are suppressed.
+    pub fn torsion_suppression(&self) -> bool {
+        self.suppress_torsions
+    }
+
+    /// Selects which inversion terms the builder emits for each planar center.
+    ///
+    /// The default [`InversionPolicy::AllAxes`] emits DREIDING's three umbrella terms per
+    /// center. Parameter assignment shares the planar force constant among whatever terms a
+    /// center has, so the total barrier is the same under every policy that keeps the center.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Inversion convention of the target engine.
+    pub fn with_inversion_policy(mut self, policy: InversionPolicy) -> Self {
+        self.inversion_policy = policy;
+        self
+    }
+
+    /// Returns which inversion terms the builder emits for each planar center.
+    pub fn inversion_policy(&self) -> InversionPolicy {
+        self.inversion_policy
+    }
+
+    /// Enables or disables the exclusion of torsions through coordination bonds.
+    ///
+    /// Bonds to metal centers are tagged [`BondClass::Coordination`](crate::BondClass) in every
+    /// topology. When exclusion is enabled, the builder also leaves out every torsion containing
+    /// a metal center, whether the metal is a central or an end atom, since metalloprotein and
+    /// MOF models usually hold the coordination geometry with bonds and angles alone. Torsions
+    /// about disulfide bonds are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{BondClass, Element, GraphBondOrder, MolecularGraph, Typer};
+    ///
+    /// // Zinc bound to the nitrogen of methylamine.
+    /// let mut graph = MolecularGraph::new();
+    /// let zn = graph.add_atom(Element::Zn);
+    /// let n = graph.add_atom(Element::N);
+    /// let c = graph.add_atom(Element::C);
+    /// graph.add_bond(zn, n, GraphBondOrder::Single).unwrap();
+    /// graph.add_bond(n, c, GraphBondOrder::Single).unwrap();
+    /// for (atom, count) in [(n, 2), (c, 3)] {
+    ///     for _ in 0..count {
+    ///         let h = graph.add_atom(Element::H);
+    ///         graph.add_bond(atom, h, GraphBondOrder::Single).unwrap();
+    ///     }
+    /// }
+    ///
+    /// let typer = Typer::new();
+    /// let all = typer.assign_topology(&graph).unwrap();
+    /// let zn_n = all.bonds.iter().find(|bond| bond.atom_ids == (zn, n)).unwrap();
+    /// assert_eq!(zn_n.class, BondClass::Coordination);
+    /// assert!(all.torsions.iter().any(|t| t.atom_ids.0 == zn || t.atom_ids.3 == zn));
+    ///
+    /// let excluded = typer
+    ///     .with_coordination_torsion_exclusion(true)
+    ///     .assign_topology(&graph)
+    ///     .unwrap();
+    /// assert_eq!(excluded.torsions.len(), 6);
+    /// ```
+    pub fn with_coordination_torsion_exclusion(mut self, enabled: bool) -> Self {
+        self.exclude_coordination_torsions = enabled;
+        self
+    }
+
+    /// Returns whether torsions through coordination bonds are left out.
+    pub fn coordination_torsion_exclusion(&self) -> bool {
+        self.exclude_coordination_torsions
+    }
+
+    /// Enables or disables lone-pair virtual sites on hydrogen-bond acceptors.
+    ///
+    /// When enabled, every acceptor in [`MolecularTopology::hb_acceptors`] receives one dummy
+    /// atom per lone pair, appended after the real atoms and described by a
+    /// [`VirtualSite`](crate::VirtualSite) in [`MolecularTopology::virtual_sites`], for
+    /// force-field variants that put off-center charges on lone pairs. Sites are added after the
+    /// united-atom collapse and before the output label map, which must then cover the `Du`
+    /// type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, Typer};
+    ///
+    /// // Formaldehyde: the carbonyl oxygen carries two in-plane lone pairs.
+    /// let mut graph = MolecularGraph::new();
+    /// let c = graph.add_atom(Element::C);
+    /// let o = graph.add_atom(Element::O);
+    /// graph.add_bond(c, o, GraphBondOrder::Double).unwrap();
+    /// for _ in 0..2 {
+    ///     let h = graph.add_atom(Element::H);
+    ///     graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+    /// }
+    ///
+    /// let topology = Typer::new()
+    ///     .with_lone_pair_sites(true)
+    ///     .assign_topology(&graph)
+    ///     .unwrap();
+    /// assert_eq!(topology.atoms.len(), 6);
+    /// assert!(topology.virtual_sites.iter().all(|site| site.parent_id == o));
+    /// assert_eq!(topology.atoms[5].atom_type, "Du");
+    /// ```
+    pub fn with_lone_pair_sites(mut self, enabled: bool) -> Self {
+        self.lone_pair_sites = enabled;
+        self
+    }
+
+    /// Returns whether lone-pair virtual sites are added to hydrogen-bond acceptors.
+    pub fn lone_pair_sites(&self) -> bool {
+        self.lone_pair_sites
+    }
+
+    /// Selects whether topologies list every hydrogen or use united-atom carbons.
+    ///
+    /// In [`TypingMode::UnitedAtom`] the molecule is still perceived and typed with explicit
+    /// hydrogens; the hydrogens bonded to carbon are then folded into their carbons, which
+    /// receive united-atom types such as `C_33` or `C_R1`. Polar hydrogens stay explicit. Atom
+    /// IDs of the topology no longer match the input graph, and
+    /// [`Typer::assign_atom_types`] keeps returning one all-atom type per input atom. An output
+    /// label map is applied after the collapse, so it must cover the united-atom types.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Hydrogen treatment of the emitted topologies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, Typer, TypingMode};
+    ///
+    /// let mut graph = MolecularGraph::new();
+    /// let c1 = graph.add_atom(Element::C);
+    /// let c2 = graph.add_atom(Element::C);
+    /// graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+    /// for carbon in [c1, c2] {
+    ///     for _ in 0..3 {
+    ///         let h = graph.add_atom(Element::H);
+    ///         graph.add_bond(carbon, h, GraphBondOrder::Single).unwrap();
+    ///     }
+    /// }
+    ///
+    /// let topology = Typer::new()
+    ///     .with_typing_mode(TypingMode::UnitedAtom)
+    ///     .assign_topology(&graph)
+    ///     .unwrap();
+    /// assert_eq!(topology.atoms.len(), 2);
+    /// assert_eq!(topology.atoms[0].atom_type, "C_33");
+    /// assert!(topology.angles.is_empty());
+    /// ```
+    pub fn with_typing_mode(mut self, mode: TypingMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns whether this typer emits all-atom or united-atom topologies.
+    pub fn typing_mode(&self) -> TypingMode {
+        self.mode
+    }
+
+    /// Registers an observer that receives [`Progress`] updates during every typing run.
+    ///
+    /// Replaces any previously registered observer. The observer is shared between clones of
+    /// this typer.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - Callback or type implementing [`ProgressObserver`].
+    pub fn with_progress_observer(mut self, observer: impl ProgressObserver + 'static) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Registers a token that aborts typing runs once it is cancelled.
+    ///
+    /// Runs stop at their next checkpoint with
+    /// [`LimitExceeded::Cancelled`](crate::LimitExceeded::Cancelled). Combined with a progress
+    /// observer, this lets front-ends abort very large systems midway without leaving the process.
+    /// Every clone of this typer observes the same token.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Token whose clones may be cancelled from any thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{
+    ///     CancellationToken, Element, GraphBondOrder, LimitExceeded, MolecularGraph, Stage,
+    ///     Typer, TyperError,
+    /// };
+    ///
+    /// let token = CancellationToken::new();
+    /// let handle = token.clone();
+    /// let typer = Typer::new()
+    ///     .with_cancellation(token)
+    ///     .with_progress_observer(move |progress: &dreid_typer::Progress| {
+    ///         if progress.stage == Stage::Typing {
+    ///             handle.cancel();
+    ///         }
+    ///     });
+    ///
+    /// let mut graph = MolecularGraph::new();
+    /// let c = graph.add_atom(Element::C);
+    /// let o = graph.add_atom(Element::O);
+    /// graph.add_bond(c, o, GraphBondOrder::Double).unwrap();
+    ///
+    /// let error = typer.assign_topology(&graph).unwrap_err();
+    /// assert!(matches!(error, TyperError::LimitExceeded(LimitExceeded::Cancelled)));
+    /// ```
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Returns the cancellation token observed by this typer, if any.
+    pub fn cancellation(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    /// Sets how many bonds of context surround a selection typed on its own.
+    ///
+    /// [`Typer::assign_topology_for_selection`] perceives every atom within this many bonds of
+    /// the selection, plus the atoms needed to avoid cutting multiple, aromatic, hydrogen, and
+    /// metal bonds, and caps the remaining cut bonds with hydrogens. Rings of up to
+    /// `2 * bonds + 1` atoms through a selected atom are always seen whole. Defaults to three
+    /// bonds.
+    ///
+    /// # Arguments
+    ///
+    /// * `bonds` - Depth of the context region around the selection.
+    pub fn with_selection_context(mut self, bonds: usize) -> Self {
+        self.selection_context = bonds;
+        self
+    }
+
+    /// Returns how many bonds of context surround a selection typed on its own.
+    pub fn selection_context(&self) -> usize {
+        self.selection_context
+    }
+
+    /// Returns the rules this typer evaluates.
+    pub fn rules(&self) -> &[Rule] {
+        self.rules.rules()
+    }
+
+    /// Returns the compiled rule set this typer evaluates.
+    pub fn rule_set(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Runs perception, typing, and topology construction for a single molecule.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Molecular graph to type.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TyperError`] under the same conditions as [`crate::assign_topology`].
+    pub fn assign_topology(&self, graph: &MolecularGraph) -> Result<MolecularTopology, TyperError> {
+        run(graph, &self.run_options())
+    }
+
+    /// Runs perception, typing, and topology construction with some atom types forced.
+    ///
+    /// Atoms addressed by `overrides` keep their forced type and are marked with
+    /// [`TypeProvenance::Override`]; the rules type every other atom and see the forced types in
+    /// their `neighbor_types` conditions. This pins a few atoms, such as a modeled metal site,
+    /// without editing the rule deck.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Molecular graph to type.
+    /// * `overrides` - Forced types, addressed by atom ID or metadata name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TyperError::InvalidOverride`] if an override addresses no atom of `graph`, and
+    /// otherwise a [`TyperError`] under the same conditions as [`Typer::assign_topology`].
+    pub fn assign_topology_with_overrides(
+        &self,
+        graph: &MolecularGraph,
+        overrides: &TypingOverrides,
+    ) -> Result<MolecularTopology, TyperError> {
+        let pinned = overrides.resolve(graph)?;
+        let options = RunOptions {
+            pinned: &pinned,
+            ..self.run_options()
+        };
+        run(graph, &options)
+    }
+
+    /// Runs perception, typing, and topology construction for a selected sub-region only.
+    ///
+    /// The selection is perceived inside a context region of
+    /// [`Typer::selection_context`] bonds, with hydrogens capping the bonds that leave the region,
+    /// so the cost scales with the selection rather than the system. This lets QM/MM and
+    /// ligand-swap workflows re-type a pocket or ligand inside an already typed protein. The
+    /// returned topology holds the selected atoms, numbered in ascending order of their graph IDs,
+    /// and only the terms whose atoms are all selected; terms crossing the selection boundary are
+    /// left to the surrounding topology.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Full molecular graph.
+    /// * `selection` - Atoms to type.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TyperError::InvalidInput`] if the selection names an atom missing from `graph`,
+    /// and otherwise a [`TyperError`] under the same conditions as [`Typer::assign_topology`],
+    /// with atom IDs referring to `graph`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{AtomSelection, Element, GraphBondOrder, MolecularGraph, Typer};
+    ///
+    /// // Ethanol; only the hydroxyl group is re-typed.
+    /// let mut graph = MolecularGraph::new();
+    /// let c1 = graph.add_atom(Element::C);
+    /// let c2 = graph.add_atom(Element::C);
+    /// let o = graph.add_atom(Element::O);
+    /// let h = graph.add_atom(Element::H);
+    /// graph.add_bond(c1, c2, GraphBondOrder::Single).unwrap();
+    /// graph.add_bond(c2, o, GraphBondOrder::Single).unwrap();
+    /// graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+    /// for carbon in [c1, c1, c1, c2, c2] {
+    ///     let hc = graph.add_atom(Element::H);
+    ///     graph.add_bond(carbon, hc, GraphBondOrder::Single).unwrap();
+    /// }
+    ///
+    /// let selection = AtomSelection::from_ids([o, h]);
+    /// let topology = Typer::new()
+    ///     .assign_topology_for_selection(&graph, &selection)
+    ///     .unwrap();
+    /// assert_eq!(topology.atoms.len(), 2);
+    /// assert_eq!(topology.atoms[0].atom_type, "O_3");
+    /// assert_eq!(topology.atoms[1].atom_type, "H_HB");
+    /// assert_eq!(topology.bonds.len(), 1);
+    /// ```
+    pub fn assign_topology_for_selection(
+        &self,
+        graph: &MolecularGraph,
+        selection: &AtomSelection,
+    ) -> Result<MolecularTopology, TyperError> {
+        selection::run(
+            graph,
+            selection,
+            self.selection_context,
+            &self.run_options(),
+        )
+    }
+
+    /// Runs perception and typing only, returning the type of every atom in input order.
+    ///
+    /// The types are identical to the `atom_type` fields of [`Typer::assign_topology`], but no
+    /// bonds, angles, torsions, or inversions are built, which saves time and memory when only
+    /// the labels are needed (e.g., for charge assignment).
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Molecular graph to type.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TyperError`] under the same conditions as [`Typer::assign_topology`].
+    pub fn assign_atom_types(&self, graph: &MolecularGraph) -> Result<Vec<String>, TyperError> {
+        run_atom_types(graph, &self.run_options())
+    }
+
+    /// Runs perception only, returning the annotated molecule that typing and topology
+    /// construction start from.
+    ///
+    /// The molecule is perceived with this typer's perception pipeline, options, and limits, and
+    /// is the input [`build_topology_streaming`](crate::build_topology_streaming) expects.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Molecular graph to perceive.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TyperError`] if the graph is invalid, a perception stage fails, or the run
+    /// exceeds its limits.
+    pub fn perceive(&self, graph: &MolecularGraph) -> Result<AnnotatedMolecule, TyperError> {
+        let budget = self.run_options().budget();
+        perception::perceive(
+            graph,
+            &[],
+            &self.pipeline,
+            &self.perception,
+            &budget,
+            &mut |_| {},
+        )
+    }
+
+    /// Runs perception, typing, and topology construction for one unit cell of a periodic system.
+    ///
+    /// Ring perception follows bonds into the image they reach, so aromatic rings and resonant
+    /// groups that straddle a cell face (framework linkers, carboxylate nodes) are typed exactly
+    /// as in an isolated molecule, while chains that close only by wrapping around the cell are
+    /// not treated as rings. Rings larger than the cell, such as the hexagons of graphene in its
+    /// primitive cell, are found through the images of their atoms and list an atom once per
+    /// image they pass through. Bonded terms in the returned topology refer to atom IDs of the
+    /// cell; the cell must be large enough that every term involves distinct atoms.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Unit cell with periodic bonds.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TyperError`] under the same conditions as [`Typer::assign_topology`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::{Element, GraphBondOrder, PeriodicMolecularGraph, Typer};
+    ///
+    /// // A benzene ring whose C3-C4 and C5-C0 bonds cross the a-face of the cell.
+    /// let mut cell =
+    ///     PeriodicMolecularGraph::new([[6.0, 0.0, 0.0], [0.0, 6.0, 0.0], [0.0, 0.0, 6.0]]);
+    /// let c: Vec<usize> = (0..6).map(|_| cell.add_atom(Element::C)).collect();
+    /// let images = [[0, 0, 0], [0, 0, 0], [1, 0, 0], [0, 0, 0], [-1, 0, 0], [0, 0, 0]];
+    /// for i in 0..6 {
+    ///     cell.add_periodic_bond(c[i], c[(i + 1) % 6], GraphBondOrder::Aromatic, images[i])
+    ///         .unwrap();
+    ///     let h = cell.add_atom(Element::H);
+    ///     cell.add_bond(c[i], h, GraphBondOrder::Single).unwrap();
+    /// }
+    ///
+    /// let topology = Typer::new().assign_periodic_topology(&cell).unwrap();
+    /// assert!(topology.atoms[..6].iter().all(|atom| atom.atom_type == "C_R"));
+    /// ```
+    pub fn assign_periodic_topology(
+        &self,
+        graph: &PeriodicMolecularGraph,
+    ) -> Result<MolecularTopology, TyperError> {
+        let options = RunOptions {
+            bond_images: &graph.bond_images,
+            ..self.run_options()
+        };
+        run(&graph.graph, &options)
+    }
+
+    /// Runs perception, typing, and topology construction for a linear chain of repeat units.
+    ///
+    /// Only a short reference oligomer is perceived and typed; its atom types and bonded terms
+    /// are propagated along the chain, including the terms that cross unit boundaries. The
+    /// result equals [`Typer::assign_topology`] on [`RepeatUnit::chain`], at a cost that no
+    /// longer depends on perception of the whole chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `unit` - Repeat unit with its head and tail attachment atoms.
+    /// * `repeats` - Number of units in the chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TyperError`] under the same conditions as [`Typer::assign_topology`] on the
+    /// explicit chain; atom IDs in errors refer to the chain.
+    pub fn assign_polymer_topology(
+        &self,
+        unit: &RepeatUnit,
+        repeats: usize,
+    ) -> Result<MolecularTopology, TyperError> {
+        polymer::assign_chain(unit, repeats, &self.run_options())
+    }
+
+    /// Types a molecule and extracts per-atom descriptor vectors for machine learning.
+    ///
+    /// Each row is aligned with the atom of the same index and with the assigned type (after any
+    /// label remapping), so the result can be used directly as a labeled training example.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Molecular graph to type.
+    /// * `layout` - Element vocabulary that fixes the descriptor length.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TyperError`] under the same conditions as [`Typer::assign_topology`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::features::DescriptorLayout;
+    /// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, Typer};
+    ///
+    /// let mut graph = MolecularGraph::new();
+    /// let h = graph.add_atom(Element::H);
+    /// let c = graph.add_atom(Element::C);
+    /// let n = graph.add_atom(Element::N);
+    /// graph.add_bond(h, c, GraphBondOrder::Single).unwrap();
+    /// graph.add_bond(c, n, GraphBondOrder::Triple).unwrap();
+    ///
+    /// let layout = DescriptorLayout::default();
+    /// let descriptors = Typer::new().atom_descriptors(&graph, &layout).unwrap();
+    /// assert_eq!(descriptors.len(), 3);
+    /// assert_eq!(descriptors.row(c).len(), layout.width());
+    /// assert_eq!(descriptors.atom_types[c], "C_1");
+    /// ```
+    pub fn atom_descriptors(
+        &self,
+        graph: &MolecularGraph,
+        layout: &DescriptorLayout,
+    ) -> Result<AtomDescriptors, TyperError> {
+        let options = self.run_options();
+        let budget = options.budget();
+        let tracker = ProgressTracker::new(options.observer, graph.atoms.len());
+        let typed = perceive_and_type(graph, &options, &budget, &tracker)?;
+
+        let atom_types = match options.labels {
+            Some(labels) => labels.apply(&typed.atom_types)?,
+            None => typed.atom_types,
+        };
+        Ok(features::describe(&typed.molecule, atom_types, layout))
+    }
+
+    /// Types a molecule and reports how decisively each atom was typed.
+    ///
+    /// Rules of equal priority are normally resolved silently by name; this audit mode lists
+    /// every atom where such rules proposed different types, together with the competing rules,
+    /// so rulesets can be refined. Types are reported in canonical DREIDING form, before variant
+    /// folding, UFF fallback, or label remapping.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Molecular graph to type.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TyperError`] if perception fails or typing exceeds its limits. Atoms left
+    /// untyped are not an error; they receive a confidence of `0.0`.
+    pub fn assignment_diagnostics(
+        &self,
+        graph: &MolecularGraph,
+    ) -> Result<AssignmentDiagnostics, TyperError> {
+        let budget = self.run_options().budget();
+        let molecule = perception::perceive(
+            graph,
+            &[],
+            &self.pipeline,
+            &self.perception,
+            &budget,
+            &mut |_| {},
+        )?;
+        let assignment =
+            typing::engine::assign_types(&molecule, &self.rules, &[], &budget, &mut |_| {})?;
+        Ok(typing::engine::diagnose(
+            &molecule,
+            &self.rules,
+            &assignment,
+        ))
+    }
+
+    /// Borrows this typer's settings for a single pipeline run.
+    pub(crate) fn run_options(&self) -> RunOptions<'_> {
+        RunOptions {
+            rules: &self.rules,
+            limits: &self.limits,
+            perception: &self.perception,
+            pipeline: &self.pipeline,
+            labels: self.labels.as_ref(),
+            uff_fallback: self.uff_fallback,
+            policy: self.policy,
+            variant: self.variant,
+            suppress_torsions: self.suppress_torsions,
+            inversion_policy: self.inversion_policy,
+            exclude_coordination_torsions: self.exclude_coordination_torsions,
+            lone_pair_sites: self.lone_pair_sites,
+            mode: self.mode,
+            observer: self.observer.as_deref(),
+            cancellation: self.cancellation.as_ref(),
+            bond_images: &[],
+            pinned: &[],
+        }
+    }
+}
+
+impl fmt::Debug for Typer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Typer")
+            .field("rules", &self.rules.len())
+            .field("limits", &self.limits)
+            .field("perception", &self.perception)
+            .field("pipeline", &self.pipeline)
+            .field("labels", &self.labels)
+            .field("uff_fallback", &self.uff_fallback)
+            .field("policy", &self.policy)
+            .field("variant", &self.variant)
+            .field("suppress_torsions", &self.suppress_torsions)
+            .field("inversion_policy", &self.inversion_policy)
+            .field(
+                "exclude_coordination_torsions",
+                &self.exclude_coordination_torsions,
+            )
+            .field("lone_pair_sites", &self.lone_pair_sites)
+            .field("mode", &self.mode)
+            .field("has_observer", &self.observer.is_some())
+            .field("cancellation", &self.cancellation)
+            .field("selection_context", &self.selection_context)
+            .finish()
+    }
+}
+
+impl Default for Typer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hydrogen treatment of the topologies a [`Typer`] emits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TypingMode {
+    /// Every hydrogen of the input is an atom of the topology.
+    #[default]
+    AllAtom,
+    /// Hydrogens bonded to carbon are folded into united-atom carbon types.
+    UnitedAtom,
+}
+
+/// Handling of atoms that no rule of the active deck can type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AssignmentPolicy {
+    /// Fail the whole molecule with an [`AssignmentError`](crate::AssignmentError).
+    #[default]
+    Strict,
+    /// Assign a generic element-based type and mark the atom as a fallback.
+    Fallback,
+}
+
+/// Borrowed view of the settings that drive a single pipeline run.
+#[derive(Clone, Copy)]
+pub(crate) struct RunOptions<'a> {
+    /// Compiled rules evaluated by the typing engine.
+    pub rules: &'a RuleSet,
+    /// Resource limits enforced during the run.
+    pub limits: &'a ResourceLimits,
+    /// Settings for the perception stages.
+    pub perception: &'a PerceptionOptions,
+    /// Perception stages run before typing.
+    pub pipeline: &'a PerceptionPipeline,
+    /// Optional output label map applied before building.
+    pub labels: Option<&'a TypeLabelMap>,
+    /// Whether untyped atoms fall back to generic UFF types.
+    pub uff_fallback: bool,
+    /// Whether atoms left untyped fail the run or receive element-based types.
+    pub policy: AssignmentPolicy,
+    /// DREIDING variant whose hydrogen types are emitted.
+    pub variant: ForceFieldVariant,
+    /// Whether torsions without a DREIDING barrier are moved to `suppressed_torsions`.
+    pub suppress_torsions: bool,
+    /// Which inversion terms are emitted for each planar center.
+    pub inversion_policy: InversionPolicy,
+    /// Whether torsions through coordination bonds to metal centers are left out.
+    pub exclude_coordination_torsions: bool,
+    /// Whether lone-pair virtual sites are added to hydrogen-bond acceptors.
+    pub lone_pair_sites: bool,
+    /// Whether hydrogens are explicit or folded into united-atom carbons.
+    pub mode: TypingMode,
+    /// Optional progress observer.
+    pub observer: Option<&'a dyn ProgressObserver>,
+    /// Optional token that aborts the run when cancelled.
+    pub cancellation: Option<&'a CancellationToken>,
+    /// Image flags of every bond for periodic inputs, or empty for molecules.
+    pub bond_images: &'a [ImageFlags],
+    /// Forced type of every atom, or empty when no atom is pinned.
+    pub pinned: &'a [Option<String>],
+}
+
+impl<'a> RunOptions<'a> {
+    /// Default settings for the given rules, as used by the crate-level free functions.
+    pub fn new(rules: &'a RuleSet) -> Self {
+        Self {
+            rules,
+            limits: &ResourceLimits::DEFAULT,
+            perception: &PerceptionOptions::DEFAULT,
+            pipeline: PerceptionPipeline::standard_ref(),
+            labels: None,
+            uff_fallback: false,
+            policy: AssignmentPolicy::Strict,
+            variant: ForceFieldVariant::Dreiding,
+            suppress_torsions: false,
+            inversion_policy: InversionPolicy::AllAxes,
+            exclude_coordination_torsions: false,
+            lone_pair_sites: false,
+            mode: TypingMode::AllAtom,
+            observer: None,
+            cancellation: None,
+            bond_images: &[],
+            pinned: &[],
+        }
+    }
+
+    /// Starts the resource budget of a run, observing the cancellation token if one is set.
+    pub fn budget(&self) -> Budget {
+        Budget::start(*self.limits).with_cancellation(self.cancellation)
+    }
+}
+
+/// Annotated molecule together with its resolved atom types.
+pub(crate) struct TypedMolecule {
+    /// Molecule after all perception passes.
+    pub molecule: AnnotatedMolecule,
+    /// Canonical atom type of every atom, before any label remapping.
+    pub atom_types: Vec<String>,
+    /// Origin of every atom type.
+    pub provenance: Vec<TypeProvenance>,
+}
+
+/// Executes the perception, typing, and building phases with the given options.
+pub(crate) fn run(
+    graph: &MolecularGraph,
+    options: &RunOptions<'_>,
+) -> Result<MolecularTopology, TyperError> {
+    let mut topology = assemble(graph, options)?;
+    if !options.bond_images.is_empty() {
+        copy_bond_images(&mut topology, graph, options.bond_images);
+    }
+    finish(topology, graph, options)
+}
+
+/// Records the image flags of the input bonds on the topology bonds between the same atoms.
+fn copy_bond_images(
+    topology: &mut MolecularTopology,
+    graph: &MolecularGraph,
+    images: &[ImageFlags],
+) {
+    let image_of: HashMap<(usize, usize), ImageFlags> = graph
+        .bonds
+        .iter()
+        .zip(images)
+        .map(|(edge, &image)| {
+            let (a, b) = edge.atom_ids;
+            if a < b {
+                ((a, b), image)
+            } else {
+                ((b, a), image.map(|t| -t))
+            }
+        })
+        .collect();
+    for bond in &mut topology.bonds {
+        bond.image = image_of[&bond.atom_ids];
+    }
+}
+
+/// Builds the all-atom topology with canonical types, typing every fragment on its own.
+///
+/// The result still needs [`finish`] to become the output of [`run`].
+pub(crate) fn assemble(
+    graph: &MolecularGraph,
+    options: &RunOptions<'_>,
+) -> Result<MolecularTopology, TyperError> {
+    let budget = options.budget();
+    let tracker = ProgressTracker::new(options.observer, graph.atoms.len());
+    let components = graph.connected_components();
+    let _span = trace::span!(
+        "molecule",
+        atoms = graph.atoms.len(),
+        bonds = graph.bonds.len(),
+        fragments = components.len()
+    );
+    if components.len() <= 1 {
+        let typed = perceive_and_type(graph, options, &budget, &tracker)?;
+        budget.check_time()?;
+        tracker.report(Stage::Building, 0);
+        let _span = trace::span!("building");
+        return Ok(build(&typed, options));
+    }
+
+    let (solvent, components) = split_solvent(graph, components, options, &budget)?;
+    let typed_fragments =
+        perceive_and_type_fragments(graph, &components, options, &budget, &tracker)?;
+
+    budget.check_time()?;
+    tracker.report(Stage::Building, 0);
+    let _span = trace::span!("building");
+    let (solvent_ids, solvent_topologies): (Vec<_>, Vec<_>) = solvent.into_iter().unzip();
+    let fragments = typed_fragments
+        .iter()
+        .map(|(atom_ids, typed)| (*atom_ids, build(typed, options)))
+        .chain(
+            solvent_ids
+                .iter()
+                .map(Vec::as_slice)
+                .zip(solvent_topologies),
+        )
+        .collect();
+    Ok(merge_fragments(graph.atoms.len(), fragments))
+}
+
+/// Executes the perception and typing phases only, returning every atom's type in input order.
+///
+/// Fragments are typed exactly as [`run`] types them, and the optional label map is applied,
+/// so the result matches the atom types of the full topology without building its terms.
+pub(crate) fn run_atom_types(
+    graph: &MolecularGraph,
+    options: &RunOptions<'_>,
+) -> Result<Vec<String>, TyperError> {
+    let budget = options.budget();
+    let tracker = ProgressTracker::new(options.observer, graph.atoms.len());
+    let components = graph.connected_components();
+    let _span = trace::span!(
+        "molecule",
+        atoms = graph.atoms.len(),
+        bonds = graph.bonds.len(),
+        fragments = components.len()
+    );
+    let atom_types = if components.len() <= 1 {
+        perceive_and_type(graph, options, &budget, &tracker)?.atom_types
+    } else {
+        let mut atom_types = vec![String::new(); graph.atoms.len()];
+        let (solvent, components) = split_solvent(graph, components, options, &budget)?;
+        for (atom_ids, topology) in solvent {
+            for (id, atom) in atom_ids.into_iter().zip(topology.atoms) {
+                atom_types[id] = atom.atom_type.to_string();
+            }
+        }
+        for (atom_ids, typed) in
+            perceive_and_type_fragments(graph, &components, options, &budget, &tracker)?
+        {
+            for (&id, atom_type) in atom_ids.iter().zip(typed.atom_types) {
+                atom_types[id] = atom_type;
+            }
+        }
+        atom_types
+    };
+
+    match options.labels {
+        Some(labels) => Ok(labels.apply(&atom_types)?),
+        None => Ok(atom_types),
+    }
+}
+
+/// Input atom IDs, in reference order, and topology of a fragment typed by lookup.
+type SolventFragment = (Vec<usize>, MolecularTopology);
+
+/// Types water, its ions, and monoatomic ions by lookup and returns the remaining components.
+///
+/// # Returns
+///
+/// The atom IDs (in reference order) and topology of every solvent fragment, and the components
+/// that still need perception.
+///
+/// # Errors
+///
+/// Returns a [`TyperError`] if a solvent species cannot be typed with the run's settings.
+fn split_solvent(
+    graph: &MolecularGraph,
+    components: Vec<Vec<usize>>,
+    options: &RunOptions<'_>,
+    budget: &Budget,
+) -> Result<(Vec<SolventFragment>, Vec<Vec<usize>>), TyperError> {
+    let mut table = solvent::SolventTable::new(graph);
+    let mut solvent = Vec::new();
+    let mut rest = Vec::with_capacity(components.len());
+    for atom_ids in components {
+        match table.recognize(graph, &atom_ids, options) {
+            Some((species, order)) => {
+                let topology = table.topology(species, &order, options, budget)?;
+                solvent.push((order, topology));
+            }
+            None => rest.push(atom_ids),
+        }
+    }
+    trace::event!(solvent_fragments = solvent.len(), "solvent typed by lookup");
+    Ok((solvent, rest))
+}
+
+/// Perceives and types every connected component on its own subgraph.
+///
+/// The observer sees the stages restart for each fragment while the atom count keeps growing.
+fn perceive_and_type_fragments<'c>(
+    graph: &MolecularGraph,
+    components: &'c [Vec<usize>],
+    options: &RunOptions<'_>,
+    budget: &Budget,
+    tracker: &ProgressTracker<'_>,
+) -> Result<Vec<(&'c [usize], TypedMolecule)>, TyperError> {
+    let mut typed_fragments = Vec::with_capacity(components.len());
+    let mut atoms_before = 0;
+    for atom_ids in components {
+        let _span = trace::span!("fragment", first_atom = atom_ids[0], atoms = atom_ids.len());
+        let (subgraph, bond_ids) = graph.subgraph(atom_ids);
+        let images: Vec<ImageFlags> = if options.bond_images.is_empty() {
+            Vec::new()
+        } else {
+            bond_ids.iter().map(|&id| options.bond_images[id]).collect()
+        };
+        let pinned: Vec<Option<String>> = if options.pinned.is_empty() {
+            Vec::new()
+        } else {
+            atom_ids
+                .iter()
+                .map(|&id| options.pinned[id].clone())
+                .collect()
+        };
+        let fragment_options = RunOptions {
+            bond_images: &images,
+            pinned: &pinned,
+            ..*options
+        };
+        let typed = perceive_and_type(
+            &subgraph,
+            &fragment_options,
+            budget,
+            &tracker.fragment(atoms_before),
+        )
+        .map_err(|mut error| {
+            error.map_atom_ids(&|id| atom_ids[id]);
+            error
+        })?;
+        atoms_before += atom_ids.len();
+        typed_fragments.push((atom_ids.as_slice(), typed));
+    }
+    Ok(typed_fragments)
+}
+
+/// Merges the topologies of disjoint fragments into one topology over the whole graph.
+///
+/// Atom `i` of a fragment topology becomes atom `atom_ids[i]`, every term is remapped and
+/// re-canonicalized, and atoms receive the index of their fragment in order of its lowest atom
+/// ID as [`Atom::fragment_id`](crate::Atom::fragment_id).
+///
+/// # Arguments
+///
+/// * `atom_count` - Number of atoms in the whole graph.
+/// * `fragments` - Global atom ID of every fragment atom, in the order of the fragment topology,
+///   and the topology of every fragment; together the fragments must cover every atom exactly
+///   once.
+pub(crate) fn merge_fragments(
+    atom_count: usize,
+    mut fragments: Vec<(&[usize], MolecularTopology)>,
+) -> MolecularTopology {
+    fragments.sort_by_key(|(atom_ids, _)| atom_ids.iter().min().copied());
+
+    let mut atoms = vec![None; atom_count];
+    let mut merged = MolecularTopology::default();
+    for (fragment_id, (atom_ids, local)) in fragments.into_iter().enumerate() {
+        let map = |id: usize| atom_ids[id];
+        for mut atom in local.atoms {
+            atom.id = map(atom.id);
+            atom.fragment_id = fragment_id;
+            let id = atom.id;
+            atoms[id] = Some(atom);
+        }
+        merged.bonds.extend(local.bonds.iter().map(|bond| {
+            let (a, b) = bond.atom_ids;
+            bond.renumbered(map(a), map(b))
+        }));
+        merged.angles.extend(local.angles.into_iter().map(|angle| {
+            let (a, center, b) = angle.atom_ids;
+            Angle::new(map(a), map(center), map(b), angle.class)
+        }));
+        let remap_torsion = |torsion: Torsion| {
+            let (i, j, k, l) = torsion.atom_ids;
+            torsion.renumbered(map(i), map(j), map(k), map(l))
+        };
+        merged
+            .torsions
+            .extend(local.torsions.into_iter().map(remap_torsion));
+        merged
+            .suppressed_torsions
+            .extend(local.suppressed_torsions.into_iter().map(remap_torsion));
+        merged
+            .inversions
+            .extend(local.inversions.into_iter().map(|inversion| {
+                let (center, axis, p1, p2) = inversion.atom_ids;
+                Inversion::new(map(center), map(axis), map(p1), map(p2), inversion.class)
+            }));
+        merged
+            .hb_donors
+            .extend(local.hb_donors.into_iter().map(|donor| HydrogenBondDonor {
+                donor_id: map(donor.donor_id),
+                hydrogen_id: map(donor.hydrogen_id),
+            }));
+        merged
+            .hb_acceptors
+            .extend(local.hb_acceptors.into_iter().map(map));
+        merged
+            .rings
+            .extend(local.rings.into_iter().map(|ring| Ring {
+                atom_ids: ring.atom_ids.into_iter().map(map).collect(),
+                ..ring
+            }));
+    }
+
+    merged.atoms = atoms
+        .into_iter()
+        .map(|atom| atom.expect("fragments cover every atom"))
+        .collect();
+    merged.hb_donors.sort_unstable_by_key(|d| d.hydrogen_id);
+    merged.hb_acceptors.sort_unstable();
+    merged.normalize_rings();
+    merged
+}
+
+/// Builds the all-atom topology of a typed molecule with canonical atom types.
+pub(crate) fn build(typed: &TypedMolecule, options: &RunOptions<'_>) -> MolecularTopology {
+    builder::build_topology(
+        &typed.molecule,
+        &typed.atom_types,
+        &typed.provenance,
+        options.suppress_torsions,
+        options.inversion_policy,
+        options.exclude_coordination_torsions,
+    )
+}
+
+/// Turns an assembled topology with canonical types into the requested output.
+///
+/// Copies the atom metadata of `graph`, collapses nonpolar hydrogens in united-atom mode, adds
+/// lone-pair sites if requested, and then applies the optional output label map, so label maps
+/// cover united-atom and site types as well.
+///
+/// # Arguments
+///
+/// * `topology` - All-atom topology whose atom IDs are those of `graph`.
+/// * `graph` - Input graph the topology was assembled from.
+/// * `options` - Options of the run.
+pub(crate) fn finish(
+    mut topology: MolecularTopology,
+    graph: &MolecularGraph,
+    options: &RunOptions<'_>,
+) -> Result<MolecularTopology, TyperError> {
+    for (atom, node) in topology.atoms.iter_mut().zip(&graph.atoms) {
+        atom.metadata.clone_from(&node.metadata);
+    }
+
+    if options.mode == TypingMode::UnitedAtom {
+        builder::collapse_nonpolar_hydrogens(&mut topology);
+    }
+
+    if options.lone_pair_sites {
+        builder::add_lone_pair_sites(&mut topology);
+    }
+
+    if let Some(labels) = options.labels {
+        let canonical: Vec<String> = topology
+            .atoms
+            .iter()
+            .map(|atom| atom.atom_type.to_string())
+            .collect();
+        let relabeled = labels.apply(&canonical)?;
+        for (atom, label) in topology.atoms.iter_mut().zip(relabeled) {
+            atom.atom_type = label.into();
+        }
+    }
+
+    Ok(topology)
+}
+
+/// Perceives and types a small standalone molecule without reporting progress.
+///
+/// Used to type auxiliary molecules, such as residue templates, outside of a user-visible run.
+pub(crate) fn perceive_and_type_standalone(
+    graph: &MolecularGraph,
+    options: &RunOptions<'_>,
+) -> Result<TypedMolecule, TyperError> {
+    let budget = options.budget();
+    let tracker = ProgressTracker::new(None, graph.atoms.len());
+    perceive_and_type(graph, options, &budget, &tracker)
+}
+
+/// Executes the perception and typing phases, stopping before the topology is built.
+pub(crate) fn perceive_and_type(
+    graph: &MolecularGraph,
+    options: &RunOptions<'_>,
+    budget: &Budget,
+    tracker: &ProgressTracker<'_>,
+) -> Result<TypedMolecule, TyperError> {
+    let molecule = perception::perceive(
+        graph,
+        options.bond_images,
+        options.pipeline,
+        options.perception,
+        budget,
+        &mut |step| tracker.report(Stage::from_perception_step(step), 0),
+    )?;
+
+    tracker.report(Stage::Typing, 0);
+    let assignment = typing::engine::assign_types(
+        &molecule,
+        options.rules,
+        options.pinned,
+        budget,
+        &mut |typed| tracker.report(Stage::Typing, typed),
+    )?;
+    let (mut atom_types, provenance) = resolve_types(&molecule, assignment, options)?;
+    for (atom_type, _) in atom_types
+        .iter_mut()
+        .zip(&provenance)
+        .filter(|(_, origin)| **origin != TypeProvenance::Override)
+    {
+        let canonical = options.variant.canonical_type(atom_type);
+        if canonical != atom_type {
+            *atom_type = canonical.to_string();
+        }
+    }
+
+    Ok(TypedMolecule {
+        molecule,
+        atom_types,
+        provenance,
+    })
+}
+
+/// Fills atoms left untyped by the rule engine and records where every type came from.
+///
+/// Pinned atoms are marked as overrides; the fallbacks configured in `options` then type the
+/// atoms no rule matched.
+///
+/// # Errors
+///
+/// Returns [`TyperError::AssignmentFailed`] if atoms remain untyped after the optional UFF
+/// fallback under [`AssignmentPolicy::Strict`].
+fn resolve_types(
+    molecule: &AnnotatedMolecule,
+    mut assignment: TypeAssignment,
+    options: &RunOptions<'_>,
+) -> Result<(Vec<String>, Vec<TypeProvenance>), TyperError> {
+    let mut provenance = vec![TypeProvenance::Rule; assignment.types.len()];
+    for (origin, forced) in provenance.iter_mut().zip(options.pinned) {
+        if forced.is_some() {
+            *origin = TypeProvenance::Override;
+        }
+    }
+
+    if options.uff_fallback {
+        for (atom, slot) in molecule.atoms.iter().zip(&mut assignment.types) {
+            if slot.is_none()
+                && let Some(label) = uff::generic_type(atom.element)
+            {
+                *slot = Some(label.to_string());
+                provenance[atom.id] = TypeProvenance::UffFallback;
+            }
+        }
+    }
+
+    if options.policy == AssignmentPolicy::Fallback {
+        for (atom, slot) in molecule.atoms.iter().zip(&mut assignment.types) {
+            if slot.is_none() {
+                *slot = Some(fallback::element_type(atom.element, atom.hybridization).to_string());
+                provenance[atom.id] = TypeProvenance::ElementFallback;
+            }
+        }
+    }
+    trace::event!(
+        fallback_atoms = provenance
+            .iter()
+            .filter(|&&origin| matches!(
+                origin,
+                TypeProvenance::UffFallback | TypeProvenance::ElementFallback
+            ))
+            .count(),
+        "atom types resolved"
+    );
+
+    Ok((assignment.into_complete(molecule)?, provenance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::error::{LimitExceeded, OverrideError, PerceptionError};
+    use crate::core::metadata::{AtomMetadata, ResidueId};
+    use crate::core::properties::{Element, GraphBondOrder, Hybridization, TopologyBondOrder};
+    use crate::core::stereo::{BondStereo, Chirality};
+    use crate::core::topology::TorsionClass;
+    use crate::typing::rules::parse_rules;
+
+    fn methane() -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        let c = graph.add_atom(Element::C);
+        for _ in 0..4 {
+            let h = graph.add_atom(Element::H);
+            graph
+                .add_bond(c, h, GraphBondOrder::Single)
+                .expect("valid C-H bond");
+        }
+        graph
+    }
+
+    #[test]
+    fn default_typer_uses_embedded_rules() {
+        let typer = Typer::new();
+        assert_eq!(typer.rules(), typing::rules::get_default_rules());
+
+        let topology = typer
+            .assign_topology(&methane())
+            .expect("methane should type");
+        assert_eq!(topology.atoms[0].atom_type, "C_3");
+        assert!(topology.atoms[1..].iter().all(|a| a.atom_type == "H_"));
+    }
+
+    #[test]
+    fn custom_rules_replace_defaults() {
+        let rules = parse_rules(
+            r#"
+            [[rule]]
+            name = "AnyCarbon"
+            priority = 10
+            type = "C_X"
+            conditions = { element = "C" }
+
+            [[rule]]
+            name = "AnyHydrogen"
+            priority = 1
+            type = "H_X"
+            conditions = { element = "H" }
+            "#,
+        )
+        .expect("rules should parse");
+
+        let typer = Typer::with_rules(rules);
+        let topology = typer
+            .assign_topology(&methane())
+            .expect("custom rules cover methane");
+        assert_eq!(topology.atoms[0].atom_type, "C_X");
+        assert_eq!(topology.atoms[4].atom_type, "H_X");
+    }
+
+    #[test]
+    fn overridden_atoms_keep_their_type_and_steer_neighbor_rules() {
+        let rules = parse_rules(
+            r#"
+            [[rule]]
+            name = "AnyCarbon"
+            priority = 10
+            type = "C_X"
+            conditions = { element = "C" }
+
+            [[rule]]
+            name = "HydrogenOnPinnedCarbon"
+            priority = 10
+            type = "H_P"
+            conditions = { element = "H", neighbor_types = { "C_PIN" = 1 } }
+
+            [[rule]]
+            name = "AnyHydrogen"
+            priority = 1
+            type = "H_X"
+            conditions = { element = "H" }
+            "#,
+        )
+        .expect("rules should parse");
+
+        // The zinc comes first, so the methane fragment's local IDs differ from its graph IDs.
+        let mut graph = MolecularGraph::new();
+        let zn = graph.add_atom(Element::Zn);
+        graph
+            .set_atom_metadata(zn, AtomMetadata::named("ZN"))
+            .unwrap();
+        let c = graph.add_atom(Element::C);
+        for _ in 0..4 {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+        }
+        let mut overrides = TypingOverrides::new();
+        overrides.pin_named("ZN", None, "Zn");
+        overrides.pin(c, "C_PIN");
+
+        let topology = Typer::with_rules(rules)
+            .assign_topology_with_overrides(&graph, &overrides)
+            .expect("overrides cover the atoms no rule types");
+        assert_eq!(topology.atoms[zn].atom_type, "Zn");
+        assert_eq!(topology.atoms[c].atom_type, "C_PIN");
+        assert_eq!(topology.atoms[c].provenance, TypeProvenance::Override);
+        assert!(
+            topology.atoms[2..]
+                .iter()
+                .all(|atom| atom.atom_type == "H_P" && atom.provenance == TypeProvenance::Rule)
+        );
+        assert_eq!(topology.fallback_atoms().count(), 0);
+
+        overrides.pin(9, "X_");
+        assert!(matches!(
+            Typer::new().assign_topology_with_overrides(&graph, &overrides),
+            Err(TyperError::InvalidOverride(OverrideError::AtomOutOfRange {
+                atom_id: 9,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn higher_precedence_pack_overrides_defaults() {
+        let custom = RulePack::parse(
+            r#"
+            namespace = "lab-custom"
+
+            [[rule]]
+            name = "MethaneCarbon"
+            priority = 0
+            type = "C_M"
+            conditions = { element = "C", neighbor_elements = { H = 4 } }
+            "#,
+        )
+        .expect("pack should parse");
+
+        let layered = Typer::with_rule_packs(&[RulePack::default_dreiding(), custom.clone()]);
+        let topology = layered
+            .assign_topology(&methane())
+            .expect("methane should type");
+        assert_eq!(topology.atoms[0].atom_type, "C_M");
+        assert_eq!(topology.atoms[1].atom_type, "H_");
+
+        let reversed = Typer::with_rule_packs(&[custom, RulePack::default_dreiding()]);
+        let topology = reversed
+            .assign_topology(&methane())
+            .expect("methane should type");
+        assert_eq!(topology.atoms[0].atom_type, "C_3");
+    }
+
+    #[test]
+    fn strained_ring_rules_can_be_remapped_by_name() {
+        // Methyloxirane: the ring carbons and oxygen, then the methyl carbon.
+        let graph = crate::io::smiles::parse_smiles("C1OC1C").unwrap();
+        let heavy_types = |typer: Typer| -> Vec<String> {
+            let topology = typer
+                .assign_topology(&graph)
+                .expect("methyloxirane should type");
+            topology.atoms[..4]
+                .iter()
+                .map(|atom| atom.atom_type.to_string())
+                .collect()
+        };
+        assert_eq!(heavy_types(Typer::new()), ["C_3", "O_3", "C_3", "C_3"]);
+
+        let strained = crate::typing::ruleset::RulesetBuilder::with_defaults()
+            .with_toml(
+                r#"
+                [[rule]]
+                name = "C_Tetrahedral_SP3_Ring3"
+                priority = 110
+                type = "C_3R3"
+                conditions = { element = "C", hybridization = "SP3", smallest_ring_size = 3 }
+
+                [[rule]]
+                name = "O_Tetrahedral_SP3_Ring3"
+                priority = 110
+                type = "O_3R3"
+                conditions = { element = "O", hybridization = "SP3", smallest_ring_size = 3 }
+                "#,
+            )
+            .expect("rules should parse")
+            .build();
+        assert_eq!(strained.len(), typing::rules::get_default_rules().len());
+        assert_eq!(
+            heavy_types(Typer::with_rule_set(strained)),
+            ["C_3R3", "O_3R3", "C_3R3", "C_3"]
+        );
+    }
+
+    #[test]
+    fn trusted_input_aromaticity_replaces_huckel_perception() {
+        let trusting = Typer::new().with_perception_options(PerceptionOptions {
+            trust_input_aromaticity: true,
+            ..PerceptionOptions::DEFAULT
+        });
+        let type_smiles = |typer: &Typer, smiles: &str| {
+            let graph = crate::io::smiles::parse_smiles(smiles).unwrap();
+            typer.assign_topology(&graph).expect("molecule should type")
+        };
+        let resonant_bonds = |topology: &MolecularTopology| {
+            topology
+                .bonds
+                .iter()
+                .filter(|bond| bond.order == TopologyBondOrder::Resonant)
+                .count()
+        };
+
+        // Biphenylene: its benzenes are joined by two single bonds that Hückel counting
+        // folds into one aromatic system.
+        let biphenylene = "c1ccc2c(c1)-c1ccccc1-2";
+        assert_eq!(resonant_bonds(&type_smiles(&Typer::new(), biphenylene)), 14);
+        assert_eq!(resonant_bonds(&type_smiles(&trusting, biphenylene)), 12);
+
+        // Kekulé benzene gives no aromatic bonds to trust.
+        let topology = type_smiles(&trusting, "C1=CC=CC=C1");
+        assert!(
+            topology.atoms[..6]
+                .iter()
+                .all(|atom| atom.atom_type == "C_2")
+        );
+        assert!(!topology.rings[0].is_aromatic);
+        assert_eq!(resonant_bonds(&topology), 0);
+    }
+
+    #[test]
+    fn progress_observer_sees_every_stage_in_order() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let typer = Typer::new().with_progress_observer(move |progress: &Progress| {
+            sink.lock().unwrap().push(*progress);
+        });
+
+        typer
+            .assign_topology(&methane())
+            .expect("methane should type");
+
+        let seen = seen.lock().unwrap();
+        let mut stages: Vec<Stage> = seen.iter().map(|p| p.stage).collect();
+        stages.dedup();
+        assert_eq!(stages, Stage::ALL);
+
+        let typed = seen
+            .iter()
+            .filter(|p| p.stage == Stage::Typing)
+            .map(|p| p.atoms_processed)
+            .max();
+        assert_eq!(typed, Some(5));
+        assert!(seen.iter().all(|p| p.total_atoms == 5));
+        assert!(seen.windows(2).all(|w| w[0].fraction() <= w[1].fraction()));
+    }
+
+    fn benzene() -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        let carbons: Vec<_> = (0..6).map(|_| graph.add_atom(Element::C)).collect();
+        for i in 0..6 {
+            graph
+                .add_bond(carbons[i], carbons[(i + 1) % 6], GraphBondOrder::Aromatic)
+                .expect("valid aromatic bond");
+            let h = graph.add_atom(Element::H);
+            graph
+                .add_bond(carbons[i], h, GraphBondOrder::Single)
+                .expect("valid C-H bond");
+        }
+        graph
+    }
+
+    #[test]
+    fn default_rules_type_common_molecules_unambiguously() {
+        let typer = Typer::new();
+        for graph in [methane(), benzene(), zinc_ion_and_water()] {
+            let diagnostics = typer
+                .assignment_diagnostics(&graph)
+                .expect("diagnostics should run");
+            assert!(diagnostics.is_unambiguous(), "{:?}", diagnostics.ambiguous);
+            assert_eq!(diagnostics.confidence, vec![1.0; graph.atoms.len()]);
+        }
+    }
+
+    fn run_with(graph: &MolecularGraph, limits: ResourceLimits) -> Result<(), TyperError> {
+        Typer::new()
+            .with_limits(limits)
+            .assign_topology(graph)
+            .map(|_| ())
+    }
+
+    #[test]
+    fn kekule_step_limit_aborts_perception() {
+        let limits = ResourceLimits {
+            max_kekule_steps: Some(2),
+            ..ResourceLimits::default()
+        };
+        match run_with(&benzene(), limits) {
+            Err(TyperError::PerceptionFailed {
+                step,
+                stage,
+                source: PerceptionError::LimitExceeded(LimitExceeded::KekuleSteps { limit: 2 }),
+            }) => {
+                assert_eq!(step, "Kekulization");
+                assert_eq!(stage, Stage::Kekulization);
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert!(run_with(&benzene(), ResourceLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn ring_candidate_limit_aborts_perception() {
+        let limits = ResourceLimits {
+            max_ring_candidates: Some(3),
+            ..ResourceLimits::default()
+        };
+        assert!(matches!(
+            run_with(&benzene(), limits),
+            Err(TyperError::PerceptionFailed {
+                source: PerceptionError::LimitExceeded(LimitExceeded::RingCandidates { limit: 3 }),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn typing_round_limit_is_reported() {
+        let limits = ResourceLimits {
+            max_typing_rounds: Some(1),
+            ..ResourceLimits::default()
+        };
+        assert!(matches!(
+            run_with(&methane(), limits),
+            Err(TyperError::LimitExceeded(LimitExceeded::TypingRounds {
+                limit: 1,
+                ..
+            }))
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn exhausted_time_budget_is_reported() {
+        let limits = ResourceLimits {
+            time_budget: Some(std::time::Duration::ZERO),
+            ..ResourceLimits::default()
+        };
+        assert!(matches!(
+            run_with(&methane(), limits),
+            Err(TyperError::LimitExceeded(LimitExceeded::TimeBudget { .. }))
+        ));
+        assert!(run_with(&methane(), ResourceLimits::unlimited()).is_ok());
+    }
+
+    #[test]
+    fn cancellation_stops_the_run_at_the_next_checkpoint() {
+        let token = CancellationToken::new();
+        let handle = token.clone();
+        let stages = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&stages);
+        let typer = Typer::new()
+            .with_cancellation(token)
+            .with_progress_observer(move |progress: &Progress| {
+                sink.lock().unwrap().push(progress.stage);
+                if progress.stage == Stage::Aromaticity {
+                    handle.cancel();
+                }
+            });
+
+        assert!(matches!(
+            typer.assign_topology(&benzene()),
+            Err(TyperError::LimitExceeded(LimitExceeded::Cancelled))
+        ));
+        assert_eq!(stages.lock().unwrap().last(), Some(&Stage::Aromaticity));
+        assert!(
+            typer
+                .cancellation()
+                .is_some_and(CancellationToken::is_cancelled)
+        );
+        assert!(Typer::new().assign_topology(&benzene()).is_ok());
+    }
+
+    #[test]
+    fn label_map_renames_output_types() {
+        let labels: TypeLabelMap = [("C_3", "CT"), ("H_", "HC")].into_iter().collect();
+        let topology = Typer::new()
+            .with_label_map(labels)
+            .assign_topology(&methane())
+            .expect("label map covers methane");
+        assert_eq!(topology.atoms[0].atom_type, "CT");
+        assert!(topology.atoms[1..].iter().all(|a| a.atom_type == "HC"));
+    }
+
+    #[test]
+    fn label_maps_apply_to_united_atom_types() {
+        let united = Typer::new().with_typing_mode(TypingMode::UnitedAtom);
+        let all_atom: TypeLabelMap = [("C_3", "CT"), ("H_", "HC")].into_iter().collect();
+        assert!(matches!(
+            united
+                .clone()
+                .with_label_map(all_atom)
+                .assign_topology(&methane()),
+            Err(TyperError::LabelMapping(_))
+        ));
+
+        let labels: TypeLabelMap = [("C_34", "CH4")].into_iter().collect();
+        let topology = united
+            .with_label_map(labels)
+            .assign_topology(&methane())
+            .expect("label map covers united methane");
+        assert_eq!(topology.atoms.len(), 1);
+        assert_eq!(topology.atoms[0].atom_type, "CH4");
+    }
+
+    #[test]
+    fn stereo_descriptors_follow_the_united_atom_collapse() {
+        let graph = crate::io::smiles::parse_smiles("F[C@H](Cl)/C=C/Br").unwrap();
+        let all_atom = Typer::new().assign_topology(&graph).unwrap();
+        assert_eq!(all_atom.atoms[1].chirality, graph.atoms[1].chirality);
+        let double_bond = |topology: &MolecularTopology| {
+            topology
+                .bonds
+                .iter()
+                .find(|bond| bond.atom_ids == (3, 4))
+                .and_then(|bond| bond.stereo)
+        };
+        assert_eq!(double_bond(&all_atom), Some(BondStereo::Trans));
+
+        // Dropping the hydrogen (ID 6) moves the missing substituent in front of F0, Cl2, and C3.
+        let united = Typer::new()
+            .with_typing_mode(TypingMode::UnitedAtom)
+            .assign_topology(&graph)
+            .unwrap();
+        assert_eq!(united.atoms.len(), 6);
+        assert_eq!(
+            united.atoms[1].chirality,
+            graph.atoms[1].chirality.map(Chirality::inverted)
+        );
+        assert_eq!(double_bond(&united), Some(BondStereo::Trans));
+    }
+
+    #[test]
+    fn atom_metadata_survives_fragments_and_the_united_atom_collapse() {
+        let mut graph = crate::io::smiles::parse_smiles("[Na+].CO").unwrap();
+        for (id, name) in [(0, "NA"), (1, "C1"), (2, "O1"), (6, "HO")] {
+            let metadata =
+                AtomMetadata::named(name).with_residue(ResidueId::new("MOH", 'A', id as i32));
+            graph.set_atom_metadata(id, metadata).unwrap();
+        }
+
+        for mode in [TypingMode::AllAtom, TypingMode::UnitedAtom] {
+            let topology = Typer::new()
+                .with_typing_mode(mode)
+                .assign_topology(&graph)
+                .unwrap();
+            let names: Vec<_> = topology
+                .atoms
+                .iter()
+                .filter_map(|atom| atom.metadata.name.as_deref())
+                .collect();
+            assert_eq!(names, ["NA", "C1", "O1", "HO"]);
+            let hydroxyl = topology.atoms.last().unwrap();
+            assert_eq!(hydroxyl.metadata, graph.atoms[6].metadata);
+        }
+    }
+
+    #[test]
+    fn rings_of_separate_fragments_form_separate_systems() {
+        let graph = crate::io::smiles::parse_smiles("C1CC1.c1ccc2ccccc2c1").unwrap();
+        let topology = Typer::new().assign_topology(&graph).unwrap();
+        let rings: Vec<_> = topology
+            .rings
+            .iter()
+            .map(|ring| (ring.size(), ring.is_aromatic, ring.system_id))
+            .collect();
+        assert_eq!(rings, [(3, false, 0), (6, true, 1), (6, true, 1)]);
+        assert!(topology.rings[1].atom_ids.iter().all(|&id| id >= 3));
+    }
+
+    #[test]
+    fn inversion_policy_selects_terms_per_planar_center() {
+        // Formamide: planar carbon 1 and resonant nitrogen 0.
+        let graph = crate::io::smiles::parse_smiles("NC=O").unwrap();
+        let inversions = |policy| {
+            Typer::new()
+                .with_inversion_policy(policy)
+                .assign_topology(&graph)
+                .unwrap()
+                .inversions
+        };
+
+        assert_eq!(inversions(InversionPolicy::AllAxes).len(), 6);
+        let single = inversions(InversionPolicy::SingleCanonical);
+        let mut axes: Vec<_> = single
+            .iter()
+            .map(|i| (i.atom_ids.0, i.atom_ids.1))
+            .collect();
+        axes.sort_unstable();
+        assert_eq!(axes, [(0, 1), (1, 2)]);
+        let nitrogen = inversions(InversionPolicy::UmbrellaOnlyForNitrogen);
+        assert_eq!(nitrogen.len(), 3);
+        assert!(nitrogen.iter().all(|i| i.atom_ids.0 == 0));
+    }
+
+    #[test]
+    fn hydrogen_bond_lists_use_canonical_types() {
+        let mut water = MolecularGraph::new();
+        let o = water.add_atom(Element::O);
+        for _ in 0..2 {
+            let h = water.add_atom(Element::H);
+            water
+                .add_bond(o, h, GraphBondOrder::Single)
+                .expect("valid O-H bond");
+        }
+
+        let labels: TypeLabelMap = [("O_3", "OW"), ("H_HB", "HW")].into_iter().collect();
+        let topology = Typer::new()
+            .with_label_map(labels)
+            .assign_topology(&water)
+            .expect("water should type");
+
+        let donors: Vec<_> = topology
+            .hb_donors
+            .iter()
+            .map(|d| (d.donor_id, d.hydrogen_id))
+            .collect();
+        assert_eq!(donors, [(0, 1), (0, 2)]);
+        assert_eq!(topology.hb_acceptors, [0]);
+
+        let topology = Typer::new()
+            .with_variant(ForceFieldVariant::DreidingA)
+            .assign_topology(&water)
+            .expect("water should type under DREIDING/A");
+        assert!(topology.atoms[1..].iter().all(|a| a.atom_type == "H_"));
+        assert!(topology.hb_donors.is_empty());
+        assert_eq!(topology.hb_acceptors, [0]);
+    }
+
+    #[test]
+    fn incomplete_label_map_is_rejected() {
+        let labels: TypeLabelMap = [("C_3", "CT")].into_iter().collect();
+        match Typer::new()
+            .with_label_map(labels)
+            .assign_topology(&methane())
+        {
+            Err(TyperError::LabelMapping(err)) => assert_eq!(err.missing, ["H_"]),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    fn zinc_ion_and_water() -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        graph.add_atom(Element::Zn);
+        let o = graph.add_atom(Element::O);
+        for _ in 0..2 {
+            let h = graph.add_atom(Element::H);
+            graph
+                .add_bond(o, h, GraphBondOrder::Single)
+                .expect("valid O-H bond");
+        }
+        graph
+    }
+
+    #[test]
+    fn uff_fallback_types_uncovered_atoms_with_provenance() {
+        let rules = parse_rules(
+            r#"
+            [[rule]]
+            name = "AnyOxygen"
+            priority = 1
+            type = "O_3"
+            conditions = { element = "O" }
+
+            [[rule]]
+            name = "AnyHydrogen"
+            priority = 1
+            type = "H_HB"
+            conditions = { element = "H" }
+            "#,
+        )
+        .expect("rules should parse");
+
+        let strict = Typer::with_rules(rules.clone()).assign_topology(&zinc_ion_and_water());
+        assert!(matches!(strict, Err(TyperError::AssignmentFailed(_))));
+
+        let topology = Typer::with_rules(rules)
+            .with_uff_fallback(true)
+            .assign_topology(&zinc_ion_and_water())
+            .expect("zinc falls back to UFF");
+        assert_eq!(topology.atoms[0].atom_type, "Zn3+2");
+        assert_eq!(topology.atoms[0].provenance, TypeProvenance::UffFallback);
+        assert!(
+            topology.atoms[1..]
+                .iter()
+                .all(|a| a.provenance == TypeProvenance::Rule)
+        );
+    }
+
+    #[test]
+    fn fallback_policy_types_untyped_atoms_by_element() {
+        let rules = parse_rules(
+            r#"
+            [[rule]]
+            name = "AnyHydrogen"
+            priority = 1
+            type = "H_"
+            conditions = { element = "H" }
+            "#,
+        )
+        .expect("rules should parse");
+
+        let strict = Typer::with_rules(rules.clone()).assign_topology(&methane());
+        assert!(matches!(strict, Err(TyperError::AssignmentFailed(_))));
+
+        let typer = Typer::with_rules(rules).with_assignment_policy(AssignmentPolicy::Fallback);
+        let topology = typer
+            .assign_topology(&methane())
+            .expect("carbon falls back to its element type");
+        assert_eq!(topology.atoms[0].atom_type, "C_3");
+        assert_eq!(
+            topology.atoms[0].provenance,
+            TypeProvenance::ElementFallback
+        );
+        let warnings: Vec<usize> = topology.fallback_atoms().map(|atom| atom.id).collect();
+        assert_eq!(warnings, vec![0]);
+
+        let atom_types = typer
+            .assign_atom_types(&methane())
+            .expect("atom types follow the same policy");
+        assert_eq!(atom_types[0], "C_3");
+    }
+
+    #[test]
+    fn errors_name_input_atoms_of_later_fragments() {
+        let mut graph = methane();
+        let la = graph.add_atom(Element::La);
+        let c = graph.add_atom(Element::C);
+        graph
+            .add_bond(la, c, GraphBondOrder::Single)
+            .expect("valid La-C bond");
+
+        let error = Typer::new()
+            .assign_topology(&graph)
+            .expect_err("lanthanum has no DREIDING type");
+        assert_eq!(error.atom_ids(), vec![la]);
+        match error {
+            TyperError::AssignmentFailed(error) => {
+                let atom = &error.untyped_atoms[0];
+                assert_eq!(atom.atom_id, la);
+                assert_eq!(atom.element, Element::La);
+                assert_eq!(atom.neighbors, vec![Element::C]);
+                assert!(
+                    error
+                        .to_string()
+                        .contains(&format!("atom {la} (La bonded to C)"))
+                );
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn uff_fallback_runs_before_the_element_fallback() {
+        let mut graph = MolecularGraph::new();
+        let zn = graph.add_atom(Element::Zn);
+        let og = graph.add_atom(Element::Og);
+
+        let topology = Typer::with_rules(Vec::new())
+            .with_uff_fallback(true)
+            .with_assignment_policy(AssignmentPolicy::Fallback)
+            .assign_topology(&graph)
+            .expect("every atom falls back");
+        assert_eq!(topology.atoms[zn].atom_type, "Zn3+2");
+        assert_eq!(topology.atoms[zn].provenance, TypeProvenance::UffFallback);
+        assert_eq!(topology.atoms[og].atom_type, fallback::UNKNOWN_TYPE);
+        assert_eq!(
+            topology.atoms[og].provenance,
+            TypeProvenance::ElementFallback
+        );
+        assert_eq!(topology.fallback_atoms().count(), 2);
+    }
+
+    #[test]
+    fn metal_ions_and_bonded_metal_centers_are_typed() {
+        let mut graph = MolecularGraph::new();
+        for element in [Element::Na, Element::Ca] {
+            graph.add_atom(element);
+        }
+        for metal in [
+            Element::Fe,
+            Element::Zn,
+            Element::Ti,
+            Element::Tc,
+            Element::Ru,
+        ] {
+            let center = graph.add_atom(metal);
+            let o = graph.add_atom(Element::O);
+            graph
+                .add_bond(center, o, GraphBondOrder::Single)
+                .expect("valid M-O bond");
+            for _ in 0..2 {
+                let h = graph.add_atom(Element::H);
+                graph
+                    .add_bond(o, h, GraphBondOrder::Single)
+                    .expect("valid O-H bond");
+            }
+        }
+
+        let topology = Typer::new()
+            .assign_topology(&graph)
+            .expect("metals should type");
+        let types: Vec<_> = topology
+            .atoms
+            .iter()
+            .filter(|a| a.element.is_metal_center())
+            .map(|a| a.atom_type.as_str())
+            .collect();
+        assert_eq!(types, ["Na", "Ca", "Fe", "Zn", "Ti", "Tc", "Ru"]);
+        assert!(
+            topology
+                .atoms
+                .iter()
+                .filter(|a| a.element == Element::O)
+                .all(|a| a.atom_type == "O_3")
+        );
+        assert!(
+            topology
+                .atoms
+                .iter()
+                .filter(|a| a.element == Element::H)
+                .all(|a| a.atom_type == "H_HB")
+        );
+    }
+
+    #[test]
+    fn hypervalent_sulfur_and_phosphorus_type_alike_however_drawn() {
+        let heavy_types = |smiles: &str| {
+            let graph = crate::io::smiles::parse_smiles(smiles).expect("valid SMILES");
+            let topology = Typer::new().assign_topology(&graph).expect("typing");
+            topology
+                .atoms
+                .iter()
+                .filter(|a| a.element != Element::H)
+                .map(|a| a.atom_type.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        for (smiles, expected) in [
+            ("CS(C)=O", ["C_3", "S_3", "C_3", "O_2"].as_slice()),
+            ("C[S+](C)[O-]", &["C_3", "S_3", "C_3", "O_2"]),
+            ("CS(C)(=O)=O", &["C_3", "S_3", "C_3", "O_2", "O_2"]),
+            ("C[S+2](C)([O-])[O-]", &["C_3", "S_3", "C_3", "O_2", "O_2"]),
+            ("OS(=O)(=O)O", &["O_3", "S_3", "O_2", "O_2", "O_3"]),
+            (
+                "[O-][S+2]([O-])([O-])[O-]",
+                &["O_2", "S_3", "O_2", "O_2", "O_2"],
+            ),
+            ("OP(=O)(O)O", &["O_3", "P_3", "O_2", "O_3", "O_3"]),
+            ("CP(=O)([O-])[O-]", &["C_3", "P_3", "O_2", "O_2", "O_2"]),
+            ("CP(C)(C)=O", &["C_3", "P_3", "C_3", "C_3", "O_2"]),
+            ("C[P+](C)(C)[O-]", &["C_3", "P_3", "C_3", "C_3", "O_2"]),
+        ] {
+            assert_eq!(heavy_types(smiles), expected, "{smiles}");
+        }
+
+        // The lone pair of an aryl sulfoxide does not join the ring's π system.
+        assert_eq!(heavy_types("CS(=O)c1ccccc1")[1], "S_3");
+    }
+
+    #[test]
+    fn charged_and_radical_carbons_follow_their_stated_state() {
+        let heavy_types = |smiles: &str| {
+            let graph = crate::io::smiles::parse_smiles(smiles).expect("valid SMILES");
+            let topology = Typer::new().assign_topology(&graph).expect("typing");
+            topology
+                .atoms
+                .iter()
+                .filter(|a| a.element != Element::H)
+                .map(|a| a.atom_type.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        for (smiles, expected) in [
+            ("C[CH2+]", ["C_3", "C_2"].as_slice()),
+            ("C[CH2-]", &["C_3", "C_3"]),
+            ("C[CH2]", &["C_3", "C_2"]),
+            ("C=C[CH2+]", &["C_R"; 3]),
+            ("[CH2+]C=C", &["C_R"; 3]),
+            ("[CH2]C=CC=C", &["C_R"; 5]),
+            ("[CH2-]C(C)=O", &["C_R", "C_R", "C_3", "O_2"]),
+            ("c1ccccc1[CH2-]", &["C_R"; 7]),
+            ("CC(C)(C)[O]", &["C_3", "C_3", "C_3", "C_3", "O_3"]),
+            ("[N]=O", &["N_2", "O_2"]),
+        ] {
+            assert_eq!(heavy_types(smiles), expected, "{smiles}");
+        }
+        // Trityl radical: the central carbon is conjugated with all three rings.
+        assert_eq!(heavy_types("[C](c1ccccc1)(c1ccccc1)c1ccccc1")[0], "C_R");
+
+        // Both C-C bonds of the allyl cation are delocalized, not only its C=C bond.
+        let allyl = crate::io::smiles::parse_smiles("[CH2+]C=C").unwrap();
+        let topology = Typer::new().assign_topology(&allyl).expect("typing");
+        let carbon_bonds: Vec<_> = topology
+            .bonds
+            .iter()
+            .filter(|bond| bond.atom_ids.0 < 3 && bond.atom_ids.1 < 3)
+            .map(|bond| bond.order)
+            .collect();
+        assert_eq!(carbon_bonds, [TopologyBondOrder::Resonant; 2]);
+
+        // Without a stated state, a three-coordinate carbon keeps its octet as a carbanion.
+        let mut graph = crate::io::smiles::parse_smiles("[CH3+]").unwrap();
+        graph.set_electron_state(0, None, 0).unwrap();
+        assert_eq!(crate::assign_atom_types(&graph).unwrap()[0], "C_3");
+    }
+
+    #[test]
+    fn dummy_sites_are_kept_without_changing_their_parents() {
+        // TIP5P-style water: two lone-pair sites bonded to the oxygen, plus a free dummy atom.
+        let mut graph = crate::io::smiles::parse_smiles("[2H]O[2H].CO").unwrap();
+        let oxygen = 1;
+        let sites: Vec<usize> = (0..2)
+            .map(|_| {
+                let site = graph.add_atom(Element::Dummy);
+                graph
+                    .add_bond(oxygen, site, GraphBondOrder::Single)
+                    .unwrap();
+                site
+            })
+            .collect();
+        let free = graph.add_atom(Element::Dummy);
+
+        let topology = Typer::new().assign_topology(&graph).expect("typing");
+        let types: Vec<&str> = topology
+            .atoms
+            .iter()
+            .map(|a| a.atom_type.as_str())
+            .collect();
+        assert_eq!(&types[..5], ["H_HB", "O_3", "H_HB", "C_3", "O_3"]);
+        for id in sites.iter().copied().chain([free]) {
+            assert_eq!(types[id], "Du");
+            assert_eq!(topology.atoms[id].hybridization, Hybridization::None);
+        }
+
+        let is_site = |id: usize| sites.contains(&id);
+        let site_bonds = topology
+            .bonds
+            .iter()
+            .filter(|bond| is_site(bond.atom_ids.0) || is_site(bond.atom_ids.1))
+            .count();
+        assert_eq!(site_bonds, 2);
+        assert!(topology.angles.iter().all(|angle| {
+            let (a, center, b) = angle.atom_ids;
+            !is_site(a) && !is_site(center) && !is_site(b)
+        }));
+        assert_eq!(
+            topology
+                .hb_donors
+                .iter()
+                .filter(|d| d.donor_id == oxygen)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn metalloporphyrin_cores_stay_resonant_through_the_metal() {
+        // Dianionic ligands with the indices of their four pyrrole nitrogens.
+        let porphyrin = "C1=CC2=CC3=CC=C([N-]3)C=C4C=CC(=N4)C=C5C=CC(=N5)C=C1[N-]2";
+        let phthalocyanine = "C1=CC=C2C(=C1)C3=NC4=C5C=CC=CC5=C([N-]4)N=C6C7=CC=CC=C7C(=N6)\
+                              N=C8C9=CC=CC=C9C(=N8)N=C2[N-]3";
+        for (smiles, pyrrole_nitrogens) in [
+            (porphyrin, [8, 14, 20, 23]),
+            (phthalocyanine, [16, 26, 36, 39]),
+        ] {
+            let smiles: String = smiles.split_whitespace().collect();
+            let mut graph = crate::io::smiles::parse_smiles(&smiles).expect("valid SMILES");
+            let zn = graph.add_atom(Element::Zn);
+            for n in pyrrole_nitrogens {
+                graph
+                    .add_bond(zn, n, GraphBondOrder::Single)
+                    .expect("valid Zn-N bond");
+            }
+
+            let topology = Typer::new()
+                .assign_topology(&graph)
+                .expect("metalloporphyrins should type");
+            let is_ligand =
+                |id: usize| !matches!(topology.atoms[id].element, Element::H | Element::Zn);
+            assert!(
+                (0..topology.atoms.len())
+                    .filter(|&id| is_ligand(id))
+                    .all(|id| matches!(topology.atoms[id].atom_type.as_str(), "C_R" | "N_R")),
+                "{smiles}"
+            );
+            for bond in &topology.bonds {
+                let (a, b) = bond.atom_ids;
+                if is_ligand(a) && is_ligand(b) {
+                    assert_eq!(bond.order, TopologyBondOrder::Resonant, "{smiles}: {a}-{b}");
+                } else if a == zn || b == zn {
+                    assert_eq!(bond.order, TopologyBondOrder::Single);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn disconnected_fragments_receive_stable_fragment_ids() {
+        let mut graph = MolecularGraph::new();
+        let o = graph.add_atom(Element::O);
+        graph.add_atom(Element::Na);
+        for _ in 0..2 {
+            let h = graph.add_atom(Element::H);
+            graph
+                .add_bond(o, h, GraphBondOrder::Single)
+                .expect("valid O-H bond");
+        }
+        graph.add_atom(Element::Cl);
+
+        let topology = Typer::new()
+            .assign_topology(&graph)
+            .expect("fragments should type");
+        let fragment_ids: Vec<_> = topology.atoms.iter().map(|a| a.fragment_id).collect();
+        assert_eq!(fragment_ids, [0, 1, 0, 0, 2]);
+        assert_eq!(topology.molecule_indices(), fragment_ids);
+        let types: Vec<_> = topology
+            .atoms
+            .iter()
+            .map(|a| a.atom_type.as_str())
+            .collect();
+        assert_eq!(types, ["O_3", "Na", "H_HB", "H_HB", "Cl"]);
+        assert_eq!(topology.bonds.len(), 2);
+        assert_eq!(topology.angles[0].atom_ids, (2, 0, 3));
+        assert_eq!(topology.hb_acceptors, [0]);
+    }
+
+    #[test]
+    fn atom_types_match_the_full_topology_across_fragments() {
+        let mut graph = MolecularGraph::new();
+        let o = graph.add_atom(Element::O);
+        graph.add_atom(Element::Na);
+        for _ in 0..2 {
+            let h = graph.add_atom(Element::H);
+            graph
+                .add_bond(o, h, GraphBondOrder::Single)
+                .expect("valid O-H bond");
+        }
+
+        for typer in [
+            Typer::new(),
+            Typer::new().with_variant(ForceFieldVariant::DreidingA),
+        ] {
+            let topology = typer
+                .assign_topology(&graph)
+                .expect("fragments should type");
+            let expected: Vec<_> = topology.atoms.iter().map(|a| a.atom_type.clone()).collect();
+            assert_eq!(typer.assign_atom_types(&graph).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn suppressed_torsions_are_remapped_across_fragments() {
+        let mut graph = methane();
+        let c1 = graph.add_atom(Element::C);
+        let c2 = graph.add_atom(Element::C);
+        let n = graph.add_atom(Element::N);
+        graph
+            .add_bond(c1, c2, GraphBondOrder::Single)
+            .expect("valid C-C bond");
+        graph
+            .add_bond(c2, n, GraphBondOrder::Triple)
+            .expect("valid C-N bond");
+        for _ in 0..3 {
+            let h = graph.add_atom(Element::H);
+            graph
+                .add_bond(c1, h, GraphBondOrder::Single)
+                .expect("valid C-H bond");
+        }
+
+        let full = Typer::new().assign_topology(&graph).unwrap();
+        assert_eq!(full.torsions.len(), 3);
+        assert!(full.suppressed_torsions.is_empty());
+
+        let suppressed = Typer::new()
+            .with_torsion_suppression(true)
+            .assign_topology(&graph)
+            .unwrap();
+        assert!(suppressed.torsions.is_empty());
+        assert_eq!(suppressed.suppressed_torsions.len(), 3);
+        for torsion in &suppressed.suppressed_torsions {
+            let (_, j, k, _) = torsion.atom_ids;
+            assert_eq!((j.min(k), j.max(k)), (c1, c2));
+            assert_eq!(torsion.class, TorsionClass::Linear);
+        }
+    }
+
+    /// Graphene in its rectangular cell of four atoms, whose hexagons each pass through two
+    /// images of two cell atoms.
+    fn rectangular_graphene(order: impl Fn(bool) -> GraphBondOrder) -> PeriodicMolecularGraph {
+        let mut cell =
+            PeriodicMolecularGraph::new([[4.26, 0.0, 0.0], [0.0, 2.46, 0.0], [0.0, 0.0, 10.0]]);
+        let c: Vec<usize> = (0..4).map(|_| cell.add_atom(Element::C)).collect();
+        for (a, b, image, is_double) in [
+            (0, 1, [0, 0, 0], true),
+            (1, 2, [0, 0, 0], false),
+            (2, 3, [0, 0, 0], true),
+            (3, 0, [1, 0, 0], false),
+            (1, 2, [0, -1, 0], false),
+            (3, 0, [1, 1, 0], false),
+        ] {
+            cell.add_periodic_bond(c[a], c[b], order(is_double), image)
+                .expect("valid graphene bond");
+        }
+        cell
+    }
+
+    #[test]
+    fn graphene_rings_larger_than_the_cell_are_aromatic() {
+        let kekule = |is_double| {
+            if is_double {
+                GraphBondOrder::Double
+            } else {
+                GraphBondOrder::Single
+            }
+        };
+        for cell in [
+            rectangular_graphene(kekule),
+            rectangular_graphene(|_| GraphBondOrder::Aromatic),
+        ] {
+            let topology = Typer::new()
+                .assign_periodic_topology(&cell)
+                .expect("graphene should be typed");
+
+            assert_eq!(topology.rings.len(), 2);
+            assert!(
+                topology
+                    .rings
+                    .iter()
+                    .all(|ring| ring.size() == 6 && ring.is_aromatic)
+            );
+            assert!(topology.atoms.iter().all(|atom| atom.atom_type == "C_R"));
+        }
+    }
+
+    #[test]
+    fn rings_wrapping_several_cell_faces_are_found() {
+        // Primitive graphene: each hexagon passes through three images of both atoms and
+        // crosses both the a- and b-faces of the cell.
+        let mut graphene =
+            PeriodicMolecularGraph::new([[2.46, 0.0, 0.0], [-1.23, 2.13, 0.0], [0.0, 0.0, 10.0]]);
+        let a = graphene.add_atom(Element::C);
+        let b = graphene.add_atom(Element::C);
+        for (image, order) in [
+            ([0, 0, 0], GraphBondOrder::Double),
+            ([-1, 0, 0], GraphBondOrder::Single),
+            ([0, -1, 0], GraphBondOrder::Single),
+        ] {
+            graphene
+                .add_periodic_bond(a, b, order, image)
+                .expect("valid graphene bond");
+        }
+        let topology = Typer::new()
+            .assign_periodic_topology(&graphene)
+            .expect("graphene should be typed");
+        assert_eq!(topology.rings.len(), 1);
+        assert_eq!(topology.rings[0].atom_ids, [a, b, a, b, a, b]);
+        assert!(topology.atoms.iter().all(|atom| atom.atom_type == "C_R"));
+
+        // Primitive diamond: chair hexagons cross all three faces.
+        let mut diamond =
+            PeriodicMolecularGraph::new([[0.0, 1.78, 1.78], [1.78, 0.0, 1.78], [1.78, 1.78, 0.0]]);
+        let a = diamond.add_atom(Element::C);
+        let b = diamond.add_atom(Element::C);
+        for image in [[0, 0, 0], [-1, 0, 0], [0, -1, 0], [0, 0, -1]] {
+            diamond
+                .add_periodic_bond(a, b, GraphBondOrder::Single, image)
+                .expect("valid diamond bond");
+        }
+        let topology = Typer::new()
+            .assign_periodic_topology(&diamond)
+            .expect("diamond should be typed");
+        assert!(!topology.rings.is_empty());
+        assert!(
+            topology
+                .rings
+                .iter()
+                .all(|ring| ring.size() == 6 && !ring.is_aromatic)
+        );
+        assert!(topology.atoms.iter().all(|atom| atom.atom_type == "C_3"));
+    }
+
+    #[test]
+    fn typer_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Typer>();
+    }
+}
@@ -0,0 +1,217 @@
+//! Progress reporting for long-running typing jobs.
+//!
+//! A [`ProgressObserver`] registered on a [`Typer`](super::Typer) receives a [`Progress`]
+//! snapshot whenever the pipeline enters a new stage and after every typing round, which lets
+//! front-ends show activity and an estimated completion time on very large systems.
+
+use crate::compat::time::{self, Instant};
+use crate::perception::STEP_NAMES;
+use core::fmt;
+use core::time::Duration;
+
+/// Coarse stage of the Perceive-Type-Build pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    /// Smallest-set-of-smallest-rings detection.
+    Rings,
+    /// Kekulé expansion of aromatic input bonds.
+    Kekulization,
+    /// Formal charge and lone pair bookkeeping.
+    Electrons,
+    /// Aromaticity classification.
+    Aromaticity,
+    /// Resonance system detection.
+    Resonance,
+    /// Hybridization inference.
+    Hybridization,
+    /// Hybridization refinement from 3D coordinates.
+    Geometry,
+    /// Functional group labeling.
+    Groups,
+    /// Iterative rule evaluation by the typing engine.
+    Typing,
+    /// Construction of bonds, angles, torsions, and inversions.
+    Building,
+}
+
+impl Stage {
+    /// All stages in execution order.
+    pub const ALL: [Stage; 10] = [
+        Stage::Rings,
+        Stage::Kekulization,
+        Stage::Electrons,
+        Stage::Aromaticity,
+        Stage::Resonance,
+        Stage::Hybridization,
+        Stage::Geometry,
+        Stage::Groups,
+        Stage::Typing,
+        Stage::Building,
+    ];
+
+    /// Returns the zero-based position of the stage within [`Stage::ALL`].
+    pub fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|&stage| stage == self)
+            .expect("every stage is listed in Stage::ALL")
+    }
+
+    /// Returns a human-readable stage name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Typing => "Typing",
+            Stage::Building => "Building",
+            perception => STEP_NAMES[perception.index()],
+        }
+    }
+
+    /// Maps a perception step index onto its stage.
+    pub(crate) fn from_perception_step(index: usize) -> Self {
+        Self::ALL[index]
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Snapshot of pipeline progress delivered to a [`ProgressObserver`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// Stage currently running.
+    pub stage: Stage,
+    /// Atoms processed within the current stage.
+    ///
+    /// Whole-molecule stages report `0` when they start; the typing stage reports the number of
+    /// atoms that already hold a type after each round.
+    pub atoms_processed: usize,
+    /// Total number of atoms in the molecule.
+    pub total_atoms: usize,
+    /// Time elapsed since the run started, or zero on targets without a clock.
+    pub elapsed: Duration,
+    /// Estimated time remaining, once enough work has completed to extrapolate.
+    pub eta: Option<Duration>,
+}
+
+impl Progress {
+    /// Estimated overall completion in the range `0.0..=1.0`.
+    ///
+    /// Every stage is weighted equally; progress within the typing stage is interpolated from
+    /// the fraction of typed atoms.
+    pub fn fraction(&self) -> f64 {
+        let within = if self.total_atoms == 0 {
+            0.0
+        } else {
+            self.atoms_processed as f64 / self.total_atoms as f64
+        };
+        ((self.stage.index() as f64 + within.min(1.0)) / Stage::ALL.len() as f64).min(1.0)
+    }
+}
+
+/// Receives progress snapshots while a [`Typer`](super::Typer) runs.
+///
+/// Observers are called synchronously on the typing thread and should return quickly. Any
+/// `Fn(&Progress) + Send + Sync` closure implements this trait.
+pub trait ProgressObserver: Send + Sync {
+    /// Handles a progress update.
+    fn on_progress(&self, progress: &Progress);
+}
+
+impl<F> ProgressObserver for F
+where
+    F: Fn(&Progress) + Send + Sync,
+{
+    fn on_progress(&self, progress: &Progress) {
+        self(progress)
+    }
+}
+
+/// Converts pipeline events into [`Progress`] snapshots for an optional observer.
+pub(crate) struct ProgressTracker<'a> {
+    observer: Option<&'a dyn ProgressObserver>,
+    total_atoms: usize,
+    atoms_offset: usize,
+    started: Option<Instant>,
+}
+
+impl<'a> ProgressTracker<'a> {
+    /// Starts timing a run over a molecule with `total_atoms` atoms.
+    pub(crate) fn new(observer: Option<&'a dyn ProgressObserver>, total_atoms: usize) -> Self {
+        Self {
+            observer,
+            total_atoms,
+            atoms_offset: 0,
+            started: time::now(),
+        }
+    }
+
+    /// Returns a tracker for one fragment of the run whose atom counts start at `atoms_before`.
+    pub(crate) fn fragment(&self, atoms_before: usize) -> Self {
+        Self {
+            atoms_offset: atoms_before,
+            ..*self
+        }
+    }
+
+    /// Emits a snapshot for `stage` if an observer is registered.
+    pub(crate) fn report(&self, stage: Stage, atoms_processed: usize) {
+        let Some(observer) = self.observer else {
+            return;
+        };
+
+        let elapsed = self
+            .started
+            .map_or(Duration::ZERO, |started| started.elapsed());
+        let mut progress = Progress {
+            stage,
+            atoms_processed: self.atoms_offset + atoms_processed,
+            total_atoms: self.total_atoms,
+            elapsed,
+            eta: None,
+        };
+        let fraction = progress.fraction();
+        if fraction > 0.0 && self.started.is_some() {
+            progress.eta = Some(elapsed.mul_f64((1.0 - fraction) / fraction));
+        }
+
+        observer.on_progress(&progress);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat::prelude::*;
+
+    #[test]
+    fn stage_names_and_indices_follow_pipeline_order() {
+        for (index, stage) in Stage::ALL.iter().enumerate() {
+            assert_eq!(stage.index(), index);
+        }
+        assert_eq!(Stage::Kekulization.name(), "Kekulization");
+        assert_eq!(Stage::Typing.to_string(), "Typing");
+        assert_eq!(Stage::from_perception_step(5), Stage::Hybridization);
+    }
+
+    #[test]
+    fn fraction_interpolates_typing_progress() {
+        let progress = Progress {
+            stage: Stage::Typing,
+            atoms_processed: 5,
+            total_atoms: 10,
+            elapsed: Duration::from_secs(1),
+            eta: None,
+        };
+        assert!((progress.fraction() - 8.5 / 10.0).abs() < 1e-12);
+
+        let start = Progress {
+            stage: Stage::Rings,
+            atoms_processed: 0,
+            ..progress
+        };
+        assert_eq!(start.fraction(), 0.0);
+    }
+}
@@ -0,0 +1,339 @@
+//! Typing of a selected sub-region of a molecular graph.
+//!
+//! Perception only looks a few bonds away from an atom, so a selection can be typed from a
+//! context region around it instead of the whole system. The region holds every atom within a
+//! fixed number of bonds of the selection, grows across bonds that must not be cut (multiple,
+//! aromatic, hydrogen, and metal bonds), and replaces every remaining outside neighbor by a
+//! capping hydrogen. Only the selected atoms and the terms among them are emitted, so the
+//! context atoms act as a fixed environment whose own types are never reported.
+
+use super::{RunOptions, assemble, finish};
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::float::Float;
+use crate::compat::prelude::*;
+use crate::core::error::{GraphValidationError, TyperError};
+use crate::core::graph::{AtomNode, MolecularGraph};
+use crate::core::properties::{Element, GraphBondOrder};
+use crate::core::selection::AtomSelection;
+use crate::core::topology::MolecularTopology;
+use alloc::collections::VecDeque;
+
+/// Default number of bonds of context kept around a selection.
+pub(crate) const DEFAULT_CONTEXT_BONDS: usize = 3;
+
+/// Length in Å of the bonds to capping hydrogens placed when the graph has coordinates.
+const CAP_BOND_LENGTH: f64 = 1.09;
+
+/// Context region cut from the full graph, with capping hydrogens in place of cut neighbors.
+struct Region {
+    /// Region graph; atoms keep the relative order of the atoms they stand for.
+    graph: MolecularGraph,
+    /// Full-graph ID of every region atom; a capping hydrogen maps to the atom it replaces.
+    origin: Vec<usize>,
+    /// Whether each region atom belongs to the selection.
+    selected: Vec<bool>,
+}
+
+/// Types the selected atoms of `graph` from a capped context region around them.
+///
+/// # Arguments
+///
+/// * `graph` - Full molecular graph.
+/// * `selection` - Atoms to type.
+/// * `context` - Number of bonds of context kept around the selection.
+/// * `options` - Options of the run.
+///
+/// # Returns
+///
+/// The topology of the selected atoms, numbered in ascending order of their graph IDs.
+///
+/// # Errors
+///
+/// Returns [`TyperError::InvalidInput`] if the selection names an atom missing from `graph`,
+/// and otherwise a [`TyperError`] whose atom IDs refer to `graph`.
+pub(crate) fn run(
+    graph: &MolecularGraph,
+    selection: &AtomSelection,
+    context: usize,
+    options: &RunOptions<'_>,
+) -> Result<MolecularTopology, TyperError> {
+    if let Some(&atom_id) = selection
+        .atom_ids()
+        .iter()
+        .find(|&&id| id >= graph.atoms.len())
+    {
+        return Err(GraphValidationError::MissingAtom { atom_id }.into());
+    }
+    if selection.is_empty() {
+        return Ok(MolecularTopology::default());
+    }
+
+    let region = cut_region(graph, selection, context);
+    let mut topology = assemble(&region.graph, options).map_err(|mut error| {
+        error.map_atom_ids(&|id| region.origin[id]);
+        error
+    })?;
+    topology.retain_atoms(|atom| region.selected[atom.id]);
+    let (selected_graph, _) = graph.subgraph(selection.atom_ids());
+    finish(topology, &selected_graph, options)
+}
+
+/// Collects the context region of a selection and caps the bonds it cuts.
+fn cut_region(graph: &MolecularGraph, selection: &AtomSelection, context: usize) -> Region {
+    let mut neighbors = vec![Vec::new(); graph.atoms.len()];
+    for bond in &graph.bonds {
+        let (a, b) = bond.atom_ids;
+        neighbors[a].push((b, bond.order));
+        neighbors[b].push((a, bond.order));
+    }
+    let is_metal = |id: usize| graph.atoms[id].element.is_metal_center();
+
+    // Breadth-first search to the context depth, then closure over bonds that must stay intact.
+    let mut depth = vec![None; graph.atoms.len()];
+    let mut queue = VecDeque::new();
+    for &id in selection.atom_ids() {
+        depth[id] = Some(0);
+        queue.push_back(id);
+    }
+    while let Some(atom) = queue.pop_front() {
+        let atom_depth = depth[atom].expect("queued atoms have a depth");
+        if is_metal(atom) && atom_depth > 0 && atom_depth >= context {
+            continue;
+        }
+        for &(neighbor, order) in &neighbors[atom] {
+            if depth[neighbor].is_some() {
+                continue;
+            }
+            let keep = atom_depth < context
+                || order != GraphBondOrder::Single
+                || graph.atoms[neighbor].element == Element::H
+                || is_metal(neighbor);
+            if keep {
+                depth[neighbor] = Some(atom_depth + 1);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    // Region atoms and caps, ordered by the graph ID they stand for.
+    let mut entries: Vec<(usize, Option<usize>)> = Vec::new();
+    for (atom, atom_depth) in depth.iter().enumerate() {
+        if atom_depth.is_none() {
+            continue;
+        }
+        entries.push((atom, None));
+        if is_metal(atom) {
+            continue;
+        }
+        for &(neighbor, _) in &neighbors[atom] {
+            if depth[neighbor].is_none() {
+                entries.push((neighbor, Some(atom)));
+            }
+        }
+    }
+    entries.sort_unstable();
+
+    let mut region = MolecularGraph::new();
+    let mut local = vec![None; graph.atoms.len()];
+    let mut origin = Vec::with_capacity(entries.len());
+    let mut selected = Vec::with_capacity(entries.len());
+    let mut caps = Vec::new();
+    for &(atom, capped) in &entries {
+        let node = &graph.atoms[atom];
+        match capped {
+            None => local[atom] = Some(copy_atom(&mut region, node)),
+            Some(parent) => caps.push((region.add_atom(Element::H), parent)),
+        }
+        origin.push(atom);
+        selected.push(capped.is_none() && selection.contains(atom));
+    }
+
+    for bond in &graph.bonds {
+        let (a, b) = bond.atom_ids;
+        if let (Some(a), Some(b)) = (local[a], local[b]) {
+            let id = region
+                .add_bond(a, b, bond.order)
+                .expect("endpoints exist in the region");
+            region.bonds[id].stereo = bond.stereo;
+        }
+    }
+    for (cap, parent) in caps {
+        let parent = local[parent].expect("capped atoms belong to the region");
+        region
+            .add_bond(parent, cap, GraphBondOrder::Single)
+            .expect("endpoints exist in the region");
+    }
+    region.positions = graph.positions.as_ref().and_then(|coords| {
+        entries
+            .iter()
+            .map(|&(atom, capped)| match capped {
+                None => coords.get(atom).copied(),
+                Some(parent) => Some(cap_position(*coords.get(parent)?, *coords.get(atom)?)),
+            })
+            .collect()
+    });
+
+    Region {
+        graph: region,
+        origin,
+        selected,
+    }
+}
+
+/// Appends a copy of `node` to `region` and returns its ID there.
+pub(super) fn copy_atom(region: &mut MolecularGraph, node: &AtomNode) -> usize {
+    let id = region.add_atom(node.element);
+    region.atoms[id].chirality = node.chirality;
+    region.atoms[id].formal_charge = node.formal_charge;
+    region.atoms[id].radical_electrons = node.radical_electrons;
+    region.atoms[id].isotope = node.isotope;
+    region.atoms[id].metadata = node.metadata.clone();
+    id
+}
+
+/// Places a capping hydrogen on the bond from `parent` toward the atom it replaces.
+pub(super) fn cap_position(parent: [f64; 3], replaced: [f64; 3]) -> [f64; 3] {
+    let direction = [
+        replaced[0] - parent[0],
+        replaced[1] - parent[1],
+        replaced[2] - parent[2],
+    ];
+    let length = direction.iter().map(|d| d * d).sum::<f64>().sqrt();
+    if length == 0.0 {
+        return replaced;
+    }
+    let scale = CAP_BOND_LENGTH / length;
+    [
+        parent[0] + direction[0] * scale,
+        parent[1] + direction[1] * scale,
+        parent[2] + direction[2] * scale,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::metadata::AtomMetadata;
+    use crate::io::smiles::parse_smiles;
+    use crate::pipeline::Typer;
+
+    /// Types the whole graph and keeps the selected atoms, as a reference for selection runs.
+    fn full_topology_of(graph: &MolecularGraph, selection: &AtomSelection) -> MolecularTopology {
+        Typer::new()
+            .assign_topology(graph)
+            .unwrap()
+            .subset(selection)
+    }
+
+    /// Sorts the bonded terms, whose order depends on how the molecule was traversed.
+    fn sorted(mut topology: MolecularTopology) -> MolecularTopology {
+        topology.bonds.sort_by_key(|bond| bond.atom_ids);
+        topology.angles.sort_by_key(|angle| angle.atom_ids);
+        topology.torsions.sort_by_key(|torsion| torsion.atom_ids);
+        topology
+            .inversions
+            .sort_by_key(|inversion| inversion.atom_ids);
+        topology
+    }
+
+    fn assert_matches_full_run(graph: &MolecularGraph, selection: &AtomSelection) {
+        let partial = sorted(
+            Typer::new()
+                .assign_topology_for_selection(graph, selection)
+                .unwrap(),
+        );
+        let full = sorted(full_topology_of(graph, selection));
+        assert_eq!(partial.atoms, full.atoms);
+        assert_eq!(partial.bonds, full.bonds);
+        assert_eq!(partial.angles, full.angles);
+        assert_eq!(partial.torsions, full.torsions);
+        assert_eq!(partial.inversions, full.inversions);
+    }
+
+    #[test]
+    fn selections_are_typed_as_in_the_full_system() {
+        // Acetamide-capped benzylamine chain ending in a carboxylic acid.
+        let graph = parse_smiles("CC(=O)NCc1ccccc1CCCC(=O)O").unwrap();
+        for ids in [vec![3], vec![0, 1, 2], vec![12, 13, 14, 15], vec![6, 7, 8]] {
+            assert_matches_full_run(&graph, &AtomSelection::from_ids(ids));
+        }
+        let everything = AtomSelection::from_ids(0..graph.atoms.len());
+        assert_matches_full_run(&graph, &everything);
+    }
+
+    #[test]
+    fn short_context_still_keeps_aromatic_rings_whole() {
+        let graph = parse_smiles("c1ccccc1CCCCCC").unwrap();
+        let selection = AtomSelection::from_ids([0]);
+        let region = cut_region(&graph, &selection, 0);
+        let carbons = region
+            .graph
+            .atoms
+            .iter()
+            .filter(|atom| atom.element == Element::C)
+            .count();
+        assert_eq!(carbons, 6);
+
+        let topology = Typer::new()
+            .with_selection_context(0)
+            .assign_topology_for_selection(&graph, &selection)
+            .unwrap();
+        assert_eq!(topology.atoms[0].atom_type, "C_R");
+    }
+
+    #[test]
+    fn cut_bonds_are_capped_with_hydrogens() {
+        let graph = parse_smiles("CCCCCCCC").unwrap();
+        let selection = AtomSelection::from_ids([3]);
+        let region = cut_region(&graph, &selection, 1);
+        let carbons: Vec<usize> = region
+            .origin
+            .iter()
+            .zip(&region.graph.atoms)
+            .filter(|(_, atom)| atom.element == Element::C)
+            .map(|(&origin, _)| origin)
+            .collect();
+        assert_eq!(carbons, [2, 3, 4]);
+        // Both outer carbons are capped in the slot of the carbon they replace.
+        assert_eq!(region.origin.iter().filter(|&&id| id == 1).count(), 1);
+        assert_eq!(region.origin.iter().filter(|&&id| id == 5).count(), 1);
+        assert_eq!(
+            region.selected.iter().filter(|&&selected| selected).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn selected_atoms_keep_their_metadata() {
+        let mut graph = parse_smiles("CCO").unwrap();
+        graph
+            .set_atom_metadata(2, AtomMetadata::named("OH"))
+            .unwrap();
+        let topology = Typer::new()
+            .assign_topology_for_selection(&graph, &AtomSelection::from_ids([2]))
+            .unwrap();
+        assert_eq!(topology.atoms.len(), 1);
+        assert_eq!(topology.atoms[0].metadata.name.as_deref(), Some("OH"));
+    }
+
+    #[test]
+    fn empty_selection_yields_an_empty_topology() {
+        let graph = parse_smiles("CCO").unwrap();
+        let topology = Typer::new()
+            .assign_topology_for_selection(&graph, &AtomSelection::new())
+            .unwrap();
+        assert_eq!(topology, MolecularTopology::default());
+    }
+
+    #[test]
+    fn selection_of_a_missing_atom_is_rejected() {
+        let graph = parse_smiles("CCO").unwrap();
+        let error = Typer::new()
+            .assign_topology_for_selection(&graph, &AtomSelection::from_ids([1, 42]))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            TyperError::InvalidInput(GraphValidationError::MissingAtom { atom_id: 42 })
+        ));
+    }
+}
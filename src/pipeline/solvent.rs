@@ -0,0 +1,315 @@
+//! Lookup typing of water, its ions, and common monoatomic ions.
+//!
+//! Solvated systems hold tens of thousands of copies of the same few fragments. Rather than
+//! perceiving and typing every copy, [`SolventTable`] recognizes water, hydronium, hydroxide, and
+//! monoatomic ions from their element counts and bonds alone, types one reference copy of each
+//! species with the run's own settings, and hands out that topology for every further copy. The
+//! output is therefore identical to typing each copy through the full pipeline.
+
+use super::progress::ProgressTracker;
+use super::{RunOptions, build, perceive_and_type};
+use crate::compat::collections::HashMap;
+use crate::compat::prelude::*;
+use crate::core::error::TyperError;
+use crate::core::graph::MolecularGraph;
+use crate::core::limits::Budget;
+use crate::core::properties::{Element, GraphBondOrder};
+use crate::core::topology::MolecularTopology;
+
+/// Elements recognized as monoatomic ions when they form a fragment on their own.
+const ION_ELEMENTS: [Element; 20] = [
+    Element::Li,
+    Element::Na,
+    Element::K,
+    Element::Rb,
+    Element::Cs,
+    Element::Mg,
+    Element::Ca,
+    Element::Sr,
+    Element::Ba,
+    Element::Mn,
+    Element::Fe,
+    Element::Co,
+    Element::Ni,
+    Element::Cu,
+    Element::Zn,
+    Element::Cd,
+    Element::F,
+    Element::Cl,
+    Element::Br,
+    Element::I,
+];
+
+/// Fragment that can be typed by lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Species {
+    /// `H2O`.
+    Water,
+    /// `H3O+`.
+    Hydronium,
+    /// `OH-`.
+    Hydroxide,
+    /// Single unbonded atom of one of the [`ION_ELEMENTS`].
+    Ion(Element),
+}
+
+impl Species {
+    /// Builds the reference copy of the species, heavy atom first.
+    fn graph(self) -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        let hydrogens = match self {
+            Species::Ion(element) => {
+                graph.add_atom(element);
+                return graph;
+            }
+            Species::Hydroxide => 1,
+            Species::Water => 2,
+            Species::Hydronium => 3,
+        };
+        let o = graph.add_atom(Element::O);
+        for _ in 0..hydrogens {
+            let h = graph.add_atom(Element::H);
+            graph
+                .add_bond(o, h, GraphBondOrder::Single)
+                .expect("reference atoms exist and differ");
+        }
+        graph
+    }
+}
+
+/// Per-run table of solvent species and their typed reference topologies.
+pub(crate) struct SolventTable {
+    /// Number of bonds of every input atom.
+    degrees: Vec<usize>,
+    /// Whether every bond of an input atom is a single bond.
+    single_bonded: Vec<bool>,
+    /// Whether the input carries coordinates that geometry perception may use.
+    has_positions: bool,
+    /// Reference topology of every species typed so far, in reference atom order.
+    topologies: HashMap<Species, MolecularTopology>,
+}
+
+impl SolventTable {
+    /// Scans the bonds of `graph` once so that fragments can be classified in constant time.
+    pub(crate) fn new(graph: &MolecularGraph) -> Self {
+        let mut degrees = vec![0; graph.atoms.len()];
+        let mut single_bonded = vec![true; graph.atoms.len()];
+        for bond in &graph.bonds {
+            let (a, b) = bond.atom_ids;
+            for atom in [a, b] {
+                degrees[atom] += 1;
+                single_bonded[atom] &= bond.order == GraphBondOrder::Single;
+            }
+        }
+        Self {
+            degrees,
+            single_bonded,
+            has_positions: graph.positions.is_some(),
+            topologies: HashMap::new(),
+        }
+    }
+
+    /// Classifies a connected fragment of the scanned graph.
+    ///
+    /// Fragments with a pinned atom or a radical (such as the hydroxyl radical) are left to the
+    /// full pipeline. Hydronium is only recognized without coordinates, since geometry
+    /// perception may flatten its three-coordinate oxygen.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Graph passed to [`SolventTable::new`].
+    /// * `atom_ids` - Atoms of one connected fragment.
+    /// * `options` - Options of the run, consulted for pinned atoms.
+    ///
+    /// # Returns
+    ///
+    /// The species and the fragment's atom IDs in reference order, or `None` if the fragment
+    /// needs full typing.
+    pub(crate) fn recognize(
+        &self,
+        graph: &MolecularGraph,
+        atom_ids: &[usize],
+        options: &RunOptions<'_>,
+    ) -> Option<(Species, Vec<usize>)> {
+        if !options.pinned.is_empty() && atom_ids.iter().any(|&id| options.pinned[id].is_some()) {
+            return None;
+        }
+        if atom_ids
+            .iter()
+            .any(|&id| graph.atoms[id].has_stated_electrons())
+        {
+            return None;
+        }
+        if let [id] = *atom_ids {
+            let element = graph.atoms[id].element;
+            return ION_ELEMENTS
+                .contains(&element)
+                .then(|| (Species::Ion(element), vec![id]));
+        }
+
+        let mut oxygen = None;
+        let mut hydrogens = Vec::with_capacity(3);
+        for &id in atom_ids {
+            if !self.single_bonded[id] {
+                return None;
+            }
+            match graph.atoms[id].element {
+                Element::O if oxygen.is_none() => oxygen = Some(id),
+                // A connected fragment of one oxygen and terminal hydrogens is a star around it.
+                Element::H if self.degrees[id] == 1 => hydrogens.push(id),
+                _ => return None,
+            }
+        }
+        let oxygen = oxygen?;
+        let species = match hydrogens.len() {
+            1 => Species::Hydroxide,
+            2 => Species::Water,
+            3 if !self.has_positions => Species::Hydronium,
+            _ => return None,
+        };
+        let mut order = Vec::with_capacity(atom_ids.len());
+        order.push(oxygen);
+        order.extend(hydrogens);
+        Some((species, order))
+    }
+
+    /// Returns the topology of a copy of `species`, typing the reference copy on first use.
+    ///
+    /// # Arguments
+    ///
+    /// * `species` - Recognized species.
+    /// * `atom_ids` - Input atom IDs of the copy in reference order, used to name the atoms of
+    ///   a failure.
+    /// * `options` - Options of the run.
+    /// * `budget` - Budget of the run.
+    ///
+    /// # Returns
+    ///
+    /// The reference topology, whose atom `i` is `atom_ids[i]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TyperError`] if the reference copy cannot be typed with the run's settings.
+    pub(crate) fn topology(
+        &mut self,
+        species: Species,
+        atom_ids: &[usize],
+        options: &RunOptions<'_>,
+        budget: &Budget,
+    ) -> Result<MolecularTopology, TyperError> {
+        if let Some(topology) = self.topologies.get(&species) {
+            return Ok(topology.clone());
+        }
+        let graph = species.graph();
+        let reference_options = RunOptions {
+            bond_images: &[],
+            pinned: &[],
+            ..*options
+        };
+        let tracker = ProgressTracker::new(None, graph.atoms.len());
+        let typed = perceive_and_type(&graph, &reference_options, budget, &tracker).map_err(
+            |mut error| {
+                error.map_atom_ids(&|id| atom_ids[id]);
+                error
+            },
+        )?;
+        let topology = build(&typed, &reference_options);
+        self.topologies.insert(species, topology.clone());
+        Ok(topology)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::Typer;
+    use crate::typing::ruleset::RuleSet;
+
+    /// Na+, H-O-H, H-O-, H3O+ with the oxygen last, and chloromethane.
+    fn solvated_box() -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        graph.add_atom(Element::Na);
+        for hydrogens in [2, 1, 3] {
+            let ids: Vec<usize> = (0..=hydrogens)
+                .map(|i| {
+                    let oxygen_at = if hydrogens == 3 { 3 } else { 1 };
+                    graph.add_atom(if i == oxygen_at {
+                        Element::O
+                    } else {
+                        Element::H
+                    })
+                })
+                .collect();
+            let o = ids[if hydrogens == 3 { 3 } else { 1 }];
+            for &h in ids.iter().filter(|&&id| id != o) {
+                graph.add_bond(o, h, GraphBondOrder::Single).unwrap();
+            }
+        }
+        let c = graph.add_atom(Element::C);
+        let cl = graph.add_atom(Element::Cl);
+        graph.add_bond(c, cl, GraphBondOrder::Single).unwrap();
+        for _ in 0..3 {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn recognizes_solvent_species_in_reference_order() {
+        let graph = solvated_box();
+        let table = SolventTable::new(&graph);
+        let rules = RuleSet::default_dreiding();
+        let options = RunOptions::new(&rules);
+        let species: Vec<_> = graph
+            .connected_components()
+            .iter()
+            .map(|atom_ids| table.recognize(&graph, atom_ids, &options))
+            .collect();
+
+        assert_eq!(
+            species,
+            [
+                Some((Species::Ion(Element::Na), vec![0])),
+                Some((Species::Water, vec![2, 1, 3])),
+                Some((Species::Hydroxide, vec![5, 4])),
+                Some((Species::Hydronium, vec![9, 6, 7, 8])),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn fast_path_matches_full_typing_of_every_fragment() {
+        let graph = solvated_box();
+        let typer = Typer::new();
+        let topology = typer.assign_topology(&graph).unwrap();
+
+        for atom_ids in graph.connected_components() {
+            let (fragment, _) = graph.subgraph(&atom_ids);
+            let expected = typer.assign_topology(&fragment).unwrap();
+            for (local, &id) in atom_ids.iter().enumerate() {
+                assert_eq!(
+                    topology.atoms[id].atom_type,
+                    expected.atoms[local].atom_type
+                );
+                assert_eq!(
+                    topology.atoms[id].provenance,
+                    expected.atoms[local].provenance
+                );
+            }
+            let angles_in_fragment = topology
+                .angles
+                .iter()
+                .filter(|angle| atom_ids.contains(&angle.atom_ids.1))
+                .count();
+            assert_eq!(angles_in_fragment, expected.angles.len());
+            let donors_in_fragment = topology
+                .hb_donors
+                .iter()
+                .filter(|donor| atom_ids.contains(&donor.hydrogen_id))
+                .count();
+            assert_eq!(donors_in_fragment, expected.hb_donors.len());
+        }
+    }
+}
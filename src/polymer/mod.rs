@@ -0,0 +1,408 @@
+//! Typing of linear polymers from a single repeat unit.
+//!
+//! A homopolymer of thousands of repeats holds the same few chemical environments over and over:
+//! the two chain ends and the interior. A [`RepeatUnit`] describes one repeat together with its
+//! head and tail attachment atoms, and
+//! [`Typer::assign_polymer_topology`](crate::Typer::assign_polymer_topology) types a short
+//! reference oligomer once, then propagates its atom types and bonded terms along a chain of any
+//! length, stitching the bonds, angles, torsions, and inversions that cross unit boundaries. The
+//! result equals typing the explicit chain returned by [`RepeatUnit::chain`].
+
+use crate::core::error::{PolymerError, TyperError};
+use crate::core::graph::MolecularGraph;
+use crate::core::properties::{Element, GraphBondOrder};
+use crate::core::topology::{
+    Angle, Atom, Bond, HydrogenBondDonor, Inversion, MolecularTopology, Ring,
+};
+use crate::pipeline::{RunOptions, assemble, copy_stereo, finish, run};
+
+/// Units on each side of the middle unit of the reference oligomer.
+///
+/// A torsion reaches at most three units past the unit of its first atom, so every term that
+/// starts in the middle unit of a `2 * WINDOW + 1`-mer sees the same atoms as one deep inside a
+/// long chain, and both chain ends keep their own neighborhood.
+const WINDOW: usize = 3;
+
+/// Number of units of the reference oligomer.
+const OLIGOMER_UNITS: usize = 2 * WINDOW + 1;
+
+/// One repeat of a linear polymer with its head and tail attachment atoms.
+///
+/// Repeat `i + 1` is bonded to repeat `i` by a single bond from its head to the tail of repeat
+/// `i`. Hydrogens cap the head of the first and the tail of the last repeat. The head and tail
+/// may be the same atom, as in `-CH2-` for polyethylene.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::smiles::parse_smiles;
+/// use dreid_typer::polymer::RepeatUnit;
+/// use dreid_typer::Typer;
+///
+/// // Polystyrene: -CH2-CH(Ph)-, head on the CH2 and tail on the CH.
+/// let unit = RepeatUnit::new(parse_smiles("[CH2][CH]c1ccccc1").unwrap(), 0, 1).unwrap();
+///
+/// let topology = Typer::new().assign_polymer_topology(&unit, 100).unwrap();
+/// assert_eq!(topology.atoms.len(), 100 * unit.graph().atoms.len() + 2);
+/// assert_eq!(topology.atoms[0].atom_type, "C_3");
+/// assert_eq!(topology.atoms[2].atom_type, "C_R");
+/// ```
+#[derive(Debug, Clone)]
+pub struct RepeatUnit {
+    /// Atoms and bonds of one repeat, with explicit hydrogens.
+    graph: MolecularGraph,
+    /// Atom bonded to the tail of the previous repeat.
+    head: usize,
+    /// Atom bonded to the head of the next repeat.
+    tail: usize,
+}
+
+impl RepeatUnit {
+    /// Creates a repeat unit.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - One repeat with explicit hydrogens and a free valence on `head` and `tail`.
+    /// * `head` - Atom bonded to the previous repeat.
+    /// * `tail` - Atom bonded to the next repeat.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PolymerError::AtomOutOfRange`] if `head` or `tail` is not an atom of `graph`.
+    pub fn new(graph: MolecularGraph, head: usize, tail: usize) -> Result<Self, PolymerError> {
+        for atom_id in [head, tail] {
+            if atom_id >= graph.atoms.len() {
+                return Err(PolymerError::AtomOutOfRange {
+                    atom_id,
+                    atom_count: graph.atoms.len(),
+                });
+            }
+        }
+        Ok(Self { graph, head, tail })
+    }
+
+    /// Returns the atoms and bonds of one repeat.
+    pub fn graph(&self) -> &MolecularGraph {
+        &self.graph
+    }
+
+    /// Returns the atom bonded to the previous repeat.
+    pub fn head(&self) -> usize {
+        self.head
+    }
+
+    /// Returns the atom bonded to the next repeat.
+    pub fn tail(&self) -> usize {
+        self.tail
+    }
+
+    /// Builds the explicit graph of a chain of `repeats` units.
+    ///
+    /// Atom `j` of repeat `i` receives ID `i * n + j`, where `n` is the atom count of the unit;
+    /// the hydrogen capping the head follows as ID `repeats * n`, the one capping the tail as
+    /// `repeats * n + 1`. Metadata is copied to every repeat, with residue numbers advanced by
+    /// the repeat index. Stereo descriptors are copied except those on the head and tail atoms
+    /// and on bonds touching them, whose neighbor order changes in the chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `repeats` - Number of units; zero yields an empty graph.
+    pub fn chain(&self, repeats: usize) -> MolecularGraph {
+        let n = self.graph.atoms.len();
+        let mut chain = MolecularGraph::new();
+        if repeats == 0 {
+            return chain;
+        }
+        let attachment = |atom: usize| atom == self.head || atom == self.tail;
+        for repeat in 0..repeats {
+            let offset = repeat * n;
+            for node in &self.graph.atoms {
+                let id = chain.add_atom(node.element);
+                let atom = &mut chain.atoms[id];
+                if !attachment(node.id) {
+                    atom.chirality = node.chirality;
+                }
+                atom.formal_charge = node.formal_charge;
+                atom.radical_electrons = node.radical_electrons;
+                atom.isotope = node.isotope;
+                atom.metadata.clone_from(&node.metadata);
+                if let Some(residue) = &mut atom.metadata.residue {
+                    residue.number += repeat as i32;
+                }
+            }
+            for edge in &self.graph.bonds {
+                let (a, b) = edge.atom_ids;
+                let bond_id = chain
+                    .add_bond(a + offset, b + offset, edge.order)
+                    .expect("unit bonds are valid");
+                if !attachment(a) && !attachment(b) {
+                    chain.bonds[bond_id].stereo = edge.stereo;
+                }
+            }
+            if repeat > 0 {
+                link(&mut chain, offset - n + self.tail, offset + self.head);
+            }
+        }
+        let head_cap = chain.add_atom(Element::H);
+        link(&mut chain, self.head, head_cap);
+        let tail_cap = chain.add_atom(Element::H);
+        link(&mut chain, (repeats - 1) * n + self.tail, tail_cap);
+        chain
+    }
+}
+
+/// Adds a single bond between two atoms of a chain under construction.
+fn link(chain: &mut MolecularGraph, a: usize, b: usize) {
+    chain
+        .add_bond(a, b, GraphBondOrder::Single)
+        .expect("chain atoms exist and differ");
+}
+
+/// Types a chain of `repeats` units by propagating a typed reference oligomer.
+///
+/// Chains no longer than the reference oligomer are typed explicitly.
+///
+/// # Errors
+///
+/// Returns a [`TyperError`] under the same conditions as typing [`RepeatUnit::chain`], with
+/// atom IDs referring to the chain.
+pub(crate) fn assign_chain(
+    unit: &RepeatUnit,
+    repeats: usize,
+    options: &RunOptions<'_>,
+) -> Result<MolecularTopology, TyperError> {
+    let chain = unit.chain(repeats);
+    if repeats <= OLIGOMER_UNITS {
+        return run(&chain, options);
+    }
+
+    let layout = Layout {
+        unit_atoms: unit.graph.atoms.len(),
+        repeats,
+    };
+    let reference = assemble(&unit.chain(OLIGOMER_UNITS), options).map_err(|mut error| {
+        error.map_atom_ids(&|id| layout.place(id, layout.fixed_shift(layout.unit_of(id))));
+        error
+    })?;
+    let mut topology = layout.propagate(&reference);
+    copy_stereo(&mut topology, &chain);
+    finish(topology, &chain, options)
+}
+
+/// Maps atoms and terms of the reference oligomer onto a long chain.
+///
+/// Every term is anchored at the lowest unit it touches. Terms anchored before the middle unit
+/// describe the head end and keep their units, terms anchored after it describe the tail end
+/// and move to the end of the chain, and terms anchored in the middle unit are repeated for
+/// every interior unit of the chain.
+struct Layout {
+    /// Atoms per repeat unit.
+    unit_atoms: usize,
+    /// Units of the chain.
+    repeats: usize,
+}
+
+impl Layout {
+    /// Returns the oligomer unit an oligomer atom belongs to, counting caps to the end units.
+    fn unit_of(&self, atom: usize) -> usize {
+        let capped = OLIGOMER_UNITS * self.unit_atoms;
+        match atom.checked_sub(capped) {
+            None => atom / self.unit_atoms,
+            Some(0) => 0,
+            Some(_) => OLIGOMER_UNITS - 1,
+        }
+    }
+
+    /// Returns the shift of a term anchored at a unit other than the middle one.
+    fn fixed_shift(&self, anchor: usize) -> usize {
+        if anchor < WINDOW {
+            0
+        } else {
+            self.repeats - OLIGOMER_UNITS
+        }
+    }
+
+    /// Returns every shift at which a term anchored at `anchor` occurs in the chain.
+    fn shifts(&self, anchor: usize) -> core::ops::RangeInclusive<usize> {
+        if anchor == WINDOW {
+            0..=self.repeats - OLIGOMER_UNITS
+        } else {
+            let shift = self.fixed_shift(anchor);
+            shift..=shift
+        }
+    }
+
+    /// Maps an oligomer atom into the chain, moving unit atoms forward by `shift` units.
+    fn place(&self, atom: usize, shift: usize) -> usize {
+        let capped = OLIGOMER_UNITS * self.unit_atoms;
+        match atom.checked_sub(capped) {
+            None => atom + shift * self.unit_atoms,
+            Some(cap) => self.repeats * self.unit_atoms + cap,
+        }
+    }
+
+    /// Calls `emit` once for every occurrence in the chain of a term over `atoms`.
+    fn for_each_placement(&self, atoms: &[usize], mut emit: impl FnMut(&dyn Fn(usize) -> usize)) {
+        let anchor = atoms
+            .iter()
+            .map(|&atom| self.unit_of(atom))
+            .min()
+            .expect("terms have atoms");
+        for shift in self.shifts(anchor) {
+            emit(&|atom| self.place(atom, shift));
+        }
+    }
+
+    /// Builds the chain topology from the typed reference oligomer.
+    fn propagate(&self, reference: &MolecularTopology) -> MolecularTopology {
+        let mut chain = MolecularTopology::default();
+        let mut atoms = vec![None; self.repeats * self.unit_atoms + 2];
+        for atom in &reference.atoms {
+            self.for_each_placement(&[atom.id], |map| {
+                let id = map(atom.id);
+                atoms[id] = Some(Atom { id, ..atom.clone() });
+            });
+        }
+        chain.atoms = atoms
+            .into_iter()
+            .map(|atom| atom.expect("placements cover every chain atom"))
+            .collect();
+
+        for bond in &reference.bonds {
+            let (a, b) = bond.atom_ids;
+            self.for_each_placement(&[a, b], |map| {
+                chain.bonds.push(Bond::new(map(a), map(b), bond.order));
+            });
+        }
+        for angle in &reference.angles {
+            let (a, center, b) = angle.atom_ids;
+            self.for_each_placement(&[a, center, b], |map| {
+                chain
+                    .angles
+                    .push(Angle::new(map(a), map(center), map(b), angle.class));
+            });
+        }
+        for (source, target) in [
+            (&reference.torsions, &mut chain.torsions),
+            (
+                &reference.suppressed_torsions,
+                &mut chain.suppressed_torsions,
+            ),
+        ] {
+            for torsion in source {
+                let (i, j, k, l) = torsion.atom_ids;
+                self.for_each_placement(&[i, j, k, l], |map| {
+                    target.push(torsion.renumbered(map(i), map(j), map(k), map(l)));
+                });
+            }
+        }
+        for inversion in &reference.inversions {
+            let (center, axis, p1, p2) = inversion.atom_ids;
+            self.for_each_placement(&[center, axis, p1, p2], |map| {
+                chain.inversions.push(Inversion::new(
+                    map(center),
+                    map(axis),
+                    map(p1),
+                    map(p2),
+                    inversion.class,
+                ));
+            });
+        }
+        for donor in &reference.hb_donors {
+            self.for_each_placement(&[donor.donor_id, donor.hydrogen_id], |map| {
+                chain.hb_donors.push(HydrogenBondDonor {
+                    donor_id: map(donor.donor_id),
+                    hydrogen_id: map(donor.hydrogen_id),
+                });
+            });
+        }
+        for &acceptor in &reference.hb_acceptors {
+            self.for_each_placement(&[acceptor], |map| chain.hb_acceptors.push(map(acceptor)));
+        }
+        for ring in &reference.rings {
+            self.for_each_placement(&ring.atom_ids, |map| {
+                chain.rings.push(Ring {
+                    atom_ids: ring.atom_ids.iter().map(|&atom| map(atom)).collect(),
+                    is_aromatic: ring.is_aromatic,
+                    system_id: ring.system_id,
+                });
+            });
+        }
+
+        chain.hb_donors.sort_unstable_by_key(|d| d.hydrogen_id);
+        chain.hb_acceptors.sort_unstable();
+        chain.normalize_rings();
+        chain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat::collections::HashSet;
+    use crate::io::smiles::parse_smiles;
+    use crate::pipeline::Typer;
+    use std::hash::Hash;
+
+    fn as_set<T: Eq + Hash>(items: &[T]) -> HashSet<&T> {
+        let set: HashSet<&T> = items.iter().collect();
+        assert_eq!(set.len(), items.len(), "terms are unique");
+        set
+    }
+
+    fn assert_matches_explicit_chain(unit: &RepeatUnit, repeats: usize) {
+        let typer = Typer::new();
+        let propagated = typer.assign_polymer_topology(unit, repeats).unwrap();
+        let explicit = typer.assign_topology(&unit.chain(repeats)).unwrap();
+
+        assert_eq!(propagated.atoms, explicit.atoms);
+        assert_eq!(as_set(&propagated.bonds), as_set(&explicit.bonds));
+        assert_eq!(as_set(&propagated.angles), as_set(&explicit.angles));
+        assert_eq!(as_set(&propagated.torsions), as_set(&explicit.torsions));
+        assert_eq!(as_set(&propagated.inversions), as_set(&explicit.inversions));
+        assert_eq!(as_set(&propagated.hb_donors), as_set(&explicit.hb_donors));
+        assert_eq!(propagated.hb_acceptors, explicit.hb_acceptors);
+        assert_eq!(as_set(&propagated.rings), as_set(&explicit.rings));
+    }
+
+    #[test]
+    fn propagated_chains_equal_explicitly_typed_chains() {
+        // Polyethylene, where head and tail coincide.
+        let ethylene = RepeatUnit::new(parse_smiles("[CH2]").unwrap(), 0, 0).unwrap();
+        assert_matches_explicit_chain(&ethylene, 12);
+        // Polystyrene, with an aromatic side ring.
+        let styrene = RepeatUnit::new(parse_smiles("[CH2][CH]c1ccccc1").unwrap(), 0, 1).unwrap();
+        assert_matches_explicit_chain(&styrene, 10);
+        // Nylon-6, with hydrogen-bonding amides and a resonant C-N bond in every repeat.
+        let nylon = RepeatUnit::new(parse_smiles("[NH]C(=O)CCCC[CH2]").unwrap(), 0, 7).unwrap();
+        assert_matches_explicit_chain(&nylon, 9);
+    }
+
+    #[test]
+    fn chains_number_repeats_consecutively_and_cap_both_ends() {
+        let unit = RepeatUnit::new(parse_smiles("[CH2][CH2]").unwrap(), 0, 1).unwrap();
+        let chain = unit.chain(3);
+        assert_eq!(chain.atoms.len(), 3 * 6 + 2);
+        let has_bond = |a, b| {
+            chain
+                .bonds
+                .iter()
+                .any(|bond| bond.atom_ids == (a, b) || bond.atom_ids == (b, a))
+        };
+        assert!(has_bond(1, 6) && has_bond(7, 12));
+        assert!(has_bond(0, 18) && has_bond(13, 19));
+        assert!(unit.chain(0).atoms.is_empty());
+    }
+
+    #[test]
+    fn attachment_atoms_must_belong_to_the_unit() {
+        let graph = parse_smiles("[CH2]").unwrap();
+        assert!(matches!(
+            RepeatUnit::new(graph, 0, 3),
+            Err(PolymerError::AtomOutOfRange {
+                atom_id: 3,
+                atom_count: 3
+            })
+        ));
+    }
+}
@@ -0,0 +1,301 @@
+//! Residue templates that shortcut typing of biopolymers.
+//!
+//! Proteins, nucleic acids, and their solvent are built from a few dozen residues repeated
+//! thousands of times. A [`ResidueTemplate`] describes one such residue as a small capped
+//! molecule: the *core* atoms belong to the residue, and the *cap* atoms stand in for the
+//! neighboring residues so that the core sees the same chemical environment it has in a chain.
+//! [`TemplateTyper`] types every template once with its own rules, finds template instances in
+//! an input graph by subgraph matching, and copies the precomputed assignments onto them.
+//! Connected fragments that are not fully covered by templates, such as ligands or nonstandard
+//! residues, are typed by the rules as usual.
+
+mod standard;
+mod typer;
+
+use crate::compat::prelude::*;
+pub use typer::{ResidueMatch, TemplateTyper};
+
+use crate::core::error::TemplateError;
+use crate::core::graph::MolecularGraph;
+use crate::core::properties::Element;
+use crate::io::smiles::parse_smiles;
+
+/// Atom class that marks cap atoms in a template SMILES string.
+const CAP_ATOM_CLASS: &str = "1";
+
+/// A residue described as a capped molecule whose core atoms are typed as a unit.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::templates::ResidueTemplate;
+///
+/// // N-methylacetamide caps around a glycine residue; atom class 1 marks the caps.
+/// let gly = ResidueTemplate::from_smiles("GLY", "[CH3:1][C:1](=[O:1])NCC(=O)[NH:1][CH3:1]")
+///     .unwrap();
+/// assert_eq!(gly.name(), "GLY");
+/// assert_eq!(gly.core_atoms().count(), 7);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResidueTemplate {
+    /// Name reported for matches of this template.
+    name: String,
+    /// Capped molecule with explicit hydrogens.
+    graph: MolecularGraph,
+    /// Whether each atom of `graph` belongs to the residue rather than a cap.
+    core: Vec<bool>,
+}
+
+impl ResidueTemplate {
+    /// Creates a template from a capped molecule and its core atoms.
+    ///
+    /// Cap atoms bonded to the core are matched by element, degree, and bond orders; cap atoms
+    /// further out only shape the environment in which the template is typed.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name reported for matches of this template.
+    /// * `graph` - Capped molecule with explicit hydrogens.
+    /// * `core` - Whether each atom of `graph` belongs to the residue.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TemplateError::InvalidCore`] if `core` does not have one entry per atom, marks
+    /// no atom, or marks atoms that are not connected through core-core bonds.
+    pub fn new(
+        name: impl Into<String>,
+        graph: MolecularGraph,
+        core: Vec<bool>,
+    ) -> Result<Self, TemplateError> {
+        let name = name.into();
+        if core.len() != graph.atoms.len() || !is_connected(&graph, &core) {
+            return Err(TemplateError::InvalidCore { name });
+        }
+        Ok(Self { name, graph, core })
+    }
+
+    /// Creates a template from a SMILES string in which atom class 1 marks the cap atoms.
+    ///
+    /// Hydrogens follow their heavy atom: those of cap atoms are caps, all others are core.
+    /// Cap atoms must be written as bracket atoms to carry the class, e.g. `[CH3:1]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name reported for matches of this template.
+    /// * `smiles` - Capped molecule in SMILES notation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TemplateError::InvalidSmiles`] if the string does not parse, or
+    /// [`TemplateError::InvalidCore`] under the conditions of [`ResidueTemplate::new`].
+    pub fn from_smiles(name: impl Into<String>, smiles: &str) -> Result<Self, TemplateError> {
+        let name = name.into();
+        let graph = match parse_smiles(smiles) {
+            Ok(graph) => graph,
+            Err(source) => return Err(TemplateError::InvalidSmiles { name, source }),
+        };
+
+        let mut core = vec![true; graph.atoms.len()];
+        for (id, is_cap) in cap_marks(smiles).into_iter().enumerate() {
+            if let Some(slot) = core.get_mut(id) {
+                *slot = !is_cap;
+            }
+        }
+        for bond in &graph.bonds {
+            let (a, b) = bond.atom_ids;
+            for (h, heavy) in [(a, b), (b, a)] {
+                if graph.atoms[h].element == Element::H && !core[heavy] {
+                    core[h] = false;
+                }
+            }
+        }
+        Self::new(name, graph, core)
+    }
+
+    /// Returns the template name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the capped molecule.
+    pub fn graph(&self) -> &MolecularGraph {
+        &self.graph
+    }
+
+    /// Returns `true` if the atom belongs to the residue rather than a cap.
+    pub fn is_core(&self, atom_id: usize) -> bool {
+        self.core.get(atom_id).copied().unwrap_or(false)
+    }
+
+    /// Iterates over the IDs of the core atoms in ascending order.
+    pub fn core_atoms(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.core.len()).filter(|&id| self.core[id])
+    }
+}
+
+/// Ordered collection of residue templates.
+///
+/// Earlier templates are tried first when several could match at the same atom.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::templates::TemplateLibrary;
+///
+/// let library = TemplateLibrary::standard();
+/// assert!(library.get("TRP").is_some());
+/// assert!(library.get("DA5").is_some());
+/// assert!(library.get("HOH").is_some());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TemplateLibrary {
+    templates: Vec<ResidueTemplate>,
+}
+
+impl TemplateLibrary {
+    /// Creates an empty library.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the built-in library of standard biopolymer residues and water.
+    ///
+    /// It covers the 20 amino acids in their usual protonation states (histidine as `HID`,
+    /// `HIE`, and `HIP`, disulfide-bonded cysteine as `CYX`) as chain, N-terminal (`N` prefix),
+    /// and C-terminal (`C` prefix) residues; the DNA (`DA`, `DC`, `DG`, `DT`) and RNA (`A`, `C`,
+    /// `G`, `U`) nucleotides as chain, 5'-terminal (`5` suffix), and 3'-terminal (`3` suffix)
+    /// residues; and water (`HOH`).
+    pub fn standard() -> Self {
+        Self {
+            templates: standard::templates(),
+        }
+    }
+
+    /// Appends a template to the library.
+    pub fn add(&mut self, template: ResidueTemplate) {
+        self.templates.push(template);
+    }
+
+    /// Returns the templates in the order they are tried.
+    pub fn templates(&self) -> &[ResidueTemplate] {
+        &self.templates
+    }
+
+    /// Returns the first template with the given name.
+    pub fn get(&self, name: &str) -> Option<&ResidueTemplate> {
+        self.templates.iter().find(|template| template.name == name)
+    }
+
+    /// Returns the number of templates.
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Returns `true` if the library holds no template.
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+}
+
+/// Returns whether each written atom of a SMILES string carries the cap atom class.
+///
+/// Atoms are counted in order of appearance, which is the order in which the parser numbers
+/// them before appending implicit hydrogens.
+fn cap_marks(smiles: &str) -> Vec<bool> {
+    let mut marks = Vec::new();
+    let mut chars = smiles.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => {
+                let content: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                let class = content.rsplit_once(':').map(|(_, class)| class);
+                marks.push(class == Some(CAP_ATOM_CLASS));
+            }
+            'B' | 'C' => {
+                chars.next_if(|&next| (c, next) == ('B', 'r') || (c, next) == ('C', 'l'));
+                marks.push(false);
+            }
+            'N' | 'O' | 'P' | 'S' | 'F' | 'I' | 'b' | 'c' | 'n' | 'o' | 'p' | 's' => {
+                marks.push(false)
+            }
+            c if c.is_whitespace() => break,
+            _ => {}
+        }
+    }
+    marks
+}
+
+/// Returns `true` if the core atoms are non-empty and connected through core-core bonds.
+fn is_connected(graph: &MolecularGraph, core: &[bool]) -> bool {
+    let Some(start) = core.iter().position(|&is_core| is_core) else {
+        return false;
+    };
+    let mut neighbors = vec![Vec::new(); core.len()];
+    for bond in &graph.bonds {
+        let (a, b) = bond.atom_ids;
+        if core[a] && core[b] {
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+    }
+
+    let mut seen = vec![false; core.len()];
+    seen[start] = true;
+    let mut stack = vec![start];
+    while let Some(atom) = stack.pop() {
+        for &next in &neighbors[atom] {
+            if !seen[next] {
+                seen[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+    core.iter()
+        .zip(&seen)
+        .all(|(&is_core, &seen)| !is_core || seen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cap_classes_mark_caps_and_their_hydrogens() {
+        let template =
+            ResidueTemplate::from_smiles("ALA", "[CH3:1][C:1](=[O:1])NC(C)C(=O)[NH:1][CH3:1]")
+                .unwrap();
+
+        let core_elements: Vec<_> = template
+            .core_atoms()
+            .map(|id| template.graph().atoms[id].element)
+            .collect();
+        let heavy = core_elements.iter().filter(|&&e| e != Element::H).count();
+        assert_eq!(heavy, 5);
+        assert_eq!(core_elements.len(), 10);
+        assert!(!template.is_core(0));
+        assert!(template.is_core(3));
+    }
+
+    #[test]
+    fn disconnected_or_empty_cores_are_rejected() {
+        assert!(matches!(
+            ResidueTemplate::from_smiles("SPLIT", "C[O:1]C"),
+            Err(TemplateError::InvalidCore { name }) if name == "SPLIT"
+        ));
+        assert!(matches!(
+            ResidueTemplate::from_smiles("CAPS", "[CH4:1]"),
+            Err(TemplateError::InvalidCore { .. })
+        ));
+        assert!(matches!(
+            ResidueTemplate::from_smiles("BAD", "C(("),
+            Err(TemplateError::InvalidSmiles { .. })
+        ));
+    }
+
+    #[test]
+    fn standard_templates_are_valid() {
+        let library = TemplateLibrary::standard();
+        assert_eq!(library.len(), 23 * 3 + 8 * 3 + 1);
+        assert_eq!(library.get("NGLY").unwrap().core_atoms().count(), 9);
+    }
+}
@@ -0,0 +1,115 @@
+//! Built-in templates for standard amino acids, nucleotides, and water.
+//!
+//! Every template is a Kekulé SMILES string so that its core matches inputs with either
+//! alternating or aromatic ring bonds. Chain residues are capped with the groups that bond to
+//! them in a polymer: acetyl and N-methylamide groups for amino acids, methyl phosphate esters
+//! for nucleotides.
+
+use super::ResidueTemplate;
+use crate::compat::prelude::*;
+
+/// Amino acids as name, backbone nitrogen, and side chain bonded to the alpha carbon.
+///
+/// The nitrogen is written for a chain residue; proline opens ring 1 on it, which its side
+/// chain closes.
+const AMINO_ACIDS: &[(&str, &str, &str)] = &[
+    ("ALA", "N", "C"),
+    ("ARG", "N", "CCCNC(N)=[NH2+]"),
+    ("ASN", "N", "CC(=O)N"),
+    ("ASP", "N", "CC(=O)[O-]"),
+    ("CYS", "N", "CS"),
+    ("CYX", "N", "CS[S:1][CH3:1]"),
+    ("GLN", "N", "CCC(=O)N"),
+    ("GLU", "N", "CCC(=O)[O-]"),
+    ("GLY", "N", ""),
+    ("HID", "N", "CC2=CN=CN2"),
+    ("HIE", "N", "CC2=CNC=N2"),
+    ("HIP", "N", "CC2=C[NH+]=CN2"),
+    ("ILE", "N", "C(C)CC"),
+    ("LEU", "N", "CC(C)C"),
+    ("LYS", "N", "CCCC[NH3+]"),
+    ("MET", "N", "CCSC"),
+    ("PHE", "N", "CC2=CC=CC=C2"),
+    ("PRO", "N1", "CCC1"),
+    ("SER", "N", "CO"),
+    ("THR", "N", "C(O)C"),
+    ("TRP", "N", "CC2=CNC3=CC=CC=C32"),
+    ("TYR", "N", "CC2=CC=C(O)C=C2"),
+    ("VAL", "N", "C(C)C"),
+];
+
+/// Nucleobases as the one-letter code and the base bonded to C1'.
+const BASES: &[(&str, &str)] = &[
+    ("A", "N2C=NC3=C(N)N=CN=C32"),
+    ("C", "N2C=CC(N)=NC2=O"),
+    ("G", "N2C=NC3=C2N=C(N)NC3=O"),
+    ("T", "N2C=C(C)C(=O)NC2=O"),
+    ("U", "N2C=CC(=O)NC2=O"),
+];
+
+/// Cap standing in for the preceding residue's carbonyl group.
+const ACETYL: &str = "[CH3:1][C:1](=[O:1])";
+/// Cap standing in for the following residue's amide nitrogen.
+const METHYLAMIDE: &str = "[NH:1][CH3:1]";
+/// Cap standing in for the preceding nucleotide's O3'.
+const METHOXY: &str = "[CH3:1][O:1]";
+/// Cap standing in for the following nucleotide's phosphate.
+const METHYL_PHOSPHATE: &str = "[P:1](=[O:1])([O-:1])[O:1][CH3:1]";
+
+/// Builds every built-in template.
+pub(super) fn templates() -> Vec<ResidueTemplate> {
+    let mut smiles = Vec::new();
+    for &(name, nitrogen, side_chain) in AMINO_ACIDS {
+        let alpha = if side_chain.is_empty() {
+            "C".to_string()
+        } else {
+            format!("C({side_chain})")
+        };
+        let terminal_nitrogen = if nitrogen == "N1" {
+            "[NH2+]1"
+        } else {
+            "[NH3+]"
+        };
+        smiles.push((
+            name.to_string(),
+            format!("{ACETYL}{nitrogen}{alpha}C(=O){METHYLAMIDE}"),
+        ));
+        smiles.push((
+            format!("N{name}"),
+            format!("{terminal_nitrogen}{alpha}C(=O){METHYLAMIDE}"),
+        ));
+        smiles.push((
+            format!("C{name}"),
+            format!("{ACETYL}{nitrogen}{alpha}C(=O)[O-]"),
+        ));
+    }
+
+    let mut nucleotides = Vec::new();
+    for &(code, base) in BASES {
+        if code != "U" {
+            nucleotides.push((format!("D{code}"), base, "C"));
+        }
+        if code != "T" {
+            nucleotides.push((code.to_string(), base, "C(O)"));
+        }
+    }
+    for (name, base, c2) in nucleotides {
+        let nucleoside = format!("OCC1OC({base}){c2}C1O");
+        let chain = format!("{METHOXY}P(=O)([O-]){nucleoside}");
+        smiles.push((name.clone(), format!("{chain}{METHYL_PHOSPHATE}")));
+        smiles.push((
+            format!("{name}5"),
+            format!("{nucleoside}{METHYL_PHOSPHATE}"),
+        ));
+        smiles.push((format!("{name}3"), chain));
+    }
+
+    smiles.push(("HOH".to_string(), "O".to_string()));
+
+    smiles
+        .into_iter()
+        .map(|(name, smiles)| {
+            ResidueTemplate::from_smiles(name, &smiles).expect("built-in templates are valid")
+        })
+        .collect()
+}
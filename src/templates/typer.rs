@@ -0,0 +1,729 @@
+//! Template matching and the typer front-end that applies matched templates.
+
+use super::{ResidueTemplate, TemplateLibrary};
+use crate::builder;
+use crate::compat::collections::{HashMap, HashSet};
+use crate::compat::prelude::*;
+use crate::core::error::{TemplateError, TyperError};
+use crate::core::graph::MolecularGraph;
+use crate::core::properties::{Element, GraphBondOrder, Hybridization};
+use crate::core::substructure::neighbors;
+use crate::core::topology::{MolecularTopology, TypeProvenance};
+use crate::perception::{AnnotatedMolecule, ResonanceSystem, STEP_NAMES, rings};
+use crate::pipeline::{
+    RunOptions, Stage, Typer, assemble, finish, merge_fragments, perceive_and_type_standalone,
+};
+use core::fmt;
+
+/// Environment of an atom bonded to a template core: element, number of bonds of each order,
+/// and the order of its bond to the core.
+type Signature = (Element, [u8; 4], GraphBondOrder);
+
+/// One instance of a template found in a molecular graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResidueMatch {
+    /// Name of the matched template.
+    pub template: String,
+    /// Graph atom matched to each core atom of the template, in template atom order.
+    pub atom_ids: Vec<usize>,
+}
+
+/// Typer front-end that types standard residues from precomputed templates.
+///
+/// Every template is typed once, at construction, with the wrapped typer's rules, perception
+/// settings, and variant. A connected fragment whose atoms are all covered by template matches
+/// is then built from the stored types, hybridizations, and bond orders without running
+/// perception or the rule engine; every other fragment is typed by the wrapped typer. The result
+/// equals [`Typer::assign_topology`] as long as the rules type residue atoms from their residue
+/// and its caps alone, which holds for the default rules. Geometry-based refinement does not
+/// apply to matched residues, whose typing depends on connectivity only.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::io::smiles::parse_smiles;
+/// use dreid_typer::templates::{TemplateLibrary, TemplateTyper};
+/// use dreid_typer::Typer;
+///
+/// let typer = TemplateTyper::new(Typer::new(), &TemplateLibrary::standard()).unwrap();
+///
+/// // Ala-Gly with free termini.
+/// let graph = parse_smiles("[NH3+]C(C)C(=O)NCC(=O)[O-]").unwrap();
+/// let residues: Vec<_> = typer
+///     .match_residues(&graph)
+///     .into_iter()
+///     .map(|found| found.template)
+///     .collect();
+/// assert_eq!(residues, ["NALA", "CGLY"]);
+///
+/// let mut topology = typer.assign_topology(&graph).unwrap();
+/// let mut expected = Typer::new().assign_topology(&graph).unwrap();
+/// topology.sort_terms();
+/// expected.sort_terms();
+/// assert_eq!(topology, expected);
+/// ```
+pub struct TemplateTyper {
+    /// Typer that types the templates and every unmatched fragment.
+    typer: Typer,
+    /// Templates with their precomputed assignments, in library order.
+    templates: Vec<CompiledTemplate>,
+    /// Indices of the templates whose first matched atom has the given element and degree.
+    anchors: HashMap<(Element, usize), Vec<usize>>,
+}
+
+impl TemplateTyper {
+    /// Types every template of a library and wraps the typer.
+    ///
+    /// # Arguments
+    ///
+    /// * `typer` - Typer whose settings apply to the templates and to unmatched fragments.
+    /// * `library` - Templates to match, tried in library order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TemplateError::Typing`] if the typer cannot type one of the capped templates.
+    pub fn new(typer: Typer, library: &TemplateLibrary) -> Result<Self, TemplateError> {
+        let options = typer.run_options();
+        let templates = library
+            .templates()
+            .iter()
+            .map(|template| CompiledTemplate::new(template, &options))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut anchors: HashMap<_, Vec<_>> = HashMap::new();
+        for (index, template) in templates.iter().enumerate() {
+            let anchor = template.search[0];
+            anchors
+                .entry((template.elements[anchor], template.neighbors[anchor].len()))
+                .or_default()
+                .push(index);
+        }
+
+        Ok(Self {
+            typer,
+            templates,
+            anchors,
+        })
+    }
+
+    /// Returns the typer used for templates and unmatched fragments.
+    pub fn typer(&self) -> &Typer {
+        &self.typer
+    }
+
+    /// Finds the template instances in a molecular graph.
+    ///
+    /// Atoms are visited in ascending order, and each atom not yet claimed by a match is tried
+    /// as the first atom of every template in library order; the first template that matches
+    /// claims its core atoms.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Molecular graph to search.
+    ///
+    /// # Returns
+    ///
+    /// The matches in the order they were found.
+    pub fn match_residues(&self, graph: &MolecularGraph) -> Vec<ResidueMatch> {
+        self.place(&GraphView::new(graph))
+            .into_iter()
+            .map(|placement| {
+                let template = &self.templates[placement.template];
+                ResidueMatch {
+                    template: template.name.clone(),
+                    atom_ids: placement.atoms.into_iter().flatten().collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// Types a molecular graph, building template-covered fragments from their templates.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Molecular graph to type.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TyperError`] under the same conditions as [`Typer::assign_topology`].
+    pub fn assign_topology(&self, graph: &MolecularGraph) -> Result<MolecularTopology, TyperError> {
+        let options = self.typer.run_options();
+        let view = GraphView::new(graph);
+        let placements = self.place(&view);
+
+        let mut owner = vec![None; graph.atoms.len()];
+        for (index, placement) in placements.iter().enumerate() {
+            for (template_atom, &atom) in placement.atoms.iter().enumerate() {
+                if let Some(atom) = atom {
+                    owner[atom] = Some((index, template_atom));
+                }
+            }
+        }
+
+        let components = graph.connected_components();
+        let mut fragments = Vec::with_capacity(components.len());
+        for atom_ids in &components {
+            let (subgraph, _) = graph.subgraph(atom_ids);
+            let topology = if atom_ids.iter().all(|&id| owner[id].is_some()) {
+                self.build_from_templates(&subgraph, atom_ids, &placements, &owner, &options)?
+            } else {
+                assemble(&subgraph, &options)?
+            };
+            fragments.push((atom_ids.as_slice(), topology));
+        }
+        finish(
+            merge_fragments(graph.atoms.len(), fragments),
+            graph,
+            &options,
+        )
+    }
+
+    /// Claims template instances greedily in ascending order of their first atom.
+    fn place(&self, view: &GraphView) -> Vec<Placement> {
+        // Radicals and charged carbons are left to rule-based typing.
+        let mut claimed = view.stated.clone();
+        let mut placements = Vec::new();
+        for anchor in 0..view.elements.len() {
+            if claimed[anchor] {
+                continue;
+            }
+            let key = (view.elements[anchor], view.neighbors[anchor].len());
+            let Some(candidates) = self.anchors.get(&key) else {
+                continue;
+            };
+            for &index in candidates {
+                if let Some(atoms) = self.templates[index].find(view, &claimed, anchor) {
+                    for &atom in atoms.iter().flatten() {
+                        claimed[atom] = true;
+                    }
+                    placements.push(Placement {
+                        template: index,
+                        atoms,
+                    });
+                    break;
+                }
+            }
+        }
+        placements
+    }
+
+    /// Builds the topology of a fragment whose atoms are all covered by template matches.
+    fn build_from_templates(
+        &self,
+        subgraph: &MolecularGraph,
+        atom_ids: &[usize],
+        placements: &[Placement],
+        owner: &[Option<(usize, usize)>],
+        options: &RunOptions<'_>,
+    ) -> Result<MolecularTopology, TyperError> {
+        let template_atom = |local: usize| {
+            let (placement, atom) = owner[atom_ids[local]].expect("fragment is fully matched");
+            (
+                &self.templates[placements[placement].template],
+                placement,
+                atom,
+            )
+        };
+
+        // Rings are the only perceived structure the topology reports, so they are searched for
+        // even though the remaining perception passes are skipped.
+        let mut molecule = AnnotatedMolecule::new(subgraph)?;
+        rings::perceive(&mut molecule, options.perception, &options.budget()).map_err(
+            |source| TyperError::PerceptionFailed {
+                step: STEP_NAMES[0].to_string(),
+                stage: Stage::Rings,
+                source,
+            },
+        )?;
+        let mut atom_types = Vec::with_capacity(atom_ids.len());
+        let mut provenance = Vec::with_capacity(atom_ids.len());
+        for atom in &mut molecule.atoms {
+            let (template, _, t) = template_atom(atom.id);
+            atom.hybridization = template.hybridization[t];
+            atom.lone_pairs = template.lone_pairs[t];
+            atom.is_aromatic = template.aromatic[t];
+            atom_types.push(template.atom_types[t].clone());
+            provenance.push(template.provenance[t]);
+        }
+
+        let mut resonant = ResonanceSystem {
+            atom_ids: Vec::new(),
+            bond_ids: Vec::new(),
+        };
+        for bond_id in 0..molecule.bonds.len() {
+            let bond = &molecule.bonds[bond_id];
+            let (a, b) = bond.atom_ids;
+            let (template_a, placement_a, ta) = template_atom(a);
+            let (template_b, placement_b, tb) = template_atom(b);
+            let (order, is_resonant) = if placement_a == placement_b {
+                template_a.bond(ta, tb)
+            } else {
+                template_a
+                    .cap_bond(ta, subgraph.atoms[b].element, bond.order)
+                    .or_else(|| template_b.cap_bond(tb, subgraph.atoms[a].element, bond.order))
+                    .unwrap_or((bond.order, false))
+            };
+            molecule.set_bond_order(bond_id, order);
+            if is_resonant {
+                resonant.atom_ids.extend([a, b]);
+                resonant.bond_ids.push(bond_id);
+            }
+        }
+        if !resonant.bond_ids.is_empty() {
+            resonant.atom_ids.sort_unstable();
+            resonant.atom_ids.dedup();
+            molecule.resonance_systems.push(resonant);
+        }
+
+        Ok(builder::build_topology(
+            &molecule,
+            &atom_types,
+            &provenance,
+            options.suppress_torsions,
+            options.inversion_policy,
+            options.exclude_coordination_torsions,
+        ))
+    }
+}
+
+impl fmt::Debug for TemplateTyper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TemplateTyper")
+            .field("typer", &self.typer)
+            .field("templates", &self.templates.len())
+            .finish()
+    }
+}
+
+/// Template matched at a set of graph atoms.
+struct Placement {
+    /// Index of the template.
+    template: usize,
+    /// Graph atom matched to each template atom; `None` for caps.
+    atoms: Vec<Option<usize>>,
+}
+
+/// Adjacency of the graph being searched.
+struct GraphView {
+    /// Element of every atom.
+    elements: Vec<Element>,
+    /// Neighbors and bond orders of every atom.
+    neighbors: Vec<Vec<(usize, GraphBondOrder)>>,
+    /// Whether each atom is a radical or charged carbon, which templates never describe.
+    stated: Vec<bool>,
+}
+
+impl GraphView {
+    fn new(graph: &MolecularGraph) -> Self {
+        Self {
+            elements: graph.atoms.iter().map(|atom| atom.element).collect(),
+            neighbors: neighbors(graph),
+            stated: graph
+                .atoms
+                .iter()
+                .map(|atom| atom.has_stated_electrons())
+                .collect(),
+        }
+    }
+
+    /// Returns the order of the bond between two atoms, if they are bonded.
+    fn bond_order(&self, a: usize, b: usize) -> Option<GraphBondOrder> {
+        self.neighbors[a]
+            .iter()
+            .find(|&&(neighbor, _)| neighbor == b)
+            .map(|&(_, order)| order)
+    }
+
+    /// Describes `atom` as seen from its neighbor `from`.
+    fn signature(&self, atom: usize, from: usize) -> Signature {
+        signature(&self.elements, &self.neighbors, atom, from)
+    }
+}
+
+/// A template prepared for matching, with the assignments of its typed capped molecule.
+struct CompiledTemplate {
+    /// Template name.
+    name: String,
+    /// Element of every template atom.
+    elements: Vec<Element>,
+    /// Neighbors and input bond orders of every template atom.
+    neighbors: Vec<Vec<(usize, GraphBondOrder)>>,
+    /// Core atoms other than terminal hydrogens, in breadth-first order from the anchor.
+    search: Vec<usize>,
+    /// For every entry of `search` after the first, the template atom it is reached from.
+    parents: Vec<usize>,
+    /// Terminal hydrogens of the core and the atom each is bonded to.
+    hydrogens: Vec<(usize, usize)>,
+    /// Whether each atom is a searched core atom.
+    searched: Vec<bool>,
+    /// Sorted signatures of the non-searched neighbors (caps and hydrogens) of every atom.
+    environment: Vec<Vec<Signature>>,
+    /// Whether each atom is aromatic, which lets its ring bonds match any Kekulé form.
+    aromatic: Vec<bool>,
+    /// Assigned type of every atom.
+    atom_types: Vec<String>,
+    /// Origin of every atom type.
+    provenance: Vec<TypeProvenance>,
+    /// Perceived hybridization of every atom.
+    hybridization: Vec<Hybridization>,
+    /// Perceived lone pairs of every atom.
+    lone_pairs: Vec<u8>,
+    /// Perceived order and resonance of every bond, keyed by its sorted atom pair.
+    bonds: HashMap<(usize, usize), (GraphBondOrder, bool)>,
+}
+
+impl CompiledTemplate {
+    fn new(template: &ResidueTemplate, options: &RunOptions<'_>) -> Result<Self, TemplateError> {
+        let typed = perceive_and_type_standalone(&template.graph, options).map_err(|source| {
+            TemplateError::Typing {
+                name: template.name.clone(),
+                source,
+            }
+        })?;
+        let molecule = &typed.molecule;
+        let view = GraphView::new(&template.graph);
+        let n = view.elements.len();
+
+        let mut hydrogens = Vec::new();
+        let mut searched = template.core.clone();
+        for atom in template.core_atoms() {
+            if let [(parent, _)] = view.neighbors[atom][..]
+                && view.elements[atom] == Element::H
+                && template.core[parent]
+            {
+                hydrogens.push((atom, parent));
+                searched[atom] = false;
+            }
+        }
+
+        let anchor = (0..n)
+            .find(|&atom| searched[atom])
+            .unwrap_or(hydrogens[0].0);
+        searched[anchor] = true;
+        let mut search = vec![anchor];
+        let mut parents = vec![anchor];
+        let mut visited = vec![false; n];
+        visited[anchor] = true;
+        let mut next = 0;
+        while next < search.len() {
+            let atom = search[next];
+            next += 1;
+            for &(neighbor, _) in &view.neighbors[atom] {
+                if searched[neighbor] && !visited[neighbor] {
+                    visited[neighbor] = true;
+                    search.push(neighbor);
+                    parents.push(atom);
+                }
+            }
+        }
+        hydrogens.retain(|&(h, _)| h != anchor);
+
+        let environment = (0..n)
+            .map(|atom| {
+                let mut signatures: Vec<_> = view.neighbors[atom]
+                    .iter()
+                    .filter(|&&(neighbor, _)| !searched[neighbor])
+                    .map(|&(neighbor, _)| view.signature(neighbor, atom))
+                    .collect();
+                signatures.sort();
+                signatures
+            })
+            .collect();
+
+        let resonant: HashSet<usize> = molecule
+            .resonance_systems
+            .iter()
+            .flat_map(|system| system.bond_ids.iter().copied())
+            .collect();
+        let bonds = molecule
+            .bonds
+            .iter()
+            .map(|bond| {
+                let (a, b) = bond.atom_ids;
+                (
+                    (a.min(b), a.max(b)),
+                    (bond.order, resonant.contains(&bond.id)),
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            name: template.name.clone(),
+            elements: view.elements,
+            neighbors: view.neighbors,
+            search,
+            parents,
+            hydrogens,
+            searched,
+            environment,
+            aromatic: molecule.atoms.iter().map(|atom| atom.is_aromatic).collect(),
+            atom_types: typed.atom_types,
+            provenance: typed.provenance,
+            hybridization: molecule.atoms.iter().map(|a| a.hybridization).collect(),
+            lone_pairs: molecule.atoms.iter().map(|a| a.lone_pairs).collect(),
+            bonds,
+        })
+    }
+
+    /// Returns the perceived order and resonance of the bond between two template atoms.
+    fn bond(&self, a: usize, b: usize) -> (GraphBondOrder, bool) {
+        self.bonds[&(a.min(b), a.max(b))]
+    }
+
+    /// Returns the perceived bond from a core atom to the cap that stands in for a neighbor.
+    ///
+    /// Prefers a cap whose input bond order matches `order`, then any cap of the element.
+    fn cap_bond(
+        &self,
+        atom: usize,
+        element: Element,
+        order: GraphBondOrder,
+    ) -> Option<(GraphBondOrder, bool)> {
+        let caps = || {
+            self.neighbors[atom]
+                .iter()
+                .filter(move |&&(cap, _)| !self.searched[cap] && self.elements[cap] == element)
+        };
+        caps()
+            .find(|&&(_, cap_order)| cap_order == order)
+            .or_else(|| caps().next())
+            .map(|&(cap, _)| self.bond(atom, cap))
+    }
+
+    /// Tries to match the template with its first atom at `anchor`.
+    ///
+    /// # Returns
+    ///
+    /// The graph atom of every template atom (`None` for caps), or `None` if the template does
+    /// not match there.
+    fn find(
+        &self,
+        view: &GraphView,
+        claimed: &[bool],
+        anchor: usize,
+    ) -> Option<Vec<Option<usize>>> {
+        let first = self.search[0];
+        if view.elements[anchor] != self.elements[first]
+            || view.neighbors[anchor].len() != self.neighbors[first].len()
+        {
+            return None;
+        }
+        let mut atoms = vec![None; self.elements.len()];
+        atoms[first] = Some(anchor);
+        let mut used = Vec::with_capacity(self.search.len() + self.hydrogens.len());
+        used.push(anchor);
+        if !self.extend(view, claimed, 1, &mut atoms, &mut used) {
+            return None;
+        }
+
+        for &(h, parent) in &self.hydrogens {
+            let parent = atoms[parent].expect("parents are searched first");
+            let hydrogen = view.neighbors[parent].iter().find(|&&(atom, _)| {
+                view.elements[atom] == Element::H
+                    && view.neighbors[atom].len() == 1
+                    && !claimed[atom]
+                    && !used.contains(&atom)
+            })?;
+            atoms[h] = Some(hydrogen.0);
+            used.push(hydrogen.0);
+        }
+        Some(atoms)
+    }
+
+    /// Maps `search[depth..]` by backtracking, then checks the environment of every atom.
+    ///
+    /// Every entry of `search` is reached from an earlier one, so candidates are the graph
+    /// neighbors of the atom its parent was mapped to.
+    fn extend(
+        &self,
+        view: &GraphView,
+        claimed: &[bool],
+        depth: usize,
+        atoms: &mut [Option<usize>],
+        used: &mut Vec<usize>,
+    ) -> bool {
+        if depth == self.search.len() {
+            return self.environment_matches(view, atoms, used);
+        }
+
+        let t = self.search[depth];
+        let parent = atoms[self.parents[depth]].expect("parents are mapped first");
+        for &(candidate, _) in &view.neighbors[parent] {
+            if claimed[candidate]
+                || used.contains(&candidate)
+                || view.elements[candidate] != self.elements[t]
+                || view.neighbors[candidate].len() != self.neighbors[t].len()
+                || !self.bonds_match(view, atoms, t, candidate)
+            {
+                continue;
+            }
+            atoms[t] = Some(candidate);
+            used.push(candidate);
+            if self.extend(view, claimed, depth + 1, atoms, used) {
+                return true;
+            }
+            atoms[t] = None;
+            used.pop();
+        }
+        false
+    }
+
+    /// Checks the bonds from template atom `t`, placed at `candidate`, to already mapped atoms.
+    fn bonds_match(
+        &self,
+        view: &GraphView,
+        atoms: &[Option<usize>],
+        t: usize,
+        candidate: usize,
+    ) -> bool {
+        self.neighbors[t].iter().all(|&(u, order)| {
+            let Some(mapped) = atoms[u] else {
+                return true;
+            };
+            match view.bond_order(candidate, mapped) {
+                Some(found) if self.aromatic[t] && self.aromatic[u] => matches!(
+                    found,
+                    GraphBondOrder::Single | GraphBondOrder::Double | GraphBondOrder::Aromatic
+                ),
+                Some(found) => found == order,
+                None => false,
+            }
+        })
+    }
+
+    /// Checks that the unmapped neighbors of every searched atom match its caps and hydrogens.
+    fn environment_matches(
+        &self,
+        view: &GraphView,
+        atoms: &[Option<usize>],
+        used: &[usize],
+    ) -> bool {
+        self.search.iter().all(|&t| {
+            let atom = atoms[t].expect("every searched atom is mapped");
+            let mut signatures = Vec::with_capacity(self.environment[t].len());
+            for &(neighbor, _) in &view.neighbors[atom] {
+                let is_core_neighbor = self.neighbors[t]
+                    .iter()
+                    .any(|&(u, _)| self.searched[u] && atoms[u] == Some(neighbor));
+                if is_core_neighbor {
+                    continue;
+                }
+                if used.contains(&neighbor) {
+                    return false;
+                }
+                signatures.push(view.signature(neighbor, atom));
+            }
+            signatures.sort();
+            signatures == self.environment[t]
+        })
+    }
+}
+
+/// Describes `atom` as seen from its neighbor `from`.
+fn signature(
+    elements: &[Element],
+    neighbors: &[Vec<(usize, GraphBondOrder)>],
+    atom: usize,
+    from: usize,
+) -> Signature {
+    let mut orders = [0u8; 4];
+    for &(_, order) in &neighbors[atom] {
+        orders[order as usize - 1] += 1;
+    }
+    let to_core = neighbors[atom]
+        .iter()
+        .find(|&&(neighbor, _)| neighbor == from)
+        .map_or(GraphBondOrder::Single, |&(_, order)| order);
+    (elements[atom], orders, to_core)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::smiles::parse_smiles;
+
+    fn standard_typer() -> TemplateTyper {
+        TemplateTyper::new(Typer::new(), &TemplateLibrary::standard()).unwrap()
+    }
+
+    fn assert_matches_full_typing(typer: &TemplateTyper, graph: &MolecularGraph) {
+        let mut expected = typer.typer().assign_topology(graph).unwrap();
+        let mut found = typer.assign_topology(graph).unwrap();
+        expected.sort_terms();
+        found.sort_terms();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn every_amino_acid_in_a_chain_matches_full_typing() {
+        let typer = standard_typer();
+        // Tryptophan sits in a second peptide so that each molecule has one fused ring system.
+        let chains = "[NH3+]C(CCCNC(N)=[NH2+])C(=O)NC(CC(=O)N)C(=O)NC(CC(=O)[O-])C(=O)\
+                      NC(CS)C(=O)NC(CCC(=O)N)C(=O)NC(CCC(=O)[O-])C(=O)NCC(=O)\
+                      NC(CC1=CN=CN1)C(=O)NC(CC1=CNC=N1)C(=O)NC(CC1=C[NH+]=CN1)C(=O)\
+                      NC(C(C)CC)C(=O)NC(CC(C)C)C(=O)NC(CCCC[NH3+])C(=O)NC(CCSC)C(=O)\
+                      NC(Cc1ccccc1)C(=O)N1C(CCC1)C(=O)NC(CO)C(=O)NC(C(O)C)C(=O)\
+                      NC(Cc1ccc(O)cc1)C(=O)NC(C(C)C)C(=O)[O-].\
+                      [NH3+]CC(=O)NC(Cc1c[nH]c2ccccc12)C(=O)NCC(=O)[O-]";
+        let graph = parse_smiles(chains).unwrap();
+
+        let residues: Vec<_> = typer
+            .match_residues(&graph)
+            .into_iter()
+            .map(|found| found.template)
+            .collect();
+        assert_eq!(
+            residues,
+            [
+                "NARG", "ASN", "ASP", "CYS", "GLN", "GLU", "GLY", "HID", "HIE", "HIP", "ILE",
+                "LEU", "LYS", "MET", "PHE", "PRO", "SER", "THR", "TYR", "CVAL", "NGLY", "TRP",
+                "CGLY"
+            ]
+        );
+        assert_matches_full_typing(&typer, &graph);
+    }
+
+    #[test]
+    fn nucleotides_and_water_match_full_typing() {
+        let typer = standard_typer();
+        let graph = parse_smiles(
+            "OCC1OC(N2C=NC3=C(N)N=CN=C32)CC1OP(=O)([O-])OCC1OC(N2C=C(C)C(=O)NC2=O)CC1O.\
+             OCC1OC(N2C=CC(N)=NC2=O)C(O)C1OP(=O)([O-])OCC1OC(N2C=NC3=C2N=C(N)NC3=O)C(O)C1O.O",
+        )
+        .unwrap();
+
+        let residues: Vec<_> = typer
+            .match_residues(&graph)
+            .into_iter()
+            .map(|found| found.template)
+            .collect();
+        assert_eq!(residues, ["DA5", "DT3", "C5", "G3", "HOH"]);
+        assert_matches_full_typing(&typer, &graph);
+    }
+
+    #[test]
+    fn unmatched_fragments_fall_back_to_rules() {
+        let typer = standard_typer();
+        // Ornithine is not a standard residue, so the whole dipeptide is typed by rules.
+        let graph = parse_smiles("[NH3+]C(CCC[NH3+])C(=O)NCC(=O)[O-].c1ccccc1.O").unwrap();
+
+        let residues: Vec<_> = typer
+            .match_residues(&graph)
+            .into_iter()
+            .map(|found| found.template)
+            .collect();
+        assert_eq!(residues, ["CGLY", "HOH"]);
+        assert_matches_full_typing(&typer, &graph);
+    }
+
+    #[test]
+    fn templates_follow_the_typer_settings() {
+        let typer = TemplateTyper::new(
+            Typer::new().with_typing_mode(crate::pipeline::TypingMode::UnitedAtom),
+            &TemplateLibrary::standard(),
+        )
+        .unwrap();
+        let graph = parse_smiles("[NH3+]C(C)C(=O)NC(C)C(=O)[O-]").unwrap();
+        assert_matches_full_typing(&typer, &graph);
+    }
+}
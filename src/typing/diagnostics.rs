@@ -0,0 +1,102 @@
+//! Audit data describing how decisively the rule engine typed each atom.
+//!
+//! The engine resolves equal-priority matches by rule name, which is deterministic but hides
+//! rulesets that genuinely cannot tell two types apart.
+//! [`AssignmentDiagnostics`](crate::rules::AssignmentDiagnostics) re-examines the converged
+//! assignment and reports every atom for which rules of the winning priority disagree.
+
+use crate::compat::prelude::*;
+
+/// A rule that matched an atom at the winning priority.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleMatch {
+    /// Name of the matching rule.
+    pub rule_name: String,
+    /// Type the rule would assign.
+    pub result_type: String,
+}
+
+/// An atom whose winning priority is shared by rules proposing different types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmbiguousAssignment {
+    /// Atom the rules compete for.
+    pub atom_id: usize,
+    /// Type the engine assigned, chosen by rule name among the candidates.
+    pub assigned_type: String,
+    /// Priority shared by all candidates.
+    pub priority: i32,
+    /// Every rule that matched at `priority`, in evaluation order.
+    pub candidates: Vec<RuleMatch>,
+}
+
+impl AmbiguousAssignment {
+    /// Returns the distinct types proposed by the candidates, in evaluation order.
+    pub fn competing_types(&self) -> Vec<&str> {
+        let mut types: Vec<&str> = Vec::new();
+        for candidate in &self.candidates {
+            if !types.contains(&candidate.result_type.as_str()) {
+                types.push(&candidate.result_type);
+            }
+        }
+        types
+    }
+}
+
+/// Per-atom confidence scores and the list of ambiguous assignments for one molecule.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::rules::{Conditions, Rule};
+/// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, Typer};
+///
+/// let rule = |name: &str, result_type: &str, element| Rule {
+///     name: name.to_string(),
+///     priority: 1,
+///     result_type: result_type.to_string(),
+///     conditions: Conditions {
+///         element: Some(element),
+///         ..Conditions::default()
+///     },
+/// };
+/// let typer = Typer::with_rules(vec![
+///     rule("Oxygen", "O_3", Element::O),
+///     rule("HydrogenA", "H_", Element::H),
+///     rule("HydrogenB", "H_HB", Element::H),
+/// ]);
+///
+/// let mut water = MolecularGraph::new();
+/// let o = water.add_atom(Element::O);
+/// for _ in 0..2 {
+///     let h = water.add_atom(Element::H);
+///     water.add_bond(o, h, GraphBondOrder::Single).unwrap();
+/// }
+///
+/// let diagnostics = typer.assignment_diagnostics(&water).unwrap();
+/// assert_eq!(diagnostics.confidence, [1.0, 0.5, 0.5]);
+/// assert_eq!(diagnostics.ambiguous.len(), 2);
+/// assert_eq!(diagnostics.ambiguous[0].assigned_type, "H_");
+/// assert_eq!(diagnostics.ambiguous[0].competing_types(), ["H_", "H_HB"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssignmentDiagnostics {
+    /// Share of the winning-priority matches that agree with the assigned type, per atom; `1.0`
+    /// for unambiguous atoms and `0.0` for atoms no rule matched.
+    pub confidence: Vec<f64>,
+    /// Atoms with competing types at the winning priority, in atom order.
+    pub ambiguous: Vec<AmbiguousAssignment>,
+}
+
+impl AssignmentDiagnostics {
+    /// Returns `true` if no atom had competing types.
+    pub fn is_unambiguous(&self) -> bool {
+        self.ambiguous.is_empty()
+    }
+
+    /// Returns the ambiguity record for an atom, if it has one.
+    pub fn ambiguity_for(&self, atom_id: usize) -> Option<&AmbiguousAssignment> {
+        self.ambiguous
+            .iter()
+            .find(|ambiguity| ambiguity.atom_id == atom_id)
+    }
+}
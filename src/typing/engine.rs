@@ -1,142 +1,387 @@
 //! Evaluates DREIDING typing rules over annotated molecules until atoms converge on types.
 //!
-//! The engine sorts the rules by priority, iteratively applies them while respecting
-//! neighbor-dependent constraints, and reports any atoms that fail to obtain an assignment.
+//! The engine walks a precompiled [`RuleSet`](crate::rules::RuleSet) in priority order,
+//! iteratively applies the rules while respecting neighbor-dependent constraints, and reports any
+//! atoms that fail to obtain an assignment.
 
-use super::rules::{Conditions, Rule};
-use crate::core::error::AssignmentError;
-use crate::core::properties::Element;
+use super::diagnostics::{AmbiguousAssignment, AssignmentDiagnostics, RuleMatch};
+use super::rules::{BondCondition, Conditions, NeighborCondition, Rule};
+use super::ruleset::RuleSet;
+use crate::compat::collections::{HashMap, HashSet};
+use crate::compat::prelude::*;
+use crate::core::error::{AssignmentError, LimitExceeded, TyperError, UnsettledAtom};
+use crate::core::limits::Budget;
+use crate::core::properties::{Element, GraphBondOrder, TopologyBondOrder};
+use crate::core::trace;
 use crate::perception::{AnnotatedAtom, AnnotatedMolecule};
-use std::collections::HashMap;
 
 /// Applies the rule deck to an annotated molecule and returns the assigned atom types.
 ///
-/// The function instantiates an internal [`TyperEngine`], sorts the incoming rules by priority,
-/// and runs iterative rounds until no new assignments occur or the maximum round budget is
-/// exhausted.
+/// The function instantiates an internal [`TyperEngine`] over the rule set's precomputed
+/// evaluation order and runs iterative rounds until no new assignments occur or the maximum round
+/// budget is exhausted.
 ///
 /// # Arguments
 ///
 /// * `molecule` - Fully perceived molecule containing the annotations consumed by each rule.
-/// * `rules` - Ordered list of rules to consider. Priority is enforced within the function.
+/// * `rules` - Compiled rule set whose evaluation order enforces priorities.
+/// * `pinned` - Forced type per atom, or an empty slice when no atom is pinned. Pinned atoms are
+///   never retyped, but their types are visible to the `neighbor_types` conditions of others.
+/// * `budget` - Resource budget providing the round cap and wall-clock limit.
+/// * `on_round` - Callback receiving the number of atoms holding a type after each round.
 ///
 /// # Returns
 ///
-/// Vector of atom-type strings aligned with `molecule.atoms`.
+/// The converged [`TypeAssignment`], including the forced types; atoms that no rule matched are
+/// left as `None` so callers can decide whether to fall back or fail.
 ///
 /// # Errors
 ///
-/// Returns [`AssignmentError`] if the engine exceeds the round limit or leaves any atoms untyped.
+/// Returns [`TyperError::LimitExceeded`] if the engine runs out of rounds or time before
+/// converging. Running out of rounds yields [`LimitExceeded::TypingRounds`], which lists the atoms
+/// still changing type together with the rules that retyped them.
 pub fn assign_types(
     molecule: &AnnotatedMolecule,
-    rules: &[Rule],
-) -> Result<Vec<String>, AssignmentError> {
+    rules: &RuleSet,
+    pinned: &[Option<String>],
+    budget: &Budget,
+    on_round: &mut dyn FnMut(usize),
+) -> Result<TypeAssignment, TyperError> {
     let mut engine = TyperEngine::new(molecule, rules);
-    engine.run()
+    engine.pin(pinned);
+    engine.run(budget, on_round)
+}
+
+/// Re-evaluates every rule against a converged assignment and reports tied matches.
+///
+/// Neighbor-type conditions are checked against the final types, so the result reflects the
+/// fixed point the engine reached rather than any intermediate round.
+///
+/// # Arguments
+///
+/// * `molecule` - Molecule the assignment was computed for.
+/// * `rules` - Rule set that produced the assignment.
+/// * `assignment` - Converged output of [`assign_types`].
+///
+/// # Returns
+///
+/// Confidence scores for every atom and the atoms whose winning priority was shared by rules
+/// proposing different types.
+pub fn diagnose(
+    molecule: &AnnotatedMolecule,
+    rules: &RuleSet,
+    assignment: &TypeAssignment,
+) -> AssignmentDiagnostics {
+    let engine = TyperEngine::converged(molecule, rules, assignment);
+
+    let mut diagnostics = AssignmentDiagnostics::default();
+    for atom in &molecule.atoms {
+        let mut matches = rules
+            .candidates(atom.element, atom.hybridization)
+            .filter(|rule| engine.match_conditions(atom, &rule.conditions));
+        let Some(best) = matches.next() else {
+            diagnostics.confidence.push(0.0);
+            continue;
+        };
+        let tied: Vec<&Rule> = core::iter::once(best)
+            .chain(matches.take_while(|rule| rules.rank(rule) == rules.rank(best)))
+            .collect();
+
+        let assigned_type = assignment.types[atom.id]
+            .clone()
+            .unwrap_or_else(|| best.result_type.clone());
+        let agreeing = tied
+            .iter()
+            .filter(|rule| rule.result_type == assigned_type)
+            .count();
+        diagnostics
+            .confidence
+            .push(agreeing as f64 / tied.len() as f64);
+
+        if agreeing < tied.len() {
+            diagnostics.ambiguous.push(AmbiguousAssignment {
+                atom_id: atom.id,
+                assigned_type,
+                priority: best.priority,
+                candidates: tied
+                    .iter()
+                    .map(|rule| RuleMatch {
+                        rule_name: rule.name.clone(),
+                        result_type: rule.result_type.clone(),
+                    })
+                    .collect(),
+            });
+        }
+    }
+    diagnostics
+}
+
+/// Returns the rule that produced each atom's converged type.
+///
+/// At the fixed point, the first rule in evaluation order that matches an atom is the one whose
+/// type the atom holds, so this re-evaluates the rules against the final neighbor types.
+///
+/// # Arguments
+///
+/// * `molecule` - Molecule the assignment was computed for.
+/// * `rules` - Rule set that produced the assignment.
+/// * `assignment` - Converged output of [`assign_types`].
+///
+/// # Returns
+///
+/// The winning rule per atom, or `None` for atoms no rule matched.
+pub fn winning_rules<'a>(
+    molecule: &'a AnnotatedMolecule,
+    rules: &'a RuleSet,
+    assignment: &TypeAssignment,
+) -> Vec<Option<&'a Rule>> {
+    let engine = TyperEngine::converged(molecule, rules, assignment);
+    molecule
+        .atoms
+        .iter()
+        .map(|atom| engine.find_best_matching_rule(atom))
+        .collect()
+}
+
+/// Converged per-atom result of the typing engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeAssignment {
+    /// Assigned type per atom, or `None` where no rule matched.
+    pub types: Vec<Option<String>>,
+    /// Number of engine rounds executed, including the final no-change round.
+    pub rounds_completed: u32,
+}
+
+impl TypeAssignment {
+    /// Returns the IDs of atoms that did not receive a type.
+    pub fn untyped_atom_ids(&self) -> Vec<usize> {
+        self.types
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.is_none())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Unwraps the assignment, requiring every atom to be typed.
+    ///
+    /// # Arguments
+    ///
+    /// * `molecule` - Molecule the assignment was computed for, used to describe untyped atoms.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AssignmentError`] listing the untyped atoms when coverage is incomplete.
+    pub fn into_complete(
+        self,
+        molecule: &AnnotatedMolecule,
+    ) -> Result<Vec<String>, AssignmentError> {
+        let untyped_atom_ids = self.untyped_atom_ids();
+        if !untyped_atom_ids.is_empty() {
+            return Err(AssignmentError {
+                untyped_atoms: untyped_atom_ids
+                    .iter()
+                    .map(|&id| molecule.atom_context(id))
+                    .collect(),
+                untyped_atom_ids,
+                rounds_completed: self.rounds_completed,
+            });
+        }
+        Ok(self.types.into_iter().flatten().collect())
+    }
 }
 
 /// Internal helper that owns iteration state while applying rules.
 struct TyperEngine<'a> {
     /// Annotated molecule referenced throughout evaluation.
     molecule: &'a AnnotatedMolecule,
-    /// Rule pointers sorted by priority (and name as tiebreaker) for deterministic iteration.
-    sorted_rules: Vec<&'a Rule>,
-    /// Current assignment per atom, storing the type name and the priority of the rule that set it.
-    atom_states: Vec<Option<(String, i32)>>,
+    /// Rule set supplying the evaluation order and rule ranks.
+    rules: &'a RuleSet,
+    /// IDs of bonds that belong to a resonance system.
+    resonant_bonds: HashSet<usize>,
+    /// Current assignment per atom, storing the type name and the rank of the rule that set it.
+    atom_states: Vec<Option<(String, (u32, i32))>>,
+    /// Every assignment made so far as `(round, atom ID, rule)`, in the order it happened.
+    changes: Vec<(u32, usize, &'a Rule)>,
 }
 
+/// Rank of forced types, above that of any rule.
+const PINNED_RANK: (u32, i32) = (u32::MAX, i32::MAX);
+
 impl<'a> TyperEngine<'a> {
-    /// Creates a typing engine with rules sorted by priority and name.
+    /// Creates a typing engine that evaluates rules in the rule set's priority order.
     ///
     /// # Arguments
     ///
     /// * `molecule` - Annotated molecule to type.
-    /// * `rules` - Rule slice provided by callers.
-    fn new(molecule: &'a AnnotatedMolecule, rules: &'a [Rule]) -> Self {
-        let mut sorted_rules: Vec<&'a Rule> = rules.iter().collect();
-        sorted_rules.sort_by(|a, b| {
-            b.priority
-                .cmp(&a.priority)
-                .then_with(|| a.name.cmp(&b.name))
-        });
+    /// * `rules` - Compiled rule set provided by callers.
+    fn new(molecule: &'a AnnotatedMolecule, rules: &'a RuleSet) -> Self {
+        let resonant_bonds = molecule
+            .resonance_systems
+            .iter()
+            .flat_map(|system| system.bond_ids.iter().copied())
+            .collect();
 
         Self {
             molecule,
-            sorted_rules,
+            rules,
+            resonant_bonds,
             atom_states: vec![None; molecule.atoms.len()],
+            changes: Vec::new(),
+        }
+    }
+
+    /// Seeds forced types that outrank every rule, so no rule ever replaces them.
+    ///
+    /// # Arguments
+    ///
+    /// * `pinned` - Forced type per atom, or an empty slice.
+    fn pin(&mut self, pinned: &[Option<String>]) {
+        for (state, forced) in self.atom_states.iter_mut().zip(pinned) {
+            if let Some(type_name) = forced {
+                *state = Some((type_name.clone(), PINNED_RANK));
+            }
         }
     }
 
+    fn converged(
+        molecule: &'a AnnotatedMolecule,
+        rules: &'a RuleSet,
+        assignment: &TypeAssignment,
+    ) -> Self {
+        let mut engine = Self::new(molecule, rules);
+        engine.atom_states = assignment
+            .types
+            .iter()
+            .map(|t| t.clone().map(|type_name| (type_name, (0, 0))))
+            .collect();
+        engine
+    }
+
     /// Executes iterative rounds until no more updates occur or the iteration cap is reached.
     ///
     /// # Errors
     ///
-    /// Returns [`AssignmentError`] when the engine hits the round limit or atoms remain untyped.
-    fn run(&mut self) -> Result<Vec<String>, AssignmentError> {
+    /// Returns [`TyperError::LimitExceeded`] when the engine hits the round or time limit.
+    /// A round-limit error carries the atoms that changed in the last round.
+    fn run(
+        &mut self,
+        budget: &Budget,
+        on_round: &mut dyn FnMut(usize),
+    ) -> Result<TypeAssignment, TyperError> {
+        let _span = trace::span!(
+            "typing",
+            atoms = self.molecule.atoms.len(),
+            pinned = self
+                .atom_states
+                .iter()
+                .filter(|state| state.as_ref().is_some_and(|(_, rank)| *rank == PINNED_RANK))
+                .count(),
+            rules = self.rules.len()
+        );
         let mut rounds = 0;
-        const MAX_ROUNDS: u32 = 100;
+        let max_rounds = budget.limits.max_typing_rounds;
 
         loop {
             rounds += 1;
-            if rounds > MAX_ROUNDS {
-                return Err(self.build_error(rounds));
+            if let Some(limit) = max_rounds.filter(|&limit| rounds > limit) {
+                return Err(LimitExceeded::TypingRounds {
+                    limit,
+                    unsettled: self.unsettled_atoms(limit),
+                }
+                .into());
             }
+            budget.check_time()?;
 
-            let changes = self.run_single_round();
+            let changes = self.run_single_round(rounds);
+            let typed = self
+                .atom_states
+                .iter()
+                .filter(|state| state.is_some())
+                .count();
+            trace::event!(
+                round = rounds,
+                rules_applied = changes,
+                typed,
+                "typing round finished"
+            );
+            on_round(typed);
             if changes == 0 {
                 break;
             }
         }
 
-        let mut final_types = Vec::with_capacity(self.molecule.atoms.len());
-        let mut untyped_ids = Vec::new();
-
-        for (i, state) in self.atom_states.iter().enumerate() {
-            if let Some((type_name, _)) = state {
-                final_types.push(type_name.clone());
-            } else {
-                untyped_ids.push(i);
-            }
-        }
-
-        if untyped_ids.is_empty() {
-            Ok(final_types)
-        } else {
-            Err(self.build_error(rounds))
-        }
+        Ok(TypeAssignment {
+            types: self
+                .atom_states
+                .drain(..)
+                .map(|state| state.map(|(type_name, _)| type_name))
+                .collect(),
+            rounds_completed: rounds,
+        })
     }
 
-    /// Performs a single pass over all atoms, applying higher-priority rules when possible.
+    /// Performs a single pass over all atoms, applying higher-ranked rules when possible.
+    ///
+    /// # Arguments
+    ///
+    /// * `round` - One-based number of the round, recorded with every change.
     ///
     /// # Returns
     ///
     /// Number of atoms whose assignment changed during the round.
-    fn run_single_round(&mut self) -> usize {
+    fn run_single_round(&mut self, round: u32) -> usize {
         let mut changes_count = 0;
 
         for atom in &self.molecule.atoms {
-            let current_priority = self.atom_states[atom.id].as_ref().map_or(-1, |(_, p)| *p);
+            let current_rank = self.atom_states[atom.id].as_ref().map(|(_, rank)| *rank);
+            if current_rank == Some(PINNED_RANK) {
+                continue;
+            }
 
-            if let Some(best_rule) = self
+            if let Some((best_rule, rank)) = self
                 .find_best_matching_rule(atom)
-                .filter(|rule| rule.priority > current_priority)
+                .map(|rule| (rule, self.rules.rank(rule)))
+                .filter(|(_, rank)| current_rank < Some(*rank))
             {
-                self.atom_states[atom.id] =
-                    Some((best_rule.result_type.clone(), best_rule.priority));
+                self.atom_states[atom.id] = Some((best_rule.result_type.clone(), rank));
+                self.changes.push((round, atom.id, best_rule));
                 changes_count += 1;
             }
         }
         changes_count
     }
 
+    /// Collects the atoms that changed in `round` along with their full assignment history.
+    fn unsettled_atoms(&self, round: u32) -> Vec<UnsettledAtom> {
+        let mut unsettled: Vec<UnsettledAtom> = self
+            .changes
+            .iter()
+            .filter(|&&(changed_in, _, _)| changed_in == round)
+            .map(|&(_, atom_id, _)| UnsettledAtom {
+                atom_id,
+                types: Vec::new(),
+                rules: Vec::new(),
+            })
+            .collect();
+        unsettled.sort_unstable_by_key(|atom| atom.atom_id);
+        for &(_, atom_id, rule) in &self.changes {
+            if let Ok(i) = unsettled.binary_search_by_key(&atom_id, |atom| atom.atom_id) {
+                unsettled[i].types.push(rule.result_type.clone());
+                unsettled[i].rules.push(rule.name.clone());
+            }
+        }
+        unsettled
+    }
+
     /// Finds the first rule whose conditions match the provided atom.
     ///
-    /// Rules are evaluated in the pre-sorted priority order, so the first match is the best match.
+    /// Only the rule set's bucket for the atom's element and hybridization is scanned. Rules are
+    /// evaluated in the pre-sorted priority order, so the first match is the best match.
     fn find_best_matching_rule(&self, atom: &AnnotatedAtom) -> Option<&'a Rule> {
-        self.sorted_rules
-            .iter()
+        self.rules
+            .candidates(atom.element, atom.hybridization)
             .find(|rule| self.match_conditions(atom, &rule.conditions))
-            .copied()
     }
 
     /// Evaluates whether an atom satisfies the condition filters of a rule.
@@ -155,18 +400,45 @@ impl<'a> TyperEngine<'a> {
         if conditions.degree.is_some_and(|d| d != atom.degree) {
             return false;
         }
+        if conditions
+            .coordination_bonds
+            .is_some_and(|count| count != atom.coordination_bonds)
+        {
+            return false;
+        }
         if conditions
             .is_in_ring
             .is_some_and(|lir| lir != atom.is_in_ring)
         {
             return false;
         }
+        if conditions
+            .is_bridgehead
+            .is_some_and(|ib| ib != atom.is_bridgehead)
+        {
+            return false;
+        }
+        if conditions.is_spiro.is_some_and(|is| is != atom.is_spiro) {
+            return false;
+        }
+        if conditions
+            .smallest_ring_size
+            .is_some_and(|size| Some(size) != atom.smallest_ring_size)
+        {
+            return false;
+        }
         if conditions
             .lone_pairs
             .is_some_and(|lp| lp != atom.lone_pairs)
         {
             return false;
         }
+        if conditions
+            .radical_electrons
+            .is_some_and(|count| count != atom.radical_electrons)
+        {
+            return false;
+        }
         if conditions
             .hybridization
             .is_some_and(|h| h != atom.hybridization)
@@ -191,12 +463,35 @@ impl<'a> TyperEngine<'a> {
         {
             return false;
         }
+        if conditions
+            .functional_group
+            .is_some_and(|fg| !atom.functional_groups.contains(&fg))
+        {
+            return false;
+        }
 
+        // Neighbor checks run from cheapest to most expensive: element counts and bond orders
+        // only read the adjacency, nested conditions recurse, and neighbor types depend on the
+        // current round's assignments.
         if !conditions.neighbor_elements.is_empty()
             && !self.match_neighbor_elements(atom, &conditions.neighbor_elements)
         {
             return false;
         }
+        if !conditions
+            .bonded_via
+            .iter()
+            .all(|required| self.match_bond_condition(atom, required))
+        {
+            return false;
+        }
+        if !conditions
+            .neighbors
+            .iter()
+            .all(|required| self.match_neighbor_condition(atom, required))
+        {
+            return false;
+        }
         if !conditions.neighbor_types.is_empty()
             && !self.match_neighbor_types(atom, &conditions.neighbor_types)
         {
@@ -217,53 +512,83 @@ impl<'a> TyperEngine<'a> {
         atom: &AnnotatedAtom,
         expected: &HashMap<Element, u8>,
     ) -> bool {
-        let mut actual_counts: HashMap<Element, u8> = HashMap::new();
-        for &(neighbor_id, _) in &self.molecule.adjacency[atom.id] {
-            let neighbor_element = self.molecule.atoms[neighbor_id].element;
-            *actual_counts.entry(neighbor_element).or_default() += 1;
-        }
+        let neighbors = &self.molecule.adjacency[atom.id];
+        expected.iter().all(|(&element, &count)| {
+            neighbors
+                .iter()
+                .filter(|&&(neighbor_id, _)| self.molecule.atoms[neighbor_id].element == element)
+                .count()
+                == usize::from(count)
+        })
+    }
 
-        expected
+    /// Checks that enough neighbors satisfy a nested condition set.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom` - Atom whose neighbors are inspected.
+    /// * `required` - Nested conditions and the minimum number of neighbors meeting them.
+    fn match_neighbor_condition(&self, atom: &AnnotatedAtom, required: &NeighborCondition) -> bool {
+        let matching = self.molecule.adjacency[atom.id]
             .iter()
-            .all(|(element, &count)| actual_counts.get(element).copied().unwrap_or(0) == count)
+            .filter(|&&(neighbor_id, _)| {
+                self.match_conditions(&self.molecule.atoms[neighbor_id], &required.conditions)
+            })
+            .count();
+        matching >= usize::from(required.count)
     }
 
-    /// Validates neighbor type assignments against the expected type-count map.
+    /// Checks that enough of the atom's bonds have the required order and neighbor element.
     ///
     /// # Arguments
     ///
-    /// * `atom` - Atom whose neighbors must already hold types.
-    /// * `expected` - Map of type labels and required counts.
-    fn match_neighbor_types(&self, atom: &AnnotatedAtom, expected: &HashMap<String, u8>) -> bool {
-        let mut actual_counts: HashMap<&str, u8> = HashMap::new();
-        for &(neighbor_id, _) in &self.molecule.adjacency[atom.id] {
-            if let Some((type_name, _)) = &self.atom_states[neighbor_id] {
-                *actual_counts.entry(type_name).or_default() += 1;
-            }
-        }
+    /// * `atom` - Atom whose bonds are inspected.
+    /// * `required` - Bond order, optional neighbor element, and minimum bond count.
+    fn match_bond_condition(&self, atom: &AnnotatedAtom, required: &BondCondition) -> bool {
+        let matching = self.molecule.adjacency[atom.id]
+            .iter()
+            .zip(&self.molecule.adjacency_with_bonds[atom.id])
+            .filter(|&(&(neighbor_id, order), edge)| {
+                let element = self.molecule.atoms[neighbor_id].element;
+                required.element.is_none_or(|e| e == element)
+                    && self.topology_order(edge.bond_id, order) == required.order
+            })
+            .count();
+        matching >= usize::from(required.count)
+    }
 
-        expected.iter().all(|(type_name, &count)| {
-            actual_counts.get(type_name.as_str()).copied().unwrap_or(0) == count
-        })
+    /// Returns the order a bond will carry in the output topology.
+    fn topology_order(&self, bond_id: usize, order: GraphBondOrder) -> TopologyBondOrder {
+        if self.resonant_bonds.contains(&bond_id) {
+            return TopologyBondOrder::Resonant;
+        }
+        match order {
+            GraphBondOrder::Single => TopologyBondOrder::Single,
+            GraphBondOrder::Double => TopologyBondOrder::Double,
+            GraphBondOrder::Triple => TopologyBondOrder::Triple,
+            GraphBondOrder::Aromatic => TopologyBondOrder::Resonant,
+        }
     }
 
-    /// Builds an [`AssignmentError`] describing the atoms left untyped along with iteration count.
+    /// Validates neighbor type assignments against the expected type-count map.
     ///
     /// # Arguments
     ///
-    /// * `rounds_completed` - Number of rounds executed before the failure was detected.
-    fn build_error(&self, rounds_completed: u32) -> AssignmentError {
-        let untyped_atom_ids = self
-            .atom_states
-            .iter()
-            .enumerate()
-            .filter(|(_, state)| state.is_none())
-            .map(|(i, _)| i)
-            .collect();
-        AssignmentError {
-            untyped_atom_ids,
-            rounds_completed,
-        }
+    /// * `atom` - Atom whose neighbors must already hold types.
+    /// * `expected` - Map of type labels and required counts.
+    fn match_neighbor_types(&self, atom: &AnnotatedAtom, expected: &HashMap<String, u8>) -> bool {
+        let neighbors = &self.molecule.adjacency[atom.id];
+        expected.iter().all(|(type_name, &count)| {
+            neighbors
+                .iter()
+                .filter(|&&(neighbor_id, _)| {
+                    self.atom_states[neighbor_id]
+                        .as_ref()
+                        .is_some_and(|(assigned, _)| assigned == type_name)
+                })
+                .count()
+                == usize::from(count)
+        })
     }
 }
 
@@ -271,7 +596,7 @@ impl<'a> TyperEngine<'a> {
 mod tests {
     use super::*;
     use crate::core::graph::MolecularGraph;
-    use crate::core::properties::{Element, GraphBondOrder, Hybridization};
+    use crate::core::properties::{Element, FunctionalGroup, GraphBondOrder, Hybridization};
     use crate::perception::AnnotatedMolecule;
 
     fn linear_ethene_like() -> AnnotatedMolecule {
@@ -315,7 +640,15 @@ mod tests {
         rules: &[Rule],
     ) -> Result<Vec<String>, AssignmentError> {
         annotate_sp2_carbons(molecule);
-        assign_types(molecule, rules)
+        assign_types(
+            molecule,
+            &RuleSet::new(rules.to_vec()),
+            &[],
+            &Budget::default(),
+            &mut |_| {},
+        )
+        .expect("typing should stay within limits")
+        .into_complete(molecule)
     }
 
     fn rule(name: &str, priority: i32, result_type: &str, conditions: Conditions) -> Rule {
@@ -417,6 +750,244 @@ mod tests {
         assert!(types.iter().take(2).all(|t| t == "C_R"));
     }
 
+    #[test]
+    fn nested_neighbor_conditions_reach_the_second_shell() {
+        let mut molecule = linear_ethene_like();
+        let element = |element| Conditions {
+            element: Some(element),
+            ..Conditions::default()
+        };
+        let vinyl_hydrogen = |carbon_hydrogens| Conditions {
+            element: Some(Element::H),
+            neighbors: vec![NeighborCondition {
+                count: 1,
+                conditions: Conditions {
+                    element: Some(Element::C),
+                    neighbors: vec![NeighborCondition {
+                        count: 1,
+                        conditions: Conditions {
+                            element: Some(Element::C),
+                            neighbor_elements: HashMap::from([(Element::H, carbon_hydrogens)]),
+                            ..Conditions::default()
+                        },
+                    }],
+                    ..Conditions::default()
+                },
+            }],
+            ..Conditions::default()
+        };
+        let rules = vec![
+            rule("Carbon", 1, "C_2", element(Element::C)),
+            rule("Hydrogen", 1, "H_", element(Element::H)),
+            rule("VinylH", 5, "H_v", vinyl_hydrogen(1)),
+            rule("GeminalH", 10, "H_g", vinyl_hydrogen(2)),
+        ];
+
+        let types = assign_types_for(&mut molecule, &rules).expect("typing should succeed");
+        assert_eq!(types, ["C_2", "C_2", "H_v", "H_v"]);
+    }
+
+    #[test]
+    fn bond_order_conditions_match_kekule_orders_to_the_right_element() {
+        let mut molecule = linear_ethene_like();
+        let bonded_via = |order, element| Conditions {
+            element: Some(Element::C),
+            bonded_via: vec![BondCondition {
+                order,
+                element: Some(element),
+                count: 1,
+            }],
+            ..Conditions::default()
+        };
+        let rules = vec![
+            rule("Carbon", 1, "C_3", condition()),
+            rule(
+                "Hydrogen",
+                5,
+                "H_",
+                Conditions {
+                    element: Some(Element::H),
+                    ..Conditions::default()
+                },
+            ),
+            rule(
+                "DoubleToC",
+                10,
+                "C_2",
+                bonded_via(TopologyBondOrder::Double, Element::C),
+            ),
+            rule(
+                "DoubleToO",
+                20,
+                "C_O",
+                bonded_via(TopologyBondOrder::Double, Element::O),
+            ),
+            rule(
+                "TripleToC",
+                20,
+                "C_1",
+                bonded_via(TopologyBondOrder::Triple, Element::C),
+            ),
+        ];
+
+        let types = assign_types_for(&mut molecule, &rules).expect("typing should succeed");
+        assert_eq!(types, ["C_2", "C_2", "H_", "H_"]);
+
+        molecule
+            .resonance_systems
+            .push(crate::perception::ResonanceSystem {
+                atom_ids: vec![0, 1],
+                bond_ids: vec![0],
+            });
+        let types = assign_types_for(&mut molecule, &rules).expect("typing should succeed");
+        assert_eq!(types, ["C_3", "C_3", "H_", "H_"]);
+    }
+
+    #[test]
+    fn functional_group_conditions_require_membership() {
+        let mut molecule = linear_ethene_like();
+        molecule.atoms[1].functional_groups = vec![FunctionalGroup::Amide, FunctionalGroup::Ester];
+        let rules = vec![
+            rule("Any", 1, "X_", condition()),
+            rule(
+                "EsterCarbon",
+                10,
+                "C_E",
+                Conditions {
+                    functional_group: Some(FunctionalGroup::Ester),
+                    ..Conditions::default()
+                },
+            ),
+            rule(
+                "NitroCarbon",
+                20,
+                "C_N",
+                Conditions {
+                    functional_group: Some(FunctionalGroup::Nitro),
+                    ..Conditions::default()
+                },
+            ),
+        ];
+
+        let types = assign_types_for(&mut molecule, &rules).expect("typing should succeed");
+        assert_eq!(types, ["X_", "C_E", "X_", "X_"]);
+    }
+
+    #[test]
+    fn ring_junction_conditions_match_flags() {
+        let mut molecule = linear_ethene_like();
+        molecule.atoms[0].is_bridgehead = true;
+        molecule.atoms[1].is_spiro = true;
+        let rules = vec![
+            rule("Any", 1, "X_", condition()),
+            rule(
+                "Bridgehead",
+                10,
+                "C_B",
+                Conditions {
+                    is_bridgehead: Some(true),
+                    ..Conditions::default()
+                },
+            ),
+            rule(
+                "Spiro",
+                10,
+                "C_S",
+                Conditions {
+                    is_spiro: Some(true),
+                    ..Conditions::default()
+                },
+            ),
+        ];
+
+        let types = assign_types_for(&mut molecule, &rules).expect("typing should succeed");
+        assert_eq!(types, ["C_B", "C_S", "X_", "X_"]);
+    }
+
+    #[test]
+    fn coordination_bonds_condition_matches_exact_count() {
+        let mut molecule = linear_ethene_like();
+        molecule.atoms[2].coordination_bonds = 1;
+        let rules = vec![
+            rule("Any", 1, "X_", condition()),
+            rule(
+                "MetalBound",
+                10,
+                "X_M",
+                Conditions {
+                    coordination_bonds: Some(1),
+                    ..Conditions::default()
+                },
+            ),
+        ];
+
+        let types = assign_types_for(&mut molecule, &rules).expect("typing should succeed");
+        assert_eq!(types, ["X_", "X_", "X_M", "X_"]);
+    }
+
+    #[test]
+    fn smallest_ring_size_condition_matches_exact_size() {
+        let mut molecule = linear_ethene_like();
+        molecule.atoms[0].smallest_ring_size = Some(3);
+        molecule.atoms[1].smallest_ring_size = Some(4);
+        let ring_of = |size| Conditions {
+            smallest_ring_size: Some(size),
+            ..Conditions::default()
+        };
+        let rules = vec![
+            rule("Any", 1, "X_", condition()),
+            rule("Ring3", 10, "C_3R", ring_of(3)),
+            rule("Ring5", 10, "C_5R", ring_of(5)),
+        ];
+
+        let types = assign_types_for(&mut molecule, &rules).expect("typing should succeed");
+        assert_eq!(types, ["C_3R", "X_", "X_", "X_"]);
+    }
+
+    #[test]
+    fn round_limit_reports_atoms_still_changing_with_their_rules() {
+        let mut molecule = linear_ethene_like();
+        annotate_sp2_carbons(&mut molecule);
+        let element = |element| Conditions {
+            element: Some(element),
+            ..Conditions::default()
+        };
+        let mut capped_carbon = element(Element::C);
+        capped_carbon.neighbor_types.insert("H_".to_string(), 1);
+        let rules = RuleSet::new(vec![
+            rule("BaseCarbon", 1, "C_2", element(Element::C)),
+            rule("CappedCarbon", 10, "C_R", capped_carbon),
+            rule("Hydrogens", 1, "H_", element(Element::H)),
+        ]);
+        let budget = Budget::start(crate::core::limits::ResourceLimits {
+            max_typing_rounds: Some(2),
+            ..Default::default()
+        });
+
+        // Hydrogens are typed after the carbons, so the carbons upgrade in round 2 and the
+        // confirming round 3 is over the cap.
+        let error = assign_types(&molecule, &rules, &[], &budget, &mut |_| {}).unwrap_err();
+        let TyperError::LimitExceeded(LimitExceeded::TypingRounds { limit, unsettled }) = &error
+        else {
+            panic!("expected a round-limit error, got {error:?}");
+        };
+        assert_eq!(*limit, 2);
+        assert_eq!(
+            unsettled
+                .iter()
+                .map(|atom| atom.atom_id)
+                .collect::<Vec<_>>(),
+            [0, 1]
+        );
+        assert_eq!(unsettled[0].types, ["C_2", "C_R"]);
+        assert_eq!(unsettled[0].rules, ["BaseCarbon", "CappedCarbon"]);
+        assert_eq!(error.atom_ids(), [0, 1]);
+        assert_eq!(
+            unsettled[0].to_string(),
+            "atom 0: C_2 (BaseCarbon) -> C_R (CappedCarbon)"
+        );
+    }
+
     #[test]
     fn returns_assignment_error_when_atoms_remain_untyped() {
         let mut molecule = linear_ethene_like();
@@ -434,5 +1005,49 @@ mod tests {
             assign_types_for(&mut molecule, &rules).expect_err("carbons should remain untyped");
         assert!(err.untyped_atom_ids.contains(&0));
         assert!(err.untyped_atom_ids.contains(&1));
+        assert_eq!(err.untyped_atoms.len(), err.untyped_atom_ids.len());
+        assert!(
+            err.untyped_atoms
+                .iter()
+                .all(|atom| atom.element == Element::C)
+        );
+    }
+
+    #[test]
+    fn diagnose_reports_ties_between_equal_priority_rules() {
+        let mut molecule = linear_ethene_like();
+        annotate_sp2_carbons(&mut molecule);
+        let carbon = || Conditions {
+            element: Some(Element::C),
+            ..Conditions::default()
+        };
+        let rules = RuleSet::new(vec![
+            rule("CarbonB", 5, "C_2", carbon()),
+            rule("CarbonA", 5, "C_R", carbon()),
+            rule("CarbonC", 5, "C_R", carbon()),
+            rule("Fallback", 1, "C_3", carbon()),
+            rule(
+                "Hydrogen",
+                1,
+                "H_",
+                Conditions {
+                    element: Some(Element::H),
+                    ..Conditions::default()
+                },
+            ),
+        ]);
+        let assignment = assign_types(&molecule, &rules, &[], &Budget::default(), &mut |_| {})
+            .expect("typing should stay within limits");
+
+        let diagnostics = diagnose(&molecule, &rules, &assignment);
+        assert_eq!(diagnostics.confidence, [2.0 / 3.0, 2.0 / 3.0, 1.0, 1.0]);
+        assert_eq!(diagnostics.ambiguous.len(), 2);
+        let ambiguity = diagnostics.ambiguity_for(1).expect("carbon 1 is ambiguous");
+        assert_eq!(ambiguity.assigned_type, "C_R");
+        assert_eq!(ambiguity.priority, 5);
+        assert_eq!(ambiguity.competing_types(), ["C_R", "C_2"]);
+        let names: Vec<_> = ambiguity.candidates.iter().map(|c| &c.rule_name).collect();
+        assert_eq!(names, ["CarbonA", "CarbonB", "CarbonC"]);
+        assert!(diagnostics.ambiguity_for(2).is_none());
     }
 }
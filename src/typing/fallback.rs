@@ -0,0 +1,89 @@
+//! Element-based DREIDING types used when degraded typing is allowed.
+//!
+//! Under [`AssignmentPolicy::Fallback`](crate::AssignmentPolicy::Fallback) an atom that no rule
+//! matches should not abort the whole molecule. Such atoms receive the most generic DREIDING
+//! type of their element instead: the hybridization-specific type where DREIDING defines one
+//! (`C_2`, `N_R`, `B_2`), the tetrahedral type otherwise, and the ion type for the metals
+//! DREIDING parameterizes. Dummy atoms keep their `Du` type, and elements DREIDING does not
+//! cover at all receive `X_`.
+
+use crate::core::properties::{Element, Hybridization};
+
+/// Placeholder type for atoms whose element has no DREIDING type.
+pub const UNKNOWN_TYPE: &str = "X_";
+
+/// Returns the generic DREIDING type for an atom of `element` with the given hybridization.
+///
+/// # Arguments
+///
+/// * `element` - Element of the atom the rules could not type.
+/// * `hybridization` - Perceived hybridization, used to pick among the B, C, N, and O types.
+///
+/// # Returns
+///
+/// A DREIDING label such as `"C_3"` or `"Si3"`, or [`UNKNOWN_TYPE`] for uncovered elements.
+pub fn element_type(element: Element, hybridization: Hybridization) -> &'static str {
+    use Element::*;
+    use Hybridization::{Resonant, SP, SP2};
+    match (element, hybridization) {
+        (H, _) => "H_",
+        (B, SP2 | Resonant) => "B_2",
+        (B, _) => "B_3",
+        (C, SP) => "C_1",
+        (C, SP2) => "C_2",
+        (C, Resonant) => "C_R",
+        (C, _) => "C_3",
+        (N, SP) => "N_1",
+        (N, SP2) => "N_2",
+        (N, Resonant) => "N_R",
+        (N, _) => "N_3",
+        (O, SP) => "O_1",
+        (O, SP2) => "O_2",
+        (O, Resonant) => "O_R",
+        (O, _) => "O_3",
+        (F, _) => "F_",
+        (Al, _) => "Al3",
+        (Si, _) => "Si3",
+        (P, _) => "P_3",
+        (S, _) => "S_3",
+        (Cl, _) => "Cl",
+        (Ga, _) => "Ga3",
+        (Ge, _) => "Ge3",
+        (As, _) => "As3",
+        (Se, _) => "Se3",
+        (Br, _) => "Br",
+        (In, _) => "In3",
+        (Sn, _) => "Sn3",
+        (Sb, _) => "Sb3",
+        (Te, _) => "Te3",
+        (I, _) => "I_",
+        (Na, _) => "Na",
+        (Ca, _) => "Ca",
+        (Fe, _) => "Fe",
+        (Zn, _) => "Zn",
+        (Dummy, _) => "Du",
+        _ => UNKNOWN_TYPE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_period_types_follow_hybridization() {
+        assert_eq!(element_type(Element::C, Hybridization::SP3), "C_3");
+        assert_eq!(element_type(Element::C, Hybridization::Unknown), "C_3");
+        assert_eq!(element_type(Element::N, Hybridization::Resonant), "N_R");
+        assert_eq!(element_type(Element::O, Hybridization::SP2), "O_2");
+        assert_eq!(element_type(Element::B, Hybridization::SP2), "B_2");
+    }
+
+    #[test]
+    fn uncovered_elements_receive_the_placeholder() {
+        assert_eq!(element_type(Element::Si, Hybridization::None), "Si3");
+        assert_eq!(element_type(Element::La, Hybridization::None), UNKNOWN_TYPE);
+        assert_eq!(element_type(Element::Og, Hybridization::None), UNKNOWN_TYPE);
+        assert_eq!(element_type(Element::Dummy, Hybridization::None), "Du");
+    }
+}
@@ -0,0 +1,162 @@
+//! Maps canonical DREIDING type names onto user-defined output labels.
+//!
+//! Legacy simulation engines often expect their own atom-type vocabulary (for example `CA` for
+//! aromatic carbon instead of `C_R`). A [`TypeLabelMap`](crate::TypeLabelMap) renames the assigned
+//! types just before the topology is built and refuses to produce partially renamed output.
+
+use crate::compat::collections::HashMap;
+use crate::compat::prelude::*;
+use crate::core::error::UnmappedTypesError;
+use alloc::collections::BTreeSet;
+use serde::Deserialize;
+
+/// Total mapping from canonical atom types to output labels.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::TypeLabelMap;
+///
+/// let labels = TypeLabelMap::parse(r#"
+///     C_R = "CA"
+///     H_ = "HA"
+/// "#).unwrap();
+///
+/// let types = vec!["C_R".to_string(), "H_".to_string()];
+/// assert_eq!(labels.apply(&types).unwrap(), ["CA", "HA"]);
+///
+/// let err = labels.apply(&["O_2".to_string()]).unwrap_err();
+/// assert_eq!(err.missing, ["O_2"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct TypeLabelMap {
+    /// Output label keyed by canonical type name.
+    labels: HashMap<String, String>,
+}
+
+impl TypeLabelMap {
+    /// Creates an empty mapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a mapping from a TOML document of `canonical = "label"` pairs.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - TOML document whose top-level keys are canonical type names.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`toml::de::Error`] when the document is not valid TOML or a value is not a string.
+    pub fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(content)
+    }
+
+    /// Adds or replaces the label for a canonical type.
+    ///
+    /// # Returns
+    ///
+    /// The previous label for `canonical`, if one was set.
+    pub fn insert(
+        &mut self,
+        canonical: impl Into<String>,
+        label: impl Into<String>,
+    ) -> Option<String> {
+        self.labels.insert(canonical.into(), label.into())
+    }
+
+    /// Returns the label configured for `canonical`, if any.
+    pub fn get(&self, canonical: &str) -> Option<&str> {
+        self.labels.get(canonical).map(String::as_str)
+    }
+
+    /// Returns the number of mapped types.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Returns `true` if no types are mapped.
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Renames every type in `types`, requiring the mapping to cover all of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `types` - Canonical atom types, typically one per atom.
+    ///
+    /// # Returns
+    ///
+    /// The relabeled types in the same order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnmappedTypesError`] listing (sorted, without duplicates) every type that has no
+    /// label.
+    pub fn apply(&self, types: &[String]) -> Result<Vec<String>, UnmappedTypesError> {
+        let missing: BTreeSet<&str> = types
+            .iter()
+            .filter(|t| !self.labels.contains_key(t.as_str()))
+            .map(String::as_str)
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(UnmappedTypesError {
+                missing: missing.into_iter().map(str::to_string).collect(),
+            });
+        }
+
+        Ok(types
+            .iter()
+            .map(|t| self.labels[t.as_str()].clone())
+            .collect())
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for TypeLabelMap {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self {
+            labels: iter
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn types(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn apply_renames_all_types_in_order() {
+        let map: TypeLabelMap = [("C_3", "CT"), ("H_", "HC")].into_iter().collect();
+        let renamed = map.apply(&types(&["C_3", "H_", "H_"])).unwrap();
+        assert_eq!(renamed, ["CT", "HC", "HC"]);
+    }
+
+    #[test]
+    fn apply_reports_every_missing_type_once() {
+        let mut map = TypeLabelMap::new();
+        map.insert("C_3", "CT");
+        let err = map
+            .apply(&types(&["O_3", "C_3", "H_HB", "O_3"]))
+            .unwrap_err();
+        assert_eq!(err.missing, ["H_HB", "O_3"]);
+    }
+
+    #[test]
+    fn parse_reads_flat_toml_table() {
+        let map = TypeLabelMap::parse("C_R = \"CA\"\n\"H_\" = \"HA\"\n").unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("C_R"), Some("CA"));
+        assert!(TypeLabelMap::parse("C_R = 1").is_err());
+    }
+}
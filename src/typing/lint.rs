@@ -0,0 +1,531 @@
+//! Static checks and corpus coverage for rule decks.
+//!
+//! Rule decks grow by accretion, and a new rule that is accidentally broader than an older one
+//! silently takes over every atom the older rule used to type. [`lint`](crate::rules::lint)
+//! inspects the rules themselves for such dead entries, while
+//! [`coverage`](crate::rules::coverage) types a corpus of molecules and reports which rules
+//! never fire.
+
+use super::engine;
+use super::rules::{Conditions, Rule};
+use super::ruleset::RuleSet;
+use crate::compat::collections::HashSet;
+use crate::compat::prelude::*;
+use crate::core::graph::MolecularGraph;
+use crate::core::limits::Budget;
+use crate::perception::{self, PerceptionOptions, PerceptionPipeline};
+use core::fmt;
+
+/// A problem found by [`lint`] in a rule deck.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleLint {
+    /// A rule that can never fire because an earlier rule matches every atom it matches.
+    Unreachable {
+        /// Name of the unreachable rule.
+        rule: String,
+        /// Name of the earlier rule whose conditions are a subset of this rule's.
+        shadowed_by: String,
+    },
+    /// A rule with the same conditions and result type as an earlier rule.
+    Duplicate {
+        /// Name of the redundant rule.
+        rule: String,
+        /// Name of the earlier rule it repeats.
+        duplicate_of: String,
+    },
+    /// A rule that requires a neighbor type no rule in the deck assigns.
+    UnknownNeighborType {
+        /// Name of the rule carrying the condition.
+        rule: String,
+        /// The neighbor type that can never be present.
+        neighbor_type: String,
+    },
+}
+
+impl RuleLint {
+    /// Returns the name of the rule the finding is about.
+    pub fn rule(&self) -> &str {
+        match self {
+            RuleLint::Unreachable { rule, .. }
+            | RuleLint::Duplicate { rule, .. }
+            | RuleLint::UnknownNeighborType { rule, .. } => rule,
+        }
+    }
+}
+
+impl fmt::Display for RuleLint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleLint::Unreachable { rule, shadowed_by } => {
+                write!(
+                    f,
+                    "rule '{rule}' is unreachable: shadowed by '{shadowed_by}'"
+                )
+            }
+            RuleLint::Duplicate { rule, duplicate_of } => {
+                write!(f, "rule '{rule}' duplicates '{duplicate_of}'")
+            }
+            RuleLint::UnknownNeighborType {
+                rule,
+                neighbor_type,
+            } => write!(
+                f,
+                "rule '{rule}' requires neighbor type '{neighbor_type}', which no rule assigns"
+            ),
+        }
+    }
+}
+
+/// Checks a rule deck for rules that can never fire or can never match.
+///
+/// A rule is unreachable when a rule evaluated before it (higher priority, or equal priority
+/// and a smaller name) places no constraint the later rule does not also place. Rules with
+/// identical conditions and result types are reported as duplicates instead.
+///
+/// # Arguments
+///
+/// * `rules` - Rule deck to inspect, in any order.
+///
+/// # Returns
+///
+/// Every finding, grouped by rule in evaluation order.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::rules::{RuleLint, lint, parse_rules};
+///
+/// let rules = parse_rules(r#"
+///     [[rule]]
+///     name = "C_any"
+///     priority = 10
+///     type = "C_3"
+///     [rule.conditions]
+///     element = "C"
+///
+///     [[rule]]
+///     name = "C_ring"
+///     priority = 5
+///     type = "C_R"
+///     [rule.conditions]
+///     element = "C"
+///     is_in_ring = true
+/// "#).unwrap();
+///
+/// assert_eq!(
+///     lint(&rules),
+///     vec![RuleLint::Unreachable {
+///         rule: "C_ring".to_string(),
+///         shadowed_by: "C_any".to_string(),
+///     }]
+/// );
+/// ```
+pub fn lint(rules: &[Rule]) -> Vec<RuleLint> {
+    let ruleset = RuleSet::new(rules.to_vec());
+    let ordered: Vec<&Rule> = ruleset.by_priority().collect();
+    let produced: HashSet<&str> = rules.iter().map(|rule| rule.result_type.as_str()).collect();
+
+    let mut findings = Vec::new();
+    for (index, rule) in ordered.iter().enumerate() {
+        let earlier = &ordered[..index];
+        if let Some(original) = earlier.iter().find(|earlier| {
+            earlier.result_type == rule.result_type && earlier.conditions == rule.conditions
+        }) {
+            findings.push(RuleLint::Duplicate {
+                rule: rule.name.clone(),
+                duplicate_of: original.name.clone(),
+            });
+        } else if let Some(shadow) = earlier
+            .iter()
+            .find(|earlier| is_subset(&earlier.conditions, &rule.conditions))
+        {
+            findings.push(RuleLint::Unreachable {
+                rule: rule.name.clone(),
+                shadowed_by: shadow.name.clone(),
+            });
+        }
+
+        let mut unknown = Vec::new();
+        collect_neighbor_types(&rule.conditions, &mut unknown);
+        unknown.retain(|neighbor_type| !produced.contains(neighbor_type.as_str()));
+        unknown.sort();
+        unknown.dedup();
+        findings.extend(
+            unknown
+                .into_iter()
+                .map(|neighbor_type| RuleLint::UnknownNeighborType {
+                    rule: rule.name.clone(),
+                    neighbor_type: neighbor_type.clone(),
+                }),
+        );
+    }
+    findings
+}
+
+/// Gathers the `neighbor_types` keys of `conditions` and of every nested neighbor condition.
+fn collect_neighbor_types<'a>(conditions: &'a Conditions, types: &mut Vec<&'a String>) {
+    types.extend(conditions.neighbor_types.keys());
+    for required in &conditions.neighbors {
+        collect_neighbor_types(&required.conditions, types);
+    }
+}
+
+/// Returns whether every atom matching `inner` also matches `outer`.
+fn is_subset(outer: &Conditions, inner: &Conditions) -> bool {
+    fn implied<T: PartialEq>(outer: &Option<T>, inner: &Option<T>) -> bool {
+        outer.is_none() || outer == inner
+    }
+
+    implied(&outer.element, &inner.element)
+        && implied(&outer.formal_charge, &inner.formal_charge)
+        && implied(&outer.degree, &inner.degree)
+        && implied(&outer.coordination_bonds, &inner.coordination_bonds)
+        && implied(&outer.is_in_ring, &inner.is_in_ring)
+        && implied(&outer.is_bridgehead, &inner.is_bridgehead)
+        && implied(&outer.is_spiro, &inner.is_spiro)
+        && implied(&outer.smallest_ring_size, &inner.smallest_ring_size)
+        && implied(&outer.lone_pairs, &inner.lone_pairs)
+        && implied(&outer.radical_electrons, &inner.radical_electrons)
+        && implied(&outer.hybridization, &inner.hybridization)
+        && implied(&outer.is_aromatic, &inner.is_aromatic)
+        && implied(&outer.is_anti_aromatic, &inner.is_anti_aromatic)
+        && implied(&outer.is_resonant, &inner.is_resonant)
+        && implied(&outer.functional_group, &inner.functional_group)
+        && outer
+            .neighbor_elements
+            .iter()
+            .all(|(element, count)| inner.neighbor_elements.get(element) == Some(count))
+        && outer
+            .neighbor_types
+            .iter()
+            .all(|(atom_type, count)| inner.neighbor_types.get(atom_type) == Some(count))
+        && outer.neighbors.iter().all(|required| {
+            inner.neighbors.iter().any(|candidate| {
+                candidate.count >= required.count
+                    && is_subset(&required.conditions, &candidate.conditions)
+            })
+        })
+        && outer.bonded_via.iter().all(|required| {
+            inner.bonded_via.iter().any(|candidate| {
+                candidate.order == required.order
+                    && candidate.count >= required.count
+                    && (required.element.is_none() || required.element == candidate.element)
+            })
+        })
+}
+
+/// How many atoms a single rule typed across a corpus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleUsage {
+    /// Name of the rule.
+    pub rule_name: String,
+    /// Number of atoms whose converged type came from this rule.
+    pub atoms_typed: usize,
+}
+
+/// Per-rule usage counts gathered by [`coverage`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleCoverage {
+    /// Usage of every rule, in the order the rules were given.
+    pub rules: Vec<RuleUsage>,
+    /// Atoms across the corpus that no rule typed.
+    pub untyped_atoms: usize,
+    /// Indices of graphs that failed perception or typing and were not counted.
+    pub failed_graphs: Vec<usize>,
+}
+
+impl RuleCoverage {
+    /// Returns the names of the rules that typed no atom, in the order the rules were given.
+    pub fn unused_rules(&self) -> Vec<&str> {
+        self.rules
+            .iter()
+            .filter(|usage| usage.atoms_typed == 0)
+            .map(|usage| usage.rule_name.as_str())
+            .collect()
+    }
+}
+
+/// Types a corpus of molecules and counts how often each rule decides an atom's type.
+///
+/// Each graph runs through the standard perception pipeline with default options and limits.
+/// Graphs that fail are listed in [`RuleCoverage::failed_graphs`] and otherwise ignored.
+///
+/// # Arguments
+///
+/// * `rules` - Rule deck to evaluate.
+/// * `graphs` - Corpus of molecules to type.
+///
+/// # Returns
+///
+/// Usage counts for every rule, plus the untyped atoms and failed graphs.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::rules::{coverage, get_default_rules};
+/// use dreid_typer::{Element, GraphBondOrder, MolecularGraph};
+///
+/// let mut water = MolecularGraph::new();
+/// let o = water.add_atom(Element::O);
+/// let h1 = water.add_atom(Element::H);
+/// let h2 = water.add_atom(Element::H);
+/// water.add_bond(o, h1, GraphBondOrder::Single).unwrap();
+/// water.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+///
+/// let report = coverage(get_default_rules(), &[water]);
+/// assert_eq!(report.untyped_atoms, 0);
+/// assert!(report.unused_rules().len() < report.rules.len());
+/// ```
+pub fn coverage(rules: &[Rule], graphs: &[MolecularGraph]) -> RuleCoverage {
+    let ruleset = RuleSet::new(rules.to_vec());
+    let mut report = RuleCoverage {
+        rules: rules
+            .iter()
+            .map(|rule| RuleUsage {
+                rule_name: rule.name.clone(),
+                atoms_typed: 0,
+            })
+            .collect(),
+        ..RuleCoverage::default()
+    };
+
+    for (index, graph) in graphs.iter().enumerate() {
+        let budget = Budget::default();
+        let typed = perception::perceive(
+            graph,
+            &[],
+            PerceptionPipeline::standard_ref(),
+            &PerceptionOptions::DEFAULT,
+            &budget,
+            &mut |_| {},
+        )
+        .and_then(|molecule| {
+            engine::assign_types(&molecule, &ruleset, &[], &budget, &mut |_| {})
+                .map(|assignment| (molecule, assignment))
+        });
+        let Ok((molecule, assignment)) = typed else {
+            report.failed_graphs.push(index);
+            continue;
+        };
+
+        for winner in engine::winning_rules(&molecule, &ruleset, &assignment) {
+            match winner.and_then(|rule| {
+                ruleset
+                    .rules()
+                    .iter()
+                    .position(|candidate| core::ptr::eq(candidate, rule))
+            }) {
+                Some(position) => report.rules[position].atoms_typed += 1,
+                None => report.untyped_atoms += 1,
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::properties::{Element, GraphBondOrder};
+    use crate::typing::rules::{get_default_rules, parse_rules};
+
+    fn rules(toml: &str) -> Vec<Rule> {
+        parse_rules(toml).expect("test rules should parse")
+    }
+
+    #[test]
+    fn default_rules_have_no_findings() {
+        assert_eq!(lint(get_default_rules()), Vec::new());
+    }
+
+    #[test]
+    fn identical_rules_are_reported_as_duplicates() {
+        let deck = rules(
+            r#"
+            [[rule]]
+            name = "A"
+            priority = 1
+            type = "H_"
+            [rule.conditions]
+            element = "H"
+
+            [[rule]]
+            name = "B"
+            priority = 1
+            type = "H_"
+            [rule.conditions]
+            element = "H"
+            "#,
+        );
+
+        assert_eq!(
+            lint(&deck),
+            vec![RuleLint::Duplicate {
+                rule: "B".to_string(),
+                duplicate_of: "A".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn neighbor_counts_must_match_exactly_to_shadow() {
+        let deck = rules(
+            r#"
+            [[rule]]
+            name = "two_h"
+            priority = 10
+            type = "O_W"
+            [rule.conditions]
+            element = "O"
+            neighbor_elements = { H = 2 }
+
+            [[rule]]
+            name = "one_h"
+            priority = 5
+            type = "O_3"
+            [rule.conditions]
+            element = "O"
+            neighbor_elements = { H = 1 }
+
+            [[rule]]
+            name = "two_h_sp3"
+            priority = 1
+            type = "O_3"
+            [rule.conditions]
+            element = "O"
+            hybridization = "SP3"
+            neighbor_elements = { H = 2 }
+            "#,
+        );
+
+        assert_eq!(
+            lint(&deck),
+            vec![RuleLint::Unreachable {
+                rule: "two_h_sp3".to_string(),
+                shadowed_by: "two_h".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn nested_neighbor_conditions_shadow_only_narrower_ones() {
+        let deck = rules(
+            r#"
+            [[rule]]
+            name = "near_nitrogen"
+            priority = 10
+            type = "C_N"
+            [rule.conditions]
+            element = "C"
+            [[rule.conditions.neighbors]]
+            conditions = { element = "N" }
+
+            [[rule]]
+            name = "near_nh"
+            priority = 5
+            type = "C_NH"
+            [rule.conditions]
+            element = "C"
+            [[rule.conditions.neighbors]]
+            conditions = { element = "N", neighbor_types = { H_X = 1 } }
+
+            [[rule]]
+            name = "near_two_oxygens"
+            priority = 1
+            type = "C_O"
+            [rule.conditions]
+            element = "C"
+            [[rule.conditions.neighbors]]
+            count = 2
+            conditions = { element = "O" }
+            "#,
+        );
+
+        assert_eq!(
+            lint(&deck),
+            vec![
+                RuleLint::Unreachable {
+                    rule: "near_nh".to_string(),
+                    shadowed_by: "near_nitrogen".to_string(),
+                },
+                RuleLint::UnknownNeighborType {
+                    rule: "near_nh".to_string(),
+                    neighbor_type: "H_X".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn neighbor_types_no_rule_assigns_are_reported() {
+        let deck = rules(
+            r#"
+            [[rule]]
+            name = "H_bond"
+            priority = 1
+            type = "H___HB"
+            [rule.conditions]
+            element = "H"
+            neighbor_types = { O_X = 1 }
+            "#,
+        );
+
+        let findings = lint(&deck);
+        assert_eq!(
+            findings,
+            vec![RuleLint::UnknownNeighborType {
+                rule: "H_bond".to_string(),
+                neighbor_type: "O_X".to_string(),
+            }]
+        );
+        assert_eq!(
+            findings[0].to_string(),
+            "rule 'H_bond' requires neighbor type 'O_X', which no rule assigns"
+        );
+    }
+
+    #[test]
+    fn coverage_counts_winning_rules_and_reports_unused_ones() {
+        let deck = rules(
+            r#"
+            [[rule]]
+            name = "hydrogen"
+            priority = 1
+            type = "H_"
+            [rule.conditions]
+            element = "H"
+
+            [[rule]]
+            name = "oxygen"
+            priority = 1
+            type = "O_3"
+            [rule.conditions]
+            element = "O"
+
+            [[rule]]
+            name = "nitrogen"
+            priority = 1
+            type = "N_3"
+            [rule.conditions]
+            element = "N"
+            "#,
+        );
+
+        let mut water = MolecularGraph::new();
+        let o = water.add_atom(Element::O);
+        let h1 = water.add_atom(Element::H);
+        let h2 = water.add_atom(Element::H);
+        water.add_bond(o, h1, GraphBondOrder::Single).unwrap();
+        water.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+        let mut carbon = MolecularGraph::new();
+        carbon.add_atom(Element::C);
+
+        let report = coverage(&deck, &[water, carbon]);
+
+        let counts: Vec<usize> = report.rules.iter().map(|usage| usage.atoms_typed).collect();
+        assert_eq!(counts, vec![2, 1, 0]);
+        assert_eq!(report.unused_rules(), vec!["nitrogen"]);
+        assert_eq!(report.untyped_atoms, 1);
+        assert!(report.failed_graphs.is_empty());
+    }
+}
@@ -3,7 +3,25 @@
 //! This namespace exposes the rule schema (`rules`) and the iterative assignment engine
 //! (`engine`) used by `assign_topology`.
 
+/// Confidence scores and tied-rule reports for audited typing runs.
+pub mod diagnostics;
 /// Typing engine that evaluates rules over annotated molecules.
 pub mod engine;
+/// Element-based DREIDING types used when the assignment policy allows degraded output.
+pub mod fallback;
+/// Output label remapping applied after typing.
+pub mod labels;
+/// Static rule-deck checks and corpus coverage reports.
+pub mod lint;
+/// Caller-pinned atom types that bypass the rules.
+pub mod overrides;
+/// Namespaced rule packs composed with explicit precedence.
+pub mod packs;
+/// Named force-field profiles selectable at runtime.
+pub mod registry;
 /// Rule definitions and parsing utilities.
 pub mod rules;
+/// Shared, precompiled rule sets.
+pub mod ruleset;
+/// Generic UFF types used when DREIDING rules do not cover an atom.
+pub mod uff;
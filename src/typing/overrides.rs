@@ -0,0 +1,257 @@
+//! Caller-pinned atom types that bypass the rule engine.
+//!
+//! A [`TypingOverrides`](crate::TypingOverrides) forces the type of selected atoms, addressed
+//! by ID, by [`AtomSelection`](crate::AtomSelection), or by the atom name (and optionally the
+//! residue) in their metadata. Pinned atoms are never retyped by rules, but their forced types
+//! are visible to the `neighbor_types` conditions of the atoms around them, so a hand-typed
+//! metal site still steers the typing of its ligands.
+
+use crate::compat::prelude::*;
+use crate::core::error::OverrideError;
+use crate::core::graph::MolecularGraph;
+use crate::core::metadata::ResidueId;
+use crate::core::selection::AtomSelection;
+use alloc::collections::BTreeMap;
+
+/// Atom types forced by the caller for a single molecule.
+///
+/// Overrides addressed by atom ID take precedence over overrides addressed by name. Forced types
+/// are emitted verbatim: they are not folded by the force-field variant, although an output
+/// label map still applies to them.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::{
+///     Element, GraphBondOrder, MolecularGraph, TypeProvenance, Typer, TypingOverrides,
+/// };
+///
+/// let mut graph = MolecularGraph::new();
+/// let c = graph.add_atom(Element::C);
+/// let o = graph.add_atom(Element::O);
+/// graph.add_bond(c, o, GraphBondOrder::Double).unwrap();
+/// for _ in 0..2 {
+///     let h = graph.add_atom(Element::H);
+///     graph.add_bond(c, h, GraphBondOrder::Single).unwrap();
+/// }
+///
+/// let mut overrides = TypingOverrides::new();
+/// overrides.pin(o, "O_3");
+///
+/// let topology = Typer::new()
+///     .assign_topology_with_overrides(&graph, &overrides)
+///     .unwrap();
+/// assert_eq!(topology.atoms[o].atom_type, "O_3");
+/// assert_eq!(topology.atoms[o].provenance, TypeProvenance::Override);
+/// assert_eq!(topology.atoms[c].atom_type, "C_2");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypingOverrides {
+    /// Forced type keyed by atom ID.
+    by_atom: BTreeMap<usize, String>,
+    /// Forced types addressed by atom name, in insertion order.
+    by_name: Vec<NamedOverride>,
+}
+
+/// Forced type for every atom carrying a given name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NamedOverride {
+    /// Atom name matched against [`AtomMetadata::name`](crate::AtomMetadata::name).
+    atom_name: String,
+    /// Residue the atom must belong to, or `None` to match the name in any residue.
+    residue: Option<ResidueId>,
+    /// Type forced onto matching atoms.
+    atom_type: String,
+}
+
+impl TypingOverrides {
+    /// Creates an empty set of overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forces the type of the atom with the given ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_id` - ID of the atom in the graph being typed.
+    /// * `atom_type` - Type the atom receives.
+    ///
+    /// # Returns
+    ///
+    /// The type previously forced onto `atom_id`, if any.
+    pub fn pin(&mut self, atom_id: usize, atom_type: impl Into<String>) -> Option<String> {
+        self.by_atom.insert(atom_id, atom_type.into())
+    }
+
+    /// Forces the same type onto every atom of a selection.
+    ///
+    /// # Arguments
+    ///
+    /// * `selection` - Atoms of the graph being typed, e.g. from a
+    ///   [`SelectionQuery`](crate::SelectionQuery).
+    /// * `atom_type` - Type every selected atom receives.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::io::smiles::parse_smiles;
+    /// use dreid_typer::{Element, SelectionQuery, Typer, TypingOverrides};
+    ///
+    /// let graph = parse_smiles("OCCO").unwrap();
+    /// let oxygens = SelectionQuery::element(Element::O).select(&graph).unwrap();
+    /// let mut overrides = TypingOverrides::new();
+    /// overrides.pin_selection(&oxygens, "O_2");
+    ///
+    /// let topology = Typer::new()
+    ///     .assign_topology_with_overrides(&graph, &overrides)
+    ///     .unwrap();
+    /// assert_eq!(topology.atoms[0].atom_type, "O_2");
+    /// assert_eq!(topology.atoms[3].atom_type, "O_2");
+    /// ```
+    pub fn pin_selection(&mut self, selection: &AtomSelection, atom_type: impl Into<String>) {
+        let atom_type = atom_type.into();
+        for &atom_id in selection.atom_ids() {
+            self.by_atom.insert(atom_id, atom_type.clone());
+        }
+    }
+
+    /// Forces the type of every atom whose metadata carries the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `atom_name` - Atom name to match (e.g., `"ZN"` or `"SG"`).
+    /// * `residue` - Residue the atom must belong to, or `None` to match every residue.
+    /// * `atom_type` - Type matching atoms receive.
+    pub fn pin_named(
+        &mut self,
+        atom_name: impl Into<String>,
+        residue: Option<ResidueId>,
+        atom_type: impl Into<String>,
+    ) {
+        self.by_name.push(NamedOverride {
+            atom_name: atom_name.into(),
+            residue,
+            atom_type: atom_type.into(),
+        });
+    }
+
+    /// Returns `true` if no override has been added.
+    pub fn is_empty(&self) -> bool {
+        self.by_atom.is_empty() && self.by_name.is_empty()
+    }
+
+    /// Resolves the overrides against a graph into a forced type per atom.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - Graph the overrides address.
+    ///
+    /// # Returns
+    ///
+    /// One entry per atom of `graph`, holding the forced type or `None` for atoms left to the
+    /// rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OverrideError`] when an atom ID is out of range or a name override matches no
+    /// atom.
+    pub fn resolve(&self, graph: &MolecularGraph) -> Result<Vec<Option<String>>, OverrideError> {
+        let mut pinned = vec![None; graph.atoms.len()];
+        for named in &self.by_name {
+            let mut matched = false;
+            for (slot, atom) in pinned.iter_mut().zip(&graph.atoms) {
+                if atom.metadata.name.as_deref() == Some(named.atom_name.as_str())
+                    && (named.residue.is_none() || atom.metadata.residue == named.residue)
+                {
+                    *slot = Some(named.atom_type.clone());
+                    matched = true;
+                }
+            }
+            if !matched {
+                return Err(OverrideError::UnmatchedName {
+                    atom_name: named.atom_name.clone(),
+                    residue: named.residue.clone(),
+                });
+            }
+        }
+        for (&atom_id, atom_type) in &self.by_atom {
+            let slot = pinned
+                .get_mut(atom_id)
+                .ok_or(OverrideError::AtomOutOfRange {
+                    atom_id,
+                    atom_count: graph.atoms.len(),
+                })?;
+            *slot = Some(atom_type.clone());
+        }
+        Ok(pinned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::metadata::AtomMetadata;
+    use crate::core::properties::Element;
+
+    fn two_residues() -> MolecularGraph {
+        let mut graph = MolecularGraph::new();
+        for number in [1, 2] {
+            let zn = graph.add_atom(Element::Zn);
+            graph
+                .set_atom_metadata(
+                    zn,
+                    AtomMetadata::named("ZN").with_residue(ResidueId::new("ZN", 'A', number)),
+                )
+                .unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn named_overrides_match_every_atom_or_only_the_given_residue() {
+        let graph = two_residues();
+
+        let mut everywhere = TypingOverrides::new();
+        everywhere.pin_named("ZN", None, "Zn");
+        assert_eq!(
+            everywhere.resolve(&graph).unwrap(),
+            [Some("Zn".to_string()), Some("Zn".to_string())]
+        );
+
+        let mut one = TypingOverrides::new();
+        one.pin_named("ZN", Some(ResidueId::new("ZN", 'A', 2)), "Zn");
+        assert_eq!(one.resolve(&graph).unwrap(), [None, Some("Zn".to_string())]);
+    }
+
+    #[test]
+    fn atom_overrides_take_precedence_over_named_ones() {
+        let mut overrides = TypingOverrides::new();
+        overrides.pin(0, "Zn2+");
+        overrides.pin_named("ZN", None, "Zn");
+        assert_eq!(
+            overrides.resolve(&two_residues()).unwrap(),
+            [Some("Zn2+".to_string()), Some("Zn".to_string())]
+        );
+    }
+
+    #[test]
+    fn unresolvable_overrides_are_rejected() {
+        let mut out_of_range = TypingOverrides::new();
+        out_of_range.pin(5, "Zn");
+        assert_eq!(
+            out_of_range.resolve(&two_residues()),
+            Err(OverrideError::AtomOutOfRange {
+                atom_id: 5,
+                atom_count: 2
+            })
+        );
+
+        let mut unmatched = TypingOverrides::new();
+        unmatched.pin_named("FE", None, "Fe");
+        assert!(matches!(
+            unmatched.resolve(&two_residues()),
+            Err(OverrideError::UnmatchedName { atom_name, residue: None }) if atom_name == "FE"
+        ));
+    }
+}
@@ -0,0 +1,221 @@
+//! Namespaced rule packs that can be composed with explicit precedence.
+//!
+//! Large organizations rarely maintain a single monolithic rule file. A
+//! [`RulePack`](crate::rules::RulePack) bundles the rules of one library (the built-in
+//! `dreiding` deck, a `lab-custom` extension, a `uff-fallback` safety net, ...) under a
+//! namespace. [`compose_rule_packs`](crate::rules::compose_rule_packs) merges several packs into
+//! one rule list in which every rule of a higher-precedence pack outranks every rule of a
+//! lower-precedence pack, while priorities keep their meaning inside each pack.
+
+use super::rules::{Rule, get_default_rules};
+use crate::compat::prelude::*;
+use serde::Deserialize;
+
+/// Namespace used for the embedded default DREIDING rules.
+pub const DEFAULT_NAMESPACE: &str = "dreiding";
+
+/// A named collection of typing rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RulePack {
+    /// Namespace identifying the pack (e.g., `"lab-custom"`).
+    pub namespace: String,
+    /// Rules contained in the pack.
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Deserialize)]
+struct RulePackFile {
+    namespace: String,
+    #[serde(rename = "rule", default)]
+    rules: Vec<Rule>,
+}
+
+impl RulePack {
+    /// Creates a pack from already parsed rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - Namespace identifying the pack.
+    /// * `rules` - Rules contained in the pack.
+    pub fn new(namespace: impl Into<String>, rules: Vec<Rule>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            rules,
+        }
+    }
+
+    /// Parses a pack from TOML containing a top-level `namespace` key and `[[rule]]` tables.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - TOML source text of the pack.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`toml::de::Error`] when the namespace is missing or any rule fails validation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::rules::RulePack;
+    ///
+    /// let pack = RulePack::parse(r#"
+    ///     namespace = "lab-custom"
+    ///
+    ///     [[rule]]
+    ///     name = "Silanol_O"
+    ///     priority = 10
+    ///     type = "O_3"
+    ///     conditions = { element = "O", neighbor_elements = { Si = 1, H = 1 } }
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(pack.namespace, "lab-custom");
+    /// assert_eq!(pack.rules.len(), 1);
+    /// ```
+    pub fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        let file: RulePackFile = toml::from_str(content)?;
+        Ok(Self::new(file.namespace, file.rules))
+    }
+
+    /// Returns the embedded DREIDING rules as a pack in the [`DEFAULT_NAMESPACE`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the embedded default rules file is malformed, which indicates a critical
+    /// library bug.
+    pub fn default_dreiding() -> Self {
+        Self::new(DEFAULT_NAMESPACE, get_default_rules().to_vec())
+    }
+}
+
+/// Merges rule packs into a single rule list, ordered from lowest to highest precedence.
+///
+/// Rule names are qualified as `namespace::name` so diagnostics identify the originating pack.
+/// Priorities are re-ranked so that every rule of a later pack outranks every rule of an earlier
+/// pack, while the relative order (including ties) within each pack is preserved.
+///
+/// # Arguments
+///
+/// * `packs` - Packs in ascending precedence; the last pack wins conflicts.
+///
+/// # Returns
+///
+/// The composed rules, ready for [`Typer::with_rules`](crate::Typer::with_rules).
+pub fn compose_rule_packs(packs: &[RulePack]) -> Vec<Rule> {
+    let mut ranked: Vec<(usize, &RulePack, &Rule)> = packs
+        .iter()
+        .enumerate()
+        .flat_map(|(precedence, pack)| pack.rules.iter().map(move |r| (precedence, pack, r)))
+        .collect();
+    ranked.sort_by_key(|&(precedence, _, rule)| (precedence, rule.priority));
+
+    let mut composed = Vec::with_capacity(ranked.len());
+    let mut rank = 0;
+    let mut previous = None;
+    for (precedence, pack, rule) in ranked {
+        let key = (precedence, rule.priority);
+        if previous.is_some_and(|prev| prev != key) {
+            rank += 1;
+        }
+        previous = Some(key);
+
+        composed.push(Rule {
+            name: format!("{}::{}", pack.namespace, rule.name),
+            priority: rank,
+            ..rule.clone()
+        });
+    }
+    composed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typing::rules::parse_rules;
+
+    fn pack(namespace: &str, toml: &str) -> RulePack {
+        RulePack::new(namespace, parse_rules(toml).expect("rules should parse"))
+    }
+
+    fn find<'a>(rules: &'a [Rule], name: &str) -> &'a Rule {
+        rules.iter().find(|r| r.name == name).expect("rule exists")
+    }
+
+    #[test]
+    fn later_packs_outrank_earlier_packs() {
+        let base = pack(
+            "base",
+            r#"
+            [[rule]]
+            name = "Specific"
+            priority = 500
+            type = "C_R"
+            conditions = { element = "C", is_aromatic = true }
+
+            [[rule]]
+            name = "Generic"
+            priority = 1
+            type = "C_3"
+            conditions = { element = "C" }
+            "#,
+        );
+        let custom = pack(
+            "lab",
+            r#"
+            [[rule]]
+            name = "Override"
+            priority = -5
+            type = "C_X"
+            conditions = { element = "C" }
+            "#,
+        );
+
+        let composed = compose_rule_packs(&[base, custom]);
+        let generic = find(&composed, "base::Generic").priority;
+        let specific = find(&composed, "base::Specific").priority;
+        let overriding = find(&composed, "lab::Override").priority;
+        assert!(generic < specific);
+        assert!(specific < overriding);
+    }
+
+    #[test]
+    fn ties_within_a_pack_are_preserved() {
+        let base = pack(
+            "base",
+            r#"
+            [[rule]]
+            name = "A"
+            priority = 7
+            type = "X"
+            conditions = {}
+
+            [[rule]]
+            name = "B"
+            priority = 7
+            type = "Y"
+            conditions = {}
+            "#,
+        );
+        let composed = compose_rule_packs(&[base]);
+        assert_eq!(composed[0].priority, composed[1].priority);
+    }
+
+    #[test]
+    fn parse_requires_namespace() {
+        assert!(
+            RulePack::parse(
+                "[[rule]]\nname = \"x\"\npriority = 1\ntype = \"X\"\nconditions = {}\n"
+            )
+            .is_err()
+        );
+        let empty = RulePack::parse("namespace = \"empty\"").expect("rules are optional");
+        assert!(empty.rules.is_empty());
+    }
+
+    #[test]
+    fn default_pack_uses_dreiding_namespace() {
+        let pack = RulePack::default_dreiding();
+        assert_eq!(pack.namespace, DEFAULT_NAMESPACE);
+        assert_eq!(pack.rules, get_default_rules());
+    }
+}
@@ -0,0 +1,327 @@
+//! Named force-field profiles that pair a rule set with a variant.
+//!
+//! A [`RuleRegistry`](crate::rules::RuleRegistry) maps profile names such as
+//! `"dreiding-default"` or `"dreiding-x6"` to rule sets, so downstream libraries can ship their
+//! own extended rules, register them once at startup, and let callers pick a profile by name.
+//! Profiles registered from TOML are parsed and compiled on first use and cached afterwards, like
+//! the embedded default deck.
+
+use super::rules::{Rule, parse_rules};
+use super::ruleset::RuleSet;
+use crate::compat::prelude::*;
+use crate::compat::sync::{OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use crate::core::error::RegistryError;
+use crate::params::ForceFieldVariant;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::fmt;
+
+/// Profile using the embedded DREIDING rules with the original variant.
+pub const DREIDING_DEFAULT: &str = "dreiding-default";
+/// Profile using the embedded DREIDING rules with the DREIDING/A variant.
+pub const DREIDING_A: &str = "dreiding-a";
+/// Profile using the embedded DREIDING rules with the exponential-6 variant.
+pub const DREIDING_X6: &str = "dreiding-x6";
+
+/// Lazily constructed registry shared by the whole process.
+static GLOBAL_REGISTRY: OnceLock<RuleRegistry> = OnceLock::new();
+
+/// A named rule set and the force-field variant it is meant to be typed for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForceFieldProfile {
+    /// Name the profile was registered under.
+    pub name: String,
+    /// Compiled rules of the profile.
+    pub rules: RuleSet,
+    /// Variant the profile's types are parameterized for.
+    pub variant: ForceFieldVariant,
+}
+
+/// Where a registered profile's rules come from.
+enum Source {
+    /// Rules compiled ahead of registration.
+    Compiled(RuleSet),
+    /// TOML text parsed on first use.
+    Toml {
+        text: Cow<'static, str>,
+        compiled: OnceLock<Result<RuleSet, toml::de::Error>>,
+    },
+}
+
+/// One registered profile.
+struct Entry {
+    source: Source,
+    variant: ForceFieldVariant,
+}
+
+impl Entry {
+    /// Returns the profile's rule set, parsing TOML sources on first use.
+    fn rules(&self, name: &str) -> Result<RuleSet, RegistryError> {
+        match &self.source {
+            Source::Compiled(rules) => Ok(rules.clone()),
+            Source::Toml { text, compiled } => compiled
+                .get_or_init(|| parse_rules(text).map(RuleSet::new))
+                .clone()
+                .map_err(|source| RegistryError::InvalidRules {
+                    name: name.to_string(),
+                    source,
+                }),
+        }
+    }
+}
+
+/// Thread-safe map from profile names to rule sets.
+///
+/// [`RuleRegistry::new`] and [`RuleRegistry::global`] both start with the built-in profiles
+/// [`DREIDING_DEFAULT`], [`DREIDING_A`], and [`DREIDING_X6`], which share the embedded default
+/// rules. Registering a name that already exists replaces the earlier profile.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::params::ForceFieldVariant;
+/// use dreid_typer::rules::RuleRegistry;
+/// use dreid_typer::{Element, MolecularGraph, Typer};
+///
+/// let registry = RuleRegistry::new();
+/// registry.register_toml(
+///     "noble-gases",
+///     r#"
+///         [[rule]]
+///         name = "Xenon"
+///         priority = 1
+///         type = "Xe"
+///         conditions = { element = "Xe" }
+///     "#,
+///     ForceFieldVariant::Dreiding,
+/// );
+///
+/// let profile = registry.profile("noble-gases").unwrap();
+/// let mut graph = MolecularGraph::new();
+/// graph.add_atom(Element::Xe);
+/// let topology = Typer::with_profile(&profile).assign_topology(&graph).unwrap();
+/// assert_eq!(topology.atoms[0].atom_type, "Xe");
+///
+/// assert!(registry.profile("missing").is_err());
+/// ```
+pub struct RuleRegistry {
+    entries: RwLock<BTreeMap<String, Arc<Entry>>>,
+}
+
+impl RuleRegistry {
+    /// Creates a registry holding only the built-in profiles.
+    pub fn new() -> Self {
+        let registry = Self {
+            entries: RwLock::new(BTreeMap::new()),
+        };
+        for (name, variant) in [
+            (DREIDING_DEFAULT, ForceFieldVariant::Dreiding),
+            (DREIDING_A, ForceFieldVariant::DreidingA),
+            (DREIDING_X6, ForceFieldVariant::X6),
+        ] {
+            registry.register(name, RuleSet::default_dreiding(), variant);
+        }
+        registry
+    }
+
+    /// Returns the process-wide registry.
+    ///
+    /// Libraries register their profiles here at startup so that applications can select them by
+    /// name without depending on the library's rule files directly.
+    pub fn global() -> &'static RuleRegistry {
+        GLOBAL_REGISTRY.get_or_init(Self::new)
+    }
+
+    /// Registers an already compiled rule set under `name`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Profile name; an existing profile with this name is replaced.
+    /// * `rules` - Rules the profile types with.
+    /// * `variant` - Variant the profile is parameterized for.
+    pub fn register(&self, name: impl Into<String>, rules: RuleSet, variant: ForceFieldVariant) {
+        self.insert(
+            name.into(),
+            Entry {
+                source: Source::Compiled(rules),
+                variant,
+            },
+        );
+    }
+
+    /// Registers rules given as TOML text, parsed the first time the profile is requested.
+    ///
+    /// Passing a `&'static str` (e.g., from `include_str!`) avoids copying the text.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Profile name; an existing profile with this name is replaced.
+    /// * `toml` - Rules in the same `[[rule]]` format as the default rules file.
+    /// * `variant` - Variant the profile is parameterized for.
+    pub fn register_toml(
+        &self,
+        name: impl Into<String>,
+        toml: impl Into<Cow<'static, str>>,
+        variant: ForceFieldVariant,
+    ) {
+        self.insert(
+            name.into(),
+            Entry {
+                source: Source::Toml {
+                    text: toml.into(),
+                    compiled: OnceLock::new(),
+                },
+                variant,
+            },
+        );
+    }
+
+    /// Registers a plain rule list, compiling it immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Profile name; an existing profile with this name is replaced.
+    /// * `rules` - Rules the profile types with.
+    /// * `variant` - Variant the profile is parameterized for.
+    pub fn register_rules(
+        &self,
+        name: impl Into<String>,
+        rules: Vec<Rule>,
+        variant: ForceFieldVariant,
+    ) {
+        self.register(name, RuleSet::new(rules), variant);
+    }
+
+    /// Removes the profile registered under `name`, returning whether one existed.
+    pub fn unregister(&self, name: &str) -> bool {
+        self.write().remove(name).is_some()
+    }
+
+    /// Looks up a profile by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RegistryError::UnknownProfile`] if no profile has that name, or
+    /// [`RegistryError::InvalidRules`] if the profile was registered from TOML that does not
+    /// parse. A parse failure is cached and reported again on every lookup.
+    pub fn profile(&self, name: &str) -> Result<ForceFieldProfile, RegistryError> {
+        let entry =
+            self.read()
+                .get(name)
+                .cloned()
+                .ok_or_else(|| RegistryError::UnknownProfile {
+                    name: name.to_string(),
+                })?;
+        Ok(ForceFieldProfile {
+            name: name.to_string(),
+            rules: entry.rules(name)?,
+            variant: entry.variant,
+        })
+    }
+
+    /// Returns `true` if a profile is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.read().contains_key(name)
+    }
+
+    /// Returns the registered profile names in lexicographic order.
+    pub fn names(&self) -> Vec<String> {
+        self.read().keys().cloned().collect()
+    }
+
+    fn insert(&self, name: String, entry: Entry) {
+        self.write().insert(name, Arc::new(entry));
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, BTreeMap<String, Arc<Entry>>> {
+        self.entries.read()
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, BTreeMap<String, Arc<Entry>>> {
+        self.entries.write()
+    }
+}
+
+impl Default for RuleRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for RuleRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RuleRegistry")
+            .field("profiles", &self.names())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_profiles_share_the_default_rules() {
+        let registry = RuleRegistry::new();
+        assert_eq!(
+            registry.names(),
+            [DREIDING_A, DREIDING_DEFAULT, DREIDING_X6]
+        );
+
+        let x6 = registry.profile(DREIDING_X6).unwrap();
+        assert_eq!(x6.variant, ForceFieldVariant::X6);
+        assert!(x6.rules.ptr_eq(&RuleSet::default_dreiding()));
+    }
+
+    #[test]
+    fn toml_profiles_are_parsed_once_and_cached() {
+        let registry = RuleRegistry::new();
+        registry.register_toml(
+            "custom",
+            r#"
+                [[rule]]
+                name = "AnyCarbon"
+                priority = 1
+                type = "C_X"
+                conditions = { element = "C" }
+            "#,
+            ForceFieldVariant::DreidingA,
+        );
+
+        let first = registry.profile("custom").unwrap();
+        let second = registry.profile("custom").unwrap();
+        assert_eq!(first.rules.len(), 1);
+        assert_eq!(first.variant, ForceFieldVariant::DreidingA);
+        assert!(first.rules.ptr_eq(&second.rules));
+    }
+
+    #[test]
+    fn lookups_report_unknown_names_and_invalid_rules() {
+        let registry = RuleRegistry::new();
+        registry.register_toml("broken", "[[rule]]\nname = 1", ForceFieldVariant::Dreiding);
+
+        assert!(matches!(
+            registry.profile("broken"),
+            Err(RegistryError::InvalidRules { name, .. }) if name == "broken"
+        ));
+        assert!(registry.unregister("broken"));
+        assert_eq!(
+            registry.profile("broken"),
+            Err(RegistryError::UnknownProfile {
+                name: "broken".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn registering_an_existing_name_replaces_the_profile() {
+        let registry = RuleRegistry::new();
+        registry.register_rules(DREIDING_DEFAULT, Vec::new(), ForceFieldVariant::X6);
+
+        let profile = registry.profile(DREIDING_DEFAULT).unwrap();
+        assert!(profile.rules.is_empty());
+        assert_eq!(profile.variant, ForceFieldVariant::X6);
+        assert_eq!(registry.names().len(), 3);
+    }
+}
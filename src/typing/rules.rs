@@ -4,12 +4,14 @@
 //! serde utilities that allow rules to reference elements, hybridizations, and neighbor counts by
 //! symbolic keys.
 
-use crate::core::properties::{Element, Hybridization};
+use crate::compat::collections::HashMap;
+use crate::compat::prelude::*;
+use crate::compat::sync::OnceLock;
+use crate::core::error::RuleParseError;
+use crate::core::properties::{Element, FunctionalGroup, Hybridization, TopologyBondOrder};
+use core::fmt;
+use core::str::FromStr;
 use serde::{Deserialize, Deserializer, de};
-use std::collections::HashMap;
-use std::fmt;
-use std::str::FromStr;
-use std::sync::OnceLock;
 
 /// User-facing rule that assigns a DREIDING type when its conditions match an atom.
 ///
@@ -47,13 +49,28 @@ pub struct Conditions {
     /// Required degree (number of σ bonds) for the atom.
     #[serde(default)]
     pub degree: Option<u8>,
+    /// Required number of coordination bonds from a ligand atom to metal centers.
+    #[serde(default)]
+    pub coordination_bonds: Option<u8>,
     /// Whether the atom must belong (or not belong) to a ring system.
     #[serde(default)]
     pub is_in_ring: Option<bool>,
+    /// Whether the atom must be a bridgehead of a bridged ring system.
+    #[serde(default)]
+    pub is_bridgehead: Option<bool>,
+    /// Whether the atom must be the single atom joining two spiro-fused rings.
+    #[serde(default)]
+    pub is_spiro: Option<bool>,
+    /// Required size of the smallest ring the atom belongs to.
+    #[serde(default)]
+    pub smallest_ring_size: Option<u8>,
 
     /// Required lone-pair count after electron perception.
     #[serde(default)]
     pub lone_pairs: Option<u8>,
+    /// Required number of unpaired electrons stated by the input.
+    #[serde(default)]
+    pub radical_electrons: Option<u8>,
     /// Required hybridization assignment (SP, SP2, SP3, etc.).
     #[serde(default)]
     pub hybridization: Option<Hybridization>,
@@ -67,13 +84,145 @@ pub struct Conditions {
     /// Whether the atom must participate in any resonance system.
     #[serde(default)]
     pub is_resonant: Option<bool>,
+    /// Functional group the atom must belong to.
+    #[serde(default)]
+    pub functional_group: Option<FunctionalGroup>,
 
-    /// Minimum counts for neighbor elements keyed by element symbol strings.
+    /// Exact counts for neighbor elements keyed by element symbol strings.
     #[serde(default, deserialize_with = "deserialize_str_keyed_map")]
     pub neighbor_elements: HashMap<Element, u8>,
-    /// Minimum counts for neighbor atom types identified by their DREIDING labels.
+    /// Exact counts for neighbor atom types identified by their DREIDING labels.
     #[serde(default)]
     pub neighbor_types: HashMap<String, u8>,
+    /// Nested requirements on neighbors, each evaluated with the neighbor as the subject.
+    #[serde(default)]
+    pub neighbors: Vec<NeighborCondition>,
+    /// Requirements on the orders of the atom's bonds.
+    #[serde(default)]
+    pub bonded_via: Vec<BondCondition>,
+}
+
+/// Requirement that enough of an atom's bonds have a given order.
+///
+/// Orders are compared as they appear in the output topology: bonds in a resonance system are
+/// `Resonant` regardless of their Kekulé order, and every other bond keeps its Kekulé order. A
+/// carbonyl carbon, for example, can be required to carry a double bond to oxygen:
+///
+/// ```toml
+/// bonded_via = [{ order = "Double", element = "O" }]
+/// ```
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BondCondition {
+    /// Required bond order.
+    pub order: TopologyBondOrder,
+    /// Required element of the bonded neighbor; any element when absent.
+    #[serde(default)]
+    pub element: Option<Element>,
+    /// Minimum number of bonds that must match; defaults to one.
+    #[serde(default = "default_neighbor_count")]
+    pub count: u8,
+}
+
+/// Requirement that enough of an atom's neighbors satisfy a nested set of conditions.
+///
+/// The nested [`Conditions`] may carry their own `neighbors`, so a rule can describe the
+/// second coordination shell and beyond, e.g. a carbon bonded to an sp2 nitrogen that carries a
+/// hydrogen:
+///
+/// ```toml
+/// [[rule.conditions.neighbors]]
+/// conditions = { element = "N", hybridization = "SP2", neighbor_elements = { H = 1 } }
+/// ```
+///
+/// A neighbor's own neighbors include the atom being typed.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct NeighborCondition {
+    /// Minimum number of neighbors that must satisfy `conditions`; defaults to one.
+    #[serde(default = "default_neighbor_count")]
+    pub count: u8,
+    /// Conditions each counted neighbor must satisfy.
+    pub conditions: Conditions,
+}
+
+/// Default for [`NeighborCondition::count`] and [`BondCondition::count`].
+fn default_neighbor_count() -> u8 {
+    1
+}
+
+/// Weight of each required neighbor in [`Conditions::specificity`].
+const NEIGHBOR_SPECIFICITY: u32 = 2;
+
+impl Conditions {
+    /// Scores how narrowly these conditions constrain an atom.
+    ///
+    /// Each populated property filter adds one point; each required neighbor adds
+    /// two, since a neighbor constraint pins down more of the environment than a single
+    /// property does. Neighbors required by a [`NeighborCondition`] also add the specificity of
+    /// their nested conditions, and bonds required by a [`BondCondition`] add one more point when
+    /// they also fix the neighbor's element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::rules::Conditions;
+    /// use dreid_typer::Element;
+    ///
+    /// let mut conditions = Conditions {
+    ///     element: Some(Element::O),
+    ///     ..Conditions::default()
+    /// };
+    /// assert_eq!(conditions.specificity(), 1);
+    ///
+    /// conditions.neighbor_elements.insert(Element::H, 2);
+    /// assert_eq!(conditions.specificity(), 5);
+    /// ```
+    pub fn specificity(&self) -> u32 {
+        let properties = [
+            self.element.is_some(),
+            self.formal_charge.is_some(),
+            self.degree.is_some(),
+            self.coordination_bonds.is_some(),
+            self.is_in_ring.is_some(),
+            self.is_bridgehead.is_some(),
+            self.is_spiro.is_some(),
+            self.smallest_ring_size.is_some(),
+            self.lone_pairs.is_some(),
+            self.radical_electrons.is_some(),
+            self.hybridization.is_some(),
+            self.is_aromatic.is_some(),
+            self.is_anti_aromatic.is_some(),
+            self.is_resonant.is_some(),
+            self.functional_group.is_some(),
+        ]
+        .into_iter()
+        .filter(|&populated| populated)
+        .count() as u32;
+        let neighbors: u32 = self
+            .neighbor_elements
+            .values()
+            .chain(self.neighbor_types.values())
+            .map(|&count| u32::from(count))
+            .sum();
+        let nested: u32 = self
+            .neighbors
+            .iter()
+            .map(|required| {
+                u32::from(required.count)
+                    * (NEIGHBOR_SPECIFICITY + required.conditions.specificity())
+            })
+            .sum();
+        let bonds: u32 = self
+            .bonded_via
+            .iter()
+            .map(|required| {
+                u32::from(required.count)
+                    * (NEIGHBOR_SPECIFICITY + u32::from(required.element.is_some()))
+            })
+            .sum();
+        properties + NEIGHBOR_SPECIFICITY * neighbors + nested + bonds
+    }
 }
 
 /// Helper struct that mirrors the `[ [rule] ]` array in the TOML file.
@@ -127,6 +276,236 @@ pub fn parse_rules(content: &str) -> Result<Vec<Rule>, toml::de::Error> {
     Ok(ruleset.rules)
 }
 
+/// Parses a JSON ruleset into a list of [`Rule`] values.
+///
+/// Rules use the same schema as [`parse_rules`]. The document may be a bare array of rule objects
+/// or an object whose `"rule"` key holds that array, mirroring the TOML `[[rule]]` layout.
+///
+/// # Arguments
+///
+/// * `content` - JSON source text.
+///
+/// # Returns
+///
+/// Vector of fully materialized [`Rule`] structs in the order they appear in the document.
+///
+/// # Errors
+///
+/// Returns [`serde_json::Error`] when the document is not valid JSON or when one of the rules
+/// fails validation.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::rules::parse_rules_json;
+///
+/// let json = r#"[
+///     {
+///         "name": "C_sp2",
+///         "priority": 10,
+///         "type": "C_R",
+///         "conditions": { "element": "C", "degree": 3 }
+///     }
+/// ]"#;
+///
+/// let rules = parse_rules_json(json).unwrap();
+/// assert_eq!(rules[0].result_type, "C_R");
+/// ```
+#[cfg(feature = "json")]
+pub fn parse_rules_json(content: &str) -> Result<Vec<Rule>, serde_json::Error> {
+    if content.trim_start().starts_with('[') {
+        serde_json::from_str(content)
+    } else {
+        serde_json::from_str::<Ruleset>(content).map(|ruleset| ruleset.rules)
+    }
+}
+
+/// Parses a YAML ruleset into a list of [`Rule`] values.
+///
+/// Rules use the same schema as [`parse_rules`]. The document may be a bare sequence of rule
+/// mappings or a mapping whose `rule` key holds that sequence, mirroring the TOML `[[rule]]`
+/// layout.
+///
+/// # Arguments
+///
+/// * `content` - YAML source text.
+///
+/// # Returns
+///
+/// Vector of fully materialized [`Rule`] structs in the order they appear in the document.
+///
+/// # Errors
+///
+/// Returns [`serde_yaml_ng::Error`] when the document is not valid YAML or when one of the rules
+/// fails validation.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::rules::parse_rules_yaml;
+///
+/// let yaml = "
+/// - name: C_sp2
+///   priority: 10
+///   type: C_R
+///   conditions: { element: C, degree: 3 }
+/// ";
+///
+/// let rules = parse_rules_yaml(yaml).unwrap();
+/// assert_eq!(rules[0].result_type, "C_R");
+/// ```
+#[cfg(feature = "yaml")]
+pub fn parse_rules_yaml(content: &str) -> Result<Vec<Rule>, serde_yaml_ng::Error> {
+    let document: serde_yaml_ng::Value = serde_yaml_ng::from_str(content)?;
+    if document.is_sequence() {
+        serde_yaml_ng::from_value(document)
+    } else {
+        serde_yaml_ng::from_value::<Ruleset>(document).map(|ruleset| ruleset.rules)
+    }
+}
+
+/// Serialization format of a rule document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuleFormat {
+    /// `[[rule]]` tables, read by [`parse_rules`].
+    Toml,
+    /// JSON, read by `parse_rules_json` with the `json` feature.
+    Json,
+    /// YAML, read by `parse_rules_yaml` with the `yaml` feature.
+    Yaml,
+}
+
+impl RuleFormat {
+    /// Returns the format conventionally stored under a file extension, ignoring ASCII case.
+    ///
+    /// Recognizes `toml`, `json`, `yaml`, and `yml`.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Guesses the format of a rule document from its first significant line.
+    ///
+    /// Blank lines and `#` comments are skipped. A document opening with `{`, or with `[` followed
+    /// by `{` or `]`, is JSON; one opening with a `[table]` header or a `key = value` pair is TOML;
+    /// anything else is YAML. An empty document is reported as TOML.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::rules::RuleFormat;
+    ///
+    /// assert_eq!(RuleFormat::detect("[[rule]]\nname = \"H\""), RuleFormat::Toml);
+    /// assert_eq!(RuleFormat::detect("[{ \"name\": \"H\" }]"), RuleFormat::Json);
+    /// assert_eq!(RuleFormat::detect("# rules\n- name: H"), RuleFormat::Yaml);
+    /// ```
+    pub fn detect(content: &str) -> Self {
+        let mut rest = content.trim_start();
+        while rest.starts_with('#') {
+            rest = rest
+                .split_once('\n')
+                .map_or("", |(_, tail)| tail)
+                .trim_start();
+        }
+
+        if rest.starts_with('{') {
+            return Self::Json;
+        }
+        if let Some(inner) = rest.strip_prefix('[') {
+            let inner = inner.trim_start();
+            return if inner.starts_with('{') || inner.starts_with(']') {
+                Self::Json
+            } else {
+                Self::Toml
+            };
+        }
+
+        let line = rest.lines().next().unwrap_or("");
+        match line.find(['=', ':']) {
+            Some(index) if line[index..].starts_with(':') => Self::Yaml,
+            Some(_) => Self::Toml,
+            None if rest.is_empty() => Self::Toml,
+            None => Self::Yaml,
+        }
+    }
+
+    /// Cargo feature required to parse this format, if any.
+    fn feature(self) -> Option<&'static str> {
+        match self {
+            Self::Toml => None,
+            Self::Json => (!cfg!(feature = "json")).then_some("json"),
+            Self::Yaml => (!cfg!(feature = "yaml")).then_some("yaml"),
+        }
+    }
+}
+
+impl fmt::Display for RuleFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Toml => "TOML",
+            Self::Json => "JSON",
+            Self::Yaml => "YAML",
+        })
+    }
+}
+
+/// Parses a rule document in the given format, or in the format [`RuleFormat::detect`] reports
+/// when `format` is `None`.
+///
+/// # Arguments
+///
+/// * `content` - Rule document text.
+/// * `format` - Format of the document, typically taken from
+///   [`RuleFormat::from_extension`]; `None` detects it from the content.
+///
+/// # Returns
+///
+/// Vector of fully materialized [`Rule`] structs in the order they appear in the document.
+///
+/// # Errors
+///
+/// Returns [`RuleParseError`] when the document does not parse in its format, or
+/// [`RuleParseError::UnsupportedFormat`] when that format's feature is not enabled.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::rules::parse_rules_as;
+///
+/// let toml = r#"
+///     [[rule]]
+///     name = "H"
+///     priority = 1
+///     type = "H_"
+///     conditions = { element = "H" }
+/// "#;
+///
+/// let rules = parse_rules_as(toml, None).unwrap();
+/// assert_eq!(rules[0].result_type, "H_");
+/// ```
+pub fn parse_rules_as(
+    content: &str,
+    format: Option<RuleFormat>,
+) -> Result<Vec<Rule>, RuleParseError> {
+    let format = format.unwrap_or_else(|| RuleFormat::detect(content));
+    if let Some(feature) = format.feature() {
+        return Err(RuleParseError::UnsupportedFormat { format, feature });
+    }
+    match format {
+        RuleFormat::Toml => Ok(parse_rules(content)?),
+        #[cfg(feature = "json")]
+        RuleFormat::Json => Ok(parse_rules_json(content)?),
+        #[cfg(feature = "yaml")]
+        RuleFormat::Yaml => Ok(parse_rules_yaml(content)?),
+        #[allow(unreachable_patterns)]
+        _ => unreachable!("formats without their feature are rejected above"),
+    }
+}
+
 static DEFAULT_RULES: OnceLock<Vec<Rule>> = OnceLock::new();
 
 const DEFAULT_RULES_TOML: &str = include_str!("../../resources/default.rules.toml");
@@ -164,7 +543,7 @@ pub fn get_default_rules() -> &'static [Rule] {
 /// `FromStr`.
 fn deserialize_str_keyed_map<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
 where
-    K: FromStr + Eq + std::hash::Hash,
+    K: FromStr + Eq + core::hash::Hash,
     K::Err: fmt::Display,
     V: Deserialize<'de>,
     D: Deserializer<'de>,
@@ -232,6 +611,243 @@ mod tests {
         assert!(second.conditions.neighbor_elements.is_empty());
     }
 
+    #[test]
+    fn parse_rules_reads_nested_neighbor_conditions() {
+        let rules = parse_rules(
+            r#"
+            [[rule]]
+            name = "C_next_to_NH"
+            priority = 1
+            type = "C_X"
+            [rule.conditions]
+            element = "C"
+
+            [[rule.conditions.neighbors]]
+            conditions = { element = "N", hybridization = "SP2", neighbor_elements = { H = 1 } }
+
+            [[rule.conditions.neighbors]]
+            count = 2
+            [rule.conditions.neighbors.conditions]
+            element = "H"
+            "#,
+        )
+        .expect("nested neighbor conditions should parse");
+
+        let neighbors = &rules[0].conditions.neighbors;
+        assert_eq!(neighbors.len(), 2);
+        assert_eq!(neighbors[0].count, 1);
+        assert_eq!(neighbors[0].conditions.element, Some(Element::N));
+        assert_eq!(
+            neighbors[0].conditions.neighbor_elements.get(&Element::H),
+            Some(&1)
+        );
+        assert_eq!(neighbors[1].count, 2);
+        assert_eq!(rules[0].conditions.specificity(), 1 + (2 + 4) + 2 * (2 + 1));
+    }
+
+    #[test]
+    fn parse_rules_reads_bond_order_conditions() {
+        let rules = parse_rules(
+            r#"
+            [[rule]]
+            name = "Carbonyl"
+            priority = 1
+            type = "C_2"
+            [rule.conditions]
+            element = "C"
+            bonded_via = [{ order = "Double", element = "O" }, { order = "Resonant", count = 2 }]
+            "#,
+        )
+        .expect("bond order conditions should parse");
+
+        let bonded_via = &rules[0].conditions.bonded_via;
+        assert_eq!(
+            bonded_via,
+            &[
+                BondCondition {
+                    order: TopologyBondOrder::Double,
+                    element: Some(Element::O),
+                    count: 1,
+                },
+                BondCondition {
+                    order: TopologyBondOrder::Resonant,
+                    element: None,
+                    count: 2,
+                },
+            ]
+        );
+        assert_eq!(rules[0].conditions.specificity(), 1 + 3 + 2 * 2);
+
+        let invalid = r#"
+            [[rule]]
+            name = "Invalid"
+            priority = 1
+            type = "C_2"
+            conditions = { bonded_via = [{ order = "Aromatic" }] }
+        "#;
+        let err = parse_rules(invalid).expect_err("graph-only bond orders should be rejected");
+        assert!(err.to_string().contains("Aromatic"));
+    }
+
+    #[test]
+    fn parse_rules_reads_functional_group_conditions() {
+        let rules = parse_rules(
+            r#"
+            [[rule]]
+            name = "Acidic_H"
+            priority = 1
+            type = "H_A"
+            conditions = { element = "H", functional_group = "Carboxyl" }
+            "#,
+        )
+        .expect("functional group conditions should parse");
+        assert_eq!(
+            rules[0].conditions.functional_group,
+            Some(FunctionalGroup::Carboxyl)
+        );
+        assert_eq!(rules[0].conditions.specificity(), 2);
+
+        let invalid = r#"
+            [[rule]]
+            name = "Invalid"
+            priority = 1
+            type = "C_2"
+            conditions = { functional_group = "Ketone" }
+        "#;
+        let err = parse_rules(invalid).expect_err("unknown groups should be rejected");
+        assert!(err.to_string().contains("Ketone"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn parse_rules_json_matches_the_toml_schema() {
+        let json = r#"{
+            "rule": [
+                {
+                    "name": "C_sp2",
+                    "priority": 10,
+                    "type": "C_R",
+                    "conditions": {
+                        "element": "C",
+                        "degree": 3,
+                        "hybridization": "SP2",
+                        "is_aromatic": true,
+                        "neighbor_elements": { "N": 1 },
+                        "neighbor_types": { "N_R": 1 }
+                    }
+                },
+                {
+                    "name": "H_sp",
+                    "priority": 5,
+                    "type": "H_",
+                    "conditions": { "element": "H" }
+                }
+            ]
+        }"#;
+
+        let from_json = parse_rules_json(json).expect("sample JSON should parse");
+        assert_eq!(from_json, parse_rules(SAMPLE_RULES).unwrap());
+
+        let bare =
+            parse_rules_json(r#"[{ "name": "H", "priority": 1, "type": "H_", "conditions": {} }]"#)
+                .expect("bare arrays should parse");
+        assert_eq!(bare.len(), 1);
+
+        let err = parse_rules_json(
+            r#"[{ "name": "X", "priority": 1, "type": "X", "conditions": { "colour": "red" } }]"#,
+        );
+        assert!(err.is_err(), "unknown condition keys must be rejected");
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn parse_rules_yaml_matches_the_toml_schema() {
+        let yaml = r#"
+rule:
+  - name: C_sp2
+    priority: 10
+    type: C_R
+    conditions:
+      element: C
+      degree: 3
+      hybridization: SP2
+      is_aromatic: true
+      neighbor_elements: { N: 1 }
+      neighbor_types: { N_R: 1 }
+  - name: H_sp
+    priority: 5
+    type: H_
+    conditions: { element: H }
+"#;
+
+        let from_yaml = parse_rules_yaml(yaml).expect("sample YAML should parse");
+        assert_eq!(from_yaml, parse_rules(SAMPLE_RULES).unwrap());
+
+        let bare = parse_rules_yaml("- { name: H, priority: 1, type: H_, conditions: {} }")
+            .expect("bare sequences should parse");
+        assert_eq!(bare.len(), 1);
+
+        let err =
+            parse_rules_yaml("- { name: X, priority: 1, type: X, conditions: { colour: red } }");
+        assert!(err.is_err(), "unknown condition keys must be rejected");
+    }
+
+    #[test]
+    fn rule_format_is_detected_from_the_document() {
+        assert_eq!(RuleFormat::detect(SAMPLE_RULES), RuleFormat::Toml);
+        assert_eq!(RuleFormat::detect("# header\n\n[[rule]]"), RuleFormat::Toml);
+        assert_eq!(RuleFormat::detect("name = \"H\""), RuleFormat::Toml);
+        assert_eq!(RuleFormat::detect(""), RuleFormat::Toml);
+        assert_eq!(RuleFormat::detect("{ \"rule\": [] }"), RuleFormat::Json);
+        assert_eq!(RuleFormat::detect("[\n  {\n"), RuleFormat::Json);
+        assert_eq!(RuleFormat::detect("[]"), RuleFormat::Json);
+        assert_eq!(RuleFormat::detect("rule:\n  - name: H"), RuleFormat::Yaml);
+        assert_eq!(RuleFormat::detect("# yaml\n- name: H"), RuleFormat::Yaml);
+
+        assert_eq!(RuleFormat::from_extension("YML"), Some(RuleFormat::Yaml));
+        assert_eq!(RuleFormat::from_extension("toml"), Some(RuleFormat::Toml));
+        assert_eq!(RuleFormat::from_extension("txt"), None);
+    }
+
+    #[test]
+    fn parse_rules_as_dispatches_on_the_detected_format() {
+        let detected = parse_rules_as(SAMPLE_RULES, None).expect("TOML should parse");
+        assert_eq!(detected, parse_rules(SAMPLE_RULES).unwrap());
+
+        let json = r#"[{ "name": "H", "priority": 1, "type": "H_", "conditions": {} }]"#;
+        let result = parse_rules_as(json, None);
+        if cfg!(feature = "json") {
+            assert_eq!(result.expect("JSON should parse").len(), 1);
+        } else {
+            assert!(matches!(
+                result,
+                Err(RuleParseError::UnsupportedFormat {
+                    format: RuleFormat::Json,
+                    feature: "json"
+                })
+            ));
+        }
+
+        let yaml = "- { name: H, priority: 1, type: H_, conditions: {} }";
+        let result = parse_rules_as(yaml, None);
+        if cfg!(feature = "yaml") {
+            assert_eq!(result.expect("YAML should parse").len(), 1);
+        } else {
+            assert!(matches!(
+                result,
+                Err(RuleParseError::UnsupportedFormat {
+                    format: RuleFormat::Yaml,
+                    ..
+                })
+            ));
+        }
+
+        assert!(matches!(
+            parse_rules_as("name = ", Some(RuleFormat::Toml)),
+            Err(RuleParseError::Toml(_))
+        ));
+    }
+
     #[test]
     fn parse_rules_rejects_missing_required_fields() {
         let invalid = r#"
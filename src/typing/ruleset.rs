@@ -0,0 +1,708 @@
+//! Shared, immutable rule sets compiled once and reused across threads.
+//!
+//! A [`RuleSet`](crate::rules::RuleSet) owns its rules behind an [`Arc`](alloc::sync::Arc) together
+//! with their precomputed evaluation order, so cloning it is a reference-count bump and the
+//! priority sort is never repeated per molecule. Services can hold any number of rule sets side by
+//! side and hand clones to worker threads without relying on the global default deck.
+//!
+//! Compilation also buckets the rules by the element and hybridization they require, so the
+//! engine only evaluates the rules that can match a given atom instead of the whole deck.
+
+use super::rules::{Rule, RuleFormat, get_default_rules, parse_rules};
+use crate::compat::collections::{HashMap, HashSet};
+use crate::compat::prelude::*;
+use crate::compat::sync::OnceLock;
+use crate::core::error::RuleParseError;
+use crate::core::properties::{Element, Hybridization};
+use alloc::sync::Arc;
+use core::fmt;
+
+/// Lazily constructed rule set wrapping the embedded default rules.
+static DEFAULT_RULE_SET: OnceLock<RuleSet> = OnceLock::new();
+
+/// How the engine picks between several rules that match the same atom.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ConflictResolution {
+    /// The rule with the highest `priority` wins; ties go to the smaller name.
+    #[default]
+    Priority,
+    /// The most specific rule wins, as scored by
+    /// [`Conditions::specificity`](crate::rules::Conditions::specificity); priorities only break
+    /// ties between equally specific rules, and names break the rest.
+    ///
+    /// Custom rules then take over exactly the atoms they describe more narrowly, without
+    /// choosing a priority that fits between the existing ones.
+    Specificity,
+}
+
+impl ConflictResolution {
+    /// Returns the key by which `rule` outranks other rules under this strategy.
+    fn rank(self, rule: &Rule) -> (u32, i32) {
+        match self {
+            ConflictResolution::Priority => (0, rule.priority),
+            ConflictResolution::Specificity => (rule.conditions.specificity(), rule.priority),
+        }
+    }
+}
+
+/// Immutable, cheaply clonable collection of typing rules.
+///
+/// This is the compiled form of a rule deck: the evaluation order and the per-element rule
+/// buckets are computed once in [`RuleSet::new`] and reused for every molecule typed with it.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::rules::{RuleSet, parse_rules};
+/// use dreid_typer::Typer;
+///
+/// let custom = RuleSet::new(parse_rules(r#"
+///     [[rule]]
+///     name = "AnyCarbon"
+///     priority = 1
+///     type = "C_X"
+///     conditions = { element = "C" }
+/// "#).unwrap());
+///
+/// let worker_copy = custom.clone();
+/// std::thread::spawn(move || assert_eq!(worker_copy.len(), 1)).join().unwrap();
+///
+/// let typer = Typer::with_rule_set(custom);
+/// assert_eq!(typer.rules().len(), 1);
+/// ```
+#[derive(Clone)]
+#[doc(alias = "CompiledRuleset")]
+pub struct RuleSet {
+    inner: Arc<Compiled>,
+}
+
+/// Rules and their evaluation order, built once per [`RuleSet`].
+struct Compiled {
+    /// Rules in the order they were supplied.
+    rules: Vec<Rule>,
+    /// Indices into `rules` in evaluation order under `resolution`.
+    order: Vec<usize>,
+    /// Strategy that produced `order`.
+    resolution: ConflictResolution,
+    /// Subsets of `order` that can match atoms of a given element and hybridization.
+    buckets: RuleBuckets,
+}
+
+/// Number of [`Hybridization`] variants, one bucket per variant.
+const HYBRIDIZATION_SLOTS: usize = 6;
+
+/// Rule indices in evaluation order, one list per hybridization.
+type BucketRow = [Vec<usize>; HYBRIDIZATION_SLOTS];
+
+/// Rules pre-filtered by the element and hybridization conditions they require.
+///
+/// A rule without an element or hybridization condition appears in every bucket along that
+/// dimension, so each bucket holds exactly the rules whose element and hybridization conditions
+/// an atom of that kind satisfies.
+struct RuleBuckets {
+    /// Buckets for every element named by at least one rule.
+    by_element: HashMap<Element, BucketRow>,
+    /// Buckets for all other elements, holding only rules without an element condition.
+    other: BucketRow,
+}
+
+impl RuleBuckets {
+    /// Sorts the rules listed in `order` into buckets, preserving their relative order.
+    fn new(rules: &[Rule], order: &[usize]) -> Self {
+        let row = |element: Option<Element>| -> BucketRow {
+            core::array::from_fn(|slot| {
+                order
+                    .iter()
+                    .copied()
+                    .filter(|&index| {
+                        let conditions = &rules[index].conditions;
+                        conditions.element.is_none_or(|e| Some(e) == element)
+                            && conditions
+                                .hybridization
+                                .is_none_or(|h| hybridization_slot(h) == slot)
+                    })
+                    .collect()
+            })
+        };
+
+        let elements: HashSet<Element> = rules
+            .iter()
+            .filter_map(|rule| rule.conditions.element)
+            .collect();
+        let by_element = elements
+            .into_iter()
+            .map(|element| (element, row(Some(element))))
+            .collect();
+        Self {
+            by_element,
+            other: row(None),
+        }
+    }
+
+    /// Returns the rule indices that can match an atom of `element` with `hybridization`.
+    fn get(&self, element: Element, hybridization: Hybridization) -> &[usize] {
+        let row = self.by_element.get(&element).unwrap_or(&self.other);
+        &row[hybridization_slot(hybridization)]
+    }
+}
+
+/// Returns the bucket slot of a hybridization.
+fn hybridization_slot(hybridization: Hybridization) -> usize {
+    match hybridization {
+        Hybridization::SP => 0,
+        Hybridization::SP2 => 1,
+        Hybridization::SP3 => 2,
+        Hybridization::Resonant => 3,
+        Hybridization::None => 4,
+        Hybridization::Unknown => 5,
+    }
+}
+
+impl RuleSet {
+    /// Compiles a rule set from the supplied rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - Complete rule list; evaluation order is derived from the priorities.
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Self::with_resolution(rules, ConflictResolution::Priority)
+    }
+
+    /// Compiles a rule set that resolves competing matches with the given strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - Complete rule list.
+    /// * `resolution` - How the evaluation order is derived from the rules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::rules::{ConflictResolution, RuleSet, parse_rules};
+    /// use dreid_typer::{Element, GraphBondOrder, MolecularGraph, Typer};
+    ///
+    /// let rules = parse_rules(r#"
+    ///     [[rule]]
+    ///     name = "Oxygen"
+    ///     priority = 10
+    ///     type = "O_3"
+    ///     conditions = { element = "O" }
+    ///
+    ///     [[rule]]
+    ///     name = "WaterOxygen"
+    ///     priority = 0
+    ///     type = "O_W"
+    ///     conditions = { element = "O", neighbor_elements = { H = 2 } }
+    ///
+    ///     [[rule]]
+    ///     name = "Hydrogen"
+    ///     priority = 0
+    ///     type = "H_"
+    ///     conditions = { element = "H" }
+    /// "#).unwrap();
+    ///
+    /// let mut water = MolecularGraph::new();
+    /// let o = water.add_atom(Element::O);
+    /// let h1 = water.add_atom(Element::H);
+    /// let h2 = water.add_atom(Element::H);
+    /// water.add_bond(o, h1, GraphBondOrder::Single).unwrap();
+    /// water.add_bond(o, h2, GraphBondOrder::Single).unwrap();
+    ///
+    /// let by_priority = Typer::with_rule_set(RuleSet::new(rules.clone()));
+    /// assert_eq!(by_priority.assign_topology(&water).unwrap().atoms[0].atom_type, "O_3");
+    ///
+    /// let set = RuleSet::with_resolution(rules, ConflictResolution::Specificity);
+    /// let by_specificity = Typer::with_rule_set(set);
+    /// assert_eq!(by_specificity.assign_topology(&water).unwrap().atoms[0].atom_type, "O_W");
+    /// ```
+    pub fn with_resolution(rules: Vec<Rule>, resolution: ConflictResolution) -> Self {
+        let ranks: Vec<(u32, i32)> = rules.iter().map(|rule| resolution.rank(rule)).collect();
+        let mut order: Vec<usize> = (0..rules.len()).collect();
+        order.sort_by(|&a, &b| {
+            ranks[b]
+                .cmp(&ranks[a])
+                .then_with(|| rules[a].name.cmp(&rules[b].name))
+        });
+
+        let buckets = RuleBuckets::new(&rules, &order);
+        Self {
+            inner: Arc::new(Compiled {
+                rules,
+                order,
+                resolution,
+                buckets,
+            }),
+        }
+    }
+
+    /// Returns a handle to the embedded default DREIDING rules.
+    ///
+    /// The underlying set is compiled on first use and shared by every caller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the embedded default rules file is malformed, which indicates a critical
+    /// library bug.
+    pub fn default_dreiding() -> Self {
+        DEFAULT_RULE_SET
+            .get_or_init(|| Self::new(get_default_rules().to_vec()))
+            .clone()
+    }
+
+    /// Returns the rules in the order they were supplied.
+    pub fn rules(&self) -> &[Rule] {
+        &self.inner.rules
+    }
+
+    /// Returns the number of rules.
+    pub fn len(&self) -> usize {
+        self.inner.rules.len()
+    }
+
+    /// Returns the strategy that orders competing rules.
+    pub fn resolution(&self) -> ConflictResolution {
+        self.inner.resolution
+    }
+
+    /// Returns `true` if the set contains no rules.
+    pub fn is_empty(&self) -> bool {
+        self.inner.rules.is_empty()
+    }
+
+    /// Iterates over the rules in evaluation order.
+    ///
+    /// Under [`ConflictResolution::Priority`] this is highest priority first, ties by name;
+    /// under [`ConflictResolution::Specificity`] rules are first ranked by specificity.
+    pub fn by_priority(&self) -> impl Iterator<Item = &Rule> + '_ {
+        self.inner
+            .order
+            .iter()
+            .map(|&index| &self.inner.rules[index])
+    }
+
+    /// Iterates over the rules that can match an atom of `element` with `hybridization`, in
+    /// evaluation order.
+    ///
+    /// Rules requiring a different element or hybridization are skipped without being
+    /// evaluated; every other condition still has to be checked against the atom.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Element of the atom being typed.
+    /// * `hybridization` - Perceived hybridization of the atom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dreid_typer::rules::RuleSet;
+    /// use dreid_typer::{Element, Hybridization};
+    ///
+    /// let rules = RuleSet::default_dreiding();
+    /// let candidates: Vec<_> = rules.candidates(Element::O, Hybridization::SP3).collect();
+    /// assert!(candidates.len() < rules.len());
+    /// assert!(candidates.iter().any(|rule| rule.result_type == "O_3"));
+    /// assert!(candidates.iter().all(|rule| rule.result_type != "C_3"));
+    /// ```
+    pub fn candidates(
+        &self,
+        element: Element,
+        hybridization: Hybridization,
+    ) -> impl Iterator<Item = &Rule> + '_ {
+        self.inner
+            .buckets
+            .get(element, hybridization)
+            .iter()
+            .map(|&index| &self.inner.rules[index])
+    }
+
+    /// Returns the key by which a rule outranks another; larger keys are evaluated first.
+    ///
+    /// Rules with equal ranks are ordered by name alone.
+    pub(crate) fn rank(&self, rule: &Rule) -> (u32, i32) {
+        self.inner.resolution.rank(rule)
+    }
+
+    /// Returns `true` if both handles share the same compiled rules.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::default_dreiding()
+    }
+}
+
+impl From<Vec<Rule>> for RuleSet {
+    fn from(rules: Vec<Rule>) -> Self {
+        Self::new(rules)
+    }
+}
+
+impl PartialEq for RuleSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other)
+            || (self.resolution() == other.resolution() && self.rules() == other.rules())
+    }
+}
+
+impl fmt::Debug for RuleSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RuleSet")
+            .field("rules", &self.len())
+            .field("resolution", &self.resolution())
+            .finish()
+    }
+}
+
+/// Assembles a rule list by layering custom rules over a base deck.
+///
+/// Adding a rule whose name already exists replaces the earlier rule in place, so a custom
+/// deck can override individual default rules (including their priority) without copying the
+/// rest of the TOML. Rules with new names are appended.
+///
+/// # Examples
+///
+/// ```
+/// use dreid_typer::rules::{RulesetBuilder, get_default_rules};
+/// use dreid_typer::{Element, MolecularGraph, Typer};
+///
+/// let rules = RulesetBuilder::with_defaults()
+///     .with_toml(r#"
+///         [[rule]]
+///         name = "Xenon"
+///         priority = 10
+///         type = "Xe"
+///         conditions = { element = "Xe" }
+///     "#)
+///     .unwrap()
+///     .build();
+/// assert_eq!(rules.len(), get_default_rules().len() + 1);
+///
+/// let mut graph = MolecularGraph::new();
+/// graph.add_atom(Element::Xe);
+/// let topology = Typer::with_rule_set(rules).assign_topology(&graph).unwrap();
+/// assert_eq!(topology.atoms[0].atom_type, "Xe");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RulesetBuilder {
+    rules: Vec<Rule>,
+    resolution: ConflictResolution,
+}
+
+impl RulesetBuilder {
+    /// Creates a builder with no rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a builder seeded with the embedded default DREIDING rules.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the embedded default rules file is malformed, which indicates a critical
+    /// library bug.
+    pub fn with_defaults() -> Self {
+        Self {
+            rules: get_default_rules().to_vec(),
+            resolution: ConflictResolution::Priority,
+        }
+    }
+
+    /// Adds a rule, replacing any existing rule with the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - Rule to add; a replaced rule keeps its position but takes the new priority,
+    ///   type, and conditions.
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        match self.rules.iter_mut().find(|r| r.name == rule.name) {
+            Some(existing) => *existing = rule,
+            None => self.rules.push(rule),
+        }
+        self
+    }
+
+    /// Adds every rule in order, replacing existing rules by name.
+    pub fn with_rules(self, rules: impl IntoIterator<Item = Rule>) -> Self {
+        rules.into_iter().fold(self, Self::with_rule)
+    }
+
+    /// Parses TOML rules and adds them, replacing existing rules by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - Rules in the same `[[rule]]` format as the default rules file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`toml::de::Error`] if the content is not a valid rule file.
+    pub fn with_toml(self, content: &str) -> Result<Self, toml::de::Error> {
+        Ok(self.with_rules(parse_rules(content)?))
+    }
+
+    /// Parses JSON rules and adds them, replacing existing rules by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - Rules in the format accepted by
+    ///   [`parse_rules_json`](crate::rules::parse_rules_json).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`serde_json::Error`] if the content is not a valid rule document.
+    #[cfg(feature = "json")]
+    pub fn with_json(self, content: &str) -> Result<Self, serde_json::Error> {
+        Ok(self.with_rules(super::rules::parse_rules_json(content)?))
+    }
+
+    /// Parses YAML rules and adds them, replacing existing rules by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - Rules in the format accepted by
+    ///   [`parse_rules_yaml`](crate::rules::parse_rules_yaml).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`serde_yaml_ng::Error`] if the content is not a valid rule document.
+    #[cfg(feature = "yaml")]
+    pub fn with_yaml(self, content: &str) -> Result<Self, serde_yaml_ng::Error> {
+        Ok(self.with_rules(super::rules::parse_rules_yaml(content)?))
+    }
+
+    /// Parses rules in any supported format and adds them, replacing existing rules by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - Rule document text.
+    /// * `format` - Format of the document, or `None` to detect it from the content as
+    ///   [`parse_rules_as`](crate::rules::parse_rules_as) does.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RuleParseError`] if the content is not a valid rule document or its format is
+    /// not enabled in this build.
+    pub fn with_document(
+        self,
+        content: &str,
+        format: Option<RuleFormat>,
+    ) -> Result<Self, RuleParseError> {
+        Ok(self.with_rules(super::rules::parse_rules_as(content, format)?))
+    }
+
+    /// Removes the rule with the given name, if present.
+    pub fn without_rule(mut self, name: &str) -> Self {
+        self.rules.retain(|r| r.name != name);
+        self
+    }
+
+    /// Sets how the built rule set resolves competing matches.
+    pub fn with_resolution(mut self, resolution: ConflictResolution) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Returns the rules assembled so far, in insertion order.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Returns the assembled rule list.
+    pub fn into_rules(self) -> Vec<Rule> {
+        self.rules
+    }
+
+    /// Compiles the assembled rules into a [`RuleSet`].
+    pub fn build(self) -> RuleSet {
+        RuleSet::with_resolution(self.rules, self.resolution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn rule_set_is_shareable_across_threads() {
+        assert_send_sync::<RuleSet>();
+    }
+
+    #[test]
+    fn evaluation_order_sorts_by_priority_then_name() {
+        let rules = parse_rules(
+            r#"
+            [[rule]]
+            name = "B"
+            priority = 5
+            type = "X"
+            conditions = {}
+
+            [[rule]]
+            name = "Low"
+            priority = 1
+            type = "Y"
+            conditions = {}
+
+            [[rule]]
+            name = "A"
+            priority = 5
+            type = "Z"
+            conditions = {}
+            "#,
+        )
+        .unwrap();
+        let set = RuleSet::new(rules);
+
+        let names: Vec<&str> = set.by_priority().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["A", "B", "Low"]);
+        assert_eq!(set.rules()[0].name, "B");
+    }
+
+    #[test]
+    fn specificity_resolution_ranks_narrow_rules_first_and_breaks_ties_by_priority() {
+        let rules = parse_rules(
+            r#"
+            [[rule]]
+            name = "Broad"
+            priority = 100
+            type = "X"
+            conditions = { element = "C" }
+
+            [[rule]]
+            name = "Neighbor"
+            priority = 0
+            type = "Y"
+            conditions = { element = "C", neighbor_elements = { O = 1 } }
+
+            [[rule]]
+            name = "TwoProperties"
+            priority = 0
+            type = "Z"
+            conditions = { element = "C", degree = 3 }
+
+            [[rule]]
+            name = "TwoPropertiesHigh"
+            priority = 5
+            type = "W"
+            conditions = { element = "C", is_in_ring = true }
+            "#,
+        )
+        .unwrap();
+        let set = RulesetBuilder::new()
+            .with_rules(rules.clone())
+            .with_resolution(ConflictResolution::Specificity)
+            .build();
+
+        let names: Vec<&str> = set.by_priority().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Neighbor", "TwoPropertiesHigh", "TwoProperties", "Broad"]
+        );
+        assert_eq!(set.resolution(), ConflictResolution::Specificity);
+        assert_ne!(set, RuleSet::new(rules));
+    }
+
+    #[test]
+    fn candidates_keep_only_rules_an_atom_can_match_in_evaluation_order() {
+        let rules = parse_rules(
+            r#"
+            [[rule]]
+            name = "Anything"
+            priority = 1
+            type = "X"
+            conditions = {}
+
+            [[rule]]
+            name = "TetrahedralCarbon"
+            priority = 30
+            type = "C_3"
+            conditions = { element = "C", hybridization = "SP3" }
+
+            [[rule]]
+            name = "Carbon"
+            priority = 20
+            type = "C_"
+            conditions = { element = "C" }
+
+            [[rule]]
+            name = "Nitrogen"
+            priority = 40
+            type = "N_"
+            conditions = { element = "N" }
+
+            [[rule]]
+            name = "Trigonal"
+            priority = 10
+            type = "Y_2"
+            conditions = { hybridization = "SP2" }
+            "#,
+        )
+        .expect("rules should parse");
+        let set = RuleSet::new(rules);
+        let names = |element, hybridization| -> Vec<String> {
+            set.candidates(element, hybridization)
+                .map(|rule| rule.name.clone())
+                .collect()
+        };
+
+        assert_eq!(
+            names(Element::C, Hybridization::SP3),
+            ["TetrahedralCarbon", "Carbon", "Anything"]
+        );
+        assert_eq!(
+            names(Element::C, Hybridization::SP2),
+            ["Carbon", "Trigonal", "Anything"]
+        );
+        assert_eq!(
+            names(Element::N, Hybridization::SP3),
+            ["Nitrogen", "Anything"]
+        );
+        assert_eq!(
+            names(Element::Xe, Hybridization::SP2),
+            ["Trigonal", "Anything"]
+        );
+        assert_eq!(names(Element::Xe, Hybridization::None), ["Anything"]);
+    }
+
+    #[test]
+    fn default_set_is_compiled_once_and_shared() {
+        let first = RuleSet::default_dreiding();
+        let second = RuleSet::default();
+        assert!(first.ptr_eq(&second));
+        assert_eq!(first.rules(), get_default_rules());
+
+        let copy = RuleSet::new(get_default_rules().to_vec());
+        assert!(!copy.ptr_eq(&first));
+        assert_eq!(copy, first);
+    }
+
+    #[test]
+    fn builder_overrides_rules_by_name_and_appends_new_ones() {
+        let default_first = get_default_rules()[0].clone();
+        let mut replacement = default_first.clone();
+        replacement.priority = 999;
+        replacement.result_type = "X_custom".to_string();
+
+        let mut extra = default_first.clone();
+        extra.name = "ExtraRule".to_string();
+
+        let rules = RulesetBuilder::with_defaults()
+            .with_rule(replacement.clone())
+            .with_rule(extra)
+            .into_rules();
+        assert_eq!(rules.len(), get_default_rules().len() + 1);
+        assert_eq!(rules[0], replacement);
+        assert_eq!(rules.last().unwrap().name, "ExtraRule");
+
+        let set = RulesetBuilder::new()
+            .with_rules(rules)
+            .without_rule(&default_first.name)
+            .build();
+        assert_eq!(set.len(), get_default_rules().len());
+        assert!(set.rules().iter().all(|r| r.name != default_first.name));
+    }
+}
@@ -0,0 +1,148 @@
+//! Generic UFF atom types used as a fallback where DREIDING has no coverage.
+//!
+//! DREIDING only parameterizes main-group elements and a handful of ions. Mixed DREIDING/UFF
+//! setups (common for metal-organic frameworks) type the organic linkers with DREIDING and the
+//! metal nodes with UFF. This module supplies the UFF label for each element in its most common
+//! coordination and oxidation state, following the naming of Rappé et al. (1992).
+
+use crate::core::properties::Element;
+
+/// Returns the default UFF atom type for `element`, if UFF defines one.
+///
+/// # Arguments
+///
+/// * `element` - Element of the atom that DREIDING could not type.
+///
+/// # Returns
+///
+/// The UFF label (e.g., `"Zn3+2"`, `"La3+3"`), or `None` for elements outside UFF.
+pub fn generic_type(element: Element) -> Option<&'static str> {
+    use Element::*;
+    let label = match element {
+        H => "H_",
+        He => "He4+4",
+        Li => "Li",
+        Be => "Be3+2",
+        B => "B_3",
+        C => "C_3",
+        N => "N_3",
+        O => "O_3",
+        F => "F_",
+        Ne => "Ne4+4",
+        Na => "Na",
+        Mg => "Mg3+2",
+        Al => "Al3",
+        Si => "Si3",
+        P => "P_3+3",
+        S => "S_3+2",
+        Cl => "Cl",
+        Ar => "Ar4+4",
+        K => "K_",
+        Ca => "Ca6+2",
+        Sc => "Sc3+3",
+        Ti => "Ti6+4",
+        V => "V_3+5",
+        Cr => "Cr6+3",
+        Mn => "Mn6+2",
+        Fe => "Fe6+2",
+        Co => "Co6+3",
+        Ni => "Ni4+2",
+        Cu => "Cu3+1",
+        Zn => "Zn3+2",
+        Ga => "Ga3+3",
+        Ge => "Ge3",
+        As => "As3+3",
+        Se => "Se3+2",
+        Br => "Br",
+        Kr => "Kr4+4",
+        Rb => "Rb",
+        Sr => "Sr6+2",
+        Y => "Y_3+3",
+        Zr => "Zr3+4",
+        Nb => "Nb3+5",
+        Mo => "Mo6+6",
+        Tc => "Tc6+5",
+        Ru => "Ru6+2",
+        Rh => "Rh6+3",
+        Pd => "Pd4+2",
+        Ag => "Ag1+1",
+        Cd => "Cd3+2",
+        In => "In3+3",
+        Sn => "Sn3",
+        Sb => "Sb3+3",
+        Te => "Te3+2",
+        I => "I_",
+        Xe => "Xe4+4",
+        Cs => "Cs",
+        Ba => "Ba6+2",
+        La => "La3+3",
+        Ce => "Ce6+3",
+        Pr => "Pr6+3",
+        Nd => "Nd6+3",
+        Pm => "Pm6+3",
+        Sm => "Sm6+3",
+        Eu => "Eu6+3",
+        Gd => "Gd6+3",
+        Tb => "Tb6+3",
+        Dy => "Dy6+3",
+        Ho => "Ho6+3",
+        Er => "Er6+3",
+        Tm => "Tm6+3",
+        Yb => "Yb6+3",
+        Lu => "Lu6+3",
+        Hf => "Hf3+4",
+        Ta => "Ta3+5",
+        W => "W_6+6",
+        Re => "Re6+5",
+        Os => "Os6+6",
+        Ir => "Ir6+3",
+        Pt => "Pt4+2",
+        Au => "Au4+3",
+        Hg => "Hg1+2",
+        Tl => "Tl3+3",
+        Pb => "Pb3",
+        Bi => "Bi3+3",
+        Po => "Po3+2",
+        At => "At",
+        Rn => "Rn4+4",
+        Fr => "Fr",
+        Ra => "Ra6+2",
+        Ac => "Ac6+3",
+        Th => "Th6+4",
+        Pa => "Pa6+4",
+        U => "U_6+4",
+        Np => "Np6+4",
+        Pu => "Pu6+4",
+        Am => "Am6+4",
+        Cm => "Cm6+3",
+        Bk => "Bk6+3",
+        Cf => "Cf6+3",
+        Es => "Es6+3",
+        Fm => "Fm6+3",
+        Md => "Md6+3",
+        No => "No6+3",
+        Lr => "Lr6+3",
+        _ => return None,
+    };
+    Some(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_framework_metals_have_uff_types() {
+        assert_eq!(generic_type(Element::Zn), Some("Zn3+2"));
+        assert_eq!(generic_type(Element::Cu), Some("Cu3+1"));
+        assert_eq!(generic_type(Element::Zr), Some("Zr3+4"));
+        assert_eq!(generic_type(Element::La), Some("La3+3"));
+        assert_eq!(generic_type(Element::Eu), Some("Eu6+3"));
+    }
+
+    #[test]
+    fn superheavy_elements_are_not_covered() {
+        assert_eq!(generic_type(Element::Rf), None);
+        assert_eq!(generic_type(Element::Og), None);
+    }
+}
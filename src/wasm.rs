@@ -0,0 +1,124 @@
+//! JavaScript bindings for running the typer in a browser.
+//!
+//! Built with the `wasm` feature for `wasm32-unknown-unknown` through the `wasm/` package crate,
+//! for example with `wasm-pack build wasm --target web --out-name dreid_typer`. The bindings
+//! exchange JSON strings so that JavaScript callers need no generated classes: [`type_molecule`]
+//! takes a molecule and returns the document produced by [`to_json`].
+
+use crate::core::graph::MolecularGraph;
+use crate::core::properties::{Element, GraphBondOrder};
+use crate::export::json::to_json;
+use crate::pipeline::{AssignmentPolicy, Typer};
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+/// Molecule accepted by [`type_molecule`].
+#[derive(Debug, Deserialize)]
+struct MoleculeInput {
+    /// Element symbol of every atom, in atom ID order.
+    atoms: Vec<Element>,
+    /// Bonds between atoms.
+    #[serde(default)]
+    bonds: Vec<BondInput>,
+    /// Optional coordinates in Å, copied onto the output atoms.
+    #[serde(default)]
+    positions: Option<Vec<[f64; 3]>>,
+    /// Assign generic UFF types to atoms no DREIDING rule matches.
+    #[serde(default)]
+    uff_fallback: bool,
+    /// Assign element-based DREIDING types to atoms no rule or UFF fallback covers.
+    #[serde(default)]
+    fallback_types: bool,
+}
+
+/// One bond of a [`MoleculeInput`].
+#[derive(Debug, Deserialize)]
+struct BondInput {
+    /// IDs of the two bonded atoms.
+    atoms: [usize; 2],
+    /// `"Single"`, `"Double"`, `"Triple"`, or `"Aromatic"`; single when omitted.
+    #[serde(default)]
+    order: Option<String>,
+}
+
+/// Assigns DREIDING types to a molecule described in JSON.
+///
+/// The input has the shape
+/// `{"atoms": ["C", "O", ...], "bonds": [{"atoms": [0, 1], "order": "Double"}, ...]}` with
+/// optional `positions` (one `[x, y, z]` per atom), `uff_fallback`, and `fallback_types` fields.
+///
+/// # Arguments
+///
+/// * `json` - Molecule to type.
+///
+/// # Returns
+///
+/// The typed topology as a JSON string; see [`to_json`] for its layout.
+///
+/// # Errors
+///
+/// Throws a JavaScript `Error` whose message describes malformed input or the typing failure.
+#[wasm_bindgen(js_name = typeMolecule)]
+pub fn type_molecule(json: &str) -> Result<String, JsError> {
+    type_molecule_json(json).map_err(|message| JsError::new(&message))
+}
+
+/// Platform-independent body of [`type_molecule`], reporting failures as messages.
+fn type_molecule_json(json: &str) -> Result<String, String> {
+    let input: MoleculeInput =
+        serde_json::from_str(json).map_err(|error| format!("invalid molecule JSON: {error}"))?;
+
+    let mut graph = MolecularGraph::new();
+    for &element in &input.atoms {
+        graph.add_atom(element);
+    }
+    for bond in &input.bonds {
+        let order = match &bond.order {
+            Some(order) => order
+                .parse::<GraphBondOrder>()
+                .map_err(|error| error.to_string())?,
+            None => GraphBondOrder::Single,
+        };
+        graph
+            .add_bond(bond.atoms[0], bond.atoms[1], order)
+            .map_err(|error| error.to_string())?;
+    }
+
+    let policy = if input.fallback_types {
+        AssignmentPolicy::Fallback
+    } else {
+        AssignmentPolicy::Strict
+    };
+    let topology = Typer::new()
+        .with_uff_fallback(input.uff_fallback)
+        .with_assignment_policy(policy)
+        .assign_topology(&graph)
+        .map_err(|error| error.to_string())?;
+    let document =
+        to_json(&topology, input.positions.as_deref()).map_err(|error| error.to_string())?;
+    Ok(document.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn molecule_json_round_trips_through_the_typer() {
+        let output = type_molecule_json(
+            r#"{"atoms": ["C", "O", "H", "H"],
+                "bonds": [{"atoms": [0, 1], "order": "Double"},
+                          {"atoms": [0, 2]}, {"atoms": [0, 3]}]}"#,
+        )
+        .unwrap();
+        let document: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(document["atoms"][0]["type"], "C_2");
+        assert_eq!(document["atoms"][1]["type"], "O_2");
+
+        let error = type_molecule_json(r#"{"atoms": ["Xx"]}"#).unwrap_err();
+        assert!(error.starts_with("invalid molecule JSON"));
+        let error =
+            type_molecule_json(r#"{"atoms": ["C"], "bonds": [{"atoms": [0, 1]}]}"#).unwrap_err();
+        assert!(!error.is_empty());
+    }
+}
@@ -0,0 +1,380 @@
+//! Hot reloading of typing rules during rule development.
+//!
+//! [`RuleWatcher`] watches a rules TOML file and, every time the file is saved, recompiles the
+//! rule set, re-types a molecule with it, and hands the resulting [`RuleReload`] to a callback.
+//! The reload carries a [`TopologyDiff`] against the previous successful typing, so the effect of
+//! an edit on each atom type is visible without restarting or recompiling the host program.
+//!
+//! Requires the `watch` feature.
+
+use crate::core::diff::TopologyDiff;
+use crate::core::error::RuleWatchError;
+use crate::core::graph::MolecularGraph;
+use crate::core::topology::MolecularTopology;
+use crate::pipeline::Typer;
+use crate::typing::ruleset::{RuleSet, RulesetBuilder};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+/// Settings for a [`RuleWatcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchOptions {
+    /// Layer the file's rules over the embedded DREIDING rules, replacing rules by name, instead
+    /// of using the file as the complete rule deck.
+    pub layer_on_defaults: bool,
+    /// Quiet period after a file event before the file is reloaded, so that editors that save in
+    /// several steps trigger a single reload.
+    pub debounce: Duration,
+}
+
+impl WatchOptions {
+    /// Rules layered over the defaults, reloaded 100 ms after the last file event.
+    pub const DEFAULT: Self = Self {
+        layer_on_defaults: true,
+        debounce: Duration::from_millis(100),
+    };
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Outcome of re-typing the watched molecule after the rules file changed.
+#[derive(Debug, Clone)]
+pub struct RuleReload {
+    /// Number of rules in the recompiled rule set.
+    pub rule_count: usize,
+    /// Topology typed with the reloaded rules.
+    pub topology: MolecularTopology,
+    /// Changes relative to the previous successful typing.
+    pub diff: TopologyDiff,
+}
+
+/// Watches a rules file and re-types a molecule whenever the file changes.
+///
+/// Reloads run on a background thread that passes each result to the callback: a
+/// [`RuleReload`] when the file parsed and the molecule was typed, or a [`RuleWatchError`] when
+/// it did not, in which case the previous rules stay in effect. Saves that leave the file content
+/// unchanged are ignored. Watching stops when the watcher is dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dreid_typer::io::smiles::parse_smiles;
+/// use dreid_typer::watch::{RuleWatcher, WatchOptions};
+///
+/// let graph = parse_smiles("CC(=O)O").unwrap();
+/// let watcher = RuleWatcher::new("my_rules.toml", graph, &WatchOptions::DEFAULT, |reload| {
+///     match reload {
+///         Ok(reload) => print!("{} rules\n{}", reload.rule_count, reload.diff),
+///         Err(err) => eprintln!("{err}"),
+///     }
+/// })
+/// .unwrap();
+///
+/// // Edit and save my_rules.toml; every save prints the atom types it changed.
+/// std::thread::park();
+/// # drop(watcher);
+/// ```
+pub struct RuleWatcher {
+    session: Arc<Mutex<Session>>,
+    // Dropping the watcher closes the event channel, which ends the reload thread.
+    _watcher: RecommendedWatcher,
+}
+
+impl RuleWatcher {
+    /// Loads the rules file, types the molecule, and starts watching the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Rules file in the `[[rule]]` TOML format.
+    /// * `graph` - Molecule to re-type on every change.
+    /// * `options` - Rule layering and debounce settings.
+    /// * `on_reload` - Callback receiving the result of every reload.
+    ///
+    /// # Returns
+    ///
+    /// A running watcher; the initial typing is available from [`RuleWatcher::topology`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RuleWatchError`] if the file cannot be read or parsed, the molecule cannot be
+    /// typed with its rules, or the file system watcher cannot be started.
+    pub fn new<F>(
+        path: impl AsRef<Path>,
+        graph: MolecularGraph,
+        options: &WatchOptions,
+        mut on_reload: F,
+    ) -> Result<Self, RuleWatchError>
+    where
+        F: FnMut(Result<RuleReload, RuleWatchError>) + Send + 'static,
+    {
+        let path = path.as_ref();
+        let path = fs::canonicalize(path).map_err(|source| RuleWatchError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let file_name = path.file_name().map(OsString::from);
+        let directory = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        let session = Arc::new(Mutex::new(Session::open(
+            path,
+            graph,
+            options.layer_on_defaults,
+        )?));
+
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        // Watch the directory rather than the file so that editors replacing the file on save
+        // keep being followed.
+        watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+
+        let worker_session = Arc::clone(&session);
+        let debounce = options.debounce;
+        thread::Builder::new()
+            .name("dreid-rule-watcher".to_string())
+            .spawn(move || {
+                while let Ok(event) = events.recv() {
+                    match event {
+                        Err(err) => on_reload(Err(err.into())),
+                        Ok(event) if concerns(&event, file_name.as_deref()) => {
+                            settle(&events, debounce);
+                            let result = lock(&worker_session).reload();
+                            if let Some(result) = result {
+                                on_reload(result);
+                            }
+                        }
+                        Ok(_) => {}
+                    }
+                }
+            })
+            .map_err(|source| RuleWatchError::Io {
+                path: directory,
+                source,
+            })?;
+
+        Ok(Self {
+            session,
+            _watcher: watcher,
+        })
+    }
+
+    /// Returns the canonical path of the watched rules file.
+    pub fn path(&self) -> PathBuf {
+        lock(&self.session).path.clone()
+    }
+
+    /// Returns the topology from the most recent successful typing.
+    pub fn topology(&self) -> MolecularTopology {
+        lock(&self.session).topology.clone()
+    }
+}
+
+impl std::fmt::Debug for RuleWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RuleWatcher")
+            .field("path", &self.path())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Rules file, molecule, and last typing shared between the watcher and its reload thread.
+#[derive(Debug)]
+struct Session {
+    path: PathBuf,
+    graph: MolecularGraph,
+    layer_on_defaults: bool,
+    contents: String,
+    topology: MolecularTopology,
+}
+
+impl Session {
+    /// Loads the rules file and types the molecule with it.
+    fn open(
+        path: PathBuf,
+        graph: MolecularGraph,
+        layer_on_defaults: bool,
+    ) -> Result<Self, RuleWatchError> {
+        let contents = read(&path)?;
+        let rules = compile(&path, &contents, layer_on_defaults)?;
+        let topology = type_graph(&graph, rules)?;
+        Ok(Self {
+            path,
+            graph,
+            layer_on_defaults,
+            contents,
+            topology,
+        })
+    }
+
+    /// Re-reads the rules file and re-types the molecule if the file content changed.
+    ///
+    /// Returns `None` when the content is unchanged.
+    fn reload(&mut self) -> Option<Result<RuleReload, RuleWatchError>> {
+        let contents = match read(&self.path) {
+            Ok(contents) => contents,
+            Err(err) => return Some(Err(err)),
+        };
+        if contents == self.contents {
+            return None;
+        }
+
+        let result = compile(&self.path, &contents, self.layer_on_defaults).and_then(|rules| {
+            let rule_count = rules.len();
+            type_graph(&self.graph, rules).map(|topology| (rule_count, topology))
+        });
+        self.contents = contents;
+        Some(result.map(|(rule_count, topology)| {
+            let diff = self.topology.diff(&topology);
+            self.topology = topology.clone();
+            RuleReload {
+                rule_count,
+                topology,
+                diff,
+            }
+        }))
+    }
+}
+
+/// Locks the shared session; a panic in an earlier reload leaves the last typing usable.
+fn lock(session: &Mutex<Session>) -> std::sync::MutexGuard<'_, Session> {
+    session.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns `true` if the event changes the watched file.
+fn concerns(event: &Event, file_name: Option<&std::ffi::OsStr>) -> bool {
+    !matches!(event.kind, EventKind::Access(_))
+        && event.paths.iter().any(|path| path.file_name() == file_name)
+}
+
+/// Waits until no event has arrived for `debounce`.
+fn settle<T>(events: &Receiver<T>, debounce: Duration) {
+    while events.recv_timeout(debounce).is_ok() {}
+}
+
+/// Reads the rules file.
+fn read(path: &Path) -> Result<String, RuleWatchError> {
+    fs::read_to_string(path).map_err(|source| RuleWatchError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Compiles the rules file content, optionally over the embedded defaults.
+fn compile(
+    path: &Path,
+    contents: &str,
+    layer_on_defaults: bool,
+) -> Result<RuleSet, RuleWatchError> {
+    let builder = if layer_on_defaults {
+        RulesetBuilder::with_defaults()
+    } else {
+        RulesetBuilder::new()
+    };
+    builder
+        .with_toml(contents)
+        .map(RulesetBuilder::build)
+        .map_err(|source| RuleWatchError::RuleParse {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Types the molecule with a freshly compiled rule set.
+fn type_graph(graph: &MolecularGraph, rules: RuleSet) -> Result<MolecularTopology, RuleWatchError> {
+    Typer::with_rule_set(rules)
+        .assign_topology(graph)
+        .map_err(RuleWatchError::Typing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::smiles::parse_smiles;
+
+    const NO_RULES: &str = "rule = []\n";
+
+    const WATER_O: &str = r#"
+        [[rule]]
+        name = "Water_O"
+        priority = 1000
+        type = "O_W"
+        conditions = { element = "O" }
+    "#;
+
+    fn temp_rules(name: &str, contents: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("dreid-typer-watch-{}-{name}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("rules.toml");
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn reload_reports_type_changes_and_keeps_rules_after_errors() {
+        let path = temp_rules("session", NO_RULES);
+        let mut session = Session::open(path.clone(), parse_smiles("O").unwrap(), true).unwrap();
+        assert_eq!(session.topology.atoms[0].atom_type, "O_3");
+        assert!(session.reload().is_none());
+
+        fs::write(&path, WATER_O).unwrap();
+        let reload = session.reload().unwrap().unwrap();
+        assert_eq!(reload.diff.to_string(), "atom 0 (O): O_3 -> O_W\n");
+        assert_eq!(reload.topology.atoms[0].atom_type, "O_W");
+
+        fs::write(&path, "[[rule]\n").unwrap();
+        assert!(matches!(
+            session.reload(),
+            Some(Err(RuleWatchError::RuleParse { .. }))
+        ));
+        assert_eq!(session.topology.atoms[0].atom_type, "O_W");
+
+        fs::write(&path, NO_RULES).unwrap();
+        let reload = session.reload().unwrap().unwrap();
+        assert_eq!(reload.diff.to_string(), "atom 0 (O): O_W -> O_3\n");
+
+        let standalone = Session::open(path.clone(), parse_smiles("O").unwrap(), false);
+        assert!(matches!(standalone, Err(RuleWatchError::Typing(_))));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn watcher_retypes_on_save() {
+        let path = temp_rules("watcher", NO_RULES);
+        let (sender, reloads) = mpsc::channel();
+        let watcher = RuleWatcher::new(
+            &path,
+            parse_smiles("O").unwrap(),
+            &WatchOptions {
+                debounce: Duration::from_millis(20),
+                ..WatchOptions::DEFAULT
+            },
+            move |reload| {
+                let _ = sender.send(reload);
+            },
+        )
+        .unwrap();
+        assert_eq!(watcher.topology().atoms[0].atom_type, "O_3");
+
+        fs::write(&path, WATER_O).unwrap();
+        let reload = match reloads.recv_timeout(Duration::from_secs(10)) {
+            Ok(Ok(reload)) => reload,
+            Ok(Err(err)) => panic!("reload failed: {err}"),
+            Err(_) => panic!("no reload within 10 s"),
+        };
+        assert_eq!(reload.diff.atom_changes.len(), 1);
+        assert_eq!(watcher.topology().atoms[0].atom_type, "O_W");
+
+        drop(watcher);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
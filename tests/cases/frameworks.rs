@@ -0,0 +1,3467 @@
+use super::super::{AtomBlueprint, InputBondBlueprint, MoleculeTestCase, OutputBondBlueprint};
+use dreid_typer::{Element, GraphBondOrder, TopologyBondOrder};
+
+pub const MOF5_FRAGMENT: MoleculeTestCase = MoleculeTestCase {
+    name: "MOF-5 Zn4O(HCOO)6 Fragment",
+    atoms: &[
+        AtomBlueprint {
+            label: "Zn1",
+            element: Element::Zn,
+            expected_type: "Zn",
+        },
+        AtomBlueprint {
+            label: "Zn2",
+            element: Element::Zn,
+            expected_type: "Zn",
+        },
+        AtomBlueprint {
+            label: "Zn3",
+            element: Element::Zn,
+            expected_type: "Zn",
+        },
+        AtomBlueprint {
+            label: "Zn4",
+            element: Element::Zn,
+            expected_type: "Zn",
+        },
+        AtomBlueprint {
+            label: "O_mu4",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "C1",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O1a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O1b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C1",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C2",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O2a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O2b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C2",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C3",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O3a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O3b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C3",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C4",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O4a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O4b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C4",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C5",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O5a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O5b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C5",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C6",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O6a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O6b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C6",
+            element: Element::H,
+            expected_type: "H_",
+        },
+    ],
+    bonds: &[
+        InputBondBlueprint {
+            atom1_label: "O_mu4",
+            atom2_label: "Zn1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_mu4",
+            atom2_label: "Zn2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_mu4",
+            atom2_label: "Zn3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_mu4",
+            atom2_label: "Zn4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C1",
+            atom2_label: "O1a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C1",
+            atom2_label: "O1b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C1",
+            atom2_label: "H_C1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O1a",
+            atom2_label: "Zn1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O1b",
+            atom2_label: "Zn2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C2",
+            atom2_label: "O2a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C2",
+            atom2_label: "O2b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C2",
+            atom2_label: "H_C2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O2a",
+            atom2_label: "Zn1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O2b",
+            atom2_label: "Zn3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C3",
+            atom2_label: "O3a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C3",
+            atom2_label: "O3b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C3",
+            atom2_label: "H_C3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O3a",
+            atom2_label: "Zn1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O3b",
+            atom2_label: "Zn4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C4",
+            atom2_label: "O4a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C4",
+            atom2_label: "O4b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C4",
+            atom2_label: "H_C4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O4a",
+            atom2_label: "Zn2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O4b",
+            atom2_label: "Zn3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C5",
+            atom2_label: "O5a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C5",
+            atom2_label: "O5b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C5",
+            atom2_label: "H_C5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O5a",
+            atom2_label: "Zn2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O5b",
+            atom2_label: "Zn4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C6",
+            atom2_label: "O6a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C6",
+            atom2_label: "O6b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C6",
+            atom2_label: "H_C6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O6a",
+            atom2_label: "Zn3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O6b",
+            atom2_label: "Zn4",
+            order: GraphBondOrder::Single,
+        },
+    ],
+    expected_bonds: &[
+        OutputBondBlueprint {
+            atom1_label: "O_mu4",
+            atom2_label: "Zn1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_mu4",
+            atom2_label: "Zn2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_mu4",
+            atom2_label: "Zn3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_mu4",
+            atom2_label: "Zn4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C1",
+            atom2_label: "O1a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C1",
+            atom2_label: "O1b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C1",
+            atom2_label: "H_C1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O1a",
+            atom2_label: "Zn1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O1b",
+            atom2_label: "Zn2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C2",
+            atom2_label: "O2a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C2",
+            atom2_label: "O2b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C2",
+            atom2_label: "H_C2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O2a",
+            atom2_label: "Zn1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O2b",
+            atom2_label: "Zn3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C3",
+            atom2_label: "O3a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C3",
+            atom2_label: "O3b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C3",
+            atom2_label: "H_C3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O3a",
+            atom2_label: "Zn1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O3b",
+            atom2_label: "Zn4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C4",
+            atom2_label: "O4a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C4",
+            atom2_label: "O4b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C4",
+            atom2_label: "H_C4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O4a",
+            atom2_label: "Zn2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O4b",
+            atom2_label: "Zn3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C5",
+            atom2_label: "O5a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C5",
+            atom2_label: "O5b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C5",
+            atom2_label: "H_C5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O5a",
+            atom2_label: "Zn2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O5b",
+            atom2_label: "Zn4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C6",
+            atom2_label: "O6a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C6",
+            atom2_label: "O6b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C6",
+            atom2_label: "H_C6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O6a",
+            atom2_label: "Zn3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O6b",
+            atom2_label: "Zn4",
+            order: TopologyBondOrder::Single,
+        },
+    ],
+};
+
+pub const UIO66_FRAGMENT: MoleculeTestCase = MoleculeTestCase {
+    name: "UiO-66 Zr6O4(OH)4(HCOO)12 Fragment",
+    atoms: &[
+        AtomBlueprint {
+            label: "Zr1",
+            element: Element::Zr,
+            expected_type: "Zr3+4",
+        },
+        AtomBlueprint {
+            label: "Zr2",
+            element: Element::Zr,
+            expected_type: "Zr3+4",
+        },
+        AtomBlueprint {
+            label: "Zr3",
+            element: Element::Zr,
+            expected_type: "Zr3+4",
+        },
+        AtomBlueprint {
+            label: "Zr4",
+            element: Element::Zr,
+            expected_type: "Zr3+4",
+        },
+        AtomBlueprint {
+            label: "Zr5",
+            element: Element::Zr,
+            expected_type: "Zr3+4",
+        },
+        AtomBlueprint {
+            label: "Zr6",
+            element: Element::Zr,
+            expected_type: "Zr3+4",
+        },
+        AtomBlueprint {
+            label: "O_oxo1",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O_hydroxo2",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H_hydroxo2",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_hydroxo3",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H_hydroxo3",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_oxo4",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O_hydroxo5",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H_hydroxo5",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_oxo6",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O_oxo7",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O_hydroxo8",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H_hydroxo8",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "C1",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O1a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O1b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C1",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C2",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O2a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O2b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C2",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C3",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O3a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O3b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C3",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C4",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O4a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O4b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C4",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C5",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O5a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O5b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C5",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C6",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O6a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O6b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C6",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C7",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O7a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O7b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C7",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C8",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O8a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O8b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C8",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C9",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O9a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O9b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C9",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C10",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O10a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O10b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C10",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C11",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O11a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O11b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C11",
+            element: Element::H,
+            expected_type: "H_",
+        },
+        AtomBlueprint {
+            label: "C12",
+            element: Element::C,
+            expected_type: "C_R",
+        },
+        AtomBlueprint {
+            label: "O12a",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "O12b",
+            element: Element::O,
+            expected_type: "O_2",
+        },
+        AtomBlueprint {
+            label: "H_C12",
+            element: Element::H,
+            expected_type: "H_",
+        },
+    ],
+    bonds: &[
+        InputBondBlueprint {
+            atom1_label: "O_oxo1",
+            atom2_label: "Zr1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_oxo1",
+            atom2_label: "Zr3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_oxo1",
+            atom2_label: "Zr5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo2",
+            atom2_label: "H_hydroxo2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo2",
+            atom2_label: "Zr1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo2",
+            atom2_label: "Zr3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo2",
+            atom2_label: "Zr6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo3",
+            atom2_label: "H_hydroxo3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo3",
+            atom2_label: "Zr1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo3",
+            atom2_label: "Zr4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo3",
+            atom2_label: "Zr5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_oxo4",
+            atom2_label: "Zr1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_oxo4",
+            atom2_label: "Zr4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_oxo4",
+            atom2_label: "Zr6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo5",
+            atom2_label: "H_hydroxo5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo5",
+            atom2_label: "Zr2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo5",
+            atom2_label: "Zr3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo5",
+            atom2_label: "Zr5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_oxo6",
+            atom2_label: "Zr2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_oxo6",
+            atom2_label: "Zr3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_oxo6",
+            atom2_label: "Zr6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_oxo7",
+            atom2_label: "Zr2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_oxo7",
+            atom2_label: "Zr4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_oxo7",
+            atom2_label: "Zr5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo8",
+            atom2_label: "H_hydroxo8",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo8",
+            atom2_label: "Zr2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo8",
+            atom2_label: "Zr4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_hydroxo8",
+            atom2_label: "Zr6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C1",
+            atom2_label: "O1a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C1",
+            atom2_label: "O1b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C1",
+            atom2_label: "H_C1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O1a",
+            atom2_label: "Zr1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O1b",
+            atom2_label: "Zr3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C2",
+            atom2_label: "O2a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C2",
+            atom2_label: "O2b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C2",
+            atom2_label: "H_C2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O2a",
+            atom2_label: "Zr1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O2b",
+            atom2_label: "Zr4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C3",
+            atom2_label: "O3a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C3",
+            atom2_label: "O3b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C3",
+            atom2_label: "H_C3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O3a",
+            atom2_label: "Zr1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O3b",
+            atom2_label: "Zr5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C4",
+            atom2_label: "O4a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C4",
+            atom2_label: "O4b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C4",
+            atom2_label: "H_C4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O4a",
+            atom2_label: "Zr1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O4b",
+            atom2_label: "Zr6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C5",
+            atom2_label: "O5a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C5",
+            atom2_label: "O5b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C5",
+            atom2_label: "H_C5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O5a",
+            atom2_label: "Zr2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O5b",
+            atom2_label: "Zr3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C6",
+            atom2_label: "O6a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C6",
+            atom2_label: "O6b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C6",
+            atom2_label: "H_C6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O6a",
+            atom2_label: "Zr2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O6b",
+            atom2_label: "Zr4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C7",
+            atom2_label: "O7a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C7",
+            atom2_label: "O7b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C7",
+            atom2_label: "H_C7",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O7a",
+            atom2_label: "Zr2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O7b",
+            atom2_label: "Zr5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C8",
+            atom2_label: "O8a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C8",
+            atom2_label: "O8b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C8",
+            atom2_label: "H_C8",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O8a",
+            atom2_label: "Zr2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O8b",
+            atom2_label: "Zr6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C9",
+            atom2_label: "O9a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C9",
+            atom2_label: "O9b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C9",
+            atom2_label: "H_C9",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O9a",
+            atom2_label: "Zr3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O9b",
+            atom2_label: "Zr5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C10",
+            atom2_label: "O10a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C10",
+            atom2_label: "O10b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C10",
+            atom2_label: "H_C10",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O10a",
+            atom2_label: "Zr3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O10b",
+            atom2_label: "Zr6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C11",
+            atom2_label: "O11a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C11",
+            atom2_label: "O11b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C11",
+            atom2_label: "H_C11",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O11a",
+            atom2_label: "Zr4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O11b",
+            atom2_label: "Zr5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C12",
+            atom2_label: "O12a",
+            order: GraphBondOrder::Double,
+        },
+        InputBondBlueprint {
+            atom1_label: "C12",
+            atom2_label: "O12b",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "C12",
+            atom2_label: "H_C12",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O12a",
+            atom2_label: "Zr4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O12b",
+            atom2_label: "Zr6",
+            order: GraphBondOrder::Single,
+        },
+    ],
+    expected_bonds: &[
+        OutputBondBlueprint {
+            atom1_label: "O_oxo1",
+            atom2_label: "Zr1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_oxo1",
+            atom2_label: "Zr3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_oxo1",
+            atom2_label: "Zr5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo2",
+            atom2_label: "H_hydroxo2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo2",
+            atom2_label: "Zr1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo2",
+            atom2_label: "Zr3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo2",
+            atom2_label: "Zr6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo3",
+            atom2_label: "H_hydroxo3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo3",
+            atom2_label: "Zr1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo3",
+            atom2_label: "Zr4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo3",
+            atom2_label: "Zr5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_oxo4",
+            atom2_label: "Zr1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_oxo4",
+            atom2_label: "Zr4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_oxo4",
+            atom2_label: "Zr6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo5",
+            atom2_label: "H_hydroxo5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo5",
+            atom2_label: "Zr2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo5",
+            atom2_label: "Zr3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo5",
+            atom2_label: "Zr5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_oxo6",
+            atom2_label: "Zr2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_oxo6",
+            atom2_label: "Zr3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_oxo6",
+            atom2_label: "Zr6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_oxo7",
+            atom2_label: "Zr2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_oxo7",
+            atom2_label: "Zr4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_oxo7",
+            atom2_label: "Zr5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo8",
+            atom2_label: "H_hydroxo8",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo8",
+            atom2_label: "Zr2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo8",
+            atom2_label: "Zr4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_hydroxo8",
+            atom2_label: "Zr6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C1",
+            atom2_label: "O1a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C1",
+            atom2_label: "O1b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C1",
+            atom2_label: "H_C1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O1a",
+            atom2_label: "Zr1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O1b",
+            atom2_label: "Zr3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C2",
+            atom2_label: "O2a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C2",
+            atom2_label: "O2b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C2",
+            atom2_label: "H_C2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O2a",
+            atom2_label: "Zr1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O2b",
+            atom2_label: "Zr4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C3",
+            atom2_label: "O3a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C3",
+            atom2_label: "O3b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C3",
+            atom2_label: "H_C3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O3a",
+            atom2_label: "Zr1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O3b",
+            atom2_label: "Zr5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C4",
+            atom2_label: "O4a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C4",
+            atom2_label: "O4b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C4",
+            atom2_label: "H_C4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O4a",
+            atom2_label: "Zr1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O4b",
+            atom2_label: "Zr6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C5",
+            atom2_label: "O5a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C5",
+            atom2_label: "O5b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C5",
+            atom2_label: "H_C5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O5a",
+            atom2_label: "Zr2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O5b",
+            atom2_label: "Zr3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C6",
+            atom2_label: "O6a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C6",
+            atom2_label: "O6b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C6",
+            atom2_label: "H_C6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O6a",
+            atom2_label: "Zr2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O6b",
+            atom2_label: "Zr4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C7",
+            atom2_label: "O7a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C7",
+            atom2_label: "O7b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C7",
+            atom2_label: "H_C7",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O7a",
+            atom2_label: "Zr2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O7b",
+            atom2_label: "Zr5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C8",
+            atom2_label: "O8a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C8",
+            atom2_label: "O8b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C8",
+            atom2_label: "H_C8",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O8a",
+            atom2_label: "Zr2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O8b",
+            atom2_label: "Zr6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C9",
+            atom2_label: "O9a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C9",
+            atom2_label: "O9b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C9",
+            atom2_label: "H_C9",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O9a",
+            atom2_label: "Zr3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O9b",
+            atom2_label: "Zr5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C10",
+            atom2_label: "O10a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C10",
+            atom2_label: "O10b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C10",
+            atom2_label: "H_C10",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O10a",
+            atom2_label: "Zr3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O10b",
+            atom2_label: "Zr6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C11",
+            atom2_label: "O11a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C11",
+            atom2_label: "O11b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C11",
+            atom2_label: "H_C11",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O11a",
+            atom2_label: "Zr4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O11b",
+            atom2_label: "Zr5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C12",
+            atom2_label: "O12a",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C12",
+            atom2_label: "O12b",
+            order: TopologyBondOrder::Resonant,
+        },
+        OutputBondBlueprint {
+            atom1_label: "C12",
+            atom2_label: "H_C12",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O12a",
+            atom2_label: "Zr4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O12b",
+            atom2_label: "Zr6",
+            order: TopologyBondOrder::Single,
+        },
+    ],
+};
+
+pub const SODALITE_CAGE: MoleculeTestCase = MoleculeTestCase {
+    name: "Sodalite Cage Si24O36(OH)24",
+    atoms: &[
+        AtomBlueprint {
+            label: "Si1",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si2",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si3",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si4",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si5",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si6",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si7",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si8",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si9",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si10",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si11",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si12",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si13",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si14",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si15",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si16",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si17",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si18",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si19",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si20",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si21",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si22",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si23",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "Si24",
+            element: Element::Si,
+            expected_type: "Si3",
+        },
+        AtomBlueprint {
+            label: "O1",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O2",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O3",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O4",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O5",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O6",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O7",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O8",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O9",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O10",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O11",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O12",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O13",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O14",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O15",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O16",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O17",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O18",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O19",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O20",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O21",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O22",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O23",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O24",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O25",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O26",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O27",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O28",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O29",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O30",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O31",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O32",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O33",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O34",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O35",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O36",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "O_H1",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H1",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H2",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H2",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H3",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H3",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H4",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H4",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H5",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H5",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H6",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H6",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H7",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H7",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H8",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H8",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H9",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H9",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H10",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H10",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H11",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H11",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H12",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H12",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H13",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H13",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H14",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H14",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H15",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H15",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H16",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H16",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H17",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H17",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H18",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H18",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H19",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H19",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H20",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H20",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H21",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H21",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H22",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H22",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H23",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H23",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+        AtomBlueprint {
+            label: "O_H24",
+            element: Element::O,
+            expected_type: "O_3",
+        },
+        AtomBlueprint {
+            label: "H24",
+            element: Element::H,
+            expected_type: "H_HB",
+        },
+    ],
+    bonds: &[
+        InputBondBlueprint {
+            atom1_label: "Si1",
+            atom2_label: "O1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O1",
+            atom2_label: "Si2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si1",
+            atom2_label: "O2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O2",
+            atom2_label: "Si3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si1",
+            atom2_label: "O3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O3",
+            atom2_label: "Si5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si2",
+            atom2_label: "O4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O4",
+            atom2_label: "Si4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si2",
+            atom2_label: "O5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O5",
+            atom2_label: "Si6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si3",
+            atom2_label: "O6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O6",
+            atom2_label: "Si4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si3",
+            atom2_label: "O7",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O7",
+            atom2_label: "Si7",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si4",
+            atom2_label: "O8",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O8",
+            atom2_label: "Si8",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si5",
+            atom2_label: "O9",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O9",
+            atom2_label: "Si9",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si5",
+            atom2_label: "O10",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O10",
+            atom2_label: "Si10",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si6",
+            atom2_label: "O11",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O11",
+            atom2_label: "Si11",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si6",
+            atom2_label: "O12",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O12",
+            atom2_label: "Si13",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si7",
+            atom2_label: "O13",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O13",
+            atom2_label: "Si12",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si7",
+            atom2_label: "O14",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O14",
+            atom2_label: "Si14",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si8",
+            atom2_label: "O15",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O15",
+            atom2_label: "Si15",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si8",
+            atom2_label: "O16",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O16",
+            atom2_label: "Si16",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si9",
+            atom2_label: "O17",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O17",
+            atom2_label: "Si11",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si9",
+            atom2_label: "O18",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O18",
+            atom2_label: "Si17",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si10",
+            atom2_label: "O19",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O19",
+            atom2_label: "Si12",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si10",
+            atom2_label: "O20",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O20",
+            atom2_label: "Si17",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si11",
+            atom2_label: "O21",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O21",
+            atom2_label: "Si18",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si12",
+            atom2_label: "O22",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O22",
+            atom2_label: "Si19",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si13",
+            atom2_label: "O23",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O23",
+            atom2_label: "Si15",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si13",
+            atom2_label: "O24",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O24",
+            atom2_label: "Si18",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si14",
+            atom2_label: "O25",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O25",
+            atom2_label: "Si16",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si14",
+            atom2_label: "O26",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O26",
+            atom2_label: "Si19",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si15",
+            atom2_label: "O27",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O27",
+            atom2_label: "Si20",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si16",
+            atom2_label: "O28",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O28",
+            atom2_label: "Si20",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si17",
+            atom2_label: "O29",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O29",
+            atom2_label: "Si21",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si18",
+            atom2_label: "O30",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O30",
+            atom2_label: "Si22",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si19",
+            atom2_label: "O31",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O31",
+            atom2_label: "Si23",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si20",
+            atom2_label: "O32",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O32",
+            atom2_label: "Si24",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si21",
+            atom2_label: "O33",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O33",
+            atom2_label: "Si22",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si21",
+            atom2_label: "O34",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O34",
+            atom2_label: "Si23",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si22",
+            atom2_label: "O35",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O35",
+            atom2_label: "Si24",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si23",
+            atom2_label: "O36",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O36",
+            atom2_label: "Si24",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si1",
+            atom2_label: "O_H1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H1",
+            atom2_label: "H1",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si2",
+            atom2_label: "O_H2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H2",
+            atom2_label: "H2",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si3",
+            atom2_label: "O_H3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H3",
+            atom2_label: "H3",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si4",
+            atom2_label: "O_H4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H4",
+            atom2_label: "H4",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si5",
+            atom2_label: "O_H5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H5",
+            atom2_label: "H5",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si6",
+            atom2_label: "O_H6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H6",
+            atom2_label: "H6",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si7",
+            atom2_label: "O_H7",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H7",
+            atom2_label: "H7",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si8",
+            atom2_label: "O_H8",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H8",
+            atom2_label: "H8",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si9",
+            atom2_label: "O_H9",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H9",
+            atom2_label: "H9",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si10",
+            atom2_label: "O_H10",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H10",
+            atom2_label: "H10",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si11",
+            atom2_label: "O_H11",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H11",
+            atom2_label: "H11",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si12",
+            atom2_label: "O_H12",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H12",
+            atom2_label: "H12",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si13",
+            atom2_label: "O_H13",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H13",
+            atom2_label: "H13",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si14",
+            atom2_label: "O_H14",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H14",
+            atom2_label: "H14",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si15",
+            atom2_label: "O_H15",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H15",
+            atom2_label: "H15",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si16",
+            atom2_label: "O_H16",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H16",
+            atom2_label: "H16",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si17",
+            atom2_label: "O_H17",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H17",
+            atom2_label: "H17",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si18",
+            atom2_label: "O_H18",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H18",
+            atom2_label: "H18",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si19",
+            atom2_label: "O_H19",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H19",
+            atom2_label: "H19",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si20",
+            atom2_label: "O_H20",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H20",
+            atom2_label: "H20",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si21",
+            atom2_label: "O_H21",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H21",
+            atom2_label: "H21",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si22",
+            atom2_label: "O_H22",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H22",
+            atom2_label: "H22",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si23",
+            atom2_label: "O_H23",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H23",
+            atom2_label: "H23",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "Si24",
+            atom2_label: "O_H24",
+            order: GraphBondOrder::Single,
+        },
+        InputBondBlueprint {
+            atom1_label: "O_H24",
+            atom2_label: "H24",
+            order: GraphBondOrder::Single,
+        },
+    ],
+    expected_bonds: &[
+        OutputBondBlueprint {
+            atom1_label: "Si1",
+            atom2_label: "O1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O1",
+            atom2_label: "Si2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si1",
+            atom2_label: "O2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O2",
+            atom2_label: "Si3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si1",
+            atom2_label: "O3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O3",
+            atom2_label: "Si5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si2",
+            atom2_label: "O4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O4",
+            atom2_label: "Si4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si2",
+            atom2_label: "O5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O5",
+            atom2_label: "Si6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si3",
+            atom2_label: "O6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O6",
+            atom2_label: "Si4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si3",
+            atom2_label: "O7",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O7",
+            atom2_label: "Si7",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si4",
+            atom2_label: "O8",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O8",
+            atom2_label: "Si8",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si5",
+            atom2_label: "O9",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O9",
+            atom2_label: "Si9",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si5",
+            atom2_label: "O10",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O10",
+            atom2_label: "Si10",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si6",
+            atom2_label: "O11",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O11",
+            atom2_label: "Si11",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si6",
+            atom2_label: "O12",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O12",
+            atom2_label: "Si13",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si7",
+            atom2_label: "O13",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O13",
+            atom2_label: "Si12",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si7",
+            atom2_label: "O14",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O14",
+            atom2_label: "Si14",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si8",
+            atom2_label: "O15",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O15",
+            atom2_label: "Si15",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si8",
+            atom2_label: "O16",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O16",
+            atom2_label: "Si16",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si9",
+            atom2_label: "O17",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O17",
+            atom2_label: "Si11",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si9",
+            atom2_label: "O18",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O18",
+            atom2_label: "Si17",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si10",
+            atom2_label: "O19",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O19",
+            atom2_label: "Si12",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si10",
+            atom2_label: "O20",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O20",
+            atom2_label: "Si17",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si11",
+            atom2_label: "O21",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O21",
+            atom2_label: "Si18",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si12",
+            atom2_label: "O22",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O22",
+            atom2_label: "Si19",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si13",
+            atom2_label: "O23",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O23",
+            atom2_label: "Si15",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si13",
+            atom2_label: "O24",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O24",
+            atom2_label: "Si18",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si14",
+            atom2_label: "O25",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O25",
+            atom2_label: "Si16",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si14",
+            atom2_label: "O26",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O26",
+            atom2_label: "Si19",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si15",
+            atom2_label: "O27",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O27",
+            atom2_label: "Si20",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si16",
+            atom2_label: "O28",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O28",
+            atom2_label: "Si20",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si17",
+            atom2_label: "O29",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O29",
+            atom2_label: "Si21",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si18",
+            atom2_label: "O30",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O30",
+            atom2_label: "Si22",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si19",
+            atom2_label: "O31",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O31",
+            atom2_label: "Si23",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si20",
+            atom2_label: "O32",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O32",
+            atom2_label: "Si24",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si21",
+            atom2_label: "O33",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O33",
+            atom2_label: "Si22",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si21",
+            atom2_label: "O34",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O34",
+            atom2_label: "Si23",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si22",
+            atom2_label: "O35",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O35",
+            atom2_label: "Si24",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si23",
+            atom2_label: "O36",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O36",
+            atom2_label: "Si24",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si1",
+            atom2_label: "O_H1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H1",
+            atom2_label: "H1",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si2",
+            atom2_label: "O_H2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H2",
+            atom2_label: "H2",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si3",
+            atom2_label: "O_H3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H3",
+            atom2_label: "H3",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si4",
+            atom2_label: "O_H4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H4",
+            atom2_label: "H4",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si5",
+            atom2_label: "O_H5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H5",
+            atom2_label: "H5",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si6",
+            atom2_label: "O_H6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H6",
+            atom2_label: "H6",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si7",
+            atom2_label: "O_H7",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H7",
+            atom2_label: "H7",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si8",
+            atom2_label: "O_H8",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H8",
+            atom2_label: "H8",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si9",
+            atom2_label: "O_H9",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H9",
+            atom2_label: "H9",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si10",
+            atom2_label: "O_H10",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H10",
+            atom2_label: "H10",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si11",
+            atom2_label: "O_H11",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H11",
+            atom2_label: "H11",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si12",
+            atom2_label: "O_H12",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H12",
+            atom2_label: "H12",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si13",
+            atom2_label: "O_H13",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H13",
+            atom2_label: "H13",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si14",
+            atom2_label: "O_H14",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H14",
+            atom2_label: "H14",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si15",
+            atom2_label: "O_H15",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H15",
+            atom2_label: "H15",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si16",
+            atom2_label: "O_H16",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H16",
+            atom2_label: "H16",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si17",
+            atom2_label: "O_H17",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H17",
+            atom2_label: "H17",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si18",
+            atom2_label: "O_H18",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H18",
+            atom2_label: "H18",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si19",
+            atom2_label: "O_H19",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H19",
+            atom2_label: "H19",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si20",
+            atom2_label: "O_H20",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H20",
+            atom2_label: "H20",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si21",
+            atom2_label: "O_H21",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H21",
+            atom2_label: "H21",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si22",
+            atom2_label: "O_H22",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H22",
+            atom2_label: "H22",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si23",
+            atom2_label: "O_H23",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H23",
+            atom2_label: "H23",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "Si24",
+            atom2_label: "O_H24",
+            order: TopologyBondOrder::Single,
+        },
+        OutputBondBlueprint {
+            atom1_label: "O_H24",
+            atom2_label: "H24",
+            order: TopologyBondOrder::Single,
+        },
+    ],
+};
@@ -1,3 +1,6 @@
 pub mod amino_acids;
 pub mod dreiding_paper;
+pub mod frameworks;
+pub mod main_group;
 pub mod nucleic_acids;
+pub mod strained_rings;
@@ -0,0 +1,159 @@
+//! All-carbon sp2 structures: graphene flakes, nanotubes, and buckminsterfullerene.
+//!
+//! Every structure is given with an explicit Kekulé structure, so the tests see the same input
+//! a builder that writes double bonds would produce. Sheets and tubes are laid out as a brick
+//! wall: row `j` holds `width` carbons chained by horizontal bonds, and carbon `(i, j)` is bonded
+//! to `(i, j + 1)` when `i + j` is even. Pairing the horizontal bonds that start at even columns
+//! is a perfect matching, which serves as the Kekulé structure. Carbons on the sheet edges and
+//! the tube ends are capped with hydrogens up to three neighbors, so the two flake corners that
+//! hang off the lattice become `=CH2` groups.
+
+#![allow(dead_code)]
+
+use dreid_typer::{Element, GraphBondOrder, MolecularGraph};
+
+/// Number of carbons in buckminsterfullerene.
+pub const C60_ATOMS: usize = 60;
+
+/// Builds a hydrogen-terminated brick-wall lattice of `width` columns and `rows` rows.
+///
+/// With `rolled`, the last column is bonded to the first, giving a zigzag nanotube of
+/// `width / 2` hexagons around its circumference.
+///
+/// # Panics
+///
+/// Panics if `width` is odd or smaller than four, or if `rows` is zero.
+fn brick_wall(width: usize, rows: usize, rolled: bool) -> MolecularGraph {
+    assert!(
+        width >= 4 && width.is_multiple_of(2),
+        "width must be even and at least four"
+    );
+    assert!(rows > 0, "at least one row is required");
+
+    let mut graph = MolecularGraph::new();
+    let carbons: Vec<Vec<usize>> = (0..rows)
+        .map(|_| (0..width).map(|_| graph.add_atom(Element::C)).collect())
+        .collect();
+
+    for (j, row) in carbons.iter().enumerate() {
+        let horizontal = if rolled { width } else { width - 1 };
+        for i in 0..horizontal {
+            let order = if i % 2 == 0 {
+                GraphBondOrder::Double
+            } else {
+                GraphBondOrder::Single
+            };
+            graph.add_bond(row[i], row[(i + 1) % width], order).unwrap();
+        }
+        if j + 1 < rows {
+            for i in (0..width).filter(|i| (i + j) % 2 == 0) {
+                graph
+                    .add_bond(row[i], carbons[j + 1][i], GraphBondOrder::Single)
+                    .unwrap();
+            }
+        }
+    }
+
+    let mut degree = vec![0; graph.atoms.len()];
+    for edge in &graph.bonds {
+        degree[edge.atom_ids.0] += 1;
+        degree[edge.atom_ids.1] += 1;
+    }
+    for &carbon in carbons.iter().flatten() {
+        for _ in degree[carbon]..3 {
+            let h = graph.add_atom(Element::H);
+            graph.add_bond(carbon, h, GraphBondOrder::Single).unwrap();
+        }
+    }
+    graph
+}
+
+/// Hydrogen-terminated graphene flake of `width` by `rows` carbons.
+pub fn graphene_flake(width: usize, rows: usize) -> MolecularGraph {
+    brick_wall(width, rows, false)
+}
+
+/// Hydrogen-capped zigzag `(n, 0)` nanotube with `rows` rings of `2 * n` carbons.
+pub fn zigzag_nanotube(n: usize, rows: usize) -> MolecularGraph {
+    brick_wall(2 * n, rows, true)
+}
+
+/// Zigzag `(10, 0)` nanotube grown until it holds at least `target` atoms.
+pub fn nanotube(target: usize) -> MolecularGraph {
+    zigzag_nanotube(10, target.div_ceil(20).max(2))
+}
+
+/// Buckminsterfullerene, C60, with double bonds on the 30 bonds shared by two hexagons.
+///
+/// Atoms are placed at the vertices of a truncated icosahedron, the cyclic permutations of
+/// `(0, ±1, ±3φ)`, `(±1, ±(2 + φ), ±2φ)`, and `(±φ, ±2, ±(2φ + 1))`, and bonded when they lie
+/// at the edge length of two apart.
+pub fn fullerene_c60() -> MolecularGraph {
+    let phi = (1.0 + 5.0_f64.sqrt()) / 2.0;
+    let mut points = Vec::with_capacity(C60_ATOMS);
+    for base in [
+        [0.0, 1.0, 3.0 * phi],
+        [1.0, 2.0 + phi, 2.0 * phi],
+        [phi, 2.0, 2.0 * phi + 1.0],
+    ] {
+        for signs in 0..8 {
+            let signed: Vec<f64> = base
+                .iter()
+                .enumerate()
+                .map(|(k, &x)| if signs >> k & 1 == 1 { -x } else { x })
+                .collect();
+            for shift in 0..3 {
+                let point = [
+                    signed[shift % 3],
+                    signed[(shift + 1) % 3],
+                    signed[(shift + 2) % 3],
+                ];
+                if !points.contains(&point) {
+                    points.push(point);
+                }
+            }
+        }
+    }
+    assert_eq!(points.len(), C60_ATOMS);
+
+    let mut edges = Vec::with_capacity(90);
+    for a in 0..C60_ATOMS {
+        for b in a + 1..C60_ATOMS {
+            let distance: f64 = (0..3).map(|k| (points[a][k] - points[b][k]).powi(2)).sum();
+            if (distance - 4.0).abs() < 1e-6 {
+                edges.push((a, b));
+            }
+        }
+    }
+    assert_eq!(edges.len(), 90);
+
+    let adjacent = |a: usize, b: usize| edges.contains(&(a.min(b), a.max(b)));
+    let in_pentagon = |a: usize, b: usize| {
+        // A bond lies on a pentagon when a three-bond path, avoiding both ends, joins its ends.
+        (0..C60_ATOMS).any(|c| {
+            c != b
+                && adjacent(a, c)
+                && (0..C60_ATOMS).any(|d| {
+                    d != a
+                        && d != b
+                        && adjacent(c, d)
+                        && (0..C60_ATOMS)
+                            .any(|e| e != a && e != c && adjacent(d, e) && adjacent(e, b))
+                })
+        })
+    };
+
+    let mut graph = MolecularGraph::new();
+    for _ in 0..C60_ATOMS {
+        graph.add_atom(Element::C);
+    }
+    for &(a, b) in &edges {
+        let order = if in_pentagon(a, b) {
+            GraphBondOrder::Single
+        } else {
+            GraphBondOrder::Double
+        };
+        graph.add_bond(a, b, order).unwrap();
+    }
+    graph
+}
@@ -0,0 +1,274 @@
+//! Random molecule construction shared by the property tests and the fuzz targets.
+//!
+//! Generators interpret a flat list of operations, so any byte string or proptest value maps to
+//! a graph and shrinking works on the operations rather than on the graph itself.
+//! [`build_molecule`] only ever produces chemically sensible, neutral molecules: every atom keeps
+//! its standard valence and hydrogens fill whatever remains. [`build_graph`] makes no such
+//! promise and is meant for robustness checks. The [`carbon`] module builds graphene flakes,
+//! nanotubes, and C60 for the tests and benchmarks of large sp2 carbon systems.
+
+#![allow(dead_code)]
+
+pub mod carbon;
+
+use dreid_typer::{Element, GraphBondOrder, MolecularGraph};
+
+/// Heavy atoms the valence-respecting generator draws from, with their standard valence.
+///
+/// Carbon is listed twice so that it dominates the generated skeletons.
+pub const PALETTE: [(Element, u8); 11] = [
+    (Element::C, 4),
+    (Element::C, 4),
+    (Element::N, 3),
+    (Element::O, 2),
+    (Element::S, 2),
+    (Element::P, 3),
+    (Element::B, 3),
+    (Element::Si, 4),
+    (Element::F, 1),
+    (Element::Cl, 1),
+    (Element::Br, 1),
+];
+
+/// Elements the unconstrained generator draws from, including metals and noble gases.
+pub const ELEMENTS: [Element; 16] = [
+    Element::H,
+    Element::He,
+    Element::B,
+    Element::C,
+    Element::N,
+    Element::O,
+    Element::F,
+    Element::Na,
+    Element::Si,
+    Element::P,
+    Element::S,
+    Element::Cl,
+    Element::Fe,
+    Element::Zn,
+    Element::Br,
+    Element::La,
+];
+
+/// One step of the valence-respecting generator.
+///
+/// Indices wrap around the atoms or bonds present when the step runs; steps that would break a
+/// valence or duplicate a bond are skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// Attaches a palette atom to `parent` by a single bond.
+    Atom { element: u8, parent: u16 },
+    /// Raises the order of a non-aromatic bond by one.
+    Raise { bond: u16 },
+    /// Closes a ring with a single bond between two atoms.
+    Close { a: u16, b: u16 },
+    /// Attaches a benzene ring, written with aromatic bonds, to `parent`.
+    Phenyl { parent: u16 },
+}
+
+impl Op {
+    /// Decodes an operation from four bytes.
+    pub fn from_bytes(bytes: [u8; 4]) -> Self {
+        let first = u16::from_le_bytes([bytes[1], bytes[2]]);
+        let second = u16::from(bytes[3]);
+        match bytes[0] % 8 {
+            0..=3 => Op::Atom {
+                element: bytes[3],
+                parent: first,
+            },
+            4 | 5 => Op::Raise { bond: first },
+            6 => Op::Close {
+                a: first,
+                b: second,
+            },
+            _ => Op::Phenyl { parent: first },
+        }
+    }
+}
+
+/// A generated molecule together with the valence every atom was built with.
+#[derive(Debug, Clone)]
+pub struct GeneratedMolecule {
+    pub graph: MolecularGraph,
+    /// Sum of bond orders each atom carries in any Kekulé structure.
+    pub valences: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PendingBond {
+    atoms: (usize, usize),
+    order: u8,
+    aromatic: bool,
+}
+
+#[derive(Debug, Default)]
+struct Builder {
+    elements: Vec<Element>,
+    valences: Vec<u8>,
+    free: Vec<u8>,
+    aromatic: Vec<bool>,
+    bonds: Vec<PendingBond>,
+}
+
+impl Builder {
+    fn add_atom(&mut self, element: Element, valence: u8, aromatic: bool) -> usize {
+        self.elements.push(element);
+        self.valences.push(valence);
+        self.free.push(valence);
+        self.aromatic.push(aromatic);
+        self.elements.len() - 1
+    }
+
+    fn bonded(&self, a: usize, b: usize) -> bool {
+        self.bonds
+            .iter()
+            .any(|bond| bond.atoms == (a, b) || bond.atoms == (b, a))
+    }
+
+    fn add_bond(&mut self, a: usize, b: usize, order: u8, aromatic: bool) {
+        self.free[a] -= order;
+        self.free[b] -= order;
+        self.bonds.push(PendingBond {
+            atoms: (a, b),
+            order,
+            aromatic,
+        });
+    }
+
+    fn apply(&mut self, op: Op) {
+        let atom_count = self.elements.len();
+        match op {
+            Op::Atom { element, parent } => {
+                let parent = usize::from(parent) % atom_count;
+                let (element, valence) = PALETTE[usize::from(element) % PALETTE.len()];
+                if self.free[parent] > 0 {
+                    let atom = self.add_atom(element, valence, false);
+                    self.add_bond(parent, atom, 1, false);
+                }
+            }
+            Op::Raise { bond } => {
+                if self.bonds.is_empty() {
+                    return;
+                }
+                let index = usize::from(bond) % self.bonds.len();
+                let PendingBond {
+                    atoms: (a, b),
+                    order,
+                    aromatic,
+                } = self.bonds[index];
+                if !aromatic
+                    && order < 3
+                    && !self.aromatic[a]
+                    && !self.aromatic[b]
+                    && self.free[a] > 0
+                    && self.free[b] > 0
+                {
+                    self.free[a] -= 1;
+                    self.free[b] -= 1;
+                    self.bonds[index].order += 1;
+                }
+            }
+            Op::Close { a, b } => {
+                let a = usize::from(a) % atom_count;
+                let b = usize::from(b) % atom_count;
+                if a != b && self.free[a] > 0 && self.free[b] > 0 && !self.bonded(a, b) {
+                    self.add_bond(a, b, 1, false);
+                }
+            }
+            Op::Phenyl { parent } => {
+                let parent = usize::from(parent) % atom_count;
+                if self.free[parent] == 0 {
+                    return;
+                }
+                let ring: Vec<usize> = (0..6).map(|_| self.add_atom(Element::C, 4, true)).collect();
+                for (i, &atom) in ring.iter().enumerate() {
+                    let next = ring[(i + 1) % 6];
+                    // Each aromatic carbon carries one single and one double ring bond.
+                    self.free[atom] -= 1;
+                    self.free[next] -= 1;
+                    self.bonds.push(PendingBond {
+                        atoms: (atom, next),
+                        order: 1,
+                        aromatic: true,
+                    });
+                }
+                for &atom in &ring {
+                    self.free[atom] -= 1;
+                }
+                self.add_bond(parent, ring[0], 1, false);
+            }
+        }
+    }
+
+    fn finish(mut self) -> GeneratedMolecule {
+        for atom in 0..self.elements.len() {
+            while self.free[atom] > 0 {
+                let hydrogen = self.add_atom(Element::H, 1, false);
+                self.add_bond(atom, hydrogen, 1, false);
+            }
+        }
+
+        let mut graph = MolecularGraph::new();
+        for &element in &self.elements {
+            graph.add_atom(element);
+        }
+        for bond in &self.bonds {
+            let order = match (bond.aromatic, bond.order) {
+                (true, _) => GraphBondOrder::Aromatic,
+                (false, 1) => GraphBondOrder::Single,
+                (false, 2) => GraphBondOrder::Double,
+                _ => GraphBondOrder::Triple,
+            };
+            graph
+                .add_bond(bond.atoms.0, bond.atoms.1, order)
+                .expect("generator only bonds distinct existing atoms");
+        }
+
+        GeneratedMolecule {
+            graph,
+            valences: self.valences,
+        }
+    }
+}
+
+/// Builds a neutral molecule in which every atom keeps its standard valence.
+///
+/// The molecule grows from a single carbon; remaining valence is filled with hydrogens.
+pub fn build_molecule(ops: &[Op]) -> GeneratedMolecule {
+    let mut builder = Builder::default();
+    builder.add_atom(Element::C, 4, false);
+    for &op in ops {
+        builder.apply(op);
+    }
+    builder.finish()
+}
+
+/// Builds a graph from raw bytes with no chemical constraints.
+///
+/// The first byte sets the atom count; every following triple names two atoms and a bond
+/// order. Bonds the graph rejects, such as self-bonds and duplicates, are dropped.
+pub fn build_graph(bytes: &[u8]) -> MolecularGraph {
+    let mut graph = MolecularGraph::new();
+    let Some((&count, rest)) = bytes.split_first() else {
+        return graph;
+    };
+    let (elements, bonds) = rest.split_at(rest.len().min(usize::from(count % 64)));
+    for &byte in elements {
+        graph.add_atom(ELEMENTS[usize::from(byte) % ELEMENTS.len()]);
+    }
+    if elements.is_empty() {
+        return graph;
+    }
+    for chunk in bonds.chunks_exact(3) {
+        let a = usize::from(chunk[0]) % elements.len();
+        let b = usize::from(chunk[1]) % elements.len();
+        let order = match chunk[2] % 4 {
+            0 => GraphBondOrder::Single,
+            1 => GraphBondOrder::Double,
+            2 => GraphBondOrder::Triple,
+            _ => GraphBondOrder::Aromatic,
+        };
+        let _ = graph.add_bond(a, b, order);
+    }
+    graph
+}
@@ -1,8 +1,10 @@
 #[path = "cases/mod.rs"]
 pub mod cases;
 
+use dreid_typer::rules::{ConflictResolution, RuleSet, get_default_rules};
 use dreid_typer::{
-    Element, GraphBondOrder, MolecularGraph, MolecularTopology, TopologyBondOrder, assign_topology,
+    Element, GraphBondOrder, MolecularGraph, MolecularTopology, TopologyBondOrder, Typer,
+    assign_atom_types, assign_bond_orders, assign_topology,
 };
 use std::collections::{HashMap, HashSet};
 
@@ -61,6 +63,63 @@ pub fn run_molecule_test_case(case: &MoleculeTestCase) {
 
     verify_atom_types(&topology, &molecule, case);
     verify_bond_orders(&topology, &molecule, case);
+
+    let atom_types = assign_atom_types(molecule.graph())
+        .unwrap_or_else(|err| panic!("Type assignment failed for '{}': {:?}", case.name, err));
+    let topology_types: Vec<&str> = topology
+        .atoms
+        .iter()
+        .map(|a| a.atom_type.as_str())
+        .collect();
+    assert_eq!(
+        atom_types, topology_types,
+        "Molecule '{}': types-only API disagrees with the topology",
+        case.name
+    );
+}
+
+/// Runs a case with every input bond reduced to a single bond, so that the orders must be
+/// inferred from connectivity before typing.
+pub fn run_connectivity_test_case(case: &MoleculeTestCase) {
+    let mut molecule = build_from_blueprint(case);
+    for bond in &mut molecule.graph.bonds {
+        bond.order = GraphBondOrder::Single;
+    }
+    assign_bond_orders(&mut molecule.graph);
+
+    let topology = assign_topology(molecule.graph())
+        .unwrap_or_else(|err| panic!("Topology assignment failed for '{}': {:?}", case.name, err));
+
+    verify_atom_types(&topology, &molecule, case);
+    verify_bond_orders(&topology, &molecule, case);
+}
+
+/// Runs a case with the default rules ordered by specificity instead of by priority.
+pub fn run_specificity_test_case(case: &MoleculeTestCase) {
+    let molecule = build_from_blueprint(case);
+    let rules = RuleSet::with_resolution(
+        get_default_rules().to_vec(),
+        ConflictResolution::Specificity,
+    );
+
+    let topology = Typer::with_rule_set(rules)
+        .assign_topology(molecule.graph())
+        .unwrap_or_else(|err| panic!("Topology assignment failed for '{}': {:?}", case.name, err));
+
+    verify_atom_types(&topology, &molecule, case);
+    verify_bond_orders(&topology, &molecule, case);
+}
+
+/// Runs a case through a materials preset instead of the default typer.
+pub fn run_preset_test_case(case: &MoleculeTestCase, typer: &Typer) {
+    let molecule = build_from_blueprint(case);
+
+    let topology = typer
+        .assign_topology(molecule.graph())
+        .unwrap_or_else(|err| panic!("Topology assignment failed for '{}': {:?}", case.name, err));
+
+    verify_atom_types(&topology, &molecule, case);
+    verify_bond_orders(&topology, &molecule, case);
 }
 
 fn build_from_blueprint(case: &MoleculeTestCase) -> LabeledMolecule {
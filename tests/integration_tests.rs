@@ -1,9 +1,16 @@
 mod harness;
 
+use dreid_typer::materials::{mof_typer, zeolite_typer};
 use harness::cases::amino_acids::*;
 use harness::cases::dreiding_paper::*;
+use harness::cases::frameworks::*;
+use harness::cases::main_group::*;
 use harness::cases::nucleic_acids::*;
-use harness::run_molecule_test_case;
+use harness::cases::strained_rings::*;
+use harness::{
+    run_connectivity_test_case, run_molecule_test_case, run_preset_test_case,
+    run_specificity_test_case,
+};
 
 macro_rules! generate_molecule_test {
     ($test_name:ident, $molecule_case:expr) => {
@@ -14,6 +21,33 @@ macro_rules! generate_molecule_test {
     };
 }
 
+macro_rules! generate_connectivity_test {
+    ($test_name:ident, $molecule_case:expr) => {
+        #[test]
+        fn $test_name() {
+            run_connectivity_test_case(&$molecule_case);
+        }
+    };
+}
+
+macro_rules! generate_preset_test {
+    ($test_name:ident, $molecule_case:expr, $typer:expr) => {
+        #[test]
+        fn $test_name() {
+            run_preset_test_case(&$molecule_case, &$typer);
+        }
+    };
+}
+
+macro_rules! generate_specificity_test {
+    ($test_name:ident, $molecule_case:expr) => {
+        #[test]
+        fn $test_name() {
+            run_specificity_test_case(&$molecule_case);
+        }
+    };
+}
+
 generate_molecule_test!(glycine_zwitterion_is_typed_correctly, GLYCINE_ZWITTERION);
 generate_molecule_test!(alanine_zwitterion_is_typed_correctly, ALANINE_ZWITTERION);
 generate_molecule_test!(valine_zwitterion_is_typed_correctly, VALINE_ZWITTERION);
@@ -97,3 +131,84 @@ generate_molecule_test!(adenosine_is_typed_correctly, ADENOSINE);
 generate_molecule_test!(phosphate_ester_is_typed_correctly, PHOSPHATE_ESTER);
 generate_molecule_test!(choline_cation_is_typed_correctly, CHOLINE_CATION);
 generate_molecule_test!(perchlorate_anion_is_typed_correctly, PERCHLORATE_ANION);
+
+generate_molecule_test!(trimethylborane_is_typed_correctly, TRIMETHYLBORANE);
+generate_molecule_test!(
+    tetrafluoroborate_anion_is_typed_correctly,
+    TETRAFLUOROBORATE_ANION
+);
+generate_molecule_test!(tetramethylsilane_is_typed_correctly, TETRAMETHYLSILANE);
+generate_molecule_test!(
+    chlorotrimethylsilane_is_typed_correctly,
+    CHLOROTRIMETHYLSILANE
+);
+generate_molecule_test!(trimethylgallium_is_typed_correctly, TRIMETHYLGALLIUM);
+generate_molecule_test!(tetramethylgermane_is_typed_correctly, TETRAMETHYLGERMANE);
+generate_molecule_test!(trimethylarsine_is_typed_correctly, TRIMETHYLARSINE);
+generate_molecule_test!(dimethyl_selenide_is_typed_correctly, DIMETHYL_SELENIDE);
+generate_molecule_test!(trimethylindium_is_typed_correctly, TRIMETHYLINDIUM);
+generate_molecule_test!(tetramethyltin_is_typed_correctly, TETRAMETHYLTIN);
+generate_molecule_test!(trimethylstibine_is_typed_correctly, TRIMETHYLSTIBINE);
+generate_molecule_test!(dimethyl_telluride_is_typed_correctly, DIMETHYL_TELLURIDE);
+generate_molecule_test!(fluoromethane_is_typed_correctly, FLUOROMETHANE);
+generate_molecule_test!(chloromethane_is_typed_correctly, CHLOROMETHANE);
+generate_molecule_test!(bromomethane_is_typed_correctly, BROMOMETHANE);
+generate_molecule_test!(iodomethane_is_typed_correctly, IODOMETHANE);
+
+generate_molecule_test!(cyclopropane_is_typed_correctly, CYCLOPROPANE);
+generate_molecule_test!(cyclopropene_is_typed_correctly, CYCLOPROPENE);
+generate_molecule_test!(oxirane_is_typed_correctly, OXIRANE);
+generate_molecule_test!(aziridine_is_typed_correctly, AZIRIDINE);
+generate_molecule_test!(thiirane_is_typed_correctly, THIIRANE);
+generate_molecule_test!(cyclobutane_is_typed_correctly, CYCLOBUTANE);
+
+generate_molecule_test!(sodalite_cage_is_typed_correctly, SODALITE_CAGE);
+
+generate_connectivity_test!(arginine_bond_orders_are_inferred, ARGININE_ZWITTERION);
+generate_connectivity_test!(histidine_bond_orders_are_inferred, HISTIDINE_ZWITTERION);
+generate_connectivity_test!(tryptophan_bond_orders_are_inferred, TRYPTOPHAN_ZWITTERION);
+generate_connectivity_test!(diglycine_bond_orders_are_inferred, DIGLYCINE);
+generate_connectivity_test!(deoxyadenosine_bond_orders_are_inferred, DEOXYADENOSINE);
+generate_connectivity_test!(acridine_bond_orders_are_inferred, ACRIDINE);
+generate_connectivity_test!(trinitrobenzene_bond_orders_are_inferred, TRINITROBENZENE);
+generate_connectivity_test!(
+    tetramethylthiourea_bond_orders_are_inferred,
+    TETRAMETHYLTHIOUREA
+);
+generate_connectivity_test!(
+    methanesulfonamide_bond_orders_are_inferred,
+    METHANESULFONAMIDE
+);
+generate_connectivity_test!(phosphate_ester_bond_orders_are_inferred, PHOSPHATE_ESTER);
+generate_connectivity_test!(perchlorate_bond_orders_are_inferred, PERCHLORATE_ANION);
+
+generate_connectivity_test!(cyclopropene_bond_orders_are_inferred, CYCLOPROPENE);
+
+generate_specificity_test!(histidine_is_typed_by_specificity, HISTIDINE_ZWITTERION);
+generate_specificity_test!(cysteine_is_typed_by_specificity, CYSTEINE_ZWITTERION);
+generate_specificity_test!(deoxyadenosine_is_typed_by_specificity, DEOXYADENOSINE);
+generate_specificity_test!(acridine_is_typed_by_specificity, ACRIDINE);
+generate_specificity_test!(
+    dimethyl_sulfoxide_is_typed_by_specificity,
+    DIMETHYL_SULFOXIDE
+);
+generate_specificity_test!(phosphate_ester_is_typed_by_specificity, PHOSPHATE_ESTER);
+generate_specificity_test!(choline_is_typed_by_specificity, CHOLINE_CATION);
+generate_specificity_test!(oxirane_is_typed_by_specificity, OXIRANE);
+generate_specificity_test!(cyclopropene_is_typed_by_specificity, CYCLOPROPENE);
+
+generate_preset_test!(
+    mof5_fragment_is_typed_by_mof_preset,
+    MOF5_FRAGMENT,
+    mof_typer()
+);
+generate_preset_test!(
+    uio66_fragment_is_typed_by_mof_preset,
+    UIO66_FRAGMENT,
+    mof_typer()
+);
+generate_preset_test!(
+    sodalite_cage_is_typed_by_zeolite_preset,
+    SODALITE_CAGE,
+    zeolite_typer()
+);
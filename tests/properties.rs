@@ -0,0 +1,222 @@
+#[path = "generators/mod.rs"]
+mod generators;
+
+use dreid_typer::{
+    AnnotatedMolecule, AssignmentPolicy, Element, GraphBondOrder, MolecularTopology,
+    PerceptionPipeline, Stage, TopologyBondOrder, Typer, assign_bond_orders,
+};
+use generators::carbon::{C60_ATOMS, fullerene_c60, graphene_flake, zigzag_nanotube};
+use generators::{GeneratedMolecule, Op, build_graph, build_molecule};
+use proptest::collection::vec;
+use proptest::prelude::*;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+fn ops() -> impl Strategy<Value = Vec<Op>> {
+    vec(any::<[u8; 4]>().prop_map(Op::from_bytes), 0..48)
+}
+
+fn molecule() -> impl Strategy<Value = GeneratedMolecule> {
+    ops().prop_map(|ops| build_molecule(&ops))
+}
+
+/// Types the graph with a pipeline that snapshots the annotated molecule after `stage`.
+fn type_with_snapshot(
+    molecule: &GeneratedMolecule,
+    stage: Stage,
+) -> (MolecularTopology, AnnotatedMolecule) {
+    let snapshot = Arc::new(Mutex::new(None));
+    let sink = Arc::clone(&snapshot);
+    let pipeline = PerceptionPipeline::builder()
+        .insert_after(
+            stage,
+            "snapshot",
+            move |annotated: &mut AnnotatedMolecule| {
+                *sink.lock().unwrap() = Some(annotated.clone());
+                Ok(())
+            },
+        )
+        .build();
+    let topology = typer()
+        .with_perception_pipeline(pipeline)
+        .assign_topology(&molecule.graph)
+        .unwrap_or_else(|err| panic!("typing failed for {:?}: {err}", molecule.graph));
+    let annotated = snapshot.lock().unwrap().take().expect("snapshot stage ran");
+    (topology, annotated)
+}
+
+/// Returns a typer that falls back to generic types for atoms no rule covers, so that rule
+/// coverage gaps do not mask perception failures.
+fn typer() -> Typer {
+    Typer::new().with_assignment_policy(AssignmentPolicy::Fallback)
+}
+
+fn bond_set(topology: &MolecularTopology) -> HashSet<(usize, usize)> {
+    topology
+        .bonds
+        .iter()
+        .flat_map(|bond| {
+            let (a, b) = bond.atom_ids;
+            [(a, b), (b, a)]
+        })
+        .collect()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn perception_never_panics_on_arbitrary_graphs(bytes in vec(any::<u8>(), 0..160)) {
+        let mut graph = build_graph(&bytes);
+        let _ = Typer::new().assign_topology(&graph);
+        assign_bond_orders(&mut graph);
+        let _ = Typer::new().assign_topology(&graph);
+    }
+
+    #[test]
+    fn valence_respecting_molecules_are_typed(molecule in molecule()) {
+        let topology = typer().assign_topology(&molecule.graph);
+        prop_assert!(topology.is_ok(), "{:?}: {:?}", molecule.graph, topology.err());
+        prop_assert_eq!(topology.unwrap().atoms.len(), molecule.valences.len());
+    }
+
+    #[test]
+    fn kekulization_conserves_valence(molecule in molecule()) {
+        let (_, annotated) = type_with_snapshot(&molecule, Stage::Kekulization);
+
+        let mut sums = vec![0u8; annotated.atoms.len()];
+        for bond in &annotated.bonds {
+            let order = match bond.order {
+                GraphBondOrder::Single => 1,
+                GraphBondOrder::Double => 2,
+                GraphBondOrder::Triple => 3,
+                GraphBondOrder::Aromatic => {
+                    return Err(TestCaseError::fail(format!("bond {} left aromatic", bond.id)));
+                }
+            };
+            sums[bond.atom_ids.0] += order;
+            sums[bond.atom_ids.1] += order;
+        }
+        prop_assert_eq!(sums, molecule.valences);
+    }
+
+    #[test]
+    fn aromatic_atoms_lie_on_rings(molecule in molecule()) {
+        let (topology, annotated) = type_with_snapshot(&molecule, Stage::Groups);
+
+        for atom in annotated.atoms.iter().filter(|atom| atom.is_aromatic) {
+            prop_assert!(atom.is_in_ring, "aromatic atom {} is not in a ring", atom.id);
+            prop_assert!(
+                annotated.rings.iter().any(|ring| ring.contains(&atom.id)),
+                "aromatic atom {} is on no perceived ring",
+                atom.id
+            );
+        }
+        for ring in topology.rings.iter().filter(|ring| ring.is_aromatic) {
+            for &atom_id in &ring.atom_ids {
+                prop_assert!(annotated.atoms[atom_id].is_in_ring);
+            }
+        }
+    }
+
+    #[test]
+    fn topology_terms_reference_bonded_atoms(molecule in molecule()) {
+        let topology = typer().assign_topology(&molecule.graph).unwrap();
+        let count = topology.atoms.len();
+        let bonds = bond_set(&topology);
+        let bonded = |a: usize, b: usize| a < count && b < count && bonds.contains(&(a, b));
+
+        for (id, atom) in topology.atoms.iter().enumerate() {
+            prop_assert_eq!(atom.id, id);
+        }
+        for bond in &topology.bonds {
+            let (a, b) = bond.atom_ids;
+            prop_assert!(a < count && b < count && a != b);
+        }
+        for angle in &topology.angles {
+            let (a, center, b) = angle.atom_ids;
+            prop_assert!(bonded(a, center) && bonded(center, b) && a != b, "{:?}", angle);
+        }
+        for torsion in topology.torsions.iter().chain(&topology.suppressed_torsions) {
+            let (i, j, k, l) = torsion.atom_ids;
+            prop_assert!(bonded(i, j) && bonded(j, k) && bonded(k, l), "{:?}", torsion);
+        }
+        for inversion in &topology.inversions {
+            let (center, axis, plane1, plane2) = inversion.atom_ids;
+            prop_assert!(
+                bonded(center, axis) && bonded(center, plane1) && bonded(center, plane2),
+                "{:?}",
+                inversion
+            );
+        }
+        for donor in &topology.hb_donors {
+            prop_assert!(bonded(donor.donor_id, donor.hydrogen_id), "{:?}", donor);
+        }
+        for &acceptor in &topology.hb_acceptors {
+            prop_assert!(acceptor < count);
+        }
+        for ring in &topology.rings {
+            for (index, &atom_id) in ring.atom_ids.iter().enumerate() {
+                let next = ring.atom_ids[(index + 1) % ring.atom_ids.len()];
+                prop_assert!(bonded(atom_id, next), "{:?}", ring);
+            }
+        }
+    }
+}
+
+/// Asserts that every ring carbon is `C_R` and every bond between two ring carbons resonant.
+fn assert_graphitic(topology: &MolecularTopology) {
+    let ring_atoms: HashSet<usize> = topology
+        .rings
+        .iter()
+        .flat_map(|ring| ring.atom_ids.iter().copied())
+        .collect();
+    for &atom_id in &ring_atoms {
+        let atom = &topology.atoms[atom_id];
+        assert_eq!(atom.element, Element::C);
+        assert_eq!(atom.atom_type, "C_R", "ring carbon {atom_id}");
+    }
+    for bond in &topology.bonds {
+        let (a, b) = bond.atom_ids;
+        if ring_atoms.contains(&a) && ring_atoms.contains(&b) {
+            assert_eq!(
+                bond.order,
+                TopologyBondOrder::Resonant,
+                "{:?}",
+                bond.atom_ids
+            );
+        }
+    }
+}
+
+#[test]
+fn fullerene_carbons_are_resonant_despite_curvature() {
+    let topology = Typer::new().assign_topology(&fullerene_c60()).unwrap();
+    assert_eq!(topology.atoms.len(), C60_ATOMS);
+    assert_eq!(topology.rings.len(), 31);
+    assert!(topology.atoms.iter().all(|atom| atom.atom_type == "C_R"));
+    assert!(
+        topology
+            .bonds
+            .iter()
+            .all(|bond| bond.order == TopologyBondOrder::Resonant)
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn nanotubes_are_typed_as_resonant_carbon(n in 3..9usize, rows in 2..12usize) {
+        let topology = Typer::new().assign_topology(&zigzag_nanotube(n, rows)).unwrap();
+        prop_assert_eq!(topology.atoms.len(), 2 * n * rows + 2 * n);
+        assert_graphitic(&topology);
+    }
+
+    #[test]
+    fn graphene_flakes_are_typed_as_resonant_carbon(half_width in 2..8usize, rows in 2..10usize) {
+        let topology = Typer::new().assign_topology(&graphene_flake(2 * half_width, rows)).unwrap();
+        prop_assert_eq!(topology.rings.len(), (half_width - 1) * (rows - 1));
+        assert_graphitic(&topology);
+    }
+}
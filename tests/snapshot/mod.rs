@@ -0,0 +1,338 @@
+//! Golden-file regression runner for the molecule corpus.
+//!
+//! Every file in `tests/corpus` (`.smi` lines of `SMILES name`, or `.sdf`/`.mol` records) is run
+//! through the full pipeline, and the resulting atom types and bond orders are compared with the
+//! golden file of the same stem in `tests/snapshots`. Mismatches are reported per molecule in the
+//! style of [`TopologyDiff`](dreid_typer::TopologyDiff), e.g. `aspirin: atom 3 (C): C_R -> C_2`.
+//! Set `UPDATE_SNAPSHOTS=1` to rewrite the golden files instead of failing.
+
+use dreid_typer::io::sdf::SdfArchive;
+use dreid_typer::io::smiles::parse_smiles;
+use dreid_typer::{MolecularGraph, MolecularTopology, Typer};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that switches the runner from checking to rewriting golden files.
+pub const UPDATE_VARIABLE: &str = "UPDATE_SNAPSHOTS";
+
+const HEADER: &str = "# Golden atom types and bond orders. Regenerate with UPDATE_SNAPSHOTS=1.";
+
+/// Recorded pipeline outcome for one molecule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub name: String,
+    pub outcome: Outcome,
+}
+
+/// Typed result of a molecule, or the error that stopped it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Typed {
+        /// Element symbol and atom type, indexed by atom ID.
+        atoms: Vec<(String, String)>,
+        /// Bond orders keyed by sorted atom IDs.
+        bonds: BTreeMap<(usize, usize), String>,
+    },
+    Failed(String),
+}
+
+impl Outcome {
+    fn from_topology(topology: &MolecularTopology) -> Self {
+        let atoms = topology
+            .atoms
+            .iter()
+            .map(|atom| (atom.element.to_string(), atom.atom_type.to_string()))
+            .collect();
+        let bonds = topology
+            .bonds
+            .iter()
+            .map(|bond| {
+                let (a, b) = bond.atom_ids;
+                ((a.min(b), a.max(b)), bond.order.to_string())
+            })
+            .collect();
+        Outcome::Typed { atoms, bonds }
+    }
+}
+
+/// Returns the corpus directory.
+pub fn corpus_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus")
+}
+
+/// Returns the golden-file directory.
+pub fn snapshot_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots")
+}
+
+/// Lists the corpus files in name order.
+pub fn corpus_files() -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(corpus_dir())
+        .expect("corpus directory is readable")
+        .map(|entry| entry.expect("corpus entry is readable").path())
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("smi" | "sdf" | "mol")
+            )
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Reads the named molecules of a corpus file; unparsable entries keep their error message.
+///
+/// # Panics
+///
+/// Panics if the file cannot be read or names two molecules alike.
+pub fn load(path: &Path) -> Vec<(String, Result<MolecularGraph, String>)> {
+    let data = fs::read(path).unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+    let molecules: Vec<_> = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("smi") => String::from_utf8_lossy(&data)
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(index, line)| {
+                let mut fields = line.split_whitespace();
+                let smiles = fields.next().unwrap_or_default();
+                let name = fields
+                    .next()
+                    .map_or_else(|| format!("line {}", index + 1), str::to_string);
+                (name, parse_smiles(smiles).map_err(|err| err.to_string()))
+            })
+            .collect(),
+        _ => SdfArchive::new(data)
+            .iter()
+            .enumerate()
+            .map(|(index, record)| match record {
+                Ok(record) if !record.title.trim().is_empty() => {
+                    (record.title.trim().to_string(), Ok(record.graph))
+                }
+                Ok(record) => (format!("record {}", index + 1), Ok(record.graph)),
+                Err(err) => (format!("record {}", index + 1), Err(err.to_string())),
+            })
+            .collect(),
+    };
+
+    let mut names = HashSet::new();
+    for (name, _) in &molecules {
+        assert!(
+            names.insert(name.as_str()),
+            "{}: duplicate molecule name '{name}'",
+            path.display()
+        );
+    }
+    molecules
+}
+
+/// Runs the default pipeline over the molecules of a corpus file.
+pub fn run(molecules: Vec<(String, Result<MolecularGraph, String>)>) -> Vec<Snapshot> {
+    let typer = Typer::new();
+    molecules
+        .into_iter()
+        .map(|(name, graph)| {
+            let outcome = match graph {
+                Ok(graph) => match typer.assign_topology(&graph) {
+                    Ok(topology) => Outcome::from_topology(&topology),
+                    Err(err) => Outcome::Failed(err.to_string()),
+                },
+                Err(err) => Outcome::Failed(err),
+            };
+            let outcome = match outcome {
+                // Golden files hold one message per line.
+                Outcome::Failed(message) => Outcome::Failed(message.replace('\n', " ")),
+                typed => typed,
+            };
+            Snapshot { name, outcome }
+        })
+        .collect()
+}
+
+/// Writes snapshots in the golden-file format.
+pub fn render(snapshots: &[Snapshot]) -> String {
+    let mut text = format!("{HEADER}\n");
+    for snapshot in snapshots {
+        writeln!(text, "\n== {}", snapshot.name).unwrap();
+        match &snapshot.outcome {
+            Outcome::Typed { atoms, bonds } => {
+                for (id, (element, atom_type)) in atoms.iter().enumerate() {
+                    writeln!(text, "atom {id} {element} {atom_type}").unwrap();
+                }
+                for ((a, b), order) in bonds {
+                    writeln!(text, "bond {a} {b} {order}").unwrap();
+                }
+            }
+            Outcome::Failed(message) => writeln!(text, "error {message}").unwrap(),
+        }
+    }
+    text
+}
+
+/// Reads a golden file written by [`render`].
+///
+/// # Panics
+///
+/// Panics on lines that [`render`] never writes.
+pub fn parse(text: &str) -> Vec<Snapshot> {
+    let mut snapshots: Vec<Snapshot> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix("== ") {
+            snapshots.push(Snapshot {
+                name: name.to_string(),
+                outcome: Outcome::Typed {
+                    atoms: Vec::new(),
+                    bonds: BTreeMap::new(),
+                },
+            });
+            continue;
+        }
+        let malformed = || panic!("snapshot line {}: malformed '{line}'", index + 1);
+        let Some(snapshot) = snapshots.last_mut() else {
+            malformed()
+        };
+        if let Some(message) = line.strip_prefix("error ") {
+            snapshot.outcome = Outcome::Failed(message.to_string());
+            continue;
+        }
+        let Outcome::Typed { atoms, bonds } = &mut snapshot.outcome else {
+            malformed()
+        };
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            ["atom", id, element, atom_type] if id.parse() == Ok(atoms.len()) => {
+                atoms.push((element.to_string(), atom_type.to_string()));
+            }
+            ["bond", a, b, order] => match (a.parse(), b.parse()) {
+                (Ok(a), Ok(b)) => {
+                    bonds.insert((a, b), order.to_string());
+                }
+                _ => malformed(),
+            },
+            _ => malformed(),
+        }
+    }
+    snapshots
+}
+
+/// Lists every difference between the golden and the current snapshots, one line each.
+pub fn diff(expected: &[Snapshot], actual: &[Snapshot]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let expected_by_name: BTreeMap<&str, &Outcome> = expected
+        .iter()
+        .map(|snapshot| (snapshot.name.as_str(), &snapshot.outcome))
+        .collect();
+    let actual_names: HashSet<&str> = actual
+        .iter()
+        .map(|snapshot| snapshot.name.as_str())
+        .collect();
+
+    for snapshot in actual {
+        let name = &snapshot.name;
+        let Some(&before) = expected_by_name.get(name.as_str()) else {
+            lines.push(format!("+ molecule {name}"));
+            continue;
+        };
+        match (before, &snapshot.outcome) {
+            (Outcome::Failed(before), Outcome::Failed(after)) if before != after => {
+                lines.push(format!("{name}: error '{before}' -> '{after}'"));
+            }
+            (Outcome::Failed(before), Outcome::Typed { .. }) => {
+                lines.push(format!("{name}: error '{before}' -> typed"));
+            }
+            (Outcome::Typed { .. }, Outcome::Failed(after)) => {
+                lines.push(format!("{name}: typed -> error '{after}'"));
+            }
+            (
+                Outcome::Typed {
+                    atoms: before_atoms,
+                    bonds: before_bonds,
+                },
+                Outcome::Typed {
+                    atoms: after_atoms,
+                    bonds: after_bonds,
+                },
+            ) => {
+                for id in 0..before_atoms.len().max(after_atoms.len()) {
+                    match (before_atoms.get(id), after_atoms.get(id)) {
+                        (Some((element, before)), Some((_, after))) if before != after => {
+                            lines.push(format!(
+                                "{name}: atom {id} ({element}): {before} -> {after}"
+                            ));
+                        }
+                        (Some((element, before)), None) => {
+                            lines.push(format!("{name}: - atom {id} ({element}) {before}"));
+                        }
+                        (None, Some((element, after))) => {
+                            lines.push(format!("{name}: + atom {id} ({element}) {after}"));
+                        }
+                        _ => {}
+                    }
+                }
+                for (&(a, b), before) in before_bonds {
+                    match after_bonds.get(&(a, b)) {
+                        Some(after) if after != before => {
+                            lines.push(format!("{name}: bond {a}-{b}: {before} -> {after}"));
+                        }
+                        None => lines.push(format!("{name}: - bond {a}-{b} ({before})")),
+                        _ => {}
+                    }
+                }
+                for (&(a, b), after) in after_bonds {
+                    if !before_bonds.contains_key(&(a, b)) {
+                        lines.push(format!("{name}: + bond {a}-{b} ({after})"));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    for snapshot in expected {
+        if !actual_names.contains(snapshot.name.as_str()) {
+            lines.push(format!("- molecule {}", snapshot.name));
+        }
+    }
+    lines
+}
+
+/// Checks one corpus file against its golden file, or rewrites the golden file when
+/// [`UPDATE_VARIABLE`] is set.
+///
+/// # Returns
+///
+/// A report of every difference, or `None` when the file matches.
+pub fn check(corpus_file: &Path) -> Option<String> {
+    let stem = corpus_file.file_stem().expect("corpus files have a stem");
+    let golden_path = snapshot_dir().join(stem).with_extension("snap");
+    let actual = run(load(corpus_file));
+
+    if std::env::var_os(UPDATE_VARIABLE).is_some() {
+        fs::create_dir_all(snapshot_dir()).expect("snapshot directory is writable");
+        fs::write(&golden_path, render(&actual)).expect("snapshot is writable");
+        return None;
+    }
+
+    let Ok(golden) = fs::read_to_string(&golden_path) else {
+        return Some(format!(
+            "{}: no golden file at {}; run with {UPDATE_VARIABLE}=1 to create it",
+            corpus_file.display(),
+            golden_path.display()
+        ));
+    };
+    let lines = diff(&parse(&golden), &actual);
+    if lines.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{} differs from {} in {} place(s); run with {UPDATE_VARIABLE}=1 to accept:\n  {}",
+        corpus_file.display(),
+        golden_path.display(),
+        lines.len(),
+        lines.join("\n  ")
+    ))
+}
@@ -0,0 +1,85 @@
+#[path = "snapshot/mod.rs"]
+mod snapshot;
+
+use snapshot::{Outcome, Snapshot, corpus_dir, corpus_files, diff, parse, render, snapshot_dir};
+use std::collections::BTreeMap;
+
+#[test]
+fn corpus_matches_golden_snapshots() {
+    let files = corpus_files();
+    assert!(
+        !files.is_empty(),
+        "no corpus files in {}",
+        corpus_dir().display()
+    );
+
+    let reports: Vec<String> = files
+        .iter()
+        .filter_map(|file| snapshot::check(file))
+        .collect();
+    assert!(reports.is_empty(), "\n{}", reports.join("\n\n"));
+}
+
+#[test]
+fn every_golden_file_has_a_corpus_file() {
+    let stems: Vec<_> = corpus_files()
+        .iter()
+        .map(|file| file.file_stem().unwrap().to_owned())
+        .collect();
+    for entry in std::fs::read_dir(snapshot_dir()).unwrap() {
+        let path = entry.unwrap().path();
+        assert!(
+            stems
+                .iter()
+                .any(|stem| Some(stem.as_os_str()) == path.file_stem()),
+            "{} has no corpus file",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn golden_format_round_trips_and_diffs_by_molecule() {
+    let before = vec![
+        Snapshot {
+            name: "formaldehyde".to_string(),
+            outcome: Outcome::Typed {
+                atoms: vec![
+                    ("C".to_string(), "C_2".to_string()),
+                    ("O".to_string(), "O_2".to_string()),
+                ],
+                bonds: BTreeMap::from([((0, 1), "Double".to_string())]),
+            },
+        },
+        Snapshot {
+            name: "broken".to_string(),
+            outcome: Outcome::Failed("unknown element".to_string()),
+        },
+    ];
+    assert_eq!(parse(&render(&before)), before);
+
+    let after = vec![Snapshot {
+        name: "formaldehyde".to_string(),
+        outcome: Outcome::Typed {
+            atoms: vec![
+                ("C".to_string(), "C_3".to_string()),
+                ("O".to_string(), "O_2".to_string()),
+                ("H".to_string(), "H_".to_string()),
+            ],
+            bonds: BTreeMap::from([
+                ((0, 1), "Single".to_string()),
+                ((0, 2), "Single".to_string()),
+            ]),
+        },
+    }];
+    assert_eq!(
+        diff(&before, &after),
+        [
+            "formaldehyde: atom 0 (C): C_2 -> C_3",
+            "formaldehyde: + atom 2 (H) H_",
+            "formaldehyde: bond 0-1: Double -> Single",
+            "formaldehyde: + bond 0-2 (Single)",
+            "- molecule broken",
+        ]
+    );
+}
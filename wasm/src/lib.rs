@@ -0,0 +1,8 @@
+//! WebAssembly package of `dreid-typer`.
+//!
+//! The bindings live in [`dreid_typer::wasm`] behind the `wasm` feature; this crate only links
+//! them into the `cdylib` that `wasm-pack` needs, so the library itself stays an `rlib` that
+//! `no_std` users can depend on. Build it from the repository root with
+//! `wasm-pack build wasm --target web --out-name dreid_typer`.
+
+pub use dreid_typer::wasm::*;